@@ -1,4 +1,4 @@
-use std::{env, fmt::Display, path::Path, sync::Arc};
+use std::{env, fmt::Display, path::Path, sync::{Arc, atomic::Ordering}};
 
 use anyhow::Result;
 use ratatui::layout::Rect;
@@ -6,7 +6,7 @@ use tracing::warn;
 use yazi_shared::env_exists;
 
 use super::{Iip, Kgp, KgpOld};
-use crate::{Chafa, Emulator, SHOWN, Sixel, TMUX, Ueberzug, WSL};
+use crate::{Chafa, Emulator, FOCUSED, Halfblock, SHOWN, SPOTTED, Sixel, TMUX, Ueberzug, WSL};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Adapter {
@@ -19,6 +19,9 @@ pub enum Adapter {
 	X11,
 	Wayland,
 	Chafa,
+
+	// Built-in, for when there's neither a graphics protocol nor chafa
+	Halfblock,
 }
 
 impl Display for Adapter {
@@ -31,13 +34,46 @@ impl Display for Adapter {
 			Self::X11 => write!(f, "x11"),
 			Self::Wayland => write!(f, "wayland"),
 			Self::Chafa => write!(f, "chafa"),
+			Self::Halfblock => write!(f, "halfblock"),
 		}
 	}
 }
 
 impl Adapter {
+	/// Shows the image in the preview pane, unless the spot overlay is showing
+	/// one, which the preview pane would've been covered by anyway.
 	pub async fn image_show(self, path: &Path, max: Rect) -> Result<Rect> {
-		if max.is_empty() {
+		if SPOTTED.load(Ordering::Relaxed) {
+			return Ok(Rect::default());
+		}
+		self.show(path, max).await
+	}
+
+	/// Shows the image in the spot overlay, replacing the one in the preview
+	/// pane until [`Self::spot_hide`] is called.
+	pub async fn spot_show(self, path: &Path, max: Rect) -> Result<Rect> {
+		SPOTTED.store(true, Ordering::Relaxed);
+		let result = self.show(path, max).await;
+		if result.is_err() {
+			self.spot_hide().ok();
+		}
+		result
+	}
+
+	pub fn spot_hide(self) -> Result<()> {
+		if SPOTTED.swap(false, Ordering::Relaxed) { self.image_hide() } else { Ok(()) }
+	}
+
+	/// The area of the image shown in the spot overlay, if any.
+	#[inline]
+	pub fn spotted(self) -> Option<Rect> {
+		if SPOTTED.load(Ordering::Relaxed) { self.shown_load() } else { None }
+	}
+
+	// Nothing's shown while unfocused, as the previewers are rerun once focused
+	// again, instead of drawing images unseen
+	async fn show(self, path: &Path, max: Rect) -> Result<Rect> {
+		if max.is_empty() || !FOCUSED.load(Ordering::Relaxed) {
 			return Ok(Rect::default());
 		}
 
@@ -48,6 +84,7 @@ impl Adapter {
 			Self::Sixel => Sixel::image_show(path, max).await,
 			Self::X11 | Self::Wayland => Ueberzug::image_show(path, max).await,
 			Self::Chafa => Chafa::image_show(path, max).await,
+			Self::Halfblock => Halfblock::image_show(path, max).await,
 		}
 	}
 
@@ -62,7 +99,8 @@ impl Adapter {
 			Self::Iip => Iip::image_erase(area),
 			Self::Sixel => Sixel::image_erase(area),
 			Self::X11 | Self::Wayland => Ueberzug::image_erase(area),
-			Self::Chafa => Chafa::image_erase(area),
+			// Both draw with characters, which are cleared the same way
+			Self::Chafa | Self::Halfblock => Chafa::image_erase(area),
 		}
 	}
 
@@ -76,7 +114,7 @@ impl Adapter {
 
 	#[inline]
 	pub(super) fn needs_ueberzug(self) -> bool {
-		!matches!(self, Self::Kgp | Self::KgpOld | Self::Iip | Self::Sixel)
+		!matches!(self, Self::Kgp | Self::KgpOld | Self::Iip | Self::Sixel | Self::Halfblock)
 	}
 }
 
@@ -105,17 +143,20 @@ impl Adapter {
 		match env::var("XDG_SESSION_TYPE").unwrap_or_default().as_str() {
 			"x11" => return Self::X11,
 			"wayland" if supported_compositor => return Self::Wayland,
-			"wayland" if !supported_compositor => return Self::Chafa,
+			"wayland" if !supported_compositor => return Self::symbols(),
 			_ => warn!("[Adapter] Could not identify XDG_SESSION_TYPE"),
 		}
 		if env_exists("WAYLAND_DISPLAY") {
-			return if supported_compositor { Self::Wayland } else { Self::Chafa };
+			return if supported_compositor { Self::Wayland } else { Self::symbols() };
 		}
 		if env_exists("DISPLAY") {
 			return Self::X11;
 		}
 
-		warn!("[Adapter] Falling back to chafa");
-		Self::Chafa
+		warn!("[Adapter] Falling back to drawing with symbols");
+		Self::symbols()
 	}
+
+	// Chafa if it's installed, as it draws far better than the built-in one
+	fn symbols() -> Self { if Chafa::installed() { Self::Chafa } else { Self::Halfblock } }
 }
@@ -64,6 +64,13 @@ impl Chafa {
 		})
 	}
 
+	/// Whether it's found in `PATH`.
+	pub(super) fn installed() -> bool {
+		let Some(paths) = std::env::var_os("PATH") else { return false };
+		std::env::split_paths(&paths)
+			.any(|p| p.join("chafa").is_file() || (cfg!(windows) && p.join("chafa.exe").is_file()))
+	}
+
 	pub(super) fn image_erase(area: Rect) -> Result<()> {
 		let s = " ".repeat(area.width as usize);
 		Emulator::move_lock((0, 0), |stderr| {
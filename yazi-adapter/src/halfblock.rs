@@ -0,0 +1,149 @@
+use std::{fmt::Write as _, io::Write, path::Path};
+
+use anyhow::Result;
+use crossterm::{cursor::MoveTo, queue};
+use image::RgbaImage;
+use ratatui::layout::Rect;
+
+use crate::{Adapter, Emulator, Image};
+
+/// Draws images with the `▀` half block, for the terminals without any
+/// graphics protocol, where each cell shows two pixels stacked, the upper one
+/// as the foreground and the lower one as the background.
+pub(super) struct Halfblock;
+
+impl Halfblock {
+	pub(super) async fn image_show(path: &Path, max: Rect) -> Result<Rect> {
+		let img = Image::downscale_to(path, (max.width as u32, max.height as u32 * 2)).await?;
+
+		let truecolor = Self::truecolor();
+		let (width, lines) = tokio::task::spawn_blocking(move || {
+			let img = img.into_rgba8();
+			(img.width() as u16, Self::render(&img, truecolor))
+		})
+		.await?;
+
+		let area = Rect { x: max.x, y: max.y, width, height: lines.len() as u16 };
+
+		Adapter::Halfblock.image_hide()?;
+		Adapter::shown_store(area);
+		Emulator::move_lock((max.x, max.y), |stderr| {
+			for (i, line) in lines.iter().enumerate() {
+				queue!(stderr, MoveTo(max.x, max.y + i as u16))?;
+				stderr.write_all(line.as_bytes())?;
+			}
+			Ok(area)
+		})
+	}
+
+	/// The lines of escape sequences drawing `img`, in 24-bit colors if
+	/// `truecolor`, or the nearest of the 256 ones otherwise.
+	///
+	/// Pixels that are mostly transparent are left to the background of the
+	/// terminal.
+	fn render(img: &RgbaImage, truecolor: bool) -> Vec<String> {
+		let pixel = |x: u32, y: u32| {
+			let p = img.get_pixel_checked(x, y)?.0;
+			Some([p[0], p[1], p[2]]).filter(|_| p[3] >= 128)
+		};
+		let color = |s: &mut String, ground: u8, [r, g, b]: [u8; 3]| {
+			if truecolor {
+				write!(s, "\x1b[{ground}8;2;{r};{g};{b}m")
+			} else {
+				write!(s, "\x1b[{ground}8;5;{}m", Self::ansi256([r, g, b]))
+			}
+		};
+
+		let mut lines = Vec::with_capacity(img.height().div_ceil(2) as usize);
+		for y in (0..img.height()).step_by(2) {
+			let mut s = String::new();
+			for x in 0..img.width() {
+				match (pixel(x, y), pixel(x, y + 1)) {
+					(Some(upper), Some(lower)) => {
+						color(&mut s, 3, upper).ok();
+						color(&mut s, 4, lower).ok();
+						s.push('▀');
+					}
+					(Some(upper), None) => {
+						s.push_str("\x1b[49m");
+						color(&mut s, 3, upper).ok();
+						s.push('▀');
+					}
+					(None, Some(lower)) => {
+						s.push_str("\x1b[49m");
+						color(&mut s, 3, lower).ok();
+						s.push('▄');
+					}
+					(None, None) => s.push_str("\x1b[0m "),
+				}
+			}
+			s.push_str("\x1b[0m");
+			lines.push(s);
+		}
+		lines
+	}
+
+	// The nearest of the 6x6x6 color cube, or of the grayscale ramp for those
+	// without a hue, as the first 16 colors vary from terminal to terminal
+	fn ansi256([r, g, b]: [u8; 3]) -> u8 {
+		if r.abs_diff(g) < 8 && g.abs_diff(b) < 8 {
+			return match r {
+				0..=3 => 16,
+				248..=255 => 231,
+				_ => 232 + (r.saturating_sub(8) / 10).min(23),
+			};
+		}
+
+		let q = |c: u8| match c {
+			0..=47 => 0,
+			48..=114 => 1,
+			c => (c - 35) / 40,
+		};
+		16 + 36 * q(r) + 6 * q(g) + q(b)
+	}
+
+	#[inline]
+	fn truecolor() -> bool {
+		matches!(std::env::var("COLORTERM").as_deref(), Ok("truecolor" | "24bit"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use image::Rgba;
+
+	use super::*;
+
+	#[test]
+	fn test_render() {
+		const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+		const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+		const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+		const CLEAR: Rgba<u8> = Rgba([0, 0, 0, 0]);
+
+		// 3 pixels high, so the last row of cells only has the upper halves
+		let img = RgbaImage::from_fn(3, 3, |x, y| match (x, y) {
+			(0, 0) | (1, 1) => RED,
+			(0, 1) | (1, 2) => BLUE,
+			(0, 2) => WHITE,
+			_ => CLEAR,
+		});
+
+		assert_eq!(Halfblock::render(&img, true), [
+			"\x1b[38;2;255;0;0m\x1b[48;2;0;0;255m▀\x1b[49m\x1b[38;2;255;0;0m▄\x1b[0m \x1b[0m",
+			"\x1b[49m\x1b[38;2;255;255;255m▀\x1b[49m\x1b[38;2;0;0;255m▀\x1b[0m \x1b[0m",
+		]);
+		assert_eq!(Halfblock::render(&img, false), [
+			"\x1b[38;5;196m\x1b[48;5;21m▀\x1b[49m\x1b[38;5;196m▄\x1b[0m \x1b[0m",
+			"\x1b[49m\x1b[38;5;231m▀\x1b[49m\x1b[38;5;21m▀\x1b[0m \x1b[0m",
+		]);
+	}
+
+	#[test]
+	fn test_ansi256() {
+		assert_eq!(Halfblock::ansi256([0, 0, 0]), 16);
+		assert_eq!(Halfblock::ansi256([255, 255, 255]), 231);
+		assert_eq!(Halfblock::ansi256([128, 128, 128]), 244);
+		assert_eq!(Halfblock::ansi256([255, 135, 0]), 208);
+	}
+}
@@ -1,4 +1,4 @@
-use std::{fs::File, io::BufReader, path::{Path, PathBuf}};
+use std::{fs::File, io::BufReader, path::{Path, PathBuf}, sync::atomic::Ordering};
 
 use anyhow::Result;
 use exif::{In, Tag};
@@ -6,7 +6,7 @@ use image::{DynamicImage, ExtendedColorType, ImageEncoder, ImageError, Limits, c
 use ratatui::layout::Rect;
 use yazi_config::{PREVIEW, TASKS};
 
-use crate::Dimension;
+use crate::{Dimension, SPOTTED};
 
 pub struct Image;
 
@@ -53,7 +53,21 @@ impl Image {
 		Ok(tokio::fs::write(cache, buf).await?)
 	}
 
+	/// The width and height of the image, without decoding it.
+	pub async fn size(path: &Path) -> Result<(u32, u32)> {
+		let path = path.to_owned();
+		let size = tokio::task::spawn_blocking(move || imagesize::size(path)).await??;
+		Ok((size.width as u32, size.height as u32))
+	}
+
+	#[inline]
 	pub(super) async fn downscale(path: &Path, rect: Rect) -> Result<DynamicImage> {
+		Self::downscale_to(path, Self::max_pixel(rect)).await
+	}
+
+	/// Decodes the image, turned the way its EXIF orientation says, and shrinks
+	/// it to fit in `max` pixels if it's larger.
+	pub(super) async fn downscale_to(path: &Path, max: (u32, u32)) -> Result<DynamicImage> {
 		let orientation = Self::orientation(path).await?;
 
 		let path = path.to_owned();
@@ -62,7 +76,7 @@ impl Image {
 		})
 		.await??;
 
-		let (mut w, mut h) = Self::max_pixel(rect);
+		let (mut w, mut h) = max;
 		if (5..=8).contains(&orientation) {
 			(w, h) = (h, w);
 		}
@@ -81,11 +95,17 @@ impl Image {
 		.await?
 	}
 
+	// The spot overlay isn't limited by the max size of the preview, as it's
+	// meant to show a larger rendition
 	pub(super) fn max_pixel(rect: Rect) -> (u32, u32) {
 		Dimension::ratio()
 			.map(|(r1, r2)| {
 				let (w, h) = ((rect.width as f64 * r1) as u32, (rect.height as f64 * r2) as u32);
-				(w.min(PREVIEW.max_width), h.min(PREVIEW.max_height))
+				if SPOTTED.load(Ordering::Relaxed) {
+					(w, h)
+				} else {
+					(w.min(PREVIEW.max_width), h.min(PREVIEW.max_height))
+				}
 			})
 			.unwrap_or((PREVIEW.max_width, PREVIEW.max_height))
 	}
@@ -116,7 +136,7 @@ impl Image {
 	async fn orientation(path: &Path) -> Result<u8> {
 		// We don't want to read the orientation of the cached image that has been
 		// rotated in the `Self::precache()` step.
-		if path.parent() == Some(&PREVIEW.cache_dir) {
+		if path.parent() == Some(PREVIEW.work_dir()) {
 			return Ok(0);
 		}
 
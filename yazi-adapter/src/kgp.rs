@@ -10,7 +10,7 @@ use ratatui::layout::Rect;
 use super::image::Image;
 use crate::{CLOSE, ESCAPE, Emulator, START, adapter::Adapter};
 
-static DIACRITICS: [char; 297] = [
+pub(super) static DIACRITICS: [char; 297] = [
 	'\u{0305}',
 	'\u{030D}',
 	'\u{030E}',
@@ -337,7 +337,7 @@ impl Kgp {
 				write!(stderr, "{s}")?;
 			}
 
-			write!(stderr, "{}_Gq=2,a=d,d=A{}\\{}", START, ESCAPE, CLOSE)?;
+			write!(stderr, "{}_Gq=2,a=d,d=I,i=1{}\\{}", START, ESCAPE, CLOSE)?;
 			Ok(())
 		})
 	}
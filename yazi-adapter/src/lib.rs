@@ -1,9 +1,11 @@
 #![allow(clippy::unit_arg)]
 
 yazi_macro::mod_flat!(
-	adapter chafa dimension emulator iip image kgp kgp_old mux sixel ueberzug
+	adapter chafa dimension emulator halfblock iip image kgp kgp_old mux sixel thumbs ueberzug
 );
 
+use std::sync::atomic::AtomicBool;
+
 use yazi_shared::{RoCell, env_exists, in_wsl};
 pub static ADAPTOR: RoCell<Adapter> = RoCell::new();
 
@@ -16,8 +18,14 @@ static CLOSE: RoCell<&'static str> = RoCell::new();
 // WSL support
 pub static WSL: RoCell<bool> = RoCell::new();
 
+// Focus reporting, the terminal is assumed to be focused if it doesn't support
+// it
+pub static FOCUSED: AtomicBool = AtomicBool::new(true);
+
 // Image state
 static SHOWN: RoCell<arc_swap::ArcSwapOption<ratatui::layout::Rect>> = RoCell::new();
+// Whether the shown image is the one in the spot overlay
+static SPOTTED: AtomicBool = AtomicBool::new(false);
 
 pub fn init() {
 	// Tmux support
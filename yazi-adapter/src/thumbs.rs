@@ -0,0 +1,162 @@
+use std::{collections::BTreeMap, io::Write, path::{Path, PathBuf}};
+
+use anyhow::Result;
+use base64::{Engine, engine::general_purpose};
+use parking_lot::Mutex;
+use ratatui::layout::Rect;
+use yazi_macro::render;
+
+use crate::{ADAPTOR, Adapter, CLOSE, ESCAPE, Emulator, Image, START, kgp::DIACRITICS};
+
+static STATE: Mutex<State> = Mutex::new(State { ids: BTreeMap::new(), next: 0, tick: 0 });
+
+struct State {
+	// Image ID and when it's last used of each transmitted thumbnail, the ID is
+	// `PENDING` while it's being transmitted, or `FAILED` if it couldn't be
+	ids:  BTreeMap<PathBuf, (u32, u64)>,
+	next: u32,
+	tick: u64,
+}
+
+/// The tiny thumbnails shown next to the file names, with the Unicode
+/// placeholders of the Kitty graphics protocol.
+///
+/// The placeholders are just text to the renderer, so they're placed, moved
+/// and erased along with the rows they're on, without having to keep them in
+/// sync with the scrolling by hand.
+pub struct Thumbs;
+
+impl Thumbs {
+	const CAPACITY: usize = 500;
+	const FAILED: u32 = u32::MAX;
+	// The preview image uses 1
+	const FIRST_ID: u32 = 2;
+	const LAST_ID: u32 = 0xff_ffff;
+	const PENDING: u32 = 0;
+	/// How many cells wide a thumbnail is.
+	pub const WIDTH: u16 = 2;
+
+	/// The placeholder text of the thumbnail of the image at `path`, and the
+	/// foreground color carrying its image ID, which are `None` if it's not
+	/// supported, or not transmitted yet, in which case it's going to be, and
+	/// a render is requested once done.
+	///
+	/// Nothing touches the disk here, as it's called for every row rendered; an
+	/// image that doesn't exist yet, e.g. not precached, is asked for again the
+	/// next time.
+	pub fn get(path: &Path) -> Option<(String, (u8, u8, u8))> {
+		if *ADAPTOR != Adapter::Kgp {
+			return None;
+		}
+
+		let mut state = STATE.lock();
+		state.tick += 1;
+
+		let tick = state.tick;
+		if let Some((id, used)) = state.ids.get_mut(path) {
+			*used = tick;
+			return (*id != Self::PENDING && *id != Self::FAILED).then(|| Self::placeholder(*id));
+		}
+
+		let evicted = if state.ids.len() >= Self::CAPACITY { state.evict() } else { None };
+		state.ids.insert(path.to_owned(), (Self::PENDING, tick));
+		drop(state);
+
+		let path = path.to_owned();
+		tokio::spawn(async move {
+			if let Some(id) = evicted {
+				Self::delete(id).ok();
+			}
+			if Self::transmit(&path).await.is_err() {
+				STATE.lock().ids.entry(path).and_modify(|(id, _)| *id = Self::FAILED);
+			}
+		});
+		None
+	}
+
+	async fn transmit(path: &Path) -> Result<()> {
+		if !tokio::fs::metadata(path).await.is_ok_and(|m| m.is_file()) {
+			STATE.lock().ids.remove(path);
+			return Ok(());
+		}
+
+		let img =
+			Image::downscale(path, Rect { width: Self::WIDTH, height: 1, ..Default::default() }).await?;
+		let size = (img.width(), img.height());
+		let raw = tokio::task::spawn_blocking(move || img.into_rgba8().into_raw()).await?;
+
+		let id = {
+			let mut state = STATE.lock();
+			if !state.ids.contains_key(path) {
+				return Ok(());
+			}
+			let id = state.next_id();
+			state.ids.get_mut(path).unwrap().0 = id;
+			id
+		};
+
+		let b64 = general_purpose::STANDARD.encode(raw).into_bytes();
+		let mut it = b64.chunks(4096).peekable();
+		let mut buf = Vec::with_capacity(b64.len() + it.len() * 50);
+		if let Some(first) = it.next() {
+			write!(
+				buf,
+				"{START}_Gq=2,a=T,U=1,i={id},c={},r=1,f=32,s={},v={},m={};{}{ESCAPE}\\{CLOSE}",
+				Self::WIDTH,
+				size.0,
+				size.1,
+				it.peek().is_some() as u8,
+				std::str::from_utf8(first)?,
+			)?;
+		}
+		while let Some(chunk) = it.next() {
+			write!(
+				buf,
+				"{START}_Gm={};{}{ESCAPE}\\{CLOSE}",
+				it.peek().is_some() as u8,
+				std::str::from_utf8(chunk)?,
+			)?;
+		}
+
+		Emulator::move_lock((0, 0), |stderr| Ok(stderr.write_all(&buf)?))?;
+		render!();
+		Ok(())
+	}
+
+	fn delete(id: u32) -> Result<()> {
+		Emulator::move_lock((0, 0), |stderr| {
+			Ok(write!(stderr, "{START}_Gq=2,a=d,d=I,i={id}{ESCAPE}\\{CLOSE}")?)
+		})
+	}
+
+	fn placeholder(id: u32) -> (String, (u8, u8, u8)) {
+		let mut s = String::with_capacity(Self::WIDTH as usize * 8);
+		for &col in DIACRITICS.iter().take(Self::WIDTH as usize) {
+			s.push('\u{10EEEE}');
+			s.push(DIACRITICS[0]);
+			s.push(col);
+		}
+		(s, ((id >> 16) as u8, (id >> 8) as u8, id as u8))
+	}
+}
+
+impl State {
+	fn next_id(&mut self) -> u32 {
+		self.next = if self.next < Thumbs::FIRST_ID || self.next >= Thumbs::LAST_ID {
+			Thumbs::FIRST_ID
+		} else {
+			self.next + 1
+		};
+		self.next
+	}
+
+	// Removes the least recently used one, returning its image ID to delete
+	fn evict(&mut self) -> Option<u32> {
+		let oldest = self.ids.iter().min_by_key(|(_, &(_, used))| used).map(|(p, _)| p.clone())?;
+		self
+			.ids
+			.remove(&oldest)
+			.map(|(id, _)| id)
+			.filter(|&id| id != Thumbs::PENDING && id != Thumbs::FAILED)
+	}
+}
@@ -1,20 +1,57 @@
 use std::path::PathBuf;
 
-use clap::{Parser, command};
+use clap::{Parser, ValueEnum, command};
 
 #[derive(Debug, Default, Parser)]
 #[command(name = "yazi")]
 pub struct Args {
-	/// Set the current working entry
-	#[arg(index = 1, num_args = 1..=9)]
+	/// Set the current working entry, each opening in its own tab, where a file
+	/// is revealed in its directory
+	#[arg(index = 1, num_args = 1..)]
 	pub entries: Vec<PathBuf>,
+	/// Open a tab for each entry listed in this file as well, one per line,
+	/// with empty lines and those starting with `#` ignored
+	#[arg(long, value_name = "FILE")]
+	pub tabs:    Option<PathBuf>,
 
 	/// Write the cwd on exit to this file
 	#[arg(long)]
-	pub cwd_file:     Option<PathBuf>,
+	pub cwd_file:       Option<PathBuf>,
+	/// Write the hovered file of the active tab on exit to this file, empty if
+	/// there's none
+	#[arg(long)]
+	pub hovered_file:   Option<PathBuf>,
 	/// Write the selected files to this file on open fired
 	#[arg(long)]
-	pub chooser_file: Option<PathBuf>,
+	pub chooser_file:   Option<PathBuf>,
+	/// How the chosen files are written to `--chooser-file`
+	#[arg(long, value_enum, default_value_t)]
+	pub chooser_format: ChooserFormat,
+	/// Choose a directory instead, writing the cwd to `--chooser-file` on quit
+	#[arg(long, requires = "chooser_file")]
+	pub chooser_dir:    bool,
+
+	/// Apply the `[profile.<name>]` sections of the config files, defaults to
+	/// `$YAZI_PROFILE`
+	#[arg(long)]
+	pub profile: Option<String>,
+
+	/// Override a key of yazi.toml for this run, e.g. `manager.show_hidden=true`
+	#[arg(long, value_name = "KEY=VALUE")]
+	pub config_set: Vec<String>,
+
+	/// Restore the tabs saved on the last quit, unless entries are given
+	#[arg(long)]
+	pub restore_session: bool,
+	/// Restore the tabs autosaved on the last change of the CWD, in case yazi
+	/// was killed
+	#[arg(long, conflicts_with = "restore_session")]
+	pub recover:         bool,
+
+	/// Disable the commands that change files, such as `remove`, `rename`,
+	/// `paste` and `shell`, which can be toggled with `read_only` at runtime
+	#[arg(long)]
+	pub read_only: bool,
 
 	/// Clear the cache directory
 	#[arg(long)]
@@ -38,3 +75,14 @@ pub struct Args {
 	#[arg(short = 'V', long)]
 	pub version: bool,
 }
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ChooserFormat {
+	/// Each path is terminated by a NUL.
+	Null,
+	/// Each path is terminated by a newline.
+	#[default]
+	Lines,
+	/// A JSON array of the paths.
+	Json,
+}
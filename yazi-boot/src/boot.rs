@@ -1,12 +1,15 @@
-use std::{collections::HashSet, ffi::OsString, path::PathBuf};
+use std::{collections::HashSet, ffi::OsString, fs, path::{Path, PathBuf}};
 
 use serde::Serialize;
 use yazi_shared::{Xdg, fs::{current_cwd, expand_path}};
 
 #[derive(Debug, Default, Serialize)]
 pub struct Boot {
-	pub cwds:  Vec<PathBuf>,
-	pub files: Vec<OsString>,
+	pub cwds:    Vec<PathBuf>,
+	pub files:   Vec<OsString>,
+	/// The entries that aren't opened, as they don't exist or are over the tab
+	/// limit, with the reason for each.
+	pub skipped: Vec<(PathBuf, &'static str)>,
 
 	pub local_events:  HashSet<String>,
 	pub remote_events: HashSet<String>,
@@ -17,16 +20,22 @@ pub struct Boot {
 	pub state_dir:  PathBuf,
 }
 
-impl Boot {
-	fn parse_entries(entries: &[PathBuf]) -> (Vec<PathBuf>, Vec<OsString>) {
-		if entries.is_empty() {
-			return (vec![current_cwd().unwrap()], vec![OsString::new()]);
-		}
+/// The most tabs that can be open at the same time.
+pub const MAX_TABS: usize = 9;
 
-		let mut cwds = Vec::with_capacity(entries.len());
-		let mut files = Vec::with_capacity(entries.len());
+impl Boot {
+	fn parse_entries(
+		entries: &[PathBuf],
+	) -> (Vec<PathBuf>, Vec<OsString>, Vec<(PathBuf, &'static str)>) {
+		let mut cwds = Vec::with_capacity(entries.len().min(MAX_TABS));
+		let mut files = Vec::with_capacity(entries.len().min(MAX_TABS));
+		let mut skipped = vec![];
 		for entry in entries.iter().map(expand_path) {
-			if let Some(p) = entry.parent().filter(|_| !entry.is_dir()) {
+			if !entry.exists() {
+				skipped.push((entry, "not found"));
+			} else if cwds.len() >= MAX_TABS {
+				skipped.push((entry, "over the tab limit"));
+			} else if let Some(p) = entry.parent().filter(|_| !entry.is_dir()) {
 				cwds.push(p.to_owned());
 				files.push(entry.file_name().unwrap().to_owned());
 			} else {
@@ -35,14 +44,37 @@ impl Boot {
 			}
 		}
 
-		(cwds, files)
+		// Falls back to the CWD, if none of them can be opened
+		if cwds.is_empty() {
+			cwds.push(current_cwd().unwrap());
+			files.push(OsString::new());
+		}
+		(cwds, files, skipped)
+	}
+
+	/// The entries listed in the `--tabs` file, or the file itself as a missing
+	/// entry if it can't be read.
+	fn read_tabs(path: &Path) -> Vec<PathBuf> {
+		let Ok(s) = fs::read_to_string(path) else {
+			return vec![path.to_owned()];
+		};
+
+		s.lines()
+			.map(str::trim)
+			.filter(|l| !l.is_empty() && !l.starts_with('#'))
+			.map(PathBuf::from)
+			.collect()
 	}
 }
 
 impl From<&crate::Args> for Boot {
 	fn from(args: &crate::Args) -> Self {
 		let config_dir = Xdg::config_dir();
-		let (cwds, files) = Self::parse_entries(&args.entries);
+		let mut entries = args.entries.clone();
+		if let Some(p) = &args.tabs {
+			entries.extend(Self::read_tabs(p));
+		}
+		let (cwds, files, skipped) = Self::parse_entries(&entries);
 
 		let local_events = args
 			.local_events
@@ -58,6 +90,7 @@ impl From<&crate::Args> for Boot {
 		Self {
 			cwds,
 			files,
+			skipped,
 
 			local_events,
 			remote_events,
@@ -2,15 +2,23 @@ yazi_macro::mod_pub!(actions);
 
 yazi_macro::mod_flat!(args boot);
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use clap::Parser;
 use yazi_shared::RoCell;
 
 pub static ARGS: RoCell<Args> = RoCell::new();
 pub static BOOT: RoCell<Boot> = RoCell::new();
 
+/// Whether the commands that change files are disabled, which starts off with
+/// `--read-only`.
+pub static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+pub fn init_args() { ARGS.with(<_>::parse); }
+
 pub fn init() {
-	ARGS.with(<_>::parse);
 	BOOT.init(From::from(&*ARGS));
+	READ_ONLY.store(ARGS.read_only, Ordering::Relaxed);
 
 	actions::Actions::act(&ARGS);
 }
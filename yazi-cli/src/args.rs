@@ -1,7 +1,4 @@
-use std::borrow::Cow;
-
-use anyhow::{Result, bail};
-use clap::{Parser, Subcommand, command};
+use clap::{Parser, Subcommand, ValueEnum, command};
 
 #[derive(Parser)]
 #[command(name = "Ya", about, long_about = None)]
@@ -22,6 +19,8 @@ pub(super) enum Command {
 	PubTo(CommandPubTo),
 	/// Subscribe to messages from all remote instances.
 	Sub(CommandSub),
+	/// Query the state of a running instance.
+	Query(CommandQuery),
 	/// Manage packages.
 	Pack(CommandPack),
 }
@@ -40,17 +39,9 @@ pub(super) struct CommandPub {
 	/// Send the message as string of list.
 	#[arg(long, num_args = 0..)]
 	pub(super) list: Vec<String>,
-}
 
-impl CommandPub {
-	#[allow(dead_code)]
-	pub(super) fn receiver(&self) -> Result<u64> {
-		if let Some(s) = std::env::var("YAZI_PID").ok().filter(|s| !s.is_empty()) {
-			Ok(s.parse()?)
-		} else {
-			bail!("No `YAZI_ID` environment variable found.")
-		}
-	}
+	#[command(flatten)]
+	pub(super) remote: RemoteArgs,
 }
 
 #[derive(clap::Args)]
@@ -70,13 +61,76 @@ pub(super) struct CommandPubTo {
 	/// Send the message as string of list.
 	#[arg(long, num_args = 0..)]
 	pub(super) list:     Vec<String>,
+
+	#[command(flatten)]
+	pub(super) remote: RemoteArgs,
 }
 
 #[derive(clap::Args)]
 pub(super) struct CommandSub {
 	/// The kind of messages to subscribe to, separated by commas if multiple.
-	#[arg(index = 1)]
-	pub(super) kinds: String,
+	#[arg(index = 1, value_delimiter = ',', required_unless_present = "filter")]
+	pub(super) kinds:  Vec<String>,
+	/// Output format of the messages.
+	#[arg(long, value_enum, default_value_t = SubFormat::Raw)]
+	pub(super) format: SubFormat,
+	/// Only output messages of the specified kinds, separated by commas if
+	/// multiple.
+	#[arg(long, value_delimiter = ',')]
+	pub(super) filter: Vec<String>,
+	/// Only output messages that belong to the specified tab.
+	#[arg(long)]
+	pub(super) tab:    Option<usize>,
+
+	#[command(flatten)]
+	pub(super) remote: RemoteArgs,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(super) enum SubFormat {
+	/// The internal line format: `kind,receiver,sender,body`.
+	Raw,
+	/// One JSON object per line, with `kind`, `receiver`, `sender`, and `body`
+	/// fields.
+	Json,
+}
+
+#[derive(clap::Args)]
+pub(super) struct CommandQuery {
+	/// The instance ID, which can be omitted if only one instance is running,
+	/// followed by the kind of state: `cwd`, `hovered`, `selection`, `tabs`,
+	/// `tasks`, or `read_only`.
+	#[arg(index = 1, num_args = 1..=2, required = true, value_name = "[ID] KIND")]
+	pub(super) args:   Vec<String>,
+	/// Query the specified tab instead of the active one.
+	#[arg(long)]
+	pub(super) tab:    Option<usize>,
+	/// Output format of the answer.
+	#[arg(long, value_enum, default_value_t = QueryFormat::Plain)]
+	pub(super) format: QueryFormat,
+
+	#[command(flatten)]
+	pub(super) remote: RemoteArgs,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(super) enum QueryFormat {
+	/// One value per line, tabs are printed as `idx\tcwd`, with `*` marking the
+	/// active one, and tasks as `id\tsucc/total\tname`.
+	Plain,
+	/// The answer as a JSON value.
+	Json,
+}
+
+#[derive(clap::Args)]
+pub(super) struct RemoteArgs {
+	/// Join the bus of an instance listening on `host:port` over TCP, instead of
+	/// the local one.
+	#[arg(long)]
+	pub(super) remote: Option<String>,
+	/// The token shared with the remote instance, defaults to `YAZI_DDS_TOKEN`.
+	#[arg(long, requires = "remote")]
+	pub(super) token:  Option<String>,
 }
 
 #[derive(clap::Args)]
@@ -95,26 +149,3 @@ pub(super) struct CommandPack {
 	#[arg(short = 'u', long)]
 	pub(super) upgrade: bool,
 }
-
-// --- Macros
-macro_rules! impl_body {
-	($name:ident) => {
-		impl $name {
-			#[allow(dead_code)]
-			pub(super) fn body(&self) -> Result<Cow<str>> {
-				if let Some(json) = &self.json {
-					Ok(json.into())
-				} else if let Some(str) = &self.str {
-					Ok(serde_json::to_string(str)?.into())
-				} else if !self.list.is_empty() {
-					Ok(serde_json::to_string(&self.list)?.into())
-				} else {
-					Ok("".into())
-				}
-			}
-		}
-	};
-}
-
-impl_body!(CommandPub);
-impl_body!(CommandPubTo);
@@ -1,6 +1,6 @@
 yazi_macro::mod_pub!(package);
 
-yazi_macro::mod_flat!(args);
+yazi_macro::mod_flat!(args message);
 
 use clap::Parser;
 
@@ -21,7 +21,7 @@ async fn main() -> anyhow::Result<()> {
 	match Args::parse().command {
 		Command::Pub(cmd) => {
 			yazi_boot::init_default();
-			yazi_dds::init();
+			yazi_dds::init_with((&cmd.remote).into());
 			if let Err(e) = yazi_dds::Client::shot(&cmd.kind, cmd.receiver()?, &cmd.body()?).await {
 				eprintln!("Cannot send message: {e}");
 				std::process::exit(1);
@@ -30,7 +30,7 @@ async fn main() -> anyhow::Result<()> {
 
 		Command::PubTo(cmd) => {
 			yazi_boot::init_default();
-			yazi_dds::init();
+			yazi_dds::init_with((&cmd.remote).into());
 			if let Err(e) = yazi_dds::Client::shot(&cmd.kind, cmd.receiver, &cmd.body()?).await {
 				eprintln!("Cannot send message: {e}");
 				std::process::exit(1);
@@ -39,12 +39,33 @@ async fn main() -> anyhow::Result<()> {
 
 		Command::Sub(cmd) => {
 			yazi_boot::init_default();
-			yazi_dds::init();
-			yazi_dds::Client::draw(cmd.kinds.split(',').collect()).await?;
+			yazi_dds::init_with((&cmd.remote).into());
+			yazi_dds::Client::draw(cmd.kinds(), |line| {
+				if let Some(s) = cmd.render(line) {
+					println!("{s}");
+				}
+			})
+			.await?;
 
 			tokio::signal::ctrl_c().await?;
 		}
 
+		Command::Query(cmd) => {
+			yazi_boot::init_default();
+			yazi_dds::init_with((&cmd.remote).into());
+			match yazi_dds::Client::query(cmd.receiver()?, &cmd.body()?)
+				.await
+				.and_then(|s| cmd.render(&s))
+			{
+				Ok(s) if s.is_empty() => {}
+				Ok(s) => println!("{s}"),
+				Err(e) => {
+					eprintln!("Cannot query the instance: {e}");
+					std::process::exit(1);
+				}
+			}
+		}
+
 		Command::Pack(cmd) => {
 			package::init()?;
 			if cmd.install {
@@ -64,3 +85,9 @@ async fn main() -> anyhow::Result<()> {
 
 	Ok(())
 }
+
+impl From<&RemoteArgs> for yazi_dds::Transport {
+	fn from(value: &RemoteArgs) -> Self {
+		Self { remote: value.remote.clone(), token: value.token.clone() }
+	}
+}
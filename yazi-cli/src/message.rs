@@ -0,0 +1,133 @@
+//! The messages the commands send and the answers they print, apart from
+//! `args.rs`, which the build script includes to generate the completions.
+
+use std::{borrow::Cow, collections::HashSet};
+
+use anyhow::{Result, bail};
+use serde_json::{Value, json};
+
+use crate::{CommandPub, CommandPubTo, CommandQuery, CommandSub, QueryFormat, SubFormat};
+
+impl CommandPub {
+	pub(super) fn receiver(&self) -> Result<u64> {
+		if let Some(s) = std::env::var("YAZI_PID").ok().filter(|s| !s.is_empty()) {
+			Ok(s.parse()?)
+		} else {
+			bail!("No `YAZI_ID` environment variable found.")
+		}
+	}
+}
+
+impl CommandSub {
+	pub(super) fn kinds(&self) -> HashSet<&str> {
+		self.kinds.iter().chain(&self.filter).map(|s| s.as_str()).filter(|s| !s.is_empty()).collect()
+	}
+
+	pub(super) fn render(&self, line: &str) -> Option<String> {
+		let mut parts = line.splitn(4, ',');
+		let kind = parts.next()?;
+		let receiver = parts.next()?;
+		let sender = parts.next()?;
+		let raw = parts.next().unwrap_or_default();
+
+		if !self.filter.is_empty() && !self.filter.iter().any(|s| s == kind) {
+			return None;
+		}
+
+		let body: Option<Value> = serde_json::from_str(raw).ok();
+		if let Some(tab) = self.tab {
+			let key = if kind == "tab" { "idx" } else { "tab" };
+			if body.as_ref().and_then(|b| b.get(key)).and_then(|v| v.as_u64()) != Some(tab as u64) {
+				return None;
+			}
+		}
+
+		if self.format == SubFormat::Raw {
+			return Some(line.to_owned());
+		}
+
+		let (receiver, sender) = (receiver.parse().unwrap_or(0u64), sender.parse().unwrap_or(0u64));
+		Some(
+			match body {
+				Some(body) => json!({ "kind": kind, "receiver": receiver, "sender": sender, "body": body }),
+				None => json!({ "kind": kind, "receiver": receiver, "sender": sender, "raw": raw }),
+			}
+			.to_string(),
+		)
+	}
+}
+
+impl CommandQuery {
+	pub(super) fn receiver(&self) -> Result<Option<u64>> {
+		if self.args.len() < 2 {
+			return Ok(None);
+		}
+		Ok(Some(self.args[0].parse()?))
+	}
+
+	pub(super) fn body(&self) -> Result<String> {
+		Ok(serde_json::to_string(&json!({ "kind": self.kind(), "tab": self.tab }))?)
+	}
+
+	pub(super) fn render(&self, raw: &str) -> Result<String> {
+		let value: Value = serde_json::from_str(raw)?;
+		if let Some(e) = value.get("error").and_then(|v| v.as_str()) {
+			bail!("{e}");
+		} else if self.format == QueryFormat::Json {
+			return Ok(value.to_string());
+		}
+
+		let s = |v: Option<&Value>| v.and_then(|v| v.as_str()).unwrap_or_default().to_owned();
+		let n = |v: Option<&Value>| v.and_then(|v| v.as_u64()).unwrap_or_default();
+		let line = |v: &Value| match self.kind() {
+			"tabs" => format!(
+				"{}{}\t{}",
+				if v.get("active").and_then(|v| v.as_bool()) == Some(true) { "*" } else { "" },
+				n(v.get("idx")),
+				s(v.get("cwd"))
+			),
+			"tasks" => format!(
+				"{}\t{}/{}\t{}",
+				n(v.get("id")),
+				n(v.get("succ")),
+				n(v.get("total")),
+				s(v.get("name"))
+			),
+			_ => match v {
+				Value::String(s) => s.clone(),
+				Value::Null => String::new(),
+				v => v.to_string(),
+			},
+		};
+
+		Ok(match value {
+			Value::Array(a) => a.iter().map(line).collect::<Vec<_>>().join("\n"),
+			v => line(&v),
+		})
+	}
+
+	#[inline]
+	fn kind(&self) -> &str { self.args.last().map(|s| s.as_str()).unwrap_or_default() }
+}
+
+// --- Macros
+macro_rules! impl_body {
+	($name:ident) => {
+		impl $name {
+			pub(super) fn body(&self) -> Result<Cow<str>> {
+				if let Some(json) = &self.json {
+					Ok(json.into())
+				} else if let Some(str) = &self.str {
+					Ok(serde_json::to_string(str)?.into())
+				} else if !self.list.is_empty() {
+					Ok(serde_json::to_string(&self.list)?.into())
+				} else {
+					Ok("".into())
+				}
+			}
+		}
+	};
+}
+
+impl_body!(CommandPub);
+impl_body!(CommandPubTo);
@@ -0,0 +1,71 @@
+//! Matches the icons of the files in a large directory, as the first render of
+//! it does, and looks them up again from the cache, as every render after does.
+//!
+//! Run with `cargo bench -p yazi-config --bench icons`.
+
+use std::{hint::black_box, time::{Duration, Instant}};
+
+use yazi_config::theme::Icons;
+use yazi_shared::fs::{Cha, ChaKind, File, Url};
+
+const FILES: usize = 50_000;
+
+fn icons() -> &'static Icons {
+	let mut t: toml::Table = include_str!("../preset/theme.toml").parse().unwrap();
+	let mut icon = t.remove("icon").unwrap();
+
+	// Those globs that can't be matched by the name or the extension alone
+	icon.as_table_mut().unwrap().insert(
+		"globs".to_owned(),
+		toml::Value::Array(
+			[
+				r#"{ name = "*.test.js", text = "T" }"#,
+				r#"{ name = "**/node_modules/", text = "N", prio = 1 }"#,
+				r#"{ name = "**/src/**/*.rs", is = "exec", text = "R" }"#,
+			]
+			.into_iter()
+			.map(|s| format!("v = {s}").parse::<toml::Table>().unwrap().remove("v").unwrap())
+			.collect(),
+		),
+	);
+
+	Box::leak(Box::new(icon.try_into().unwrap()))
+}
+
+fn files() -> Vec<File> {
+	const NAMES: [&str; 8] =
+		["main.rs", "index.test.js", "README.md", "node_modules", ".git", "photo.JPG", "Makefile", "x"];
+
+	(0..FILES)
+		.map(|i| {
+			let name = NAMES[i % NAMES.len()];
+			let kind =
+				if matches!(name, "node_modules" | ".git") { ChaKind::DIR } else { ChaKind::empty() };
+			File {
+				url: Url::from(format!("/home/user/projects/p{}/src/{i}-{name}", i % 97)),
+				cha: Cha { kind, ..Default::default() },
+				..Default::default()
+			}
+		})
+		.collect()
+}
+
+fn measure(name: &str, f: impl Fn() -> usize) {
+	let (mut runs, mut total, mut found) = (0, Duration::ZERO, 0);
+	while total < Duration::from_secs(2) {
+		let start = Instant::now();
+		found = black_box(f());
+		total += start.elapsed();
+		runs += 1;
+	}
+
+	let per = total / (runs * FILES) as u32;
+	println!("{name:>8}: {per:>8.2?} per file, {found} of {FILES} with an icon, {runs} runs");
+}
+
+fn main() {
+	let (icons, files) = (icons(), files());
+
+	measure("matched", || files.iter().filter(|&f| icons.matches(f).is_some()).count());
+	measure("cached", || files.iter().filter(|&f| icons.cached(f).is_some()).count());
+}
@@ -4,18 +4,20 @@ use indexmap::IndexSet;
 use serde::{Deserialize, Deserializer};
 use yazi_shared::Layer;
 
-use super::Chord;
+use super::{Chord, MouseChord};
 use crate::Preset;
 
 #[derive(Debug)]
 pub struct Keymap {
 	pub manager:    Vec<Chord>,
 	pub tasks:      Vec<Chord>,
+	pub spot:       Vec<Chord>,
 	pub pick:       Vec<Chord>,
 	pub input:      Vec<Chord>,
 	pub confirm:    Vec<Chord>,
 	pub help:       Vec<Chord>,
 	pub completion: Vec<Chord>,
+	pub mouse:      Vec<MouseChord>,
 }
 
 impl Keymap {
@@ -25,6 +27,7 @@ impl Keymap {
 			Layer::App => unreachable!(),
 			Layer::Manager => &self.manager,
 			Layer::Tasks => &self.tasks,
+			Layer::Spot => &self.spot,
 			Layer::Pick => &self.pick,
 			Layer::Input => &self.input,
 			Layer::Confirm => &self.confirm,
@@ -50,11 +53,13 @@ impl<'de> Deserialize<'de> for Keymap {
 		struct Shadow {
 			manager:    Inner,
 			tasks:      Inner,
+			spot:       Inner,
 			pick:       Inner,
 			input:      Inner,
 			confirm:    Inner,
 			help:       Inner,
 			completion: Inner,
+			mouse:      MouseInner,
 		}
 		#[derive(Deserialize)]
 		struct Inner {
@@ -64,6 +69,14 @@ impl<'de> Deserialize<'de> for Keymap {
 			#[serde(default)]
 			append_keymap:  IndexSet<Chord>,
 		}
+		#[derive(Deserialize)]
+		struct MouseInner {
+			keymap:         IndexSet<MouseChord>,
+			#[serde(default)]
+			prepend_keymap: IndexSet<MouseChord>,
+			#[serde(default)]
+			append_keymap:  IndexSet<MouseChord>,
+		}
 
 		fn mix(mut a: IndexSet<Chord>, b: IndexSet<Chord>, c: IndexSet<Chord>) -> Vec<Chord> {
 			let mut seen = HashSet::new();
@@ -81,6 +94,8 @@ impl<'de> Deserialize<'de> for Keymap {
 			#[rustfmt::skip]
 			tasks:      mix(shadow.tasks.keymap, shadow.tasks.prepend_keymap, shadow.tasks.append_keymap),
 			#[rustfmt::skip]
+			spot:       mix(shadow.spot.keymap, shadow.spot.prepend_keymap, shadow.spot.append_keymap),
+			#[rustfmt::skip]
 			pick:     mix(shadow.pick.keymap, shadow.pick.prepend_keymap, shadow.pick.append_keymap),
 			#[rustfmt::skip]
 			input:      mix(shadow.input.keymap, shadow.input.prepend_keymap, shadow.input.append_keymap),
@@ -90,6 +105,8 @@ impl<'de> Deserialize<'de> for Keymap {
 			help:       mix(shadow.help.keymap, shadow.help.prepend_keymap, shadow.help.append_keymap),
 			#[rustfmt::skip]
 			completion: mix(shadow.completion.keymap, shadow.completion.prepend_keymap, shadow.completion.append_keymap),
+			#[rustfmt::skip]
+			mouse:      Preset::mix(shadow.mouse.keymap, shadow.mouse.prepend_keymap, shadow.mouse.append_keymap).collect(),
 		})
 	}
 }
@@ -1 +1 @@
-yazi_macro::mod_flat!(chord cow deserializers key keymap);
+yazi_macro::mod_flat!(chord cow deserializers key keymap mouse);
@@ -0,0 +1,59 @@
+use std::{collections::VecDeque, hash::{Hash, Hasher}};
+
+use crossterm::event::{MouseButton, MouseEventKind};
+use serde::Deserialize;
+use yazi_shared::event::Cmd;
+
+#[derive(Debug, Deserialize)]
+pub struct MouseChord {
+	pub on:   MouseOn,
+	#[serde(rename = "in")]
+	pub in_:  String,
+	#[serde(deserialize_with = "super::deserialize_run")]
+	pub run:  Vec<Cmd>,
+	pub desc: Option<String>,
+}
+
+impl PartialEq for MouseChord {
+	fn eq(&self, other: &Self) -> bool { self.on == other.on && self.in_ == other.in_ }
+}
+
+impl Eq for MouseChord {}
+
+impl Hash for MouseChord {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.on.hash(state);
+		self.in_.hash(state);
+	}
+}
+
+impl MouseChord {
+	#[inline]
+	pub fn matches(&self, on: MouseOn, id: &str) -> bool {
+		self.on == on && (self.in_ == "*" || self.in_ == id)
+	}
+
+	#[inline]
+	pub fn to_seq(&self) -> VecDeque<Cmd> { self.run.iter().map(|c| c.shallow_clone()).collect() }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum MouseOn {
+	Left,
+	Double,
+	Right,
+	Middle,
+}
+
+impl MouseOn {
+	pub fn from_kind(kind: MouseEventKind, double: bool) -> Option<Self> {
+		Some(match kind {
+			MouseEventKind::Down(MouseButton::Left) if double => Self::Double,
+			MouseEventKind::Down(MouseButton::Left) => Self::Left,
+			MouseEventKind::Down(MouseButton::Right) => Self::Right,
+			MouseEventKind::Down(MouseButton::Middle) => Self::Middle,
+			_ => return None,
+		})
+	}
+}
@@ -23,11 +23,14 @@ pub static CONFIRM: RoCell<popup::Confirm> = RoCell::new();
 pub static PICK: RoCell<popup::Pick> = RoCell::new();
 pub static WHICH: RoCell<which::Which> = RoCell::new();
 
-pub fn init() -> anyhow::Result<()> {
+pub fn init(sets: &[String], profile: Option<&str>) -> anyhow::Result<()> {
+	let profile = profile.map(ToOwned::to_owned).or_else(|| std::env::var("YAZI_PROFILE").ok());
+	let profile = profile.as_deref();
+
 	let config_dir = Xdg::config_dir();
-	let yazi_toml = &Preset::yazi(&config_dir)?;
-	let keymap_toml = &Preset::keymap(&config_dir)?;
-	let theme_toml = &Preset::theme(&config_dir)?;
+	let yazi_toml = &Preset::set(Preset::yazi(&config_dir, profile)?, sets)?;
+	let keymap_toml = &Preset::keymap(&config_dir, profile)?;
+	let theme_toml = &Preset::theme(&config_dir, profile)?;
 
 	LAYOUT.with(<_>::default);
 
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardBackend {
+	/// The native clipboard, or OSC 52 if there's none, e.g. over SSH.
+	#[default]
+	Auto,
+	Native,
+	Osc52,
+}
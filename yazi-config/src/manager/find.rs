@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FindCase {
+	/// Insensitive, unless the query contains an uppercase letter.
+	#[default]
+	Smart,
+	Sensitive,
+	Insensitive,
+}
@@ -1,9 +1,10 @@
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use validator::Validate;
+use yazi_shared::fs::expand_path;
 
-use super::{ManagerRatio, MouseEvents, SortBy};
+use super::{ClipboardBackend, FindCase, ManagerRatio, MouseEvents, Protected, ShowHidden, SortBy, SortDigits, SortOverrides};
 
 #[derive(Debug, Deserialize, Serialize, Validate)]
 pub struct Manager {
@@ -15,15 +16,66 @@ pub struct Manager {
 	pub sort_reverse:   bool,
 	pub sort_dir_first: bool,
 	pub sort_translit:  bool,
+	pub sort_by_locale: bool,
+	pub sort_digits:    SortDigits,
+	pub sort_overrides: SortOverrides,
+	pub remember_views: usize,
+	pub recent_dirs:    usize,
 
 	// Display
 	#[validate(length(min = 1, max = 20, message = "must be between 1 and 20 characters"))]
 	pub linemode:     String,
-	pub show_hidden:  bool,
+	pub calc_size:    bool,
+	pub show_hidden:  ShowHidden,
 	pub show_symlink: bool,
+	pub thumbnails:   bool,
 	pub scrolloff:    u8,
 	pub mouse_events: MouseEvents,
+	pub double_click: u16,
 	pub title_format: String,
+	pub cd_realpath:  bool,
+	pub find_case:    FindCase,
+
+	// Linemode
+	pub mtime_format:      String,
+	pub mtime_format_past: String,
+
+	// Filtering
+	pub follow_filter: bool,
+
+	// Polling
+	pub poll_interval: u64,
+	pub poll_prefixes: Vec<PathBuf>,
+
+	// Watching
+	#[validate(range(min = 10, max = 5000, message = "must be between 10 and 5000"))]
+	pub watch_window: u64,
+	#[validate(range(min = 10, message = "must be at least 10"))]
+	pub watch_burst:  usize,
+
+	// Metadata
+	pub stat_timeout: u64,
+
+	// Focus
+	pub pause_unfocused: bool,
+
+	// Duplicate
+	pub duplicate_format: String,
+
+	// Session
+	pub restore_session: bool,
+
+	// Clipboard
+	pub clipboard: ClipboardBackend,
+
+	// Protection
+	pub protected: Protected,
+
+	// Renaming
+	pub confirm_ext_change: bool,
+
+	// Opening
+	pub confirm_open_size: u64,
 }
 
 impl FromStr for Manager {
@@ -35,9 +87,19 @@ impl FromStr for Manager {
 			manager: Manager,
 		}
 
-		let manager = toml::from_str::<Outer>(s)?.manager;
+		let mut manager = toml::from_str::<Outer>(s)?.manager;
 		manager.validate()?;
 
+		manager.poll_prefixes = manager.poll_prefixes.into_iter().map(expand_path).collect();
+
 		Ok(manager)
 	}
 }
+
+impl Manager {
+	#[inline]
+	pub fn stat_timeout(&self) -> Duration { Duration::from_millis(self.stat_timeout) }
+
+	#[inline]
+	pub fn watch_window(&self) -> Duration { Duration::from_millis(self.watch_window) }
+}
@@ -1 +1 @@
-yazi_macro::mod_flat!(manager mouse ratio sorting);
+yazi_macro::mod_flat!(clipboard find manager mouse protected ratio show_hidden sort_override sorting);
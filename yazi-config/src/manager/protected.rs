@@ -0,0 +1,158 @@
+use std::{fs, path::{Component, Path, PathBuf}};
+
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize, Serializer};
+use yazi_shared::fs::expand_path;
+
+/// The paths that destructive operations have to be confirmed for, by typing
+/// their names, along with everything under them.
+#[derive(Debug, Default, Deserialize)]
+#[serde(try_from = "Vec<String>")]
+pub struct Protected {
+	globs:    Vec<String>,
+	patterns: Vec<PathBuf>,
+	set:      GlobSet,
+}
+
+impl Protected {
+	/// The protected path that `path` is, or is under, if any.
+	pub fn of<'a>(&self, path: &'a Path) -> Option<&'a Path> {
+		if self.globs.is_empty() {
+			return None;
+		}
+		path.ancestors().find(|p| self.set.is_match(p))
+	}
+
+	/// The protected paths that exist under the directory `dir`, which go along
+	/// with it when it's moved or removed as a whole.
+	pub fn under(&self, dir: &Path) -> Vec<PathBuf> {
+		let mut paths = vec![];
+		for pattern in &self.patterns {
+			// Up to a `**`, which can match any depth, the pattern has to match `dir`
+			let mut it = pattern.components().peekable();
+			if !dir.components().all(|c| match it.next_if(|p| p.as_os_str() != "**") {
+				Some(p) => p == c || Self::matches(p, c),
+				None => it.peek().is_some(),
+			}) {
+				continue;
+			}
+
+			let mut candidates = vec![dir.to_owned()];
+			for p in it {
+				if p.as_os_str() == "**" {
+					candidates = candidates.iter().flat_map(|c| self.walk(c)).collect();
+					break;
+				} else if Self::literal(p) {
+					candidates.iter_mut().for_each(|c| c.push(p));
+				} else {
+					candidates = candidates
+						.iter()
+						.filter_map(|c| fs::read_dir(c).ok())
+						.flat_map(|it| it.flatten())
+						.filter(|e| Self::matches(p, Component::Normal(&e.file_name())))
+						.map(|e| e.path())
+						.collect();
+				}
+			}
+			paths.extend(candidates.into_iter().filter(|c| c != dir && c.symlink_metadata().is_ok()));
+		}
+
+		paths.sort_unstable();
+		paths.dedup();
+		paths
+	}
+
+	// Whether the component `c` of a path is matched by the glob component `p`
+	fn matches(p: Component, c: Component) -> bool {
+		!Self::literal(p)
+			&& Glob::new(&p.as_os_str().to_string_lossy())
+				.is_ok_and(|g| g.compile_matcher().is_match(c.as_os_str()))
+	}
+
+	#[inline]
+	fn literal(p: Component) -> bool {
+		!p.as_os_str().to_string_lossy().contains(['*', '?', '[', '{'])
+	}
+
+	// Everything under `dir` that is protected, as for a `**` that can match any
+	// depth
+	fn walk(&self, dir: &Path) -> Vec<PathBuf> {
+		let mut paths = vec![];
+		let mut dirs = vec![dir.to_owned()];
+		while let Some(dir) = dirs.pop() {
+			for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+				let path = entry.path();
+				if self.set.is_match(&path) {
+					paths.push(path);
+				} else if entry.file_type().is_ok_and(|t| t.is_dir()) {
+					dirs.push(path);
+				}
+			}
+		}
+		paths
+	}
+}
+
+impl TryFrom<Vec<String>> for Protected {
+	type Error = anyhow::Error;
+
+	fn try_from(globs: Vec<String>) -> Result<Self, Self::Error> {
+		let mut builder = GlobSetBuilder::new();
+		let mut patterns = Vec::with_capacity(globs.len());
+		for g in &globs {
+			let path = expand_path(g.trim_end_matches('/'));
+			builder.add(GlobBuilder::new(&path.to_string_lossy()).literal_separator(true).build()?);
+			patterns.push(path);
+		}
+		Ok(Self { set: builder.build()?, patterns, globs })
+	}
+}
+
+impl Serialize for Protected {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.globs.serialize(serializer)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_of() {
+		let p =
+			Protected::try_from(vec!["/home/a/.ssh/".to_owned(), "/srv/*/data".to_owned()]).unwrap();
+		assert_eq!(p.of(Path::new("/home/a/.ssh")), Some(Path::new("/home/a/.ssh")));
+		assert_eq!(p.of(Path::new("/home/a/.ssh/id_ed25519")), Some(Path::new("/home/a/.ssh")));
+		assert_eq!(p.of(Path::new("/srv/web/data/db")), Some(Path::new("/srv/web/data")));
+		assert_eq!(p.of(Path::new("/srv/web/x/data")), None);
+		assert_eq!(p.of(Path::new("/home/a")), None);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_under() {
+		let root = std::env::temp_dir().join(format!("yazi-protected-{}", std::process::id()));
+		for d in ["a/.ssh", "a/b/.git", "a/c", "srv/web/data", "srv/db/data", "srv/db/x"] {
+			fs::create_dir_all(root.join(d)).unwrap();
+		}
+
+		let r = root.to_string_lossy();
+		let p = Protected::try_from(vec![
+			format!("{r}/a/.ssh"),
+			format!("{r}/srv/*/data"),
+			format!("{r}/**/.git"),
+			format!("{r}/a/missing"),
+			format!("{r}/x/**/data"),
+		])
+		.unwrap();
+
+		assert_eq!(p.under(&root.join("a")), [root.join("a/.ssh"), root.join("a/b/.git")]);
+		assert_eq!(p.under(&root.join("srv")), [root.join("srv/db/data"), root.join("srv/web/data")]);
+		assert_eq!(p.under(&root.join("srv/db")), [root.join("srv/db/data")]);
+		assert!(p.under(&root.join("a/c")).is_empty());
+		assert!(p.under(&root.join("a/.ssh")).is_empty());
+
+		fs::remove_dir_all(root).unwrap();
+	}
+}
@@ -0,0 +1,79 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// How the hidden files are shown, where `true` and `false` are taken for
+/// `Show` and `Hide` as well.
+#[derive(Clone, Copy, Debug, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ShowHidden {
+	#[default]
+	Hide,
+	Show,
+	/// Listed as with `Show`, but rendered in the `hidden` style of the theme.
+	Dim,
+}
+
+impl ShowHidden {
+	/// Whether they're listed, i.e. `Show` or `Dim`.
+	#[inline]
+	pub fn is_shown(self) -> bool { self != Self::Hide }
+
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Hide => "hide",
+			Self::Show => "show",
+			Self::Dim => "dim",
+		}
+	}
+}
+
+impl FromStr for ShowHidden {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"hide" | "false" => Self::Hide,
+			"show" | "true" => Self::Show,
+			"dim" => Self::Dim,
+			_ => bail!("invalid hidden mode: {s}, expected 'hide', 'show' or 'dim'"),
+		})
+	}
+}
+
+impl<'de> Deserialize<'de> for ShowHidden {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		#[derive(Deserialize)]
+		#[serde(untagged)]
+		enum Shadow {
+			Bool(bool),
+			Str(String),
+		}
+
+		match Shadow::deserialize(deserializer)? {
+			Shadow::Bool(b) => Ok(if b { Self::Show } else { Self::Hide }),
+			Shadow::Str(s) => s.parse().map_err(serde::de::Error::custom),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_deserialize() {
+		#[derive(Deserialize)]
+		struct Outer {
+			show_hidden: ShowHidden,
+		}
+
+		let parse = |s: &str| toml::from_str::<Outer>(s).map(|o| o.show_hidden).ok();
+		assert_eq!(parse("show_hidden = true"), Some(ShowHidden::Show));
+		assert_eq!(parse("show_hidden = false"), Some(ShowHidden::Hide));
+		assert_eq!(parse(r#"show_hidden = "dim""#), Some(ShowHidden::Dim));
+		assert_eq!(parse(r#"show_hidden = "hide""#), Some(ShowHidden::Hide));
+		assert_eq!(parse(r#"show_hidden = "faint""#), None);
+	}
+}
@@ -0,0 +1,147 @@
+use std::path::Path;
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize, Serializer};
+use yazi_shared::fs::expand_path;
+
+use super::SortBy;
+
+/// The sorting of the directories that match a glob, which is used instead of
+/// the one of the tab, unless changed by `sort` at runtime.
+#[derive(Debug, Default, Deserialize)]
+#[serde(try_from = "Vec<SortOverride>")]
+pub struct SortOverrides {
+	rules: Vec<SortOverride>,
+	set:   GlobSet,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SortOverride {
+	pub path: String,
+	#[serde(flatten)]
+	pub opts: SortOpts,
+}
+
+/// The sort options that are set, each taking the place of the one of the tab.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SortOpts {
+	pub sort_by:   Option<SortBy>,
+	pub reverse:   Option<bool>,
+	pub dir_first: Option<bool>,
+	pub sensitive: Option<bool>,
+}
+
+impl SortOverrides {
+	/// The first override whose glob matches the directory `path`.
+	pub fn of(&self, path: &Path) -> Option<&SortOverride> {
+		if self.rules.is_empty() {
+			return None;
+		}
+		self.set.matches(path).into_iter().min().map(|i| &self.rules[i])
+	}
+}
+
+impl SortOpts {
+	/// The options of `self` that aren't set in `other`.
+	pub fn without(self, other: &Self) -> Self {
+		Self {
+			sort_by:   self.sort_by.filter(|_| other.sort_by.is_none()),
+			reverse:   self.reverse.filter(|_| other.reverse.is_none()),
+			dir_first: self.dir_first.filter(|_| other.dir_first.is_none()),
+			sensitive: self.sensitive.filter(|_| other.sensitive.is_none()),
+		}
+	}
+
+	/// The options of `self`, with those not set in it taken from `lower`.
+	pub fn or(self, lower: Self) -> Self {
+		Self {
+			sort_by:   self.sort_by.or(lower.sort_by),
+			reverse:   self.reverse.or(lower.reverse),
+			dir_first: self.dir_first.or(lower.dir_first),
+			sensitive: self.sensitive.or(lower.sensitive),
+		}
+	}
+
+	#[inline]
+	pub fn is_empty(&self) -> bool { *self == Self::default() }
+}
+
+impl TryFrom<Vec<SortOverride>> for SortOverrides {
+	type Error = anyhow::Error;
+
+	fn try_from(rules: Vec<SortOverride>) -> Result<Self, Self::Error> {
+		let mut builder = GlobSetBuilder::new();
+		for r in &rules {
+			let path = expand_path(r.path.trim_end_matches('/'));
+			builder.add(GlobBuilder::new(&path.to_string_lossy()).literal_separator(true).build()?);
+		}
+		Ok(Self { set: builder.build()?, rules })
+	}
+}
+
+impl Serialize for SortOverrides {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.rules.serialize(serializer)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_of() {
+		#[derive(Deserialize)]
+		struct Outer {
+			sort_overrides: SortOverrides,
+		}
+
+		let o: Outer = toml::from_str(
+			r#"
+			[[sort_overrides]]
+			path = "/home/a/Downloads/"
+			sort_by = "mtime"
+			dir_first = false
+
+			[[sort_overrides]]
+			path = "/home/a/*"
+			reverse = true
+			"#,
+		)
+		.unwrap();
+
+		let o = o.sort_overrides;
+		let d = o.of(Path::new("/home/a/Downloads")).unwrap();
+		assert_eq!(d.path, "/home/a/Downloads/");
+		assert_eq!(d.opts, SortOpts {
+			sort_by: Some(SortBy::Mtime),
+			dir_first: Some(false),
+			..Default::default()
+		});
+
+		assert_eq!(o.of(Path::new("/home/a/Music")).unwrap().opts.reverse, Some(true));
+		assert!(o.of(Path::new("/home/a/Downloads/x")).is_none());
+		assert!(o.of(Path::new("/home/a")).is_none());
+	}
+
+	#[test]
+	fn test_precedence() {
+		let runtime = SortOpts { reverse: Some(true), ..Default::default() };
+		let remembered =
+			SortOpts { sort_by: Some(SortBy::Natural), reverse: Some(false), ..Default::default() };
+		let over =
+			SortOpts { sort_by: Some(SortBy::Mtime), dir_first: Some(false), ..Default::default() };
+
+		// Runtime > per-directory memory > path-based override
+		assert_eq!(runtime.or(remembered).or(over), SortOpts {
+			sort_by:   Some(SortBy::Natural),
+			reverse:   Some(true),
+			dir_first: Some(false),
+			sensitive: None,
+		});
+
+		// The global default is left for what none of them sets
+		assert_eq!(SortOpts::default().or(SortOpts::default()).or(over), over);
+		assert_eq!(runtime.or(SortOpts::default()).sort_by, None);
+	}
+}
@@ -55,3 +55,12 @@ impl Display for SortBy {
 		})
 	}
 }
+
+/// Where the numbers go relative to the letters in natural sorting.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDigits {
+	#[default]
+	First,
+	Last,
+}
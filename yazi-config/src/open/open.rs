@@ -47,10 +47,37 @@ impl Open {
 		self.openers(path, mime).and_then(|o| o.into_iter().find(|o| o.block))
 	}
 
-	pub fn common_openers(&self, targets: &[(impl AsRef<Path>, impl AsRef<str>)]) -> Vec<&Opener> {
-		let grouped: Vec<_> = targets.iter().filter_map(|(p, m)| self.openers(p, m)).collect();
-		let flat: IndexSet<_> = grouped.iter().flatten().copied().collect();
-		flat.into_iter().filter(|&o| grouped.iter().all(|g| g.contains(o))).collect()
+	/// The openers for `targets`, each with the indices of the targets it
+	/// applies to.
+	///
+	/// Those common to all targets are preferred, if there're none, all the
+	/// openers of any target are returned instead, the most applicable first.
+	pub fn applicable_openers(
+		&self,
+		targets: &[(impl AsRef<Path>, impl AsRef<str>)],
+	) -> Vec<(&Opener, Vec<usize>)> {
+		let grouped: Vec<_> = targets.iter().map(|(p, m)| self.openers(p, m)).collect();
+		let flat: IndexSet<_> = grouped.iter().flatten().flatten().copied().collect();
+
+		let mut openers: Vec<_> = flat
+			.into_iter()
+			.map(|o| {
+				let indices = grouped
+					.iter()
+					.enumerate()
+					.filter(|(_, g)| g.as_ref().is_some_and(|g| g.contains(o)))
+					.map(|(i, _)| i)
+					.collect::<Vec<_>>();
+				(o, indices)
+			})
+			.collect();
+
+		if openers.iter().any(|(_, v)| v.len() == targets.len()) {
+			openers.retain(|(_, v)| v.len() == targets.len());
+		} else {
+			openers.sort_by_key(|(_, v)| std::cmp::Reverse(v.len()));
+		}
+		openers
 	}
 }
 
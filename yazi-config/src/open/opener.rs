@@ -1,4 +1,9 @@
+use std::{collections::BTreeMap, path::{Path, PathBuf, is_separator}};
+
 use serde::{Deserialize, Deserializer};
+use yazi_shared::fs::expand_path;
+
+use crate::MANAGER;
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Opener {
@@ -7,10 +12,73 @@ pub struct Opener {
 	pub orphan: bool,
 	pub desc:   String,
 	pub for_:   Option<String>,
+	/// Whether all the files are opened in a single process, either because
+	/// `run` takes them all with `$@`, or it's asked for with `multi`.
 	pub spread: bool,
+	/// The working directory to run in, where `$d` at its start is the directory
+	/// of the hovered file, or the CWD of Yazi if `None`.
+	pub cwd:    Option<String>,
+	/// The environment variables added to the ones of Yazi.
+	pub env:    BTreeMap<String, String>,
+	/// The `confirm_size` in megabytes taking the place of
+	/// `manager.confirm_open_size`, where `0` never asks.
+	pub guard:  Option<u64>,
 }
 
 impl Opener {
+	/// The `run` of the built-in opener that opens files with the handler
+	/// registered for them in the system, like double-clicking in Explorer.
+	pub const SYSTEM: &'static str = "@system";
+	/// The `run` of the built-in opener that shows the "Open with" dialog.
+	pub const SYSTEM_DIALOG: &'static str = "@system-dialog";
+
+	pub fn system(dialog: bool) -> Self {
+		Self {
+			run:    if dialog { Self::SYSTEM_DIALOG } else { Self::SYSTEM }.to_owned(),
+			block:  false,
+			orphan: true,
+			desc:   if dialog { "Open with…" } else { "System default" }.to_owned(),
+			for_:   None,
+			spread: false,
+			cwd:    None,
+			env:    Default::default(),
+			guard:  None,
+		}
+	}
+
+	/// Whether it's one of the built-in openers, which are only available on
+	/// Windows.
+	#[inline]
+	pub fn is_system(&self) -> bool { self.run == Self::SYSTEM || self.run == Self::SYSTEM_DIALOG }
+
+	/// The size in bytes of the files over which opening them has to be
+	/// confirmed, which only applies to the blocking openers.
+	pub fn guarded_size(&self) -> Option<u64> {
+		if !self.block {
+			return None;
+		}
+		let mb = self.guard.unwrap_or_else(|| MANAGER.confirm_open_size);
+		Some(mb << 20).filter(|_| mb > 0)
+	}
+
+	/// The working directory to run in, with `$d` taken as the directory of
+	/// `hovered`, which is `None` if there's no such directory.
+	///
+	/// That directory is used as it is, rather than expanded along with the
+	/// rest, so a `~`, `$` or `%` in its name is kept, and so is a name that
+	/// isn't valid UTF-8.
+	pub fn cwd(&self, hovered: Option<&Path>) -> Option<PathBuf> {
+		let cwd = self.cwd.as_ref()?;
+		let Some(rest) = cwd.strip_prefix("$d").filter(|s| s.is_empty() || s.starts_with(is_separator))
+		else {
+			return Some(expand_path(cwd));
+		};
+
+		let dir = hovered.and_then(Path::parent)?;
+		let rest = rest.trim_start_matches(is_separator);
+		Some(if rest.is_empty() { dir.to_owned() } else { dir.join(rest) })
+	}
+
 	pub fn take(mut self) -> Option<Self> {
 		if let Some(for_) = self.for_.take() {
 			match for_.as_bytes() {
@@ -40,18 +108,90 @@ impl<'de> Deserialize<'de> for Opener {
 			desc:   Option<String>,
 			#[serde(rename = "for")]
 			for_:   Option<String>,
+			multi:  Option<bool>,
+			cwd:    Option<String>,
+			#[serde(default)]
+			env:    BTreeMap<String, String>,
+			#[serde(rename = "confirm_size")]
+			guard:  Option<u64>,
 		}
 
 		let shadow = Shadow::deserialize(deserializer)?;
 
-		let run = shadow.run;
+		let mut run = shadow.run;
 		if run.is_empty() {
 			return Err(serde::de::Error::custom("`run` cannot be empty"));
 		}
 
 		let desc = shadow.desc.unwrap_or_else(|| run.split_whitespace().next().unwrap().to_string());
 
-		let spread = run.contains("$@") || run.contains("%*") || run.contains("$*");
-		Ok(Self { run, block: shadow.block, orphan: shadow.orphan, desc, for_: shadow.for_, spread })
+		// Those not taking all the files with `$@` get them appended, if `multi` is set
+		let takes_all = run.contains("$@") || run.contains("%*") || run.contains("$*");
+		let spread = shadow.multi.unwrap_or(takes_all);
+		if spread && !takes_all {
+			run.push_str(if cfg!(windows) { " %*" } else { r#" "$@""# });
+		}
+		Ok(Self {
+			run,
+			block: shadow.block,
+			orphan: shadow.orphan,
+			desc,
+			for_: shadow.for_,
+			spread,
+			cwd: shadow.cwd,
+			env: shadow.env,
+			guard: shadow.guard,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_multi() {
+		let opener = |s: &str| toml::from_str::<Opener>(s).unwrap();
+
+		let o = opener(r#"run = 'nvim -p "$@"'"#);
+		assert!(o.spread);
+		assert_eq!(o.run, r#"nvim -p "$@""#);
+
+		let o = opener(
+			r#"run = 'nvim -p "$@"'
+multi = false"#,
+		);
+		assert!(!o.spread);
+
+		let o = opener(
+			r#"run = 'nvim -p'
+multi = true"#,
+		);
+		assert!(o.spread);
+		assert_eq!(o.run, if cfg!(windows) { "nvim -p %*" } else { r#"nvim -p "$@""# });
+	}
+	#[test]
+	fn test_guarded_size() {
+		let opener = |s: &str| toml::from_str::<Opener>(s).unwrap();
+
+		let o = opener("run = 'vi'\nblock = true\nconfirm_size = 2");
+		assert_eq!(o.guarded_size(), Some(2 << 20));
+
+		// Never for the non-blocking ones, nor with a size of 0
+		assert_eq!(opener("run = 'mpv'\nconfirm_size = 2").guarded_size(), None);
+		assert_eq!(opener("run = 'vi'\nblock = true\nconfirm_size = 0").guarded_size(), None);
+	}
+
+	#[test]
+	fn test_cwd() {
+		let opener = |s: &str| toml::from_str::<Opener>(s).unwrap();
+		let hovered = Some(Path::new("/tmp/~x/$HOME/file"));
+
+		let o = opener("run = 'vi'\ncwd = '$d'");
+		assert_eq!(o.cwd(hovered), Some(PathBuf::from("/tmp/~x/$HOME")));
+		assert_eq!(o.cwd(None), None);
+
+		let o = opener("run = 'vi'\ncwd = '$d/sub'");
+		assert_eq!(o.cwd(hovered), Some(Path::new("/tmp/~x/$HOME").join("sub")));
 	}
 }
@@ -18,6 +18,13 @@ pub struct Fetcher {
 	pub run:  Cmd,
 	#[serde(default)]
 	pub prio: Priority,
+
+	/// How many calls of this fetcher can run at the same time, unlimited if
+	/// `None`.
+	pub max_concurrency: Option<usize>,
+	/// How many files are passed to a single call at most, with the rest split
+	/// into further calls, unlimited if `None`.
+	pub max_batch:       Option<usize>,
 }
 
 impl Fetcher {
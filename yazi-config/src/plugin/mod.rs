@@ -1,3 +1,3 @@
-yazi_macro::mod_flat!(fetcher plugin preloader previewer);
+yazi_macro::mod_flat!(fetcher plugin preloader previewer spotter);
 
 pub const MAX_PREWORKERS: u8 = 32;
@@ -2,14 +2,16 @@ use std::{collections::HashSet, path::Path, str::FromStr};
 
 use serde::Deserialize;
 
-use super::{Fetcher, Preloader, Previewer};
+use super::{Fetcher, Preloader, Previewer, Spotter};
 use crate::{Preset, plugin::MAX_PREWORKERS};
 
 #[derive(Deserialize)]
 pub struct Plugin {
-	pub fetchers:   Vec<Fetcher>,
-	pub preloaders: Vec<Preloader>,
-	pub previewers: Vec<Previewer>,
+	pub fetchers:     Vec<Fetcher>,
+	pub preloaders:   Vec<Preloader>,
+	pub previewers:   Vec<Previewer>,
+	pub spotters:     Vec<Spotter>,
+	pub http_domains: Vec<String>,
 }
 
 impl Plugin {
@@ -52,6 +54,25 @@ impl Plugin {
 	pub fn previewer(&self, path: &Path, mime: &str) -> Option<&Previewer> {
 		self.previewers.iter().find(|&p| p.matches(path, mime))
 	}
+
+	pub fn spotter(&self, path: &Path, mime: &str) -> Option<&Spotter> {
+		self.spotters.iter().find(|&s| s.matches(path, mime))
+	}
+
+	/// Whether plugins are allowed to make HTTP requests to the `host`,
+	/// either `*` for any host, or `*.example.com` for any of its subdomains.
+	pub fn http_allowed(&self, host: &str) -> bool {
+		let host = host.to_ascii_lowercase();
+		self.http_domains.iter().map(|d| d.to_ascii_lowercase()).any(|d| {
+			if d == "*" {
+				true
+			} else if let Some(suffix) = d.strip_prefix("*.") {
+				host.strip_suffix(suffix).is_some_and(|s| s.ends_with('.'))
+			} else {
+				d == host
+			}
+		})
+	}
 }
 
 impl FromStr for Plugin {
@@ -82,6 +103,16 @@ impl FromStr for Plugin {
 			prepend_previewers: Vec<Previewer>,
 			#[serde(default)]
 			append_previewers:  Vec<Previewer>,
+
+			#[serde(default)]
+			spotters:         Vec<Spotter>,
+			#[serde(default)]
+			prepend_spotters: Vec<Spotter>,
+			#[serde(default)]
+			append_spotters:  Vec<Spotter>,
+
+			#[serde(default)]
+			http_domains: Vec<String>,
 		}
 
 		let mut shadow = toml::from_str::<Outer>(s)?.plugin;
@@ -98,6 +129,8 @@ impl FromStr for Plugin {
 			Preset::mix(shadow.preloaders, shadow.prepend_preloaders, shadow.append_preloaders).collect();
 		shadow.previewers =
 			Preset::mix(shadow.previewers, shadow.prepend_previewers, shadow.append_previewers).collect();
+		shadow.spotters =
+			Preset::mix(shadow.spotters, shadow.prepend_spotters, shadow.append_spotters).collect();
 
 		if shadow.fetchers.len() + shadow.preloaders.len() > MAX_PREWORKERS as usize {
 			panic!("Fetchers and preloaders exceed the limit of {MAX_PREWORKERS}");
@@ -111,9 +144,11 @@ impl FromStr for Plugin {
 		}
 
 		Ok(Self {
-			fetchers:   shadow.fetchers,
-			preloaders: shadow.preloaders,
-			previewers: shadow.previewers,
+			fetchers:     shadow.fetchers,
+			preloaders:   shadow.preloaders,
+			previewers:   shadow.previewers,
+			spotters:     shadow.spotters,
+			http_domains: shadow.http_domains,
 		})
 	}
 }
@@ -7,11 +7,15 @@ use crate::Pattern;
 
 #[derive(Debug, Deserialize)]
 pub struct Previewer {
-	pub name: Option<Pattern>,
-	pub mime: Option<Pattern>,
-	pub run:  Cmd,
+	pub name:     Option<Pattern>,
+	pub mime:     Option<Pattern>,
+	pub run:      Cmd,
 	#[serde(default)]
-	pub sync: bool,
+	pub sync:     bool,
+	/// Whether to keep the previewer instance alive for the hovered file, and
+	/// send `seek` and `resize` calls to it, instead of starting over each time.
+	#[serde(default)]
+	pub stateful: bool,
 }
 
 impl Previewer {
@@ -6,6 +6,10 @@ use super::{Offset, Origin};
 
 #[derive(Deserialize)]
 pub struct Confirm {
+	/// Whether the commands sent with `dds-emit` skip their confirmations, as if
+	/// given `--yes`.
+	pub bypass_on_dds: bool,
+
 	// trash
 	pub trash_title:  String,
 	pub trash_origin: Origin,
@@ -16,6 +20,11 @@ pub struct Confirm {
 	pub delete_origin: Origin,
 	pub delete_offset: Offset,
 
+	// untrashable
+	pub untrashable_title:  String,
+	pub untrashable_origin: Origin,
+	pub untrashable_offset: Offset,
+
 	// overwrite
 	pub overwrite_title:   String,
 	pub overwrite_content: String,
@@ -27,6 +36,40 @@ pub struct Confirm {
 	pub quit_content: String,
 	pub quit_origin:  Origin,
 	pub quit_offset:  Offset,
+
+	// extract
+	pub extract_title:   String,
+	pub extract_content: String,
+	pub extract_origin:  Origin,
+	pub extract_offset:  Offset,
+
+	// checksum
+	pub checksum_title:   String,
+	pub checksum_content: String,
+	pub checksum_origin:  Origin,
+	pub checksum_offset:  Offset,
+
+	// verify
+	pub verify_title:  String,
+	pub verify_origin: Origin,
+	pub verify_offset: Offset,
+
+	// unquarantine
+	pub unquarantine_title:   String,
+	pub unquarantine_content: String,
+	pub unquarantine_origin:  Origin,
+	pub unquarantine_offset:  Offset,
+
+	// ext_change
+	pub ext_change_title:   String,
+	pub ext_change_content: String,
+	pub ext_change_origin:  Origin,
+	pub ext_change_offset:  Offset,
+
+	// large_open
+	pub large_open_title:  String,
+	pub large_open_origin: Origin,
+	pub large_open_offset: Offset,
 }
 
 impl FromStr for Confirm {
@@ -13,6 +13,11 @@ pub struct Input {
 	pub cd_origin: Origin,
 	pub cd_offset: Offset,
 
+	// recent
+	pub recent_title:  String,
+	pub recent_origin: Origin,
+	pub recent_offset: Offset,
+
 	// create
 	pub create_title:  InputCreateTitle,
 	pub create_origin: Origin,
@@ -23,6 +28,11 @@ pub struct Input {
 	pub rename_origin: Origin,
 	pub rename_offset: Offset,
 
+	// rename (selected)
+	pub template_title:  String,
+	pub template_origin: Origin,
+	pub template_offset: Offset,
+
 	// filter
 	pub filter_title:  String,
 	pub filter_origin: Origin,
@@ -42,6 +52,26 @@ pub struct Input {
 	pub shell_title:  [String; 2],
 	pub shell_origin: Origin,
 	pub shell_offset: Offset,
+
+	// password
+	pub password_title:  String,
+	pub password_origin: Origin,
+	pub password_offset: Offset,
+
+	// compress
+	pub compress_title:  String,
+	pub compress_origin: Origin,
+	pub compress_offset: Offset,
+
+	// checksum
+	pub checksum_title:  String,
+	pub checksum_origin: Origin,
+	pub checksum_offset: Offset,
+
+	// protected
+	pub protected_title:  String,
+	pub protected_origin: Origin,
+	pub protected_offset: Offset,
 }
 
 impl Input {
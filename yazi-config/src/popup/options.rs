@@ -1,4 +1,4 @@
-use ratatui::{text::Text, widgets::{Paragraph, Wrap}};
+use ratatui::{text::{Line, Text}, widgets::{Paragraph, Wrap}};
 use yazi_shared::fs::Url;
 
 use super::{Offset, Origin, Position};
@@ -12,7 +12,14 @@ pub struct InputCfg {
 	pub position:   Position,
 	pub realtime:   bool,
 	pub completion: bool,
+	/// Whether the completion is offered right away, rather than once typed.
+	pub eager:      bool,
 	pub highlight:  bool,
+	pub obscure:    bool,
+	pub selected:   bool,
+	/// Whether each typed value is validated, holding back submitting until it
+	/// is.
+	pub validate:   bool,
 }
 
 #[derive(Default)]
@@ -20,6 +27,10 @@ pub struct PickCfg {
 	pub title:    String,
 	pub items:    Vec<String>,
 	pub position: Position,
+	/// Whether several items can be toggled with `<Space>` and picked at once.
+	pub multi:    bool,
+	/// The items toggled at first, if `multi`.
+	pub selected: Vec<usize>,
 }
 
 #[derive(Default)]
@@ -28,6 +39,8 @@ pub struct ConfirmCfg {
 	pub position: Position,
 	pub content:  Paragraph<'static>,
 	pub list:     Paragraph<'static>,
+	/// Whether "Yes" is focused at first, which is what `<Enter>` goes for.
+	pub default:  bool,
 }
 
 impl InputCfg {
@@ -40,6 +53,16 @@ impl InputCfg {
 		}
 	}
 
+	pub fn recent() -> Self {
+		Self {
+			title: INPUT.recent_title.to_owned(),
+			position: Position::new(INPUT.recent_origin, INPUT.recent_offset),
+			completion: true,
+			eager: true,
+			..Default::default()
+		}
+	}
+
 	pub fn create(dir: bool) -> Self {
 		Self {
 			title: INPUT.create_title.as_array()[dir as usize].to_owned(),
@@ -56,6 +79,14 @@ impl InputCfg {
 		}
 	}
 
+	pub fn template(n: usize) -> Self {
+		Self {
+			title: INPUT.template_title.replace("{n}", &n.to_string()),
+			position: Position::new(INPUT.template_origin, INPUT.template_offset),
+			..Default::default()
+		}
+	}
+
 	pub fn filter() -> Self {
 		Self {
 			title: INPUT.filter_title.to_owned(),
@@ -91,6 +122,39 @@ impl InputCfg {
 		}
 	}
 
+	pub fn password(name: &str) -> Self {
+		Self {
+			title: INPUT.password_title.replace("{n}", name),
+			position: Position::new(INPUT.password_origin, INPUT.password_offset),
+			obscure: true,
+			..Default::default()
+		}
+	}
+
+	pub fn compress() -> Self {
+		Self {
+			title: INPUT.compress_title.to_owned(),
+			position: Position::new(INPUT.compress_origin, INPUT.compress_offset),
+			..Default::default()
+		}
+	}
+
+	pub fn checksum(name: &str) -> Self {
+		Self {
+			title: INPUT.checksum_title.replace("{n}", name),
+			position: Position::new(INPUT.checksum_origin, INPUT.checksum_offset),
+			..Default::default()
+		}
+	}
+
+	pub fn protected(name: &str) -> Self {
+		Self {
+			title: INPUT.protected_title.replace("{n}", name),
+			position: Position::new(INPUT.protected_origin, INPUT.protected_offset),
+			..Default::default()
+		}
+	}
+
 	#[inline]
 	pub fn with_value(mut self, value: impl Into<String>) -> Self {
 		self.value = value.into();
@@ -110,30 +174,49 @@ impl ConfirmCfg {
 		(origin, offset): (Origin, Offset),
 		content: Option<Text<'static>>,
 		list: Option<Text<'static>>,
+		default: bool,
 	) -> Self {
 		Self {
 			title,
 			position: Position::new(origin, offset),
 			content: content.map(|c| Paragraph::new(c).wrap(Wrap { trim: false })).unwrap_or_default(),
 			list: list.map(|l| Paragraph::new(l).wrap(Wrap { trim: false })).unwrap_or_default(),
+			default,
 		}
 	}
 
-	pub fn trash(urls: &[yazi_shared::fs::Url]) -> Self {
+	/// The files to trash, with a `summary` of how many files and bytes are under
+	/// them above the list.
+	pub fn trash(urls: &[yazi_shared::fs::Url], summary: String) -> Self {
 		Self::new(
 			Self::replace_number(&CONFIRM.trash_title, urls.len()),
 			(CONFIRM.trash_origin, CONFIRM.trash_offset),
-			None,
+			Some(Text::raw(summary)),
 			Self::truncate_list(urls.iter(), urls.len(), 100),
+			false,
 		)
 	}
 
-	pub fn delete(urls: &[yazi_shared::fs::Url]) -> Self {
+	/// The files to delete, with a `summary` like [`Self::trash`].
+	pub fn delete(urls: &[yazi_shared::fs::Url], summary: String) -> Self {
 		Self::new(
 			Self::replace_number(&CONFIRM.delete_title, urls.len()),
 			(CONFIRM.delete_origin, CONFIRM.delete_offset),
-			None,
+			Some(Text::raw(summary)),
+			Self::truncate_list(urls.iter(), urls.len(), 100),
+			false,
+		)
+	}
+
+	/// The files to delete permanently as they can't go to the trash, with a
+	/// `summary` of the whole removal, which includes the trashable ones too.
+	pub fn untrashable(urls: &[yazi_shared::fs::Url], summary: String) -> Self {
+		Self::new(
+			Self::replace_number(&CONFIRM.untrashable_title, urls.len()),
+			(CONFIRM.untrashable_origin, CONFIRM.untrashable_offset),
+			Some(Text::raw(summary)),
 			Self::truncate_list(urls.iter(), urls.len(), 100),
+			false,
 		)
 	}
 
@@ -143,6 +226,17 @@ impl ConfirmCfg {
 			(CONFIRM.overwrite_origin, CONFIRM.overwrite_offset),
 			Some(Text::raw(&CONFIRM.overwrite_content)),
 			Some(url.to_string().into()),
+			false,
+		)
+	}
+
+	pub fn unquarantine(url: &Url) -> Self {
+		Self::new(
+			CONFIRM.unquarantine_title.to_owned(),
+			(CONFIRM.unquarantine_origin, CONFIRM.unquarantine_offset),
+			Some(Text::raw(&CONFIRM.unquarantine_content)),
+			Some(url.to_string().into()),
+			false,
 		)
 	}
 
@@ -152,6 +246,63 @@ impl ConfirmCfg {
 			(CONFIRM.quit_origin, CONFIRM.quit_offset),
 			Some(Text::raw(&CONFIRM.quit_content)),
 			Self::truncate_list(names.into_iter(), len, 10),
+			true,
+		)
+	}
+
+	pub fn extract(names: Vec<String>) -> Self {
+		Self::new(
+			Self::replace_number(&CONFIRM.extract_title, names.len()),
+			(CONFIRM.extract_origin, CONFIRM.extract_offset),
+			Some(Text::raw(&CONFIRM.extract_content)),
+			Self::truncate_list(names.iter().cloned(), names.len(), 100),
+			false,
+		)
+	}
+
+	pub fn checksum(lines: Vec<Line<'static>>) -> Self {
+		let len = lines.len();
+		Self::new(
+			Self::replace_number(&CONFIRM.checksum_title, len),
+			(CONFIRM.checksum_origin, CONFIRM.checksum_offset),
+			Some(Text::raw(&CONFIRM.checksum_content)),
+			Some(Text::from(lines)),
+			true,
+		)
+	}
+
+	/// The results of verifying files, one line each, with a `summary` of how
+	/// many passed or failed above them.
+	pub fn verify(summary: String, lines: Vec<Line<'static>>) -> Self {
+		Self::new(
+			Self::replace_number(&CONFIRM.verify_title, lines.len()),
+			(CONFIRM.verify_origin, CONFIRM.verify_offset),
+			Some(Text::raw(summary)),
+			Some(Text::from(lines)),
+			true,
+		)
+	}
+
+	/// The renames changing the extension of files, as `old -> new` lines.
+	pub fn ext_change(lines: Vec<String>) -> Self {
+		Self::new(
+			Self::replace_number(&CONFIRM.ext_change_title, lines.len()),
+			(CONFIRM.ext_change_origin, CONFIRM.ext_change_offset),
+			Some(Text::raw(&CONFIRM.ext_change_content)),
+			Self::truncate_list(lines.iter().cloned(), lines.len(), 100),
+			false,
+		)
+	}
+
+	/// The files to open with a blocking opener that are too large together, as
+	/// `path (size)` lines, with a `summary` of how large they are in total.
+	pub fn large_open(lines: Vec<String>, summary: String) -> Self {
+		Self::new(
+			Self::replace_number(&CONFIRM.large_open_title, lines.len()),
+			(CONFIRM.large_open_origin, CONFIRM.large_open_offset),
+			Some(Text::raw(summary)),
+			Self::truncate_list(lines.iter().cloned(), lines.len(), 100),
+			false,
 		)
 	}
 
@@ -188,6 +339,38 @@ impl PickCfg {
 			title: PICK.open_title.to_owned(),
 			items,
 			position: Position::new(PICK.open_origin, Offset { height: max_height, ..PICK.open_offset }),
+			multi: false,
+			selected: vec![],
+		}
+	}
+
+	pub fn action(items: Vec<String>) -> Self {
+		let max_height =
+			PICK.action_offset.height.min(PICK.border().saturating_add(items.len() as u16));
+		Self {
+			title: PICK.action_title.to_owned(),
+			items,
+			position: Position::new(PICK.action_origin, Offset {
+				height: max_height,
+				..PICK.action_offset
+			}),
+			multi: false,
+			selected: vec![],
+		}
+	}
+
+	/// The yanked files to paste, all of which are toggled at first.
+	pub fn paste(items: Vec<String>) -> Self {
+		let max_height = PICK.paste_offset.height.min(PICK.border().saturating_add(items.len() as u16));
+		Self {
+			title: PICK.paste_title.to_owned(),
+			selected: (0..items.len()).collect(),
+			items,
+			position: Position::new(PICK.paste_origin, Offset {
+				height: max_height,
+				..PICK.paste_offset
+			}),
+			multi: true,
 		}
 	}
 }
@@ -10,6 +10,16 @@ pub struct Pick {
 	pub open_title:  String,
 	pub open_origin: Origin,
 	pub open_offset: Offset,
+
+	// action
+	pub action_title:  String,
+	pub action_origin: Origin,
+	pub action_offset: Offset,
+
+	// paste
+	pub paste_title:  String,
+	pub paste_origin: Origin,
+	pub paste_offset: Offset,
 }
 
 impl Pick {
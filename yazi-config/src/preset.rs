@@ -1,6 +1,6 @@
 use std::{borrow::Cow, path::{Path, PathBuf}};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use toml::{Table, Value};
 use yazi_macro::config_preset as preset;
 
@@ -9,18 +9,19 @@ use crate::theme::Flavor;
 pub(crate) struct Preset;
 
 impl Preset {
-	pub(crate) fn yazi(p: &Path) -> Result<Cow<str>> {
-		Self::merge_path(p.join("yazi.toml"), preset!("yazi"))
+	pub(crate) fn yazi<'a>(p: &Path, profile: Option<&str>) -> Result<Cow<'a, str>> {
+		Self::merge_path(p.join("yazi.toml"), preset!("yazi"), profile)
 	}
 
-	pub(crate) fn keymap(p: &Path) -> Result<Cow<str>> {
-		Self::merge_path(p.join("keymap.toml"), preset!("keymap"))
+	pub(crate) fn keymap<'a>(p: &Path, profile: Option<&str>) -> Result<Cow<'a, str>> {
+		Self::merge_path(p.join("keymap.toml"), preset!("keymap"), profile)
 	}
 
-	pub(crate) fn theme(p: &Path) -> Result<Cow<str>> {
-		let Ok(user) = std::fs::read_to_string(p.join("theme.toml")) else {
+	pub(crate) fn theme<'a>(p: &Path, profile: Option<&str>) -> Result<Cow<'a, str>> {
+		let Some(user) = Self::read(&p.join("theme.toml"), profile)? else {
 			return Ok(preset!("theme"));
 		};
+		let user = user.to_string();
 		let Some(use_) = Flavor::parse_use(&user) else {
 			return Self::merge_str(&user, &preset!("theme"));
 		};
@@ -32,6 +33,50 @@ impl Preset {
 		Self::merge_str(&user, &Self::merge_str(&flavor, &preset!("theme"))?)
 	}
 
+	/// Applies the `key.path=value` overrides to the merged `yazi.toml`, where
+	/// the value is parsed as TOML, or taken as a string if it's not valid TOML.
+	pub(crate) fn set<'a>(s: Cow<'a, str>, sets: &[String]) -> Result<Cow<'a, str>> {
+		if sets.is_empty() {
+			return Ok(s);
+		}
+
+		let mut t: Table = s.parse()?;
+		for set in sets {
+			let Some((path, value)) = set.split_once('=') else {
+				bail!("invalid config override `{set}`, expected `KEY=VALUE`");
+			};
+
+			let (path, value) = (path.trim(), value.trim());
+			let mut new = match format!("v = {value}").parse::<Table>() {
+				Ok(mut v) => v.remove("v").unwrap(),
+				Err(_) => Value::String(value.to_owned()),
+			};
+
+			let mut keys = path.split('.');
+			let mut cur = t.get_mut(keys.next().unwrap_or_default());
+			for key in keys {
+				cur = cur.and_then(|v| v.as_table_mut()).and_then(|t| t.get_mut(key));
+			}
+
+			let Some(old) = cur else {
+				bail!("unknown config key `{path}`");
+			};
+			if let (Value::Float(_), Value::Integer(n)) = (&old, &new) {
+				new = Value::Float(*n as f64);
+			}
+			if old.type_str() != new.type_str() {
+				bail!(
+					"invalid value for config key `{path}`: expected {}, found {}",
+					old.type_str(),
+					new.type_str()
+				);
+			}
+			*old = new;
+		}
+
+		Ok(t.to_string().into())
+	}
+
 	#[inline]
 	pub(crate) fn mix<T, E>(a: T, b: T, c: T) -> impl Iterator<Item = E>
 	where
@@ -49,13 +94,120 @@ impl Preset {
 	}
 
 	#[inline]
-	fn merge_path(user: PathBuf, base: Cow<str>) -> Result<Cow<str>> {
-		let s = std::fs::read_to_string(&user).unwrap_or_default();
-		if s.is_empty() {
+	fn merge_path<'a>(
+		user: PathBuf,
+		base: Cow<'a, str>,
+		profile: Option<&str>,
+	) -> Result<Cow<'a, str>> {
+		let Some(mut t) = Self::read(&user, profile)? else {
 			return Ok(base);
+		};
+
+		Self::merge(&mut t, base.parse()?, 2);
+		Ok(t.to_string().into())
+	}
+
+	/// Reads the user config at `path`, with the files pulled in by its
+	/// `[include]` section layered beneath it, and the `[profile.<name>]`
+	/// section over it, or `None` if it's absent or empty.
+	fn read(path: &Path, profile: Option<&str>) -> Result<Option<Table>> {
+		let mut t = match std::fs::read_to_string(path) {
+			Ok(s) if !s.is_empty() => {
+				let canon = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+				Self::load(path, &s, &mut vec![canon])?
+			}
+			_ => return Ok(None),
+		};
+
+		let Some(Value::Table(mut profiles)) = t.remove("profile") else {
+			return Ok(Some(t));
+		};
+		match profile.and_then(|p| profiles.remove(p)) {
+			Some(Value::Table(mut p)) => {
+				Self::merge(&mut p, t, 2);
+				Ok(Some(p))
+			}
+			_ => Ok(Some(t)),
+		}
+	}
+
+	fn load(path: &Path, s: &str, stack: &mut Vec<PathBuf>) -> Result<Table> {
+		let mut t: Table = s.parse().with_context(|| format!("failed to parse config: {path:?}"))?;
+		let Some(Value::Table(include)) = t.remove("include") else {
+			return Ok(t);
+		};
+
+		let dir = path.parent().unwrap_or(Path::new(""));
+		let patterns = include.get("from").and_then(|v| v.as_array()).map_or(&[][..], |a| a.as_slice());
+
+		let mut base = Table::new();
+		for pat in patterns.iter().filter_map(|v| v.as_str()) {
+			let files = Self::glob(dir, pat);
+			if files.is_empty() {
+				eprintln!("WARNING: No config files match `{pat}`, included in {path:?}");
+			}
+
+			for file in files {
+				let canon = file.canonicalize().unwrap_or_else(|_| file.clone());
+				if stack.contains(&canon) {
+					eprintln!("WARNING: Skipping {file:?} included in {path:?}, as it forms a cycle");
+					continue;
+				}
+
+				let s = std::fs::read_to_string(&file)
+					.with_context(|| format!("failed to read config: {file:?}"))?;
+				stack.push(canon);
+				let t = Self::load(&file, &s, stack)?;
+				stack.pop();
+
+				Self::layer(&mut base, t);
+			}
 		}
 
-		Self::merge_str(&s, &base).with_context(|| format!("failed to parse config: {user:?}"))
+		Self::layer(&mut base, t);
+		Ok(base)
+	}
+
+	/// Expands `pat` relative to `dir`, where only the file name can contain a
+	/// glob, e.g. `keymap.d/*.toml`, sorted by name.
+	fn glob(dir: &Path, pat: &str) -> Vec<PathBuf> {
+		let pat = dir.join(pat);
+		let (Some(parent), Some(name)) = (pat.parent(), pat.file_name().and_then(|n| n.to_str()))
+		else {
+			return vec![];
+		};
+
+		let Ok(matcher) = globset::Glob::new(name).map(|g| g.compile_matcher()) else {
+			return if pat.is_file() { vec![pat] } else { vec![] };
+		};
+		let Ok(it) = std::fs::read_dir(parent) else {
+			return vec![];
+		};
+
+		let mut files: Vec<_> = it
+			.flatten()
+			.filter(|e| matcher.is_match(e.file_name()) && e.path().is_file())
+			.map(|e| e.path())
+			.collect();
+		files.sort_unstable();
+		files
+	}
+
+	/// Puts the user config `b` over another user config `a`, merging the tables
+	/// at any depth, where the `prepend_*` and `append_*` arrays are concatenated
+	/// instead of replaced.
+	fn layer(a: &mut Table, b: Table) {
+		for (k, v) in b {
+			match (a.get_mut(&k), v) {
+				(Some(Value::Table(a)), Value::Table(b)) => Self::layer(a, b),
+				(Some(Value::Array(a)), Value::Array(b))
+					if k.starts_with("prepend_") || k.starts_with("append_") =>
+				{
+					a.extend(b)
+				}
+				(_, v) => _ = a.insert(k, v),
+			}
+		}
 	}
 
 	fn merge(a: &mut Table, b: Table, max: u8) {
@@ -79,3 +231,44 @@ impl Preset {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn layer(a: &str, b: &str) -> Table {
+		let mut a: Table = a.parse().unwrap();
+		Preset::layer(&mut a, b.parse().unwrap());
+		a
+	}
+
+	#[test]
+	fn test_layer() {
+		let t = layer(
+			"[mgr]\nratio = [1, 4, 3]\nprepend_keymap = [1]\n[plugin.a.b]\nc = 1\nd = 2",
+			"[mgr]\nprepend_keymap = [2]\n[plugin.a.b]\nc = 3\ne = 4",
+		);
+		assert_eq!(
+			t,
+			"[mgr]\nratio = [1, 4, 3]\nprepend_keymap = [1, 2]\n[plugin.a.b]\nc = 3\nd = 2\ne = 4"
+				.parse()
+				.unwrap()
+		);
+	}
+
+	#[test]
+	fn test_layer_deep() {
+		let t = layer(
+			"[a.b.c]\nappend_rules = [1]\nx = { y = 1, z = 2 }",
+			"[a.b.c]\nappend_rules = [2]\nx = { y = 3 }\n[a.d]\nw = 5",
+		);
+		assert_eq!(
+			t,
+			"[a.b.c]\nappend_rules = [1, 2]\nx = { y = 3, z = 2 }\n[a.d]\nw = 5".parse().unwrap()
+		);
+
+		// Those of different types are replaced rather than merged
+		let t = layer("[a]\nb = { c = 1 }\nappend_d = 1", "[a]\nb = 2\nappend_d = [2]");
+		assert_eq!(t, "[a]\nb = 2\nappend_d = [2]".parse().unwrap());
+	}
+}
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, path::PathBuf, str::FromStr, time::{SystemTime, UNIX_EPOCH}};
+use std::{borrow::Cow, env, fs, io::{self, Write}, path::{Path, PathBuf}, str::FromStr, sync::OnceLock, time::{SystemTime, UNIX_EPOCH}};
 
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
@@ -27,13 +27,52 @@ pub struct Preview {
 
 	pub ueberzug_scale:  f32,
 	pub ueberzug_offset: (f32, f32, f32, f32),
+
+	pub peek_footer:       bool,
+	pub peek_footer_under: u16,
+
+	#[serde(skip)]
+	fallback: OnceLock<PathBuf>,
 }
 
 impl Preview {
 	#[inline]
 	pub fn tmpfile(&self, prefix: &str) -> PathBuf {
 		let time = SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards");
-		self.cache_dir.join(format!("{prefix}-{}", time.as_nanos() / 1000))
+		self.work_dir().join(format!("{prefix}-{}", time.as_nanos() / 1000))
+	}
+
+	/// Where the caches and temporary files go, which is the `cache_dir`, unless
+	/// [`Self::check_dir`] has found it not writable.
+	#[inline]
+	pub fn work_dir(&self) -> &Path { self.fallback.get().unwrap_or(&self.cache_dir) }
+
+	/// Checks whether the `cache_dir` is writable, and if it's not, falls back to
+	/// a directory under `$TMPDIR`, returning a message saying so. It's done at
+	/// startup and once writing there fails, as it can fill up in the meantime.
+	pub fn check_dir(&self) -> Option<String> {
+		if self.fallback.get().is_some() {
+			return None;
+		}
+
+		let e = Self::probe(&self.cache_dir).err()?;
+		let tmp = env::temp_dir().join("yazi");
+		if let Err(e2) = fs::create_dir_all(&tmp).and_then(|_| Self::probe(&tmp)) {
+			return Some(format!(
+				"Preview cache dir not writable: {e}, nor is the fallback {}: {e2}",
+				tmp.display()
+			));
+		}
+
+		let msg = format!("Preview cache dir not writable: {e}, falling back to {}", tmp.display());
+		self.fallback.set(tmp).ok().map(|_| msg)
+	}
+
+	fn probe(dir: &Path) -> io::Result<()> {
+		let path = dir.join(format!(".probe-{}", std::process::id()));
+		let result = fs::File::create_new(&path).and_then(|mut f| f.write_all(b"yazi"));
+		fs::remove_file(&path).ok();
+		result
 	}
 
 	#[inline]
@@ -72,6 +111,9 @@ impl FromStr for Preview {
 
 			ueberzug_scale:  f32,
 			ueberzug_offset: (f32, f32, f32, f32),
+
+			peek_footer:       bool,
+			peek_footer_under: u16,
 		}
 
 		let preview = toml::from_str::<Outer>(s)?.preview;
@@ -96,6 +138,11 @@ impl FromStr for Preview {
 
 			ueberzug_scale: preview.ueberzug_scale,
 			ueberzug_offset: preview.ueberzug_offset,
+
+			peek_footer: preview.peek_footer,
+			peek_footer_under: preview.peek_footer_under,
+
+			fallback: OnceLock::new(),
 		})
 	}
 }
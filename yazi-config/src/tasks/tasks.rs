@@ -11,11 +11,22 @@ pub struct Tasks {
 	pub macro_workers: u8,
 	#[validate(range(min = 1, message = "Cannot be less than 1"))]
 	pub bizarre_retry: u8,
+	#[validate(range(min = 1, message = "Cannot be less than 1"))]
+	pub size_workers:  u8,
 
 	pub image_alloc: u32,
 	pub image_bound: [u16; 2],
 
 	pub suppress_preload: bool,
+	pub confirm_quit:     bool,
+	pub mime_cache:       usize,
+
+	pub spawn_shell:      String,
+	pub abort_unwritable: bool,
+
+	pub notify_desktop: bool,
+	pub notify_after:   u64,
+	pub preserve_owner: bool,
 }
 
 impl FromStr for Tasks {
@@ -33,3 +44,16 @@ impl FromStr for Tasks {
 		Ok(tasks)
 	}
 }
+
+impl Tasks {
+	/// The command `shell --spawn` runs, the user's login shell by default.
+	pub fn spawn_shell(&self) -> String {
+		if !self.spawn_shell.is_empty() {
+			self.spawn_shell.clone()
+		} else if cfg!(windows) {
+			"cmd".to_owned()
+		} else {
+			r#"exec "${SHELL:-sh}""#.to_owned()
+		}
+	}
+}
@@ -1,13 +1,14 @@
-use std::collections::HashMap;
+use std::{cmp::Reverse, collections::HashMap};
 
 use anyhow::Result;
 use serde::{Deserialize, Deserializer};
-use yazi_shared::{Condition, fs::File, theme::{Color, Icon, Style}};
+use yazi_shared::{Condition, fs::File, theme::{Color, Icon, IconCache, Style}};
 
+use super::Is;
 use crate::{Pattern, Preset};
 
 pub struct Icons {
-	globs: Vec<(Pattern, Icon)>,
+	globs: Vec<(Pattern, Is, Icon)>,
 	dirs:  HashMap<String, Icon>,
 	files: HashMap<String, Icon>,
 	exts:  HashMap<String, Icon>,
@@ -15,6 +16,20 @@ pub struct Icons {
 }
 
 impl Icons {
+	/// The icon of the file, matched only once and then cached in it, as it's
+	/// looked up for each visible file on every render.
+	pub fn cached(&'static self, file: &File) -> Option<&'static Icon> {
+		match file.icon.get() {
+			IconCache::Missing => {
+				let matched = self.matches(file);
+				file.icon.set(matched.map_or(IconCache::Undefined, IconCache::Icon));
+				matched
+			}
+			IconCache::Undefined => None,
+			IconCache::Icon(cached) => Some(cached),
+		}
+	}
+
 	pub fn matches(&self, file: &File) -> Option<&Icon> {
 		if let Some(i) = self.match_by_glob(file) {
 			return Some(i);
@@ -43,7 +58,11 @@ impl Icons {
 
 	#[inline]
 	fn match_by_glob(&self, file: &File) -> Option<&Icon> {
-		self.globs.iter().find(|(p, _)| p.match_path(&file.url, file.is_dir())).map(|(_, i)| i)
+		self
+			.globs
+			.iter()
+			.find(|(p, is, _)| p.match_path(&file.url, file.is_dir()) && is.check(&file.cha))
+			.map(|(_, _, i)| i)
 	}
 
 	#[inline]
@@ -107,6 +126,10 @@ impl<'de> Deserialize<'de> for Icons {
 		#[derive(Deserialize)]
 		pub struct ShadowPat {
 			name:     Pattern,
+			#[serde(default)]
+			is:       Is,
+			#[serde(default)]
+			prio:     i16,
 			text:     String,
 			fg_dark:  Option<Color>,
 			#[allow(dead_code)]
@@ -132,9 +155,16 @@ impl<'de> Deserialize<'de> for Icons {
 
 		let shadow = Shadow::deserialize(deserializer)?;
 
-		let globs = Preset::mix(shadow.globs, shadow.prepend_globs, shadow.append_globs)
+		// Rules with a higher `prio` are tried first, and the order is kept for equal
+		// ones
+		let mut globs: Vec<_> =
+			Preset::mix(shadow.globs, shadow.prepend_globs, shadow.append_globs).collect();
+		globs.sort_by_key(|v| Reverse(v.prio));
+		let globs = globs
+			.into_iter()
 			.map(|v| {
-				(v.name, Icon { text: v.text, style: Style { fg: v.fg_dark, ..Default::default() } })
+				let icon = Icon { text: v.text, style: Style { fg: v.fg_dark, ..Default::default() } };
+				(v.name, v.is, icon)
 			})
 			.collect();
 
@@ -9,6 +9,8 @@ use yazi_shared::fs::Cha;
 pub enum Is {
 	#[default]
 	None,
+	Dir,
+	File,
 	Hidden,
 	Link,
 	Orphan,
@@ -26,6 +28,8 @@ impl FromStr for Is {
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
 		Ok(match s {
+			"dir" => Self::Dir,
+			"file" => Self::File,
 			"hidden" => Self::Hidden,
 			"link" => Self::Link,
 			"orphan" => Self::Orphan,
@@ -52,6 +56,8 @@ impl Is {
 	pub fn check(&self, cha: &Cha) -> bool {
 		match self {
 			Self::None => true,
+			Self::Dir => cha.is_dir(),
+			Self::File => !cha.is_dir(),
 			Self::Hidden => cha.is_hidden(),
 			Self::Link => cha.is_link(),
 			Self::Orphan => cha.is_orphan(),
@@ -1,4 +1,4 @@
-use std::{path::PathBuf, str::FromStr};
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 use validator::Validate;
@@ -13,8 +13,11 @@ pub struct Theme {
 	status:         Status,
 	pub input:      Input,
 	pub pick:       Pick,
+	pub confirm:    Confirm,
 	pub completion: Completion,
 	pub tasks:      Tasks,
+	pub spot:       Spot,
+	pub tag:        Tag,
 	pub which:      Which,
 	pub help:       Help,
 	pub notify:     Notify,
@@ -69,6 +72,12 @@ pub struct Manager {
 	#[validate(range(min = 1, message = "Must be greater than 0"))]
 	tab_width:    u8,
 
+	// Hidden
+	hidden: Style,
+
+	// Error
+	error: Style,
+
 	// Count
 	count_copied:   Style,
 	count_cut:      Style,
@@ -112,6 +121,7 @@ pub struct Input {
 	pub title:    Style,
 	pub value:    Style,
 	pub selected: Style,
+	pub error:    Style,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -121,6 +131,15 @@ pub struct Pick {
 	pub inactive: Style,
 }
 
+#[derive(Deserialize, Serialize)]
+pub struct Confirm {
+	pub border:        Style,
+	pub title:         Style,
+	pub btn_focused:   Style,
+	pub btn_unfocused: Style,
+	pub btn_labels:    [String; 2],
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct Completion {
 	pub border:   Style,
@@ -137,6 +156,20 @@ pub struct Tasks {
 	pub border:  Style,
 	pub title:   Style,
 	pub hovered: Style,
+	pub group:   Style,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Spot {
+	pub border: Style,
+	pub title:  Style,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Tag {
+	pub symbol:  String,
+	pub default: Style,
+	pub styles:  HashMap<String, Style>,
 }
 
 #[derive(Deserialize, Serialize, Validate)]
@@ -1 +1 @@
-yazi_macro::mod_flat!(sorting which);
+yazi_macro::mod_flat!(sorting timeout which);
@@ -0,0 +1,35 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+/// What to do with a key sequence that's still pending once `timeout` elapses.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(try_from = "String")]
+pub enum OnTimeout {
+	/// Drop the keys typed so far, and run the exact match of a sequence as soon
+	/// as it's typed, even if longer ones start with it.
+	#[default]
+	Cancel,
+	/// Wait for the longer sequences, and run the exact match of the keys typed
+	/// so far, if any, once the time is up.
+	Exact,
+}
+
+impl FromStr for OnTimeout {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"cancel" => Self::Cancel,
+			"exact" => Self::Exact,
+			_ => bail!("Invalid `on_timeout` value: {s}"),
+		})
+	}
+}
+
+impl TryFrom<String> for OnTimeout {
+	type Error = anyhow::Error;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> { Self::from_str(&value) }
+}
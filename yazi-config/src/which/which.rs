@@ -3,7 +3,7 @@ use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use super::SortBy;
+use super::{OnTimeout, SortBy};
 
 #[derive(Debug, Deserialize, Serialize, Validate)]
 pub struct Which {
@@ -12,6 +12,14 @@ pub struct Which {
 	pub sort_sensitive: bool,
 	pub sort_reverse:   bool,
 	pub sort_translit:  bool,
+
+	// Timing
+	/// How long to wait for the next key of a sequence, in milliseconds, where
+	/// `0` means waiting indefinitely.
+	pub timeout:     u64,
+	pub on_timeout:  OnTimeout,
+	/// How long to wait before showing the candidates, in milliseconds.
+	pub popup_delay: u64,
 }
 
 impl FromStr for Which {
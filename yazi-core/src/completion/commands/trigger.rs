@@ -15,6 +15,8 @@ const SEPARATOR: char = std::path::MAIN_SEPARATOR;
 struct Opt {
 	word:   String,
 	ticket: usize,
+	// Those given by the caller, rather than the paths under the word
+	cands:  Option<Vec<String>>,
 }
 
 impl From<Cmd> for Opt {
@@ -22,6 +24,7 @@ impl From<Cmd> for Opt {
 		Self {
 			word:   c.take_first_str().unwrap_or_default(),
 			ticket: c.get("ticket").and_then(Data::as_usize).unwrap_or(0),
+			cands:  c.take_any("cands"),
 		}
 	}
 }
@@ -34,6 +37,16 @@ impl Completion {
 		}
 
 		self.ticket = opt.ticket;
+		if let Some(cands) = opt.cands {
+			return self.show(
+				Cmd::new("show")
+					.with_any("cache", cands)
+					.with("cache-name", "\0cands")
+					.with("word", opt.word)
+					.with("ticket", opt.ticket),
+			);
+		}
+
 		let Some((parent, child)) = Self::split_path(&opt.word) else {
 			return self.close(false);
 		};
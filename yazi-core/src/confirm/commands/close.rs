@@ -4,21 +4,22 @@ use yazi_shared::event::Cmd;
 use crate::confirm::Confirm;
 
 struct Opt {
-	submit: bool,
+	submit:  bool,
+	focused: bool,
 }
 
 impl From<Cmd> for Opt {
-	fn from(c: Cmd) -> Self { Self { submit: c.bool("submit") } }
+	fn from(c: Cmd) -> Self { Self { submit: c.bool("submit"), focused: c.bool("focused") } }
 }
 impl From<bool> for Opt {
-	fn from(submit: bool) -> Self { Self { submit } }
+	fn from(submit: bool) -> Self { Self { submit, focused: false } }
 }
 
 impl Confirm {
 	#[yazi_codegen::command]
 	pub fn close(&mut self, opt: Opt) {
 		if let Some(cb) = self.callback.take() {
-			_ = cb.send(opt.submit);
+			_ = cb.send(if opt.focused { self.yes } else { opt.submit });
 		}
 
 		self.visible = false;
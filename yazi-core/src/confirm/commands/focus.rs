@@ -0,0 +1,30 @@
+use yazi_macro::render;
+use yazi_shared::event::{Cmd, Data};
+
+use crate::confirm::Confirm;
+
+struct Opt {
+	yes: Option<bool>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self {
+		Self {
+			yes: match c.first().and_then(Data::as_str) {
+				Some("yes") => Some(true),
+				Some("no") => Some(false),
+				_ => None,
+			},
+		}
+	}
+}
+
+impl Confirm {
+	/// Focuses the "Yes" or "No" button, or the other one if neither is given.
+	#[yazi_codegen::command]
+	pub fn focus(&mut self, opt: Opt) {
+		let old = self.yes;
+		self.yes = opt.yes.unwrap_or(!self.yes);
+		render!(old != self.yes);
+	}
+}
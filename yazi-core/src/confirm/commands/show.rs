@@ -31,6 +31,7 @@ impl Confirm {
 
 		self.offset = 0;
 		self.position = opt.cfg.position;
+		self.yes = opt.cfg.default;
 
 		self.callback = Some(opt.tx);
 		self.visible = true;
@@ -10,6 +10,8 @@ pub struct Confirm {
 
 	pub offset:   usize,
 	pub position: Position,
+	/// Whether the "Yes" button is focused, otherwise "No".
+	pub yes:      bool,
 
 	pub(super) callback: Option<Sender<bool>>,
 	pub visible:         bool,
@@ -17,6 +17,13 @@ impl From<bool> for Opt {
 impl Input {
 	#[yazi_codegen::command]
 	pub fn backspace(&mut self, opt: Opt) {
+		if self.take_preselected() {
+			self.snap_mut().value.clear();
+			self.move_(0);
+			self.flush_value();
+			return render!();
+		}
+
 		let snap = self.snaps.current_mut();
 		if !opt.under && snap.cursor < 1 {
 			return;
@@ -18,6 +18,16 @@ impl From<bool> for Opt {
 impl Input {
 	#[yazi_codegen::command]
 	pub fn close(&mut self, opt: Opt) {
+		// Submitted once the typed value is validated, see `Input::error()`
+		if opt.submit && self.validate && self.validated != self.snap().value {
+			self.submitting = true;
+			return;
+		}
+
+		if opt.submit && self.error.is_some() {
+			return;
+		}
+
 		if self.completion {
 			CompletionProxy::close();
 		}
@@ -1,4 +1,4 @@
-use std::path::MAIN_SEPARATOR_STR;
+use std::path::{MAIN_SEPARATOR_STR, Path};
 
 use yazi_macro::render;
 use yazi_shared::event::{Cmd, Data};
@@ -32,8 +32,11 @@ impl Input {
 			return;
 		}
 
+		// An absolute path takes the place of everything before the cursor
 		let [before, after] = self.partition();
-		let new = if let Some((prefix, _)) = before.rsplit_once(SEPARATOR) {
+		let new = if Path::new(&opt.word).is_absolute() {
+			format!("{}{after}", opt.word)
+		} else if let Some((prefix, _)) = before.rsplit_once(SEPARATOR) {
 			format!("{prefix}/{}{after}", opt.word).replace(SEPARATOR, MAIN_SEPARATOR_STR)
 		} else {
 			format!("{}{after}", opt.word).replace(SEPARATOR, MAIN_SEPARATOR_STR)
@@ -0,0 +1,40 @@
+use std::mem;
+
+use yazi_macro::render;
+use yazi_shared::event::Cmd;
+
+use crate::input::Input;
+
+struct Opt {
+	value:   String,
+	message: Option<String>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		Self {
+			value:   c.take_str("value").unwrap_or_default(),
+			message: c.take_first_str().filter(|s| !s.is_empty()),
+		}
+	}
+}
+
+impl Input {
+	#[yazi_codegen::command]
+	pub fn error(&mut self, opt: Opt) {
+		// The result of a value that has been typed over since, a newer one follows
+		if !self.visible || opt.value != self.snap().value {
+			return;
+		}
+
+		self.validated = opt.value;
+		if self.error != opt.message {
+			self.error = opt.message;
+			render!();
+		}
+
+		if mem::take(&mut self.submitting) {
+			self.close(true);
+		}
+	}
+}
@@ -24,6 +24,8 @@ impl From<isize> for Opt {
 impl Input {
 	#[yazi_codegen::command]
 	pub fn move_(&mut self, opt: Opt) {
+		self.take_preselected();
+
 		let snap = self.snap();
 		if opt.in_operating && snap.op == InputOp::None {
 			return;
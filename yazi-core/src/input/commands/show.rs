@@ -3,7 +3,7 @@ use yazi_config::popup::InputCfg;
 use yazi_macro::render;
 use yazi_shared::{errors::InputError, event::Cmd};
 
-use crate::input::Input;
+use crate::input::{Input, op::InputOp};
 
 pub struct Opt {
 	cfg: InputCfg,
@@ -31,11 +31,16 @@ impl Input {
 		self.callback = Some(opt.tx);
 		self.realtime = opt.cfg.realtime;
 		self.completion = opt.cfg.completion;
+		self.obscure = opt.cfg.obscure;
+		self.error = None;
+		self.validate = opt.cfg.validate;
+		self.submitting = false;
 
 		// Shell
 		self.highlight = opt.cfg.highlight;
 
 		// Reset snaps
+		self.validated = opt.cfg.value.clone();
 		self.snaps.reset(opt.cfg.value, self.limit());
 
 		// Set cursor after reset
@@ -44,6 +49,17 @@ impl Input {
 			self.move_(0);
 		}
 
+		// Pre-select the default value, which is replaced once the user types
+		if opt.cfg.selected && !self.snap().value.is_empty() {
+			let snap = self.snap_mut();
+			snap.cursor = snap.count();
+			self.move_(0);
+			self.snap_mut().op = InputOp::Select(0);
+		}
+
+		if opt.cfg.eager {
+			self.flush_value();
+		}
 		render!();
 	}
 }
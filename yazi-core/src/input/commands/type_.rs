@@ -3,10 +3,12 @@ use yazi_shared::event::Cmd;
 
 use crate::input::{Input, InputMode};
 
-struct Opt;
+struct Opt {
+	text: String,
+}
 
 impl From<Cmd> for Opt {
-	fn from(_: Cmd) -> Self { Self }
+	fn from(mut c: Cmd) -> Self { Self { text: c.take_first_str().unwrap_or_default() } }
 }
 
 impl Input {
@@ -22,4 +24,13 @@ impl Input {
 
 		false
 	}
+
+	/// Types the text given as if it's typed with the keys, e.g. the text typed
+	/// in a macro being replayed.
+	#[yazi_codegen::command]
+	pub fn type_text(&mut self, opt: Opt) {
+		if !opt.text.is_empty() {
+			self.type_str(&opt.text);
+		}
+	}
 }
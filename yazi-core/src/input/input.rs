@@ -22,6 +22,12 @@ pub struct Input {
 	pub(super) callback:   Option<UnboundedSender<Result<String, InputError>>>,
 	pub(super) realtime:   bool,
 	pub(super) completion: bool,
+	pub obscure:           bool,
+	pub error:             Option<String>,
+	// Validation
+	pub(super) validate:   bool,
+	pub(super) validated:  String,
+	pub(super) submitting: bool,
 
 	// Shell
 	pub highlight: bool,
@@ -34,6 +40,10 @@ impl Input {
 	}
 
 	pub fn type_str(&mut self, s: &str) {
+		if self.take_preselected() {
+			self.snap_mut().value.clear();
+		}
+
 		let snap = self.snaps.current_mut();
 		if snap.cursor < 1 {
 			snap.value.insert_str(0, s);
@@ -59,7 +69,7 @@ impl Input {
 				let Range { start, end } = snap.idx(range.start)..snap.idx(range.end);
 
 				let drain = snap.value.drain(start.unwrap()..end.unwrap()).collect::<String>();
-				if cut {
+				if cut && !self.obscure {
 					futures::executor::block_on(CLIPBOARD.set(&drain));
 				}
 
@@ -73,7 +83,9 @@ impl Input {
 				let yanked = &snap.value[start.unwrap()..end.unwrap()];
 
 				snap.op = InputOp::None;
-				futures::executor::block_on(CLIPBOARD.set(yanked));
+				if !self.obscure {
+					futures::executor::block_on(CLIPBOARD.set(yanked));
+				}
 			}
 		};
 
@@ -87,15 +99,31 @@ impl Input {
 		true
 	}
 
+	/// Drops the selection made by `selected` in [`InputCfg`], returning whether
+	/// there was one, so that typing in insert mode replaces the default value.
+	///
+	/// [`InputCfg`]: yazi_config::popup::InputCfg
+	pub(super) fn take_preselected(&mut self) -> bool {
+		let snap = self.snap_mut();
+		if snap.mode != InputMode::Insert || !matches!(snap.op, InputOp::Select(_)) {
+			return false;
+		}
+
+		snap.op = InputOp::None;
+		true
+	}
+
 	pub(super) fn flush_value(&mut self) {
 		let Some(tx) = &self.callback else { return };
 		self.ticket = self.ticket.wrapping_add(1);
 
 		if self.realtime {
-			let value = self.snap().value.clone();
-			tx.send(Err(InputError::Typed(value))).ok();
+			tx.send(Err(InputError::Typed(self.snap().value.clone()))).ok();
 		}
 
+		// Typing again holds off the submitting that's waiting for the validation
+		self.submitting = false;
+
 		if self.completion {
 			let before = self.partition()[0].to_owned();
 			tx.send(Err(InputError::Completed(before, self.ticket))).ok();
@@ -107,13 +135,18 @@ impl Input {
 	#[inline]
 	pub fn value(&self) -> &str { self.snap().slice(self.snap().window(self.limit())) }
 
+	#[inline]
+	fn width(&self, s: &str) -> u16 {
+		if self.obscure { s.chars().count() as u16 } else { s.width() as u16 }
+	}
+
 	#[inline]
 	pub fn mode(&self) -> InputMode { self.snap().mode }
 
 	#[inline]
 	pub fn cursor(&self) -> u16 {
 		let snap = self.snap();
-		snap.slice(snap.offset..snap.cursor).width() as u16
+		self.width(snap.slice(snap.offset..snap.cursor))
 	}
 
 	pub fn selected(&self) -> Option<Range<u16>> {
@@ -126,8 +159,8 @@ impl Input {
 		let win = snap.window(self.limit());
 		let Range { start, end } = start.max(win.start)..end.min(win.end);
 
-		let s = snap.slice(snap.offset..start).width() as u16;
-		Some(s..s + snap.slice(start..end).width() as u16)
+		let s = self.width(snap.slice(snap.offset..start));
+		Some(s..s + self.width(snap.slice(start..end)))
 	}
 
 	#[inline]
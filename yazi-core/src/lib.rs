@@ -6,9 +6,16 @@
 	clippy::unit_arg
 )]
 
-yazi_macro::mod_pub!(completion confirm help input manager notify pick tab tasks which);
+yazi_macro::mod_pub!(completion confirm help input manager notify pick recorder spot tab tasks which);
 
 pub fn init() {
 	manager::WATCHED.with(<_>::default);
 	manager::LINKED.with(<_>::default);
+	manager::MIME_CACHE.with(<_>::default);
+	manager::DIR_VIEWS.init(parking_lot::Mutex::new(manager::DirViews::load()));
+	manager::RECENT_DIRS.init(parking_lot::Mutex::new(manager::RecentDirs::load()));
+	yazi_fs::TAGS.with(<_>::default);
+
+	tokio::spawn(manager::MimeCache::load());
+	tokio::spawn(yazi_fs::Tags::load());
 }
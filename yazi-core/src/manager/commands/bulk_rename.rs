@@ -1,17 +1,34 @@
-use std::{borrow::Cow, collections::HashMap, ffi::{OsStr, OsString}, io::{BufWriter, Write, stderr}, path::PathBuf};
+use std::{borrow::Cow, collections::HashMap, ffi::{OsStr, OsString}, io::{BufWriter, Write, stderr}, path::{Path, PathBuf}};
 
 use anyhow::{Result, anyhow};
 use scopeguard::defer;
-use tokio::{fs::{self, OpenOptions}, io::{AsyncReadExt, AsyncWriteExt, stdin}};
-use yazi_config::{OPEN, PREVIEW};
-use yazi_dds::Pubsub;
-use yazi_proxy::{AppProxy, HIDER, TasksProxy, WATCHER};
-use yazi_shared::{fs::{File, FilesOp, Url, max_common_root, maybe_exists, paths_to_same_file}, terminal_clear};
+use tokio::{fs::{self, OpenOptions}, io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, stdin}};
+use yazi_config::{MANAGER, OPEN, PREVIEW, TASKS, popup::InputCfg};
+use yazi_dds::{Pubsub, body::BodyBulk};
+use yazi_fs::{TAGS, Tags, Unwritable};
+use yazi_proxy::{AppProxy, HIDER, InputProxy, ManagerProxy, TasksProxy, WATCHER};
+use yazi_shared::{fs::{File, FilesOp, Url, escape_non_utf8, ext_changed, long, max_common_root, maybe_exists, must_be_dir, paths_from_lines, paths_to_same_file}, terminal_clear};
 
-use crate::manager::Manager;
+use crate::manager::{Manager, RenameTemplate};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RenameStep {
+	Rename(usize),
+	ToTmp(usize),
+	FromTmp(usize),
+}
+
+#[derive(Default)]
+struct BulkRenamed {
+	failed:    Vec<(PathBuf, PathBuf, anyhow::Error)>,
+	succeeded: HashMap<Url, File>,
+	// Each rename done, including those to and from temporary names, in order
+	steps:     Vec<(Url, Url)>,
+	aborted:   Option<Unwritable>,
+}
 
 impl Manager {
-	pub(super) fn bulk_rename(&self) {
+	pub(super) fn bulk_rename(&self, yes: bool) {
 		let Some(opener) = OPEN.block_opener("bulk-rename.txt", "text/plain") else {
 			return AppProxy::notify_warn("Bulk rename", "No text opener found");
 		};
@@ -22,32 +39,110 @@ impl Manager {
 		let old: Vec<_> = old.into_iter().map(|p| p.strip_prefix(&root).unwrap().to_owned()).collect();
 
 		tokio::spawn(async move {
-			let tmp = PREVIEW.tmpfile("bulk");
 			let s = old.iter().map(|o| o.as_os_str()).collect::<Vec<_>>().join(OsStr::new("\n"));
-			OpenOptions::new()
-				.write(true)
-				.create_new(true)
-				.open(&tmp)
-				.await?
-				.write_all(s.as_encoded_bytes())
-				.await?;
+			let tmp = match Self::bulk_rename_tmp(s.as_encoded_bytes()).await {
+				Ok(p) => p,
+				Err(e) => {
+					let e = format!("Failed to create the temporary file: {e}");
+					return AppProxy::notify_error("Bulk rename", e);
+				}
+			};
 
 			defer! { tokio::spawn(fs::remove_file(tmp.clone())); }
-			TasksProxy::process_exec(vec![OsString::new(), tmp.to_owned().into()], Cow::Borrowed(opener))
-				.await;
+			let args = vec![OsString::new(), tmp.to_owned().into()];
+			TasksProxy::process_exec(root.clone(), args, Cow::Borrowed(opener)).await;
+
+			let _permit = HIDER.acquire().await.unwrap();
+			defer!(AppProxy::resume());
+			AppProxy::stop().await;
+
+			let result = match fs::read(&tmp).await {
+				Ok(b) => Self::bulk_rename_do(root, old, paths_from_lines(&b), yes).await,
+				Err(e) => Err(e.into()),
+			};
+			if let Err(e) = result {
+				AppProxy::notify_error("Bulk rename", e);
+			}
+			ManagerProxy::reload();
+		});
+	}
+
+	// Writes the names to edit to a new temporary file, in the fallback directory
+	// if the cache directory turns out not writable
+	async fn bulk_rename_tmp(s: &[u8]) -> Result<PathBuf> {
+		async fn write(path: &Path, s: &[u8]) -> std::io::Result<()> {
+			OpenOptions::new().write(true).create_new(true).open(path).await?.write_all(s).await
+		}
+
+		let tmp = PREVIEW.tmpfile("bulk");
+		let Err(e) = write(&tmp, s).await else { return Ok(tmp) };
+		let Some(msg) = PREVIEW.check_dir() else { return Err(e.into()) };
+		AppProxy::notify_warn("Preview", msg);
+
+		let tmp = PREVIEW.tmpfile("bulk");
+		write(&tmp, s).await?;
+		Ok(tmp)
+	}
+
+	// Renames the selected files by a template, instead of editing them one by one
+	pub(super) fn rename_selected(&self, yes: bool) {
+		let old: Vec<_> = self.selected_or_hovered(true).cloned().collect();
+		if let Some(u) = old.iter().find(|u| u.name().to_str().is_none()) {
+			return AppProxy::notify_warn(
+				"Rename",
+				format!("`{}` isn't valid UTF-8, use bulk rename instead", escape_non_utf8(u.name())),
+			);
+		}
+
+		tokio::spawn(async move {
+			let mut result = InputProxy::show(InputCfg::template(old.len()));
+			let Some(Ok(s)) = result.recv().await else {
+				return;
+			};
+
+			let template = match s.parse::<RenameTemplate>() {
+				Ok(t) => t,
+				Err(e) => return AppProxy::notify_warn("Rename", e),
+			};
+
+			let root = max_common_root(&old);
+			let (mut olds, mut news) = (Vec::with_capacity(old.len()), Vec::with_capacity(old.len()));
+			for (i, u) in old.iter().enumerate() {
+				let mtime = fs::symlink_metadata(u).await.ok().and_then(|m| m.modified().ok());
+				let name = template.apply(i, u, mtime);
+				if name.is_empty() {
+					return AppProxy::notify_warn("Rename", "The template results in an empty name");
+				}
+
+				let o = u.strip_prefix(&root).unwrap();
+				news.push(o.with_file_name(name));
+				olds.push(o.to_owned());
+			}
 
 			let _permit = HIDER.acquire().await.unwrap();
 			defer!(AppProxy::resume());
 			AppProxy::stop().await;
 
-			let new: Vec<_> = fs::read_to_string(&tmp).await?.lines().map(PathBuf::from).collect();
-			Self::bulk_rename_do(root, old, new).await
+			if let Err(e) = Self::bulk_rename_do(root, olds, news, yes).await {
+				AppProxy::notify_error("Rename", e);
+			}
+			ManagerProxy::reload();
 		});
 	}
 
-	async fn bulk_rename_do(root: PathBuf, old: Vec<PathBuf>, new: Vec<PathBuf>) -> Result<()> {
+	// With `yes`, the renames are done without asking, and those that would ask
+	// otherwise fail instead, such as the protected ones
+	async fn bulk_rename_do(
+		root: PathBuf,
+		old: Vec<PathBuf>,
+		new: Vec<PathBuf>,
+		yes: bool,
+	) -> Result<()> {
 		terminal_clear(&mut stderr())?;
 		if old.len() != new.len() {
+			if yes {
+				return Err(anyhow!("Number of old and new differ"));
+			}
 			eprintln!("Number of old and new differ, press ENTER to exit");
 			stdin().read_exact(&mut [0]).await?;
 			return Ok(());
@@ -58,57 +153,250 @@ impl Manager {
 			return Ok(());
 		}
 
-		{
-			let mut stderr = BufWriter::new(stderr().lock());
-			for (o, n) in &todo {
-				writeln!(stderr, "{} -> {}", o.display(), n.display())?;
-			}
-			write!(stderr, "Continue to rename? (y/N): ")?;
-			stderr.flush()?;
+		// Those changing the extension of a file are marked
+		let mut changed = Vec::with_capacity(todo.len());
+		for (o, n) in &todo {
+			changed.push(ext_changed(o, n) && !must_be_dir(root.join(o)).await);
 		}
 
-		let mut buf = [0; 10];
-		_ = stdin().read(&mut buf).await?;
-		if buf[0] != b'y' && buf[0] != b'Y' {
-			return Ok(());
+		if !yes {
+			{
+				let mut stderr = BufWriter::new(stderr().lock());
+				for ((o, n), &c) in todo.iter().zip(&changed) {
+					let mark = if c { "  [extension changed]" } else { "" };
+					writeln!(
+						stderr,
+						"{} -> {}{mark}",
+						escape_non_utf8(o.as_os_str()),
+						escape_non_utf8(n.as_os_str())
+					)?;
+				}
+				write!(stderr, "Continue to rename? (y/N): ")?;
+				stderr.flush()?;
+			}
+
+			if !Self::bulk_rename_yes().await? {
+				return Ok(());
+			}
+
+			let len = changed.iter().filter(|&&c| c).count();
+			if MANAGER.confirm_ext_change && len > 0 {
+				{
+					let mut stderr = BufWriter::new(stderr().lock());
+					let s = if len > 1 { "s" } else { "" };
+					writeln!(stderr, "\nThe extension of {len} file{s} will change:")?;
+					for ((o, n), _) in todo.iter().zip(&changed).filter(|(_, c)| **c) {
+						writeln!(
+							stderr,
+							"{} -> {}",
+							escape_non_utf8(o.as_os_str()),
+							escape_non_utf8(n.as_os_str())
+						)?;
+					}
+					write!(stderr, "Continue anyway? (y/N): ")?;
+					stderr.flush()?;
+				}
+
+				if !Self::bulk_rename_yes().await? {
+					return Ok(());
+				}
+			}
 		}
 
-		let permit = WATCHER.acquire().await.unwrap();
-		let (mut failed, mut succeeded) = (Vec::new(), HashMap::with_capacity(todo.len()));
-		for (o, n) in todo {
-			let (old, new) = (root.join(&o), root.join(&n));
+		// Renaming protected files away has to be confirmed by typing their names as
+		// well
+		let old: Vec<_> = todo.iter().map(|(o, _)| Url::from(root.join(o))).collect();
+		for path in Self::protected(old.iter()) {
+			if yes {
+				return Err(anyhow!(
+					"`{}` is protected, which can't be renamed with `--yes`",
+					escape_non_utf8(path.as_os_str())
+				));
+			}
 
-			if maybe_exists(&new).await && !paths_to_same_file(&old, &new).await {
-				failed.push((o, n, anyhow!("Destination already exists")));
-			} else if let Err(e) = fs::rename(&old, &new).await {
-				failed.push((o, n, e.into()));
-			} else if let Ok(f) = File::from(new.into()).await {
-				succeeded.insert(Url::from(old), f);
-			} else {
-				failed.push((o, n, anyhow!("Failed to retrieve file info")));
+			let name = escape_non_utf8(path.file_name().unwrap_or(path.as_os_str()));
+			{
+				let mut stderr = stderr().lock();
+				write!(
+					stderr,
+					"`{}` is protected, type \"{name}\" to proceed: ",
+					escape_non_utf8(path.as_os_str())
+				)?;
+				stderr.flush()?;
+			}
+
+			let mut line = String::new();
+			BufReader::new(stdin()).read_line(&mut line).await?;
+			if line.trim_end_matches(['\r', '\n']) != name {
+				return Ok(());
 			}
 		}
 
+		let changes: Vec<(Url, Url)> =
+			todo.iter().map(|(o, n)| (root.join(o).into(), root.join(n).into())).collect();
+		if !Pubsub::pre(BodyBulk::owned(&changes.iter().map(|(o, n)| (o, n)).collect())).await {
+			return Ok(());
+		}
+
+		let permit = WATCHER.acquire().await.unwrap();
+		let total = todo.len();
+		let BulkRenamed { failed, succeeded, steps, aborted } =
+			Self::bulk_rename_exec(&root, todo).await;
+
+		let skipped = total - failed.len() - succeeded.len();
 		if !succeeded.is_empty() {
+			// In the order they're done, as a cycle of them is broken up by temporary names
+			Tags::resolve(steps.iter().flat_map(|(o, n)| [o, n])).await;
+			let mut tags = TAGS.write();
+			steps.iter().for_each(|(o, n)| tags.rename(o, n));
+			drop(tags);
+
 			Pubsub::pub_from_bulk(succeeded.iter().map(|(o, n)| (o, &n.url)).collect());
 			FilesOp::rename(succeeded);
 		}
 		drop(permit);
 
-		if !failed.is_empty() {
-			Self::output_failed(failed).await?;
+		if !failed.is_empty() && yes {
+			let (o, _, e) = &failed[0];
+			let more =
+				if failed.len() > 1 { format!(", and {} more", failed.len() - 1) } else { String::new() };
+			return Err(anyhow!("Failed to rename `{}`: {e}{more}", escape_non_utf8(o.as_os_str())));
+		} else if !failed.is_empty() {
+			let aborted = aborted.map(|u| format!("Aborted as {u}, {skipped} more skipped"));
+			Self::output_failed(failed, aborted).await?;
 		}
 		Ok(())
 	}
 
-	async fn output_failed(failed: Vec<(PathBuf, PathBuf, anyhow::Error)>) -> Result<()> {
+	// Renames each of `todo` under `root`, in the order of `bulk_rename_plan()`
+	async fn bulk_rename_exec(root: &Path, todo: Vec<(PathBuf, PathBuf)>) -> BulkRenamed {
+		let mut done =
+			BulkRenamed { succeeded: HashMap::with_capacity(todo.len()), ..Default::default() };
+		let mut tmps = HashMap::new();
+		for step in Self::bulk_rename_plan(&todo) {
+			let (i, old, new) = match step {
+				RenameStep::Rename(i) => (i, root.join(&todo[i].0), root.join(&todo[i].1)),
+				RenameStep::ToTmp(i) => {
+					let old = root.join(&todo[i].0);
+					let tmp = Self::bulk_rename_tmpname(&old).await;
+					(i, old, tmp)
+				}
+				RenameStep::FromTmp(i) => {
+					let Some(tmp) = tmps.remove(&i) else { continue };
+					(i, tmp, root.join(&todo[i].1))
+				}
+			};
+
+			let (o, n) = (todo[i].0.clone(), todo[i].1.clone());
+			let left = |e: anyhow::Error| match step {
+				RenameStep::FromTmp(_) => anyhow!("{e}, left as `{}`", escape_non_utf8(old.as_os_str())),
+				_ => e,
+			};
+
+			if maybe_exists(&new).await && !paths_to_same_file(&old, &new).await {
+				done.failed.push((o, n, left(anyhow!("Destination already exists"))));
+			} else if let Err(e) = long::rename(&old, &new).await {
+				// The rest would run into the same, so they're not tried one by one
+				let dir = new.parent().unwrap_or(&new);
+				if TASKS.abort_unwritable {
+					done.aborted = Unwritable::of(&e, dir).await;
+				}
+				done.failed.push((o, n, left(e.into())));
+				if done.aborted.is_some() {
+					break;
+				}
+			} else if let RenameStep::ToTmp(_) = step {
+				done.steps.push((old.into(), new.clone().into()));
+				tmps.insert(i, new);
+			} else if let Ok(f) = File::from(new.clone().into()).await {
+				done.steps.push((old.into(), new.into()));
+				done.succeeded.insert(Url::from(root.join(o)), f);
+			} else {
+				done.steps.push((old.into(), new.into()));
+				done.failed.push((o, n, anyhow!("Failed to retrieve file info")));
+			}
+		}
+		done
+	}
+
+	// The order to rename `todo` in, so that none is renamed onto a file that's
+	// yet to be renamed away, e.g. `b -> c` before `a -> b`. A cycle of them, such
+	// as `a -> b` and `b -> a`, is broken by renaming one to a temporary name
+	// first, and to its new name once the others are done.
+	//
+	// Each wants at most one other out of the way, so the cycles are the strongly
+	// connected components, found by following the chains.
+	fn bulk_rename_plan(todo: &[(PathBuf, PathBuf)]) -> Vec<RenameStep> {
+		let index: HashMap<_, _> =
+			todo.iter().enumerate().map(|(i, (o, _))| (o.as_path(), i)).collect();
+
+		// 0 for not visited yet, 1 for on the current chain, 2 for planned
+		let (mut steps, mut state) = (Vec::with_capacity(todo.len() + 2), vec![0u8; todo.len()]);
+		for start in 0..todo.len() {
+			let (mut chain, mut next) = (vec![], Some(start));
+			while let Some(i) = next.filter(|&i| state[i] == 0) {
+				state[i] = 1;
+				chain.push(i);
+				next = index.get(todo[i].1.as_path()).copied();
+			}
+
+			// Those leading into a cycle want the same name as one in it, so they'd fail
+			let split = next.filter(|&i| state[i] == 1).and_then(|i| chain.iter().position(|&j| j == i));
+			let (tail, cycle) = chain.split_at(split.unwrap_or(chain.len()));
+			if let Some((&first, rest)) = cycle.split_first() {
+				steps.push(RenameStep::ToTmp(first));
+				steps.extend(rest.iter().rev().map(|&i| RenameStep::Rename(i)));
+				steps.push(RenameStep::FromTmp(first));
+			}
+			steps.extend(tail.iter().rev().map(|&i| RenameStep::Rename(i)));
+
+			chain.into_iter().for_each(|i| state[i] = 2);
+		}
+		steps
+	}
+
+	// A name next to `path` that's not taken, to hold it while breaking a cycle
+	async fn bulk_rename_tmpname(path: &Path) -> PathBuf {
+		let name = path.file_name().unwrap_or_default();
+		let mut n = 0;
+		loop {
+			let mut s = OsString::from(".");
+			s.push(name);
+			s.push(format!(".yazi-{}-{n}", std::process::id()));
+
+			let tmp = path.with_file_name(s);
+			if !maybe_exists(&tmp).await {
+				return tmp;
+			}
+			n += 1;
+		}
+	}
+
+	async fn bulk_rename_yes() -> Result<bool> {
+		let mut buf = [0; 10];
+		_ = stdin().read(&mut buf).await?;
+		Ok(buf[0] == b'y' || buf[0] == b'Y')
+	}
+
+	async fn output_failed(
+		failed: Vec<(PathBuf, PathBuf, anyhow::Error)>,
+		aborted: Option<String>,
+	) -> Result<()> {
 		terminal_clear(&mut stderr())?;
 
 		{
 			let mut stderr = BufWriter::new(stderr().lock());
 			writeln!(stderr, "Failed to rename:")?;
 			for (o, n, e) in failed {
-				writeln!(stderr, "{} -> {}: {e}", o.display(), n.display())?;
+				writeln!(
+					stderr,
+					"{} -> {}: {e}",
+					escape_non_utf8(o.as_os_str()),
+					escape_non_utf8(n.as_os_str())
+				)?;
+			}
+			if let Some(s) = aborted {
+				writeln!(stderr, "\n{s}")?;
 			}
 			writeln!(stderr, "\nPress ENTER to exit")?;
 			stderr.flush()?;
@@ -118,3 +406,88 @@ impl Manager {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn todo(pairs: &[(&str, &str)]) -> Vec<(PathBuf, PathBuf)> {
+		pairs.iter().map(|&(o, n)| (PathBuf::from(o), PathBuf::from(n))).collect()
+	}
+
+	#[test]
+	fn test_plan() {
+		use RenameStep::*;
+
+		// Chains go from the end
+		let plan = Manager::bulk_rename_plan(&todo(&[("a", "b"), ("b", "c"), ("x", "y")]));
+		assert_eq!(plan, [Rename(1), Rename(0), Rename(2)]);
+
+		// Cycles go through a temporary name
+		let plan = Manager::bulk_rename_plan(&todo(&[("a", "b"), ("b", "a")]));
+		assert_eq!(plan, [ToTmp(0), Rename(1), FromTmp(0)]);
+
+		let plan = Manager::bulk_rename_plan(&todo(&[("c", "a"), ("a", "b"), ("b", "c"), ("d", "c")]));
+		assert_eq!(plan, [ToTmp(0), Rename(2), Rename(1), FromTmp(0), Rename(3)]);
+	}
+
+	#[tokio::test]
+	async fn test_exec() {
+		let root = std::env::temp_dir().join(format!("yazi-bulk-rename-{}", std::process::id()));
+		fs::remove_dir_all(&root).await.ok();
+		fs::create_dir_all(&root).await.unwrap();
+		for name in ["a", "b", "c", "x", "y"] {
+			fs::write(root.join(name), name).await.unwrap();
+		}
+
+		let done = Manager::bulk_rename_exec(
+			&root,
+			todo(&[("a", "b"), ("b", "c"), ("c", "a"), ("x", "y"), ("y", "z")]),
+		)
+		.await;
+		assert!(done.failed.is_empty());
+		assert_eq!(done.succeeded.len(), 5);
+		assert_eq!(done.steps.len(), 6);
+
+		for (name, content) in [("a", "c"), ("b", "a"), ("c", "b"), ("y", "x"), ("z", "y")] {
+			assert_eq!(fs::read_to_string(root.join(name)).await.unwrap(), content);
+		}
+		let mut it = fs::read_dir(&root).await.unwrap();
+		let mut names = vec![];
+		while let Some(entry) = it.next_entry().await.unwrap() {
+			names.push(entry.file_name().into_string().unwrap());
+		}
+		names.sort();
+		assert_eq!(names, ["a", "b", "c", "y", "z"]);
+
+		fs::remove_dir_all(&root).await.unwrap();
+	}
+
+	#[cfg(windows)]
+	#[tokio::test]
+	async fn test_exec_long_path() {
+		use yazi_shared::fs::{long, must_exists};
+
+		// Nested beyond `MAX_PATH`, where renaming failed before
+		let root = std::env::temp_dir().join(format!("yazi-bulk-rename-long-{}", std::process::id()));
+		let deep = (0..20).fold(root.clone(), |p, i| p.join(format!("{i:02}-{}", "x".repeat(12))));
+		assert!(deep.as_os_str().len() > 260);
+
+		long::remove_dir_all(&root).await.ok();
+		long::create_dir_all(&deep).await.unwrap();
+		for name in ["a", "b", "c"] {
+			long::write(deep.join(name), name).await.unwrap();
+		}
+
+		let done = Manager::bulk_rename_exec(&deep, todo(&[("a", "b"), ("b", "a"), ("c", "d")])).await;
+		assert!(done.failed.is_empty());
+		assert_eq!(done.succeeded.len(), 3);
+
+		for name in ["a", "b", "d"] {
+			assert!(must_exists(deep.join(name)).await);
+		}
+		assert!(!maybe_exists(deep.join("c")).await);
+
+		long::remove_dir_all(&root).await.unwrap();
+	}
+}
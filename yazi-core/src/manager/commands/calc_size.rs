@@ -0,0 +1,26 @@
+use std::collections::HashSet;
+
+use yazi_shared::event::Cmd;
+
+use crate::{manager::Manager, tasks::Tasks};
+
+struct Opt {
+	all: bool,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self { Self { all: c.bool("all") } }
+}
+
+impl Manager {
+	#[yazi_codegen::command]
+	pub fn calc_size(&mut self, opt: Opt, tasks: &Tasks) {
+		let files = &self.current().files;
+		if opt.all {
+			return tasks.prework_size(files, files.iter());
+		}
+
+		let targets: HashSet<_> = self.selected_or_hovered(false).collect();
+		tasks.prework_size(files, files.iter().filter(|f| targets.contains(&f.url)));
+	}
+}
@@ -0,0 +1,45 @@
+use yazi_proxy::AppProxy;
+use yazi_scheduler::checksum::ChecksumAlgo;
+use yazi_shared::event::Cmd;
+
+use crate::{manager::Manager, tasks::Tasks};
+
+struct Opt {
+	algo:    Option<String>,
+	verify:  bool,
+	hovered: bool,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		Self { algo: c.take_str("algo"), verify: c.bool("verify"), hovered: c.bool("hovered") }
+	}
+}
+
+impl Manager {
+	#[yazi_codegen::command]
+	pub fn checksum(&mut self, opt: Opt, tasks: &Tasks) {
+		if !self.active_mut().try_escape_visual() {
+			return;
+		}
+
+		let algo = match opt.algo.as_deref().map(str::parse::<ChecksumAlgo>).transpose() {
+			Ok(a) => a.unwrap_or_default(),
+			Err(e) => return AppProxy::notify_warn("Checksum", e),
+		};
+
+		let targets: Vec<_> = if opt.hovered {
+			self.hovered().map(|h| vec![h.url_owned()]).unwrap_or_default()
+		} else {
+			self.selected_or_hovered(true).cloned().collect()
+		};
+
+		if targets.is_empty() {
+			return;
+		} else if Self::reject_archive("Checksum", targets.iter()) {
+			return;
+		}
+
+		tasks.checksum(targets, algo, opt.verify);
+	}
+}
@@ -3,10 +3,10 @@ use yazi_shared::event::Cmd;
 use crate::{manager::Manager, tasks::Tasks};
 
 impl Manager {
-	pub fn close(&mut self, _: Cmd, tasks: &Tasks) {
+	pub fn close(&mut self, c: Cmd, tasks: &Tasks) {
 		if self.tabs.len() > 1 {
 			return self.tabs.close(self.tabs.cursor);
 		}
-		self.quit((), tasks);
+		self.quit(super::quit::Opt::from(c), tasks);
 	}
 }
@@ -0,0 +1,85 @@
+use yazi_config::popup::{ConfirmCfg, InputCfg};
+use yazi_fs::archive::ArchiveKind;
+use yazi_proxy::{AppProxy, ConfirmProxy, InputProxy, ManagerProxy};
+use yazi_shared::{event::{Cmd, Data}, fs::{Url, maybe_exists}};
+
+use crate::{manager::Manager, tasks::Tasks};
+
+struct Opt {
+	hovered: bool,
+	to:      Option<Url>,
+	targets: Vec<Url>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		Self {
+			hovered: c.bool("hovered"),
+			to:      c.take_first().and_then(Data::into_url),
+			targets: c.take_any("targets").unwrap_or_default(),
+		}
+	}
+}
+
+impl Manager {
+	#[yazi_codegen::command]
+	pub fn compress(&mut self, opt: Opt) {
+		if !self.active_mut().try_escape_visual() {
+			return;
+		}
+		let Some(hovered) = self.hovered().map(|h| &h.url) else {
+			return;
+		};
+
+		let targets: Vec<_> = if opt.hovered {
+			vec![hovered.clone()]
+		} else {
+			self.selected_or_hovered(true).cloned().collect()
+		};
+		if Self::reject_archive("Compress", targets.iter().chain([self.cwd()])) {
+			return;
+		}
+
+		let stem = match &targets[..] {
+			[u] => u.name(),
+			_ => self.cwd().name(),
+		};
+		let name = match stem.to_string_lossy() {
+			s if s.is_empty() => "archive.zip".to_owned(),
+			s => format!("{s}.zip"),
+		};
+		let cursor = name.chars().count() - 4;
+
+		let cwd = self.cwd().clone();
+		tokio::spawn(async move {
+			let mut result =
+				InputProxy::show(InputCfg::compress().with_value(name).with_cursor(Some(cursor)));
+			let Some(Ok(name)) = result.recv().await else {
+				return;
+			};
+
+			match ArchiveKind::detect(&name) {
+				Some((kind, _)) if kind.native() => {}
+				_ => {
+					return AppProxy::notify_warn("Compress", format!("Unsupported archive format: {name}"));
+				}
+			}
+
+			let to = cwd.join(name);
+			if !maybe_exists(&to).await || ConfirmProxy::show(ConfirmCfg::overwrite(&to)).await {
+				ManagerProxy::compress_do(targets, to);
+			}
+		});
+	}
+
+	#[yazi_codegen::command]
+	pub fn compress_do(&mut self, opt: Opt, tasks: &Tasks) {
+		let Some(to) = opt.to else { return };
+		let Some((kind, _)) = to.file_name().and_then(|s| s.to_str()).and_then(ArchiveKind::detect)
+		else {
+			return;
+		};
+
+		tasks.archive_compress(opt.targets, to, kind);
+	}
+}
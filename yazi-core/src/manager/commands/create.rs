@@ -1,10 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
-use tokio::fs;
 use yazi_config::popup::{ConfirmCfg, InputCfg};
 use yazi_proxy::{ConfirmProxy, InputProxy, TabProxy, WATCHER};
-use yazi_shared::{event::Cmd, fs::{File, FilesOp, Url, UrnBuf, maybe_exists, ok_or_not_found, realname}};
+use yazi_shared::{event::Cmd, fs::{File, FilesOp, Url, UrnBuf, long, maybe_exists, ok_or_not_found, realname}};
 
 use crate::manager::Manager;
 
@@ -20,6 +19,10 @@ impl From<Cmd> for Opt {
 impl Manager {
 	#[yazi_codegen::command]
 	pub fn create(&self, opt: Opt) {
+		if Self::reject_archive("Create", [self.cwd()].into_iter()) {
+			return;
+		}
+
 		let cwd = self.cwd().to_owned();
 		tokio::spawn(async move {
 			let mut result = InputProxy::show(InputCfg::create(opt.dir));
@@ -47,15 +50,15 @@ impl Manager {
 		let _permit = WATCHER.acquire().await.unwrap();
 
 		if dir {
-			fs::create_dir_all(&new).await?;
+			long::create_dir_all(&new).await?;
 		} else if let Some(real) = realname(&new).await {
-			ok_or_not_found(fs::remove_file(&new).await)?;
+			ok_or_not_found(long::remove_file(&new).await)?;
 			FilesOp::Deleting(parent.clone(), HashSet::from_iter([UrnBuf::from(real)])).emit();
-			fs::File::create(&new).await?;
+			long::create_file(&new).await?;
 		} else {
-			fs::create_dir_all(&parent).await.ok();
-			ok_or_not_found(fs::remove_file(&new).await)?;
-			fs::File::create(&new).await?;
+			long::create_dir_all(&parent).await.ok();
+			ok_or_not_found(long::remove_file(&new).await)?;
+			long::create_file(&new).await?;
 		}
 
 		if let Ok(f) = File::from(new.clone()).await {
@@ -0,0 +1,110 @@
+use yazi_config::{MANAGER, popup::InputCfg};
+use yazi_proxy::{AppProxy, InputProxy, ManagerProxy};
+use yazi_shared::{event::Cmd, fs::{Url, escape_non_utf8, maybe_exists, must_be_dir}};
+
+use crate::{manager::Manager, tasks::Tasks};
+
+struct Opt {
+	hovered: bool,
+	prompt:  bool,
+	pairs:   Vec<(Url, Url)>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		Self {
+			hovered: c.bool("hovered"),
+			prompt:  c.bool("prompt"),
+			pairs:   c.take_any("pairs").unwrap_or_default(),
+		}
+	}
+}
+
+impl Manager {
+	#[yazi_codegen::command]
+	pub fn duplicate(&mut self, opt: Opt) {
+		if !self.active_mut().try_escape_visual() {
+			return;
+		}
+
+		let targets: Vec<_> = if opt.hovered {
+			self.hovered().map(|h| vec![h.url_owned()]).unwrap_or_default()
+		} else {
+			self.selected_or_hovered(true).cloned().collect()
+		};
+		if Self::reject_archive("Duplicate", targets.iter()) {
+			return;
+		}
+
+		tokio::spawn(async move {
+			let mut pairs = Vec::with_capacity(targets.len());
+			for from in targets {
+				if from.name().to_str().is_none() {
+					let name = escape_non_utf8(from.name());
+					AppProxy::notify_warn("Duplicate", format!("`{name}` isn't valid UTF-8, skipped"));
+					continue;
+				}
+
+				let dir = must_be_dir(&from).await;
+				let (Some(parent), Some(mut name)) =
+					(from.parent_url(), Self::duplicate_name(&from, dir).await)
+				else {
+					continue;
+				};
+
+				if opt.prompt {
+					let cursor = Self::duplicate_cursor(&name, dir);
+					let mut result =
+						InputProxy::show(InputCfg::rename().with_value(name).with_cursor(cursor));
+					match result.recv().await {
+						Some(Ok(s)) if !s.is_empty() => name = s,
+						_ => continue,
+					}
+				}
+				pairs.push((from, parent.join(name)));
+			}
+
+			if !pairs.is_empty() {
+				ManagerProxy::duplicate_do(pairs);
+			}
+		});
+	}
+
+	#[yazi_codegen::command]
+	pub fn duplicate_do(&mut self, opt: Opt, tasks: &Tasks) { tasks.file_duplicate(opt.pairs); }
+
+	/// The first name following [`MANAGER.duplicate_format`] that doesn't exist
+	/// next to `url` yet, or `None` if the name of `url` isn't valid UTF-8.
+	async fn duplicate_name(url: &Url, dir: bool) -> Option<String> {
+		let name = url.file_name()?.to_str()?;
+		let (stem, ext) = match (dir, name.rfind('.')) {
+			(false, Some(i)) if i > 0 => name.split_at(i),
+			_ => (name, ""),
+		};
+
+		let fmt = &MANAGER.duplicate_format;
+		let counted = fmt.contains("{n}");
+		for n in 1u64.. {
+			let candidate = match (counted, n) {
+				(true, _) => fmt.replace("{n}", &n.to_string()),
+				(false, 1) => fmt.to_owned(),
+				(false, _) if fmt.contains("{ext}") => fmt.replace("{ext}", &format!(" {n}{{ext}}")),
+				(false, _) => format!("{fmt} {n}"),
+			};
+
+			let candidate = candidate.replace("{stem}", stem).replace("{ext}", ext);
+			if candidate != name && !maybe_exists(url.with_file_name(&candidate)).await {
+				return Some(candidate);
+			}
+		}
+		None
+	}
+
+	// Places the cursor before the extension, like `rename --cursor=before_ext`
+	fn duplicate_cursor(name: &str, dir: bool) -> Option<usize> {
+		if dir {
+			return None;
+		}
+		name.rfind('.').filter(|&i| i != 0).map(|i| name[..i].chars().count())
+	}
+}
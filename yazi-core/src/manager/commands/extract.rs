@@ -0,0 +1,33 @@
+use yazi_shared::event::Cmd;
+
+use crate::{manager::Manager, tasks::Tasks};
+
+struct Opt {
+	hovered: bool,
+	here:    bool,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self { Self { hovered: c.bool("hovered"), here: c.bool("here") } }
+}
+
+impl Manager {
+	#[yazi_codegen::command]
+	pub fn extract(&mut self, opt: Opt, tasks: &Tasks) {
+		if !self.active_mut().try_escape_visual() {
+			return;
+		}
+
+		let targets: Vec<_> = if opt.hovered {
+			self.hovered().map(|h| vec![&h.url]).unwrap_or_default()
+		} else {
+			self.selected_or_hovered(true).collect()
+		};
+
+		if Self::reject_archive("Extract", targets.iter().copied().chain([self.cwd()])) {
+			return;
+		}
+
+		tasks.archive_extract(&targets, self.cwd(), opt.here);
+	}
+}
@@ -17,6 +17,9 @@ impl Manager {
 		if self.yanked.cut {
 			return;
 		}
+		if Self::reject_archive("Hardlink", self.yanked.iter().chain([self.cwd()])) {
+			return;
+		}
 
 		tasks.file_hardlink(&self.yanked, self.cwd(), opt.force, opt.follow);
 	}
@@ -0,0 +1,67 @@
+use yazi_config::{MANAGER, manager::ShowHidden};
+use yazi_shared::event::{Cmd, Data};
+
+use crate::manager::Manager;
+
+#[derive(Clone, Copy)]
+enum State {
+	Set(ShowHidden),
+	Toggle,
+	Cycle,
+}
+
+struct Opt {
+	state: State,
+	all:   bool,
+	tab:   Option<usize>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		Self {
+			state: match c.take_first_str().as_deref() {
+				Some("cycle") => State::Cycle,
+				Some(s) => s.parse().map_or(State::Toggle, State::Set),
+				None => State::Toggle,
+			},
+			all:   c.bool("all"),
+			tab:   c.get("tab").and_then(Data::as_usize),
+		}
+	}
+}
+
+impl State {
+	/// The mode to go to from `old`, where toggling shows them the way the
+	/// config does, or plainly if it hides them, and cycling goes through
+	/// `hide`, `show` and `dim` in turn.
+	fn apply(self, old: ShowHidden) -> ShowHidden {
+		match self {
+			Self::Set(new) => new,
+			Self::Toggle if old.is_shown() => ShowHidden::Hide,
+			Self::Toggle if MANAGER.show_hidden.is_shown() => MANAGER.show_hidden,
+			Self::Toggle => ShowHidden::Show,
+			Self::Cycle => match old {
+				ShowHidden::Hide => ShowHidden::Show,
+				ShowHidden::Show => ShowHidden::Dim,
+				ShowHidden::Dim => ShowHidden::Hide,
+			},
+		}
+	}
+}
+
+impl Manager {
+	/// Shows, dims or hides the hidden files of the active tab, or of all the
+	/// tabs with `--all`, which follow the active one when toggled or cycled.
+	#[yazi_codegen::command]
+	pub fn hidden(&mut self, opt: Opt) {
+		if !opt.all {
+			let tab = self.active_or_mut(opt.tab);
+			return tab.hidden(opt.state.apply(tab.conf.show_hidden));
+		}
+
+		let state = opt.state.apply(self.active().conf.show_hidden);
+		for tab in self.tabs.iter_mut() {
+			tab.hidden(state);
+		}
+	}
+}
@@ -1,11 +1,18 @@
-use std::{collections::HashSet, path::PathBuf};
+use std::{collections::{BTreeSet, HashMap, HashSet}, io, path::PathBuf};
 
+use parking_lot::Mutex;
+use tokio::time::timeout;
+use yazi_config::MANAGER;
 use yazi_dds::Pubsub;
 use yazi_macro::render;
-use yazi_shared::{event::{Cmd, Data}, fs::{Url, Urn}};
+use yazi_shared::{event::{Cmd, Data}, fs::{File, FilesOp, Url, Urn}};
 
 use crate::manager::Manager;
 
+// The hovered placeholders whose metadata is being read, so that hovering them
+// again doesn't pile up more reads on a stalled mount
+static STATING: Mutex<BTreeSet<Url>> = Mutex::new(BTreeSet::new());
+
 struct Opt {
 	url: Option<Url>,
 	tab: Option<usize>,
@@ -32,21 +39,30 @@ impl Manager {
 			self.current_or_mut(opt.tab).repos(None);
 		}
 
+		// Fill in the metadata
+		self.hover_stat();
+		self.active_or_mut(opt.tab).pub_filter_find();
+
 		// Repeek
 		self.peek(false);
 
 		// Refresh watcher
 		let mut to_watch = HashSet::with_capacity(3 * self.tabs.len());
-		for tab in self.tabs.iter() {
-			to_watch.insert(tab.cwd());
+		let mut to_poll = HashSet::with_capacity(3);
+		for (i, tab) in self.tabs.iter().enumerate() {
+			let mut visible = vec![tab.cwd()];
 			if let Some(ref p) = tab.parent {
-				to_watch.insert(&p.url);
+				visible.push(&p.url);
 			}
 			if let Some(h) = tab.hovered().filter(|&h| h.is_dir()) {
-				to_watch.insert(&h.url);
+				visible.push(&h.url);
+			}
+			if i == self.tabs.cursor {
+				to_poll.extend(visible.iter().copied());
 			}
+			to_watch.extend(visible);
 		}
-		self.watcher.watch(to_watch);
+		self.watcher.watch(to_watch, to_poll);
 
 		// Publish through DDS
 		Pubsub::pub_from_hover(self.active().idx, self.hovered().map(|h| &h.url));
@@ -65,4 +81,44 @@ impl Manager {
 			self.current_or_mut(tab).tracing = true;
 		}
 	}
+
+	// Reads the metadata of the hovered file in the background if it's only a
+	// placeholder, e.g. one just revealed, or one whose metadata was unavailable,
+	// which it's marked as again if that takes longer than `stat_timeout`
+	fn hover_stat(&self) {
+		let Some(h) = self.hovered().filter(|&h| h.is_dummy() && h.url.is_regular()) else {
+			return;
+		};
+		let Some((parent, urn)) = h.url.pair() else { return };
+		if !STATING.lock().insert(h.url_owned()) {
+			return;
+		}
+
+		let (url, unavailable) = (h.url_owned(), h.is_unavailable());
+		tokio::spawn(async move {
+			let upsert =
+				|f: File| FilesOp::Upserting(parent.clone(), HashMap::from_iter([(urn.clone(), f)]));
+
+			let fut = File::from(url.clone());
+			tokio::pin!(fut);
+			let result = match timeout(MANAGER.stat_timeout(), &mut fut).await {
+				Ok(r) => r,
+				Err(_) => {
+					if !unavailable {
+						upsert(File::from_unavailable(url.clone(), None)).emit();
+					}
+					fut.await
+				}
+			};
+
+			STATING.lock().remove(&url);
+			match result.map_err(|e| e.downcast::<io::Error>().map(|e| e.kind())) {
+				Ok(f) => upsert(f).emit(),
+				Err(Ok(io::ErrorKind::NotFound)) => {
+					FilesOp::Deleting(parent, HashSet::from_iter([urn])).emit();
+				}
+				Err(_) => {}
+			}
+		});
+	}
 }
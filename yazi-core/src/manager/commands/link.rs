@@ -17,6 +17,9 @@ impl Manager {
 		if self.yanked.cut {
 			return;
 		}
+		if Self::reject_archive("Link", self.yanked.iter().chain([self.cwd()])) {
+			return;
+		}
 
 		tasks.file_link(&self.yanked, self.cwd(), opt.relative, opt.force);
 	}
@@ -1,27 +1,40 @@
 yazi_macro::mod_flat!(
 	bulk_rename
+	calc_size
+	checksum
 	close
+	compress
 	create
+	duplicate
+	extract
 	hardlink
+	hidden
 	hover
 	link
 	open
 	paste
 	peek
 	quit
+	read_only
 	refresh
 	remove
 	rename
 	seek
+	session
+	spot
 	suspend
 	tab_close
 	tab_create
 	tab_swap
 	tab_switch
+	tag
+	touch
+	unquarantine
 	unyank
 	update_files
 	update_mimes
 	update_paged
+	update_tagged
 	update_tasks
 	update_yanked
 	yank
@@ -1,13 +1,13 @@
-use std::{borrow::Cow, ffi::OsString};
+use std::{borrow::Cow, collections::{HashMap, HashSet}};
 
 use tracing::error;
 use yazi_boot::ARGS;
-use yazi_config::{OPEN, popup::PickCfg};
-use yazi_fs::Folder;
+use yazi_config::{OPEN, open::Opener, popup::PickCfg};
+use yazi_fs::{Folder, archive::Vfs};
 use yazi_macro::emit;
-use yazi_plugin::isolate;
-use yazi_proxy::{ManagerProxy, TasksProxy, options::OpenDoOpt};
-use yazi_shared::{MIME_DIR, event::{Cmd, EventQuit}, fs::{File, Url}};
+use yazi_plugin::{isolate, utils::PluginOpener};
+use yazi_proxy::{AppProxy, ConfirmProxy, ManagerProxy, PickProxy, TasksProxy, options::{OpenDoOpt, ProcessOrigin}};
+use yazi_shared::{Layer, MIME_DIR, event::{Cmd, EventQuit}, fs::{File, Url}};
 
 use crate::{manager::Manager, tasks::Tasks};
 
@@ -15,11 +15,20 @@ use crate::{manager::Manager, tasks::Tasks};
 struct Opt {
 	interactive: bool,
 	hovered:     bool,
+	enter:       bool,
+	with_dialog: bool,
+	multi:       bool,
 }
 
 impl From<Cmd> for Opt {
 	fn from(c: Cmd) -> Self {
-		Self { interactive: c.bool("interactive"), hovered: c.bool("hovered") }
+		Self {
+			interactive: c.bool("interactive"),
+			hovered:     c.bool("hovered"),
+			enter:       c.bool("enter"),
+			with_dialog: c.bool("with-dialog"),
+			multi:       c.bool("multi"),
+		}
 	}
 }
 
@@ -33,16 +42,27 @@ impl Manager {
 			return;
 		};
 
+		// Browsing an archive is opted into along with entering directories, as it's
+		// meant to be opened with the openers otherwise
+		if opt.enter && self.hovered().is_some_and(|h| h.is_dir() || Vfs::browsable(&h.url)) {
+			return self.active_mut().enter(());
+		}
+
 		let selected =
 			if opt.hovered { vec![&hovered] } else { self.selected_or_hovered(true).collect() };
 
-		if Self::quit_with_selected(opt, &selected) {
+		if self.quit_with_selected(opt, &selected) {
 			return;
 		}
+		if opt.with_dialog {
+			return Self::open_with_dialog(&hovered, &selected, self.active().origin(), tasks);
+		}
 
 		let (mut done, mut todo) = (Vec::with_capacity(selected.len()), vec![]);
 		for u in selected {
-			if self.mimetype.contains(u) {
+			if u.is_archive() {
+				todo.push(u.clone());
+			} else if self.mimetype.contains(u) {
 				done.push((u.clone(), String::new()));
 			} else if self.guess_folder(u) {
 				done.push((u.clone(), MIME_DIR.to_owned()));
@@ -52,13 +72,28 @@ impl Manager {
 		}
 
 		if todo.is_empty() {
-			return self
-				.open_do(OpenDoOpt { hovered, targets: done, interactive: opt.interactive }, tasks);
+			let (interactive, multi, origin) = (opt.interactive, opt.multi, self.active().origin());
+			return self.open_do(OpenDoOpt { hovered, targets: done, interactive, multi, origin }, tasks);
 		}
 
+		let origin = self.active().origin();
 		tokio::spawn(async move {
+			let mut hovered = hovered;
+			if hovered.is_archive() {
+				match Vfs::materialize(&hovered).await {
+					Ok(p) => hovered = Url::from(p),
+					Err(e) => {
+						return AppProxy::notify_warn("Open", format!("Failed to extract {hovered}: {e}"));
+					}
+				}
+			}
+
 			let mut files = Vec::with_capacity(todo.len());
-			for u in todo {
+			for mut u in todo {
+				if u.is_archive() {
+					let Ok(p) = Vfs::materialize(&u).await else { continue };
+					u = Url::from(p);
+				}
 				if let Ok(f) = File::from(u).await {
 					files.push(f);
 				}
@@ -69,7 +104,8 @@ impl Manager {
 				error!("Fetch `mime` failed in opening: {e}");
 			}
 
-			ManagerProxy::open_do(OpenDoOpt { hovered, targets: done, interactive: opt.interactive });
+			let (interactive, multi) = (opt.interactive, opt.multi);
+			ManagerProxy::open_do(OpenDoOpt { hovered, targets: done, interactive, multi, origin });
 		});
 	}
 
@@ -85,26 +121,112 @@ impl Manager {
 
 		if targets.is_empty() {
 			return;
-		} else if !opt.interactive {
-			return tasks.process_from_files(opt.hovered, targets);
 		}
 
-		let openers: Vec<_> = OPEN.common_openers(&targets);
-		if openers.is_empty() {
+		let sizes = self.cached_sizes(&targets);
+		if !opt.interactive {
+			return tasks.process_from_files(opt.hovered, targets, &sizes, opt.origin);
+		}
+
+		let mut openers: Vec<_> = OPEN
+			.applicable_openers(&targets)
+			.into_iter()
+			.map(|(o, indices)| (Cow::Borrowed(o), indices))
+			.collect();
+		if cfg!(windows) && !openers.iter().any(|(o, _)| o.run == Opener::SYSTEM) {
+			openers.push((Cow::Owned(Opener::system(false)), (0..targets.len()).collect()));
+		}
+
+		// Those added by plugins go after the configured ones
+		let plugins = PluginOpener::provide(&targets);
+		if openers.is_empty() && plugins.is_empty() {
 			return;
 		}
 
-		let urls = [opt.hovered].into_iter().chain(targets.into_iter().map(|(u, _)| u)).collect();
+		// Show how many of the targets each opener applies to, for a multi-selection
+		let total = targets.len();
+		let items = openers
+			.iter()
+			.map(|(o, indices)| (&o.desc, indices))
+			.chain(plugins.iter().map(|p| (&p.desc, &p.indices)))
+			.map(|(desc, indices)| match total {
+				1 => desc.clone(),
+				_ => format!("{desc} ({}/{total})", indices.len()),
+			})
+			.collect();
+
+		let urls: Vec<_> = targets.into_iter().map(|(u, _)| u).collect();
 		tokio::spawn(async move {
-			let result = yazi_proxy::PickProxy::show(PickCfg::open(
-				openers.iter().map(|o| o.desc.clone()).collect(),
-			));
-			if let Ok(choice) = result.await {
-				TasksProxy::open_with(urls, Cow::Borrowed(openers[choice]));
+			let choices = if opt.multi {
+				PickProxy::show_multi(PickCfg::open(items)).await
+			} else {
+				PickProxy::show(PickCfg::open(items)).await.map(|i| vec![i])
+			};
+			let Ok(choices) = choices else { return };
+
+			// Only the targets each chosen opener applies to are dispatched to it, in
+			// order, each after those before it have finished
+			for choice in choices {
+				let Some((opener, indices)) = openers.get(choice) else {
+					let plugin = plugins[choice - openers.len()].clone();
+					let targets: Vec<_> = plugin.indices.iter().map(|&i| urls[i].clone()).collect();
+					emit!(Call(
+						Cmd::new("open_plugin").with_any("opener", plugin).with_any("targets", targets),
+						Layer::App
+					));
+					continue;
+				};
+
+				let targets = indices.iter().map(|&i| urls[i].clone());
+				let targets: Vec<_> = [opt.hovered.clone()].into_iter().chain(targets).collect();
+				if let Some(cfg) = Tasks::large_open(opener, &targets[1..], &sizes) {
+					if !ConfirmProxy::show(cfg).await {
+						continue;
+					}
+				}
+				TasksProxy::open_with_wait(targets, opener.clone(), opt.origin.clone()).await;
 			}
 		});
 	}
 
+	// Archived files are skipped, as they'd have to be extracted first
+	fn open_with_dialog(hovered: &Url, selected: &[&Url], origin: ProcessOrigin, tasks: &Tasks) {
+		if cfg!(not(windows)) {
+			return AppProxy::notify_warn("Open", "`--with-dialog` is only available on Windows");
+		}
+
+		let args: Vec<_> = [hovered]
+			.into_iter()
+			.chain(selected.iter().copied().filter(|u| !u.is_archive()))
+			.map(|u| u.as_os_str().to_owned())
+			.collect();
+		if args.len() > 1 {
+			tasks.process_from_opener(Cow::Owned(Opener::system(true)), args, origin, None);
+		}
+	}
+
+	// The sizes of the files among `targets` in the folders loaded, so that
+	// opening them doesn't have to read their metadata again
+	fn cached_sizes(&self, targets: &[(Url, String)]) -> HashMap<Url, u64> {
+		let urls: HashSet<_> = targets.iter().map(|(u, _)| u).collect();
+		let dirs: HashSet<_> = urls.iter().filter_map(|u| u.parent_url()).collect();
+
+		let mut sizes = HashMap::with_capacity(urls.len());
+		for dir in dirs {
+			let folder = [Some(self.current()), self.parent(), self.hovered_folder()]
+				.into_iter()
+				.flatten()
+				.find(|f| f.url == dir)
+				.or_else(|| self.active().history.get(&dir));
+
+			let Some(folder) = folder else { continue };
+			for f in folder.files.iter().filter(|f| !f.is_dir() && urls.contains(&f.url)) {
+				sizes.insert(f.url_owned(), f.len);
+			}
+		}
+		sizes
+	}
+
 	fn guess_folder(&self, url: &Url) -> bool {
 		let Some(p) = url.parent_url() else {
 			return true;
@@ -122,16 +244,17 @@ impl Manager {
 			|| find(self.active().history.get(&p))
 	}
 
-	fn quit_with_selected(opt: Opt, selected: &[&Url]) -> bool {
+	fn quit_with_selected(&self, opt: Opt, selected: &[&Url]) -> bool {
 		if opt.interactive || ARGS.chooser_file.is_none() {
 			return false;
 		}
 
-		let paths = selected.iter().fold(OsString::new(), |mut s, &u| {
-			s.push(u.as_os_str());
-			s.push("\n");
-			s
-		});
+		// In the directory mode it's the CWD that gets chosen, whatever is selected
+		let paths = if ARGS.chooser_dir {
+			vec![self.cwd().to_path_buf()]
+		} else {
+			selected.iter().map(|u| u.to_path_buf()).collect()
+		};
 
 		emit!(Quit(EventQuit { selected: Some(paths), ..Default::default() }));
 		true
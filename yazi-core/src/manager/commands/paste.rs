@@ -1,28 +1,172 @@
-use yazi_shared::event::Cmd;
+use std::time::Duration;
+
+use yazi_config::popup::PickCfg;
+use yazi_dds::{Pubsub, body::{BodyCopy, BodyMove, BodyMoveItem}};
+use yazi_fs::DirStats;
+use yazi_macro::{emit, render};
+use yazi_proxy::{AppProxy, PickProxy};
+use yazi_shared::{Layer, event::Cmd, fs::{Url, maybe_exists}, readable_size};
 
 use crate::{manager::Manager, tasks::Tasks};
 
 struct Opt {
-	force:  bool,
-	follow: bool,
+	force:     bool,
+	follow:    bool,
+	vetted:    bool,
+	confirmed: bool,
+	yes:       bool,
+	pick:      bool,
+	preserve:  bool,
+	// Those picked with `--pick`, rather than all the yanked
+	targets:   Option<Vec<Url>>,
 }
 
 impl From<Cmd> for Opt {
-	fn from(c: Cmd) -> Self { Self { force: c.bool("force"), follow: c.bool("follow") } }
+	fn from(mut c: Cmd) -> Self {
+		Self {
+			force:     c.bool("force"),
+			follow:    c.bool("follow"),
+			vetted:    c.bool("vetted"),
+			confirmed: c.bool("confirmed"),
+			yes:       c.bool("yes"),
+			pick:      c.bool("pick"),
+			preserve:  !c.bool("no-preserve"),
+			targets:   c.take_any("targets"),
+		}
+	}
 }
 
 impl Manager {
 	#[yazi_codegen::command]
 	pub fn paste(&mut self, opt: Opt, tasks: &Tasks) {
-		let (src, dest) = (self.yanked.iter().collect::<Vec<_>>(), self.cwd());
+		if opt.pick {
+			return self.paste_pick(opt);
+		}
+
+		let dest = self.cwd();
+		let src: Vec<_> = match &opt.targets {
+			Some(targets) => targets.iter().filter(|&u| self.yanked.contains(u)).collect(),
+			None => self.yanked.iter().collect(),
+		};
+		let written = if self.yanked.cut { &src[..] } else { &[] };
+		if Self::reject_archive("Paste", written.iter().copied().chain([dest])) {
+			return;
+		}
+
+		// Moving protected files away, or overwriting them, has to be confirmed
+		if !opt.confirmed {
+			let mut overwritten = vec![];
+			if opt.force {
+				overwritten = src.iter().filter_map(|u| u.file_name()).map(|n| dest.join(n)).collect();
+			}
+
+			let moved: Vec<_> = written.iter().map(|&u| u.clone()).collect();
+			if !Self::protected(moved.iter().chain(&overwritten)).is_empty() {
+				let (yes, again) = (opt.yes, Self::paste_again(&opt, &src));
+				tokio::spawn(async move {
+					let mut urls = moved;
+					for u in overwritten {
+						if maybe_exists(&u).await {
+							urls.push(u);
+						}
+					}
+
+					// Typing the names of those protected isn't skipped by `--yes`
+					let protected = Self::protected(urls.iter());
+					if yes && !protected.is_empty() {
+						return AppProxy::notify_warn(
+							"Paste",
+							format!(
+								"`{}` is protected, which can't be pasted with `--yes`",
+								protected[0].display()
+							),
+						);
+					}
+					if Self::confirm_protected("Paste", &protected).await {
+						emit!(Call(again.with_bool("confirmed", true), Layer::Manager));
+					}
+				});
+				return;
+			}
+		}
+
+		let kind = if self.yanked.cut { "move" } else { "copy" };
+		if !opt.vetted && Pubsub::any_pre(kind) {
+			let items: Vec<_> = src
+				.iter()
+				.filter_map(|&u| Some(BodyMoveItem { from: u.clone(), to: dest.join(u.file_name()?) }))
+				.collect();
+
+			let body = if self.yanked.cut { BodyMove::owned(items) } else { BodyCopy::owned(items) };
+			let again = Self::paste_again(&opt, &src);
+			tokio::spawn(async move {
+				if Pubsub::pre(body).await {
+					emit!(Call(again.with_bool("vetted", true).with_bool("confirmed", true), Layer::Manager));
+				}
+			});
+			return;
+		}
 
 		if self.yanked.cut {
-			tasks.file_cut(&src, dest, opt.force);
+			tasks.file_cut(&src, dest, opt.force, opt.preserve);
+
+			for tab in self.tabs.iter_mut() {
+				tab.selected.remove_many(&src, false);
+				tab.selected.catchup_revision(tab.idx);
+			}
 
-			self.tabs.iter_mut().for_each(|t| _ = t.selected.remove_many(&src, false));
-			self.unyank(());
+			// Only those pasted are no longer yanked, the others picked out stay
+			let src: Vec<_> = src.into_iter().cloned().collect();
+			src.iter().for_each(|u| self.yanked.remove(u));
+			render!(self.yanked.catchup_revision(false));
 		} else {
-			tasks.file_copy(&src, dest, opt.force, opt.follow);
+			tasks.file_copy(&src, dest, opt.force, opt.follow, opt.preserve);
+		}
+	}
+
+	// Picks some of the yanked files to paste, with their sizes, and those gone
+	// since they were yanked marked
+	fn paste_pick(&self, opt: Opt) {
+		let mut urls: Vec<_> = self.yanked.iter().cloned().collect();
+		if urls.is_empty() {
+			return;
+		}
+		urls.sort_unstable();
+
+		let again = Self::paste_again(&opt, &[]);
+		tokio::spawn(async move {
+			let items = futures::future::join_all(urls.iter().map(Self::paste_item)).await;
+			let Ok(picked) = PickProxy::show_multi(PickCfg::paste(items)).await else { return };
+			if picked.is_empty() {
+				return;
+			}
+
+			let targets: Vec<_> = picked.into_iter().filter_map(|i| urls.get(i).cloned()).collect();
+			emit!(Call(again.with_any("targets", targets), Layer::Manager));
+		});
+	}
+
+	async fn paste_item(url: &Url) -> String {
+		if !maybe_exists(url).await {
+			return format!("{:>8}  {url}", "missing");
+		}
+
+		let (stats, complete) =
+			DirStats::total(std::slice::from_ref(url), Duration::from_millis(200)).await;
+		let size = readable_size(stats.len);
+		format!("{:>8}  {url}", if complete { size } else { format!(">{size}") })
+	}
+
+	// The same `paste` again, e.g. once confirmed, of only `src` if it was picked
+	fn paste_again(opt: &Opt, src: &[&Url]) -> Cmd {
+		let cmd = Cmd::new("paste")
+			.with_bool("force", opt.force)
+			.with_bool("follow", opt.follow)
+			.with_bool("no-preserve", !opt.preserve);
+
+		match opt.targets {
+			Some(_) => cmd.with_any("targets", src.iter().map(|&u| u.clone()).collect::<Vec<_>>()),
+			None => cmd,
 		}
 	}
 }
@@ -1,7 +1,16 @@
-use yazi_shared::{event::{Cmd, Data}, fs::Url};
+use std::collections::BTreeSet;
+
+use parking_lot::Mutex;
+use tracing::error;
+use yazi_fs::archive::Vfs;
+use yazi_plugin::isolate;
+use yazi_proxy::ManagerProxy;
+use yazi_shared::{event::{Cmd, Data}, fs::{File, Url}};
 
 use crate::manager::Manager;
 
+static MATERIALIZING: Mutex<BTreeSet<Url>> = Mutex::new(BTreeSet::new());
+
 #[derive(Debug, Default)]
 struct Opt {
 	skip:        Option<usize>,
@@ -27,17 +36,35 @@ impl From<bool> for Opt {
 impl Manager {
 	#[yazi_codegen::command]
 	pub fn peek(&mut self, opt: Opt) {
-		let Some(hovered) = self.hovered().cloned() else {
+		let Some(mut hovered) = self.hovered().cloned() else {
+			self.active_mut().spot.reset();
+			self.active_mut().preview.reset_footer();
 			return self.active_mut().preview.reset();
 		};
 
+		// Files inside archives are previewed by their extracted copy in the cache
+		if hovered.url.is_archive() && !hovered.is_dir() {
+			let Some(path) = Vfs::materialized(&hovered.url) else {
+				self.active_mut().preview.reset();
+				let url = hovered.url_owned();
+				return Self::materialize_to_peek(url);
+			};
+			hovered.url = Url::from(path);
+		}
+
 		let mime = self.mimetype.get_owned(&hovered.url).unwrap_or_default();
+		self.active_mut().preview.go_footer(&hovered, &mime);
+		if self.active().spot.visible() && !self.active().spot.same_url(&hovered.url) {
+			self.spot(());
+		}
 		let folder = self.active().hovered_folder().map(|f| (f.offset, f.cha));
 
 		if !self.active().preview.same_url(&hovered.url) {
 			self.active_mut().preview.skip = folder.map(|f| f.0).unwrap_or_default();
 		}
-		if !self.active().preview.same_file(&hovered, &mime) {
+		if !self.active().preview.same_file(&hovered, &mime)
+			&& !self.active().preview.same_peeker(&hovered, &mime)
+		{
 			self.active_mut().preview.reset();
 		}
 
@@ -60,4 +87,24 @@ impl Manager {
 			self.active_mut().preview.go(hovered, mime.into(), opt.force);
 		}
 	}
+
+	// Each entry is extracted once at a time, however many times it's peeked
+	// meanwhile, e.g. while scrolling past it
+	fn materialize_to_peek(url: Url) {
+		if !MATERIALIZING.lock().insert(url.clone()) {
+			return;
+		}
+
+		tokio::spawn(async move {
+			let result = Vfs::materialize(&url).await;
+			MATERIALIZING.lock().remove(&url);
+
+			let Ok(path) = result else { return };
+			let Ok(file) = File::from(Url::from(path)).await else { return };
+			if let Err(e) = isolate::fetch("mime", vec![file]).await {
+				error!("Fetch `mime` failed in peeking an archive entry: {e}");
+			}
+			ManagerProxy::peek(false);
+		});
+	}
 }
@@ -1,36 +1,45 @@
 use std::time::Duration;
 
 use tokio::{select, time};
-use yazi_config::popup::ConfirmCfg;
+use yazi_config::{TASKS, popup::ConfirmCfg};
 use yazi_macro::emit;
 use yazi_proxy::ConfirmProxy;
+use yazi_scheduler::{Task, TaskKind};
 use yazi_shared::event::{Cmd, EventQuit};
 
 use crate::{manager::Manager, tasks::Tasks};
 
 #[derive(Default)]
-struct Opt {
+pub(super) struct Opt {
 	no_cwd_file: bool,
+	force:       bool,
 }
 impl From<()> for Opt {
 	fn from(_: ()) -> Self { Self::default() }
 }
 impl From<Cmd> for Opt {
-	fn from(c: Cmd) -> Self { Self { no_cwd_file: c.bool("no-cwd-file") } }
+	fn from(c: Cmd) -> Self {
+		Self {
+			no_cwd_file: c.bool("no-cwd-file"),
+			force:       c.bool("force") || c.bool("yes") || !TASKS.confirm_quit,
+		}
+	}
 }
 
 impl Manager {
 	#[yazi_codegen::command]
 	pub fn quit(&self, opt: Opt, tasks: &Tasks) {
+		let force = opt.force;
 		let opt = EventQuit { no_cwd_file: opt.no_cwd_file, ..Default::default() };
 
 		let ongoing = tasks.ongoing().clone();
 		let (left, left_names) = {
 			let ongoing = ongoing.lock();
-			(ongoing.len(), ongoing.values().take(11).map(|t| t.name.clone()).collect())
+			let user = || ongoing.values().filter(|t| t.kind == TaskKind::User);
+			(user().count(), user().take(11).map(Self::quit_task_name).collect())
 		};
 
-		if left == 0 {
+		if force || left == 0 {
 			emit!(Quit(opt));
 			return;
 		}
@@ -43,7 +52,7 @@ impl Manager {
 					_ = time::sleep(Duration::from_millis(100)) => {
 						i += 1;
 						if i > 30 { break }
-						else if ongoing.lock().len() == 0 {
+						else if !ongoing.lock().values().any(|t| t.kind == TaskKind::User) {
 							emit!(Quit(opt));
 							return;
 						}
@@ -62,4 +71,12 @@ impl Manager {
 			}
 		});
 	}
+
+	fn quit_task_name(task: &Task) -> String {
+		if task.total == 0 {
+			task.name.clone()
+		} else {
+			format!("{} ({}/{})", task.name, task.succ + task.fail, task.total)
+		}
+	}
 }
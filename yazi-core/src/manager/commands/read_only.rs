@@ -0,0 +1,72 @@
+use std::{sync::atomic::Ordering, time::Duration};
+
+use yazi_boot::READ_ONLY;
+use yazi_macro::render;
+use yazi_proxy::{AppProxy, options::{NotifyLevel, NotifyOpt}};
+use yazi_shared::event::Cmd;
+
+use crate::manager::Manager;
+
+struct Opt {
+	state: Option<bool>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		Self {
+			state: match c.take_first_str().as_deref() {
+				Some("on") => Some(true),
+				Some("off") => Some(false),
+				_ => None,
+			},
+		}
+	}
+}
+
+impl Manager {
+	/// Turns the read-only mode on or off, or toggles it without an argument.
+	#[yazi_codegen::command]
+	pub fn read_only(&mut self, opt: Opt) {
+		let state = opt.state.unwrap_or(!READ_ONLY.load(Ordering::Relaxed));
+		if READ_ONLY.swap(state, Ordering::Relaxed) == state {
+			return;
+		}
+
+		AppProxy::notify(NotifyOpt {
+			title:   "Read-only mode".to_owned(),
+			content: if state { "Turned on" } else { "Turned off" }.to_owned(),
+			level:   NotifyLevel::Info,
+			timeout: Duration::from_secs(5),
+			actions: vec![],
+		});
+		render!();
+	}
+
+	/// Warns and returns `true` if `cmd` changes files while in the read-only
+	/// mode.
+	pub fn reject_read_only(cmd: &str) -> bool {
+		const MUTATING: [&str; 15] = [
+			"paste",
+			"link",
+			"hardlink",
+			"compress",
+			"compress_do",
+			"duplicate",
+			"duplicate_do",
+			"extract",
+			"remove",
+			"remove_do",
+			"create",
+			"rename",
+			"touch",
+			"unquarantine",
+			"shell",
+		];
+
+		if !READ_ONLY.load(Ordering::Relaxed) || !MUTATING.contains(&cmd) {
+			return false;
+		}
+		AppProxy::notify_warn("Read-only mode", format!("`{cmd}` is disabled in the read-only mode"));
+		true
+	}
+}
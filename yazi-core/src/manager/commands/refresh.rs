@@ -4,10 +4,14 @@ use crossterm::{execute, terminal::SetTitle};
 use yazi_config::MANAGER;
 use yazi_shared::event::Cmd;
 
-use crate::{manager::Manager, tasks::Tasks};
+use crate::{manager::{Manager, Session}, tasks::Tasks};
 
 impl Manager {
-	pub fn refresh(&mut self, _: Cmd, tasks: &Tasks) {
+	pub fn refresh(&mut self, c: Cmd, tasks: &Tasks) {
+		if c.bool("force") {
+			return self.refresh_force();
+		}
+
 		env::set_current_dir(self.cwd()).ok();
 		env::set_var("PWD", self.cwd());
 
@@ -23,10 +27,37 @@ impl Manager {
 			self.watcher.trigger_dirs(&[self.current()]);
 		}
 
+		tasks.prework_size_cancel(&self.tabs.iter().map(|t| t.cwd()).collect::<Vec<_>>());
+		tasks.prework_retain_folders(
+			&self
+				.tabs
+				.iter()
+				.flat_map(|t| {
+					let hovered = t.hovered().filter(|&h| h.is_dir()).map(|h| &h.url);
+					[Some(t.cwd()), t.parent.as_ref().map(|p| &p.url), hovered]
+				})
+				.flatten()
+				.collect::<Vec<_>>(),
+		);
+
 		self.hover(None);
 		self.update_paged((), tasks);
 
 		tasks.prework_sorted(&self.current().files);
+		Session::autosave(&self.tabs);
+	}
+
+	// Reads the CWD, parent and hovered directory of the active tab again, keeping
+	// the hovered file by name, or the one taking its place if it's gone
+	fn refresh_force(&mut self) {
+		self.current_mut().tracing = true;
+
+		let tab = self.active();
+		let folders: Vec<_> = [Some(&tab.current), tab.parent.as_ref(), tab.hovered_folder()]
+			.into_iter()
+			.flatten()
+			.collect();
+		self.watcher.reload_dirs(&folders);
 	}
 
 	fn title(&self) -> String {
@@ -1,6 +1,10 @@
+use std::time::Duration;
+
 use yazi_config::popup::ConfirmCfg;
-use yazi_proxy::{ConfirmProxy, ManagerProxy};
-use yazi_shared::{event::Cmd, fs::Url};
+use yazi_dds::{Pubsub, body::{BodyDelete, BodyTrash}};
+use yazi_fs::DirStats;
+use yazi_proxy::{AppProxy, ConfirmProxy, ManagerProxy};
+use yazi_shared::{event::Cmd, fs::Url, readable_size};
 
 use crate::{manager::Manager, tasks::Tasks};
 
@@ -8,6 +12,7 @@ struct Opt {
 	force:       bool,
 	permanently: bool,
 	hovered:     bool,
+	yes:         bool,
 	targets:     Vec<Url>,
 }
 
@@ -17,6 +22,7 @@ impl From<Cmd> for Opt {
 			force:       c.bool("force"),
 			permanently: c.bool("permanently"),
 			hovered:     c.bool("hovered"),
+			yes:         c.bool("yes"),
 			targets:     c.take_any("targets").unwrap_or_default(),
 		}
 	}
@@ -38,19 +44,81 @@ impl Manager {
 			self.selected_or_hovered(true).cloned().collect()
 		};
 
-		if opt.force {
+		if Self::reject_archive("Remove", opt.targets.iter()) {
+			return;
+		}
+
+		let protected = Self::protected(opt.targets.iter());
+		if opt.permanently && !opt.force && !protected.is_empty() {
+			return AppProxy::notify_warn(
+				"Remove",
+				format!(
+					"`{}` is protected, add `--force` to delete it permanently",
+					protected[0].display()
+				),
+			);
+		}
+
+		// Those protected have to be confirmed by typing their names, which isn't
+		// skipped
+		if opt.yes && !protected.is_empty() {
+			return AppProxy::notify_warn(
+				"Remove",
+				format!("`{}` is protected, which can't be removed with `--yes`", protected[0].display()),
+			);
+		}
+
+		let kind = if opt.permanently { "delete" } else { "trash" };
+		let quiet = opt.force || opt.yes;
+		if opt.permanently && quiet && protected.is_empty() && !Pubsub::any_pre(kind) {
 			return self.remove_do(opt, tasks);
 		}
 
 		tokio::spawn(async move {
-			let result = ConfirmProxy::show(if opt.permanently {
-				ConfirmCfg::delete(&opt.targets)
-			} else {
-				ConfirmCfg::trash(&opt.targets)
-			});
+			// Those that can't go to the trash are deleted permanently, which always
+			// has to be confirmed, and protected ones need `--force` for it
+			let untrashable =
+				if opt.permanently { vec![] } else { Self::untrashable(&opt.targets).await };
+			if let Some(p) = Self::protected(untrashable.iter()).first().filter(|_| !opt.force) {
+				return AppProxy::notify_warn(
+					"Remove",
+					format!(
+						"`{}` can't go to the trash, add `--force` to delete it permanently",
+						p.display()
+					),
+				);
+			}
+
+			if !untrashable.is_empty() && !opt.yes {
+				let summary = Self::remove_summary(&opt.targets, false, untrashable.len()).await;
+				if !ConfirmProxy::show(ConfirmCfg::untrashable(&untrashable, summary)).await {
+					return;
+				}
+			} else if untrashable.is_empty() && !quiet {
+				let summary = Self::remove_summary(&opt.targets, opt.permanently, 0).await;
+				let result = ConfirmProxy::show(if opt.permanently {
+					ConfirmCfg::delete(&opt.targets, summary)
+				} else {
+					ConfirmCfg::trash(&opt.targets, summary)
+				});
+				if !result.await {
+					return;
+				}
+			}
+			if !Self::confirm_protected("Remove", &protected).await {
+				return;
+			}
 
-			if result.await {
-				ManagerProxy::remove_do(opt.targets, opt.permanently);
+			let (trash, delete): (Vec<_>, _) = if opt.permanently {
+				(vec![], opt.targets)
+			} else {
+				opt.targets.into_iter().partition(|u| !untrashable.contains(u))
+			};
+			if !trash.is_empty() && Pubsub::pre(BodyTrash::owned(trash.clone())).await {
+				ManagerProxy::remove_do(trash, false);
+			}
+			if !delete.is_empty() && Pubsub::pre(BodyDelete::owned(delete.clone())).await {
+				ManagerProxy::remove_do(delete, true);
 			}
 		});
 	}
@@ -59,6 +127,7 @@ impl Manager {
 	pub fn remove_do(&mut self, opt: Opt, tasks: &Tasks) {
 		self.tabs.iter_mut().for_each(|t| {
 			t.selected.remove_many(&opt.targets, false);
+			t.selected.catchup_revision(t.idx);
 		});
 
 		for u in &opt.targets {
@@ -68,4 +137,35 @@ impl Manager {
 		self.yanked.catchup_revision(false);
 		tasks.file_remove(opt.targets, opt.permanently);
 	}
+
+	/// The `targets` that can't go to the trash.
+	async fn untrashable(targets: &[Url]) -> Vec<Url> {
+		let targets = targets.to_vec();
+		let result = tokio::task::spawn_blocking(move || {
+			targets.into_iter().filter(|u| !yazi_scheduler::file::trashable(u)).collect()
+		});
+		result.await.unwrap_or_default()
+	}
+
+	// How many files and bytes are going to be removed, or at least, if counting
+	// them takes too long on a huge tree, and what happens to them
+	async fn remove_summary(targets: &[Url], permanently: bool, untrashable: usize) -> String {
+		let (stats, complete) = DirStats::total(targets, Duration::from_millis(500)).await;
+		let fate = if permanently || untrashable >= targets.len() {
+			"will be permanently deleted".to_owned()
+		} else if untrashable == 0 {
+			"will be moved to the trash".to_owned()
+		} else {
+			format!(
+				"will be removed, the following {untrashable} of which can't go to the trash and will be permanently deleted"
+			)
+		};
+		format!(
+			"{}{} file{}, {} {fate}",
+			if complete { "" } else { "At least " },
+			stats.files,
+			if stats.files == 1 { "" } else { "s" },
+			readable_size(stats.len),
+		)
+	}
 }
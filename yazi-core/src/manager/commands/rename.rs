@@ -1,17 +1,19 @@
 use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
-use tokio::fs;
-use yazi_config::popup::{ConfirmCfg, InputCfg};
-use yazi_dds::Pubsub;
-use yazi_proxy::{ConfirmProxy, InputProxy, TabProxy, WATCHER};
-use yazi_shared::{event::Cmd, fs::{File, FilesOp, Url, UrnBuf, maybe_exists, ok_or_not_found, paths_to_same_file, realname}};
+use yazi_config::{MANAGER, popup::{ConfirmCfg, InputCfg}};
+use yazi_dds::{Pubsub, body::BodyRename};
+use yazi_fs::{TAGS, Tags};
+use yazi_proxy::{AppProxy, ConfirmProxy, InputProxy, TabProxy, WATCHER};
+use yazi_shared::{event::Cmd, fs::{File, FilesOp, Url, UrnBuf, escape_non_utf8, ext_changed, long, maybe_exists, ok_or_not_found, paths_to_same_file, realname}};
 
 use crate::manager::Manager;
 
 struct Opt {
 	hovered: bool,
+	bulk:    bool,
 	force:   bool,
+	yes:     bool,
 	empty:   String,
 	cursor:  String,
 }
@@ -20,7 +22,9 @@ impl From<Cmd> for Opt {
 	fn from(mut c: Cmd) -> Self {
 		Self {
 			hovered: c.bool("hovered"),
+			bulk:    c.bool("bulk"),
 			force:   c.bool("force"),
+			yes:     c.bool("yes"),
 			empty:   c.take_str("empty").unwrap_or_default(),
 			cursor:  c.take_str("cursor").unwrap_or_default(),
 		}
@@ -33,12 +37,22 @@ impl Manager {
 		if !self.active_mut().try_escape_visual() {
 			return;
 		}
-		let Some(hovered) = self.hovered().map(|h| h.url_owned()) else {
+		let Some((hovered, is_dir)) = self.hovered().map(|h| (h.url_owned(), h.is_dir())) else {
 			return;
 		};
+		if Self::reject_archive("Rename", self.active().selected.keys().chain([&hovered])) {
+			return;
+		}
 
 		if !opt.hovered && !self.active().selected.is_empty() {
-			return self.bulk_rename();
+			return if opt.bulk { self.bulk_rename(opt.yes) } else { self.rename_selected(opt.yes) };
+		}
+
+		if hovered.name().to_str().is_none() {
+			return AppProxy::notify_warn(
+				"Rename",
+				format!("`{}` isn't valid UTF-8, use bulk rename instead", escape_non_utf8(hovered.name())),
+			);
 		}
 
 		let name = Self::empty_url_part(&hovered, &opt.empty);
@@ -65,6 +79,14 @@ impl Manager {
 			}
 
 			let new = Url::from(hovered.parent().unwrap().join(name));
+			if MANAGER.confirm_ext_change && !is_dir && ext_changed(&hovered, &new) {
+				let line =
+					format!("{} -> {}", escape_non_utf8(hovered.name()), escape_non_utf8(new.name()));
+				if !ConfirmProxy::show(ConfirmCfg::ext_change(vec![line])).await {
+					return;
+				}
+			}
+
 			if opt.force || !maybe_exists(&new).await || paths_to_same_file(&hovered, &new).await {
 				Self::rename_do(tab, hovered, new).await.ok();
 			} else if ConfirmProxy::show(ConfirmCfg::overwrite(&new)).await {
@@ -76,15 +98,21 @@ impl Manager {
 	async fn rename_do(tab: usize, old: Url, new: Url) -> Result<()> {
 		let Some((p_old, n_old)) = old.pair() else { return Ok(()) };
 		let Some((p_new, n_new)) = new.pair() else { return Ok(()) };
+		if !Pubsub::pre(BodyRename::dummy(tab, &old, &new)).await {
+			return Ok(());
+		}
+
 		let _permit = WATCHER.acquire().await.unwrap();
 
 		let overwritten = realname(&new).await;
-		fs::rename(&old, &new).await?;
+		long::rename(&old, &new).await?;
 
 		if let Some(o) = overwritten {
-			ok_or_not_found(fs::rename(p_new.join(&o), &new).await)?;
+			ok_or_not_found(long::rename(&p_new.join(&o), &new).await)?;
 			FilesOp::Deleting(p_new.clone(), HashSet::from_iter([UrnBuf::from(o)])).emit();
 		}
+		Tags::resolve([&old, &new]).await;
+		TAGS.write().rename(&old, &new);
 		Pubsub::pub_from_rename(tab, &old, &new);
 
 		let file = File::from(new.clone()).await?;
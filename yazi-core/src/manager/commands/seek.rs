@@ -19,6 +19,9 @@ impl Manager {
 		let Some(hovered) = self.hovered() else {
 			return self.active_mut().preview.reset();
 		};
+		if self.active().preview.seek(&hovered.url, opt.units) {
+			return;
+		}
 
 		let mime = if hovered.is_dir() {
 			MIME_DIR
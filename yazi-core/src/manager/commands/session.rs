@@ -0,0 +1,40 @@
+use yazi_macro::render;
+use yazi_proxy::{AppProxy, ManagerProxy};
+use yazi_shared::event::Cmd;
+
+use crate::manager::{Manager, Session};
+
+struct Opt {
+	action: String,
+	name:   String,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		Self {
+			action: c.take_first_str().unwrap_or_default(),
+			name:   c.take_str("1").unwrap_or_else(|| Session::LAST.to_owned()),
+		}
+	}
+}
+
+impl Manager {
+	#[yazi_codegen::command]
+	pub fn session(&mut self, opt: Opt) {
+		let result = match opt.action.as_str() {
+			"save" => Session::capture(&self.tabs).save(&opt.name),
+			"load" => Session::load(&opt.name).map(|s| {
+				self.active_mut().preview.reset_image();
+				self.tabs.fill(s);
+				ManagerProxy::refresh();
+				ManagerProxy::peek(true);
+				render!();
+			}),
+			_ => return,
+		};
+
+		if let Err(e) = result {
+			AppProxy::notify_warn("Session", e);
+		}
+	}
+}
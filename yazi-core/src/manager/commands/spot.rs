@@ -0,0 +1,39 @@
+use yazi_shared::event::{Cmd, Data};
+
+use crate::manager::Manager;
+
+struct Opt {
+	skip:    Option<usize>,
+	refresh: bool,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self {
+		Self { skip: c.first().and_then(Data::as_usize), refresh: c.bool("refresh") }
+	}
+}
+impl From<()> for Opt {
+	fn from(_: ()) -> Self { Self { skip: None, refresh: false } }
+}
+
+impl Manager {
+	#[yazi_codegen::command]
+	pub fn spot(&mut self, opt: Opt) {
+		if opt.refresh && !self.active().spot.visible() {
+			return;
+		}
+		let Some(hovered) = self.hovered().cloned() else {
+			return self.active_mut().spot.reset();
+		};
+
+		let mime = self.mimetype.get_owned(&hovered.url).unwrap_or_default();
+		let spot = &mut self.active_mut().spot;
+		if let Some(skip) = opt.skip {
+			spot.skip = skip;
+		} else if !spot.same_url(&hovered.url) {
+			spot.skip = 0;
+		}
+
+		spot.go(hovered, mime.into());
+	}
+}
@@ -1,12 +1,10 @@
-use yazi_boot::BOOT;
+use yazi_boot::{BOOT, MAX_TABS};
 use yazi_macro::render;
 use yazi_proxy::AppProxy;
 use yazi_shared::{event::{Cmd, Data}, fs::Url};
 
 use crate::{manager::Tabs, tab::Tab};
 
-const MAX_TABS: usize = 9;
-
 struct Opt {
 	url:     Url,
 	current: bool,
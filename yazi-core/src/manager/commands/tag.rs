@@ -0,0 +1,39 @@
+use yazi_fs::{TAGS, Tags};
+use yazi_proxy::{AppProxy, ManagerProxy};
+use yazi_shared::event::Cmd;
+
+use crate::manager::Manager;
+
+struct Opt {
+	name: String,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self { Self { name: c.take_first_str().unwrap_or_default() } }
+}
+
+impl Manager {
+	/// Toggles the tag given as the first argument on the selected files, which
+	/// is removed if all of them have it already, otherwise added to all of them.
+	#[yazi_codegen::command]
+	pub fn tag(&mut self, opt: Opt) {
+		if !self.active_mut().try_escape_visual() {
+			return;
+		}
+		if opt.name.is_empty() {
+			return AppProxy::notify_warn("Tag", "No tag name given, e.g. `tag keep`");
+		}
+
+		let targets: Vec<_> = self.selected_or_hovered(false).cloned().collect();
+		if targets.is_empty() || Self::reject_archive("Tag", targets.iter()) {
+			return;
+		}
+
+		tokio::spawn(async move {
+			Tags::resolve(&targets).await;
+			if TAGS.write().toggle(&targets, &opt.name) {
+				ManagerProxy::update_tagged();
+			}
+		});
+	}
+}
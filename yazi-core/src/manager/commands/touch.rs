@@ -0,0 +1,90 @@
+use std::{collections::HashMap, time::{Duration, SystemTime}};
+
+use anyhow::{Result, bail};
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+use filetime::FileTime;
+use tokio::task::spawn_blocking;
+use yazi_proxy::AppProxy;
+use yazi_shared::{event::Cmd, fs::{File, FilesOp, Url}};
+
+use crate::manager::Manager;
+
+struct Opt {
+	time: Option<String>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self { Self { time: c.take_str("time") } }
+}
+
+impl Manager {
+	/// Updates the access and modification time of the selected files to now, or
+	/// to `--time`, without creating any file like `create` does.
+	#[yazi_codegen::command]
+	pub fn touch(&mut self, opt: Opt) {
+		if !self.active_mut().try_escape_visual() {
+			return;
+		}
+
+		let time = match opt.time.as_deref().map(Self::parse_time).transpose() {
+			Ok(t) => t.unwrap_or_else(SystemTime::now),
+			Err(e) => return AppProxy::notify_warn("Touch", e),
+		};
+
+		let targets: Vec<_> = self.selected_or_hovered(false).cloned().collect();
+		if targets.is_empty() || Self::reject_archive("Touch", targets.iter()) {
+			return;
+		}
+
+		tokio::spawn(async move {
+			let mut failed = vec![];
+			for url in targets {
+				if let Err(e) = Self::touch_do(&url, time).await {
+					failed.push(format!("{}: {e}", url.name().to_string_lossy()));
+					continue;
+				}
+				if let (Some(parent), Ok(f)) = (url.parent_url(), File::from(url).await) {
+					FilesOp::Upserting(parent, HashMap::from_iter([(f.urn_owned(), f)])).emit();
+				}
+			}
+
+			if !failed.is_empty() {
+				AppProxy::notify_warn(
+					"Touch",
+					format!("Failed to touch {} file(s):\n{}", failed.len(), failed.join("\n")),
+				);
+			}
+		});
+	}
+
+	async fn touch_do(url: &Url, time: SystemTime) -> Result<()> {
+		// `utimensat` on Unix and `SetFileTime` on Windows, which work for directories
+		// too
+		let (path, time) = (url.to_path_buf(), FileTime::from_system_time(time));
+		Ok(spawn_blocking(move || filetime::set_file_times(path, time, time)).await??)
+	}
+
+	// Accepts `YYYY-MM-DD`, optionally followed by `HH:MM` or `HH:MM:SS` in the
+	// local time zone, or `@<seconds>` since the Unix epoch.
+	fn parse_time(s: &str) -> Result<SystemTime> {
+		let s = s.trim();
+		if let Some(secs) = s.strip_prefix('@') {
+			return Ok(SystemTime::UNIX_EPOCH + Duration::from_secs(secs.parse()?));
+		}
+
+		let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+			.iter()
+			.find_map(|f| NaiveDateTime::parse_from_str(s, f).ok())
+			.or_else(|| {
+				NaiveDate::parse_from_str(s, "%Y-%m-%d").ok().and_then(|d| d.and_hms_opt(0, 0, 0))
+			});
+
+		let Some(naive) = naive else {
+			bail!("Invalid time `{s}`, expected e.g. `2024-01-01 12:00` or `@1704110400`");
+		};
+		let Some(local) = Local.from_local_datetime(&naive).earliest() else {
+			bail!("Time `{s}` doesn't exist in the local time zone");
+		};
+		Ok(local.into())
+	}
+}
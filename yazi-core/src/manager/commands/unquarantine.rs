@@ -0,0 +1,39 @@
+use yazi_config::popup::ConfirmCfg;
+use yazi_proxy::{AppProxy, ConfirmProxy, ManagerProxy};
+use yazi_shared::{event::Cmd, fs::{XATTR_QUARANTINE, remove_xattr}};
+
+use crate::manager::Manager;
+
+struct Opt;
+
+impl From<Cmd> for Opt {
+	fn from(_: Cmd) -> Self { Self }
+}
+
+impl Manager {
+	/// Removes the quarantine attribute that macOS sets on downloaded files from
+	/// the hovered file after confirming, so that it can be run.
+	#[yazi_codegen::command]
+	pub fn unquarantine(&mut self, _: Opt) {
+		let Some(hovered) = self.hovered().map(|h| h.url_owned()) else {
+			return;
+		};
+		if Self::reject_archive("Unquarantine", [&hovered].into_iter()) {
+			return;
+		}
+
+		tokio::spawn(async move {
+			if !ConfirmProxy::show(ConfirmCfg::unquarantine(&hovered)).await {
+				return;
+			}
+
+			match remove_xattr(&hovered, XATTR_QUARANTINE).await {
+				Ok(()) => ManagerProxy::respot(),
+				Err(e) => AppProxy::notify_warn(
+					"Unquarantine",
+					format!("Failed to remove the quarantine attribute of {hovered}: {e}"),
+				),
+			}
+		});
+	}
+}
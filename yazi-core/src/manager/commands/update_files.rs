@@ -40,13 +40,16 @@ impl Manager {
 			Self::update_tab(self.active_mut(), Cow::Owned(op), tasks);
 		}
 
+		// The folders new to a tab have to take on its own settings, e.g. `hidden`
 		render!(self.yanked.catchup_revision(false));
-		self.active_mut().apply_files_attrs();
+		self.tabs.iter_mut().for_each(Tab::apply_files_attrs);
+		self.tabs.iter_mut().for_each(Tab::pub_filter_find);
 	}
 
 	fn update_tab(tab: &mut Tab, op: Cow<FilesOp>, tasks: &Tasks) {
 		let url = op.cwd();
 		tab.selected.apply_op(&op);
+		tab.selected.catchup_revision(tab.idx);
 
 		if url == tab.cwd() {
 			Self::update_current(tab, op, tasks);
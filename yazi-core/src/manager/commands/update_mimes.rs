@@ -4,20 +4,35 @@ use tracing::error;
 use yazi_macro::render;
 use yazi_shared::{event::Cmd, fs::Url};
 
-use crate::{manager::{LINKED, Manager}, tasks::Tasks};
+use crate::{manager::{LINKED, Manager, MimeCache}, tasks::Tasks};
 
 pub struct Opt {
-	updates: HashMap<String, String>,
+	updates: HashMap<Url, String>,
+	persist: bool,
 }
 
 impl TryFrom<Cmd> for Opt {
 	type Error = ();
 
 	fn try_from(mut c: Cmd) -> Result<Self, Self::Error> {
-		Ok(Self { updates: c.take("updates").ok_or(())?.into_dict_string() })
+		Ok(Self {
+			updates: c
+				.take("updates")
+				.ok_or(())?
+				.into_dict_string()
+				.into_iter()
+				.map(|(url, mime)| (Url::from(url), mime))
+				.collect(),
+			persist: true,
+		})
 	}
 }
 
+// Those from the `MimeCache`, which don't need to be saved again
+impl From<HashMap<Url, String>> for Opt {
+	fn from(updates: HashMap<Url, String>) -> Self { Self { updates, persist: false } }
+}
+
 impl Manager {
 	pub fn update_mimes(&mut self, opt: impl TryInto<Opt>, tasks: &Tasks) {
 		let Ok(opt) = opt.try_into() else {
@@ -28,7 +43,6 @@ impl Manager {
 		let updates = opt
 			.updates
 			.into_iter()
-			.map(|(url, mime)| (Url::from(url), mime))
 			.filter(|(url, mime)| self.mimetype.get(url) != Some(mime))
 			.fold(HashMap::new(), |mut map, (u, m)| {
 				for u in linked.from_file(&u) {
@@ -45,13 +59,16 @@ impl Manager {
 
 		let affected: Vec<_> = self
 			.current()
-			.paginate(self.current().page)
-			.iter()
+			.readahead()
+			.into_iter()
 			.filter(|&f| updates.contains_key(&f.url))
 			.cloned()
 			.collect();
 
 		let repeek = self.hovered().is_some_and(|f| updates.contains_key(&f.url));
+		if opt.persist {
+			MimeCache::persist(updates.iter().map(|(u, m)| (u.clone(), m.clone())));
+		}
 		self.mimetype.extend(updates);
 
 		if repeek {
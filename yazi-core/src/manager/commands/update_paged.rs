@@ -1,6 +1,9 @@
+use std::collections::HashMap;
+
+use yazi_config::MANAGER;
 use yazi_shared::{event::{Cmd, Data}, fs::Url};
 
-use crate::{manager::Manager, tasks::Tasks};
+use crate::{manager::{MIME_CACHE, Manager}, tasks::Tasks};
 
 #[derive(Default)]
 pub struct Opt {
@@ -31,8 +34,28 @@ impl Manager {
 			return;
 		}
 
-		let targets = self.current().paginate(opt.page.unwrap_or(self.current().page));
-		tasks.fetch_paged(targets, &self.mimetype);
-		tasks.preload_paged(targets, &self.mimetype);
+		let page = opt.page.unwrap_or(self.current().page);
+		let cached: HashMap<_, _> = {
+			let mut cache = MIME_CACHE.lock();
+			self
+				.current()
+				.paginate_block(page)
+				.iter()
+				.filter(|&f| !f.is_dir() && !self.mimetype.contains(&f.url))
+				.filter_map(|f| Some((f.url_owned(), cache.get(f)?.to_owned())))
+				.collect()
+		};
+		if !cached.is_empty() {
+			self.update_mimes(cached, tasks);
+		}
+
+		// Fetchers are cheap per file, so they run over a block of pages at once
+		tasks.fetch_paged(self.current().paginate_block(page), &self.mimetype);
+
+		tasks.preload_retain(self.current().readahead_retained());
+		tasks.preload_paged(self.current().readahead(), &self.mimetype);
+		if MANAGER.calc_size {
+			tasks.prework_size(&self.current().files, self.current().paginate(page).iter());
+		}
 	}
 }
@@ -0,0 +1,14 @@
+use yazi_macro::render;
+use yazi_shared::event::Cmd;
+
+use crate::manager::Manager;
+
+impl Manager {
+	/// Re-renders after the tags changed, applying the tag filters again.
+	pub fn update_tagged(&mut self, _: Cmd) {
+		for tab in self.tabs.iter_mut() {
+			tab.refilter_tagged();
+		}
+		render!();
+	}
+}
@@ -9,6 +9,7 @@ use crate::manager::{Manager, Yanked};
 pub struct Opt {
 	cut:  bool,
 	urls: HashSet<Url>,
+	ts:   u64,
 }
 
 impl TryFrom<Cmd> for Opt {
@@ -16,7 +17,7 @@ impl TryFrom<Cmd> for Opt {
 
 	fn try_from(mut c: Cmd) -> Result<Self, Self::Error> {
 		if let Some(iter) = c.take_any::<yazi_dds::body::BodyYankIter>("urls") {
-			Ok(Self { urls: iter.urls.into_iter().collect(), cut: iter.cut })
+			Ok(Self { urls: iter.urls.into_iter().collect(), cut: iter.cut, ts: iter.ts })
 		} else {
 			Err(())
 		}
@@ -31,7 +32,13 @@ impl Manager {
 			return;
 		}
 
-		self.yanked = Yanked::new(opt.cut, opt.urls);
+		// The last write wins if both instances have yanked something, a timestamp
+		// of 0 is unknown, e.g. from an older instance, and taken as the latest
+		if opt.ts != 0 && opt.ts < self.yanked.ts() {
+			return;
+		}
+
+		self.yanked = Yanked::new(opt.cut, opt.urls).with_ts(opt.ts);
 		render!();
 	}
 }
@@ -0,0 +1,195 @@
+use std::{collections::HashMap, fs, path::{Path, PathBuf}};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use yazi_boot::BOOT;
+use yazi_config::{MANAGER, manager::{ShowHidden, SortOpts}};
+use yazi_shared::{RoCell, fs::{Url, write_atomic}};
+
+pub static DIR_VIEWS: RoCell<Mutex<DirViews>> = RoCell::new();
+
+/// The `sort`, `hidden` and `linemode` last used in each directory, which are
+/// saved to `<state_dir>/views.json` on quit, and reapplied on entering the
+/// directory again.
+///
+/// The least recently used directories are dropped beyond
+/// `manager.remember_views`, where 0 disables it.
+#[derive(Default)]
+pub struct DirViews {
+	entries: HashMap<PathBuf, DirView>,
+	limit:   usize,
+	tick:    u64,
+	dirty:   bool,
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct DirView {
+	#[serde(default, skip_serializing_if = "SortOpts::is_empty")]
+	pub sort:     SortOpts,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub hidden:   Option<ShowHidden>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub linemode: Option<String>,
+	#[serde(skip)]
+	used:         u64,
+}
+
+impl DirViews {
+	/// Loads the saved views, which is done before any tab is created so that
+	/// the startup directories get theirs as well.
+	pub fn load() -> Self {
+		let mut me = Self { limit: MANAGER.remember_views, ..Default::default() };
+		if me.limit == 0 {
+			return me;
+		}
+
+		let Ok(Ok(saved)) =
+			fs::read(Self::path()).map(|b| serde_json::from_slice::<Vec<(PathBuf, DirView)>>(&b))
+		else {
+			return me;
+		};
+
+		for (path, mut view) in saved {
+			me.tick += 1;
+			view.used = me.tick;
+			me.entries.insert(path, view);
+		}
+		me.evict();
+		me
+	}
+
+	pub fn save(&mut self) -> Result<()> {
+		if !self.dirty {
+			return Ok(());
+		}
+
+		let mut entries: Vec<_> = self.entries.iter().collect();
+		entries.sort_unstable_by_key(|(_, v)| v.used);
+
+		let path = Self::path();
+		fs::create_dir_all(path.parent().unwrap())?;
+		write_atomic(&path, serde_json::to_vec(&entries)?)?;
+
+		self.dirty = false;
+		Ok(())
+	}
+
+	/// The view last used in the directory at `url`.
+	pub fn get(&self, url: &Url) -> Option<&DirView> {
+		if self.entries.is_empty() {
+			return None;
+		}
+		self.entries.get(Self::key(url)?)
+	}
+
+	/// Marks the view of the directory at `url` as used, after entering it.
+	pub fn touch(&mut self, url: &Url) -> Option<&DirView> {
+		let view = self.entries.get_mut(Self::key(url)?)?;
+		self.tick += 1;
+		view.used = self.tick;
+		Some(view)
+	}
+
+	/// Changes the view of the directory at `url` with `f`.
+	pub fn record(&mut self, url: &Url, f: impl FnOnce(&mut DirView)) {
+		if self.limit == 0 {
+			return;
+		}
+		let Some(key) = Self::key(url) else { return };
+
+		self.tick += 1;
+		let view = self.entries.entry(key.to_owned()).or_default();
+		f(view);
+		view.used = self.tick;
+
+		self.dirty = true;
+		self.evict();
+	}
+
+	/// Forgets the sorting of the directory at `url`, along with its view if
+	/// nothing else is left in it, returns whether it had one.
+	pub fn forget_sort(&mut self, url: &Url) -> bool {
+		let Some(key) = Self::key(url) else { return false };
+		let Some(view) = self.entries.get_mut(key) else { return false };
+
+		let had = !view.sort.is_empty();
+		view.sort = SortOpts::default();
+		if view.hidden.is_none() && view.linemode.is_none() {
+			self.entries.remove(key);
+		}
+
+		self.dirty |= had;
+		had
+	}
+
+	fn evict(&mut self) {
+		while self.entries.len() > self.limit {
+			let Some(oldest) = self.entries.iter().min_by_key(|(_, v)| v.used).map(|(p, _)| p.clone())
+			else {
+				break;
+			};
+			self.entries.remove(&oldest);
+			self.dirty = true;
+		}
+	}
+
+	#[inline]
+	fn key(url: &Url) -> Option<&Path> { url.is_regular().then(|| url.as_path()) }
+
+	#[inline]
+	fn path() -> PathBuf { BOOT.state_dir.join("views.json") }
+}
+
+#[cfg(test)]
+mod tests {
+	use yazi_config::manager::SortBy;
+
+	use super::*;
+
+	fn views(limit: usize) -> DirViews { DirViews { limit, ..Default::default() } }
+
+	#[test]
+	fn test_evict() {
+		let mut v = views(2);
+		let by = |b| move |v: &mut DirView| v.sort.sort_by = Some(b);
+		v.record(&Url::from("/a"), by(SortBy::Mtime));
+		v.record(&Url::from("/b"), by(SortBy::Size));
+
+		// Entering `/a` again makes `/b` the least recently used
+		assert!(v.touch(&Url::from("/a")).is_some());
+		v.record(&Url::from("/c"), by(SortBy::Natural));
+		assert!(v.get(&Url::from("/b")).is_none());
+		assert_eq!(v.get(&Url::from("/a")).unwrap().sort.sort_by, Some(SortBy::Mtime));
+		assert_eq!(v.get(&Url::from("/c")).unwrap().sort.sort_by, Some(SortBy::Natural));
+
+		// Nothing is remembered if disabled, nor for a search
+		let mut v = views(0);
+		v.record(&Url::from("/a"), by(SortBy::Mtime));
+		assert!(v.get(&Url::from("/a")).is_none());
+
+		let mut v = views(2);
+		v.record(&Url::from("/a").into_search("x"), by(SortBy::Mtime));
+		assert!(v.entries.is_empty());
+	}
+
+	#[test]
+	fn test_forget_sort() {
+		let mut v = views(10);
+		v.record(&Url::from("/a"), |v| {
+			v.sort.reverse = Some(true);
+			v.hidden = Some(ShowHidden::Show);
+		});
+		v.record(&Url::from("/b"), |v| v.sort.reverse = Some(true));
+
+		// The hidden mode of `/a` stays, while `/b` has nothing left
+		assert!(v.forget_sort(&Url::from("/a")));
+		assert!(v.forget_sort(&Url::from("/b")));
+		assert!(!v.forget_sort(&Url::from("/a")));
+
+		let a = v.get(&Url::from("/a")).unwrap();
+		assert!(a.sort.is_empty());
+		assert_eq!(a.hidden, Some(ShowHidden::Show));
+		assert!(v.get(&Url::from("/b")).is_none());
+	}
+}
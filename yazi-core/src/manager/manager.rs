@@ -1,8 +1,11 @@
+use std::path::PathBuf;
+
 use ratatui::layout::Rect;
 use yazi_adapter::Dimension;
-use yazi_config::popup::{Origin, Position};
+use yazi_config::{MANAGER, popup::{InputCfg, Origin, Position}};
 use yazi_fs::Folder;
-use yazi_shared::fs::{File, Url};
+use yazi_proxy::{AppProxy, InputProxy};
+use yazi_shared::fs::{File, Url, escape_non_utf8};
 
 use super::{Mimetype, Tabs, Watcher, Yanked};
 use crate::tab::Tab;
@@ -35,6 +38,50 @@ impl Manager {
 	}
 
 	pub fn shutdown(&mut self) { self.tabs.iter_mut().for_each(|t| t.shutdown()); }
+
+	/// Warns and returns `true` if any of the `urls` is inside an archive, as
+	/// they're read-only for now.
+	pub(super) fn reject_archive<'a>(op: &str, mut urls: impl Iterator<Item = &'a Url>) -> bool {
+		if !urls.any(|u| u.is_archive()) {
+			return false;
+		}
+		AppProxy::notify_warn(op, "Archives are read-only, files can only be copied out of them");
+		true
+	}
+
+	/// The protected paths that any of the `urls` is, is under, or has under it,
+	/// as a directory takes everything in it along.
+	pub(super) fn protected<'a>(urls: impl Iterator<Item = &'a Url>) -> Vec<PathBuf> {
+		let mut paths: Vec<_> = urls
+			.flat_map(|u| match MANAGER.protected.of(u) {
+				Some(p) => vec![p.to_owned()],
+				None => MANAGER.protected.under(u),
+			})
+			.collect();
+		paths.sort_unstable();
+		paths.dedup();
+		paths
+	}
+
+	/// Asks to type the name of each of the `protected` paths, and returns
+	/// whether all of them were typed correctly.
+	pub(super) async fn confirm_protected(op: &str, protected: &[PathBuf]) -> bool {
+		for path in protected {
+			let name = escape_non_utf8(path.file_name().unwrap_or(path.as_os_str()));
+			match InputProxy::show(InputCfg::protected(&name)).recv().await {
+				Some(Ok(s)) if s == name => {}
+				Some(Ok(_)) => {
+					AppProxy::notify_warn(
+						op,
+						format!("The name of `{}` doesn't match", escape_non_utf8(path.as_os_str())),
+					);
+					return false;
+				}
+				_ => return false,
+			}
+		}
+		true
+	}
 }
 
 impl Manager {
@@ -0,0 +1,173 @@
+use std::{collections::HashMap, fs, path::PathBuf, time::{SystemTime, UNIX_EPOCH}};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::task::spawn_blocking;
+use yazi_boot::BOOT;
+use yazi_config::TASKS;
+use yazi_proxy::ManagerProxy;
+use yazi_shared::{RoCell, fs::{File, Url, UrlScheme}};
+
+pub static MIME_CACHE: RoCell<Mutex<MimeCache>> = RoCell::new();
+
+/// The mimetypes detected in the previous runs, which are saved to
+/// `<state_dir>/mimes.json` on quit, so revisiting a directory doesn't have to
+/// detect them again.
+///
+/// An entry is only used while the size and mtime of its file are unchanged,
+/// and the least recently used ones are dropped beyond `tasks.mime_cache`.
+#[derive(Default)]
+pub struct MimeCache {
+	entries: HashMap<PathBuf, MimeEntry>,
+	tick:    u64,
+	dirty:   bool,
+}
+
+#[derive(Deserialize, Serialize)]
+struct MimeEntry {
+	len:   u64,
+	mtime: Option<(u64, u32)>,
+	mime:  String,
+	#[serde(skip)]
+	used:  u64,
+}
+
+impl MimeCache {
+	/// Loads the saved entries in the background, and applies them to the
+	/// visible files once done.
+	pub async fn load() {
+		if TASKS.mime_cache == 0 {
+			return;
+		}
+
+		let Ok(Ok(saved)) = spawn_blocking(|| -> Result<Vec<(PathBuf, MimeEntry)>> {
+			Ok(serde_json::from_slice(&fs::read(Self::path())?)?)
+		})
+		.await
+		else {
+			return;
+		};
+
+		{
+			// They're older than any entry that's been used since the startup
+			let mut me = MIME_CACHE.lock();
+			let n = saved.len() as u64;
+			me.tick += n;
+			me.entries.values_mut().for_each(|e| e.used += n);
+
+			for (i, (path, mut entry)) in saved.into_iter().enumerate() {
+				entry.used = i as u64;
+				me.entries.entry(path).or_insert(entry);
+			}
+			me.evict();
+		}
+		ManagerProxy::update_paged();
+	}
+
+	pub fn save(&mut self) -> Result<()> {
+		if !self.dirty {
+			return Ok(());
+		}
+
+		let mut entries: Vec<_> = self.entries.iter().collect();
+		entries.sort_unstable_by_key(|(_, e)| e.used);
+
+		let path = Self::path();
+		fs::create_dir_all(path.parent().unwrap())?;
+
+		let tmp = path.with_extension("json.tmp");
+		fs::write(&tmp, serde_json::to_vec(&entries)?)?;
+		fs::rename(tmp, path)?;
+
+		self.dirty = false;
+		Ok(())
+	}
+
+	/// The mimetype of `file`, if it's been detected before and the file hasn't
+	/// been modified since then.
+	pub fn get(&mut self, file: &File) -> Option<&str> {
+		let path = Self::key(&file.url)?;
+		let fresh =
+			self.entries.get(path).map(|e| e.len == file.len && e.mtime == Self::stamp(file.mtime))?;
+
+		if !fresh {
+			self.entries.remove(path);
+			self.dirty = true;
+			return None;
+		}
+
+		self.tick += 1;
+		let entry = self.entries.get_mut(path)?;
+		entry.used = self.tick;
+		Some(&entry.mime)
+	}
+
+	/// Remembers the mimetypes just detected, along with the size and mtime of
+	/// their files, which are read in the background.
+	pub fn persist(updates: impl IntoIterator<Item = (Url, String)>) {
+		if TASKS.mime_cache == 0 {
+			return;
+		}
+
+		let todo: Vec<_> = updates
+			.into_iter()
+			.filter_map(|(u, m)| Some((Self::key(&u)?.to_owned(), m)))
+			.filter(|(p, _)| p.to_str().is_some())
+			.collect();
+		if todo.is_empty() {
+			return;
+		}
+
+		tokio::spawn(async move {
+			let mut done = Vec::with_capacity(todo.len());
+			for (path, mime) in todo {
+				if let Ok(meta) = tokio::fs::metadata(&path).await {
+					let entry =
+						MimeEntry { len: meta.len(), mtime: Self::stamp(meta.modified().ok()), mime, used: 0 };
+					done.push((path, entry));
+				}
+			}
+
+			let mut me = MIME_CACHE.lock();
+			for (path, mut entry) in done {
+				me.tick += 1;
+				entry.used = me.tick;
+				me.entries.insert(path, entry);
+			}
+			me.dirty = true;
+			me.evict();
+		});
+	}
+
+	// Drops the least recently used entries in bulk once there're a tenth more
+	// than allowed, rather than one at a time.
+	fn evict(&mut self) {
+		let cap = TASKS.mime_cache;
+		if self.entries.len() <= cap + cap / 10 {
+			return;
+		}
+
+		let mut used: Vec<_> = self.entries.values().map(|e| e.used).collect();
+		let n = used.len() - cap;
+		let threshold = *used.select_nth_unstable(n).1;
+
+		self.entries.retain(|_, e| e.used >= threshold);
+		self.dirty = true;
+	}
+
+	fn key(url: &Url) -> Option<&std::path::Path> {
+		match url.scheme() {
+			UrlScheme::Regular | UrlScheme::SearchItem => Some(url.as_path()),
+			UrlScheme::Search | UrlScheme::Archive => None,
+		}
+	}
+
+	#[inline]
+	fn stamp(time: Option<SystemTime>) -> Option<(u64, u32)> {
+		time?.duration_since(UNIX_EPOCH).ok().map(|d| (d.as_secs(), d.subsec_nanos()))
+	}
+
+	#[inline]
+	fn path() -> PathBuf { BOOT.state_dir.join("mimes.json") }
+}
@@ -1,3 +1,3 @@
 yazi_macro::mod_pub!(commands);
 
-yazi_macro::mod_flat!(linked manager mimetype tabs watcher yanked);
+yazi_macro::mod_flat!(dir_views linked manager mime_cache mimetype recent_dirs rename_template session tabs watcher yanked);
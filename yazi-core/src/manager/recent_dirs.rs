@@ -0,0 +1,173 @@
+use std::{collections::HashMap, fs, path::{Path, PathBuf}};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use yazi_boot::BOOT;
+use yazi_config::MANAGER;
+use yazi_shared::{RoCell, fs::{Url, write_atomic}, timestamp_us};
+
+pub static RECENT_DIRS: RoCell<Mutex<RecentDirs>> = RoCell::new();
+
+/// The directories visited in any tab, which are saved to
+/// `<state_dir>/recent.json` on quit, and ranked by their frecency for
+/// `cd --recent`.
+///
+/// The lowest ranked ones are dropped beyond `manager.recent_dirs`, where 0
+/// disables it, and those gone are dropped once they're found to be.
+#[derive(Default)]
+pub struct RecentDirs {
+	entries: HashMap<PathBuf, RecentDir>,
+	dirty:   bool,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize)]
+struct RecentDir {
+	visits: u32,
+	// In seconds since the Unix epoch
+	last:   u64,
+}
+
+impl RecentDirs {
+	pub fn load() -> Self {
+		if MANAGER.recent_dirs == 0 {
+			return Self::default();
+		}
+
+		let entries =
+			fs::read(Self::path()).ok().and_then(|b| serde_json::from_slice(&b).ok()).unwrap_or_default();
+		Self { entries, dirty: false }
+	}
+
+	pub fn save(&mut self) -> Result<()> {
+		if !self.dirty {
+			return Ok(());
+		}
+
+		let path = Self::path();
+		fs::create_dir_all(path.parent().unwrap())?;
+		write_atomic(&path, serde_json::to_vec(&self.entries)?)?;
+
+		self.dirty = false;
+		Ok(())
+	}
+
+	/// Counts a visit to the directory at `url`.
+	pub fn visit(&mut self, url: &Url) {
+		if MANAGER.recent_dirs == 0 || !url.is_regular() {
+			return;
+		}
+
+		let entry = self.entries.entry(url.to_path_buf()).or_insert(RecentDir { visits: 0, last: 0 });
+		entry.visits = entry.visits.saturating_add(1);
+		entry.last = Self::now();
+
+		self.dirty = true;
+		self.evict();
+	}
+
+	pub fn forget(&mut self, path: &Path) { self.dirty |= self.entries.remove(path).is_some(); }
+
+	/// The directories matching `query`, with those whose name contains it
+	/// first, and then those whose path has its characters in order, each
+	/// ranked by frecency.
+	pub fn matches(&self, query: &str) -> Vec<PathBuf> {
+		let now = Self::now();
+		let mut matched: Vec<_> = self
+			.entries
+			.iter()
+			.filter_map(|(p, e)| Some((Self::tier(query, p)?, e.score(now), p)))
+			.collect();
+
+		matched.sort_unstable_by(|a, b| a.0.cmp(&b.0).then(b.1.total_cmp(&a.1)).then(a.2.cmp(b.2)));
+		matched.into_iter().map(|(_, _, p)| p.clone()).collect()
+	}
+
+	// Matches case-insensitively unless `query` contains an uppercase letter
+	fn tier(query: &str, path: &Path) -> Option<u8> {
+		let (query, path) = if query.chars().any(char::is_uppercase) {
+			(query.to_owned(), path.to_string_lossy().into_owned())
+		} else {
+			(query.to_lowercase(), path.to_string_lossy().to_lowercase())
+		};
+
+		let name = path.rsplit(std::path::MAIN_SEPARATOR).next().unwrap_or_default();
+		if name.contains(&query) {
+			return Some(0);
+		}
+
+		let mut chars = path.chars();
+		query.chars().all(|q| chars.any(|c| c == q)).then_some(1)
+	}
+
+	// Drops the lowest ranked ones in bulk once there're a tenth more than
+	// allowed, rather than one at a time.
+	fn evict(&mut self) {
+		let cap = MANAGER.recent_dirs;
+		if self.entries.len() <= cap + cap / 10 {
+			return;
+		}
+
+		let now = Self::now();
+		let mut scores: Vec<_> = self.entries.values().map(|e| e.score(now)).collect();
+		let n = scores.len() - cap;
+		let threshold = *scores.select_nth_unstable_by(n, f64::total_cmp).1;
+
+		self.entries.retain(|_, e| e.score(now) >= threshold);
+	}
+
+	#[inline]
+	fn now() -> u64 { timestamp_us() / 1_000_000 }
+
+	#[inline]
+	fn path() -> PathBuf { BOOT.state_dir.join("recent.json") }
+}
+
+impl RecentDir {
+	// The visits, weighted by how long ago the last one was
+	fn score(self, now: u64) -> f64 {
+		let weight = match now.saturating_sub(self.last) {
+			..3600 => 4.0,
+			3600..86400 => 2.0,
+			86400..604800 => 0.5,
+			_ => 0.25,
+		};
+		self.visits as f64 * weight
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_matches() {
+		let now = RecentDirs::now();
+		let mut dirs = RecentDirs::default();
+		for (path, visits, ago) in [
+			("/home/a/code", 3, 60),
+			("/home/a/code/yazi", 10, 30 * 86400),
+			("/home/a/Documents", 1, 60),
+			("/tmp/cache", 50, 60),
+		] {
+			dirs.entries.insert(PathBuf::from(path), RecentDir { visits, last: now - ago });
+		}
+
+		let paths =
+			|q| dirs.matches(q).into_iter().map(|p| p.to_string_lossy().into_owned()).collect::<Vec<_>>();
+
+		// The names first, then the paths with the characters in order
+		assert_eq!(paths("code"), ["/home/a/code", "/home/a/code/yazi"]);
+		assert_eq!(paths("c"), [
+			"/tmp/cache",
+			"/home/a/code",
+			"/home/a/Documents",
+			"/home/a/code/yazi"
+		]);
+
+		// 3 visits a minute ago outrank 10 a month ago
+		assert_eq!(paths("hacd"), ["/home/a/code", "/home/a/code/yazi"]);
+		assert!(paths("Code").is_empty());
+		assert_eq!(paths("").len(), 4);
+	}
+}
@@ -0,0 +1,130 @@
+use std::{path::Path, str::FromStr, time::SystemTime};
+
+use anyhow::{Result, bail};
+use chrono::{DateTime, Local, format::{Item, StrftimeItems}};
+
+/// A template to rename the selected files by, with the tokens:
+///
+/// - `{n}`, `{n:03}` or `{n:03:10}`: the counter, optionally zero-padded to a
+///   width, and starting at 1 unless given
+/// - `{name}`: the file name without the extension
+/// - `{ext}`: the extension, without the dot
+/// - `{date}` or `{date:%Y%m%d}`: the mtime, `%Y-%m-%d` unless formatted
+///
+/// `{{` and `}}` stand for literal braces.
+#[derive(Debug, PartialEq)]
+pub struct RenameTemplate(Vec<Part>);
+
+#[derive(Debug, PartialEq)]
+enum Part {
+	Text(String),
+	Counter { width: usize, start: usize },
+	Name,
+	Ext,
+	Date(String),
+}
+
+impl FromStr for RenameTemplate {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (mut parts, mut text) = (vec![], String::new());
+		let mut it = s.chars().peekable();
+		while let Some(c) = it.next() {
+			match c {
+				'{' if it.peek() == Some(&'{') => _ = (it.next(), text.push('{')),
+				'}' if it.peek() == Some(&'}') => _ = (it.next(), text.push('}')),
+				'}' => bail!("Unmatched `}}` in the template"),
+				'{' => {
+					let mut token = String::new();
+					loop {
+						match it.next() {
+							Some('}') => break,
+							Some(c) => token.push(c),
+							None => bail!("Unclosed `{{` in the template"),
+						}
+					}
+					if !text.is_empty() {
+						parts.push(Part::Text(std::mem::take(&mut text)));
+					}
+					parts.push(Part::parse(&token)?);
+				}
+				c => text.push(c),
+			}
+		}
+
+		if !text.is_empty() {
+			parts.push(Part::Text(text));
+		}
+		Ok(Self(parts))
+	}
+}
+
+impl Part {
+	fn parse(token: &str) -> Result<Self> {
+		let (key, arg) = token.split_once(':').map_or((token, None), |(k, a)| (k, Some(a)));
+		Ok(match (key, arg) {
+			("n", None) => Self::Counter { width: 0, start: 1 },
+			("n", Some(arg)) => {
+				let (width, start) = arg.split_once(':').unwrap_or((arg, ""));
+				Self::Counter {
+					width: if width.is_empty() { 0 } else { width.parse()? },
+					start: if start.is_empty() { 1 } else { start.parse()? },
+				}
+			}
+			("name", None) => Self::Name,
+			("ext", None) => Self::Ext,
+			("date", None) => Self::Date("%Y-%m-%d".to_owned()),
+			("date", Some(fmt)) => {
+				if StrftimeItems::new(fmt).any(|i| i == Item::Error) {
+					bail!("Invalid date format `{fmt}` in the template");
+				}
+				Self::Date(fmt.to_owned())
+			}
+			_ => bail!("Unknown token `{{{token}}}` in the template"),
+		})
+	}
+}
+
+impl RenameTemplate {
+	/// The new name of the `i`th file, counted from 0, whose path is `path` and
+	/// modification time is `mtime`.
+	pub fn apply(&self, i: usize, path: &Path, mtime: Option<SystemTime>) -> String {
+		let mut s = String::new();
+		for part in &self.0 {
+			match part {
+				Part::Text(t) => s.push_str(t),
+				Part::Counter { width, start } => s.push_str(&format!("{:0width$}", start + i)),
+				Part::Name => s.push_str(&path.file_stem().unwrap_or_default().to_string_lossy()),
+				Part::Ext => s.push_str(&path.extension().unwrap_or_default().to_string_lossy()),
+				Part::Date(fmt) => {
+					if let Some(t) = mtime {
+						s.push_str(&DateTime::<Local>::from(t).format(fmt).to_string());
+					}
+				}
+			}
+		}
+		s
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_apply() {
+		let apply =
+			|t: &str, i, p: &str| t.parse::<RenameTemplate>().unwrap().apply(i, Path::new(p), None);
+
+		assert_eq!(apply("photo-{n:03}.{ext}", 0, "/a/IMG_1234.JPG"), "photo-001.JPG");
+		assert_eq!(apply("{name}_{n::0}", 4, "b.tar.gz"), "b.tar_4");
+		assert_eq!(apply("{n:2:9}-{name}", 1, "c"), "10-c");
+		assert_eq!(apply("{{{name}}}", 0, "d.txt"), "{d}");
+
+		assert!("{n:x}".parse::<RenameTemplate>().is_err());
+		assert!("{size}".parse::<RenameTemplate>().is_err());
+		assert!("{name".parse::<RenameTemplate>().is_err());
+		assert!("a}".parse::<RenameTemplate>().is_err());
+	}
+}
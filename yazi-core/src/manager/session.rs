@@ -0,0 +1,203 @@
+use std::{fs, path::{Path, PathBuf}, time::Duration};
+
+use anyhow::{Result, bail};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::{pin, sync::mpsc, task::spawn_blocking};
+use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
+use tracing::warn;
+use yazi_boot::{ARGS, BOOT};
+use yazi_config::manager::{ShowHidden, SortBy, SortOpts};
+use yazi_fs::{Filter, FilterCase};
+use yazi_shared::{Debounce, fs::{Url, write_atomic}};
+
+use super::Tabs;
+use crate::tab::Tab;
+
+static AUTOSAVE_TX: Mutex<Option<mpsc::UnboundedSender<Session>>> = Mutex::new(None);
+// Held while autosaving, and set once stopped, so none is written after quit
+static AUTOSAVE_STOPPED: Mutex<bool> = Mutex::new(false);
+
+/// The layout of the tabs, which is saved to
+/// `<state_dir>/sessions/<name>.json`.
+///
+/// Selection and yanked files aren't included.
+#[derive(Deserialize, Serialize)]
+pub struct Session {
+	pub cursor: usize,
+	pub tabs:   Vec<SessionTab>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct SessionTab {
+	pub cwd:     Url,
+	pub hovered: Option<Url>,
+
+	pub sort_by:        SortBy,
+	pub sort_sensitive: bool,
+	pub sort_reverse:   bool,
+	pub sort_dir_first: bool,
+	pub sort_translit:  bool,
+	#[serde(default)]
+	pub sort_by_locale: bool,
+	#[serde(default)]
+	pub sort_runtime:   SortOpts,
+	pub linemode:       String,
+	pub show_hidden:    ShowHidden,
+
+	pub filter:      Option<String>,
+	pub filter_case: String,
+}
+
+impl Session {
+	/// The session saved on every change of the CWD, and restored by
+	/// `--recover`.
+	pub const AUTOSAVE: &'static str = "autosave";
+	/// The session saved on quit, and restored by `--restore-session`.
+	pub const LAST: &'static str = "last";
+
+	pub fn capture(tabs: &Tabs) -> Self {
+		let cursor = tabs.cursor;
+		let tabs = tabs
+			.iter()
+			.map(|tab| {
+				let filter = tab.current.files.filter();
+				SessionTab {
+					cwd:     tab.cwd().clone(),
+					hovered: tab.hovered().map(|h| h.url_owned()),
+
+					sort_by:        tab.conf.sort_by,
+					sort_sensitive: tab.conf.sort_sensitive,
+					sort_reverse:   tab.conf.sort_reverse,
+					sort_dir_first: tab.conf.sort_dir_first,
+					sort_translit:  tab.conf.sort_translit,
+					sort_by_locale: tab.conf.sort_by_locale,
+					sort_runtime:   tab.conf.sort_runtime,
+					linemode:       tab.conf.linemode.clone(),
+					show_hidden:    tab.conf.show_hidden,
+
+					filter:      filter.map(|f| f.tagged().map_or_else(|| f.to_string(), ToOwned::to_owned)),
+					filter_case: match filter.map(|f| (f.tagged().is_some(), f.case())).unwrap_or_default() {
+						(true, _) => "tag",
+						(_, FilterCase::Smart) => "smart",
+						(_, FilterCase::Sensitive) => "sensitive",
+						(_, FilterCase::Insensitive) => "insensitive",
+					}
+					.to_owned(),
+				}
+			})
+			.collect();
+
+		Self { cursor, tabs }
+	}
+
+	pub fn save(&self, name: &str) -> Result<()> {
+		let path = Self::path(name)?;
+		fs::create_dir_all(path.parent().unwrap())?;
+
+		Ok(write_atomic(&path, serde_json::to_vec(self)?)?)
+	}
+
+	/// Saves the `tabs` as the [`Self::AUTOSAVE`] session, and writes the CWD to
+	/// `--cwd-file`, once they stop changing for a moment, so that both survive
+	/// yazi getting killed.
+	pub fn autosave(tabs: &Tabs) {
+		let mut tx = AUTOSAVE_TX.lock();
+		let tx = tx.get_or_insert_with(|| {
+			let (tx, rx) = mpsc::unbounded_channel::<Self>();
+			tokio::spawn(async move {
+				let rx = Debounce::new(UnboundedReceiverStream::new(rx), Duration::from_millis(500));
+				pin!(rx);
+
+				while let Some(session) = rx.next().await {
+					spawn_blocking(move || session.autosave_do()).await.ok();
+				}
+			});
+			tx
+		});
+		tx.send(Self::capture(tabs)).ok();
+	}
+
+	/// Drops the pending autosave, and waits for the one being written if any,
+	/// so that those written on quit aren't overwritten afterwards.
+	pub fn autosave_stop() {
+		*AUTOSAVE_STOPPED.lock() = true;
+		AUTOSAVE_TX.lock().take();
+	}
+
+	fn autosave_do(&self) {
+		let stopped = AUTOSAVE_STOPPED.lock();
+		if *stopped {
+			return;
+		}
+
+		if let Err(e) = self.save(Self::AUTOSAVE) {
+			warn!("Failed to autosave the session: {e}");
+		}
+
+		// Only regular files, as writing a FIFO would wait for a reader
+		let Some(tab) = self.tabs.get(self.cursor) else { return };
+		let regular = |p: &Path| fs::symlink_metadata(p).map_or(true, |m| m.is_file());
+		if let Some(p) = ARGS.cwd_file.as_deref().filter(|&p| regular(p)) {
+			write_atomic(p, tab.cwd.as_os_str().as_encoded_bytes()).ok();
+		}
+		if let Some(p) = ARGS.hovered_file.as_deref().filter(|&p| regular(p)) {
+			let hovered = tab.hovered.as_ref().map(|u| u.as_os_str()).unwrap_or_default();
+			write_atomic(p, hovered.as_encoded_bytes()).ok();
+		}
+	}
+
+	/// Loads the session `name`, skipping the tabs whose directory no longer
+	/// exists.
+	pub fn load(name: &str) -> Result<Self> {
+		let mut me: Self = serde_json::from_slice(&fs::read(Self::path(name)?)?)?;
+		me.tabs.retain(|t| !t.cwd.is_regular() || t.cwd.is_dir());
+		if me.tabs.is_empty() {
+			bail!("None of the directories in the session `{name}` exist");
+		}
+
+		me.cursor = me.cursor.min(me.tabs.len() - 1);
+		Ok(me)
+	}
+
+	fn path(name: &str) -> Result<PathBuf> {
+		if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+			bail!("Invalid session name: `{name}`");
+		}
+		Ok(BOOT.state_dir.join("sessions").join(format!("{name}.json")))
+	}
+}
+
+impl SessionTab {
+	pub fn restore(self, tab: &mut Tab) {
+		tab.conf.sort_by = self.sort_by;
+		tab.conf.sort_sensitive = self.sort_sensitive;
+		tab.conf.sort_reverse = self.sort_reverse;
+		tab.conf.sort_dir_first = self.sort_dir_first;
+		tab.conf.sort_translit = self.sort_translit;
+		tab.conf.sort_by_locale = self.sort_by_locale;
+		tab.conf.sort_runtime = self.sort_runtime;
+		tab.conf.linemode = self.linemode;
+		tab.conf.show_hidden = self.show_hidden;
+		tab.apply_files_attrs();
+
+		// A hovered file that's gone is dropped once its metadata is read
+		match self.hovered {
+			Some(h) => tab.reveal(h),
+			None => tab.cd(self.cwd),
+		}
+
+		let case = match self.filter_case.as_str() {
+			"smart" => FilterCase::Smart,
+			"insensitive" => FilterCase::Insensitive,
+			_ => FilterCase::Sensitive,
+		};
+		let filter = self.filter.and_then(|s| match self.filter_case.as_str() {
+			"tag" => Some(Filter::tag(&s)),
+			_ => Filter::new(&s, case).ok(),
+		});
+		if let Some(f) = filter {
+			tab.current.files.set_filter(Some(f));
+		}
+	}
+}
@@ -1,10 +1,12 @@
-use std::ops::{Deref, DerefMut};
+use std::{io, ops::{Deref, DerefMut}, time::Duration};
 
-use yazi_boot::BOOT;
+use yazi_boot::{ARGS, BOOT};
+use yazi_config::MANAGER;
 use yazi_dds::Pubsub;
-use yazi_proxy::ManagerProxy;
+use yazi_proxy::{AppProxy, ManagerProxy, options::{NotifyLevel, NotifyOpt}};
 use yazi_shared::fs::Url;
 
+use super::Session;
 use crate::tab::Tab;
 
 pub struct Tabs {
@@ -14,6 +16,47 @@ pub struct Tabs {
 
 impl Tabs {
 	pub fn make() -> Self {
+		if ARGS.recover {
+			match Session::load(Session::AUTOSAVE) {
+				Ok(session) => {
+					let n = session.tabs.len();
+					AppProxy::notify(NotifyOpt {
+						title:   "Recover".to_owned(),
+						content: format!("Restored {n} tab(s) from the last autosave"),
+						level:   NotifyLevel::Info,
+						timeout: Duration::from_secs(5),
+						actions: vec![],
+					});
+
+					let mut tabs = Self { cursor: 0, items: vec![] };
+					tabs.fill(session);
+					return tabs;
+				}
+				Err(e) => AppProxy::notify_warn("Recover", format!("Nothing to recover: {e}")),
+			}
+		}
+
+		// The entries given on the command line win over the session
+		let explicit = !ARGS.entries.is_empty() || ARGS.tabs.is_some();
+		if (ARGS.restore_session || MANAGER.restore_session) && !explicit {
+			match Session::load(Session::LAST) {
+				Ok(session) => {
+					let mut tabs = Self { cursor: 0, items: vec![] };
+					tabs.fill(session);
+					return tabs;
+				}
+				Err(e) => {
+					// No session saved yet is expected, unless it's asked for explicitly
+					let missing =
+						e.downcast_ref::<io::Error>().is_some_and(|e| e.kind() == io::ErrorKind::NotFound);
+					if ARGS.restore_session || !missing {
+						let content = format!("Failed to restore the last session: {e}");
+						AppProxy::notify_warn("Restore session", content);
+					}
+				}
+			}
+		}
+
 		let mut tabs =
 			Self { cursor: 0, items: (0..BOOT.cwds.len()).map(|_| Tab::default()).collect() };
 		tabs.reorder();
@@ -26,9 +69,26 @@ impl Tabs {
 				tab.reveal(Url::from(BOOT.cwds[i].join(file)));
 			}
 		}
+
+		if !BOOT.skipped.is_empty() {
+			let list: Vec<_> =
+				BOOT.skipped.iter().map(|(p, why)| format!("- {} ({why})", p.display())).collect();
+			AppProxy::notify_warn("Open entries", format!("Skipped:\n{}", list.join("\n")));
+		}
 		tabs
 	}
 
+	/// Replaces all the tabs with the ones from the `session`.
+	pub(super) fn fill(&mut self, session: Session) {
+		self.items.iter_mut().for_each(|t| t.shutdown());
+		self.items = (0..session.tabs.len()).map(|idx| Tab { idx, ..Default::default() }).collect();
+		self.cursor = session.cursor;
+
+		for (tab, s) in self.items.iter_mut().zip(session.tabs) {
+			s.restore(tab);
+		}
+	}
+
 	pub(super) fn absolute(&self, rel: isize) -> usize {
 		if rel > 0 {
 			(self.cursor + rel as usize).min(self.items.len() - 1)
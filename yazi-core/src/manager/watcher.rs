@@ -1,15 +1,15 @@
-use std::{collections::{HashMap, HashSet}, time::Duration};
+use std::{collections::{HashMap, HashSet}, sync::atomic::Ordering, time::{Duration, SystemTime}};
 
 use anyhow::Result;
 use notify::{PollWatcher, RecommendedWatcher, RecursiveMode, Watcher as _Watcher};
 use parking_lot::RwLock;
-use tokio::{fs, pin, sync::{mpsc::{self, UnboundedReceiver}, watch}};
-use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
+use tokio::{fs, sync::{mpsc::{self, UnboundedReceiver}, watch}, task::spawn_blocking, time::{Instant, timeout_at}};
 use tracing::error;
-use yazi_fs::{Files, Folder};
+use yazi_config::MANAGER;
+use yazi_fs::{Files, Folder, FolderStage, is_remote};
 use yazi_plugin::isolate;
-use yazi_proxy::WATCHER;
-use yazi_shared::{RoCell, fs::{Cha, File, FilesOp, Url, realname_unchecked}};
+use yazi_proxy::{HIDER, WATCHER};
+use yazi_shared::{RoCell, fs::{Cha, File, FilesOp, Url, UrnBuf, long_path, realname_unchecked, short_path}};
 
 use super::Linked;
 
@@ -17,13 +17,15 @@ pub(crate) static WATCHED: RoCell<RwLock<HashSet<Url>>> = RoCell::new();
 pub static LINKED: RoCell<RwLock<Linked>> = RoCell::new();
 
 pub struct Watcher {
-	in_tx:  watch::Sender<HashSet<Url>>,
-	out_tx: mpsc::UnboundedSender<Url>,
+	in_tx:   watch::Sender<HashSet<Url>>,
+	poll_tx: watch::Sender<HashSet<Url>>,
+	out_tx:  mpsc::UnboundedSender<Url>,
 }
 
 impl Watcher {
 	pub(super) fn serve() -> Self {
 		let (in_tx, in_rx) = watch::channel(Default::default());
+		let (poll_tx, poll_rx) = watch::channel(Default::default());
 		let (out_tx, out_rx) = mpsc::unbounded_channel();
 
 		let out_tx_ = out_tx.clone();
@@ -33,7 +35,7 @@ impl Watcher {
 				return;
 			}
 			for path in event.paths {
-				out_tx_.send(Url::from(path)).ok();
+				out_tx_.send(Url::from(short_path(path))).ok();
 			}
 		};
 
@@ -45,12 +47,18 @@ impl Watcher {
 		}
 
 		tokio::spawn(Self::fan_out(out_rx));
-		Self { in_tx, out_tx }
+		tokio::spawn(Self::poll(poll_rx));
+		Self { in_tx, poll_tx, out_tx }
 	}
 
-	pub(super) fn watch(&mut self, mut new: HashSet<&Url>) {
+	/// Watches `new` with the native watcher, and polls `visible`, the
+	/// directories shown in the active tab, if they're on a network filesystem.
+	pub(super) fn watch(&mut self, mut new: HashSet<&Url>, mut visible: HashSet<&Url>) {
 		new.retain(|&u| u.is_regular());
 		self.in_tx.send(new.into_iter().cloned().collect()).ok();
+
+		visible.retain(|&u| u.is_regular());
+		self.poll_tx.send(visible.into_iter().cloned().collect()).ok();
 	}
 
 	pub(super) fn push_files(&self, url: Vec<Url>) {
@@ -62,23 +70,44 @@ impl Watcher {
 		}
 	}
 
-	pub(super) fn trigger_dirs(&self, folders: &[&Folder]) {
-		let todo: Vec<_> =
-			folders.iter().filter(|&f| f.url.is_regular()).map(|&f| (f.url.to_owned(), f.cha)).collect();
+	#[inline]
+	pub(super) fn trigger_dirs(&self, folders: &[&Folder]) { self.trigger(folders, false) }
+
+	/// Reads the `folders` again even if they don't look changed, as the watcher
+	/// may not have caught up with what was done to them yet.
+	#[inline]
+	pub(super) fn reload_dirs(&self, folders: &[&Folder]) { self.trigger(folders, true) }
+
+	fn trigger(&self, folders: &[&Folder], force: bool) {
+		let todo: Vec<_> = folders
+			.iter()
+			.filter(|&f| f.url.is_regular() || f.url.is_archive())
+			.map(|&f| {
+				let cha = if force { Cha::dummy() } else { f.cha };
+				(f.url.to_owned(), cha, f.stage == FolderStage::Loading)
+			})
+			.collect();
 		if todo.is_empty() {
 			return;
 		}
 
-		async fn go(cwd: Url, cha: Cha) {
+		// Those being loaded for the first time are shown as they're read, while the
+		// loaded ones are replaced at once, so a refresh doesn't empty them meanwhile,
+		// unless it fails, then read again in chunks to show what can be read
+		async fn go(cwd: Url, cha: Cha, loading: bool) {
 			let Some(cha) = Files::assert_stale(&cwd, cha).await else { return };
 
-			if let Ok(files) = Files::from_dir_bulk(&cwd).await {
-				FilesOp::Full(cwd, files, cha).emit();
+			if !loading {
+				if let Ok(files) = Files::from_dir_bulk(&cwd).await {
+					return FilesOp::Full(cwd, files, cha).emit();
+				}
 			}
+			Files::from_dir_chunked(cwd, cha).await;
 		}
 
 		tokio::spawn(async move {
-			futures::future::join_all(todo.into_iter().map(|(cwd, cha)| go(cwd, cha))).await;
+			futures::future::join_all(todo.into_iter().map(|(cwd, cha, loading)| go(cwd, cha, loading)))
+				.await;
 		});
 	}
 
@@ -89,7 +118,7 @@ impl Watcher {
 				(old.difference(new).cloned().collect(), new.difference(old).cloned().collect())
 			};
 
-			to_unwatch.retain(|u| match watcher.unwatch(u) {
+			to_unwatch.retain(|u| match watcher.unwatch(&long_path(u)) {
 				Ok(_) => true,
 				Err(e) if matches!(e.kind, notify::ErrorKind::WatchNotFound) => true,
 				Err(e) => {
@@ -97,7 +126,7 @@ impl Watcher {
 					false
 				}
 			});
-			to_watch.retain(|u| watcher.watch(u, RecursiveMode::NonRecursive).is_ok());
+			to_watch.retain(|u| watcher.watch(&long_path(u), RecursiveMode::NonRecursive).is_ok());
 
 			{
 				let mut watched = WATCHED.write();
@@ -115,44 +144,131 @@ impl Watcher {
 		}
 	}
 
-	async fn fan_out(rx: UnboundedReceiver<Url>) {
-		// TODO: revert this once a new notification is implemented
-		let rx = UnboundedReceiverStream::new(rx).chunks_timeout(1000, Duration::from_millis(100));
-		pin!(rx);
+	async fn fan_out(mut rx: UnboundedReceiver<Url>) {
+		while let Some(first) = rx.recv().await {
+			let mut batch = Batch::new(MANAGER.watch_burst);
+			batch.push(first);
 
-		while let Some(chunk) = rx.next().await {
-			let urls: HashSet<_> = chunk.into_iter().collect();
-			let mut cached: HashMap<_, _> = HashMap::new();
+			let deadline = Instant::now() + MANAGER.watch_window();
+			let mut closed = Self::gather(&mut rx, &mut batch, deadline).await;
+
+			// Nothing is looked at while unfocused, so batch the changes for longer
+			if !closed && !yazi_adapter::FOCUSED.load(Ordering::Relaxed) {
+				let deadline = Instant::now() + Duration::from_secs(3);
+				closed = Self::gather(&mut rx, &mut batch, deadline).await;
+			}
 
 			let _permit = WATCHER.acquire().await.unwrap();
-			let mut ops = Vec::with_capacity(urls.len());
-			let mut reload = Vec::with_capacity(urls.len());
+			for dir in batch.hot {
+				let Ok(files) = Files::from_dir_bulk(&dir).await else { continue };
+				let Ok(cha) = fs::metadata(&dir).await.map(Cha::from) else { continue };
+				FilesOp::Full(dir, files, cha).emit();
+			}
 
-			for u in urls {
-				let Some((parent, urn)) = u.pair() else { continue };
-				let Ok(file) = File::from(u).await else {
-					ops.push(FilesOp::Deleting(parent, HashSet::from_iter([urn])));
-					continue;
-				};
+			Self::apply(batch.urls.into_values().flatten().collect()).await;
+			if closed {
+				break;
+			}
+		}
+	}
+
+	// Takes the changes until `deadline`, returns whether the channel is closed.
+	async fn gather(rx: &mut UnboundedReceiver<Url>, batch: &mut Batch, deadline: Instant) -> bool {
+		loop {
+			match timeout_at(deadline, rx.recv()).await {
+				Ok(Some(u)) => batch.push(u),
+				Ok(None) => return true,
+				Err(_) => return false,
+			}
+		}
+	}
+
+	async fn apply(urls: HashSet<Url>) {
+		if urls.is_empty() {
+			return;
+		}
+		let mut cached: HashMap<_, _> = HashMap::new();
+		let mut ops = Vec::with_capacity(urls.len());
+		let mut reload = Vec::with_capacity(urls.len());
+
+		for u in urls {
+			let Some((parent, urn)) = u.pair() else { continue };
+			let Ok(file) = File::from(u).await else {
+				ops.push(FilesOp::Deleting(parent, HashSet::from_iter([urn])));
+				continue;
+			};
+
+			let u = &file.url;
+			let eq = (!file.is_link() && fs::canonicalize(u).await.is_ok_and(|p| p == ***u))
+				|| realname_unchecked(u, &mut cached).await.is_ok_and(|s| urn.as_urn() == s);
+
+			if !eq {
+				ops.push(FilesOp::Deleting(parent, HashSet::from_iter([urn])));
+				continue;
+			}
+
+			if !file.is_dir() {
+				reload.push(file.clone());
+			}
+			ops.push(FilesOp::Upserting(parent, HashMap::from_iter([(urn, file)])));
+		}
+
+		// Applied as a single diff per directory
+		FilesOp::mutate(ops);
+		if let Err(e) = isolate::fetch("mime", reload).await {
+			error!("Fetch `mime` failed in watcher: {e}");
+		}
+	}
+
+	// The native watcher doesn't receive the changes made by other hosts on a
+	// network filesystem, so the visible directories on it are listed periodically
+	// instead, and replaced entirely once their entries differ from the last time.
+	async fn poll(rx: watch::Receiver<HashSet<Url>>) {
+		if MANAGER.poll_interval == 0 {
+			return;
+		}
+
+		type Snapshot = HashSet<(UrnBuf, u64, Option<SystemTime>)>;
+		let mut remote: HashMap<Url, bool> = HashMap::new();
+		let mut snapshots: HashMap<Url, Snapshot> = HashMap::new();
 
-				let u = &file.url;
-				let eq = (!file.is_link() && fs::canonicalize(u).await.is_ok_and(|p| p == ***u))
-					|| realname_unchecked(u, &mut cached).await.is_ok_and(|s| urn.as_urn() == s);
+		loop {
+			tokio::time::sleep(Duration::from_millis(MANAGER.poll_interval)).await;
+			if !yazi_adapter::FOCUSED.load(Ordering::Relaxed) || HIDER.available_permits() == 0 {
+				continue;
+			}
 
-				if !eq {
-					ops.push(FilesOp::Deleting(parent, HashSet::from_iter([urn])));
+			let visible = rx.borrow().clone();
+			remote.retain(|u, _| visible.contains(u));
+			snapshots.retain(|u, _| visible.contains(u));
+
+			for url in visible {
+				let is_remote = match remote.get(&url) {
+					Some(&b) => b,
+					None => {
+						let b = if MANAGER.poll_prefixes.iter().any(|p| url.starts_with(p)) {
+							true
+						} else {
+							let path = url.to_path_buf();
+							spawn_blocking(move || is_remote(&path)).await.unwrap_or(false)
+						};
+						*remote.entry(url.clone()).or_insert(b)
+					}
+				};
+				if !is_remote {
 					continue;
 				}
 
-				if !file.is_dir() {
-					reload.push(file.clone());
+				let Ok(files) = Files::from_dir_bulk(&url).await else { continue };
+				let new: Snapshot = files.iter().map(|f| (f.urn_owned(), f.len, f.mtime)).collect();
+				match snapshots.insert(url.clone(), new) {
+					Some(old) if old != snapshots[&url] => {}
+					_ => continue,
 				}
-				ops.push(FilesOp::Upserting(parent, HashMap::from_iter([(urn, file)])));
-			}
 
-			FilesOp::mutate(ops);
-			if let Err(e) = isolate::fetch("mime", reload).await {
-				error!("Fetch `mime` failed in watcher: {e}");
+				let Ok(cha) = fs::metadata(&url).await.map(Cha::from) else { continue };
+				let _permit = WATCHER.acquire().await.unwrap();
+				FilesOp::Full(url, files, cha).emit();
 			}
 		}
 	}
@@ -180,3 +296,105 @@ impl Watcher {
 		go(old).await;
 	}
 }
+
+/// The changes gathered in a window, grouped by their directories, where a
+/// directory is taken as hot once it has more than `manager.watch_burst` of
+/// them, and the individual ones are dropped in favor of reading it again.
+struct Batch {
+	burst:  usize,
+	urls:   HashMap<Url, HashSet<Url>>,
+	counts: HashMap<Url, usize>,
+	hot:    HashSet<Url>,
+}
+
+impl Batch {
+	fn new(burst: usize) -> Self {
+		Self { burst, urls: Default::default(), counts: Default::default(), hot: Default::default() }
+	}
+
+	fn push(&mut self, url: Url) {
+		let Some(parent) = url.parent_url() else { return };
+		if self.hot.contains(&parent) {
+			return;
+		}
+
+		let count = self.counts.entry(parent.clone()).or_default();
+		*count += 1;
+		if *count > self.burst {
+			self.urls.remove(&parent);
+			self.hot.insert(parent);
+		} else {
+			self.urls.entry(parent).or_default().insert(url);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_batch() {
+		let mut batch = Batch::new(4);
+		for name in ["a", "b", "c", "b"] {
+			batch.push(Url::from(format!("/hot/{name}")));
+		}
+		batch.push(Url::from("/cool/a"));
+		assert!(batch.hot.is_empty());
+		assert_eq!(batch.urls[&Url::from("/hot")].len(), 3);
+		assert_eq!(batch.counts[&Url::from("/hot")], 4);
+
+		// Once over the burst, the individual changes are dropped for a full re-read
+		batch.push(Url::from("/hot/d"));
+		batch.push(Url::from("/hot/e"));
+		assert_eq!(batch.hot, HashSet::from_iter([Url::from("/hot")]));
+		assert!(!batch.urls.contains_key(&Url::from("/hot")));
+		assert_eq!(batch.counts[&Url::from("/hot")], 5);
+		assert_eq!(batch.urls[&Url::from("/cool")], HashSet::from_iter([Url::from("/cool/a")]));
+	}
+
+	#[tokio::test]
+	async fn test_stress() {
+		let dir = std::env::temp_dir().join(format!("yazi-watch-stress-{}", std::process::id()));
+		fs::create_dir_all(&dir).await.unwrap();
+		let dir = Url::from(short_path(fs::canonicalize(&dir).await.unwrap()));
+
+		let (tx, mut rx) = mpsc::unbounded_channel();
+		let mut watcher = RecommendedWatcher::new(
+			move |res: Result<notify::Event, notify::Error>| {
+				for path in res.map(|e| e.paths).unwrap_or_default() {
+					tx.send(Url::from(short_path(path))).ok();
+				}
+			},
+			Default::default(),
+		)
+		.unwrap();
+		watcher.watch(&dir, RecursiveMode::NonRecursive).unwrap();
+
+		// 10k files written as fast as possible, e.g. by a build
+		let writer = {
+			let dir = dir.clone();
+			spawn_blocking(move || {
+				for i in 0..10_000 {
+					std::fs::write(dir.join(format!("{i}.o")), "").unwrap();
+				}
+			})
+		};
+
+		// Each window keeps no more than the burst of them, and reads the directory
+		// once instead, rather than applying them one by one
+		let (mut hot, started) = (0, Instant::now());
+		while !writer.is_finished() || !rx.is_empty() {
+			let mut batch = Batch::new(500);
+			Watcher::gather(&mut rx, &mut batch, Instant::now() + Duration::from_millis(200)).await;
+			assert!(batch.urls.get(&dir).is_none_or(|u| u.len() <= 500));
+			hot += batch.hot.contains(&dir) as usize;
+		}
+		writer.await.unwrap();
+		assert!(hot > 0);
+		assert!(started.elapsed() < Duration::from_secs(30));
+
+		drop(watcher);
+		fs::remove_dir_all(&dir).await.ok();
+	}
+}
@@ -1,13 +1,16 @@
 use std::{collections::HashSet, ops::Deref};
 
 use yazi_dds::Pubsub;
-use yazi_shared::fs::{FilesOp, Url};
+use yazi_shared::{fs::{FilesOp, Url}, timestamp_us};
 
 #[derive(Default)]
 pub struct Yanked {
 	pub cut: bool,
 	urls:    HashSet<Url>,
 
+	// When this state was last published, used to resolve conflicts with
+	// the yanks from other instances on a last-write-wins basis
+	ts:       u64,
 	version:  u64,
 	revision: u64,
 }
@@ -23,6 +26,15 @@ impl Yanked {
 		Self { cut, urls, version: 0, ..Default::default() }
 	}
 
+	#[inline]
+	pub(super) fn with_ts(mut self, ts: u64) -> Self {
+		self.ts = ts;
+		self
+	}
+
+	#[inline]
+	pub fn ts(&self) -> u64 { self.ts }
+
 	pub fn remove(&mut self, url: &Url) {
 		if self.urls.remove(url) {
 			self.revision += 1;
@@ -58,8 +70,9 @@ impl Yanked {
 			return false;
 		}
 
+		self.ts = timestamp_us();
 		self.version = self.revision;
-		Pubsub::pub_from_yank(self.cut, &self.urls);
+		Pubsub::pub_from_yank(self.cut, &self.urls, self.ts);
 		true
 	}
 }
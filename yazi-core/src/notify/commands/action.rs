@@ -0,0 +1,65 @@
+use std::{mem, time::Duration};
+
+use yazi_config::popup::PickCfg;
+use yazi_macro::emit;
+use yazi_proxy::PickProxy;
+use yazi_shared::{Layer, event::{Cmd, Data}};
+
+use crate::notify::Notify;
+
+struct Opt {
+	id:     Option<usize>,
+	action: usize,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self {
+		Self {
+			id:     c.get("id").and_then(Data::as_usize),
+			action: c.get("action").and_then(Data::as_usize).unwrap_or(usize::MAX),
+		}
+	}
+}
+
+impl Notify {
+	#[yazi_codegen::command]
+	pub fn action(&mut self, opt: Opt) {
+		let Some(id) = opt.id else {
+			return self.pick_action();
+		};
+		let Some(msg) = self.messages.iter_mut().find(|m| m.id == id && !m.actions.is_empty()) else {
+			return;
+		};
+
+		// Running an action, or dismissing, lets the message time out
+		let actions = mem::take(&mut msg.actions);
+		msg.timeout = Duration::ZERO;
+		if let Some(action) = actions.into_iter().nth(opt.action) {
+			(action.run)();
+		}
+
+		emit!(Call(Cmd::args("update_notify", &[0]), Layer::App));
+	}
+
+	fn pick_action(&self) {
+		let (mut items, mut targets) = (vec![], vec![]);
+		for m in self.messages.iter().filter(|m| !m.actions.is_empty()) {
+			for (i, a) in m.actions.iter().enumerate() {
+				items.push(format!("{}: {}", m.title, a.label));
+				targets.push((m.id, i));
+			}
+			items.push(format!("{}: Dismiss", m.title));
+			targets.push((m.id, usize::MAX));
+		}
+
+		if items.is_empty() {
+			return;
+		}
+
+		tokio::spawn(async move {
+			let Ok(choice) = PickProxy::show(PickCfg::action(items)).await else { return };
+			let (id, action) = targets[choice];
+			emit!(Call(Cmd::new("notify_action").with("id", id).with("action", action), Layer::Manager));
+		});
+	}
+}
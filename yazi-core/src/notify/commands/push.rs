@@ -8,6 +8,19 @@ use crate::notify::{Message, Notify};
 impl Notify {
 	pub fn push(&mut self, msg: impl Into<Message>) {
 		let mut msg = msg.into() as Message;
+		self.last_id += 1;
+		msg.id = self.last_id;
+
+		// Those with actions stay until dismissed, so instead of piling up, a new one
+		// replaces the one of the same title, e.g. for repeated failures
+		if !msg.actions.is_empty() {
+			let old = self.messages.iter_mut().find(|m| !m.actions.is_empty() && m.title == msg.title);
+			if let Some(old) = old {
+				(msg.instant, msg.percent, msg.timeout) = (old.instant, old.percent, old.timeout);
+				*old = msg;
+				return emit!(Call(Cmd::args("update_notify", &[0]), Layer::App));
+			}
+		}
 
 		let instant = Instant::now();
 		msg.timeout += instant - self.messages.first().map_or(instant, |m| m.instant);
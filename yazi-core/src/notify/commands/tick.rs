@@ -40,20 +40,23 @@ impl Notify {
 				m.percent = m.percent.saturating_sub(20);
 			} else if m.percent < 100 {
 				m.percent += 20;
-			} else {
+			} else if m.actions.is_empty() {
 				m.timeout = m.timeout.saturating_sub(opt.interval);
 			}
 		}
 
 		self.messages.retain(|m| m.percent > 0 || !m.timeout.is_zero());
 		let limit = self.limit(area);
-		let timeouts: Vec<_> = self.messages[..limit]
+		let settled = self.messages[..limit]
 			.iter()
 			.filter(|&m| m.percent == 100 && !m.timeout.is_zero())
-			.map(|m| m.timeout)
-			.collect();
+			.collect::<Vec<_>>();
 
-		let interval = if timeouts.len() != limit {
+		// Messages with actions stay until dismissed, so they don't need ticking
+		let timeouts: Vec<_> =
+			settled.iter().filter(|&m| m.actions.is_empty()).map(|m| m.timeout).collect();
+
+		let interval = if settled.len() != limit {
 			Duration::from_millis(50)
 		} else if let Some(min) = timeouts.iter().min() {
 			*min
@@ -1,7 +1,7 @@
 use std::time::{Duration, Instant};
 
 use unicode_width::UnicodeWidthStr;
-use yazi_proxy::options::{NotifyLevel, NotifyOpt};
+use yazi_proxy::options::{NotifyAction, NotifyLevel, NotifyOpt};
 
 use super::NOTIFY_BORDER;
 
@@ -10,7 +10,9 @@ pub struct Message {
 	pub content: String,
 	pub level:   NotifyLevel,
 	pub timeout: Duration,
+	pub actions: Vec<NotifyAction>,
 
+	pub id:        usize,
 	pub instant:   Instant,
 	pub percent:   u8,
 	pub max_width: usize,
@@ -21,14 +23,19 @@ impl From<NotifyOpt> for Message {
 		let title = opt.title.lines().next().unwrap_or_default();
 		let title_width = title.width() + (opt.level.icon().width() + /* Space */ 1);
 
-		let max_width = opt.content.lines().map(|s| s.width()).max().unwrap_or(0).max(title_width);
+		let actions_width = opt.actions.iter().map(|a| a.label.width() + /* Separator */ 3).sum();
+
+		let max_width = opt.content.lines().map(|s| s.width()).max().unwrap_or(0);
+		let max_width = max_width.max(title_width).max(actions_width);
 
 		Self {
 			title:   title.to_owned(),
 			content: opt.content,
 			level:   opt.level,
 			timeout: opt.timeout,
+			actions: opt.actions,
 
+			id:        0,
 			instant:   Instant::now(),
 			percent:   0,
 			max_width: max_width + NOTIFY_BORDER as usize,
@@ -8,6 +8,7 @@ use super::{Message, NOTIFY_SPACING};
 #[derive(Default)]
 pub struct Notify {
 	pub(super) tick_handle: Option<JoinHandle<()>>,
+	pub(super) last_id:     usize,
 	pub messages:           Vec<Message>,
 }
 
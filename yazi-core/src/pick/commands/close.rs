@@ -19,11 +19,18 @@ impl Pick {
 	#[yazi_codegen::command]
 	pub fn close(&mut self, opt: Opt) {
 		if let Some(cb) = self.callback.take() {
-			_ = cb.send(if opt.submit { Ok(self.cursor) } else { Err(anyhow!("canceled")) });
+			_ = cb.send(if !opt.submit {
+				Err(anyhow!("canceled"))
+			} else if self.selected.is_empty() && !self.preset {
+				Ok(vec![self.cursor])
+			} else {
+				Ok(self.selected.iter().copied().collect())
+			});
 		}
 
 		self.cursor = 0;
 		self.offset = 0;
+		self.selected.clear();
 		self.visible = false;
 		render!();
 	}
@@ -1 +1 @@
-yazi_macro::mod_flat!(arrow close show);
+yazi_macro::mod_flat!(arrow close show toggle);
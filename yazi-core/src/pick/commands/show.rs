@@ -7,7 +7,7 @@ use crate::pick::Pick;
 
 pub struct Opt {
 	cfg: PickCfg,
-	tx:  oneshot::Sender<anyhow::Result<usize>>,
+	tx:  oneshot::Sender<anyhow::Result<Vec<usize>>>,
 }
 
 impl TryFrom<Cmd> for Opt {
@@ -28,6 +28,11 @@ impl Pick {
 		self.title = opt.cfg.title;
 		self.items = opt.cfg.items;
 		self.position = opt.cfg.position;
+		self.multi = opt.cfg.multi;
+		self.preset = self.multi && !opt.cfg.selected.is_empty();
+		if self.multi {
+			self.selected = opt.cfg.selected.into_iter().filter(|&i| i < self.items.len()).collect();
+		}
 
 		self.callback = Some(opt.tx);
 		self.visible = true;
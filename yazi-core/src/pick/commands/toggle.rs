@@ -0,0 +1,17 @@
+use yazi_macro::render;
+use yazi_shared::event::Cmd;
+
+use crate::pick::Pick;
+
+impl Pick {
+	pub fn toggle(&mut self, _: Cmd) {
+		if !self.multi || self.cursor >= self.items.len() {
+			return;
+		}
+
+		if !self.selected.remove(&self.cursor) {
+			self.selected.insert(self.cursor);
+		}
+		render!();
+	}
+}
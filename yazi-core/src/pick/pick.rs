@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+
 use anyhow::Result;
 use tokio::sync::oneshot::Sender;
 use yazi_config::{PICK, popup::Position};
@@ -10,7 +12,12 @@ pub struct Pick {
 
 	pub(super) offset:   usize,
 	pub(super) cursor:   usize,
-	pub(super) callback: Option<Sender<Result<usize>>>,
+	pub(super) multi:    bool,
+	pub(super) selected: BTreeSet<usize>,
+	// Whether some were toggled at first, then none toggled means none picked,
+	// rather than the one under the cursor
+	pub(super) preset:   bool,
+	pub(super) callback: Option<Sender<Result<Vec<usize>>>>,
 
 	pub visible: bool,
 }
@@ -29,8 +36,17 @@ impl Pick {
 }
 
 impl Pick {
+	pub fn title(&self) -> String {
+		if self.multi {
+			format!("{} ({}/{})", self.title, self.selected.len(), self.items.len())
+		} else {
+			self.title.clone()
+		}
+	}
+
+	/// Whether the `i`th item of [`Self::window`] is toggled.
 	#[inline]
-	pub fn title(&self) -> String { self.title.clone() }
+	pub fn is_selected(&self, i: usize) -> bool { self.selected.contains(&(self.offset + i)) }
 
 	#[inline]
 	pub fn rel_cursor(&self) -> usize { self.cursor - self.offset }
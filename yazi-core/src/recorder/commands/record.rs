@@ -0,0 +1,32 @@
+use yazi_macro::render;
+use yazi_proxy::AppProxy;
+use yazi_shared::event::Cmd;
+
+use crate::recorder::Recorder;
+
+struct Opt {
+	name: String,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self { Self { name: c.take_first_str().unwrap_or_default() } }
+}
+
+impl Recorder {
+	/// Starts recording the macro named by the first argument, or stops the one
+	/// being recorded, which replaces the macro of the same name.
+	#[yazi_codegen::command]
+	pub fn record(&mut self, opt: Opt) {
+		if let Some((name, cmds)) = self.recording.take() {
+			self.macros.insert(name, cmds);
+			return render!();
+		}
+
+		if opt.name.is_empty() {
+			return AppProxy::notify_warn("Record", "No macro name given, e.g. `record q`");
+		}
+
+		self.recording = Some((opt.name, vec![]));
+		render!();
+	}
+}
@@ -0,0 +1,39 @@
+use yazi_macro::emit;
+use yazi_proxy::AppProxy;
+use yazi_shared::{Layer, event::{Cmd, Data}};
+
+use crate::recorder::Recorder;
+
+struct Opt {
+	name:  String,
+	times: usize,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		Self {
+			name:  c.take_first_str().unwrap_or_default(),
+			times: c.get("times").and_then(Data::as_usize).unwrap_or(1),
+		}
+	}
+}
+
+impl Recorder {
+	#[yazi_codegen::command]
+	pub fn replay(&mut self, opt: Opt) {
+		if self.replaying() {
+			return AppProxy::notify_warn("Replay", "Another macro is being replayed");
+		} else if self.recording() == Some(&opt.name) {
+			return AppProxy::notify_warn("Replay", format!("Macro `{}` is being recorded", opt.name));
+		}
+
+		match self.macros.get(&opt.name) {
+			None => AppProxy::notify_warn("Replay", format!("No macro named `{}`", opt.name)),
+			Some(cmds) if cmds.is_empty() || opt.times == 0 => {}
+			Some(_) => {
+				(self.replaying, self.times) = (opt.name, opt.times);
+				emit!(Call(Cmd::new("replay_do"), Layer::App));
+			}
+		}
+	}
+}
@@ -0,0 +1,3 @@
+yazi_macro::mod_pub!(commands);
+
+yazi_macro::mod_flat!(recorder);
@@ -0,0 +1,181 @@
+use std::{collections::{HashMap, VecDeque}, time::{Duration, Instant}};
+
+use yazi_macro::emit;
+use yazi_proxy::AppProxy;
+use yazi_shared::{Layer, event::{Cmd, Data}};
+
+// How long a command waits for its layer to show up, e.g. the input opened by
+// the `rename` before it, until the iteration is given up
+const WAIT: Duration = Duration::from_secs(3);
+
+/// The macros recorded with `record` and replayed with `replay`, for this
+/// session only.
+///
+/// They're made of the commands resolved from the keys rather than the keys
+/// themselves, so they don't depend on the timing of the which-key, along with
+/// the text typed or pasted into an input.
+#[derive(Default)]
+pub struct Recorder {
+	pub(super) recording: Option<(String, Vec<(Cmd, Layer)>)>,
+	pub(super) macros:    HashMap<String, Vec<(Cmd, Layer)>>,
+
+	// The macro being replayed, the rest of its current iteration, and the number
+	// of iterations after it
+	pub(super) replaying: String,
+	pub(super) pending:   VecDeque<(Cmd, Layer)>,
+	pub(super) times:     usize,
+
+	// Whether the events of the command run last are being handled, and since
+	// when the next one has been waiting for its layer to show up, if it has
+	running: bool,
+	waiting: Option<Instant>,
+}
+
+impl Recorder {
+	/// The name of the macro being recorded.
+	#[inline]
+	pub fn recording(&self) -> Option<&str> { self.recording.as_ref().map(|(n, _)| n.as_str()) }
+
+	#[inline]
+	pub fn replaying(&self) -> bool { !self.pending.is_empty() || self.times > 0 }
+
+	/// Whether a command of the macro being replayed has been run, and the
+	/// events it emitted right away are being handled, so a warning or error
+	/// among them is its own, rather than something else going on meanwhile.
+	#[inline]
+	pub fn running(&self) -> bool { self.running }
+
+	/// Whether the next command of the macro being replayed is waiting for its
+	/// layer to show up, which any command run may have done.
+	#[inline]
+	pub fn waiting(&self) -> bool { self.waiting.is_some() }
+
+	/// Captures the commands resolved from a key, if a macro is being recorded.
+	pub fn capture(&mut self, cmds: &VecDeque<Cmd>, layer: Layer) {
+		let Some((_, recorded)) = &mut self.recording else { return };
+		for cmd in cmds {
+			if !matches!(cmd.name.as_str(), "record" | "replay") {
+				recorded.push((cmd.shallow_clone(), layer));
+			}
+		}
+	}
+
+	/// Captures the text typed or pasted into the input, if a macro is being
+	/// recorded, which is joined to the text captured right before it.
+	pub fn capture_typed(&mut self, s: &str) {
+		let Some((_, recorded)) = &mut self.recording else { return };
+		if let Some((cmd, Layer::Input)) = recorded.last_mut() {
+			if let (true, Some(Data::String(text))) = (cmd.name == "type", cmd.args.get_mut("0")) {
+				return text.push_str(s);
+			}
+		}
+		recorded.push((Cmd::args("type", &[s]), Layer::Input));
+	}
+
+	/// The next command of the macro being replayed to run, once its layer is
+	/// the `active` one the keys go to, as what opens it may do so in the
+	/// background.
+	///
+	/// Until then it's asked for again whenever another command is run, and
+	/// given up on along with the rest of the iteration once the timer started
+	/// for it runs out, which asks with `timeout` set.
+	pub fn next(&mut self, active: Layer, timeout: bool) -> Option<(Cmd, Layer)> {
+		if timeout && self.waiting.is_none_or(|t| t.elapsed() < WAIT) {
+			return None;
+		}
+		self.running = false;
+
+		if let Some((cmd, layer)) = self.pending.front() {
+			if !Self::ready(*layer, active) {
+				if !timeout {
+					if self.waiting.is_none() {
+						self.waiting = Some(Instant::now());
+						Self::time_out();
+					}
+					return None;
+				}
+
+				AppProxy::notify_warn(
+					"Replay",
+					format!(
+						"Gave up on `{}` of macro `{}`, as no {layer} showed up",
+						cmd.name, self.replaying
+					),
+				);
+				self.abort();
+			}
+		}
+		self.waiting = None;
+
+		if self.pending.is_empty() {
+			let Some(cmds) = self.macros.get(&self.replaying).filter(|_| self.times > 0) else {
+				self.times = 0;
+				return None;
+			};
+
+			self.times -= 1;
+			self.pending = cmds.iter().map(|(c, l)| (c.shallow_clone(), *l)).collect();
+			return self.next(active, false);
+		}
+
+		let next = self.pending.pop_front();
+		self.running = next.is_some();
+		next
+	}
+
+	/// Drops the rest of the current iteration of the macro being replayed, e.g.
+	/// after one of its commands failed, and goes on with the next one.
+	#[inline]
+	pub fn abort(&mut self) {
+		self.pending.clear();
+		self.waiting = None;
+	}
+
+	#[inline]
+	fn ready(layer: Layer, active: Layer) -> bool {
+		layer == active || layer == Layer::App || (layer == Layer::Input && active == Layer::Completion)
+	}
+
+	fn time_out() {
+		tokio::spawn(async {
+			tokio::time::sleep(WAIT).await;
+			emit!(Call(Cmd::new("replay_do").with_bool("timeout", true), Layer::App));
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_replay() {
+		let mut r = Recorder::default();
+		r.record(Cmd::args("record", &["q"]));
+		r.capture(&[Cmd::new("rename")].into(), Layer::Manager);
+		r.capture_typed("fo");
+		r.capture_typed("o");
+		r.capture(&[Cmd::new("close").with_bool("submit", true)].into(), Layer::Input);
+		r.record(Cmd::new("record"));
+
+		(r.replaying, r.times) = ("q".to_owned(), 2);
+		let name = |next: Option<(Cmd, Layer)>| next.map(|(c, _)| c.name);
+		assert_eq!(name(r.next(Layer::Manager, false)).as_deref(), Some("rename"));
+		assert!(r.running());
+
+		// Waits for the input opened by `rename`, which a stale timer doesn't end
+		assert!(r.next(Layer::Manager, false).is_none());
+		assert!(r.waiting() && !r.running());
+		assert!(r.next(Layer::Manager, true).is_none());
+		assert!(r.waiting());
+
+		let (typed, _) = r.next(Layer::Input, false).unwrap();
+		assert_eq!(typed.first().and_then(Data::as_str), Some("foo"));
+		assert!(!r.waiting());
+
+		// The second iteration goes on after the first one fails
+		r.abort();
+		assert_eq!(name(r.next(Layer::Manager, false)).as_deref(), Some("rename"));
+		assert_eq!(r.times, 0);
+	}
+}
@@ -0,0 +1,24 @@
+use yazi_shared::event::{Cmd, Data};
+
+use crate::spot::Spot;
+
+struct Opt {
+	step: isize,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self { Self { step: c.first().and_then(Data::as_isize).unwrap_or(0) } }
+}
+
+impl Spot {
+	#[yazi_codegen::command]
+	pub fn arrow(&mut self, opt: Opt) {
+		let skip = self.skip.saturating_add_signed(opt.step);
+		if skip == self.skip {
+			return;
+		}
+
+		self.skip = skip;
+		self.run(None);
+	}
+}
@@ -0,0 +1,5 @@
+yazi_macro::mod_pub!(commands);
+
+yazi_macro::mod_flat!(spot);
+
+pub const SPOT_PERCENT: u16 = 80;
@@ -0,0 +1,96 @@
+use std::borrow::Cow;
+
+use ratatui::layout::{self, Constraint, Margin, Rect};
+use tokio_util::sync::CancellationToken;
+use yazi_adapter::{ADAPTOR, Dimension};
+use yazi_config::{PLUGIN, keymap::Key};
+use yazi_macro::render;
+use yazi_plugin::{isolate, utils::SpotLock};
+use yazi_proxy::ManagerProxy;
+use yazi_shared::fs::{File, Url};
+
+use super::SPOT_PERCENT;
+
+#[derive(Default)]
+pub struct Spot {
+	pub lock: Option<SpotLock>,
+	pub skip: usize,
+
+	target: Option<(String, File, Cow<'static, str>)>,
+	ct:     Option<CancellationToken>,
+}
+
+impl Spot {
+	pub fn go(&mut self, file: File, mime: Cow<'static, str>) {
+		if mime.is_empty() {
+			return; // Wait till mimetype is resolved to avoid flickering
+		}
+
+		let Some(spotter) = PLUGIN.spotter(&file.url, &mime) else {
+			return self.reset();
+		};
+
+		self.target = Some((spotter.run.name.clone(), file, mime));
+		self.run(None);
+	}
+
+	pub fn type_(&mut self, key: &Key) -> bool {
+		let Some(lock) = &self.lock else { return false };
+		if !lock.keys.iter().any(|k| k.on == *key) {
+			return false;
+		}
+
+		self.run(Some(key.to_string()));
+		true
+	}
+
+	pub(super) fn run(&mut self, key: Option<String>) {
+		self.abort();
+		let Some((name, file, mime)) = &self.target else { return };
+
+		let area = Self::area().inner(Margin::new(1, 1));
+		self.ct = Some(isolate::spot(name, file.clone(), mime.clone(), self.skip, area, key));
+	}
+
+	#[inline]
+	pub fn abort(&mut self) { self.ct.take().map(|ct| ct.cancel()); }
+
+	#[inline]
+	pub fn reset(&mut self) {
+		self.abort();
+		self.skip = 0;
+		self.target = None;
+		if ADAPTOR.spotted().is_some() {
+			ADAPTOR.spot_hide().ok();
+			ManagerProxy::peek(true); // Restore the image of the preview pane
+		}
+		render!(self.lock.take().is_some());
+	}
+
+	#[inline]
+	pub fn visible(&self) -> bool { self.lock.is_some() }
+
+	#[inline]
+	pub fn same_url(&self, url: &Url) -> bool {
+		self.target.as_ref().is_some_and(|(_, f, _)| *url == f.url)
+	}
+
+	pub fn area() -> Rect {
+		let size = Dimension::available();
+		let area = Rect::new(0, 0, size.columns, size.rows);
+
+		let chunk = layout::Layout::vertical([
+			Constraint::Percentage((100 - SPOT_PERCENT) / 2),
+			Constraint::Percentage(SPOT_PERCENT),
+			Constraint::Percentage((100 - SPOT_PERCENT) / 2),
+		])
+		.split(area)[1];
+
+		layout::Layout::horizontal([
+			Constraint::Percentage((100 - SPOT_PERCENT) / 2),
+			Constraint::Percentage(SPOT_PERCENT),
+			Constraint::Percentage((100 - SPOT_PERCENT) / 2),
+		])
+		.split(chunk)[1]
+	}
+}
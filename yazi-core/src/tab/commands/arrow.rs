@@ -31,6 +31,7 @@ impl Tab {
 		if !self.current.arrow(opt.step) {
 			return;
 		}
+		self.unpin_revealed();
 
 		// Visual selection
 		if let Some((start, items)) = self.mode.visual_mut() {
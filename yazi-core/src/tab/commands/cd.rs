@@ -1,18 +1,20 @@
-use std::{mem, time::Duration};
+use std::{mem, path::{Path, PathBuf}, time::Duration};
 
 use tokio::{fs, pin};
 use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
-use yazi_config::popup::InputCfg;
+use yazi_config::{MANAGER, popup::InputCfg};
 use yazi_dds::Pubsub;
 use yazi_macro::render;
-use yazi_proxy::{CompletionProxy, InputProxy, ManagerProxy, TabProxy};
-use yazi_shared::{Debounce, errors::InputError, event::{Cmd, Data}, fs::{Url, expand_path}};
+use yazi_proxy::{AppProxy, CompletionProxy, InputProxy, ManagerProxy, TabProxy};
+use yazi_shared::{Debounce, errors::InputError, event::{Cmd, Data}, fs::{Url, UrnBuf, expand_path, long}};
 
-use crate::tab::Tab;
+use crate::{manager::{DIR_VIEWS, RECENT_DIRS}, tab::Tab};
 
 struct Opt {
 	target:      Url,
 	interactive: bool,
+	recent:      bool,
+	realpath:    bool,
 }
 
 impl From<Cmd> for Opt {
@@ -22,11 +24,18 @@ impl From<Cmd> for Opt {
 			target = Url::from(expand_path(&target));
 		}
 
-		Self { target, interactive: c.bool("interactive") }
+		Self {
+			target,
+			interactive: c.bool("interactive"),
+			recent: c.bool("recent"),
+			realpath: c.bool("realpath"),
+		}
 	}
 }
 impl From<Url> for Opt {
-	fn from(target: Url) -> Self { Self { target, interactive: false } }
+	fn from(target: Url) -> Self {
+		Self { target, interactive: false, recent: false, realpath: false }
+	}
 }
 
 impl Tab {
@@ -39,19 +48,27 @@ impl Tab {
 		if opt.interactive {
 			return self.cd_interactive();
 		}
+		if opt.recent {
+			return self.cd_recent();
+		}
+		if opt.realpath {
+			return self.cd_realpath(opt.target);
+		}
 
 		if opt.target == *self.cwd() {
 			return;
 		}
 
-		// Take parent to history
-		if let Some(rep) = self.parent.take() {
+		// Take parent to history, where it no longer keeps the old CWD pinned
+		if let Some(mut rep) = self.parent.take() {
+			rep.files.set_pinned(None);
 			self.history.insert(rep.url.to_owned(), rep);
 		}
 
-		// Current
+		// Current, where the file revealed in it isn't kept pinned
 		let rep = self.history.remove_or(&opt.target);
-		let rep = mem::replace(&mut self.current, rep);
+		let mut rep = mem::replace(&mut self.current, rep);
+		rep.files.set_pinned(None);
 		if rep.url.is_regular() {
 			self.history.insert(rep.url.to_owned(), rep);
 		}
@@ -65,10 +82,65 @@ impl Tab {
 		if opt.target.is_regular() {
 			self.backstack.push(opt.target.clone());
 		}
+		RECENT_DIRS.lock().visit(&opt.target);
+
+		self.apply_dir_view();
 
 		Pubsub::pub_from_cd(self.idx, self.cwd());
 		ManagerProxy::refresh();
 		render!();
+
+		if MANAGER.cd_realpath && opt.target.is_regular() {
+			Self::cd_canonical(opt.target, None, false);
+		}
+	}
+
+	// The sorting changed by `sort` stays with the directory it's remembered for,
+	// while the hidden mode and linemode remembered for the new CWD are taken on,
+	// or those of the config if it has none
+	fn apply_dir_view(&mut self) {
+		if MANAGER.remember_views == 0 {
+			return;
+		}
+
+		self.conf.sort_runtime = Default::default();
+		let mut views = DIR_VIEWS.lock();
+		let view = views.touch(self.cwd());
+		self.conf.show_hidden = view.and_then(|v| v.hidden).unwrap_or(MANAGER.show_hidden);
+		self.conf.linemode = match view.and_then(|v| v.linemode.as_ref()) {
+			Some(linemode) => linemode.clone(),
+			None => MANAGER.linemode.clone(),
+		};
+	}
+
+	// Without a target, the CWD is resolved, and the hovered file stays hovered
+	fn cd_realpath(&mut self, target: Url) {
+		if !target.as_os_str().is_empty() {
+			return Self::cd_canonical(target, None, true);
+		}
+		if self.cwd().is_regular() {
+			Self::cd_canonical(self.cwd().clone(), self.hovered().map(|h| h.urn_owned()), false);
+		}
+	}
+
+	/// Goes on to the canonical location of `dir` if it's not there already,
+	/// hovering the file named `hovered` in it, or even if it is with `always`,
+	/// as for a target that isn't the CWD yet.
+	fn cd_canonical(dir: Url, hovered: Option<UrnBuf>, always: bool) {
+		tokio::spawn(async move {
+			let real = match long::canonicalize(&dir).await {
+				Ok(p) => Url::from(p),
+				Err(e) => {
+					return AppProxy::notify_warn("Cd", format!("Cannot resolve {}: {e}", dir.display()));
+				}
+			};
+
+			match hovered {
+				_ if real == dir && !always => {}
+				Some(h) => TabProxy::reveal(&real.join(h)),
+				None => TabProxy::cd(&real),
+			}
+		});
 	}
 
 	fn cd_interactive(&mut self) {
@@ -100,4 +172,59 @@ impl Tab {
 			}
 		});
 	}
+
+	// The recent directories matching what's typed are offered as completions,
+	// where the submitted one, or the best match of the query, is gone to
+	fn cd_recent(&mut self) {
+		let cwd = self.cwd().to_path_buf();
+		tokio::spawn(async move {
+			let mut rx = InputProxy::show(InputCfg::recent());
+			while let Some(result) = rx.recv().await {
+				match result {
+					Ok(s) => {
+						let found = Self::recent_matches(&s, &cwd, 1).await;
+						match found.into_iter().next() {
+							Some(dir) => TabProxy::cd(&Url::from(dir)),
+							None => AppProxy::notify_warn("Cd", format!("No recent directory matches `{s}`")),
+						}
+						break;
+					}
+					Err(InputError::Completed(before, ticket)) => {
+						let found = Self::recent_matches(&before, &cwd, 30).await;
+						if found.is_empty() {
+							CompletionProxy::close();
+						} else {
+							let cands = found.into_iter().filter_map(|p| p.into_os_string().into_string().ok());
+							CompletionProxy::show(cands.collect(), "", ticket);
+						}
+					}
+					_ => break,
+				}
+			}
+		});
+	}
+
+	// Those that no longer exist are forgotten as they're come across
+	async fn recent_matches(query: &str, cwd: &Path, limit: usize) -> Vec<PathBuf> {
+		// The submitted completion is taken as is
+		let query = query.trim();
+		let ranked = if Path::new(query).is_absolute() {
+			vec![PathBuf::from(query)]
+		} else {
+			RECENT_DIRS.lock().matches(query).into_iter().filter(|p| p != cwd).collect()
+		};
+
+		let mut found = Vec::with_capacity(limit);
+		for dir in ranked {
+			if fs::metadata(&dir).await.is_ok_and(|m| m.is_dir()) {
+				found.push(dir);
+			} else {
+				RECENT_DIRS.lock().forget(&dir);
+			}
+			if found.len() >= limit {
+				break;
+			}
+		}
+		found
+	}
 }
@@ -1,9 +1,25 @@
+use yazi_fs::archive::Vfs;
 use yazi_shared::event::Cmd;
 
 use crate::tab::Tab;
 
+struct Opt;
+impl From<()> for Opt {
+	fn from(_: ()) -> Self { Self }
+}
+impl From<Cmd> for Opt {
+	fn from(_: Cmd) -> Self { Self }
+}
+
 impl Tab {
-	pub fn enter(&mut self, _: Cmd) {
-		self.hovered().filter(|h| h.is_dir()).map(|h| h.url.to_regular()).map(|u| self.cd(u));
+	#[yazi_codegen::command]
+	pub fn enter(&mut self, _: Opt) {
+		let Some(h) = self.hovered() else { return };
+		if h.is_dir() {
+			let url = if h.url.is_archive() { h.url_owned() } else { h.url.to_regular() };
+			self.cd(url);
+		} else if Vfs::browsable(&h.url) {
+			self.cd(h.url.to_archive());
+		}
 	}
 }
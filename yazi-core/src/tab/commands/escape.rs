@@ -60,7 +60,14 @@ impl Tab {
 		}
 	}
 
-	pub fn escape_find(&mut self) -> bool { render_and!(self.finder.take().is_some()) }
+	pub fn escape_find(&mut self) -> bool {
+		if self.finder.take().is_none() {
+			return false;
+		}
+
+		self.pub_filter_find();
+		render_and!(true)
+	}
 
 	pub fn escape_visual(&mut self) -> bool {
 		if !self.mode.is_visual() {
@@ -86,6 +93,7 @@ impl Tab {
 		}
 
 		self.selected.clear();
+		self.selected.catchup_revision(self.idx);
 		if self.hovered().is_some_and(|h| h.is_dir()) {
 			ManagerProxy::peek(true);
 		}
@@ -110,9 +118,15 @@ impl Tab {
 			indices.into_iter().filter_map(|i| self.current.files.get(i)).map(|f| &f.url).collect();
 
 		let same = !self.cwd().is_search();
-		if !select {
+		let b = if !select {
 			self.selected.remove_many(&urls, same);
-		} else if self.selected.add_many(&urls, same) != urls.len() {
+			true
+		} else {
+			self.selected.add_many(&urls, same) == urls.len()
+		};
+
+		self.selected.catchup_revision(self.idx);
+		if !b {
 			AppProxy::notify_warn(
 				"Escape visual mode",
 				"Some files cannot be selected, due to path nesting conflict.",
@@ -14,6 +14,7 @@ use crate::tab::Tab;
 pub(super) struct Opt {
 	pub query: String,
 	pub case:  FilterCase,
+	pub tag:   Option<String>,
 	pub done:  bool,
 }
 
@@ -22,6 +23,7 @@ impl From<Cmd> for Opt {
 		Self {
 			query: c.take_first_str().unwrap_or_default(),
 			case:  FilterCase::from(&c),
+			tag:   c.take_str("tag"),
 			done:  c.bool("done"),
 		}
 	}
@@ -30,6 +32,10 @@ impl From<Cmd> for Opt {
 impl Tab {
 	#[yazi_codegen::command]
 	pub fn filter(&mut self, opt: Opt) {
+		if opt.tag.is_some() {
+			return self.filter_do(Opt { done: true, ..opt });
+		}
+
 		tokio::spawn(async move {
 			let rx = InputProxy::show(InputCfg::filter());
 
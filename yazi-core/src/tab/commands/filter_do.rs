@@ -8,7 +8,9 @@ use crate::tab::Tab;
 impl Tab {
 	#[yazi_codegen::command]
 	pub fn filter_do(&mut self, opt: Opt) {
-		let filter = if opt.query.is_empty() {
+		let filter = if let Some(tag) = opt.tag.as_deref().filter(|t| !t.is_empty()) {
+			Some(Filter::tag(tag))
+		} else if opt.query.is_empty() {
 			None
 		} else if let Ok(f) = Filter::new(&opt.query, opt.case) {
 			Some(f)
@@ -20,8 +22,10 @@ impl Tab {
 			ManagerProxy::update_paged(); // Update for paged files in next loop
 		}
 
+		// The file revealed is no longer kept, once filtered anew
 		let hovered = self.hovered().map(|f| f.urn_owned());
-		if !self.current.files.set_filter(filter) {
+		let unpinned = self.current.files.set_pinned(None);
+		if !self.current.files.set_filter(filter) && !unpinned {
 			return;
 		}
 
@@ -29,7 +33,11 @@ impl Tab {
 		if self.hovered().map(|f| f.urn()) != hovered.as_ref().map(|u| u.as_urn()) {
 			ManagerProxy::hover(None, self.idx);
 		}
+		if self.conf.follow_filter {
+			self.apply_files_attrs();
+		}
 
+		self.pub_filter_find();
 		render!();
 	}
 }
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use tokio::pin;
 use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
-use yazi_config::popup::InputCfg;
+use yazi_config::{MANAGER, popup::InputCfg};
 use yazi_fs::FilterCase;
 use yazi_macro::emit;
 use yazi_proxy::InputProxy;
@@ -11,14 +11,20 @@ use yazi_shared::{Debounce, Layer, errors::InputError, event::Cmd};
 use crate::tab::Tab;
 
 pub(super) struct Opt {
-	pub(super) query: Option<String>,
-	pub(super) prev:  bool,
-	pub(super) case:  FilterCase,
+	pub(super) query:  Option<String>,
+	pub(super) prev:   bool,
+	pub(super) case:   FilterCase,
+	pub(super) global: bool,
 }
 
 impl From<Cmd> for Opt {
 	fn from(mut c: Cmd) -> Self {
-		Self { query: c.take_first_str(), prev: c.bool("previous"), case: FilterCase::from(&c) }
+		let case = if c.bool("sensitive") || c.bool("smart") || c.bool("insensitive") {
+			FilterCase::from(&c)
+		} else {
+			MANAGER.find_case.into()
+		};
+		Self { query: c.take_first_str(), prev: c.bool("previous"), case, global: c.bool("global") }
 	}
 }
 
@@ -35,7 +41,9 @@ impl Tab {
 				emit!(Call(
 					Cmd::args("find_do", &[s])
 						.with_bool("previous", opt.prev)
+						.with_bool("global", opt.global)
 						.with_bool("smart", opt.case == FilterCase::Smart)
+						.with_bool("sensitive", opt.case == FilterCase::Sensitive)
 						.with_bool("insensitive", opt.case == FilterCase::Insensitive),
 					Layer::Manager
 				));
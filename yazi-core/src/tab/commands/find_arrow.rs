@@ -1,5 +1,5 @@
 use yazi_macro::render;
-use yazi_shared::event::Cmd;
+use yazi_shared::{event::Cmd, fs::Url};
 
 use crate::tab::Tab;
 
@@ -17,12 +17,35 @@ impl Tab {
 		let Some(finder) = &mut self.finder else {
 			return;
 		};
+		if let Some((_, url)) = finder.take_pending() {
+			return self.find_cross(url);
+		}
 
-		render!(finder.catchup(&self.current.files));
-		if opt.prev {
-			finder.prev(&self.current.files, self.current.cursor, false).map(|s| self.arrow(s));
+		render!(finder.catchup(&self.current));
+		let step = if opt.prev {
+			finder.prev(&self.current.files, self.current.cursor, false)
 		} else {
-			finder.next(&self.current.files, self.current.cursor, false).map(|s| self.arrow(s));
+			finder.next(&self.current.files, self.current.cursor, false)
+		};
+
+		// Going past the last match of the current pane goes on to the parent or child
+		let wrapped = !matches!(step, Some(s) if (s < 0) == opt.prev);
+		if finder.global && wrapped {
+			let child =
+				self.current.hovered().filter(|h| h.is_dir()).and_then(|h| self.history.get(&h.url));
+			let cross = finder.cross(&self.current.url, self.parent.as_ref(), child, opt.prev);
+			if let Some((_, url)) = cross {
+				return self.find_cross(url);
+			}
 		}
+
+		step.map(|s| self.arrow(s));
+	}
+
+	// A match in the parent is hovered after going up, and one in the child after
+	// entering it
+	fn find_cross(&mut self, url: Url) {
+		self.reveal(url);
+		render!();
 	}
 }
@@ -14,10 +14,10 @@ impl Tab {
 			return;
 		}
 
-		let Ok(finder) = Finder::new(&query, opt.case) else {
+		let Ok(mut finder) = Finder::new(&query, opt.case, opt.global) else {
 			return;
 		};
-		if matches!(&self.finder, Some(f) if f.filter == finder.filter) {
+		if matches!(&self.finder, Some(f) if f.filter == finder.filter && f.global == finder.global) {
 			return;
 		}
 
@@ -27,11 +27,16 @@ impl Tab {
 			finder.next(&self.current.files, self.current.cursor, true)
 		};
 
+		// With no match here, `find_arrow` goes to the one in the parent or child
 		if let Some(step) = step {
 			self.arrow(step);
+		} else if finder.global {
+			let cross = finder.cross(self.cwd(), self.parent.as_ref(), self.hovered_folder(), opt.prev);
+			finder.set_pending(cross);
 		}
 
 		self.finder = Some(finder);
+		self.pub_filter_find();
 		render!();
 	}
 }
@@ -0,0 +1,47 @@
+use yazi_fs::Folder;
+use yazi_proxy::ManagerProxy;
+use yazi_shared::event::Cmd;
+
+use crate::tab::Tab;
+
+impl Tab {
+	pub fn follow_filter(&mut self, mut c: Cmd) {
+		let state = match c.take_first_str().as_deref() {
+			Some("on") => true,
+			Some("off") => false,
+			_ => !self.conf.follow_filter,
+		};
+		if !self.conf.patch(|c| c.follow_filter = state) {
+			return;
+		}
+
+		// The filters followed so far go away along with it
+		if let Some(filter) = self.current.files.filter().cloned().filter(|_| !state) {
+			let unfollow = |f: &mut Folder| {
+				if f.files.filter() == Some(&filter) {
+					let hovered = f.hovered().map(|h| h.urn_owned());
+					f.files.set_filter(None);
+					f.repos(hovered.as_ref().map(|u| u.as_urn()));
+				}
+			};
+
+			self.parent.as_mut().map(unfollow);
+			self
+				.current
+				.hovered()
+				.filter(|h| h.is_dir())
+				.and_then(|h| self.history.get_mut(&h.url))
+				.map(unfollow);
+		}
+
+		let hovered = self.hovered().map(|f| f.url_owned());
+		self.apply_files_attrs();
+
+		if hovered.as_ref() != self.hovered().map(|f| &f.url) {
+			ManagerProxy::hover(hovered, self.idx);
+		} else if self.hovered().is_some_and(|f| f.is_dir()) {
+			ManagerProxy::peek(true);
+		}
+		ManagerProxy::update_paged();
+	}
+}
@@ -1,17 +1,38 @@
+use yazi_config::manager::ShowHidden;
+use yazi_macro::render;
 use yazi_proxy::ManagerProxy;
-use yazi_shared::event::Cmd;
 
-use crate::tab::Tab;
+use crate::{manager::DIR_VIEWS, tab::Tab};
 
 impl Tab {
-	pub fn hidden(&mut self, mut c: Cmd) {
-		self.conf.show_hidden = match c.take_first_str().as_deref() {
-			Some("show") => true,
-			Some("hide") => false,
-			_ => !self.conf.show_hidden,
-		};
+	/// Shows, dims or hides the hidden files of this tab.
+	pub fn hidden(&mut self, mode: ShowHidden) {
+		if mode == self.conf.show_hidden {
+			return;
+		}
+		DIR_VIEWS.lock().record(self.cwd(), |v| v.hidden = Some(mode));
+
+		// Dimming them lists the same files as showing them, only styled apart, so
+		// it's just the preview to be drawn again
+		let (old, state) = (self.conf.show_hidden.is_shown(), mode.is_shown());
+		self.conf.show_hidden = mode;
+		if old == state {
+			if self.hovered().is_some_and(|f| f.is_dir()) {
+				ManagerProxy::peek(true);
+			}
+			return render!();
+		}
 
-		let hovered = self.hovered().map(|f| f.url_owned());
+		// A hovered file going hidden is taken over by its nearest visible neighbor
+		let hovered = match self.hovered() {
+			Some(h) if !state && h.is_hidden() => {
+				let (files, cursor) = (&self.current.files, self.current.cursor);
+				files[cursor..].iter().chain(files[..cursor].iter().rev()).find(|f| !f.is_hidden())
+			}
+			h => h,
+		}
+		.map(|f| f.url_owned());
+		self.current.files.set_pinned(None);
 		self.apply_files_attrs();
 
 		if hovered.as_ref() != self.hovered().map(|f| &f.url) {
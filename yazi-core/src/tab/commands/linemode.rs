@@ -1,17 +1,22 @@
 use yazi_macro::render;
 use yazi_shared::event::Cmd;
 
-use crate::tab::Tab;
+use crate::{manager::DIR_VIEWS, tab::Tab};
 
 impl Tab {
 	pub fn linemode(&mut self, mut c: Cmd) {
-		render!(self.conf.patch(|new| {
+		let changed = self.conf.patch(|new| {
 			let Some(mode) = c.take_first_str() else {
 				return;
 			};
 			if !mode.is_empty() && mode.len() <= 20 {
 				new.linemode = mode;
 			}
-		}));
+		});
+
+		if changed {
+			DIR_VIEWS.lock().record(self.cwd(), |v| v.linemode = Some(self.conf.linemode.clone()));
+			render!();
+		}
 	}
 }
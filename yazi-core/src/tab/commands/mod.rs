@@ -10,6 +10,7 @@ yazi_macro::mod_flat!(
 	find
 	find_arrow
 	find_do
+	follow_filter
 	forward
 	hidden
 	leave
@@ -20,9 +21,9 @@ yazi_macro::mod_flat!(
 	select_all
 	shell
 	sort
-	spot
 	toggle
 	toggle_all
+	update_footer
 	update_peeked
 	update_spotted
 	visual_mode
@@ -5,6 +5,7 @@ use crate::tab::Tab;
 
 struct Opt {
 	target: Url,
+	open:   bool,
 }
 
 impl From<Cmd> for Opt {
@@ -14,11 +15,11 @@ impl From<Cmd> for Opt {
 			target = Url::from(expand_path(&target));
 		}
 
-		Self { target }
+		Self { target, open: c.bool("open") }
 	}
 }
 impl From<Url> for Opt {
-	fn from(target: Url) -> Self { Self { target } }
+	fn from(target: Url) -> Self { Self { target, open: false } }
 }
 
 impl Tab {
@@ -29,7 +30,17 @@ impl Tab {
 		};
 
 		self.cd(parent.clone());
+		// Kept visible even if it's hidden or filtered out, including when the files
+		// of the CWD arrive after it, until the cursor leaves it, or the CWD, the
+		// hidden mode or the filter changes
+		if *self.cwd() == parent {
+			self.current.files.set_pinned(Some(opt.target.urn_owned()));
+		}
+
 		FilesOp::Creating(parent, vec![File::from_dummy(opt.target.clone(), None)]).emit();
 		ManagerProxy::hover(Some(opt.target), self.idx);
+		if opt.open {
+			ManagerProxy::open_hovered();
+		}
 	}
 }
@@ -1,4 +1,4 @@
-use std::{mem, time::Duration};
+use std::{io, mem, time::Duration};
 
 use tokio::pin;
 use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
@@ -45,10 +45,10 @@ impl Tab {
 		}
 
 		let cwd = self.cwd().to_search(&opt.subject);
-		let hidden = self.conf.show_hidden;
+		let hidden = self.conf.show_hidden.is_shown();
 
 		self.search = Some(tokio::spawn(async move {
-			let rx = if opt.via == SearchOptVia::Rg {
+			let result = if opt.via == SearchOptVia::Rg {
 				external::rg(external::RgOpt {
 					cwd: cwd.clone(),
 					hidden,
@@ -62,16 +62,26 @@ impl Tab {
 					subject: opt.subject,
 					args: opt.args,
 				})
-			}?;
+			};
+
+			let ((), ticket) = (TabProxy::cd(&cwd), FilesOp::prepare(&cwd));
+			let rx = match result {
+				Ok(rx) => rx,
+				Err(e) => {
+					// Shown in the pane, e.g. when the search tool isn't installed
+					let kind = e.downcast_ref::<io::Error>().map_or(io::ErrorKind::Other, io::Error::kind);
+					FilesOp::IOErr(cwd, kind).emit();
+					return Err(e);
+				}
+			};
 
 			let rx = UnboundedReceiverStream::new(rx).chunks_timeout(1000, Duration::from_millis(300));
 			pin!(rx);
 
-			let ((), ticket) = (TabProxy::cd(&cwd), FilesOp::prepare(&cwd));
 			while let Some(chunk) = rx.next().await {
 				FilesOp::Part(cwd.clone(), chunk, ticket).emit();
 			}
-			FilesOp::Done(cwd, Cha::dummy(), ticket).emit();
+			FilesOp::Done(cwd, Cha::dummy(), ticket, None).emit();
 
 			Ok(())
 		}));
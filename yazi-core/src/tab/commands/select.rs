@@ -1,23 +1,32 @@
 use std::time::Duration;
 
+use yazi_fs::TAGS;
+use yazi_macro::render;
+use yazi_proxy::AppProxy;
 use yazi_shared::event::Cmd;
 
 use crate::tab::Tab;
 
-struct Opt;
+struct Opt {
+	tag: Option<String>,
+}
 
 impl From<Cmd> for Opt {
-	fn from(_: Cmd) -> Self { Self }
+	fn from(mut c: Cmd) -> Self { Self { tag: c.take_str("tag") } }
 }
 
 impl From<()> for Opt {
-	fn from(_: ()) -> Self { Self }
+	fn from(_: ()) -> Self { Self { tag: None } }
 }
 
 impl Tab {
-	// TODO: remove this in Yazi 0.4.1
 	#[yazi_codegen::command]
-	pub fn select(&mut self, _: Opt) {
+	pub fn select(&mut self, opt: Opt) {
+		if let Some(tag) = opt.tag {
+			return self.select_tagged(&tag);
+		}
+
+		// TODO: remove this in Yazi 0.4.1
 		yazi_proxy::AppProxy::notify(yazi_proxy::options::NotifyOpt {
 			title:   "Deprecated command".to_owned(),
 			content: "`select` and `select_all` command has been renamed to `toggle` and `toggle_all` in Yazi v0.4
@@ -25,6 +34,25 @@ impl Tab {
 Please change it in your keymap.toml, see #1773 for details: https://github.com/sxyazi/yazi/pull/1773".to_owned(),
 			level:   yazi_proxy::options::NotifyLevel::Error,
 			timeout: Duration::from_secs(20),
+			actions: vec![],
 		});
 	}
+
+	// Adds the files in the current directory tagged with `tag` to the selection
+	fn select_tagged(&mut self, tag: &str) {
+		let tags = TAGS.read();
+		let urls: Vec<_> =
+			self.current.files.iter().map(|f| &f.url).filter(|&u| tags.contains(u, tag)).collect();
+
+		let added = self.selected.add_many(&urls, !self.cwd().is_search());
+		render!(added > 0);
+		self.selected.catchup_revision(self.idx);
+
+		if added != urls.len() {
+			AppProxy::notify_warn(
+				"Select",
+				"Some files cannot be selected, due to path nesting conflict.",
+			);
+		}
+	}
 }
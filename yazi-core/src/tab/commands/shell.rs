@@ -1,9 +1,9 @@
-use std::{borrow::Cow, fmt::Display};
+use std::{borrow::Cow, collections::BTreeMap, fmt::Display};
 
 use anyhow::bail;
-use yazi_config::{open::Opener, popup::InputCfg};
+use yazi_config::{TASKS, open::Opener, popup::InputCfg};
 use yazi_proxy::{AppProxy, InputProxy, TasksProxy};
-use yazi_shared::event::{Cmd, Data};
+use yazi_shared::{event::{Cmd, Data}, shell::split_unix};
 
 use crate::tab::Tab;
 
@@ -13,7 +13,10 @@ pub struct Opt {
 	orphan:      bool,
 	confirm:     bool,
 	interactive: bool,
+	spawn:       bool,
 	cursor:      Option<usize>,
+	cwd:         Option<String>,
+	env:         BTreeMap<String, String>,
 }
 
 impl TryFrom<Cmd> for Opt {
@@ -26,7 +29,10 @@ impl TryFrom<Cmd> for Opt {
 			orphan:      c.bool("orphan"),
 			confirm:     c.bool("confirm"),
 			interactive: c.bool("interactive"),
+			spawn:       c.bool("spawn"),
 			cursor:      c.get("cursor").and_then(Data::as_usize),
+			cwd:         c.take_str("cwd"),
+			env:         Self::parse_env(&c.take_str("env").unwrap_or_default())?,
 		};
 
 		if me.cursor.is_some_and(|c| c > me.run.chars().count()) {
@@ -37,6 +43,20 @@ impl TryFrom<Cmd> for Opt {
 	}
 }
 
+impl Opt {
+	// `--env` takes space-separated `KEY=VALUE` pairs, quoted like in a Unix shell
+	fn parse_env(s: &str) -> anyhow::Result<BTreeMap<String, String>> {
+		let mut env = BTreeMap::new();
+		for pair in split_unix(s)? {
+			let Some((k, v)) = pair.split_once('=').filter(|(k, _)| !k.is_empty()) else {
+				bail!("Invalid environment variable `{pair}`, expected `KEY=VALUE`.");
+			};
+			env.insert(k.to_owned(), v.to_owned());
+		}
+		Ok(env)
+	}
+}
+
 impl Tab {
 	pub fn shell(&mut self, opt: impl TryInto<Opt, Error = impl Display>) {
 		if !self.try_escape_visual() {
@@ -48,6 +68,26 @@ impl Tab {
 			Err(e) => return AppProxy::notify_warn("`shell` command", e),
 		};
 
+		// Suspend for an interactive shell in the CWD, which inherits `YAZI_LEVEL`
+		// so that it can tell it's nested, and resume once it exits
+		if opt.spawn {
+			return TasksProxy::open_with(
+				vec![],
+				Cow::Owned(Opener {
+					run:    if opt.run.is_empty() { TASKS.spawn_shell() } else { opt.run },
+					block:  true,
+					orphan: false,
+					desc:   Default::default(),
+					for_:   None,
+					spread: true,
+					cwd:    opt.cwd.or_else(|| Some(self.cwd().to_string_lossy().into_owned())),
+					env:    opt.env,
+					guard:  None,
+				}),
+				self.origin(),
+			);
+		}
+
 		// TODO: Remove in v0.3.2
 		if !opt.interactive && !opt.confirm {
 			AppProxy::notify_error(
@@ -65,7 +105,7 @@ Please replace e.g. `shell` with `shell --interactive`, `shell "my-template"` wi
 			return;
 		}
 
-		let selected = self.hovered_and_selected(true).cloned().collect();
+		let (selected, origin) = (self.hovered_and_selected(true).cloned().collect(), self.origin());
 		tokio::spawn(async move {
 			if !opt.confirm || opt.run.is_empty() {
 				let mut result =
@@ -85,7 +125,11 @@ Please replace e.g. `shell` with `shell --interactive`, `shell "my-template"` wi
 					desc:   Default::default(),
 					for_:   None,
 					spread: true,
+					cwd:    opt.cwd,
+					env:    opt.env,
+					guard:  None,
 				}),
+				origin,
 			);
 		});
 	}
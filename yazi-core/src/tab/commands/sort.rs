@@ -1,22 +1,56 @@
 use std::str::FromStr;
 
-use yazi_config::manager::SortBy;
+use yazi_config::{MANAGER, manager::{SortBy, SortOpts}};
 use yazi_proxy::ManagerProxy;
 use yazi_shared::event::Cmd;
 
-use crate::{tab::Tab, tasks::Tasks};
+use crate::{manager::DIR_VIEWS, tab::Tab, tasks::Tasks};
 
 impl Tab {
 	pub fn sort(&mut self, mut c: Cmd, tasks: &Tasks) {
-		let conf = &mut self.conf;
-		if let Some(by) = c.take_first_str() {
-			conf.sort_by = SortBy::from_str(&by).unwrap_or_default();
+		if c.bool("reset") {
+			return self.sort_reset(tasks);
+		}
+
+		// Those given take the place of the overrides of the directories, too
+		let given = SortOpts {
+			sort_by:   c.take_first_str().map(|s| SortBy::from_str(&s).unwrap_or_default()),
+			reverse:   c.maybe_bool("reverse"),
+			dir_first: c.maybe_bool("dir-first"),
+			sensitive: c.maybe_bool("sensitive"),
+		};
+
+		// Remembered for the CWD alone, if each directory has its own
+		let per_dir = MANAGER.remember_views > 0;
+		if per_dir && self.cwd().is_regular() {
+			DIR_VIEWS.lock().record(self.cwd(), |v| v.sort = given.or(v.sort));
+		} else {
+			self.conf.set_sort(given, !per_dir);
 		}
 
-		conf.sort_reverse = c.maybe_bool("reverse").unwrap_or(conf.sort_reverse);
-		conf.sort_dir_first = c.maybe_bool("dir-first").unwrap_or(conf.sort_dir_first);
-		conf.sort_sensitive = c.maybe_bool("sensitive").unwrap_or(conf.sort_sensitive);
+		let conf = &mut self.conf;
 		conf.sort_translit = c.maybe_bool("translit").unwrap_or(conf.sort_translit);
+		conf.sort_by_locale = c.maybe_bool("locale").unwrap_or(conf.sort_by_locale);
+
+		self.apply_files_attrs();
+		ManagerProxy::update_paged();
+
+		tasks.prework_sorted(&self.current.files);
+	}
+
+	// Forgets the sorting remembered for the CWD, and goes back to the one of the
+	// config, along with the override of the directory if any
+	fn sort_reset(&mut self, tasks: &Tasks) {
+		DIR_VIEWS.lock().forget_sort(self.cwd());
+
+		let conf = &mut self.conf;
+		conf.sort_by = MANAGER.sort_by;
+		conf.sort_sensitive = MANAGER.sort_sensitive;
+		conf.sort_reverse = MANAGER.sort_reverse;
+		conf.sort_dir_first = MANAGER.sort_dir_first;
+		conf.sort_translit = MANAGER.sort_translit;
+		conf.sort_by_locale = MANAGER.sort_by_locale;
+		conf.sort_runtime = SortOpts::default();
 
 		self.apply_files_attrs();
 		ManagerProxy::update_paged();
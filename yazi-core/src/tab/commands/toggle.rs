@@ -1,11 +1,12 @@
 use yazi_macro::render_and;
 use yazi_proxy::AppProxy;
-use yazi_shared::event::Cmd;
+use yazi_shared::{event::{Cmd, Data}, fs::Url};
 
 use crate::tab::Tab;
 
 struct Opt {
 	state: Option<bool>,
+	url:   Option<Url>,
 }
 
 impl From<Cmd> for Opt {
@@ -16,6 +17,7 @@ impl From<Cmd> for Opt {
 				Some("off") => Some(false),
 				_ => None,
 			},
+			url:   c.take("url").and_then(Data::into_url),
 		}
 	}
 }
@@ -23,7 +25,7 @@ impl From<Cmd> for Opt {
 impl Tab {
 	#[yazi_codegen::command]
 	pub fn toggle(&mut self, opt: Opt) {
-		let Some(url) = self.current.hovered().map(|h| &h.url) else {
+		let Some(url) = opt.url.as_ref().or(self.current.hovered().map(|h| &h.url)) else {
 			return;
 		};
 
@@ -33,6 +35,8 @@ impl Tab {
 			None => render_and!(self.selected.remove(url) || self.selected.add(url)),
 		};
 
+		self.selected.catchup_revision(self.idx);
+
 		if !b {
 			AppProxy::notify_warn(
 				"Toggle",
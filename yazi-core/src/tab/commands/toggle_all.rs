@@ -38,6 +38,7 @@ impl Tab {
 
 		let added = self.selected.add_many(&addition, same);
 		render!(added > 0);
+		self.selected.catchup_revision(self.idx);
 
 		if added != addition.len() {
 			AppProxy::notify_warn(
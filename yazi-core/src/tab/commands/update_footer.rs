@@ -0,0 +1,27 @@
+use yazi_macro::render;
+use yazi_shared::{event::Cmd, fs::Url};
+
+use crate::tab::Tab;
+
+pub struct Opt {
+	url:  Url,
+	line: String,
+}
+
+impl TryFrom<Cmd> for Opt {
+	type Error = ();
+
+	fn try_from(mut c: Cmd) -> Result<Self, Self::Error> {
+		Ok(Self { url: c.take_any("url").ok_or(())?, line: c.take_str("line").ok_or(())? })
+	}
+}
+
+impl Tab {
+	pub fn update_footer(&mut self, opt: impl TryInto<Opt>) {
+		let Ok(opt) = opt.try_into() else {
+			return;
+		};
+
+		render!(self.preview.fill_footer(&opt.url, opt.line));
+	}
+}
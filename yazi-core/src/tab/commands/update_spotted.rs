@@ -1,10 +1,11 @@
-use yazi_plugin::utils::PreviewLock;
+use yazi_macro::render;
+use yazi_plugin::utils::SpotLock;
 use yazi_shared::event::Cmd;
 
 use crate::tab::Tab;
 
 pub struct Opt {
-	lock: PreviewLock,
+	lock: SpotLock,
 }
 
 impl TryFrom<Cmd> for Opt {
@@ -17,6 +18,19 @@ impl TryFrom<Cmd> for Opt {
 
 impl Tab {
 	pub fn update_spotted(&mut self, opt: impl TryInto<Opt>) {
-		todo!();
+		let Some(hovered) = self.hovered().map(|h| &h.url) else {
+			return self.spot.reset();
+		};
+
+		let Ok(opt) = opt.try_into() else {
+			return;
+		};
+
+		if opt.lock.url != *hovered || !self.spot.same_url(hovered) {
+			return;
+		}
+
+		self.spot.lock = Some(opt.lock);
+		render!();
 	}
 }
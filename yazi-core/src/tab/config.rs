@@ -1,4 +1,4 @@
-use yazi_config::{MANAGER, manager::SortBy};
+use yazi_config::{MANAGER, manager::{ShowHidden, SortBy, SortOpts, SortOverride}};
 use yazi_fs::FilesSorter;
 
 #[derive(Clone, PartialEq)]
@@ -9,10 +9,16 @@ pub struct Config {
 	pub sort_reverse:   bool,
 	pub sort_dir_first: bool,
 	pub sort_translit:  bool,
+	pub sort_by_locale: bool,
+	/// The options changed by `sort`, which take precedence over the overrides.
+	pub sort_runtime:   SortOpts,
 
 	// Display
 	pub linemode:    String,
-	pub show_hidden: bool,
+	pub show_hidden: ShowHidden,
+
+	// Filtering
+	pub follow_filter: bool,
 }
 
 impl Default for Config {
@@ -24,10 +30,15 @@ impl Default for Config {
 			sort_reverse:   MANAGER.sort_reverse,
 			sort_dir_first: MANAGER.sort_dir_first,
 			sort_translit:  MANAGER.sort_translit,
+			sort_by_locale: MANAGER.sort_by_locale,
+			sort_runtime:   SortOpts::default(),
 
 			// Display
 			linemode:    MANAGER.linemode.to_owned(),
 			show_hidden: MANAGER.show_hidden,
+
+			// Filtering
+			follow_filter: MANAGER.follow_filter,
 		}
 	}
 }
@@ -39,14 +50,111 @@ impl Config {
 		*self != old
 	}
 
-	#[inline]
-	pub(super) fn sorter(&self) -> FilesSorter {
+	/// The override of a directory, if it still has anything not changed by
+	/// `sort` since, nor remembered for the directory.
+	pub fn sort_override(
+		&self,
+		over: Option<&'static SortOverride>,
+		remembered: SortOpts,
+	) -> Option<&'static SortOverride> {
+		over.filter(|o| !o.opts.without(&self.sort_runtime.or(remembered)).is_empty())
+	}
+
+	/// Takes on the options `given` to `sort` until the CWD changes, and for the
+	/// whole tab as well with `tab_wide`, which isn't the case when each
+	/// directory remembers its own.
+	pub(super) fn set_sort(&mut self, given: SortOpts, tab_wide: bool) {
+		self.sort_runtime = given.or(self.sort_runtime);
+		if tab_wide {
+			let o = self.sort_runtime;
+			self.sort_by = given.sort_by.unwrap_or(self.sort_by);
+			self.sort_reverse = o.reverse.unwrap_or(self.sort_reverse);
+			self.sort_dir_first = o.dir_first.unwrap_or(self.sort_dir_first);
+			self.sort_sensitive = o.sensitive.unwrap_or(self.sort_sensitive);
+		}
+	}
+
+	/// The sorter of a directory, see [`Self::sort_opts`].
+	pub(super) fn sorter(&self, over: Option<&SortOverride>, remembered: SortOpts) -> FilesSorter {
+		let o = self.sort_opts(over, remembered);
 		FilesSorter {
-			by:        self.sort_by,
-			sensitive: self.sort_sensitive,
-			reverse:   self.sort_reverse,
-			dir_first: self.sort_dir_first,
+			by:        o.sort_by.unwrap_or(self.sort_by),
+			sensitive: o.sensitive.unwrap_or(self.sort_sensitive),
+			reverse:   o.reverse.unwrap_or(self.sort_reverse),
+			dir_first: o.dir_first.unwrap_or(self.sort_dir_first),
 			translit:  self.sort_translit,
+			locale:    self.sort_by_locale,
+			digits:    MANAGER.sort_digits,
+		}
+	}
+
+	/// The sort options of a directory, where the ones changed by `sort` take
+	/// precedence over the ones remembered for it, then over its override, and
+	/// then over those of the tab.
+	fn sort_opts(&self, over: Option<&SortOverride>, remembered: SortOpts) -> SortOpts {
+		self.sort_runtime.or(remembered).or(over.map(|o| o.opts).unwrap_or_default()).or(SortOpts {
+			sort_by:   Some(self.sort_by),
+			reverse:   Some(self.sort_reverse),
+			dir_first: Some(self.sort_dir_first),
+			sensitive: Some(self.sort_sensitive),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn config() -> Config {
+		Config {
+			sort_by:        SortBy::Alphabetical,
+			sort_sensitive: false,
+			sort_reverse:   false,
+			sort_dir_first: true,
+			sort_translit:  false,
+			sort_by_locale: false,
+			sort_runtime:   SortOpts::default(),
+			linemode:       "none".to_owned(),
+			show_hidden:    ShowHidden::Hide,
+			follow_filter:  false,
 		}
 	}
+
+	#[test]
+	fn test_sort_opts() {
+		let over = SortOverride {
+			path: "/a".to_owned(),
+			opts: SortOpts { sort_by: Some(SortBy::Mtime), reverse: Some(true), ..Default::default() },
+		};
+		let remembered = SortOpts { sort_by: Some(SortBy::Size), ..Default::default() };
+
+		// The tab's own is left for what none of the others sets
+		let mut c = config();
+		assert_eq!(c.sort_opts(Some(&over), remembered), SortOpts {
+			sort_by:   Some(SortBy::Size),
+			reverse:   Some(true),
+			dir_first: Some(true),
+			sensitive: Some(false),
+		});
+
+		// Kept out of the tab's own, where each directory remembers its own
+		let given =
+			SortOpts { sort_by: Some(SortBy::Natural), dir_first: Some(false), ..Default::default() };
+		c.set_sort(given, false);
+		assert_eq!(c.sort_opts(Some(&over), remembered).sort_by, Some(SortBy::Natural));
+		assert_eq!((c.sort_by, c.sort_dir_first), (SortBy::Alphabetical, true));
+
+		c.sort_runtime = SortOpts::default();
+		assert_eq!(c.sort_opts(None, SortOpts::default()).sort_by, Some(SortBy::Alphabetical));
+
+		// Or taken on by the whole tab otherwise
+		c.set_sort(given, true);
+		c.sort_runtime = SortOpts::default();
+		assert_eq!(c.sort_opts(None, SortOpts::default()), SortOpts {
+			sort_by:   Some(SortBy::Natural),
+			reverse:   Some(false),
+			dir_first: Some(false),
+			sensitive: Some(false),
+		});
+	}
 }
@@ -1,24 +1,42 @@
 use std::collections::HashMap;
 
 use anyhow::Result;
-use yazi_fs::{Files, Filter, FilterCase};
+use yazi_fs::{Files, Filter, FilterCase, Folder};
 use yazi_shared::fs::Url;
 
 pub struct Finder {
 	pub filter: Filter,
+	/// Whether the parent and the hovered child folder are searched as well,
+	/// once there're no more matches in the current one.
+	pub global: bool,
 	matched:    HashMap<Url, u8>,
-	revision:   u64,
+	revision:   (Url, u64),
+	pending:    Option<(FindPane, Url)>,
+}
+
+/// The pane a match of [`Finder`] lives in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FindPane {
+	Parent,
+	Current,
+	Child,
 }
 
 impl Finder {
-	pub(super) fn new(s: &str, case: FilterCase) -> Result<Self> {
-		Ok(Self { filter: Filter::new(s, case)?, matched: Default::default(), revision: 0 })
+	pub(super) fn new(s: &str, case: FilterCase, global: bool) -> Result<Self> {
+		Ok(Self {
+			filter: Filter::new(s, case)?,
+			global,
+			matched: Default::default(),
+			revision: Default::default(),
+			pending: None,
+		})
 	}
 
 	pub(super) fn prev(&self, files: &Files, cursor: usize, include: bool) -> Option<isize> {
 		for i in !include as usize..files.len() {
 			let idx = (cursor + files.len() - i) % files.len();
-			if self.filter.matches(files[idx].name()) {
+			if self.filter.matches(&files[idx]) {
 				return Some(idx as isize - cursor as isize);
 			}
 		}
@@ -28,22 +46,44 @@ impl Finder {
 	pub(super) fn next(&self, files: &Files, cursor: usize, include: bool) -> Option<isize> {
 		for i in !include as usize..files.len() {
 			let idx = (cursor + i) % files.len();
-			if self.filter.matches(files[idx].name()) {
+			if self.filter.matches(&files[idx]) {
 				return Some(idx as isize - cursor as isize);
 			}
 		}
 		None
 	}
 
-	pub(super) fn catchup(&mut self, files: &Files) -> bool {
-		if self.revision == files.revision {
+	/// The first match in the hovered `child` and then the `parent` of `cwd`, or
+	/// the last one in the `parent` and then the `child` if `prev`.
+	pub(super) fn cross(
+		&self,
+		cwd: &Url,
+		parent: Option<&Folder>,
+		child: Option<&Folder>,
+		prev: bool,
+	) -> Option<(FindPane, Url)> {
+		let find = |pane: FindPane, folder: Option<&Folder>| {
+			let mut it = folder?.files.iter().filter(|&f| f.url != *cwd && self.filter.matches(f));
+			let file = if prev { it.last() } else { it.next() };
+			file.map(|f| (pane, f.url_owned()))
+		};
+
+		if prev {
+			find(FindPane::Parent, parent).or_else(|| find(FindPane::Child, child))
+		} else {
+			find(FindPane::Child, child).or_else(|| find(FindPane::Parent, parent))
+		}
+	}
+
+	pub(super) fn catchup(&mut self, folder: &Folder) -> bool {
+		if self.revision.0 == folder.url && self.revision.1 == folder.files.revision {
 			return false;
 		}
 		self.matched.clear();
 
 		let mut i = 0u8;
-		for file in files.iter() {
-			if !self.filter.matches(file.name()) {
+		for file in folder.files.iter() {
+			if !self.filter.matches(file) {
 				continue;
 			}
 
@@ -55,9 +95,15 @@ impl Finder {
 			i += 1;
 		}
 
-		self.revision = files.revision;
+		self.revision = (folder.url.clone(), folder.files.revision);
 		true
 	}
+
+	#[inline]
+	pub(super) fn set_pending(&mut self, pending: Option<(FindPane, Url)>) { self.pending = pending; }
+
+	#[inline]
+	pub(super) fn take_pending(&mut self) -> Option<(FindPane, Url)> { self.pending.take() }
 }
 
 impl Finder {
@@ -66,4 +112,9 @@ impl Finder {
 
 	#[inline]
 	pub fn matched_idx(&self, url: &Url) -> Option<u8> { self.matched.get(url).copied() }
+
+	/// The pane of the match to go to, which is the hovered file in the current
+	/// one, unless only the parent or the child has any.
+	#[inline]
+	pub fn pane(&self) -> FindPane { self.pending.as_ref().map_or(FindPane::Current, |(p, _)| *p) }
 }
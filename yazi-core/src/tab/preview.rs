@@ -1,29 +1,33 @@
 use std::{borrow::Cow, ops::Not, time::Duration};
 
-use tokio::{pin, task::JoinHandle};
-use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
+use tokio::{fs, io::AsyncReadExt, task::JoinHandle, time::sleep};
 use tokio_util::sync::CancellationToken;
-use yazi_adapter::ADAPTOR;
-use yazi_config::PLUGIN;
+use yazi_adapter::{ADAPTOR, Image};
+use yazi_config::{PLUGIN, PREVIEW};
 use yazi_fs::Files;
-use yazi_macro::render;
-use yazi_plugin::{external::Highlighter, isolate, utils::PreviewLock};
-use yazi_shared::{MIME_DIR, fs::{Cha, File, FilesOp, Url}};
+use yazi_macro::{emit, render};
+use yazi_plugin::{external::Highlighter, isolate::{self, Peeker}, utils::PreviewLock};
+use yazi_shared::{Layer, MIME_DIR, event::Cmd, fs::{Cha, File, Url}};
 
 #[derive(Default)]
 pub struct Preview {
-	pub lock: Option<PreviewLock>,
-	pub skip: usize,
+	pub lock:   Option<PreviewLock>,
+	pub skip:   usize,
+	/// The first non-empty line of the hovered text file, or the size of the
+	/// hovered image, for `peek_footer`.
+	pub footer: Option<String>,
 
 	previewer_ct:  Option<CancellationToken>,
+	peeker:        Option<Peeker>,
 	folder_loader: Option<JoinHandle<()>>,
+	footer_loader: Option<(Url, JoinHandle<()>)>,
 }
 
 impl Preview {
 	pub fn go(&mut self, file: File, mime: Cow<'static, str>, force: bool) {
 		if mime.is_empty() {
 			return; // Wait till mimetype is resolved to avoid flickering
-		} else if !force && self.same_lock(&file, &mime) {
+		} else if !force && (self.same_lock(&file, &mime) || self.same_peeker(&file, &mime)) {
 			return;
 		}
 
@@ -32,7 +36,9 @@ impl Preview {
 		};
 
 		self.abort();
-		if previewer.sync {
+		if previewer.stateful && !previewer.sync {
+			self.peeker = Some(Peeker::new(&previewer.run, file, mime, self.skip));
+		} else if previewer.sync {
 			isolate::peek_sync(&previewer.run, file, mime, self.skip);
 		} else {
 			self.previewer_ct = Some(isolate::peek(&previewer.run, file, mime, self.skip));
@@ -49,26 +55,95 @@ impl Preview {
 			let Some(new) = Files::assert_stale(&cwd, dir.unwrap_or(Cha::dummy())).await else {
 				return;
 			};
-			let Ok(rx) = Files::from_dir(&cwd).await else { return };
+			Files::from_dir_chunked(cwd, new).await;
+		}));
+	}
+
+	/// Peeks at the first few bytes of `file` for the footer, after the same
+	/// delay as images, so only the file that stays hovered is read.
+	pub fn go_footer(&mut self, file: &File, mime: &str) {
+		if !PREVIEW.peek_footer || self.footer_loader.as_ref().is_some_and(|(u, _)| *u == file.url) {
+			return;
+		}
 
-			let stream =
-				UnboundedReceiverStream::new(rx).chunks_timeout(50000, Duration::from_millis(500));
-			pin!(stream);
+		self.reset_footer();
+		if file.is_dir() || mime.is_empty() {
+			return;
+		}
 
-			let ticket = FilesOp::prepare(&cwd);
-			while let Some(chunk) = stream.next().await {
-				FilesOp::Part(cwd.clone(), chunk, ticket).emit();
-			}
-			FilesOp::Done(cwd, new, ticket).emit();
-		}));
+		let (url, image) = (file.url_owned(), mime.starts_with("image/"));
+		self.footer_loader = Some((
+			url.clone(),
+			tokio::spawn(async move {
+				sleep(Duration::from_millis(PREVIEW.image_delay as u64)).await;
+				let line = if image {
+					Image::size(&url).await.ok().map(|(w, h)| format!("{w}x{h}"))
+				} else {
+					Self::first_line(&url).await
+				};
+				if let Some(line) = line {
+					emit!(Call(
+						Cmd::new("update_footer").with_any("url", url).with("line", line),
+						Layer::Manager
+					));
+				}
+			}),
+		));
+	}
+
+	#[inline]
+	pub fn reset_footer(&mut self) {
+		self.footer_loader.take().map(|(_, h)| h.abort());
+		render!(self.footer.take().is_some());
+	}
+
+	pub(super) fn fill_footer(&mut self, url: &Url, line: String) -> bool {
+		if self.footer_loader.as_ref().is_none_or(|(u, _)| u != url) {
+			return false;
+		}
+		self.footer = Some(line);
+		true
+	}
+
+	// The first non-empty line in the first 256 bytes, or `None` for binaries
+	async fn first_line(url: &Url) -> Option<String> {
+		let mut buf = Vec::with_capacity(256);
+		fs::File::open(url).await.ok()?.take(256).read_to_end(&mut buf).await.ok()?;
+		if buf.contains(&0) {
+			return None;
+		}
+
+		let s = match std::str::from_utf8(&buf) {
+			Ok(s) => s,
+			// Cut in the middle of a character
+			Err(e) if e.error_len().is_none() => std::str::from_utf8(&buf[..e.valid_up_to()]).ok()?,
+			Err(_) => return None,
+		};
+		s.lines().map(str::trim).find(|l| !l.is_empty()).map(ToOwned::to_owned)
 	}
 
 	#[inline]
 	pub fn abort(&mut self) {
 		self.previewer_ct.take().map(|ct| ct.cancel());
+		self.peeker = None;
 		Highlighter::abort();
 	}
 
+	/// Sends the `units` to the stateful previewer of the `url`, if any.
+	#[inline]
+	pub fn seek(&self, url: &Url, units: i16) -> bool {
+		let Some(peeker) = self.peeker.as_ref().filter(|p| p.same_url(url)) else { return false };
+		peeker.seek(units);
+		true
+	}
+
+	/// Notifies the stateful previewer of the new area, if any.
+	#[inline]
+	pub fn resize(&self) -> bool { self.peeker.as_ref().map(|p| p.resize()).is_some() }
+
+	#[inline]
+	pub fn stateful(&self) -> bool { self.peeker.is_some() }
+
 	#[inline]
 	pub fn reset(&mut self) {
 		self.abort();
@@ -91,6 +166,11 @@ impl Preview {
 			&& self.lock.as_ref().is_some_and(|l| file.cha.hits(l.cha) && mime == l.mime)
 	}
 
+	#[inline]
+	pub fn same_peeker(&self, file: &File, mime: &str) -> bool {
+		self.peeker.as_ref().is_some_and(|p| p.same_file(file, mime))
+	}
+
 	#[inline]
 	pub fn same_lock(&self, file: &File, mime: &str) -> bool {
 		self.same_file(file, mime) && self.lock.as_ref().is_some_and(|l| self.skip == l.skip)
@@ -1,11 +1,15 @@
 use std::{collections::HashMap, ops::Deref};
 
+use yazi_dds::Pubsub;
 use yazi_shared::{fs::{FilesOp, Url}, timestamp_us};
 
 #[derive(Default)]
 pub struct Selected {
 	inner:   HashMap<Url, u64>,
 	parents: HashMap<Url, usize>,
+
+	version:  u64,
+	revision: u64,
 }
 
 impl Deref for Selected {
@@ -58,6 +62,8 @@ impl Selected {
 		for u in parents {
 			*self.parents.entry(u).or_insert(0) += self.inner.len() - len;
 		}
+
+		self.revision += 1;
 		urls.len()
 	}
 
@@ -95,12 +101,19 @@ impl Selected {
 
 			parent = u.parent_url();
 		}
+
+		self.revision += 1;
 		count
 	}
 
 	pub fn clear(&mut self) {
+		if self.inner.is_empty() {
+			return;
+		}
+
 		self.inner.clear();
 		self.parents.clear();
+		self.revision += 1;
 	}
 
 	pub fn apply_op(&mut self, op: &FilesOp) {
@@ -112,6 +125,16 @@ impl Selected {
 			self.add_many(&addition, !op.cwd().is_search());
 		}
 	}
+
+	pub fn catchup_revision(&mut self, tab: usize) -> bool {
+		if self.version == self.revision {
+			return false;
+		}
+
+		self.version = self.revision;
+		Pubsub::pub_from_select(tab, &self.inner);
+		true
+	}
 }
 
 #[cfg(test)]
@@ -255,4 +278,22 @@ mod tests {
 		assert!(s.inner.is_empty());
 		assert!(s.parents.is_empty());
 	}
+
+	#[test]
+	fn test_revision() {
+		let mut s = Selected::default();
+
+		s.clear();
+		assert_eq!(s.revision, 0);
+
+		assert!(s.add(&Url::from("/a/b")));
+		assert!(!s.add(&Url::from("/a/b/c")));
+		assert_eq!(s.revision, 1);
+
+		assert!(!s.remove(&Url::from("/a/c")));
+		assert_eq!(s.revision, 1);
+
+		s.clear();
+		assert_eq!(s.revision, 2);
+	}
 }
@@ -5,12 +5,14 @@ use ratatui::layout::Rect;
 use tokio::task::JoinHandle;
 use yazi_adapter::Dimension;
 use yazi_config::{LAYOUT, popup::{Origin, Position}};
-use yazi_fs::{Folder, FolderStage};
+use yazi_dds::{Pubsub, body::{BodyFilter, BodyFind}};
+use yazi_fs::Folder;
 use yazi_macro::render;
+use yazi_proxy::{ManagerProxy, options::ProcessOrigin};
 use yazi_shared::fs::{File, Url};
 
 use super::{Backstack, Config, Finder, History, Mode, Preview};
-use crate::tab::Selected;
+use crate::{manager::DIR_VIEWS, spot::Spot, tab::Selected};
 
 #[derive(Default)]
 pub struct Tab {
@@ -25,8 +27,12 @@ pub struct Tab {
 	pub selected:  Selected,
 
 	pub preview: Preview,
+	pub spot:    Spot,
 	pub finder:  Option<Finder>,
 	pub search:  Option<JoinHandle<Result<()>>>,
+
+	// The filter and find last published, to tell when they change
+	pub(crate) published: Option<(BodyFilter, BodyFind)>,
 }
 
 impl Tab {
@@ -42,11 +48,22 @@ impl Tab {
 	#[inline]
 	pub fn cwd(&self) -> &Url { &self.current.url }
 
+	/// Where the processes queued from this tab run, which is the directory of
+	/// the archive when browsing inside one.
+	pub fn origin(&self) -> ProcessOrigin {
+		let cwd = match self.cwd().archive_split() {
+			Some((archive, _)) => archive.parent().unwrap_or(archive).to_owned(),
+			None => self.cwd().to_path(),
+		};
+		ProcessOrigin { cwd, tab: Some(self.idx) }
+	}
+
 	#[inline]
 	pub fn hovered(&self) -> Option<&File> { self.current.hovered() }
 
 	pub fn hovered_rect(&self) -> Option<Rect> {
-		let y = self.current.files.position(self.hovered()?.urn())? - self.current.offset;
+		self.hovered()?;
+		let y = self.current.cursor - self.current.offset;
 
 		let mut rect = LAYOUT.load().current;
 		rect.y = rect.y.saturating_sub(1) + y as u16;
@@ -95,15 +112,36 @@ impl Tab {
 		self.hovered().filter(|&h| h.is_dir()).and_then(|h| self.history.get(&h.url))
 	}
 
+	/// Unpins the file revealed in the CWD, once the cursor leaves it, keeping
+	/// the one hovered now.
+	pub(super) fn unpin_revealed(&mut self) {
+		let hovered = self.hovered().map(|h| h.urn_owned());
+		self.current.files.set_pinned(None);
+		render!(self.current.repos(hovered.as_ref().map(|u| u.as_urn())));
+	}
+
+	/// Applies the tag filter of the CWD again, if any, after the tags changed.
+	pub fn refilter_tagged(&mut self) {
+		if self.current.files.filter().and_then(|f| f.tagged()).is_none() {
+			return;
+		}
+
+		let hovered = self.hovered().map(|h| h.urn_owned());
+		self.current.files.refilter();
+		self.current.repos(hovered.as_ref().map(|u| u.as_urn()));
+		if self.hovered().map(|h| h.urn()) != hovered.as_ref().map(|u| u.as_urn()) {
+			ManagerProxy::hover(None, self.idx);
+		}
+		render!();
+	}
+
 	pub fn apply_files_attrs(&mut self) {
+		// Files read so far are merged into the sorted ones as well while loading
 		let apply = |f: &mut Folder| {
-			if f.stage == FolderStage::Loading {
-				return render!();
-			}
-
 			let hovered = f.hovered().filter(|_| f.tracing).map(|h| h.urn_owned());
-			f.files.set_show_hidden(self.conf.show_hidden);
-			f.files.set_sorter(self.conf.sorter());
+			f.files.set_show_hidden(self.conf.show_hidden.is_shown());
+			let remembered = DIR_VIEWS.lock().get(&f.url).map(|v| v.sort).unwrap_or_default();
+			f.files.set_sorter(self.conf.sorter(f.sort_override, remembered));
 
 			render!(f.files.catchup_revision());
 			render!(f.repos(hovered.as_ref().map(|u| u.as_urn())));
@@ -111,8 +149,20 @@ impl Tab {
 
 		apply(&mut self.current);
 
+		// The filter of the CWD is followed by the parent and preview as well
+		let filter = self.conf.follow_filter.then(|| self.current.files.filter().cloned());
+		let follow = |f: &mut Folder| {
+			if let Some(filter) = filter.clone() {
+				let hovered = f.hovered().map(|h| h.urn_owned());
+				f.files.set_filter(filter);
+				render!(f.repos(hovered.as_ref().map(|u| u.as_urn())));
+			}
+			apply(f);
+		};
+
 		if let Some(parent) = &mut self.parent {
-			apply(parent);
+			parent.files.set_pinned(Some(self.current.url.urn_owned()));
+			follow(parent);
 
 			// The parent should always track the CWD
 			parent.hover(self.current.url.urn());
@@ -124,6 +174,63 @@ impl Tab {
 			.hovered()
 			.filter(|h| h.is_dir())
 			.and_then(|h| self.history.get_mut(&h.url))
-			.map(apply);
+			.map(follow);
+	}
+}
+
+impl Tab {
+	/// The filter of the CWD, and how many files it matches.
+	pub fn filter_body(&self) -> BodyFilter {
+		let files = &self.current.files;
+		let Some(filter) = files.filter() else {
+			return BodyFilter { tab: self.idx, ..Default::default() };
+		};
+
+		BodyFilter {
+			tab:     self.idx,
+			pattern: Some(filter.pattern().to_owned()),
+			case:    filter.case().as_str().to_owned(),
+			tag:     filter.tagged().is_some(),
+			matched: files.matched(),
+		}
+	}
+
+	/// The find, and where the hovered file is among its matches.
+	pub fn find_body(&self) -> BodyFind {
+		let Some(finder) = &self.finder else {
+			return BodyFind { tab: self.idx, ..Default::default() };
+		};
+
+		BodyFind {
+			tab:     self.idx,
+			query:   Some(finder.filter.pattern().to_owned()),
+			case:    finder.filter.case().as_str().to_owned(),
+			current: self.hovered().and_then(|h| finder.matched_idx(&h.url)).map(|i| i as usize + 1),
+			total:   finder.matched().len(),
+		}
+	}
+
+	/// Publishes `@filter` and `@find` if they've changed since the last time.
+	pub fn pub_filter_find(&mut self) {
+		if let Some(finder) = &mut self.finder {
+			render!(finder.catchup(&self.current));
+		}
+
+		// A tab that has never had either starts off with neither
+		let (filter, find) = (self.filter_body(), self.find_body());
+		let (old_filter, old_find) = self.published.take().unwrap_or_else(|| {
+			(BodyFilter { tab: self.idx, ..Default::default() }, BodyFind {
+				tab: self.idx,
+				..Default::default()
+			})
+		});
+
+		if old_filter != filter {
+			Pubsub::pub_from_filter(&filter);
+		}
+		if old_find != find {
+			Pubsub::pub_from_find(&find);
+		}
+		self.published = Some((filter, find));
 	}
 }
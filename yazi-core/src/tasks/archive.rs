@@ -0,0 +1,33 @@
+use yazi_fs::archive::ArchiveKind;
+use yazi_proxy::AppProxy;
+use yazi_shared::fs::Url;
+
+use super::Tasks;
+
+impl Tasks {
+	pub fn archive_extract(&self, targets: &[&Url], cwd: &Url, here: bool) {
+		let mut unsupported = vec![];
+		for &u in targets {
+			let name = u.name().to_string_lossy();
+			let Some((kind, stem)) = ArchiveKind::detect(&name) else {
+				unsupported.push(name.into_owned());
+				continue;
+			};
+
+			let to = if here { cwd.clone() } else { cwd.join(stem) };
+			self.scheduler.archive_extract(u.clone(), to, kind, !here);
+		}
+
+		if !unsupported.is_empty() {
+			AppProxy::notify_warn(
+				"Extract",
+				format!("Not a supported archive:\n{}", unsupported.join("\n")),
+			);
+		}
+	}
+
+	#[inline]
+	pub fn archive_compress(&self, targets: Vec<Url>, to: Url, kind: ArchiveKind) {
+		self.scheduler.archive_compress(targets, to, kind);
+	}
+}
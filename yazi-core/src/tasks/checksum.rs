@@ -0,0 +1,11 @@
+use yazi_scheduler::checksum::ChecksumAlgo;
+use yazi_shared::fs::Url;
+
+use super::Tasks;
+
+impl Tasks {
+	#[inline]
+	pub fn checksum(&self, targets: Vec<Url>, algo: ChecksumAlgo, verify: bool) {
+		self.scheduler.checksum(targets, algo, verify);
+	}
+}
@@ -1 +1 @@
-yazi_macro::mod_flat!(arrow cancel inspect open_with process_exec toggle);
+yazi_macro::mod_flat!(arrow cancel inspect open_with plugin_job process_exec toggle);
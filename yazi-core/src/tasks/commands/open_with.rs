@@ -8,6 +8,8 @@ impl Tasks {
 			self.process_from_opener(
 				opt.opener,
 				opt.targets.into_iter().map(|u| u.into_path().into_os_string()).collect(),
+				opt.origin,
+				opt.done,
 			);
 		}
 	}
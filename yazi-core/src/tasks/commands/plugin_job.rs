@@ -0,0 +1,11 @@
+use yazi_proxy::options::PluginJobOpt;
+
+use crate::tasks::Tasks;
+
+impl Tasks {
+	pub fn plugin_job(&mut self, opt: impl TryInto<PluginJobOpt>) {
+		if let Ok(opt) = opt.try_into() {
+			self.scheduler.plugin_job(opt.title, opt.total, opt.done);
+		}
+	}
+}
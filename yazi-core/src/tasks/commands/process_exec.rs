@@ -1,11 +1,12 @@
-use yazi_proxy::options::ProcessExecOpt;
+use yazi_proxy::options::{ProcessExecOpt, ProcessOrigin};
 
 use crate::tasks::Tasks;
 
 impl Tasks {
 	pub fn process_exec(&mut self, opt: impl TryInto<ProcessExecOpt>) {
 		if let Ok(opt) = opt.try_into() {
-			self.scheduler.process_open(opt.opener, opt.args, Some(opt.done));
+			let origin = ProcessOrigin { cwd: opt.cwd, tab: None };
+			self.scheduler.process_open(opt.opener, opt.args, origin, Some(opt.done));
 		}
 	}
 }
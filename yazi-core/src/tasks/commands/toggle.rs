@@ -18,8 +18,7 @@ impl Tasks {
 		self.visible = !self.visible;
 
 		if self.visible {
-			self.summaries = self.paginate();
-			self.arrow(0);
+			self.repaginate();
 		}
 
 		render!();
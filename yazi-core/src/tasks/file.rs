@@ -1,33 +1,59 @@
 use std::collections::HashSet;
 
 use tracing::debug;
-use yazi_shared::fs::Url;
+use yazi_config::TASKS;
+use yazi_scheduler::file::FileBatch;
+use yazi_shared::fs::{Preserve, Url};
 
 use super::Tasks;
 
 impl Tasks {
-	pub fn file_cut(&self, src: &[&Url], dest: &Url, force: bool) {
+	pub fn file_cut(&self, src: &[&Url], dest: &Url, force: bool, preserve: bool) {
+		let (preserve, batch) = (Self::preserve(preserve), FileBatch::default());
 		for &u in src {
 			let to = dest.join(u.file_name().unwrap());
 			if force && *u == to {
 				debug!("file_cut: same file, skipping {:?}", to);
 			} else {
-				self.scheduler.file_cut(u.clone(), to, force);
+				self.scheduler.file_cut(u.clone(), to, force, preserve, batch.clone());
 			}
 		}
 	}
 
-	pub fn file_copy(&self, src: &[&Url], dest: &Url, force: bool, follow: bool) {
+	pub fn file_copy(&self, src: &[&Url], dest: &Url, force: bool, follow: bool, preserve: bool) {
+		let (preserve, batch) = (Self::preserve(preserve), FileBatch::default());
 		for &u in src {
 			let to = dest.join(u.file_name().unwrap());
 			if force && *u == to {
 				debug!("file_copy: same file, skipping {:?}", to);
+			} else if u.is_archive() {
+				self.scheduler.archive_copy(u.clone(), to, force);
 			} else {
-				self.scheduler.file_copy(u.clone(), to, force, follow);
+				self.scheduler.file_copy(u.clone(), to, force, follow, false, preserve, batch.clone());
 			}
 		}
 	}
 
+	/// Copies each of `pairs` from the first to the second, and hovers the first
+	/// copy once it's done.
+	pub fn file_duplicate(&self, pairs: Vec<(Url, Url)>) {
+		let batch = FileBatch::default();
+		for (i, (from, to)) in pairs.into_iter().enumerate() {
+			self.scheduler.file_copy(from, to, false, false, i == 0, Self::preserve(true), batch.clone());
+		}
+	}
+
+	/// What pasting keeps of the sources, which is nothing but the permissions
+	/// under the umask unless `preserve`, and the ownership, too, if configured
+	/// and running as root.
+	fn preserve(preserve: bool) -> Preserve {
+		#[cfg(unix)]
+		if preserve && TASKS.preserve_owner && unsafe { libc::getuid() } == 0 {
+			return Preserve::Owner;
+		}
+		if preserve { Preserve::Attrs } else { Preserve::None }
+	}
+
 	pub fn file_link(&self, src: &HashSet<Url>, dest: &Url, relative: bool, force: bool) {
 		for u in src {
 			let to = dest.join(u.file_name().unwrap());
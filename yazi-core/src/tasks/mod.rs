@@ -1,6 +1,6 @@
 yazi_macro::mod_pub!(commands);
 
-yazi_macro::mod_flat!(file plugin preload process progress tasks);
+yazi_macro::mod_flat!(archive checksum file plugin preload process progress tasks);
 
 pub const TASKS_BORDER: u16 = 2;
 pub const TASKS_PADDING: u16 = 2;
@@ -1,6 +1,8 @@
+use std::{collections::HashMap, sync::atomic::Ordering};
+
 use yazi_config::{PLUGIN, manager::SortBy, plugin::MAX_PREWORKERS};
-use yazi_fs::Files;
-use yazi_shared::{MIME_DIR, fs::File};
+use yazi_fs::{DirStats, Files, SIZE_LOADING};
+use yazi_shared::{MIME_DIR, fs::{File, FilesOp, Url}};
 
 use super::Tasks;
 use crate::manager::Mimetype;
@@ -9,7 +11,8 @@ impl Tasks {
 	pub fn fetch_paged(&self, paged: &[File], mimetype: &Mimetype) {
 		let mut loaded = self.scheduler.prework.loaded.lock();
 		let mut tasks: [Vec<_>; MAX_PREWORKERS as usize] = Default::default();
-		for f in paged {
+		// Entries of archives can't be read by plugins until they're materialized
+		for f in paged.iter().filter(|f| !f.url.is_archive()) {
 			let mime = if f.is_dir() { MIME_DIR } else { mimetype.get(&f.url).unwrap_or_default() };
 			let factors = |s: &str| match s {
 				"mime" => !mime.is_empty(),
@@ -34,9 +37,14 @@ impl Tasks {
 		}
 	}
 
-	pub fn preload_paged(&self, paged: &[File], mimetype: &Mimetype) {
+	pub fn preload_paged<'a>(&self, paged: impl IntoIterator<Item = &'a File>, mimetype: &Mimetype) {
+		// Paused while unfocused, the visible pages are preloaded again once focused
+		if !yazi_adapter::FOCUSED.load(Ordering::Relaxed) {
+			return;
+		}
+
 		let mut loaded = self.scheduler.prework.loaded.lock();
-		for f in paged {
+		for f in paged.into_iter().filter(|f| !f.url.is_archive()) {
 			let mime = if f.is_dir() { MIME_DIR } else { mimetype.get(&f.url).unwrap_or_default() };
 			for p in PLUGIN.preloaders(&f.url, mime) {
 				match loaded.get_mut(&f.url) {
@@ -49,6 +57,27 @@ impl Tasks {
 		}
 	}
 
+	/// Drops the queued preloads of the files that aren't in `retained` anymore
+	/// when they're about to run.
+	pub fn preload_retain(&self, retained: &[File]) {
+		*self.scheduler.prework.retained.write() =
+			Some(retained.iter().map(|f| f.url_owned()).collect());
+	}
+
+	/// Drops the queued fetches and preloads of the files that aren't in any of
+	/// the `folders` anymore when they're about to run.
+	pub fn prework_retain_folders(&self, folders: &[&Url]) {
+		*self.scheduler.prework.folders.write() =
+			Some(folders.iter().map(|&u| u.to_path_buf()).collect());
+	}
+
+	/// How many fetches and preloads have run, and been dropped before running.
+	#[inline]
+	pub fn prework_counts(&self) -> (usize, usize) {
+		let prework = &self.scheduler.prework;
+		(prework.completed.load(Ordering::Relaxed), prework.canceled.load(Ordering::Relaxed))
+	}
+
 	pub fn prework_affected(&self, affected: &[File], mimetype: &Mimetype) {
 		let mask = PLUGIN.fetchers_mask();
 		{
@@ -63,27 +92,55 @@ impl Tasks {
 	}
 
 	pub fn prework_sorted(&self, targets: &Files) {
-		if targets.sorter().by != SortBy::Size {
-			return;
+		if targets.sorter().by == SortBy::Size {
+			self.prework_size(targets, targets.iter());
 		}
+	}
 
-		let targets: Vec<_> = {
-			let loading = self.scheduler.prework.size_loading.read();
-			targets
-				.iter()
-				.filter(|f| f.is_dir() && !targets.sizes.contains_key(f.urn()) && !loading.contains(&f.url))
-				.map(|f| &f.url)
-				.collect()
-		};
-		if targets.is_empty() {
-			return;
+	/// Calculates the size of the directories among `targets`, which are in
+	/// `files`, reusing the cached sizes of the ones that haven't been modified.
+	pub fn prework_size<'a>(&self, files: &Files, targets: impl Iterator<Item = &'a File>) {
+		let Some(cwd) = files.iter().next().and_then(|f| f.url.parent_url()) else { return };
+
+		let (mut cached, mut todo) = (HashMap::new(), vec![]);
+		{
+			let loading = SIZE_LOADING.read();
+			for f in targets.filter(|f| f.is_dir() && !f.url.is_archive()) {
+				match DirStats::cached(&f.url, f.cha.mtime) {
+					Some(DirStats { len, .. }) => {
+						if files.sizes.get(f.urn()) != Some(&len) {
+							cached.insert(f.urn_owned(), len);
+						}
+					}
+					_ if loading.contains_key(&f.url) => {}
+					_ => todo.push(f),
+				}
+			}
 		}
 
-		let mut loading = self.scheduler.prework.size_loading.write();
-		for &target in &targets {
-			loading.insert(target.clone());
+		if !cached.is_empty() {
+			FilesOp::Size(cwd, cached).emit();
 		}
+		if todo.is_empty() {
+			return;
+		}
+
+		let mut loading = SIZE_LOADING.write();
+		let todo =
+			todo.into_iter().map(|f| (f, loading.entry(f.url_owned()).or_default().clone())).collect();
 
-		self.scheduler.prework_size(targets);
+		self.scheduler.prework_size(todo);
+	}
+
+	/// Cancels calculating the size of the directories that aren't in any of the
+	/// `cwds` anymore.
+	pub fn prework_size_cancel(&self, cwds: &[&Url]) {
+		SIZE_LOADING.write().retain(|url, canceled| {
+			let keep = url.parent_url().is_some_and(|p| cwds.contains(&&p));
+			if !keep {
+				canceled.store(true, Ordering::Relaxed);
+			}
+			keep
+		});
 	}
 }
@@ -1,41 +1,256 @@
 use std::{borrow::Cow, collections::HashMap, ffi::OsString, mem};
 
-use yazi_config::{OPEN, open::Opener};
-use yazi_shared::fs::Url;
+use tokio::sync::oneshot;
+use yazi_config::{OPEN, open::Opener, popup::ConfirmCfg};
+use yazi_proxy::{ConfirmProxy, TasksProxy, options::ProcessOrigin};
+use yazi_shared::{fs::Url, readable_size};
 
 use super::Tasks;
 
 impl Tasks {
-	pub fn process_from_files(&self, hovered: Url, targets: Vec<(Url, String)>) {
-		let mut openers = HashMap::new();
-		for (url, mime) in targets {
-			if let Some(opener) = OPEN.openers(&url, mime).and_then(|o| o.first().copied()) {
-				openers.entry(opener).or_insert_with(|| vec![hovered.clone()]).push(url);
+	/// Opens the `targets` with their first openers, where `sizes` are those
+	/// known of them, to ask before opening too much with a blocking one.
+	pub fn process_from_files(
+		&self,
+		hovered: Url,
+		targets: Vec<(Url, String)>,
+		sizes: &HashMap<Url, u64>,
+		origin: ProcessOrigin,
+	) {
+		let openers = Self::group_by_opener(hovered, targets, |url, mime| {
+			// Files without any opener configured are opened like double-clicking them on
+			// Windows
+			match OPEN.openers(url, mime).and_then(|o| o.first().copied()) {
+				Some(o) => Some(Cow::Borrowed(o)),
+				None if cfg!(windows) => Some(Cow::Owned(Opener::system(false))),
+				None => None,
 			}
-		}
-		for (opener, args) in openers {
+		});
+		for (opener, urls) in openers {
+			if let Some(cfg) = Self::large_open(&opener, &urls[1..], sizes) {
+				let origin = origin.clone();
+				tokio::spawn(async move {
+					if ConfirmProxy::show(cfg).await {
+						TasksProxy::open_with(urls, opener, origin);
+					}
+				});
+				continue;
+			}
+
 			self.process_from_opener(
-				Cow::Borrowed(opener),
-				args.into_iter().map(|u| u.into_path().into_os_string()).collect(),
+				opener,
+				urls.into_iter().map(|u| u.into_path().into_os_string()).collect(),
+				origin.clone(),
+				None,
 			);
 		}
 	}
 
-	pub fn process_from_opener(&self, opener: Cow<'static, Opener>, mut args: Vec<OsString>) {
+	/// The confirmation to ask for before opening `targets` with `opener`, if
+	/// it's a blocking one and their `sizes` add up to more than it allows.
+	///
+	/// Only the sizes already known are counted, as reading the metadata again
+	/// would be slow for the very files it's about.
+	pub fn large_open(
+		opener: &Opener,
+		targets: &[Url],
+		sizes: &HashMap<Url, u64>,
+	) -> Option<ConfirmCfg> {
+		let max = opener.guarded_size()?;
+		let total: u64 = targets.iter().filter_map(|u| sizes.get(u)).sum();
+		if total <= max {
+			return None;
+		}
+
+		let lines = targets
+			.iter()
+			.map(|u| match sizes.get(u) {
+				Some(&n) => format!("{u} ({})", readable_size(n)),
+				None => u.to_string(),
+			})
+			.collect();
+		let summary =
+			format!("{} in total, to be opened with `{}`:", readable_size(total), opener.desc);
+		Some(ConfirmCfg::large_open(lines, summary))
+	}
+
+	// The targets grouped by the opener of each, in the order they're selected,
+	// following the hovered one that leads each group
+	fn group_by_opener(
+		hovered: Url,
+		targets: Vec<(Url, String)>,
+		opener: impl Fn(&Url, String) -> Option<Cow<'static, Opener>>,
+	) -> Vec<(Cow<'static, Opener>, Vec<Url>)> {
+		let mut groups: Vec<(Cow<Opener>, Vec<Url>)> = vec![];
+		for (url, mime) in targets {
+			let Some(o) = opener(&url, mime) else { continue };
+			match groups.iter_mut().find(|(g, _)| *g == o) {
+				Some((_, urls)) => urls.push(url),
+				None => groups.push((o, vec![hovered.clone(), url])),
+			}
+		}
+		groups
+	}
+
+	/// Runs the `opener` with `args`, the hovered file followed by the targets,
+	/// at once if it spreads them or once for each of them otherwise, and tells
+	/// `done` once all of those have finished.
+	pub fn process_from_opener(
+		&self,
+		opener: Cow<'static, Opener>,
+		mut args: Vec<OsString>,
+		origin: ProcessOrigin,
+		done: Option<oneshot::Sender<()>>,
+	) {
 		if opener.spread {
-			self.scheduler.process_open(opener, args, None);
+			self.scheduler.process_open(opener, args, origin, done);
 			return;
 		}
 		if args.is_empty() {
 			return;
 		}
 		if args.len() == 2 {
-			self.scheduler.process_open(opener, args, None);
+			self.scheduler.process_open(opener, args, origin, done);
 			return;
 		}
 		let hovered = mem::take(&mut args[0]);
+		let mut each = Vec::with_capacity(args.len() - 1);
 		for target in args.into_iter().skip(1) {
-			self.scheduler.process_open(opener.clone(), vec![hovered.clone(), target], None);
+			let args = vec![hovered.clone(), target];
+			let (tx, rx) = done.as_ref().map(|_| oneshot::channel()).unzip();
+			self.scheduler.process_open(opener.clone(), args, origin.clone(), tx);
+			each.extend(rx);
+		}
+		if let Some(done) = done {
+			tokio::spawn(async move {
+				futures::future::join_all(each).await;
+				done.send(()).ok();
+			});
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Once;
+
+	use super::*;
+
+	fn init() {
+		static INIT: Once = Once::new();
+		INIT.call_once(|| {
+			yazi_shared::init();
+			yazi_boot::init_default();
+			yazi_config::init(&[], None).unwrap();
+			yazi_dds::init();
+		});
+	}
+
+	#[test]
+	fn test_group_by_opener() {
+		let opener = |run: &str| Opener { run: run.to_owned(), ..Opener::system(false) };
+		let (edit, play) = (opener("edit"), opener("play"));
+
+		let targets = ["b.rs", "my song.mp3", "a b.txt", "z.bin", "c.md", "x.mp4"]
+			.map(|s| (Url::from(format!("/d/{s}")), String::new()));
+		let groups = Tasks::group_by_opener(Url::from("/d/b.rs"), targets.to_vec(), |u, _| {
+			Some(Cow::Owned(match u.extension()?.to_str()? {
+				"mp3" | "mp4" => play.clone(),
+				"bin" => return None,
+				_ => edit.clone(),
+			}))
+		});
+
+		let names: Vec<Vec<_>> = groups
+			.iter()
+			.map(|(_, urls)| urls.iter().map(|u| u.name().to_str().unwrap()).collect())
+			.collect();
+		assert_eq!(groups.iter().map(|(o, _)| o.run.as_str()).collect::<Vec<_>>(), ["edit", "play"]);
+		assert_eq!(names, [vec!["b.rs", "b.rs", "a b.txt", "c.md"], vec![
+			"b.rs",
+			"my song.mp3",
+			"x.mp4"
+		]]);
+	}
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_origin_after_switch() {
+		use std::{env, time::Duration};
+
+		use yazi_fs::Folder;
+
+		use crate::tab::Tab;
+
+		init();
+
+		let root =
+			env::temp_dir().canonicalize().unwrap().join(format!("yazi-origin-{}", std::process::id()));
+		let (a, b) = (root.join("a"), root.join("b"));
+		std::fs::create_dir_all(&a).unwrap();
+		std::fs::create_dir_all(&b).unwrap();
+
+		let tabs: Vec<_> = [&a, &b]
+			.into_iter()
+			.enumerate()
+			.map(|(idx, dir)| Tab { idx, current: Folder::from(&Url::from(dir)), ..Default::default() })
+			.collect();
+
+		// Queued from tab A, and run after switching to tab B, whose CWD Yazi follows
+		let (origin, prev) = (tabs[0].origin(), env::current_dir().unwrap());
+		env::set_current_dir(tabs[1].cwd()).unwrap();
+
+		let tasks = Tasks::serve();
+		let opener =
+			Opener { run: "pwd -P > cwd.txt".to_owned(), orphan: false, ..Opener::system(false) };
+		let args = vec![a.join("f").into_os_string(), a.join("f").into_os_string()];
+		tasks.process_from_opener(Cow::Owned(opener), args, origin, None);
+
+		let out = a.join("cwd.txt");
+		for _ in 0..100 {
+			if let Ok(s) = std::fs::read_to_string(&out) {
+				if s.ends_with('\n') {
+					break;
+				}
+			}
+			tokio::time::sleep(Duration::from_millis(50)).await;
+		}
+
+		tasks.shutdown();
+		env::set_current_dir(prev).unwrap();
+		let written = std::fs::read_to_string(&out);
+		std::fs::remove_dir_all(&root).ok();
+		assert_eq!(written.unwrap().trim_end(), a.to_str().unwrap());
+		assert!(!b.join("cwd.txt").exists());
+	}
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_done_after_all() {
+		init();
+		let root = std::env::temp_dir()
+			.canonicalize()
+			.unwrap()
+			.join(format!("yazi-done-{}", std::process::id()));
+		std::fs::create_dir_all(&root).unwrap();
+
+		// Run once for each of the targets, the one given last finishing first
+		let tasks = Tasks::serve();
+		let opener = Opener {
+			run: r#"sleep "0.$(basename "$1")"; echo "$1" >> done.txt"#.to_owned(),
+			orphan: false,
+			..Opener::system(false)
+		};
+		let args = ["1", "3", "2", "1"].map(|s| root.join(s).into_os_string()).to_vec();
+		let origin = ProcessOrigin { cwd: root.clone(), tab: None };
+
+		let (tx, rx) = oneshot::channel();
+		tasks.process_from_opener(Cow::Owned(opener), args, origin, Some(tx));
+		rx.await.unwrap();
+
+		tasks.shutdown();
+		let written = std::fs::read_to_string(root.join("done.txt"));
+		std::fs::remove_dir_all(&root).ok();
+		assert_eq!(written.unwrap().lines().count(), 3);
+	}
+}
@@ -59,10 +59,130 @@ impl Tasks {
 			as usize
 	}
 
+	/// The summaries of the tasks that fit in the list, grouped by their state,
+	/// and in the order they were added within each group, with the room for
+	/// the header of each group taken into account.
 	pub fn paginate(&self) -> Vec<TaskSummary> {
-		self.ongoing().lock().values().take(Self::limit()).map(Into::into).collect()
+		let summaries = self.ongoing().lock().values().map(Into::into).collect();
+		Self::paginate_within(summaries, Self::limit())
+	}
+
+	/// Updates the summaries, keeping the cursor on the task it was on if it's
+	/// still there, and returns whether they've changed.
+	pub fn repaginate(&mut self) -> bool {
+		let new = self.paginate();
+		let same = |(a, b): (&TaskSummary, &TaskSummary)| {
+			a.id == b.id && a.name == b.name && a.state() == b.state()
+		};
+		if new.len() == self.summaries.len() && new.iter().zip(&self.summaries).all(same) {
+			return false;
+		}
+
+		self.cursor = Self::anchor(&self.summaries, self.cursor, &new);
+		self.summaries = new;
+		self.arrow(0);
+		true
+	}
+
+	fn paginate_within(mut summaries: Vec<TaskSummary>, limit: usize) -> Vec<TaskSummary> {
+		summaries.sort_unstable_by_key(|s| (s.state(), s.id));
+
+		let mut rows = 0;
+		let mut last = None;
+		summaries
+			.into_iter()
+			.take_while(|s| {
+				rows += 1 + (last.replace(s.state()) != Some(s.state())) as usize;
+				rows <= limit
+			})
+			.collect()
+	}
+
+	// Where the cursor at `cursor` of `old` goes in `new`, which is the task it
+	// was on if it's still there, or stays where it was otherwise
+	fn anchor(old: &[TaskSummary], cursor: usize, new: &[TaskSummary]) -> usize {
+		old.get(cursor).and_then(|o| new.iter().position(|s| s.id == o.id)).unwrap_or(cursor)
+	}
+
+	/// The ID of the hovered task.
+	#[inline]
+	pub fn hovered_id(&self) -> Option<usize> { self.summaries.get(self.cursor).map(|s| s.id) }
+
+	/// The summaries of all the ongoing tasks in the order they were added, for
+	/// plugins and DDS queries, holding the lock only while copying them.
+	pub fn snapshot(&self) -> Vec<TaskSummary> {
+		let mut summaries: Vec<TaskSummary> = self.ongoing().lock().values().map(Into::into).collect();
+		summaries.sort_unstable_by_key(|s| s.id);
+		summaries
 	}
 
 	#[inline]
 	pub fn ongoing(&self) -> &Arc<Mutex<Ongoing>> { &self.scheduler.ongoing }
 }
+
+#[cfg(test)]
+mod tests {
+	use yazi_scheduler::{TaskKind, TaskStage};
+
+	use super::*;
+
+	fn summary(id: usize, running: bool, fail: u32) -> TaskSummary {
+		TaskSummary {
+			id,
+			kind: TaskKind::User,
+			name: format!("Task {id}"),
+			stage: if fail > 0 { TaskStage::Dispatched } else { TaskStage::Pending },
+			total: fail,
+			succ: 0,
+			fail,
+			found: 0,
+			processed: 0,
+			running,
+		}
+	}
+
+	#[test]
+	fn test_paginate() {
+		let all = || {
+			vec![
+				summary(5, false, 0),
+				summary(1, true, 0),
+				summary(3, false, 1),
+				summary(2, false, 0),
+				summary(4, true, 0),
+			]
+		};
+		let ids = |limit| Tasks::paginate_within(all(), limit).iter().map(|s| s.id).collect::<Vec<_>>();
+
+		// Running, pending, then finished, with a header row before each group
+		assert_eq!(ids(10), [1, 4, 2, 5, 3]);
+		assert_eq!(ids(7), [1, 4, 2, 5]);
+		assert_eq!(ids(6), [1, 4, 2, 5]);
+		assert_eq!(ids(5), [1, 4, 2]);
+		assert_eq!(ids(4), [1, 4]);
+		assert_eq!(ids(0), [0; 0]);
+	}
+
+	#[test]
+	fn test_anchor() {
+		let old = Tasks::paginate_within(
+			vec![summary(1, true, 0), summary(2, false, 0), summary(3, false, 0)],
+			10,
+		);
+		assert_eq!(old.iter().map(|s| s.id).collect::<Vec<_>>(), [1, 2, 3]);
+
+		// Task 3 starts running, and moves ahead of task 2
+		let new = Tasks::paginate_within(
+			vec![summary(1, true, 0), summary(2, false, 0), summary(3, true, 0)],
+			10,
+		);
+		assert_eq!(Tasks::anchor(&old, 2, &new), 1);
+		assert_eq!(Tasks::anchor(&old, 1, &new), 2);
+
+		// Task 1 is gone, so the cursor stays put
+		let new = Tasks::paginate_within(vec![summary(2, false, 0), summary(3, false, 0)], 10);
+		assert_eq!(Tasks::anchor(&old, 0, &new), 0);
+		assert_eq!(Tasks::anchor(&old, 2, &new), 1);
+		assert_eq!(Tasks::anchor(&[], 0, &new), 0);
+	}
+}
@@ -0,0 +1,14 @@
+use yazi_macro::render;
+use yazi_shared::event::Cmd;
+
+use crate::which::Which;
+
+impl Which {
+	pub fn popup(&mut self, _: Cmd) {
+		self.popup_handle = None;
+		if self.visible && !self.popped {
+			self.popped = true;
+			render!();
+		}
+	}
+}
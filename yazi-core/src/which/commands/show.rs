@@ -40,6 +40,7 @@ impl Which {
 
 		self.visible = true;
 		self.silent = opt.silent;
+		self.popped = true;
 		render!();
 	}
 
@@ -56,6 +57,7 @@ impl Which {
 		WhichSorter::default().sort(&mut self.cands);
 		self.visible = true;
 		self.silent = false;
+		self.arm();
 		render!();
 	}
 }
@@ -0,0 +1,38 @@
+use std::collections::VecDeque;
+
+use yazi_config::{WHICH, which::OnTimeout};
+use yazi_macro::render;
+use yazi_shared::{Layer, event::{Cmd, Data}};
+
+use crate::which::Which;
+
+pub struct Opt {
+	times: usize,
+}
+
+impl TryFrom<Cmd> for Opt {
+	type Error = ();
+
+	fn try_from(c: Cmd) -> Result<Self, Self::Error> {
+		Ok(Self { times: c.first().and_then(Data::as_usize).ok_or(())? })
+	}
+}
+
+impl Which {
+	/// Gives up on the pending keys, returns the commands of the chord they
+	/// exactly are, if `on_timeout` is set to run it, along with its layer.
+	pub fn timeout(&mut self, opt: impl TryInto<Opt>) -> Option<(VecDeque<Cmd>, Layer)> {
+		let opt = opt.try_into().ok()?;
+		if !self.visible || opt.times != self.times {
+			return None;
+		}
+
+		let exact = self.exact().filter(|_| WHICH.on_timeout == OnTimeout::Exact);
+		let chord = exact.map(|i| self.cands.swap_remove(i));
+
+		let layer = self.layer;
+		self.reset();
+		render!();
+		chord.map(|c| (c.into_seq(), layer))
+	}
+}
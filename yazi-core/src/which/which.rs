@@ -1,6 +1,9 @@
-use yazi_config::keymap::{ChordCow, Key};
-use yazi_macro::{emit, render_and};
-use yazi_shared::Layer;
+use std::{collections::VecDeque, time::Duration};
+
+use tokio::task::JoinHandle;
+use yazi_config::{WHICH, keymap::{ChordCow, Key}, which::OnTimeout};
+use yazi_macro::{emit, render};
+use yazi_shared::{Layer, event::Cmd};
 
 #[derive(Default)]
 pub struct Which {
@@ -11,31 +14,89 @@ pub struct Which {
 	// Visibility
 	pub visible: bool,
 	pub silent:  bool,
+	/// Whether the candidates are shown, which happens `popup_delay` after the
+	/// first key, while only the pending keys are until then.
+	pub popped:  bool,
+
+	// Timers
+	pub(super) popup_handle:   Option<JoinHandle<()>>,
+	pub(super) timeout_handle: Option<JoinHandle<()>>,
 }
 
 impl Which {
-	pub fn type_(&mut self, key: Key) -> bool {
+	/// Types a key, returns the commands of the chord it completes, along with
+	/// the layer they belong to.
+	pub fn type_(&mut self, key: Key) -> Option<(VecDeque<Cmd>, Layer)> {
 		self.cands.retain(|c| c.on.len() > self.times && c.on[self.times] == key);
 		self.times += 1;
+		render!();
+
+		let wait = WHICH.on_timeout == OnTimeout::Exact && WHICH.timeout > 0;
+		let chord = if self.cands.len() == 1 {
+			self.cands.remove(0)
+		} else if let Some(i) = self.exact().filter(|_| !wait) {
+			self.cands.remove(i)
+		} else {
+			if self.cands.is_empty() {
+				self.reset();
+			} else {
+				self.arm();
+			}
+			return None;
+		};
+
+		let layer = self.layer;
+		self.reset();
+		Some((chord.into_seq(), layer))
+	}
+
+	/// The keys typed so far, that all the candidates start with.
+	#[inline]
+	pub fn pending(&self) -> &[Key] {
+		self.cands.first().map_or(&[], |c| &c.on[..self.times.min(c.on.len())])
+	}
+
+	/// The index of the candidate that's exactly the keys typed so far.
+	#[inline]
+	pub(super) fn exact(&self) -> Option<usize> {
+		self.cands.iter().position(|c| c.on.len() == self.times)
+	}
 
-		if self.cands.is_empty() {
-			self.reset();
-		} else if self.cands.len() == 1 {
-			emit!(Seq(self.cands.remove(0).into_seq(), self.layer));
-			self.reset();
-		} else if let Some(i) = self.cands.iter().position(|c| c.on.len() == self.times) {
-			emit!(Seq(self.cands.remove(i).into_seq(), self.layer));
-			self.reset();
+	/// Starts the timers of the popup and the timeout over, for the keys typed
+	/// so far.
+	pub(super) fn arm(&mut self) {
+		self.timeout_handle.take().map(|h| h.abort());
+		if WHICH.timeout > 0 {
+			let (times, timeout) = (self.times, Duration::from_millis(WHICH.timeout));
+			self.timeout_handle = Some(tokio::spawn(async move {
+				tokio::time::sleep(timeout).await;
+				emit!(Call(Cmd::args("timeout", &[times]), Layer::Which));
+			}));
 		}
 
-		render_and!(true)
+		if self.popped || self.popup_handle.is_some() {
+			return;
+		}
+		if WHICH.popup_delay == 0 {
+			self.popped = true;
+		} else {
+			let delay = Duration::from_millis(WHICH.popup_delay);
+			self.popup_handle = Some(tokio::spawn(async move {
+				tokio::time::sleep(delay).await;
+				emit!(Call(Cmd::new("popup"), Layer::Which));
+			}));
+		}
 	}
 
-	fn reset(&mut self) {
+	pub(super) fn reset(&mut self) {
 		self.times = 0;
 		self.cands.clear();
 
 		self.visible = false;
 		self.silent = false;
+		self.popped = false;
+
+		self.popup_handle.take().map(|h| h.abort());
+		self.timeout_handle.take().map(|h| h.abort());
 	}
 }
@@ -2,7 +2,7 @@ use anyhow::{Result, bail};
 use mlua::{ExternalResult, IntoLua, Lua, Value};
 use serde::Serialize;
 
-use super::{BodyBulk, BodyBye, BodyCd, BodyCustom, BodyDelete, BodyHey, BodyHi, BodyHover, BodyMove, BodyRename, BodyTab, BodyTrash, BodyYank};
+use super::{BodyBulk, BodyBye, BodyCd, BodyCopy, BodyCustom, BodyDelete, BodyFilter, BodyFind, BodyHey, BodyHi, BodyHover, BodyMove, BodyRename, BodySelect, BodyTab, BodyTask, BodyTrash, BodyYank};
 use crate::Payload;
 
 #[derive(Debug, Serialize)]
@@ -17,9 +17,14 @@ pub enum Body<'a> {
 	Rename(BodyRename<'a>),
 	Bulk(BodyBulk<'a>),
 	Yank(BodyYank<'a>),
+	Filter(BodyFilter),
+	Find(BodyFind),
+	Select(BodySelect<'a>),
 	Move(BodyMove<'a>),
+	Copy(BodyCopy<'a>),
 	Trash(BodyTrash<'a>),
 	Delete(BodyDelete<'a>),
+	Task(BodyTask),
 	Custom(BodyCustom),
 }
 
@@ -35,9 +40,14 @@ impl Body<'static> {
 			"rename" => Self::Rename(serde_json::from_str(body)?),
 			"bulk" => Self::Bulk(serde_json::from_str(body)?),
 			"@yank" => Self::Yank(serde_json::from_str(body)?),
+			"@filter" => Self::Filter(serde_json::from_str(body)?),
+			"@find" => Self::Find(serde_json::from_str(body)?),
+			"@select" => Self::Select(serde_json::from_str(body)?),
 			"move" => Self::Move(serde_json::from_str(body)?),
+			"copy" => Self::Copy(serde_json::from_str(body)?),
 			"trash" => Self::Trash(serde_json::from_str(body)?),
 			"delete" => Self::Delete(serde_json::from_str(body)?),
+			"task" => Self::Task(serde_json::from_str(body)?),
 			_ => BodyCustom::from_str(kind, body)?,
 		})
 	}
@@ -59,9 +69,14 @@ impl Body<'static> {
 				| "rename"
 				| "bulk"
 				| "@yank"
+				| "@filter"
+				| "@find"
+				| "@select"
 				| "move"
+				| "copy"
 				| "trash"
 				| "delete"
+				| "task"
 		) {
 			bail!("Cannot construct system event");
 		}
@@ -91,9 +106,14 @@ impl<'a> Body<'a> {
 			Self::Rename(_) => "rename",
 			Self::Bulk(_) => "bulk",
 			Self::Yank(_) => "@yank",
+			Self::Filter(_) => "@filter",
+			Self::Find(_) => "@find",
+			Self::Select(_) => "@select",
 			Self::Move(_) => "move",
+			Self::Copy(_) => "copy",
 			Self::Trash(_) => "trash",
 			Self::Delete(_) => "delete",
+			Self::Task(_) => "task",
 			Self::Custom(b) => b.kind.as_str(),
 		}
 	}
@@ -119,9 +139,14 @@ impl IntoLua<'_> for Body<'static> {
 			Self::Rename(b) => b.into_lua(lua),
 			Self::Bulk(b) => b.into_lua(lua),
 			Self::Yank(b) => b.into_lua(lua),
+			Self::Filter(b) => b.into_lua(lua),
+			Self::Find(b) => b.into_lua(lua),
+			Self::Select(b) => b.into_lua(lua),
 			Self::Move(b) => b.into_lua(lua),
+			Self::Copy(b) => b.into_lua(lua),
 			Self::Trash(b) => b.into_lua(lua),
 			Self::Delete(b) => b.into_lua(lua),
+			Self::Task(b) => b.into_lua(lua),
 			Self::Custom(b) => b.into_lua(lua),
 		}
 	}
@@ -0,0 +1,35 @@
+use std::borrow::Cow;
+
+use mlua::{IntoLua, Lua, Value};
+use serde::{Deserialize, Serialize};
+
+use super::{Body, BodyMoveItem};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BodyCopy<'a> {
+	pub items: Cow<'a, Vec<BodyMoveItem>>,
+}
+
+impl<'a> BodyCopy<'a> {
+	#[inline]
+	pub fn borrowed(items: &'a Vec<BodyMoveItem>) -> Body<'a> {
+		Self { items: Cow::Borrowed(items) }.into()
+	}
+}
+
+impl BodyCopy<'static> {
+	#[inline]
+	pub fn owned(items: Vec<BodyMoveItem>) -> Body<'static> {
+		Self { items: Cow::Owned(items) }.into()
+	}
+}
+
+impl<'a> From<BodyCopy<'a>> for Body<'a> {
+	fn from(value: BodyCopy<'a>) -> Self { Self::Copy(value) }
+}
+
+impl IntoLua<'_> for BodyCopy<'static> {
+	fn into_lua(self, lua: &Lua) -> mlua::Result<Value<'_>> {
+		lua.create_table_from([("items", self.items.into_owned())])?.into_lua(lua)
+	}
+}
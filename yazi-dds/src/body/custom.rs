@@ -1,6 +1,8 @@
+use std::collections::HashMap;
+
 use mlua::{IntoLua, Lua, Value};
 use serde::Serialize;
-use yazi_shared::event::Data;
+use yazi_shared::event::{Data, DataKey};
 
 use super::Body;
 use crate::Sendable;
@@ -21,6 +23,17 @@ impl BodyCustom {
 	pub fn from_lua(kind: &str, data: Value) -> mlua::Result<Body<'static>> {
 		Ok(Self { kind: kind.to_owned(), data: Sendable::value_to_data(data)? }.into())
 	}
+
+	/// Reports the result of a remote command back to its sender.
+	pub fn ack(kind: &str, from: u64, error: Option<String>) -> Body<'static> {
+		let data = Data::Dict(HashMap::from_iter([
+			(DataKey::String("kind".to_owned()), Data::String(kind.to_owned())),
+			(DataKey::String("from".to_owned()), Data::String(from.to_string())),
+			(DataKey::String("ok".to_owned()), Data::Boolean(error.is_none())),
+			(DataKey::String("error".to_owned()), error.map_or(Data::Nil, Data::String)),
+		]));
+		Self { kind: "dds-ack".to_owned(), data }.into()
+	}
 }
 
 impl From<BodyCustom> for Body<'_> {
@@ -0,0 +1,34 @@
+use mlua::{IntoLua, Lua, Value};
+use serde::{Deserialize, Serialize};
+
+use super::Body;
+
+/// The filter of the CWD of a tab, published as `@filter` once it changes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BodyFilter {
+	pub tab:     usize,
+	/// The pattern, or the tag if `tag` is set, `None` if there's no filter.
+	pub pattern: Option<String>,
+	pub case:    String,
+	pub tag:     bool,
+	/// How many files it matches.
+	pub matched: usize,
+}
+
+impl<'a> From<BodyFilter> for Body<'a> {
+	fn from(value: BodyFilter) -> Self { Self::Filter(value) }
+}
+
+impl IntoLua<'_> for BodyFilter {
+	fn into_lua(self, lua: &Lua) -> mlua::Result<Value<'_>> {
+		lua
+			.create_table_from([
+				("tab", self.tab.into_lua(lua)?),
+				("pattern", self.pattern.into_lua(lua)?),
+				("case", self.case.into_lua(lua)?),
+				("tag", self.tag.into_lua(lua)?),
+				("matched", self.matched.into_lua(lua)?),
+			])?
+			.into_lua(lua)
+	}
+}
@@ -0,0 +1,34 @@
+use mlua::{IntoLua, Lua, Value};
+use serde::{Deserialize, Serialize};
+
+use super::Body;
+
+/// The find of a tab, published as `@find` once it changes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BodyFind {
+	pub tab:     usize,
+	/// The query, `None` if there's no find.
+	pub query:   Option<String>,
+	pub case:    String,
+	/// The position of the hovered file among the matches, starting from 1.
+	pub current: Option<usize>,
+	pub total:   usize,
+}
+
+impl<'a> From<BodyFind> for Body<'a> {
+	fn from(value: BodyFind) -> Self { Self::Find(value) }
+}
+
+impl IntoLua<'_> for BodyFind {
+	fn into_lua(self, lua: &Lua) -> mlua::Result<Value<'_>> {
+		lua
+			.create_table_from([
+				("tab", self.tab.into_lua(lua)?),
+				("query", self.query.into_lua(lua)?),
+				("case", self.case.into_lua(lua)?),
+				("current", self.current.into_lua(lua)?),
+				("total", self.total.into_lua(lua)?),
+			])?
+			.into_lua(lua)
+	}
+}
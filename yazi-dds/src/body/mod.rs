@@ -1,5 +1,6 @@
 #![allow(clippy::module_inception)]
 
 yazi_macro::mod_flat!(
-	body bulk bye cd custom delete hey hi hover move_ rename tab trash yank
+	body bulk bye cd copy custom delete filter find hey hi hover move_ rename select tab task trash
+	yank
 );
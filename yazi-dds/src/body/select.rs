@@ -0,0 +1,73 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use mlua::{IntoLua, Lua, Value};
+use serde::{Deserialize, Serialize};
+use yazi_shared::fs::Url;
+
+use super::Body;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BodySelect<'a> {
+	pub tab:       usize,
+	pub urls:      Vec<Cow<'a, Url>>,
+	pub count:     usize,
+	#[serde(default)]
+	pub truncated: bool,
+	#[serde(skip)]
+	dummy:         bool,
+}
+
+impl<'a> BodySelect<'a> {
+	/// The maximum number of URLs carried by a single message; beyond it,
+	/// only the `count` is reported, `urls` is left empty and `truncated` is
+	/// set, the full selection can then be asked for with a `dds-query` of
+	/// `selection`, e.g. `ya query selection`.
+	pub const LIMIT: usize = 1000;
+
+	#[inline]
+	pub fn borrowed(tab: usize, selected: &'a HashMap<Url, u64>) -> Body<'a> {
+		let truncated = selected.len() > Self::LIMIT;
+		let urls = if truncated {
+			vec![]
+		} else {
+			let mut vec: Vec<_> = selected.iter().collect();
+			vec.sort_unstable_by(|a, b| a.1.cmp(b.1));
+			vec.into_iter().map(|(u, _)| Cow::Borrowed(u)).collect()
+		};
+
+		Self { tab, urls, count: selected.len(), truncated, dummy: false }.into()
+	}
+}
+
+impl BodySelect<'static> {
+	#[inline]
+	pub fn dummy(tab: usize) -> Body<'static> {
+		Self { tab, urls: vec![], count: 0, truncated: false, dummy: true }.into()
+	}
+}
+
+impl<'a> From<BodySelect<'a>> for Body<'a> {
+	fn from(value: BodySelect<'a>) -> Self { Self::Select(value) }
+}
+
+impl IntoLua<'_> for BodySelect<'static> {
+	fn into_lua(self, lua: &Lua) -> mlua::Result<Value<'_>> {
+		if self.dummy {
+			return lua.create_table_from([("tab", self.tab)])?.into_lua(lua);
+		}
+
+		let urls = lua.create_table_with_capacity(self.urls.len(), 0)?;
+		for (i, url) in self.urls.into_iter().enumerate() {
+			urls.raw_set(i + 1, lua.create_any_userdata(url.into_owned())?)?;
+		}
+
+		lua
+			.create_table_from([
+				("tab", self.tab.into_lua(lua)?),
+				("urls", urls.into_lua(lua)?),
+				("count", self.count.into_lua(lua)?),
+				("truncated", self.truncated.into_lua(lua)?),
+			])?
+			.into_lua(lua)
+	}
+}
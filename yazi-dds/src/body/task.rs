@@ -0,0 +1,45 @@
+use mlua::{IntoLua, Lua, Value};
+use serde::{Deserialize, Serialize};
+
+use super::Body;
+
+/// Lifecycle of a user-visible task: `event` is one of `start`, `progress`
+/// or `finish`, and `status` is only set on `finish`, to one of `succeeded`,
+/// `failed` or `canceled`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BodyTask {
+	pub id:     usize,
+	pub event:  String,
+	pub name:   String,
+	pub status: Option<String>,
+
+	pub total: u32,
+	pub succ:  u32,
+	pub fail:  u32,
+
+	pub found:     u64,
+	pub processed: u64,
+}
+
+impl From<BodyTask> for Body<'_> {
+	fn from(value: BodyTask) -> Self { Self::Task(value) }
+}
+
+impl IntoLua<'_> for BodyTask {
+	fn into_lua(self, lua: &Lua) -> mlua::Result<Value<'_>> {
+		let t = lua.create_table_from([
+			("id", self.id.into_lua(lua)?),
+			("event", self.event.into_lua(lua)?),
+			("name", self.name.into_lua(lua)?),
+			("total", self.total.into_lua(lua)?),
+			("succ", self.succ.into_lua(lua)?),
+			("fail", self.fail.into_lua(lua)?),
+			("found", self.found.into_lua(lua)?),
+			("processed", self.processed.into_lua(lua)?),
+		])?;
+		if let Some(status) = self.status {
+			t.raw_set("status", status)?;
+		}
+		t.into_lua(lua)
+	}
+}
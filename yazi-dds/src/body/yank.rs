@@ -10,21 +10,23 @@ use super::Body;
 pub struct BodyYank<'a> {
 	pub cut:  bool,
 	pub urls: Cow<'a, HashSet<Url>>,
+	#[serde(default)]
+	pub ts:   u64,
 	#[serde(skip)]
 	dummy:    bool,
 }
 
 impl<'a> BodyYank<'a> {
 	#[inline]
-	pub fn borrowed(cut: bool, urls: &'a HashSet<Url>) -> Body<'a> {
-		Self { cut, urls: Cow::Borrowed(urls), dummy: false }.into()
+	pub fn borrowed(cut: bool, urls: &'a HashSet<Url>, ts: u64) -> Body<'a> {
+		Self { cut, urls: Cow::Borrowed(urls), ts, dummy: false }.into()
 	}
 }
 
 impl BodyYank<'static> {
 	#[inline]
 	pub fn dummy() -> Body<'static> {
-		Self { cut: false, urls: Default::default(), dummy: true }.into()
+		Self { cut: false, urls: Default::default(), ts: 0, dummy: true }.into()
 	}
 }
 
@@ -35,7 +37,7 @@ impl<'a> From<BodyYank<'a>> for Body<'a> {
 impl IntoLua<'_> for BodyYank<'static> {
 	fn into_lua(self, lua: &Lua) -> mlua::Result<Value<'_>> {
 		if let Some(Cow::Owned(urls)) = Some(self.urls).filter(|_| !self.dummy) {
-			BodyYankIter { cut: self.cut, urls: urls.into_iter().collect() }.into_lua(lua)
+			BodyYankIter { cut: self.cut, urls: urls.into_iter().collect(), ts: self.ts }.into_lua(lua)
 		} else {
 			lua.create_table()?.into_lua(lua)
 		}
@@ -46,11 +48,13 @@ impl IntoLua<'_> for BodyYank<'static> {
 pub struct BodyYankIter {
 	pub cut:  bool,
 	pub urls: Vec<Url>,
+	pub ts:   u64,
 }
 
 impl UserData for BodyYankIter {
 	fn add_fields<'lua, F: mlua::UserDataFields<'lua, Self>>(fields: &mut F) {
 		fields.add_field_method_get("cut", |_, me| Ok(me.cut));
+		fields.add_field_method_get("ts", |_, me| Ok(me.ts));
 	}
 
 	fn add_methods<'lua, M: mlua::UserDataMethods<'lua, Self>>(methods: &mut M) {
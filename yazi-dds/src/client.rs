@@ -7,7 +7,7 @@ use tokio::{io::AsyncWriteExt, select, sync::mpsc, task::JoinHandle, time};
 use tracing::error;
 use yazi_shared::RoCell;
 
-use crate::{ClientReader, ClientWriter, Payload, Pubsub, Server, Stream, body::{Body, BodyBye, BodyHi}};
+use crate::{ClientReader, ClientWriter, Payload, Pubsub, Remote, Server, Stream, body::{Body, BodyBye, BodyHi}};
 
 pub(super) static ID: RoCell<u64> = RoCell::new();
 pub(super) static PEERS: RoCell<RwLock<HashMap<u64, Peer>>> = RoCell::new();
@@ -102,11 +102,73 @@ impl Client {
 		Ok(())
 	}
 
+	/// Connect to an existing server to query the state of the `receiver`
+	/// instance, or the only running one if it's `None`, returning the raw
+	/// JSON body of the answer.
+	pub async fn query(receiver: Option<u64>, body: &str) -> Result<String> {
+		let (mut lines, mut writer) =
+			Stream::connect().await.context("No running Yazi instance found")?;
+		let hi = Payload::new(BodyHi::borrowed(HashSet::from_iter([Remote::ANSWER])));
+		writer.write_all(format!("{hi}\n").as_bytes()).await?;
+		writer.flush().await?;
+
+		let timeout = time::sleep(time::Duration::from_secs(2));
+		tokio::pin!(timeout);
+
+		let mut sent = false;
+		loop {
+			let line = select! {
+				_ = &mut timeout => bail!("Timed out waiting for the answer"),
+				line = lines.next_line() => match line? {
+					Some(line) => line,
+					None => bail!("Connection closed before the answer arrived"),
+				},
+			};
+
+			match line.split(',').next() {
+				Some("hey") if !sent => {
+					let Ok(Body::Hey(hey)) = Payload::from_str(&line).map(|p| p.body) else { continue };
+					if hey.version != BodyHi::version() {
+						bail!("Incompatible version (Ya {}, Yazi {})", BodyHi::version(), hey.version);
+					}
+
+					let mut ids: Vec<_> =
+						hey.peers.iter().filter(|(_, p)| p.able(Remote::QUERY)).map(|(&id, _)| id).collect();
+					ids.sort_unstable();
+
+					let receiver = match receiver {
+						Some(id) if ids.contains(&id) => id,
+						Some(id) => bail!("No running Yazi instance with ID {id}"),
+						None if ids.len() == 1 => ids[0],
+						None if ids.is_empty() => bail!("No running Yazi instance found"),
+						None => bail!(
+							"Multiple Yazi instances are running, specify one of:\n{}",
+							ids.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+						),
+					};
+
+					writer
+						.write_all(format!("{},{receiver},{ID},{body}\n", Remote::QUERY).as_bytes())
+						.await?;
+					writer.flush().await?;
+					sent = true;
+				}
+				Some(kind) if sent && kind == Remote::ANSWER => {
+					let answer = line.splitn(4, ',').nth(3).unwrap_or_default().to_owned();
+					writer.write_all(format!("{}\n", Payload::new(BodyBye::owned())).as_bytes()).await?;
+					writer.flush().await.ok();
+					return Ok(answer);
+				}
+				_ => {}
+			}
+		}
+	}
+
 	/// Connect to an existing server and listen in on the messages that are being
-	/// sent by other yazi instances:
+	/// sent by other yazi instances, passing each of them to `f`:
 	///   - If no server is running, fail right away;
 	///   - If a server is closed, attempt to reconnect forever.
-	pub async fn draw(kinds: HashSet<&str>) -> Result<()> {
+	pub async fn draw(kinds: HashSet<&str>, mut f: impl FnMut(&str)) -> Result<()> {
 		async fn make(kinds: &HashSet<&str>) -> Result<ClientReader> {
 			let (lines, mut writer) = Stream::connect().await?;
 			let hi = Payload::new(BodyHi::borrowed(kinds.clone()));
@@ -121,7 +183,7 @@ impl Client {
 				Some(s) => {
 					let kind = s.split(',').next();
 					if matches!(kind, Some(kind) if kinds.contains(kind)) {
-						println!("{s}");
+						f(&s);
 					}
 				}
 				None => loop {
@@ -2,13 +2,16 @@
 
 yazi_macro::mod_pub!(body);
 
-yazi_macro::mod_flat!(client payload pubsub pump sendable server state stream);
+yazi_macro::mod_flat!(client payload pubsub pump remote sendable server state stream);
 
-pub fn init() {
+pub fn init() { init_with(Default::default()) }
+
+pub fn init_with(transport: Transport) {
 	let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
 
 	// Client
 	ID.init(yazi_boot::ARGS.client_id.unwrap_or(yazi_shared::timestamp_us()));
+	TRANSPORT.init(transport.or_env());
 	PEERS.with(<_>::default);
 	QUEUE_TX.init(tx);
 	QUEUE_RX.init(rx);
@@ -20,6 +23,7 @@ pub fn init() {
 	// Pubsub
 	LOCAL.with(<_>::default);
 	REMOTE.with(<_>::default);
+	PRE.with(<_>::default);
 
 	// Env
 	if let Some(s) = std::env::var("YAZI_ID").ok().filter(|s| !s.is_empty()) {
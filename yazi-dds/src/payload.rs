@@ -87,9 +87,14 @@ impl Display for Payload<'_> {
 			Body::Rename(b) => serde_json::to_string(b),
 			Body::Bulk(b) => serde_json::to_string(b),
 			Body::Yank(b) => serde_json::to_string(b),
+			Body::Filter(b) => serde_json::to_string(b),
+			Body::Find(b) => serde_json::to_string(b),
+			Body::Select(b) => serde_json::to_string(b),
 			Body::Move(b) => serde_json::to_string(b),
+			Body::Copy(b) => serde_json::to_string(b),
 			Body::Trash(b) => serde_json::to_string(b),
 			Body::Delete(b) => serde_json::to_string(b),
+			Body::Task(b) => serde_json::to_string(b),
 			Body::Custom(b) => serde_json::to_string(b),
 		};
 
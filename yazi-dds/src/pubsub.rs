@@ -2,10 +2,12 @@ use std::collections::{HashMap, HashSet};
 
 use mlua::Function;
 use parking_lot::RwLock;
+use tokio::sync::oneshot;
 use yazi_boot::BOOT;
-use yazi_shared::{RoCell, fs::Url};
+use yazi_macro::emit;
+use yazi_shared::{Layer, RoCell, event::{Cmd, Data}, fs::Url};
 
-use crate::{Client, ID, PEERS, body::{Body, BodyBulk, BodyCd, BodyDelete, BodyHi, BodyHover, BodyMove, BodyMoveItem, BodyRename, BodyTab, BodyTrash, BodyYank}};
+use crate::{Client, ID, PEERS, Remote, body::{Body, BodyBulk, BodyCd, BodyCopy, BodyCustom, BodyDelete, BodyFilter, BodyFind, BodyHi, BodyHover, BodyMove, BodyMoveItem, BodyRename, BodySelect, BodyTab, BodyTask, BodyTrash, BodyYank}};
 
 pub static LOCAL: RoCell<RwLock<HashMap<String, HashMap<String, Function<'static>>>>> =
 	RoCell::new();
@@ -13,6 +15,8 @@ pub static LOCAL: RoCell<RwLock<HashMap<String, HashMap<String, Function<'static
 pub static REMOTE: RoCell<RwLock<HashMap<String, HashMap<String, Function<'static>>>>> =
 	RoCell::new();
 
+pub static PRE: RoCell<RwLock<HashMap<String, HashMap<String, Function<'static>>>>> = RoCell::new();
+
 macro_rules! sub {
 	($var:ident) => {
 		|plugin: &str, kind: &str, f: Function<'static>| {
@@ -59,8 +63,29 @@ impl Pubsub {
 		sub!(REMOTE)(plugin, kind, f) && Self::pub_from_hi()
 	}
 
+	pub fn sub_pre(plugin: &str, kind: &str, f: Function<'static>) -> bool {
+		sub!(PRE)(plugin, kind, f)
+	}
+
 	pub fn unsub(plugin: &str, kind: &str) -> bool { unsub!(LOCAL)(plugin, kind) }
 
+	pub fn unsub_pre(plugin: &str, kind: &str) -> bool { unsub!(PRE)(plugin, kind) }
+
+	#[inline]
+	pub fn any_pre(kind: &str) -> bool { PRE.read().contains_key(kind) }
+
+	/// Run the pre-hooks of an operation on the main thread, resolving to
+	/// `false` if any of them vetoes it by returning `false`.
+	pub async fn pre(body: Body<'static>) -> bool {
+		if !Self::any_pre(body.kind()) {
+			return true;
+		}
+
+		let (tx, rx) = oneshot::channel();
+		emit!(Call(Cmd::new("accept_pre").with_any("body", body).with_any("tx", tx), Layer::App));
+		rx.await.unwrap_or(true)
+	}
+
 	pub fn unsub_remote(plugin: &str, kind: &str) -> bool {
 		unsub!(REMOTE)(plugin, kind) && Self::pub_from_hi()
 	}
@@ -82,7 +107,13 @@ impl Pubsub {
 
 	pub fn pub_from_hi() -> bool {
 		let abilities = REMOTE.read().keys().cloned().collect();
-		let abilities = BOOT.remote_events.union(&abilities).map(|s| s.as_str()).collect();
+		let abilities = BOOT
+			.remote_events
+			.union(&abilities)
+			.map(|s| s.as_str())
+			.chain(Remote::KINDS.iter().copied())
+			.chain([Remote::QUERY])
+			.collect();
 
 		Client::push(BodyHi::borrowed(abilities));
 		true
@@ -148,15 +179,75 @@ impl Pubsub {
 		}
 	}
 
-	pub fn pub_from_yank(cut: bool, urls: &HashSet<Url>) {
+	pub fn pub_from_yank(cut: bool, urls: &HashSet<Url>, ts: u64) {
 		if LOCAL.read().contains_key("@yank") {
 			Self::pub_(BodyYank::dummy());
 		}
 		if Self::any_remote_own("@yank") {
-			Client::push(BodyYank::borrowed(cut, urls));
+			Client::push(BodyYank::borrowed(cut, urls, ts));
 		}
 		if BOOT.local_events.contains("@yank") {
-			BodyYank::borrowed(cut, urls).with_receiver(*ID).flush();
+			BodyYank::borrowed(cut, urls, ts).with_receiver(*ID).flush();
+		}
+	}
+
+	pub fn pub_from_filter(body: &BodyFilter) {
+		if LOCAL.read().contains_key("@filter") {
+			Self::pub_(body.clone().into());
+		}
+		if Self::any_remote_own("@filter") {
+			Client::push(Body::from(body.clone()));
+		}
+		if BOOT.local_events.contains("@filter") {
+			Body::from(body.clone()).with_receiver(*ID).flush();
+		}
+	}
+
+	pub fn pub_from_find(body: &BodyFind) {
+		if LOCAL.read().contains_key("@find") {
+			Self::pub_(body.clone().into());
+		}
+		if Self::any_remote_own("@find") {
+			Client::push(Body::from(body.clone()));
+		}
+		if BOOT.local_events.contains("@find") {
+			Body::from(body.clone()).with_receiver(*ID).flush();
+		}
+	}
+
+	pub fn pub_from_select(tab: usize, selected: &HashMap<Url, u64>) {
+		if LOCAL.read().contains_key("@select") {
+			Self::pub_(BodySelect::dummy(tab));
+		}
+		if Self::any_remote_own("@select") {
+			Client::push(BodySelect::borrowed(tab, selected));
+		}
+		if BOOT.local_events.contains("@select") {
+			BodySelect::borrowed(tab, selected).with_receiver(*ID).flush();
+		}
+	}
+
+	pub fn pub_from_task(body: BodyTask) {
+		if LOCAL.read().contains_key("task") {
+			Self::pub_(body.clone().into());
+		}
+		if PEERS.read().values().any(|p| p.able("task")) {
+			Client::push(Body::from(body.clone()));
+		}
+		if BOOT.local_events.contains("task") {
+			Body::from(body).with_receiver(*ID).flush();
+		}
+	}
+
+	pub fn pub_from_answer(receiver: u64, data: Data) {
+		Client::push(
+			Body::from(BodyCustom { kind: Remote::ANSWER.to_owned(), data }).with_receiver(receiver),
+		);
+	}
+
+	pub fn pub_from_ack(kind: &str, from: u64, error: Option<String>) {
+		if Self::any_remote_own("dds-ack") {
+			Client::push(BodyCustom::ack(kind, from, error));
 		}
 	}
 
@@ -172,6 +263,18 @@ impl Pubsub {
 		}
 	}
 
+	pub(super) fn pub_from_copy(items: Vec<BodyMoveItem>) {
+		if PEERS.read().values().any(|p| p.able("copy")) {
+			Client::push(BodyCopy::borrowed(&items));
+		}
+		if BOOT.local_events.contains("copy") {
+			BodyCopy::borrowed(&items).with_receiver(*ID).flush();
+		}
+		if LOCAL.read().contains_key("copy") {
+			Self::pub_(BodyCopy::owned(items));
+		}
+	}
+
 	pub(super) fn pub_from_trash(urls: Vec<Url>) {
 		if PEERS.read().values().any(|p| p.able("trash")) {
 			Client::push(BodyTrash::borrowed(&urls));
@@ -10,6 +10,7 @@ use crate::{Pubsub, body::BodyMoveItem};
 
 static CT: RoCell<CancellationToken> = RoCell::new();
 static MOVE_TX: Mutex<Option<mpsc::UnboundedSender<BodyMoveItem>>> = Mutex::new(None);
+static COPY_TX: Mutex<Option<mpsc::UnboundedSender<BodyMoveItem>>> = Mutex::new(None);
 static TRASH_TX: Mutex<Option<mpsc::UnboundedSender<Url>>> = Mutex::new(None);
 static DELETE_TX: Mutex<Option<mpsc::UnboundedSender<Url>>> = Mutex::new(None);
 
@@ -23,6 +24,13 @@ impl Pump {
 		}
 	}
 
+	#[inline]
+	pub fn push_copy(from: Url, to: Url) {
+		if let Some(tx) = &*COPY_TX.lock() {
+			tx.send(BodyMoveItem { from, to }).ok();
+		}
+	}
+
 	#[inline]
 	pub fn push_trash(target: Url) {
 		if let Some(tx) = &*TRASH_TX.lock() {
@@ -39,29 +47,35 @@ impl Pump {
 
 	pub(super) fn serve() {
 		let (move_tx, move_rx) = mpsc::unbounded_channel();
+		let (copy_tx, copy_rx) = mpsc::unbounded_channel();
 		let (trash_tx, trash_rx) = mpsc::unbounded_channel();
 		let (delete_tx, delete_rx) = mpsc::unbounded_channel();
 
 		CT.with(<_>::default);
 		MOVE_TX.lock().replace(move_tx);
+		COPY_TX.lock().replace(copy_tx);
 		TRASH_TX.lock().replace(trash_tx);
 		DELETE_TX.lock().replace(delete_tx);
 
 		tokio::spawn(async move {
 			let move_rx =
 				UnboundedReceiverStream::new(move_rx).chunks_timeout(1000, Duration::from_millis(500));
+			let copy_rx =
+				UnboundedReceiverStream::new(copy_rx).chunks_timeout(1000, Duration::from_millis(500));
 			let trash_rx =
 				UnboundedReceiverStream::new(trash_rx).chunks_timeout(1000, Duration::from_millis(500));
 			let delete_rx =
 				UnboundedReceiverStream::new(delete_rx).chunks_timeout(1000, Duration::from_millis(500));
 
 			pin!(move_rx);
+			pin!(copy_rx);
 			pin!(trash_rx);
 			pin!(delete_rx);
 
 			loop {
 				select! {
 					Some(items) = move_rx.next() => Pubsub::pub_from_move(items),
+					Some(items) = copy_rx.next() => Pubsub::pub_from_copy(items),
 					Some(urls) = trash_rx.next() => Pubsub::pub_from_trash(urls),
 					Some(urls) = delete_rx.next() => Pubsub::pub_from_delete(urls),
 					else => {
@@ -75,6 +89,7 @@ impl Pump {
 
 	pub(super) async fn shutdown() {
 		drop(MOVE_TX.lock().take());
+		drop(COPY_TX.lock().take());
 		drop(TRASH_TX.lock().take());
 		drop(DELETE_TX.lock().take());
 		CT.cancelled().await;
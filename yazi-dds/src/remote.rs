@@ -0,0 +1,142 @@
+use std::collections::VecDeque;
+
+use anyhow::{Result, bail};
+use tokio::fs;
+use yazi_config::CONFIRM;
+use yazi_macro::emit;
+use yazi_shared::{Layer, event::{Cmd, Data, DataKey}, fs::Url};
+
+use crate::{Payload, Pubsub, body::Body};
+
+/// Navigation commands that other processes can send to a running instance,
+/// e.g. `ya pub-to <id> dds-reveal --str /path/to/file`, while `dds-select`
+/// is left to the preset `dds` plugin. They're only honored from peers on the
+/// local socket, owned by the same user, those sent over TCP are dropped by the
+/// server.
+///
+/// The body is either a string, a list of strings, or a dict with
+/// `url`/`urls` and an optional `tab` field. Once handled, a `dds-ack`
+/// message reporting the result is broadcast on the bus.
+///
+/// `dds-read-only` turns the read-only mode `on`, `off`, or toggles it, e.g.
+/// `ya pub-to <id> dds-read-only --str on`.
+///
+/// `dds-emit` runs any command of the manager, given as a string, e.g.
+/// `ya pub-to <id> dds-emit --str "remove --permanently --yes"`, with
+/// `--yes` added to it if `confirm.bypass_on_dds` is set.
+pub struct Remote {
+	kind: String,
+	urls: Vec<Url>,
+	tab:  Option<usize>,
+}
+
+impl Remote {
+	pub const ANSWER: &'static str = "dds-answer";
+	pub const KINDS: [&'static str; 5] =
+		["dds-cd", "dds-reveal", "dds-open", "dds-read-only", "dds-emit"];
+	/// Asks for a snapshot of the state, such as `cwd`, `hovered`, `selection` or
+	/// `tabs`, which is answered with a `dds-answer` message to the sender.
+	pub const QUERY: &'static str = "dds-query";
+
+	/// Whether `kind` is only honored from peers on the local socket, and not
+	/// from those connected over TCP.
+	#[inline]
+	pub(super) fn local_only(kind: &str) -> bool {
+		kind == "dds-select" || Self::KINDS.contains(&kind)
+	}
+
+	pub fn accept(payload: Payload<'static>) {
+		let Body::Custom(body) = payload.body else { return };
+		if body.kind == "dds-emit" {
+			let error = Self::emit(body.data).err().map(|e| e.to_string());
+			return Pubsub::pub_from_ack(&body.kind, payload.sender, error);
+		}
+
+		let mut me = Self { kind: body.kind, urls: vec![], tab: None };
+		match body.data {
+			Data::String(s) => me.urls.push(Url::from(s)),
+			Data::List(l) => me.urls.extend(l.into_iter().filter_map(Data::into_url)),
+			Data::Dict(mut d) => {
+				me.tab = d.remove(&DataKey::String("tab".to_owned())).and_then(|d| d.as_usize());
+				if let Some(u) = d.remove(&DataKey::String("url".to_owned())).and_then(Data::into_url) {
+					me.urls.push(u);
+				}
+				if let Some(Data::List(l)) = d.remove(&DataKey::String("urls".to_owned())) {
+					me.urls.extend(l.into_iter().filter_map(Data::into_url));
+				}
+			}
+			_ => {}
+		}
+
+		let sender = payload.sender;
+		tokio::spawn(async move {
+			let error = me.dispatch().await.err().map(|e| e.to_string());
+			Pubsub::pub_from_ack(&me.kind, sender, error);
+		});
+	}
+
+	fn emit(data: Data) -> Result<()> {
+		let Data::String(s) = data else {
+			bail!("Expected a command as a string");
+		};
+
+		let mut cmd: Cmd = s.parse()?;
+		if CONFIRM.bypass_on_dds {
+			cmd = cmd.with_bool("yes", true);
+		}
+		emit!(Call(cmd, Layer::Manager));
+		Ok(())
+	}
+
+	async fn dispatch(&self) -> Result<()> {
+		// Takes `on`, `off`, or `toggle`
+		if self.kind == "dds-read-only" {
+			let state = self.urls.first().map(|u| u.to_string()).unwrap_or_else(|| "toggle".to_owned());
+			if !matches!(state.as_str(), "on" | "off" | "toggle") {
+				bail!("Invalid state: {state}, expected 'on', 'off' or 'toggle'");
+			}
+			emit!(Call(Cmd::args("read_only", &[state]), Layer::Manager));
+			return Ok(());
+		}
+
+		let Some(first) = self.urls.first() else {
+			bail!("No path specified");
+		};
+
+		let cmds = match self.kind.as_str() {
+			"dds-cd" => {
+				if !fs::metadata(first).await?.is_dir() {
+					bail!("Not a directory: {first}");
+				}
+				vec![Cmd::args("cd", &[first])]
+			}
+			"dds-reveal" => {
+				fs::symlink_metadata(first).await?;
+				vec![Cmd::args("reveal", &[first])]
+			}
+			"dds-open" => {
+				if fs::metadata(first).await?.is_dir() {
+					vec![Cmd::args("cd", &[first])]
+				} else if let Some(tab) = self.tab {
+					// Opening goes through the active tab, so switch to it first
+					vec![
+						Cmd::args("tab_switch", &[tab]),
+						Cmd::args("reveal", &[first]),
+						Cmd::new("open").with_bool("hovered", true),
+					]
+				} else {
+					vec![Cmd::args("reveal", &[first]), Cmd::new("open").with_bool("hovered", true)]
+				}
+			}
+			_ => bail!("Unknown remote command: {}", self.kind),
+		};
+
+		let cmds: VecDeque<_> = match self.tab {
+			Some(tab) => cmds.into_iter().map(|c| c.with("tab", tab)).collect(),
+			None => cmds.into(),
+		};
+
+		emit!(Seq(cmds, Layer::Manager));
+		Ok(())
+	}
+}
@@ -2,10 +2,11 @@ use std::{collections::HashMap, str::FromStr, time::Duration};
 
 use anyhow::Result;
 use parking_lot::RwLock;
-use tokio::{io::{AsyncBufReadExt, AsyncWriteExt, BufReader}, select, sync::mpsc::{self, UnboundedReceiver}, task::JoinHandle, time};
+use tokio::{io::{AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader}, net::{TcpListener, TcpStream}, select, sync::mpsc::{self, UnboundedReceiver}, task::JoinHandle, time};
+use tracing::error;
 use yazi_shared::RoCell;
 
-use crate::{Client, ClientWriter, Payload, Peer, STATE, Stream, body::{Body, BodyBye, BodyHey}};
+use crate::{Client, Io, Payload, Peer, Remote, STATE, Stream, body::{Body, BodyBye, BodyHey}};
 
 pub(super) static CLIENTS: RoCell<RwLock<HashMap<u64, Client>>> = RoCell::new();
 
@@ -15,76 +16,110 @@ impl Server {
 	pub(super) async fn make() -> Result<JoinHandle<()>> {
 		CLIENTS.write().clear();
 		let listener = Stream::bind().await?;
+		let remote = Stream::bind_remote().await.unwrap_or_else(|e| {
+			error!("Failed to start the DDS remote listener: {e}");
+			None
+		});
 
 		Ok(tokio::spawn(async move {
-			while let Ok((stream, _)) = listener.accept().await {
-				let (tx, mut rx) = mpsc::unbounded_channel::<String>();
-				let (reader, mut writer) = tokio::io::split(stream);
-
-				tokio::spawn(async move {
-					let mut id = None;
-					let mut lines = BufReader::new(reader).lines();
-					loop {
-						select! {
-							Some(payload) = rx.recv() => {
-								if writer.write_all(payload.as_bytes()).await.is_err() {
-									break;
-								}
-							}
-							_ = time::sleep(Duration::from_secs(5)) => {
-								if writer.write_u8(b'\n').await.is_err() {
-									break;
-								}
-							}
-							Ok(Some(mut line)) = lines.next_line() => {
-								if line.starts_with("hi,") {
-									Self::handle_hi(line, &mut id, tx.clone());
-									continue;
-								}
-
-								let Some(id) = id else { continue };
-								if line.starts_with("bye,") {
-									Self::handle_bye(id, rx, writer).await;
-									break;
-								}
-
-								let mut parts = line.splitn(4, ',');
-								let Some(kind) = parts.next() else { continue };
-								let Some(receiver) = parts.next().and_then(|s| s.parse().ok()) else { continue };
-								let Some(sender) = parts.next().and_then(|s| s.parse::<u64>().ok()) else { continue };
-
-								let clients = CLIENTS.read();
-								let clients: Vec<_> = if receiver == 0 {
-									clients.values().filter(|c| c.able(kind)).collect()
-								} else if let Some(c) = clients.get(&receiver).filter(|c| c.able(kind)) {
-									vec![c]
-								} else {
-									vec![]
-								};
-
-								if clients.is_empty() {
-									continue;
-								}
-
-								if receiver == 0 && kind.starts_with('@') {
-									let Some(body) = parts.next() else { continue };
-									if !STATE.set(kind, sender, body) { continue }
-								}
-
-								line.push('\n');
-								clients.into_iter().for_each(|c| _ = c.tx.send(line.clone()));
+			loop {
+				select! {
+					Ok((stream, _)) = listener.accept() => {
+						if Stream::trusted(&stream) {
+							Self::handle(stream, false);
+						}
+					}
+					Ok((stream, _)) = Self::accept_remote(remote.as_ref()) => {
+						tokio::spawn(async move {
+							if let Some(stream) = Stream::authenticate(stream).await {
+								Self::handle(stream, true);
 							}
-							else => break
+						});
+					}
+					else => break
+				}
+			}
+		}))
+	}
+
+	fn handle(stream: impl Io + 'static, remote: bool) {
+		let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+		let (reader, mut writer) = tokio::io::split(stream);
+
+		tokio::spawn(async move {
+			let mut id = None;
+			let mut lines = BufReader::new(reader).lines();
+			loop {
+				select! {
+					Some(payload) = rx.recv() => {
+						if writer.write_all(payload.as_bytes()).await.is_err() {
+							break;
 						}
 					}
+					_ = time::sleep(Duration::from_secs(5)) => {
+						if writer.write_u8(b'\n').await.is_err() {
+							break;
+						}
+					}
+					Ok(Some(mut line)) = lines.next_line() => {
+						if line.starts_with("hi,") {
+							Self::handle_hi(line, &mut id, tx.clone());
+							continue;
+						}
 
-					let mut clients = CLIENTS.write();
-					if id.and_then(|id| clients.remove(&id)).is_some() {
-						Self::handle_hey(&clients);
+						let Some(id) = id else { continue };
+						if line.starts_with("bye,") {
+							Self::handle_bye(id, rx, writer).await;
+							break;
+						}
+
+						let mut parts = line.splitn(4, ',');
+						let Some(kind) = parts.next() else { continue };
+						if remote && Remote::local_only(kind) {
+							continue;
+						}
+						let Some(receiver) = parts.next().and_then(|s| s.parse().ok()) else { continue };
+						let Some(sender) = parts.next().and_then(|s| s.parse::<u64>().ok()) else { continue };
+
+						let clients = CLIENTS.read();
+						let clients: Vec<_> = if receiver == 0 {
+							clients.values().filter(|c| c.able(kind)).collect()
+						} else if let Some(c) = clients.get(&receiver).filter(|c| c.able(kind)) {
+							vec![c]
+						} else {
+							vec![]
+						};
+
+						if clients.is_empty() {
+							continue;
+						}
+
+						if receiver == 0 && kind.starts_with('@') {
+							let Some(body) = parts.next() else { continue };
+							if !STATE.set(kind, sender, body) { continue }
+						}
+
+						line.push('\n');
+						clients.into_iter().for_each(|c| _ = c.tx.send(line.clone()));
 					}
-				});
+					else => break
+				}
 			}
-		}))
+
+			let mut clients = CLIENTS.write();
+			if id.and_then(|id| clients.remove(&id)).is_some() {
+				Self::handle_hey(&clients);
+			}
+		});
+	}
+
+	async fn accept_remote(
+		listener: Option<&TcpListener>,
+	) -> std::io::Result<(TcpStream, std::net::SocketAddr)> {
+		match listener {
+			Some(l) => l.accept().await,
+			None => std::future::pending().await,
+		}
 	}
 
 	fn handle_hi(s: String, id: &mut Option<u64>, tx: mpsc::UnboundedSender<String>) {
@@ -118,7 +153,11 @@ impl Server {
 		clients.values().for_each(|c| _ = c.tx.send(payload.clone()));
 	}
 
-	async fn handle_bye(id: u64, mut rx: UnboundedReceiver<String>, mut writer: ClientWriter) {
+	async fn handle_bye(
+		id: u64,
+		mut rx: UnboundedReceiver<String>,
+		mut writer: impl AsyncWrite + Unpin,
+	) {
 		while let Ok(payload) = rx.try_recv() {
 			if writer.write_all(payload.as_bytes()).await.is_err() {
 				break;
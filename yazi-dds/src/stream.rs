@@ -1,18 +1,27 @@
-use tokio::io::{BufReader, Lines, ReadHalf, WriteHalf};
+use std::{env, io, time::Duration};
+
+use tokio::{io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, Lines, ReadHalf, WriteHalf}, net::{TcpListener, TcpStream}, time};
+use yazi_shared::RoCell;
+
+pub(super) static TRANSPORT: RoCell<Transport> = RoCell::new();
 
 pub(super) struct Stream;
 
-use tokio::io::AsyncBufReadExt;
+/// The remote server to connect to over TCP, and the token shared with it,
+/// e.g. given on the command line of `ya`.
+#[derive(Default)]
+pub struct Transport {
+	pub remote: Option<String>,
+	pub token:  Option<String>,
+}
 
-#[cfg(unix)]
-pub(super) type ClientReader = Lines<BufReader<ReadHalf<tokio::net::UnixStream>>>;
-#[cfg(not(unix))]
-pub(super) type ClientReader = Lines<BufReader<ReadHalf<tokio::net::TcpStream>>>;
+pub(super) trait Io: AsyncRead + AsyncWrite + Send + Unpin {}
 
-#[cfg(unix)]
-pub(super) type ClientWriter = WriteHalf<tokio::net::UnixStream>;
-#[cfg(not(unix))]
-pub(super) type ClientWriter = WriteHalf<tokio::net::TcpStream>;
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> Io for T {}
+
+pub(super) type ClientReader = Lines<BufReader<ReadHalf<Box<dyn Io>>>>;
+
+pub(super) type ClientWriter = WriteHalf<Box<dyn Io>>;
 
 #[cfg(unix)]
 pub(super) type ServerListener = tokio::net::UnixListener;
@@ -20,22 +29,37 @@ pub(super) type ServerListener = tokio::net::UnixListener;
 pub(super) type ServerListener = tokio::net::TcpListener;
 
 impl Stream {
-	#[cfg(unix)]
-	pub(super) async fn connect() -> std::io::Result<(ClientReader, ClientWriter)> {
-		let stream = tokio::net::UnixStream::connect(Self::socket_file()).await?;
+	/// Connect to the remote server given by the [`Transport`] if set,
+	/// or the local one otherwise.
+	pub(super) async fn connect() -> io::Result<(ClientReader, ClientWriter)> {
+		let stream: Box<dyn Io> = if let Some(addr) = &TRANSPORT.remote {
+			let Some(token) = &TRANSPORT.token else {
+				return Err(io::Error::other(
+					"A token is required for remote connections, see `YAZI_DDS_TOKEN`",
+				));
+			};
+
+			let mut stream = TcpStream::connect(addr).await?;
+			stream.write_all(format!("{token}\n").as_bytes()).await?;
+			Box::new(stream)
+		} else {
+			Box::new(Self::connect_local().await?)
+		};
+
 		let (reader, writer) = tokio::io::split(stream);
 		Ok((BufReader::new(reader).lines(), writer))
 	}
 
-	#[cfg(not(unix))]
-	pub(super) async fn connect() -> std::io::Result<(ClientReader, ClientWriter)> {
-		let stream = tokio::net::TcpStream::connect("127.0.0.1:33581").await?;
-		let (reader, writer) = tokio::io::split(stream);
-		Ok((BufReader::new(reader).lines(), writer))
+	#[cfg(unix)]
+	async fn connect_local() -> io::Result<tokio::net::UnixStream> {
+		tokio::net::UnixStream::connect(Self::socket_file()).await
 	}
 
+	#[cfg(not(unix))]
+	async fn connect_local() -> io::Result<TcpStream> { TcpStream::connect("127.0.0.1:33581").await }
+
 	#[cfg(unix)]
-	pub(super) async fn bind() -> std::io::Result<ServerListener> {
+	pub(super) async fn bind() -> io::Result<ServerListener> {
 		let p = Self::socket_file();
 
 		tokio::fs::remove_file(&p).await.ok();
@@ -43,10 +67,55 @@ impl Stream {
 	}
 
 	#[cfg(not(unix))]
-	pub(super) async fn bind() -> std::io::Result<ServerListener> {
+	pub(super) async fn bind() -> io::Result<ServerListener> {
 		tokio::net::TcpListener::bind("127.0.0.1:33581").await
 	}
 
+	/// Bind the TCP listener for remote peers, which is disabled unless both
+	/// `YAZI_DDS_LISTEN` and `YAZI_DDS_TOKEN` are set.
+	pub(super) async fn bind_remote() -> io::Result<Option<TcpListener>> {
+		let Some(addr) = Self::var("YAZI_DDS_LISTEN") else { return Ok(None) };
+		if TRANSPORT.token.is_none() {
+			return Err(io::Error::other("`YAZI_DDS_LISTEN` requires `YAZI_DDS_TOKEN` to be set"));
+		}
+
+		TcpListener::bind(addr).await.map(Some)
+	}
+
+	/// Only connections from the same user are trusted.
+	#[cfg(unix)]
+	pub(super) fn trusted(stream: &tokio::net::UnixStream) -> bool {
+		use uzers::Users;
+		use yazi_shared::USERS_CACHE;
+
+		stream.peer_cred().is_ok_and(|c| c.uid() == USERS_CACHE.get_current_uid())
+	}
+
+	/// Only connections from the loopback interface are trusted.
+	#[cfg(not(unix))]
+	pub(super) fn trusted(stream: &TcpStream) -> bool {
+		stream.peer_addr().is_ok_and(|a| a.ip().is_loopback())
+	}
+
+	/// Remote peers have to present the shared token as their first line,
+	/// within 5 seconds of connecting.
+	pub(super) async fn authenticate(stream: TcpStream) -> Option<BufReader<TcpStream>> {
+		let token = TRANSPORT.token.as_deref()?;
+		let mut stream = BufReader::new(stream);
+
+		let mut line = String::new();
+		time::timeout(Duration::from_secs(5), stream.read_line(&mut line)).await.ok()?.ok()?;
+
+		let line = line.trim_end_matches(['\r', '\n']).as_bytes();
+		let matched = line.len() == token.len()
+			&& line.iter().zip(token.as_bytes()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0;
+
+		matched.then_some(stream)
+	}
+
+	#[inline]
+	fn var(key: &str) -> Option<String> { env::var(key).ok().filter(|s| !s.is_empty()) }
+
 	#[cfg(unix)]
 	fn socket_file() -> std::path::PathBuf {
 		use std::env::temp_dir;
@@ -57,3 +126,13 @@ impl Stream {
 		temp_dir().join(format!(".yazi_dds-{}.sock", USERS_CACHE.get_current_uid()))
 	}
 }
+
+impl Transport {
+	/// Fills in those not given with `YAZI_DDS_REMOTE` and `YAZI_DDS_TOKEN`.
+	pub(super) fn or_env(self) -> Self {
+		Self {
+			remote: self.remote.or_else(|| Stream::var("YAZI_DDS_REMOTE")),
+			token:  self.token.or_else(|| Stream::var("YAZI_DDS_TOKEN")),
+		}
+	}
+}
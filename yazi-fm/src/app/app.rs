@@ -1,7 +1,7 @@
-use std::{collections::VecDeque, sync::atomic::Ordering};
+use std::{collections::VecDeque, sync::atomic::Ordering, time::Instant};
 
 use anyhow::Result;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use yazi_config::keymap::Key;
 use yazi_core::input::InputMode;
 use yazi_macro::emit;
@@ -13,6 +13,7 @@ pub(crate) struct App {
 	pub(crate) cx:      Ctx,
 	pub(crate) term:    Option<Term>,
 	pub(crate) signals: Signals,
+	pub(crate) clicked: Option<(Instant, MouseEvent)>,
 }
 
 impl App {
@@ -21,7 +22,7 @@ impl App {
 		let (mut rx, signals) = (Event::take(), Signals::start()?);
 
 		Lives::register()?;
-		let mut app = Self { cx: Ctx::make(), term: Some(term), signals };
+		let mut app = Self { cx: Ctx::make(), term: Some(term), signals, clicked: None };
 		app.render();
 
 		let mut times = 0;
@@ -90,6 +91,7 @@ impl App {
 			let input = &mut self.cx.input;
 			if input.mode() == InputMode::Insert {
 				input.type_str(&str);
+				self.cx.recorder.capture_typed(&str);
 			}
 		}
 	}
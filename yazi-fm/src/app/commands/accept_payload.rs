@@ -1,6 +1,6 @@
 use mlua::IntoLua;
 use tracing::error;
-use yazi_dds::{LOCAL, Payload, REMOTE};
+use yazi_dds::{LOCAL, Payload, REMOTE, Remote};
 use yazi_plugin::LUA;
 use yazi_shared::event::Cmd;
 
@@ -13,6 +13,12 @@ impl App {
 		};
 
 		let kind = payload.body.kind().to_owned();
+		if kind == Remote::QUERY {
+			return self.answer_query(payload);
+		} else if Remote::KINDS.contains(&kind.as_str()) {
+			return Remote::accept(payload);
+		}
+
 		let map = if payload.receiver == 0 || payload.receiver != payload.sender {
 			REMOTE.read()
 		} else {
@@ -0,0 +1,52 @@
+use std::time::{Duration, Instant};
+
+use mlua::{ExternalError, HookTriggers, IntoLua, Value};
+use tokio::sync::oneshot;
+use tracing::error;
+use yazi_dds::{PRE, body::Body};
+use yazi_plugin::LUA;
+use yazi_shared::event::Cmd;
+
+use crate::{app::App, lives::Lives};
+
+// Pre-hooks block the operation, so a misbehaving one is aborted after this
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+impl App {
+	pub(crate) fn accept_pre(&mut self, mut cmd: Cmd) {
+		let Some(tx) = cmd.take_any::<oneshot::Sender<bool>>("tx") else {
+			return;
+		};
+		let Some(body) = cmd.take_any::<Body>("body") else {
+			tx.send(true).ok();
+			return;
+		};
+
+		let kind = body.kind().to_owned();
+		let map = PRE.read();
+		let Some(map) = map.get(&kind).filter(|&m| !m.is_empty()) else {
+			tx.send(true).ok();
+			return;
+		};
+
+		let start = Instant::now();
+		LUA.set_hook(HookTriggers::new().every_nth_instruction(1000), move |_, _| {
+			if start.elapsed() > TIMEOUT { Err("Pre-hook timed out".into_lua_err()) } else { Ok(()) }
+		});
+
+		let result = Lives::scope(&self.cx, |_| {
+			let body = body.into_lua(&LUA)?;
+			for (plugin, f) in map {
+				match f.call::<_, Value>(body.clone()) {
+					Ok(Value::Boolean(false)) => return Ok(false),
+					Ok(_) => {}
+					Err(e) => error!("Failed to call `{kind}` pre-hook of `{plugin}`: {e}"),
+				}
+			}
+			Ok(true)
+		});
+
+		LUA.remove_hook();
+		tx.send(result.unwrap_or(true)).ok();
+	}
+}
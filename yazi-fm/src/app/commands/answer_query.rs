@@ -0,0 +1,66 @@
+use std::{collections::HashMap, sync::atomic::Ordering};
+
+use yazi_boot::READ_ONLY;
+use yazi_dds::{Payload, Pubsub, body::Body};
+use yazi_shared::{event::{Data, DataKey}, fs::Url};
+
+use crate::app::App;
+
+impl App {
+	/// Answers a `dds-query` with a snapshot of the requested state, the body is
+	/// either the kind of state, or a dict with `kind` and an optional `tab`.
+	pub(crate) fn answer_query(&mut self, payload: Payload<'static>) {
+		let Body::Custom(body) = payload.body else { return };
+
+		let (kind, tab) = match body.data {
+			Data::String(s) => (s, None),
+			Data::Dict(mut d) => (
+				d.remove(&DataKey::String("kind".to_owned()))
+					.and_then(|d| d.as_str().map(ToOwned::to_owned))
+					.unwrap_or_default(),
+				d.remove(&DataKey::String("tab".to_owned())).and_then(|d| d.as_usize()),
+			),
+			_ => (String::new(), None),
+		};
+
+		let url = |u: &Url| Data::String(u.to_string());
+		let tabs = &self.cx.manager.tabs;
+		let tab = tabs.active_or(tab);
+
+		let data = match kind.as_str() {
+			"cwd" => url(tab.cwd()),
+			"hovered" => tab.hovered().map_or(Data::Nil, |h| url(&h.url)),
+			"selection" => {
+				let mut selected: Vec<_> = tab.selected.iter().collect();
+				selected.sort_unstable_by_key(|(_, &n)| n);
+				Data::List(selected.into_iter().map(|(u, _)| url(u)).collect())
+			}
+			"tabs" => Data::List(
+				tabs
+					.iter()
+					.map(|t| {
+						Data::Dict(HashMap::from_iter([
+							(DataKey::String("idx".to_owned()), Data::Integer(t.idx as i64)),
+							(DataKey::String("cwd".to_owned()), url(t.cwd())),
+							(
+								DataKey::String("hovered".to_owned()),
+								t.hovered().map_or(Data::Nil, |h| url(&h.url)),
+							),
+							(DataKey::String("active".to_owned()), Data::Boolean(t.idx == tabs.cursor)),
+						]))
+					})
+					.collect(),
+			),
+			"read_only" => Data::Boolean(READ_ONLY.load(Ordering::Relaxed)),
+			"tasks" => serde_json::to_value(self.cx.tasks.snapshot())
+				.and_then(serde_json::from_value)
+				.unwrap_or(Data::Nil),
+			_ => Data::Dict(HashMap::from_iter([(
+				DataKey::String("error".to_owned()),
+				Data::String(format!("Unknown query: `{kind}`")),
+			)])),
+		};
+
+		Pubsub::pub_from_answer(payload.sender, data);
+	}
+}
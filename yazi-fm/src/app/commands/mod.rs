@@ -1,9 +1,13 @@
 yazi_macro::mod_flat!(
 	accept_payload
+	accept_pre
+	answer_query
 	mouse
 	notify
+	open_plugin
 	plugin
 	quit
+	replay_do
 	render
 	resize
 	resume
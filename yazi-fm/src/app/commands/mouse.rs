@@ -1,8 +1,12 @@
+use std::time::{Duration, Instant};
+
 use crossterm::event::{MouseEvent, MouseEventKind};
 use mlua::{Table, TableExt};
 use tracing::error;
-use yazi_config::MANAGER;
+use yazi_config::{KEYMAP, MANAGER, keymap::MouseOn};
+use yazi_macro::emit;
 use yazi_plugin::{LUA, bindings::Cast};
+use yazi_shared::Layer;
 
 use crate::{app::App, lives::Lives};
 
@@ -21,6 +25,7 @@ impl App {
 		let Some(size) = self.term.as_ref().and_then(|t| t.size().ok()) else { return };
 		let Ok(evt) = yazi_plugin::bindings::MouseEvent::cast(&LUA, event) else { return };
 
+		let on = MouseOn::from_kind(event.kind, self.double_clicked(event));
 		let res = Lives::scope(&self.cx, move |_| {
 			let area = yazi_plugin::elements::Rect::from(size);
 			let root = LUA.globals().raw_get::<_, Table>("Root")?.call_method::<_, Table>("new", area)?;
@@ -30,7 +35,7 @@ impl App {
 			}
 
 			match event.kind {
-				MouseEventKind::Down(_) => root.call_method("click", (evt, false))?,
+				MouseEventKind::Down(_) => return root.call_method("click", (evt, false)),
 				MouseEventKind::Up(_) => root.call_method("click", (evt, true))?,
 
 				MouseEventKind::ScrollDown => root.call_method("scroll", (evt, 1))?,
@@ -43,11 +48,39 @@ impl App {
 				MouseEventKind::Drag(_) => root.call_method("drag", evt)?,
 			}
 
-			Ok(())
+			Ok(None)
 		});
 
-		if let Err(e) = res {
-			error!("{e}");
+		match res {
+			Ok(Some(id)) => Self::mouse_bind(on, id),
+			Ok(None) => {}
+			Err(e) => error!("{e}"),
+		}
+	}
+
+	fn double_clicked(&mut self, event: MouseEvent) -> bool {
+		let MouseEventKind::Down(button) = event.kind else { return false };
+
+		let now = Instant::now();
+		let last = self.clicked.replace((now, event));
+		let double = last.is_some_and(|(at, e)| {
+			e.kind == MouseEventKind::Down(button)
+				&& (e.column, e.row) == (event.column, event.row)
+				&& now.duration_since(at) <= Duration::from_millis(MANAGER.double_click as u64)
+		});
+
+		// A third click shouldn't be treated as another double-click
+		if double {
+			self.clicked = None;
+		}
+		double
+	}
+
+	fn mouse_bind(on: Option<MouseOn>, id: String) {
+		let Some(on) = on else { return };
+
+		if let Some(chord) = KEYMAP.mouse.iter().find(|c| c.matches(on, &id)) {
+			emit!(Seq(chord.to_seq(), Layer::Manager));
 		}
 	}
 }
@@ -1,4 +1,4 @@
-use yazi_proxy::options::NotifyOpt;
+use yazi_proxy::options::{NotifyLevel, NotifyOpt};
 
 use crate::app::App;
 
@@ -8,6 +8,10 @@ impl App {
 			return;
 		};
 
+		// The command of the macro being replayed that's just been run went wrong
+		if !matches!(opt.level, NotifyLevel::Info) && self.cx.recorder.running() {
+			self.cx.recorder.abort();
+		}
 		self.cx.notify.push(opt);
 	}
 }
@@ -0,0 +1,18 @@
+use yazi_plugin::utils::PluginOpener;
+use yazi_proxy::AppProxy;
+use yazi_shared::{event::Cmd, fs::Url};
+
+use crate::{app::App, lives::Lives};
+
+impl App {
+	pub(crate) fn open_plugin(&mut self, mut cmd: Cmd) {
+		let Some(opener) = cmd.take_any::<PluginOpener>("opener") else {
+			return;
+		};
+
+		let targets = cmd.take_any::<Vec<Url>>("targets").unwrap_or_default();
+		if let Err(e) = Lives::scope(&self.cx, |_| opener.run(targets)) {
+			AppProxy::notify_error("Open", format!("Failed to run `{}`: {e}", opener.desc));
+		}
+	}
+}
@@ -1,37 +1,79 @@
-use std::ffi::OsString;
+use std::{ffi::OsString, io, path::PathBuf};
 
-use yazi_boot::ARGS;
-use yazi_shared::event::EventQuit;
+use yazi_boot::{ARGS, ChooserFormat};
+use yazi_core::manager::{DIR_VIEWS, MIME_CACHE, RECENT_DIRS, Session};
+use yazi_shared::{event::EventQuit, fs::write_atomic};
 
 use crate::{Term, app::App};
 
 impl App {
 	pub(crate) fn quit(&mut self, opt: EventQuit) -> ! {
+		Session::autosave_stop();
+		Session::capture(&self.cx.manager.tabs).save(Session::LAST).ok();
+		MIME_CACHE.lock().save().ok();
+		DIR_VIEWS.lock().save().ok();
+		RECENT_DIRS.lock().save().ok();
 		self.cx.tasks.shutdown();
 		self.cx.manager.shutdown();
 		futures::executor::block_on(yazi_dds::shutdown());
 		futures::executor::block_on(yazi_dds::STATE.drain()).ok();
+		futures::executor::block_on(yazi_plugin::STORE.drain()).ok();
 
-		if !opt.no_cwd_file {
-			self.cwd_to_file();
-		}
-		if let Some(selected) = opt.selected {
-			self.selected_to_file(selected);
-		}
+		self.cwd_to_file(opt.no_cwd_file);
+		self.hovered_to_file();
+
+		// Choosing the CWD in the directory mode, unless it's quit without it
+		let selected = if ARGS.chooser_dir && !opt.no_cwd_file {
+			Some(vec![self.cx.manager.cwd().to_path_buf()])
+		} else {
+			opt.selected
+		};
+
+		// Exits with 1 if nothing's chosen, so the caller can tell it's cancelled
+		let cancelled = ARGS.chooser_file.is_some() && selected.is_none();
+		let written = selected.map(|s| self.selected_to_file(s)).unwrap_or(Ok(()));
 
-		Term::goodbye(|| false);
+		Term::goodbye(|| match written {
+			Ok(()) => cancelled,
+			Err(e) => {
+				eprintln!("Failed to write the chooser file: {e}");
+				true
+			}
+		});
 	}
 
-	fn cwd_to_file(&self) {
+	// Emptied if quit without it, as the autosave may have written it already
+	fn cwd_to_file(&self, no: bool) {
 		if let Some(p) = &ARGS.cwd_file {
-			let cwd = self.cx.manager.cwd().as_os_str();
-			std::fs::write(p, cwd.as_encoded_bytes()).ok();
+			let cwd = if no { "".as_ref() } else { self.cx.manager.cwd().as_os_str() };
+			write_atomic(p, cwd.as_encoded_bytes()).ok();
 		}
 	}
 
-	fn selected_to_file(&self, selected: OsString) {
-		if let Some(p) = &ARGS.chooser_file {
-			std::fs::write(p, selected.as_encoded_bytes()).ok();
+	fn hovered_to_file(&self) {
+		if let Some(p) = &ARGS.hovered_file {
+			let hovered = self.cx.manager.hovered().map(|h| h.url.as_os_str()).unwrap_or_default();
+			write_atomic(p, hovered.as_encoded_bytes()).ok();
 		}
 	}
+
+	fn selected_to_file(&self, selected: Vec<PathBuf>) -> io::Result<()> {
+		let Some(p) = &ARGS.chooser_file else { return Ok(()) };
+
+		let s = match ARGS.chooser_format {
+			ChooserFormat::Null | ChooserFormat::Lines => {
+				let sep = if ARGS.chooser_format == ChooserFormat::Null { "\0" } else { "\n" };
+				selected.iter().fold(OsString::new(), |mut s, p| {
+					s.push(p);
+					s.push(sep);
+					s
+				})
+			}
+			ChooserFormat::Json => {
+				let paths: Vec<_> = selected.iter().map(|p| p.to_string_lossy()).collect();
+				serde_json::to_string(&paths).unwrap_or_default().into()
+			}
+		};
+		write_atomic(p, s.as_encoded_bytes())
+	}
 }
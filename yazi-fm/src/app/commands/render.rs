@@ -1,10 +1,12 @@
-use std::{io::{BufWriter, stderr}, sync::atomic::Ordering};
+use std::{io::{BufWriter, stderr}, sync::atomic::{AtomicBool, Ordering}, time::Duration};
 
 use crossterm::{execute, queue, terminal::{BeginSynchronizedUpdate, EndSynchronizedUpdate}};
 use ratatui::{CompletedFrame, backend::{Backend, CrosstermBackend}, buffer::Buffer};
 use scopeguard::defer;
+use yazi_fs::FolderStage;
+use yazi_macro::emit;
 use yazi_plugin::elements::COLLISION;
-use yazi_shared::event::NEED_RENDER;
+use yazi_shared::{event::NEED_RENDER, tick_clock};
 
 use crate::{app::App, lives::Lives, root::Root};
 
@@ -16,6 +18,11 @@ impl App {
 		queue!(stderr(), BeginSynchronizedUpdate).ok();
 		defer! { execute!(stderr(), EndSynchronizedUpdate).ok(); }
 
+		// Keep the times of the `mtime_relative` linemode up to date, once a minute
+		if self.cx.manager.tabs.iter().any(|t| t.conf.linemode == "mtime_relative") {
+			Self::tick_later();
+		}
+
 		let collision = COLLISION.swap(false, Ordering::Relaxed);
 		let frame = term
 			.draw(|f| {
@@ -38,6 +45,41 @@ impl App {
 		if collision && !COLLISION.load(Ordering::Relaxed) {
 			self.cx.manager.peek(true);
 		}
+
+		// Keep rendering while the current folder is loading, for the spinner in the
+		// header
+		if self.cx.manager.current().stage == FolderStage::Loading {
+			Self::render_later();
+		}
+	}
+
+	fn render_later() {
+		static PENDING: AtomicBool = AtomicBool::new(false);
+		if PENDING.swap(true, Ordering::Relaxed) {
+			return;
+		}
+
+		tokio::spawn(async {
+			tokio::time::sleep(Duration::from_millis(100)).await;
+			PENDING.store(false, Ordering::Relaxed);
+			emit!(Render);
+		});
+	}
+
+	// Moves the clock on, which the relative times are measured from, and renders
+	// again a minute later to move it on again
+	fn tick_later() {
+		static PENDING: AtomicBool = AtomicBool::new(false);
+		if PENDING.swap(true, Ordering::Relaxed) {
+			return;
+		}
+
+		tick_clock();
+		tokio::spawn(async {
+			tokio::time::sleep(Duration::from_secs(60)).await;
+			PENDING.store(false, Ordering::Relaxed);
+			emit!(Render);
+		});
 	}
 
 	pub(crate) fn render_notify(&mut self) {
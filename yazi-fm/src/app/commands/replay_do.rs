@@ -0,0 +1,29 @@
+use yazi_macro::emit;
+use yazi_shared::{Layer, event::Cmd};
+
+use crate::{Executor, app::App};
+
+struct Opt {
+	timeout: bool,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self { Self { timeout: c.bool("timeout") } }
+}
+
+impl From<()> for Opt {
+	fn from(_: ()) -> Self { Self { timeout: false } }
+}
+
+impl App {
+	/// Runs the next command of the macro being replayed, and comes back for the
+	/// one after it once the events it emitted right away have been handled.
+	#[yazi_codegen::command]
+	pub fn replay_do(&mut self, opt: Opt) {
+		let active = self.cx.layer();
+		if let Some((cmd, layer)) = self.cx.recorder.next(active, opt.timeout) {
+			Executor::new(self).execute(cmd, layer);
+			emit!(Call(Cmd::new("replay_do"), Layer::App));
+		}
+	}
+}
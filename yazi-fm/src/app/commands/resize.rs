@@ -1,3 +1,4 @@
+use yazi_adapter::ADAPTOR;
 use yazi_shared::event::Cmd;
 
 use crate::app::App;
@@ -15,8 +16,15 @@ impl From<()> for Opt {
 impl App {
 	#[yazi_codegen::command]
 	pub fn resize(&mut self, _: Opt) {
-		self.cx.manager.active_mut().preview.reset();
+		// A stateful previewer is kept and told about the new area once it's rendered
+		let preview = &mut self.cx.manager.active_mut().preview;
+		if preview.stateful() {
+			ADAPTOR.image_hide().ok();
+		} else {
+			preview.reset();
+		}
 		self.render();
+		self.cx.manager.active().preview.resize();
 
 		self.cx.manager.current_mut().sync_page(true);
 		self.cx.manager.hover(None);
@@ -28,15 +28,8 @@ impl App {
 		tasks.progress = opt.progress;
 
 		// If the task manager is visible, update the summaries with a complete render.
-		if tasks.visible {
-			let new = tasks.paginate();
-			if new.len() != tasks.summaries.len()
-				|| new.iter().zip(&tasks.summaries).any(|(a, b)| a.name != b.name)
-			{
-				tasks.summaries = new;
-				tasks.arrow(0);
-				return render!();
-			}
+		if tasks.visible && tasks.repaginate() {
+			return render!();
 		}
 
 		// Otherwise, only partially update the progress.
@@ -1,13 +1,23 @@
-use ratatui::{buffer::Buffer, layout::{Constraint, Rect}, style::Stylize, text::Span, widgets::{Paragraph, Widget}};
+use ratatui::{buffer::Buffer, layout::{Constraint, Rect}, text::Span, widgets::{Paragraph, Widget}};
+use yazi_config::THEME;
 
-pub(crate) struct Buttons;
+pub(crate) struct Buttons {
+	yes: bool,
+}
+
+impl Buttons {
+	pub(crate) fn new(yes: bool) -> Self { Self { yes } }
+}
 
 impl Widget for Buttons {
 	fn render(self, area: Rect, buf: &mut Buffer) {
 		let chunks =
 			ratatui::layout::Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).split(area);
 
-		Paragraph::new(Span::raw("  [Y]es  ").reversed()).centered().render(chunks[0], buf);
-		Paragraph::new(Span::raw("  (N)o  ")).centered().render(chunks[1], buf);
+		let style =
+			|focused| if focused { THEME.confirm.btn_focused } else { THEME.confirm.btn_unfocused };
+		let [yes, no] = &THEME.confirm.btn_labels;
+		Paragraph::new(Span::styled(yes, style(self.yes))).centered().render(chunks[0], buf);
+		Paragraph::new(Span::styled(no, style(!self.yes))).centered().render(chunks[1], buf);
 	}
 }
@@ -1,4 +1,5 @@
-use ratatui::{buffer::Buffer, layout::{Margin, Rect}, style::{Style, Stylize}, widgets::{Block, Borders, Paragraph, Widget}};
+use ratatui::{buffer::Buffer, layout::{Margin, Rect}, widgets::{Block, Borders, Paragraph, Widget}};
+use yazi_config::THEME;
 
 pub(crate) struct Content<'a> {
 	p: Paragraph<'a>,
@@ -14,7 +15,7 @@ impl<'a> Widget for Content<'a> {
 		let inner = area.inner(Margin::new(1, 0));
 
 		// Bottom border
-		let block = Block::new().borders(Borders::BOTTOM).border_style(Style::new().blue());
+		let block = Block::new().borders(Borders::BOTTOM).border_style(THEME.confirm.border);
 		block.clone().render(area.inner(Margin::new(1, 0)), buf);
 
 		self.p.alignment(ratatui::layout::Alignment::Center).block(block).render(inner, buf);
@@ -1,5 +1,6 @@
 use ratatui::layout::Rect;
-use yazi_core::{completion::Completion, confirm::Confirm, help::Help, input::Input, manager::Manager, notify::Notify, pick::Pick, tasks::Tasks, which::Which};
+use yazi_core::{completion::Completion, confirm::Confirm, help::Help, input::Input, manager::Manager, notify::Notify, pick::Pick, recorder::Recorder, tasks::Tasks, which::Which};
+use yazi_shared::Layer;
 
 pub struct Ctx {
 	pub manager:    Manager,
@@ -11,6 +12,7 @@ pub struct Ctx {
 	pub completion: Completion,
 	pub which:      Which,
 	pub notify:     Notify,
+	pub recorder:   Recorder,
 }
 
 impl Ctx {
@@ -25,6 +27,29 @@ impl Ctx {
 			completion: Default::default(),
 			which:      Default::default(),
 			notify:     Default::default(),
+			recorder:   Default::default(),
+		}
+	}
+
+	/// The layer the keys go to, i.e. the topmost one shown, in the same order
+	/// as they're routed.
+	pub fn layer(&self) -> Layer {
+		if self.completion.visible {
+			Layer::Completion
+		} else if self.help.visible {
+			Layer::Help
+		} else if self.input.visible {
+			Layer::Input
+		} else if self.confirm.visible {
+			Layer::Confirm
+		} else if self.pick.visible {
+			Layer::Pick
+		} else if self.tasks.visible {
+			Layer::Tasks
+		} else if self.manager.active().spot.visible() {
+			Layer::Spot
+		} else {
+			Layer::Manager
 		}
 	}
 
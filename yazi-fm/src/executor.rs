@@ -1,5 +1,6 @@
-use yazi_core::input::InputMode;
-use yazi_shared::{Layer, event::Cmd};
+use yazi_core::{input::InputMode, manager::Manager};
+use yazi_macro::emit;
+use yazi_shared::{Layer, event::{Cmd, Data}};
 
 use crate::app::App;
 
@@ -17,6 +18,7 @@ impl<'a> Executor<'a> {
 			Layer::App => self.app(cmd),
 			Layer::Manager => self.manager(cmd),
 			Layer::Tasks => self.tasks(cmd),
+			Layer::Spot => self.spot(cmd),
 			Layer::Pick => self.pick(cmd),
 			Layer::Input => self.input(cmd),
 			Layer::Confirm => self.confirm(cmd),
@@ -24,6 +26,11 @@ impl<'a> Executor<'a> {
 			Layer::Completion => self.completion(cmd),
 			Layer::Which => self.which(cmd),
 		}
+
+		// What's run may have opened the layer the macro being replayed waits for
+		if self.app.cx.recorder.waiting() {
+			self.app.replay_do(());
+		}
 	}
 
 	fn app(&mut self, cmd: Cmd) {
@@ -36,7 +43,9 @@ impl<'a> Executor<'a> {
 		}
 
 		on!(accept_payload);
+		on!(accept_pre);
 		on!(notify);
+		on!(open_plugin);
 		on!(plugin);
 		on!(plugin_do);
 		on!(update_notify);
@@ -44,6 +53,7 @@ impl<'a> Executor<'a> {
 		on!(resize);
 		on!(stop);
 		on!(resume);
+		on!(replay_do);
 	}
 
 	fn manager(&mut self, cmd: Cmd) {
@@ -58,6 +68,13 @@ impl<'a> Executor<'a> {
 					return self.app.cx.manager.active_mut().$name(cmd, $($args),*);
 				}
 			};
+			// Those sent from other processes can target a tab other than the active one
+			(ACTIVE_OR, $name:ident) => {
+				if cmd.name == stringify!($name) {
+					let tab = cmd.get("tab").and_then(Data::as_usize);
+					return self.app.cx.manager.active_or_mut(tab).$name(cmd);
+				}
+			};
 			(TABS, $name:ident) => {
 				if cmd.name == concat!("tab_", stringify!($name)) {
 					return self.app.cx.manager.tabs.$name(cmd);
@@ -65,21 +82,29 @@ impl<'a> Executor<'a> {
 			};
 		}
 
+		if Manager::reject_read_only(&cmd.name) {
+			return;
+		}
+
 		on!(MANAGER, update_tasks);
 		on!(MANAGER, update_files, &self.app.cx.tasks);
 		on!(MANAGER, update_mimes, &self.app.cx.tasks);
 		on!(MANAGER, update_paged, &self.app.cx.tasks);
 		on!(MANAGER, update_yanked);
+		on!(MANAGER, update_tagged);
 		on!(MANAGER, hover);
 		on!(MANAGER, peek);
 		on!(MANAGER, seek);
-		on!(ACTIVE, spot);
+		on!(MANAGER, spot);
+		on!(MANAGER, session);
 		on!(MANAGER, refresh, &self.app.cx.tasks);
 		on!(MANAGER, quit, &self.app.cx.tasks);
 		on!(MANAGER, close, &self.app.cx.tasks);
 		on!(MANAGER, suspend);
+		on!(MANAGER, read_only);
 		on!(ACTIVE, escape);
 		on!(ACTIVE, update_peeked);
+		on!(ACTIVE, update_footer);
 		on!(ACTIVE, update_spotted);
 
 		// Navigation
@@ -88,8 +113,8 @@ impl<'a> Executor<'a> {
 		on!(ACTIVE, enter);
 		on!(ACTIVE, back);
 		on!(ACTIVE, forward);
-		on!(ACTIVE, cd);
-		on!(ACTIVE, reveal);
+		on!(ACTIVE_OR, cd);
+		on!(ACTIVE_OR, reveal);
 
 		// Toggle
 		on!(ACTIVE, toggle);
@@ -106,13 +131,23 @@ impl<'a> Executor<'a> {
 		on!(MANAGER, paste, &self.app.cx.tasks);
 		on!(MANAGER, link, &self.app.cx.tasks);
 		on!(MANAGER, hardlink, &self.app.cx.tasks);
+		on!(MANAGER, calc_size, &self.app.cx.tasks);
+		on!(MANAGER, checksum, &self.app.cx.tasks);
+		on!(MANAGER, compress);
+		on!(MANAGER, compress_do, &self.app.cx.tasks);
+		on!(MANAGER, duplicate);
+		on!(MANAGER, duplicate_do, &self.app.cx.tasks);
+		on!(MANAGER, extract, &self.app.cx.tasks);
 		on!(MANAGER, remove, &self.app.cx.tasks);
 		on!(MANAGER, remove_do, &self.app.cx.tasks);
 		on!(MANAGER, create);
 		on!(MANAGER, rename);
+		on!(MANAGER, touch);
+		on!(MANAGER, unquarantine);
+		on!(MANAGER, tag);
 		on!(ACTIVE, copy);
 		on!(ACTIVE, shell);
-		on!(ACTIVE, hidden);
+		on!(MANAGER, hidden);
 		on!(ACTIVE, linemode);
 		on!(ACTIVE, search);
 		on!(ACTIVE, search_do);
@@ -120,6 +155,7 @@ impl<'a> Executor<'a> {
 		// Filter
 		on!(ACTIVE, filter);
 		on!(ACTIVE, filter_do);
+		on!(ACTIVE, follow_filter);
 
 		// Find
 		on!(ACTIVE, find);
@@ -138,10 +174,15 @@ impl<'a> Executor<'a> {
 		match cmd.name.as_bytes() {
 			// Tasks
 			b"tasks_show" => self.app.cx.tasks.toggle(()),
+			// Notify
+			b"notify_action" => self.app.cx.notify.action(cmd),
 			// Help
 			b"help" => self.app.cx.help.toggle(Layer::Manager),
 			// Plugin
 			b"plugin" => self.app.plugin(cmd),
+			// Macro
+			b"record" => self.app.cx.recorder.record(cmd),
+			b"replay" => self.app.cx.recorder.replay(cmd),
 			_ => {}
 		}
 	}
@@ -166,6 +207,7 @@ impl<'a> Executor<'a> {
 		on!(cancel);
 		on!(open_with);
 		on!(process_exec);
+		on!(plugin_job);
 
 		match cmd.name.as_str() {
 			// Help
@@ -176,6 +218,27 @@ impl<'a> Executor<'a> {
 		}
 	}
 
+	fn spot(&mut self, cmd: Cmd) {
+		macro_rules! on {
+			($name:ident) => {
+				if cmd.name == stringify!($name) {
+					return self.app.cx.manager.active_mut().spot.$name(cmd);
+				}
+			};
+		}
+
+		on!(arrow);
+		on!(close);
+
+		match cmd.name.as_str() {
+			// Help
+			"help" => self.app.cx.help.toggle(Layer::Spot),
+			// Plugin
+			"plugin" => self.app.plugin(cmd),
+			_ => {}
+		}
+	}
+
 	fn pick(&mut self, cmd: Cmd) {
 		macro_rules! on {
 			($name:ident) => {
@@ -188,6 +251,7 @@ impl<'a> Executor<'a> {
 		on!(show);
 		on!(close);
 		on!(arrow);
+		on!(toggle);
 
 		match cmd.name.as_str() {
 			// Help
@@ -215,6 +279,7 @@ impl<'a> Executor<'a> {
 		on!(show);
 		on!(close);
 		on!(escape);
+		on!(error);
 		on!(move_, "move");
 		on!(backward);
 		on!(forward);
@@ -248,6 +313,7 @@ impl<'a> Executor<'a> {
 				}
 			}
 			InputMode::Insert => {
+				on!(type_text, "type");
 				on!(backspace);
 				on!(kill);
 			}
@@ -266,6 +332,7 @@ impl<'a> Executor<'a> {
 		on!(arrow, &self.app.cx.manager);
 		on!(show);
 		on!(close);
+		on!(focus);
 	}
 
 	fn help(&mut self, cmd: Cmd) {
@@ -324,5 +391,13 @@ impl<'a> Executor<'a> {
 
 		on!(show);
 		on!(callback);
+		on!(popup);
+
+		if cmd.name == "timeout" {
+			if let Some((cmds, layer)) = self.app.cx.which.timeout(cmd) {
+				self.app.cx.recorder.capture(&cmds, layer);
+				emit!(Seq(cmds, layer));
+			}
+		}
 	}
 }
@@ -36,16 +36,22 @@ impl<'a> Widget for Input<'a> {
 		let input = &self.cx.input;
 		let area = self.cx.manager.area(input.position);
 
+		let value = if input.obscure {
+			Line::from("•".repeat(input.value().chars().count()))
+		} else {
+			self.highlighted_value().unwrap_or_else(|_| Line::from(input.value()))
+		};
+
+		let mut block = Block::bordered()
+			.border_type(BorderType::Rounded)
+			.border_style(THEME.input.border)
+			.title(Line::styled(&input.title, THEME.input.title));
+		if let Some(error) = &input.error {
+			block = block.title_bottom(Line::styled(error, THEME.input.error));
+		}
+
 		yazi_plugin::elements::Clear::default().render(area, buf);
-		Paragraph::new(self.highlighted_value().unwrap_or_else(|_| Line::from(input.value())))
-			.block(
-				Block::bordered()
-					.border_type(BorderType::Rounded)
-					.border_style(THEME.input.border)
-					.title(Line::styled(&input.title, THEME.input.title)),
-			)
-			.style(THEME.input.value)
-			.render(area, buf);
+		Paragraph::new(value).block(block).style(THEME.input.value).render(area, buf);
 
 		if let Some(Range { start, end }) = input.selected() {
 			let x = win.width.min(area.x + 1 + start);
@@ -27,9 +27,11 @@ impl Config {
 			reg.add_field_method_get("sort_reverse", |_, me| Ok(me.sort_reverse));
 			reg.add_field_method_get("sort_dir_first", |_, me| Ok(me.sort_dir_first));
 			reg.add_field_method_get("sort_translit", |_, me| Ok(me.sort_translit));
+			reg.add_field_method_get("sort_by_locale", |_, me| Ok(me.sort_by_locale));
 
 			reg.add_field_method_get("linemode", |_, me| Ok(me.linemode.to_owned()));
-			reg.add_field_method_get("show_hidden", |_, me| Ok(me.show_hidden));
+			reg.add_field_method_get("show_hidden", |_, me| Ok(me.show_hidden.is_shown()));
+			reg.add_field_method_get("hidden", |_, me| Ok(me.show_hidden.as_str()));
 		})
 	}
 }
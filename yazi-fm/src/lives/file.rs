@@ -1,8 +1,10 @@
 use std::ops::Deref;
 
 use mlua::{AnyUserData, IntoLua, Lua, UserDataFields, UserDataMethods};
-use yazi_config::THEME;
-use yazi_plugin::{bindings::Range, elements::Style};
+use yazi_adapter::Thumbs;
+use yazi_config::{MANAGER, THEME, manager::ShowHidden};
+use yazi_fs::TAGS;
+use yazi_plugin::{bindings::Range, elements::Style, utils::file_cache};
 use yazi_shared::MIME_DIR;
 
 use super::{CtxRef, SCOPE};
@@ -61,6 +63,31 @@ impl File {
 
 				Ok(THEME.filetypes.iter().find(|&x| x.matches(me, mime)).map(|x| Style::from(x.style)))
 			});
+			reg.add_method("tags", |lua, me, ()| {
+				let tags = TAGS.read();
+				lua.create_sequence_from(tags.get(&me.url).into_iter().flatten().map(|s| s.as_str()))
+			});
+			reg.add_method("thumb", |lua, me, ()| {
+				if !MANAGER.thumbnails {
+					return Ok((None, None));
+				}
+
+				let cx = lua.named_registry_value::<CtxRef>("cx")?;
+				if !cx.manager.mimetype.get(&me.url).is_some_and(|m| m.starts_with("image/")) {
+					return Ok((None, None));
+				}
+
+				// Only the images that have been precached, as decoding the originals is too
+				// expensive for that many of them, which is checked aside by the thumbnails
+				let Some(cache) = file_cache(me, 0) else {
+					return Ok((None, None));
+				};
+
+				Ok(match Thumbs::get(&cache) {
+					Some((s, (r, g, b))) => (Some(s), Some(format!("#{r:02x}{g:02x}{b:02x}"))),
+					None => (None, None),
+				})
+			});
 			reg.add_method("is_hovered", |_, me, ()| Ok(me.idx == me.folder().cursor));
 			reg.add_method("is_yanked", |lua, me, ()| {
 				let cx = lua.named_registry_value::<CtxRef>("cx")?;
@@ -85,6 +112,10 @@ impl File {
 				})
 			});
 			reg.add_method("is_selected", |_, me, ()| Ok(me.tab().selected.contains_key(&me.url)));
+			reg.add_method("is_unmatched", |_, me, ()| Ok(me.folder().files.is_pinned_unmatched(me)));
+			reg.add_method("is_dimmed", |_, me, ()| {
+				Ok(me.tab().conf.show_hidden == ShowHidden::Dim && me.is_hidden())
+			});
 			reg.add_method("in_parent", |_, me, ()| {
 				Ok(me.tab().parent.as_ref().is_some_and(|f| me.folder().url == f.url))
 			});
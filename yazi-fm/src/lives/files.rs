@@ -28,7 +28,9 @@ impl Files {
 
 	pub(super) fn register(lua: &Lua) -> mlua::Result<()> {
 		lua.register_userdata_type::<Self>(|reg| {
-			reg.add_field_method_get("filter", |_, me| me.filter().map(Filter::make).transpose());
+			reg.add_field_method_get("filter", |_, me| {
+				me.filter().map(|f| Filter::make(f, me.matched())).transpose()
+			});
 
 			reg.add_meta_method(MetaMethod::Len, |_, me, ()| Ok(me.window.end - me.window.start));
 
@@ -1,11 +1,12 @@
 use std::ops::Deref;
 
-use mlua::{AnyUserData, Lua, MetaMethod, UserDataMethods};
+use mlua::{AnyUserData, Lua, MetaMethod, UserDataFields, UserDataMethods};
 
 use super::SCOPE;
 
 pub(super) struct Filter {
-	inner: *const yazi_fs::Filter,
+	inner:   *const yazi_fs::Filter,
+	matched: usize,
 }
 
 impl Deref for Filter {
@@ -16,12 +17,20 @@ impl Deref for Filter {
 
 impl Filter {
 	#[inline]
-	pub(super) fn make(inner: &yazi_fs::Filter) -> mlua::Result<AnyUserData<'static>> {
-		SCOPE.create_any_userdata(Self { inner })
+	pub(super) fn make(
+		inner: &yazi_fs::Filter,
+		matched: usize,
+	) -> mlua::Result<AnyUserData<'static>> {
+		SCOPE.create_any_userdata(Self { inner, matched })
 	}
 
 	pub(super) fn register(lua: &Lua) -> mlua::Result<()> {
 		lua.register_userdata_type::<Self>(|reg| {
+			reg.add_field_method_get("pattern", |_, me| Ok(me.pattern().to_owned()));
+			reg.add_field_method_get("case", |_, me| Ok(me.case().as_str()));
+			reg.add_field_method_get("tag", |_, me| Ok(me.tagged().is_some()));
+			reg.add_field_method_get("matched", |_, me| Ok(me.matched));
+
 			reg.add_meta_method(MetaMethod::ToString, |_, me, ()| Ok(me.to_string()));
 		})
 	}
@@ -1,11 +1,13 @@
 use std::ops::Deref;
 
-use mlua::{AnyUserData, Lua};
+use mlua::{AnyUserData, Lua, UserDataFields};
+use yazi_core::tab::FindPane;
 
 use super::SCOPE;
 
 pub(super) struct Finder {
-	inner: *const yazi_core::tab::Finder,
+	inner:   *const yazi_core::tab::Finder,
+	current: Option<usize>,
 }
 
 impl Deref for Finder {
@@ -16,11 +18,27 @@ impl Deref for Finder {
 
 impl Finder {
 	#[inline]
-	pub(super) fn make(inner: &yazi_core::tab::Finder) -> mlua::Result<AnyUserData<'static>> {
-		SCOPE.create_any_userdata(Self { inner })
+	pub(super) fn make(
+		inner: &yazi_core::tab::Finder,
+		current: Option<usize>,
+	) -> mlua::Result<AnyUserData<'static>> {
+		SCOPE.create_any_userdata(Self { inner, current })
 	}
 
 	pub(super) fn register(lua: &Lua) -> mlua::Result<()> {
-		lua.register_userdata_type::<Self>(|_| {})
+		lua.register_userdata_type::<Self>(|reg| {
+			reg.add_field_method_get("query", |_, me| Ok(me.filter.pattern().to_owned()));
+			reg.add_field_method_get("case", |_, me| Ok(me.filter.case().as_str()));
+			reg.add_field_method_get("current", |_, me| Ok(me.current));
+			reg.add_field_method_get("total", |_, me| Ok(me.matched().len()));
+			reg.add_field_method_get("global", |_, me| Ok(me.global));
+			reg.add_field_method_get("pane", |_, me| {
+				Ok(match me.pane() {
+					FindPane::Parent => "parent",
+					FindPane::Current => "current",
+					FindPane::Child => "child",
+				})
+			});
+		})
 	}
 }
@@ -1,11 +1,18 @@
-use std::ops::{Deref, Range};
+use std::{ops::{Deref, Range}, time::Instant};
 
 use mlua::{AnyUserData, Lua, UserDataFields};
 use yazi_config::LAYOUT;
+use yazi_core::manager::DIR_VIEWS;
+use yazi_fs::FolderStage;
 use yazi_plugin::{bindings::Cast, url::Url};
 
 use super::{File, Files, SCOPE};
 
+struct Stage {
+	inner: FolderStage,
+	at:    Option<Instant>,
+}
+
 pub(super) struct Folder {
 	window: Range<usize>,
 	inner:  *const yazi_fs::Folder,
@@ -40,8 +47,14 @@ impl Folder {
 		lua.register_userdata_type::<Self>(|reg| {
 			reg.add_field_method_get("cwd", |lua, me| Url::cast(lua, me.url.to_owned()));
 			reg.add_field_method_get("files", |_, me| Files::make(0..me.files.len(), me, me.tab()));
-			reg.add_field_method_get("stage", |lua, me| lua.create_any_userdata(me.stage));
+			reg.add_field_method_get("stage", |lua, me| {
+				lua.create_any_userdata(Stage { inner: me.stage, at: me.staged_at })
+			});
 			reg.add_field_method_get("window", |_, me| Files::make(me.window.clone(), me, me.tab()));
+			reg.add_field_method_get("sort_override", |_, me| {
+				let remembered = DIR_VIEWS.lock().get(&me.url).map(|v| v.sort).unwrap_or_default();
+				Ok(me.tab().conf.sort_override(me.sort_override, remembered).map(|o| o.path.as_str()))
+			});
 
 			reg.add_field_method_get("offset", |_, me| Ok(me.offset));
 			reg.add_field_method_get("cursor", |_, me| Ok(me.cursor));
@@ -50,8 +63,18 @@ impl Folder {
 			});
 		})?;
 
-		lua.register_userdata_type::<yazi_fs::FolderStage>(|reg| {
-			reg.add_field_method_get("is_loading", |_, me| Ok(*me == yazi_fs::FolderStage::Loading));
+		lua.register_userdata_type::<Stage>(|reg| {
+			reg.add_field_method_get("is_loading", |_, me| Ok(me.inner == FolderStage::Loading));
+			reg.add_field_method_get("is_truncated", |_, me| {
+				Ok(matches!(me.inner, FolderStage::Truncated(_)))
+			});
+			reg.add_field_method_get("error", |_, me| {
+				Ok(match me.inner {
+					FolderStage::Failed(kind) | FolderStage::Truncated(kind) => Some(kind.to_string()),
+					_ => None,
+				})
+			});
+			reg.add_field_method_get("elapsed", |_, me| Ok(me.at.map(|t| t.elapsed().as_secs_f64())));
 		})?;
 
 		Ok(())
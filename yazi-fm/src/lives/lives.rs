@@ -42,16 +42,14 @@ impl Lives {
 			});
 			LUA.set_named_registry_value("cx", scope.create_any_userdata_ref(cx)?)?;
 
-			let globals = LUA.globals();
-			globals.raw_set(
-				"cx",
-				LUA.create_table_from([
-					("active", super::Tab::make(cx.manager.active())?),
-					("tabs", super::Tabs::make(&cx.manager.tabs)?),
-					("tasks", super::Tasks::make(&cx.tasks)?),
-					("yanked", super::Yanked::make(&cx.manager.yanked)?),
-				])?,
-			)?;
+			let table = LUA.create_table_from([
+				("active", super::Tab::make(cx.manager.active())?),
+				("tabs", super::Tabs::make(&cx.manager.tabs)?),
+				("tasks", super::Tasks::make(&cx.tasks)?),
+				("yanked", super::Yanked::make(&cx.manager.yanked)?),
+			])?;
+			table.raw_set("recording", cx.recorder.recording())?;
+			LUA.globals().raw_set("cx", table)?;
 
 			f(scope)
 		});
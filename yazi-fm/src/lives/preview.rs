@@ -24,6 +24,9 @@ impl Preview {
 	pub(super) fn register(lua: &Lua) -> mlua::Result<()> {
 		lua.register_userdata_type::<Self>(|reg| {
 			reg.add_field_method_get("skip", |_, me| Ok(me.skip));
+			reg.add_field_method_get("footer", |lua, me| {
+				me.footer.as_deref().map(|s| lua.create_string(s)).transpose()
+			});
 			reg.add_field_method_get("folder", |_, me| {
 				me.tab()
 					.hovered_folder()
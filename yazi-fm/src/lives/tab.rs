@@ -40,7 +40,9 @@ impl Tab {
 			reg.add_field_method_get("selected", |_, me| Selected::make(&me.selected));
 
 			reg.add_field_method_get("preview", |_, me| Preview::make(me));
-			reg.add_field_method_get("finder", |_, me| me.finder.as_ref().map(Finder::make).transpose());
+			reg.add_field_method_get("finder", |_, me| {
+				me.finder.as_ref().map(|f| Finder::make(f, me.find_body().current)).transpose()
+			});
 		})?;
 
 		Ok(())
@@ -1,6 +1,6 @@
 use std::ops::Deref;
 
-use mlua::{AnyUserData, Lua, LuaSerdeExt, UserDataFields};
+use mlua::{AnyUserData, Lua, LuaSerdeExt, UserDataFields, UserDataMethods};
 
 use super::SCOPE;
 
@@ -22,7 +22,8 @@ impl Tasks {
 
 	pub(super) fn register(lua: &Lua) -> mlua::Result<()> {
 		lua.register_userdata_type::<Self>(|reg| {
-			reg.add_field_method_get("progress", |lua, me| lua.to_value(&me.progress))
+			reg.add_field_method_get("progress", |lua, me| lua.to_value(&me.progress));
+			reg.add_method("snapshot", |lua, me, ()| lua.to_value(&me.snapshot()));
 		})?;
 
 		Ok(())
@@ -6,7 +6,7 @@
 static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 
 yazi_macro::mod_pub!(
-	app completion components confirm help input lives notify pick tasks which
+	app completion components confirm help input lives notify pick spot tasks which
 );
 
 yazi_macro::mod_flat!(context executor logs panic root router signals term);
@@ -20,7 +20,9 @@ async fn main() -> anyhow::Result<()> {
 
 	yazi_shared::init();
 
-	yazi_config::init()?;
+	yazi_boot::init_args();
+
+	yazi_config::init(&yazi_boot::ARGS.config_set, yazi_boot::ARGS.profile.as_deref())?;
 
 	yazi_adapter::init();
 
@@ -34,6 +36,10 @@ async fn main() -> anyhow::Result<()> {
 
 	yazi_core::init();
 
+	if let Some(msg) = yazi_config::PREVIEW.check_dir() {
+		yazi_proxy::AppProxy::notify_warn("Preview", msg);
+	}
+
 	yazi_dds::serve();
 	app::App::serve().await
 }
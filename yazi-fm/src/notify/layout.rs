@@ -51,17 +51,18 @@ impl<'a> Widget for Layout<'a> {
 				tile[i].offset(Offset { x: (100 - m.percent) as i32 * tile[i].width as i32 / 100, y: 0 });
 			rect.width -= rect.x - tile[i].x;
 
+			let mut block = Block::bordered()
+				.border_type(BorderType::Rounded)
+				.title(format!("{} {}", m.level.icon(), m.title))
+				.title_style(*m.level.style())
+				.border_style(*m.level.style());
+			if !m.actions.is_empty() {
+				let labels: Vec<_> = m.actions.iter().map(|a| a.label.as_str()).collect();
+				block = block.title_bottom(format!(" {} ", labels.join(" │ ")));
+			}
+
 			yazi_plugin::elements::Clear::default().render(rect, buf);
-			Paragraph::new(m.content.as_str())
-				.wrap(Wrap { trim: false })
-				.block(
-					Block::bordered()
-						.border_type(BorderType::Rounded)
-						.title(format!("{} {}", m.level.icon(), m.title))
-						.title_style(*m.level.style())
-						.border_style(*m.level.style()),
-				)
-				.render(rect, buf);
+			Paragraph::new(m.content.as_str()).wrap(Wrap { trim: false }).block(block).render(rect, buf);
 		}
 	}
 }
@@ -21,11 +21,12 @@ impl<'a> Widget for Pick<'a> {
 			.iter()
 			.enumerate()
 			.map(|(i, v)| {
+				let mark = if pick.is_selected(i) { "✓ " } else { "" };
 				if i != pick.rel_cursor() {
-					return ListItem::new(format!("  {v}")).style(THEME.pick.inactive);
+					return ListItem::new(format!("  {mark}{v}")).style(THEME.pick.inactive);
 				}
 
-				ListItem::new(format!(" {v}")).style(THEME.pick.active)
+				ListItem::new(format!(" {mark}{v}")).style(THEME.pick.active)
 			})
 			.collect();
 
@@ -3,7 +3,7 @@ use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
 use tracing::error;
 use yazi_plugin::{LUA, elements::render_widgets};
 
-use super::{completion, confirm, input, pick, tasks, which};
+use super::{completion, confirm, input, pick, spot, tasks, which};
 use crate::{Ctx, components, help};
 
 pub(super) struct Root<'a> {
@@ -29,6 +29,10 @@ impl<'a> Widget for Root<'a> {
 
 		components::Preview::new(self.cx).render(area, buf);
 
+		if self.cx.manager.active().spot.visible() {
+			spot::Spot::new(self.cx).render(area, buf);
+		}
+
 		if self.cx.tasks.visible {
 			tasks::Layout::new(self.cx).render(area, buf);
 		}
@@ -1,6 +1,8 @@
+use std::collections::VecDeque;
+
 use yazi_config::{KEYMAP, keymap::{Chord, Key}};
 use yazi_macro::emit;
-use yazi_shared::Layer;
+use yazi_shared::{Layer, event::Cmd};
 
 use crate::app::App;
 
@@ -17,12 +19,18 @@ impl<'a> Router<'a> {
 		let cx = &mut self.app.cx;
 
 		if cx.which.visible {
-			return cx.which.type_(key);
+			if let Some((cmds, layer)) = cx.which.type_(key) {
+				self.emit(cmds, layer);
+			}
+			return true;
 		}
 		if cx.help.visible && cx.help.type_(&key) {
 			return true;
 		}
 		if cx.input.visible && cx.input.type_(&key) {
+			if let Some(c) = key.plain() {
+				cx.recorder.capture_typed(c.encode_utf8(&mut [0; 4]));
+			}
 			return true;
 		}
 
@@ -38,6 +46,8 @@ impl<'a> Router<'a> {
 			self.matches(Layer::Pick, key)
 		} else if cx.tasks.visible {
 			self.matches(Layer::Tasks, key)
+		} else if cx.manager.active().spot.visible() {
+			cx.manager.active_mut().spot.type_(&key) || self.matches(Layer::Spot, key)
 		} else {
 			self.matches(Layer::Manager, key)
 		}
@@ -53,10 +63,16 @@ impl<'a> Router<'a> {
 			if on.len() > 1 {
 				self.app.cx.which.show_with(key, layer);
 			} else {
-				emit!(Seq(ctrl.to_seq(), layer));
+				self.emit(ctrl.to_seq(), layer);
 			}
 			return true;
 		}
 		false
 	}
+
+	#[inline]
+	fn emit(&mut self, cmds: VecDeque<Cmd>, layer: Layer) {
+		self.app.cx.recorder.capture(&cmds, layer);
+		emit!(Seq(cmds, layer));
+	}
 }
@@ -1,9 +1,13 @@
+use std::sync::atomic::Ordering;
+
 use anyhow::Result;
 use crossterm::event::{Event as CrosstermEvent, EventStream, KeyEvent, KeyEventKind};
 use futures::StreamExt;
 use tokio::{select, sync::{mpsc, oneshot}};
 use yazi_config::MANAGER;
-use yazi_shared::event::Event;
+use yazi_macro::emit;
+use yazi_proxy::ManagerProxy;
+use yazi_shared::{Layer, event::{Cmd, Event}};
 
 pub(super) struct Signals {
 	tx: mpsc::UnboundedSender<(bool, Option<oneshot::Sender<()>>)>,
@@ -58,19 +62,35 @@ impl Signals {
 	fn handle_term(event: CrosstermEvent) {
 		match event {
 			CrosstermEvent::Key(key @ KeyEvent { kind: KeyEventKind::Press, .. }) => {
+				Self::focus();
 				Event::Key(key).emit()
 			}
 			CrosstermEvent::Mouse(mouse) => {
 				if MANAGER.mouse_events.contains(mouse.kind.into()) {
+					Self::focus();
 					Event::Mouse(mouse).emit();
 				}
 			}
 			CrosstermEvent::Paste(str) => Event::Paste(str).emit(),
 			CrosstermEvent::Resize(..) => Event::Resize.emit(),
+			CrosstermEvent::FocusGained => Self::focus(),
+			CrosstermEvent::FocusLost if MANAGER.pause_unfocused => {
+				yazi_adapter::FOCUSED.store(false, Ordering::Relaxed)
+			}
 			_ => {}
 		}
 	}
 
+	// Catches up on what's been paused while unfocused, which is also done on
+	// any input in case the focus event got lost
+	fn focus() {
+		if !yazi_adapter::FOCUSED.swap(true, Ordering::Relaxed) {
+			emit!(Call(Cmd::new("refresh"), Layer::Manager));
+			ManagerProxy::peek(true);
+			emit!(Render);
+		}
+	}
+
 	fn spawn(mut rx: mpsc::UnboundedReceiver<(bool, Option<oneshot::Sender<()>>)>) -> Result<()> {
 		#[cfg(unix)]
 		use libc::{SIGCONT, SIGHUP, SIGQUIT, SIGTERM, SIGTSTP};
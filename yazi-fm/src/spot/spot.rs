@@ -0,0 +1,59 @@
+use ratatui::{buffer::Buffer, layout::Rect, text::{Line, Span}, widgets::{Block, BorderType, Widget}};
+use yazi_adapter::ADAPTOR;
+use yazi_config::THEME;
+use yazi_shared::fs::escape_non_utf8;
+
+use crate::Ctx;
+
+pub(crate) struct Spot<'a> {
+	cx: &'a Ctx,
+}
+
+impl<'a> Spot<'a> {
+	pub(crate) fn new(cx: &'a Ctx) -> Self { Self { cx } }
+}
+
+impl Widget for Spot<'_> {
+	fn render(self, _: Rect, buf: &mut Buffer) {
+		let Some(lock) = &self.cx.manager.active().spot.lock else {
+			return;
+		};
+
+		let area = yazi_core::spot::Spot::area();
+		if let Some(image) = ADAPTOR.spotted() {
+			// Skip the cells of the image shown by the spotter, so it's not drawn over
+			ratatui::widgets::Clear.render(area, buf);
+			let image = image.intersection(area);
+			for y in image.top()..image.bottom() {
+				for x in image.left()..image.right() {
+					buf[(x, y)].set_skip(true);
+				}
+			}
+		} else {
+			yazi_plugin::elements::Clear::default().render(area, buf);
+		}
+
+		let mut block = Block::bordered()
+			.border_type(BorderType::Rounded)
+			.border_style(THEME.spot.border)
+			.title(Line::styled(escape_non_utf8(lock.url.name()), THEME.spot.title));
+
+		// List the keys claimed by the spotter, so they're discoverable
+		if !lock.keys.is_empty() {
+			let spans = lock.keys.iter().enumerate().flat_map(|(i, k)| {
+				[
+					Span::raw(if i == 0 { " " } else { "  " }),
+					Span::styled(k.on.to_string(), THEME.help.on),
+					Span::raw(" "),
+					Span::styled(&k.desc, THEME.help.desc),
+				]
+			});
+			block = block.title_bottom(Line::from_iter(spans.chain([Span::raw(" ")])));
+		}
+		block.render(area, buf);
+
+		for w in &lock.data {
+			w.clone_render(buf);
+		}
+	}
+}
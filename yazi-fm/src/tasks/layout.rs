@@ -32,29 +32,38 @@ impl<'a> Widget for Layout<'a> {
 	fn render(self, area: Rect, buf: &mut Buffer) {
 		let area = Self::area(area);
 
+		// How the fetches and preloads went, to tell whether leaving the folders
+		// cancels theirs
+		let (completed, canceled) = self.cx.tasks.prework_counts();
+		let counts = format!(" Preworks: {completed} completed, {canceled} canceled ");
+
 		yazi_plugin::elements::Clear::default().render(area, buf);
 		let block = Block::bordered()
 			.title(Line::styled("Tasks", THEME.tasks.title))
+			.title_bottom(Line::styled(counts, THEME.tasks.group).right_aligned())
 			.title_alignment(Alignment::Center)
 			.padding(Padding::symmetric(1, 1))
 			.border_type(BorderType::Rounded)
 			.border_style(THEME.tasks.border);
 		block.clone().render(area, buf);
 
+		// A header goes before each group, which the cursor doesn't stop at
 		let tasks = &self.cx.tasks;
-		let items = tasks
-			.summaries
-			.iter()
-			.take(area.height.saturating_sub(2) as usize)
-			.enumerate()
-			.map(|(i, v)| {
-				let mut item = ListItem::new(v.name.clone());
-				if i == tasks.cursor {
-					item = item.style(THEME.tasks.hovered);
-				}
-				item
-			})
-			.collect::<Vec<_>>();
+		let mut items = Vec::with_capacity(tasks.summaries.len() + 3);
+		let mut last = None;
+		for (i, v) in tasks.summaries.iter().enumerate() {
+			let state = v.state();
+			if last.replace(state) != Some(state) {
+				items.push(ListItem::new(state.title()).style(THEME.tasks.group));
+			}
+
+			let mut item = ListItem::new(v.name.clone());
+			if i == tasks.cursor {
+				item = item.style(THEME.tasks.hovered);
+			}
+			items.push(item);
+		}
+		items.truncate(area.height.saturating_sub(2) as usize);
 
 		List::new(items).render(block.inner(area), buf);
 	}
@@ -1,7 +1,7 @@
 use std::{io::{self, BufWriter, Stderr, stderr}, ops::{Deref, DerefMut}, sync::atomic::{AtomicBool, AtomicU8, Ordering}};
 
 use anyhow::Result;
-use crossterm::{event::{DisableBracketedPaste, EnableBracketedPaste, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags}, execute, queue, style::Print, terminal::{EnterAlternateScreen, LeaveAlternateScreen, SetTitle, disable_raw_mode, enable_raw_mode}};
+use crossterm::{event::{DisableBracketedPaste, DisableFocusChange, EnableBracketedPaste, EnableFocusChange, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags}, execute, queue, style::Print, terminal::{EnterAlternateScreen, LeaveAlternateScreen, SetTitle, disable_raw_mode, enable_raw_mode}};
 use cursor::RestoreCursor;
 use ratatui::{CompletedFrame, Frame, Terminal, backend::CrosstermBackend, buffer::Buffer, layout::Rect};
 use yazi_adapter::{Emulator, Mux};
@@ -33,6 +33,7 @@ impl Term {
 			Print(Mux::csi("\x1b[?u\x1b[0c")), // Request keyboard enhancement flags (CSI u)
 			EnterAlternateScreen,
 			EnableBracketedPaste,
+			EnableFocusChange,
 			mouse::SetMouse(true),
 		)?;
 
@@ -73,6 +74,7 @@ impl Term {
 			mouse::SetMouse(false),
 			RestoreCursor,
 			DisableBracketedPaste,
+			DisableFocusChange,
 			LeaveAlternateScreen,
 		)?;
 
@@ -95,6 +97,7 @@ impl Term {
 			RestoreCursor,
 			SetTitle(""),
 			DisableBracketedPaste,
+			DisableFocusChange,
 			LeaveAlternateScreen,
 			crossterm::cursor::Show
 		)
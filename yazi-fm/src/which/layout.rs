@@ -1,4 +1,4 @@
-use ratatui::{buffer::Buffer, layout, layout::{Constraint, Rect}, widgets::{Block, Widget}};
+use ratatui::{buffer::Buffer, layout, layout::{Constraint, Rect}, text::{Line, Span}, widgets::{Block, Widget}};
 use yazi_config::THEME;
 
 use super::Cand;
@@ -13,6 +13,15 @@ pub(crate) struct Which<'a> {
 
 impl<'a> Which<'a> {
 	pub(crate) fn new(cx: &'a Ctx) -> Self { Self { cx } }
+
+	fn pending(&self) -> Line<'static> {
+		let keys: String = self.cx.which.pending().iter().map(ToString::to_string).collect();
+		if keys.is_empty() {
+			Line::default()
+		} else {
+			Span::styled(format!(" {keys} "), THEME.which.cand).into()
+		}
+	}
 }
 
 impl Widget for Which<'_> {
@@ -22,6 +31,22 @@ impl Widget for Which<'_> {
 			return;
 		}
 
+		// Only the pending keys until the candidates pop up
+		let pending = self.pending();
+		if !which.popped {
+			let area = Rect {
+				x:      PADDING_X.min(area.width),
+				y:      area.height.saturating_sub(1 + PADDING_Y * 2),
+				width:  (pending.width() as u16).min(area.width.saturating_sub(PADDING_X * 2)),
+				height: 1.min(area.height),
+			};
+
+			yazi_plugin::elements::Clear::default().render(area, buf);
+			Block::new().style(THEME.which.mask).render(area, buf);
+			pending.render(area, buf);
+			return;
+		}
+
 		let cols = THEME.which.cols as usize;
 		let height = area.height.min(which.cands.len().div_ceil(cols) as u16 + PADDING_Y * 2);
 		let area = Rect {
@@ -48,6 +73,7 @@ impl Widget for Which<'_> {
 
 		yazi_plugin::elements::Clear::default().render(area, buf);
 		Block::new().style(THEME.which.mask).render(area, buf);
+		pending.render(Rect { height: 1, ..area }, buf);
 
 		for y in 0..area.height {
 			for (x, chunk) in chunks.iter().enumerate() {
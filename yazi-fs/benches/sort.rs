@@ -0,0 +1,58 @@
+//! Sorts a large directory naturally, by comparing the names for every pair as
+//! it was done before the sort keys, by making the keys on the first sort, and
+//! by reusing them on a sort after, e.g. reversing the order.
+//!
+//! Run with `cargo bench -p yazi-fs --bench sort`.
+
+use std::{hint::black_box, time::{Duration, Instant}};
+
+use yazi_config::manager::SortBy;
+use yazi_fs::{FilesSorter, SortKeys};
+use yazi_shared::{LcgRng, fs::{File, Url}, natsort};
+
+const FILES: usize = 50_000;
+
+// `img00001.png` to `img50000.png`, shuffled
+fn files() -> Vec<File> {
+	let mut rng = LcgRng::default();
+	let mut files: Vec<_> = (1..=FILES)
+		.map(|i| {
+			let url = Url::from(format!("/home/user/photos/img{i:05}.png"));
+			(rng.next(), File { url, ..Default::default() })
+		})
+		.collect();
+	files.sort_unstable_by_key(|(n, _)| *n);
+	files.into_iter().map(|(_, f)| f).collect()
+}
+
+fn measure(name: &str, files: &[File], mut f: impl FnMut(&mut Vec<File>)) {
+	let (mut runs, mut total) = (0, Duration::ZERO);
+	while total < Duration::from_secs(2) {
+		let mut items = files.to_vec();
+		let start = Instant::now();
+		f(black_box(&mut items));
+		total += start.elapsed();
+		runs += 1;
+	}
+
+	let per = total / runs;
+	println!("{name:>8}: {per:>10.2?} per sort of {FILES} files, {runs} runs");
+}
+
+fn main() {
+	let files = files();
+	let sorter = FilesSorter { by: SortBy::Natural, ..Default::default() };
+	let sizes = Default::default();
+
+	measure("pairwise", &files, |items| {
+		items.sort_unstable_by(|a, b| {
+			natsort(a.name().as_encoded_bytes(), b.name().as_encoded_bytes(), true)
+		})
+	});
+	measure("keyed", &files, |items| sorter.sort(items, 0, &sizes, &mut SortKeys::new()));
+
+	let mut keys = SortKeys::new();
+	sorter.sort(&mut files.to_vec(), 0, &sizes, &mut keys);
+	let reversed = FilesSorter { reverse: true, ..sorter };
+	measure("cached", &files, |items| reversed.sort(items, 0, &sizes, &mut keys));
+}
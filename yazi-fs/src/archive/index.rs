@@ -0,0 +1,244 @@
+use std::{collections::{BTreeMap, HashMap}, fs::{self, File}, io::{self, BufReader}, ops::Bound, path::{Component, Path, PathBuf}, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use zip::ZipArchive;
+
+use super::{ArchiveKind, safe_link, through_link};
+
+#[derive(Clone, Copy, Debug)]
+pub struct ArchiveEntry {
+	pub index:     Option<usize>,
+	pub size:      u64,
+	pub mtime:     Option<SystemTime>,
+	pub mode:      Option<u32>,
+	pub dir:       bool,
+	pub encrypted: bool,
+}
+
+/// The entries of an archive, keyed by their path inside it.
+///
+/// Directories that aren't stored in the archive but only implied by the paths
+/// of their children are included too, without an `index`.
+#[derive(Debug)]
+pub struct ArchiveIndex {
+	pub kind:  ArchiveKind,
+	pub len:   u64,
+	pub mtime: Option<SystemTime>,
+
+	entries: BTreeMap<PathBuf, ArchiveEntry>,
+}
+
+impl ArchiveIndex {
+	pub fn build(path: &Path, kind: ArchiveKind) -> io::Result<Self> {
+		let meta = fs::metadata(path)?;
+		let mut entries = BTreeMap::new();
+
+		match kind {
+			ArchiveKind::Zip => {
+				let mut zip = ZipArchive::new(BufReader::new(File::open(path)?))?;
+				for index in 0..zip.len() {
+					let file = zip.by_index_raw(index)?;
+					let Some(name) = file.enclosed_name() else { continue };
+					entries.insert(name, ArchiveEntry {
+						index:     Some(index),
+						size:      file.size(),
+						mtime:     file.last_modified().and_then(|t| {
+							Self::civil_time(t.year(), t.month(), t.day(), t.hour(), t.minute(), t.second())
+						}),
+						mode:      file.unix_mode(),
+						dir:       file.is_dir(),
+						encrypted: file.encrypted(),
+					});
+				}
+			}
+			ArchiveKind::Tar(c) => {
+				let mut tar = tar::Archive::new(c.decoder(path)?);
+				for (index, entry) in tar.entries()?.enumerate() {
+					let entry = entry?;
+					let Some(name) = entry.path().ok().and_then(|p| Self::enclosed(&p)) else { continue };
+					let header = entry.header();
+					entries.insert(name, ArchiveEntry {
+						index:     Some(index),
+						size:      header.size()?,
+						mtime:     header.mtime().ok().map(|s| UNIX_EPOCH + Duration::from_secs(s)),
+						mode:      header.mode().ok(),
+						dir:       header.entry_type().is_dir(),
+						encrypted: false,
+					});
+				}
+			}
+			ArchiveKind::SevenZip | ArchiveKind::Rar => {
+				return Err(io::Error::new(io::ErrorKind::Unsupported, "Unsupported archive format"));
+			}
+		}
+
+		let implied: Vec<_> = entries
+			.keys()
+			.flat_map(|p| p.ancestors().skip(1))
+			.filter(|&p| !entries.contains_key(p))
+			.map(ToOwned::to_owned)
+			.collect();
+		for p in implied.into_iter().chain([PathBuf::new()]) {
+			entries.entry(p).or_insert(ArchiveEntry {
+				index:     None,
+				size:      0,
+				mtime:     meta.modified().ok(),
+				mode:      None,
+				dir:       true,
+				encrypted: false,
+			});
+		}
+
+		Ok(Self { kind, len: meta.len(), mtime: meta.modified().ok(), entries })
+	}
+
+	#[inline]
+	pub fn get(&self, path: &Path) -> Option<&ArchiveEntry> { self.entries.get(path) }
+
+	/// The entries directly under the directory `dir`.
+	pub fn children<'a>(
+		&'a self,
+		dir: &'a Path,
+	) -> impl Iterator<Item = (&'a Path, &'a ArchiveEntry)> {
+		self
+			.entries
+			.range::<Path, _>((Bound::Excluded(dir), Bound::Unbounded))
+			.take_while(move |(p, _)| p.starts_with(dir))
+			.filter(move |(p, _)| p.parent() == Some(dir))
+			.map(|(p, e)| (p.as_path(), e))
+	}
+
+	/// The entry at `path` itself and everything under it.
+	pub fn descendants<'a>(
+		&'a self,
+		path: &'a Path,
+	) -> impl Iterator<Item = (&'a Path, &'a ArchiveEntry)> {
+		self
+			.entries
+			.range::<Path, _>((Bound::Included(path), Bound::Unbounded))
+			.take_while(move |(p, _)| p.starts_with(path))
+			.map(|(p, e)| (p.as_path(), e))
+	}
+
+	/// Extracts the entry at `path` of the archive `archive`, recursively if it's
+	/// a directory, to `to`.
+	pub fn extract(&self, archive: &Path, path: &Path, to: &Path) -> io::Result<()> {
+		let targets: Vec<_> =
+			self.descendants(path).map(|(p, e)| (p.strip_prefix(path).unwrap(), e)).collect();
+		if targets.is_empty() {
+			return Err(io::Error::from(io::ErrorKind::NotFound));
+		}
+
+		let mut todo = HashMap::with_capacity(targets.len());
+		for (rel, e) in targets {
+			if through_link(to, rel) {
+				return Err(io::Error::other(format!(
+					"Refusing to extract through a symlink: {}",
+					rel.display()
+				)));
+			}
+
+			let dest = to.join(rel);
+			if e.dir {
+				fs::create_dir_all(&dest)?;
+			} else if let Some(index) = e.index {
+				todo.insert(index, (dest, e));
+			}
+		}
+		if todo.is_empty() {
+			return Ok(());
+		}
+
+		match self.kind {
+			ArchiveKind::Zip => {
+				let mut zip = ZipArchive::new(BufReader::new(File::open(archive)?))?;
+				for (index, (dest, e)) in todo {
+					if e.encrypted {
+						return Err(io::Error::other("Encrypted entries can't be extracted here"));
+					}
+
+					let mut file = zip.by_index(index)?;
+					Self::prepare(&dest)?;
+					io::copy(&mut file, &mut File::create(&dest)?)?;
+
+					#[cfg(unix)]
+					if let Some(mode) = e.mode {
+						use std::os::unix::fs::PermissionsExt;
+						fs::set_permissions(&dest, fs::Permissions::from_mode(mode & 0o777))?;
+					}
+				}
+			}
+			ArchiveKind::Tar(c) => {
+				let mut tar = tar::Archive::new(c.decoder(archive)?);
+				for (index, entry) in tar.entries()?.enumerate() {
+					let Some((dest, _)) = todo.remove(&index) else { continue };
+					let mut entry = entry?;
+					Self::check_link(&entry)?;
+
+					Self::prepare(&dest)?;
+					entry.unpack(&dest)?;
+
+					if todo.is_empty() {
+						break;
+					}
+				}
+			}
+			ArchiveKind::SevenZip | ArchiveKind::Rar => unreachable!(),
+		}
+		Ok(())
+	}
+
+	// Only the symlinks whose target stays inside where they're extracted, and no
+	// hard links, as they're resolved against the current directory.
+	fn check_link<R: io::Read>(entry: &tar::Entry<R>) -> io::Result<()> {
+		let kind = entry.header().entry_type();
+		if kind.is_hard_link() {
+			return Err(io::Error::other("Hard links can't be extracted here"));
+		} else if !kind.is_symlink() {
+			return Ok(());
+		}
+
+		match entry.link_name()? {
+			Some(target) if safe_link(&target) => Ok(()),
+			target => Err(io::Error::other(format!(
+				"Unsafe symlink target in archive: {}",
+				target.unwrap_or_default().display()
+			))),
+		}
+	}
+
+	fn prepare(dest: &Path) -> io::Result<()> {
+		if let Some(parent) = dest.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		match fs::symlink_metadata(dest) {
+			Ok(m) if !m.is_dir() => fs::remove_file(dest),
+			_ => Ok(()),
+		}
+	}
+
+	// Only keeps the paths that stay inside the archive.
+	fn enclosed(path: &Path) -> Option<PathBuf> {
+		let mut buf = PathBuf::new();
+		for c in path.components() {
+			match c {
+				Component::Normal(s) => buf.push(s),
+				Component::CurDir => {}
+				_ => return None,
+			}
+		}
+		Some(buf).filter(|b| !b.as_os_str().is_empty())
+	}
+
+	// Zip stores the local time without a timezone, so it's treated as UTC.
+	fn civil_time(y: u16, m: u8, d: u8, hh: u8, mm: u8, ss: u8) -> Option<SystemTime> {
+		let (y, m) = if m <= 2 { (y as i64 - 1, m as i64 + 9) } else { (y as i64, m as i64 - 3) };
+		let era = y.div_euclid(400);
+		let yoe = y - era * 400;
+		let doy = (153 * m + 2) / 5 + d as i64 - 1;
+		let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+		let days = era * 146097 + doe - 719468;
+
+		let secs = days * 86400 + hh as i64 * 3600 + mm as i64 * 60 + ss as i64;
+		Some(UNIX_EPOCH + Duration::from_secs(u64::try_from(secs).ok()?))
+	}
+}
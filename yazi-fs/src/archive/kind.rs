@@ -0,0 +1,65 @@
+use std::{fs::File, io::{self, BufReader, Read}, path::Path};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ArchiveKind {
+	Zip,
+	Tar(ArchiveCompress),
+	SevenZip,
+	Rar,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ArchiveCompress {
+	None,
+	Gz,
+	Bz2,
+	Xz,
+	Zst,
+}
+
+impl ArchiveKind {
+	const SUFFIXES: [(&'static str, Self); 14] = [
+		(".zip", Self::Zip),
+		(".tar", Self::Tar(ArchiveCompress::None)),
+		(".tar.gz", Self::Tar(ArchiveCompress::Gz)),
+		(".tgz", Self::Tar(ArchiveCompress::Gz)),
+		(".tar.bz2", Self::Tar(ArchiveCompress::Bz2)),
+		(".tbz2", Self::Tar(ArchiveCompress::Bz2)),
+		(".tbz", Self::Tar(ArchiveCompress::Bz2)),
+		(".tar.xz", Self::Tar(ArchiveCompress::Xz)),
+		(".txz", Self::Tar(ArchiveCompress::Xz)),
+		(".tar.zst", Self::Tar(ArchiveCompress::Zst)),
+		(".tzst", Self::Tar(ArchiveCompress::Zst)),
+		(".tar.zstd", Self::Tar(ArchiveCompress::Zst)),
+		(".7z", Self::SevenZip),
+		(".rar", Self::Rar),
+	];
+
+	/// Detects the format from the file name, and returns it along with the name
+	/// stripped of its archive suffix, e.g. `foo` for `foo.tar.gz`.
+	pub fn detect(name: &str) -> Option<(Self, &str)> {
+		let lower = name.to_ascii_lowercase();
+		Self::SUFFIXES
+			.iter()
+			.filter(|(s, _)| lower.len() > s.len() && lower.ends_with(s))
+			.max_by_key(|(s, _)| s.len())
+			.map(|&(s, kind)| (kind, &name[..name.len() - s.len()]))
+	}
+
+	#[inline]
+	pub fn native(self) -> bool { matches!(self, Self::Zip | Self::Tar(_)) }
+}
+
+impl ArchiveCompress {
+	/// Wraps the tarball at `path` in a reader that decompresses it.
+	pub fn decoder(self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+		let file = BufReader::new(File::open(path)?);
+		Ok(match self {
+			Self::None => Box::new(file),
+			Self::Gz => Box::new(flate2::bufread::MultiGzDecoder::new(file)),
+			Self::Bz2 => Box::new(bzip2::bufread::MultiBzDecoder::new(file)),
+			Self::Xz => Box::new(xz2::bufread::XzDecoder::new_multi_decoder(file)),
+			Self::Zst => Box::new(zstd::Decoder::with_buffer(file)?),
+		})
+	}
+}
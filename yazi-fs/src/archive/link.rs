@@ -0,0 +1,35 @@
+use std::{fs, path::{Component, Path}};
+
+/// Whether the target of a symlink in an archive stays inside where it's
+/// extracted, i.e. it's relative and never climbs up with `..`.
+pub fn safe_link(target: &Path) -> bool {
+	target.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Whether `rel` under `root` goes through a symlink, e.g. one extracted
+/// earlier from the same archive, which mustn't be written through.
+///
+/// The last component isn't checked, as it's replaced rather than followed.
+pub fn through_link(root: &Path, rel: &Path) -> bool {
+	let Some(parent) = rel.parent() else { return false };
+
+	let mut p = root.to_owned();
+	parent.components().any(|c| {
+		p.push(c);
+		fs::symlink_metadata(&p).is_ok_and(|m| m.is_symlink())
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_safe_link() {
+		assert!(safe_link(Path::new("a")));
+		assert!(safe_link(Path::new("./a/b")));
+		assert!(!safe_link(Path::new("../a")));
+		assert!(!safe_link(Path::new("a/../../b")));
+		assert!(!safe_link(Path::new("/etc/passwd")));
+	}
+}
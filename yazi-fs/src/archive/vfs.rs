@@ -0,0 +1,129 @@
+use std::{collections::BTreeMap, hash::{DefaultHasher, Hash, Hasher}, io, path::{Path, PathBuf}, sync::Arc};
+
+use parking_lot::Mutex;
+use tokio::{fs, task::spawn_blocking};
+use yazi_config::PREVIEW;
+use yazi_shared::fs::{Cha, ChaKind, File, Url, Urn};
+
+use super::{ArchiveEntry, ArchiveIndex, ArchiveKind};
+
+static INDEXES: Mutex<BTreeMap<PathBuf, Arc<ArchiveIndex>>> = Mutex::new(BTreeMap::new());
+static MATERIALIZED: Mutex<BTreeMap<Url, PathBuf>> = Mutex::new(BTreeMap::new());
+
+/// The virtual file system behind archive URLs, which reads the directories
+/// from the index of the archive instead of the OS.
+///
+/// It's read-only, individual entries can only be copied out of it by
+/// extracting them.
+pub struct Vfs;
+
+impl Vfs {
+	pub async fn cha(url: &Url) -> io::Result<Cha> {
+		let (index, path) = Self::index(url).await?;
+		let entry = index.get(&path).ok_or(io::ErrorKind::NotFound)?;
+		Ok(Self::cha_of(&path, entry))
+	}
+
+	pub async fn read_dir(dir: &Url) -> io::Result<Vec<File>> {
+		let (index, path) = Self::index(dir).await?;
+		if !index.get(&path).is_some_and(|e| e.dir) {
+			return Err(io::ErrorKind::NotFound.into());
+		}
+
+		Ok(
+			index
+				.children(&path)
+				.map(|(p, e)| File {
+					url: dir.join(p.file_name().unwrap()),
+					cha: Self::cha_of(p, e),
+					..Default::default()
+				})
+				.collect(),
+		)
+	}
+
+	/// Extracts the entry at `url`, recursively if it's a directory, to `to`.
+	pub async fn extract(url: &Url, to: &Path) -> io::Result<()> {
+		let (index, path) = Self::index(url).await?;
+		let (archive, to) = (url.archive_split().unwrap().0.to_owned(), to.to_owned());
+		spawn_blocking(move || index.extract(&archive, &path, &to)).await?
+	}
+
+	/// Extracts the entry at `url` into the cache directory, so it can be
+	/// accessed as a regular file, e.g. to preview or open it.
+	pub async fn materialize(url: &Url) -> io::Result<PathBuf> {
+		if let Some(p) = Self::materialized(url) {
+			return Ok(p);
+		}
+
+		let (index, path) = Self::index(url).await?;
+		let (archive, _) = url.archive_split().unwrap();
+
+		let mut h = DefaultHasher::new();
+		(archive, index.len, index.mtime).hash(&mut h);
+		let to = PREVIEW.work_dir().join("archive").join(format!("{:x}", h.finish())).join(&path);
+
+		Self::extract(url, &to).await?;
+		MATERIALIZED.lock().insert(url.clone(), to.clone());
+		Ok(to)
+	}
+
+	/// The path of the entry at `url` in the cache directory, if it's been
+	/// materialized with [`Self::materialize`].
+	#[inline]
+	pub fn materialized(url: &Url) -> Option<PathBuf> { MATERIALIZED.lock().get(url).cloned() }
+
+	/// Whether the regular file at `url` is an archive that can be browsed.
+	#[inline]
+	pub fn browsable(url: &Url) -> bool {
+		url.is_regular()
+			&& url.name().to_str().and_then(ArchiveKind::detect).is_some_and(|(k, _)| k.native())
+	}
+
+	async fn index(url: &Url) -> io::Result<(Arc<ArchiveIndex>, PathBuf)> {
+		let Some((archive, path)) = url.archive_split() else {
+			return Err(io::ErrorKind::InvalidInput.into());
+		};
+
+		let meta = fs::metadata(archive).await?;
+		let fresh = |i: &ArchiveIndex| i.len == meta.len() && i.mtime == meta.modified().ok();
+		if let Some(index) = INDEXES.lock().get(archive).filter(|&i| fresh(i)) {
+			return Ok((index.clone(), path.to_owned()));
+		}
+
+		let Some((kind, _)) =
+			archive.file_name().and_then(|s| s.to_str()).and_then(ArchiveKind::detect)
+		else {
+			return Err(io::ErrorKind::Unsupported.into());
+		};
+
+		let owned = archive.to_owned();
+		let index = Arc::new(spawn_blocking(move || ArchiveIndex::build(&owned, kind)).await??);
+
+		INDEXES.lock().insert(archive.to_owned(), index.clone());
+		MATERIALIZED.lock().retain(|u, _| match u.archive_split() {
+			Some((a, _)) => a != archive,
+			None => true,
+		});
+		Ok((index, path.to_owned()))
+	}
+
+	fn cha_of(path: &Path, entry: &ArchiveEntry) -> Cha {
+		let mut kind = if entry.dir { ChaKind::DIR } else { ChaKind::empty() };
+		if Urn::new(path.file_name().unwrap_or_default()).is_hidden() {
+			kind |= ChaKind::HIDDEN;
+		}
+
+		Cha {
+			kind,
+			len: entry.size,
+			mtime: entry.mtime,
+			#[cfg(unix)]
+			perm: {
+				let mode = entry.mode.map_or(if entry.dir { 0o755 } else { 0o644 }, |m| m & 0o7777);
+				mode as libc::mode_t | if entry.dir { libc::S_IFDIR } else { libc::S_IFREG }
+			},
+			..Default::default()
+		}
+	}
+}
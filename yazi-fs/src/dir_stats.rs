@@ -0,0 +1,152 @@
+use std::{collections::{BTreeMap, VecDeque}, fs::Metadata, path::PathBuf, sync::{Arc, atomic::AtomicBool}, time::{Duration, Instant, SystemTime}};
+
+use parking_lot::{Mutex, RwLock};
+use tokio::fs;
+use yazi_shared::fs::Url;
+
+// The stats of the directories walked, of which the least recently walked ones
+// are dropped beyond `DirStats::CACHED`
+static CACHE: Mutex<BTreeMap<Url, Cached>> = Mutex::new(BTreeMap::new());
+
+struct Cached {
+	mtime:  Option<SystemTime>,
+	stats:  DirStats,
+	walked: Instant,
+}
+
+/// The directories being walked, by the size calculation or the directory
+/// spotter, with the flags to cancel them, so that each one is only walked by
+/// one of them at a time.
+pub static SIZE_LOADING: RwLock<BTreeMap<Url, Arc<AtomicBool>>> = RwLock::new(BTreeMap::new());
+
+/// The aggregate stats of everything under a directory, shared by the size
+/// calculation of directories and the directory spotter, so that the tree is
+/// only walked once until the directory is modified.
+#[derive(Clone, Debug, Default)]
+pub struct DirStats {
+	pub len:     u64,
+	pub files:   u64,
+	pub dirs:    u64,
+	/// The largest files, in descending order of size.
+	pub largest: Vec<(PathBuf, u64)>,
+	/// The most recently modified file.
+	pub newest:  Option<(PathBuf, SystemTime)>,
+}
+
+impl DirStats {
+	const CACHED: usize = 1000;
+	const LARGEST: usize = 5;
+
+	/// The stats of `url` as of when it had the modification time `mtime`.
+	#[inline]
+	pub fn cached(url: &Url, mtime: Option<SystemTime>) -> Option<Self> {
+		CACHE.lock().get(url).filter(|c| c.mtime == mtime).map(|c| c.stats.clone())
+	}
+
+	/// Walks the directory `url`, whose modification time is `mtime`, calling
+	/// `report` with the stats so far once per `every`, or returns `None` as
+	/// soon as `canceled` returns true.
+	pub async fn walk(
+		url: &Url,
+		mtime: Option<SystemTime>,
+		every: Duration,
+		canceled: impl Fn() -> bool,
+		mut report: impl FnMut(&Self),
+	) -> Option<Self> {
+		let (mut stats, mut last) = (Self::default(), Instant::now());
+		let mut stack = VecDeque::from([url.to_path_buf()]);
+		while let Some(dir) = stack.pop_front() {
+			let Ok(mut it) = fs::read_dir(dir).await else { continue };
+			while let Ok(Some(entry)) = it.next_entry().await {
+				if canceled() {
+					return None;
+				}
+
+				let Ok(meta) = entry.metadata().await else { continue };
+				if meta.is_dir() {
+					stats.dirs += 1;
+					stack.push_back(entry.path());
+				} else {
+					stats.add_file(entry.path(), &meta);
+				}
+
+				if last.elapsed() >= every {
+					last = Instant::now();
+					report(&stats);
+				}
+			}
+		}
+
+		Self::cache(url, mtime, &stats);
+		Some(stats)
+	}
+
+	fn cache(url: &Url, mtime: Option<SystemTime>, stats: &Self) {
+		let mut cache = CACHE.lock();
+		if cache.len() >= Self::CACHED && !cache.contains_key(url) {
+			let oldest = cache.iter().min_by_key(|(_, c)| c.walked).map(|(u, _)| u.clone());
+			oldest.map(|u| cache.remove(&u));
+		}
+		cache.insert(url.clone(), Cached { mtime, stats: stats.clone(), walked: Instant::now() });
+	}
+
+	/// The stats of all `urls` together, each being a file or a directory, with
+	/// `false` if walking them took longer than `limit`, in which case they're
+	/// only the ones counted so far.
+	pub async fn total(urls: &[Url], limit: Duration) -> (Self, bool) {
+		let deadline = Instant::now() + limit;
+		let mut total = Self::default();
+		for url in urls {
+			let Ok(meta) = fs::symlink_metadata(url).await else { continue };
+			if !meta.is_dir() {
+				total.len += meta.len();
+				total.files += 1;
+				continue;
+			}
+
+			let mtime = meta.modified().ok();
+			let mut partial = Self::default();
+			let stats = match Self::cached(url, mtime) {
+				Some(s) => Some(s),
+				None => {
+					let every = Duration::from_millis(10);
+					let report = |s: &Self| partial = s.clone();
+					Self::walk(url, mtime, every, || Instant::now() >= deadline, report).await
+				}
+			};
+
+			total.dirs += 1;
+			let Some(stats) = stats else {
+				total.merge(&partial);
+				return (total, false);
+			};
+			total.merge(&stats);
+		}
+		(total, true)
+	}
+
+	#[inline]
+	fn merge(&mut self, other: &Self) {
+		self.len += other.len;
+		self.files += other.files;
+		self.dirs += other.dirs;
+	}
+
+	fn add_file(&mut self, path: PathBuf, meta: &Metadata) {
+		let len = meta.len();
+		self.len += len;
+		self.files += 1;
+
+		match (&self.newest, meta.modified()) {
+			(Some((_, t)), Ok(mtime)) if *t >= mtime => {}
+			(_, Ok(mtime)) => self.newest = Some((path.clone(), mtime)),
+			(_, Err(_)) => {}
+		}
+
+		if self.largest.len() < Self::LARGEST || self.largest.last().is_some_and(|&(_, l)| l < len) {
+			let i = self.largest.partition_point(|&(_, l)| l >= len);
+			self.largest.insert(i, (path, len));
+			self.largest.truncate(Self::LARGEST);
+		}
+	}
+}
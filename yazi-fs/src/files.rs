@@ -1,14 +1,18 @@
-use std::{collections::{HashMap, HashSet}, mem, ops::Deref, sync::atomic::Ordering};
+use std::{collections::{HashMap, HashSet}, fs::{FileType, Metadata}, future::Future, mem, ops::Deref, path::Path, sync::atomic::Ordering, time::Duration};
 
-use tokio::{fs::{self, DirEntry}, select, sync::mpsc::{self, UnboundedReceiver}};
+use tokio::{fs::DirEntry, select, sync::mpsc::{self, UnboundedReceiver}, time::{Instant, timeout, timeout_at}};
 use yazi_config::{MANAGER, manager::SortBy};
-use yazi_shared::fs::{Cha, FILES_TICKET, File, FilesOp, Url, Urn, UrnBuf, maybe_exists};
+use yazi_shared::fs::{Cha, FILES_TICKET, File, FilesOp, Url, Urn, UrnBuf, long, maybe_exists};
 
-use super::{FilesSorter, Filter};
+use super::{FilesSorter, Filter, SortKeys};
+use crate::archive::Vfs;
 
 pub struct Files {
 	hidden:       Vec<File>,
 	items:        Vec<File>,
+	// The leading `items` that are in order, the rest are merged into them on the
+	// next `catchup_revision()`
+	sorted:       usize,
 	ticket:       u64,
 	version:      u64,
 	pub revision: u64,
@@ -16,7 +20,11 @@ pub struct Files {
 	pub sizes: HashMap<UrnBuf, u64>,
 
 	sorter:      FilesSorter,
+	keys:        SortKeys,
 	filter:      Option<Filter>,
+	// Kept visible even if it's hidden or the filter doesn't match it, e.g. the
+	// CWD in the parent, or a file revealed in the CWD
+	pinned:      Option<UrnBuf>,
 	show_hidden: bool,
 }
 
@@ -25,6 +33,7 @@ impl Default for Files {
 		Self {
 			items:    Default::default(),
 			hidden:   Default::default(),
+			sorted:   Default::default(),
 			ticket:   Default::default(),
 			version:  Default::default(),
 			revision: Default::default(),
@@ -32,8 +41,10 @@ impl Default for Files {
 			sizes: Default::default(),
 
 			sorter:      Default::default(),
+			keys:        Default::default(),
 			filter:      Default::default(),
-			show_hidden: MANAGER.show_hidden,
+			pinned:      Default::default(),
+			show_hidden: MANAGER.show_hidden.is_shown(),
 		}
 	}
 }
@@ -45,50 +56,66 @@ impl Deref for Files {
 }
 
 impl Files {
-	pub async fn from_dir(dir: &Url) -> std::io::Result<UnboundedReceiver<File>> {
-		let mut it = fs::read_dir(dir).await?;
+	/// Reads the directory `dir`, sending the files as they're read, and then
+	/// the error if the reading stops partway through.
+	pub async fn from_dir(dir: &Url) -> std::io::Result<UnboundedReceiver<std::io::Result<File>>> {
+		if dir.is_archive() {
+			let (tx, rx) = mpsc::unbounded_channel();
+			Vfs::read_dir(dir).await?.into_iter().for_each(|f| _ = tx.send(Ok(f)));
+			return Ok(rx);
+		}
+
+		let mut it = long::read_dir(dir).await?;
 		let (tx, rx) = mpsc::unbounded_channel();
 
+		let dir = dir.to_path();
 		tokio::spawn(async move {
-			while let Ok(Some(item)) = it.next_entry().await {
+			loop {
+				let item = match it.next_entry().await {
+					Ok(Some(item)) => item,
+					Ok(None) => break,
+					Err(e) => {
+						_ = tx.send(Err(e));
+						break;
+					}
+				};
+
+				let url = Url::from(dir.join(item.file_name()));
 				select! {
 					_ = tx.closed() => break,
-					result = item.metadata() => {
-						let url = Url::from(item.path());
-						_ = tx.send(match result {
-							Ok(meta) => File::from_meta(url, meta).await,
-							Err(_) => File::from_dummy(url, item.file_type().await.ok())
-						});
-					}
+					file = Self::from_entry(url, &item) => { _ = tx.send(Ok(file)); }
 				}
 			}
 		});
 		Ok(rx)
 	}
 
+	/// Reads the directory `dir` at once, which fails as a whole if the reading
+	/// stops partway through, so the files read before aren't taken for all.
 	pub async fn from_dir_bulk(dir: &Url) -> std::io::Result<Vec<File>> {
-		let mut it = fs::read_dir(dir).await?;
+		if dir.is_archive() {
+			return Vfs::read_dir(dir).await;
+		}
+
+		let mut it = long::read_dir(dir).await?;
+		let dir = dir.to_path();
 		let mut entries = Vec::with_capacity(5000);
-		while let Ok(Some(entry)) = it.next_entry().await {
+		while let Some(entry) = it.next_entry().await? {
 			entries.push(entry);
 		}
 
 		let (first, rest) = entries.split_at(entries.len() / 3);
 		let (second, third) = rest.split_at(entries.len() / 3);
-		async fn go(entries: &[DirEntry]) -> Vec<File> {
+		async fn go(dir: &Path, entries: &[DirEntry]) -> Vec<File> {
 			let mut files = Vec::with_capacity(entries.len() / 3 + 1);
 			for entry in entries {
-				let url = Url::from(entry.path());
-				files.push(match entry.metadata().await {
-					Ok(meta) => File::from_meta(url, meta).await,
-					Err(_) => File::from_dummy(url, entry.file_type().await.ok()),
-				});
+				files.push(Files::from_entry(Url::from(dir.join(entry.file_name())), entry).await);
 			}
 			files
 		}
 
 		Ok(
-			futures::future::join_all([go(first), go(second), go(third)])
+			futures::future::join_all([go(&dir, first), go(&dir, second), go(&dir, third)])
 				.await
 				.into_iter()
 				.flatten()
@@ -96,8 +123,38 @@ impl Files {
 		)
 	}
 
+	// The file of a directory entry, which is marked unavailable if reading its
+	// metadata takes longer than `stat_timeout`
+	async fn from_entry(url: Url, entry: &DirEntry) -> File {
+		let dur = MANAGER.stat_timeout();
+		let ft = timeout(dur, entry.file_type()).await.ok().and_then(Result::ok);
+		Self::from_meta_within(url, ft, entry.metadata(), dur).await
+	}
+
+	// Each entry is given up on its own, keeping the kind the directory tells
+	// for it, so one hanging on a slow mount doesn't take the rest with it
+	async fn from_meta_within(
+		url: Url,
+		ft: Option<FileType>,
+		meta: impl Future<Output = std::io::Result<Metadata>>,
+		dur: Duration,
+	) -> File {
+		let file = async {
+			match meta.await {
+				Ok(meta) => File::from_meta(url.clone(), meta).await,
+				Err(_) => File::from_dummy(url.clone(), ft),
+			}
+		};
+		match timeout(dur, file).await {
+			Ok(file) => file,
+			Err(_) => File::from_unavailable(url, ft),
+		}
+	}
+
 	pub async fn assert_stale(cwd: &Url, cha: Cha) -> Option<Cha> {
-		match fs::metadata(cwd).await.map(Cha::from) {
+		let result =
+			if cwd.is_archive() { Vfs::cha(cwd).await } else { long::metadata(cwd).await.map(Cha::from) };
+		match result {
 			Ok(c) if !c.is_dir() => {
 				// TODO: use `ErrorKind::NotADirectory` instead once it gets stabilized
 				FilesOp::IOErr(cwd.clone(), std::io::ErrorKind::AlreadyExists).emit();
@@ -105,7 +162,7 @@ impl Files {
 			Ok(c) if c.hits(cha) => {}
 			Ok(c) => return Some(c),
 			Err(e) => {
-				if maybe_exists(cwd).await {
+				if cwd.is_archive() || maybe_exists(cwd).await {
 					FilesOp::IOErr(cwd.clone(), e.kind()).emit();
 				} else if let Some((p, n)) = cwd.pair() {
 					FilesOp::Deleting(p, HashSet::from_iter([n])).emit();
@@ -114,6 +171,59 @@ impl Files {
 		}
 		None
 	}
+
+	/// Reads the directory `cwd`, whose latest `cha` is known, emitting the files
+	/// with `FilesOp::Part` as they're read, and then `FilesOp::Done` along with
+	/// the error if the reading stopped partway through.
+	pub async fn from_dir_chunked(cwd: Url, cha: Cha) {
+		let rx = match Self::from_dir(&cwd).await {
+			Ok(rx) => rx,
+			Err(e) => return FilesOp::IOErr(cwd, e.kind()).emit(),
+		};
+
+		let ticket = FilesOp::prepare(&cwd);
+		let err = Self::chunks(rx, Duration::from_millis(500), |files| {
+			FilesOp::Part(cwd.clone(), files, ticket).emit();
+		})
+		.await;
+		FilesOp::Done(cwd, cha, ticket, err).emit();
+	}
+
+	// Show the first few files read shortly, so large or slow directories don't
+	// look frozen, and batch the rest once per `every` to avoid updating for every
+	// few files. Returns the error the reading stopped with, if any
+	async fn chunks(
+		mut rx: UnboundedReceiver<std::io::Result<File>>,
+		every: Duration,
+		mut cb: impl FnMut(Vec<File>),
+	) -> Option<std::io::ErrorKind> {
+		let (mut chunk, mut limit, mut err) = (vec![], 100, None);
+		let mut deadline = Instant::now() + Duration::from_millis(50);
+		loop {
+			match timeout_at(deadline, rx.recv()).await {
+				Ok(Some(Ok(file))) => {
+					chunk.push(file);
+					if chunk.len() < limit {
+						continue;
+					}
+				}
+				Ok(Some(Err(e))) => {
+					err = Some(e.kind());
+					break;
+				}
+				Ok(None) => break,
+				Err(_) => {}
+			}
+			if !chunk.is_empty() {
+				cb(mem::take(&mut chunk));
+			}
+			(limit, deadline) = (50000, Instant::now() + every);
+		}
+		if !chunk.is_empty() {
+			cb(chunk);
+		}
+		err
+	}
 }
 
 impl Files {
@@ -121,6 +231,7 @@ impl Files {
 		self.ticket = FILES_TICKET.fetch_add(1, Ordering::Relaxed);
 
 		(self.hidden, self.items) = self.split_files(files);
+		self.sorted = 0;
 		if !self.items.is_empty() {
 			self.revision += 1;
 		}
@@ -144,6 +255,7 @@ impl Files {
 
 		self.ticket = ticket;
 		self.hidden.clear();
+		self.sorted = 0;
 		if !self.items.is_empty() {
 			self.revision += 1;
 			self.items.clear();
@@ -156,6 +268,7 @@ impl Files {
 		}
 
 		if self.sorter.by == SortBy::Size {
+			self.sorted = 0;
 			self.revision += 1;
 		}
 		self.sizes.extend(sizes);
@@ -165,6 +278,8 @@ impl Files {
 		self.ticket = FILES_TICKET.fetch_add(1, Ordering::Relaxed);
 		self.hidden.clear();
 		self.items.clear();
+		self.keys.clear();
+		self.sorted = 0;
 	}
 
 	pub fn update_creating(&mut self, files: Vec<File>) {
@@ -201,6 +316,9 @@ impl Files {
 		if urns.is_empty() {
 			return;
 		}
+		for u in &urns {
+			self.keys.remove(u);
+		}
 
 		macro_rules! go {
 			($dist:expr, $src:expr, $inc:literal) => {
@@ -212,19 +330,23 @@ impl Files {
 			};
 		}
 
-		let (mut hidden, mut items) = if let Some(filter) = &self.filter {
+		let (mut hidden, mut items) = if self.filter.as_ref().is_some_and(|f| f.tagged().is_some()) {
+			// Whether they're filtered out can't be told without the files, so look in both
+			(urns.clone(), urns)
+		} else if let Some(filter) = &self.filter {
 			urns.into_iter().partition(|u| {
-				(!self.show_hidden && u.as_urn().is_hidden())
-					|| !u.as_urn().name().is_some_and(|s| filter.matches(s))
+				self.pinned.as_ref() != Some(u)
+					&& ((!self.show_hidden && u.as_urn().is_hidden())
+						|| u.as_urn().name().and_then(|s| filter.matches_name(s)) != Some(true))
 			})
 		} else if self.show_hidden {
 			(HashSet::new(), urns)
 		} else {
-			urns.into_iter().partition(|u| u.as_urn().is_hidden())
+			urns.into_iter().partition(|u| u.as_urn().is_hidden() && self.pinned.as_ref() != Some(u))
 		};
 
 		if !items.is_empty() {
-			go!(self.items, items, 1);
+			self.retain_items(|f| !items.remove(f.urn()));
 		}
 		if !hidden.is_empty() {
 			go!(self.hidden, hidden, 0);
@@ -243,8 +365,11 @@ impl Files {
 			};
 		}
 
+		for u in &urns {
+			self.keys.remove(u);
+		}
 		if !urns.is_empty() {
-			go!(self.items, urns, 1);
+			self.retain_items(|f| !urns.remove(f.urn()));
 		}
 		if !urns.is_empty() {
 			go!(self.hidden, urns, 0);
@@ -260,12 +385,16 @@ impl Files {
 		}
 
 		macro_rules! go {
-			($dist:expr, $src:expr, $inc:literal) => {
-				let mut b = true;
+			($dist:expr, $src:expr, $inc:literal) => {{
+				let mut changed = vec![];
 				for i in 0..$dist.len() {
 					if let Some(f) = $src.remove($dist[i].urn()) {
-						b = b && $dist[i].cha.hits(f.cha);
-						b = b && $dist[i].urn() == f.urn();
+						if $dist[i].urn() != f.urn() {
+							self.keys.remove($dist[i].urn());
+							changed.push(i);
+						} else if !$dist[i].cha.hits(f.cha) {
+							changed.push(i);
+						}
 
 						$dist[i] = f;
 						if $src.is_empty() {
@@ -273,22 +402,20 @@ impl Files {
 						}
 					}
 				}
-				self.revision += if b { 0 } else { $inc };
-			};
+				self.revision += if changed.is_empty() { 0 } else { $inc };
+				changed
+			}};
 		}
 
-		let (mut hidden, mut items) = if let Some(filter) = &self.filter {
-			files
-				.into_iter()
-				.partition(|(_, f)| (f.is_hidden() && !self.show_hidden) || !filter.matches(f.name()))
-		} else if self.show_hidden {
+		let (mut hidden, mut items) = if self.filter.is_none() && self.show_hidden {
 			(HashMap::new(), files)
 		} else {
-			files.into_iter().partition(|(_, f)| f.is_hidden())
+			files.into_iter().partition(|(_, f)| self.concealed(f))
 		};
 
 		if !items.is_empty() {
-			go!(self.items, items, 1);
+			let changed = go!(self.items, items, 1);
+			self.unsort_items(changed);
 		}
 		if !hidden.is_empty() {
 			go!(self.hidden, hidden, 0);
@@ -325,21 +452,74 @@ impl Files {
 		}
 
 		self.version = self.revision;
-		self.sorter.sort(&mut self.items, &self.sizes);
+		self.sort_items();
 		true
 	}
 
+	#[inline]
+	fn sort_items(&mut self) {
+		self.sorter.sort(&mut self.items, self.sorted, &self.sizes, &mut self.keys);
+		self.sorted = self.items.len();
+	}
+
+	// Removing items keeps the rest in order, only the sorted ones are recounted.
+	fn retain_items(&mut self, mut f: impl FnMut(&File) -> bool) {
+		let (len, mut i, mut sorted) = (self.items.len(), 0, self.sorted);
+		self.items.retain(|file| {
+			let keep = f(file);
+			if !keep && i < self.sorted {
+				sorted -= 1;
+			}
+			i += 1;
+			keep
+		});
+
+		self.sorted = sorted;
+		if self.items.len() != len {
+			self.revision += 1;
+		}
+	}
+
+	// Moves the sorted items at `indices`, which have been changed in place and
+	// may be out of order now, after the sorted ones, to be merged again.
+	fn unsort_items(&mut self, mut indices: Vec<usize>) {
+		indices.retain(|&i| i < self.sorted);
+		if indices.is_empty() {
+			return;
+		}
+
+		let mut it = indices.iter().peekable();
+		let (mut kept, mut moved) = (Vec::with_capacity(self.items.len()), vec![]);
+		for (i, f) in mem::take(&mut self.items).into_iter().enumerate() {
+			if it.next_if(|&&j| j == i).is_some() { moved.push(f) } else { kept.push(f) }
+		}
+
+		self.sorted -= moved.len();
+		kept.extend(moved);
+		self.items = kept;
+	}
+
 	fn split_files(&self, files: impl IntoIterator<Item = File>) -> (Vec<File>, Vec<File>) {
-		if let Some(filter) = &self.filter {
-			files
-				.into_iter()
-				.partition(|f| (f.is_hidden() && !self.show_hidden) || !filter.matches(f.name()))
-		} else if self.show_hidden {
+		if self.filter.is_none() && self.show_hidden {
 			(vec![], files.into_iter().collect())
 		} else {
-			files.into_iter().partition(|f| f.is_hidden())
+			files.into_iter().partition(|f| self.concealed(f))
 		}
 	}
+
+	// Whether `file` goes to the hidden ones, unless it's pinned
+	#[inline]
+	fn concealed(&self, file: &File) -> bool { self.unwanted(file) && !self.is_pinned(file) }
+
+	// Whether `file` is hidden or the filter doesn't match it
+	#[inline]
+	fn unwanted(&self, file: &File) -> bool {
+		(file.is_hidden() && !self.show_hidden)
+			|| self.filter.as_ref().is_some_and(|f| !f.matches(file))
+	}
+
+	#[inline]
+	fn is_pinned(&self, file: &File) -> bool { self.pinned.as_ref().is_some_and(|u| u == file.urn()) }
 }
 
 impl Files {
@@ -347,6 +527,22 @@ impl Files {
 	#[inline]
 	pub fn position(&self, urn: &Urn) -> Option<usize> { self.iter().position(|f| urn == f.urn()) }
 
+	/// Like [`Self::position()`], but searching outward from `hint`, as the file
+	/// is usually close to where it was, e.g. the hovered one after an update, so
+	/// that large directories aren't scanned in full.
+	pub fn position_near(&self, urn: &Urn, hint: usize) -> Option<usize> {
+		let (before, after) = self.split_at(hint.min(self.len()));
+		let (mut back, mut forth) = (before.iter().rev().enumerate(), after.iter().enumerate());
+		loop {
+			match (forth.next(), back.next()) {
+				(None, None) => return None,
+				(Some((i, f)), _) if urn == f.urn() => return Some(before.len() + i),
+				(_, Some((i, f))) if urn == f.urn() => return Some(before.len() - 1 - i),
+				_ => {}
+			}
+		}
+	}
+
 	// --- Ticket
 	#[inline]
 	pub fn ticket(&self) -> u64 { self.ticket }
@@ -357,7 +553,11 @@ impl Files {
 
 	pub fn set_sorter(&mut self, sorter: FilesSorter) {
 		if self.sorter != sorter {
+			if !self.sorter.same_keys(&sorter) {
+				self.keys.clear();
+			}
 			self.sorter = sorter;
+			self.sorted = 0;
 			self.revision += 1;
 		}
 	}
@@ -379,14 +579,78 @@ impl Files {
 			self.hidden = hidden;
 			if !items.is_empty() {
 				self.items.extend(items);
-				self.sorter.sort(&mut self.items, &self.sizes);
+				self.sort_items();
 			}
 			return true;
 		}
 
-		let it = mem::take(&mut self.items).into_iter().chain(mem::take(&mut self.hidden));
-		(self.hidden, self.items) = self.split_files(it);
-		self.sorter.sort(&mut self.items, &self.sizes);
+		// The items that still match keep their order, so only the ones that
+		// start matching need to be sorted and merged into them
+		let (mut items, old) = (mem::take(&mut self.items), mem::take(&mut self.hidden));
+		let tail = items.split_off(self.sorted);
+
+		let (hidden, items) = self.split_files(items);
+		let (more, tail) = self.split_files(tail);
+		let (rest, revealed) = self.split_files(old);
+
+		self.sorted = items.len();
+		self.items = items.into_iter().chain(tail).chain(revealed).collect();
+		self.hidden = hidden.into_iter().chain(more).chain(rest).collect();
+
+		self.sort_items();
+		true
+	}
+
+	/// Applies the filter again, for what it matches may have changed without
+	/// the files being, i.e. their tags, returns whether there's one.
+	pub fn refilter(&mut self) -> bool {
+		let Some(filter) = self.filter.take() else { return false };
+		self.set_filter(Some(filter))
+	}
+
+	/// Whether `file` is only shown because it's pinned, which is hidden or the
+	/// filter doesn't match.
+	#[inline]
+	pub fn is_pinned_unmatched(&self, file: &File) -> bool {
+		self.unwanted(file) && self.is_pinned(file)
+	}
+
+	/// How many of the files are shown for matching, i.e. all but the pinned one
+	/// if it's only kept visible.
+	pub fn matched(&self) -> usize {
+		let unmatched = self.pinned.is_some() && self.items.iter().any(|f| self.is_pinned_unmatched(f));
+		self.items.len() - unmatched as usize
+	}
+
+	// --- Pinned
+	/// Pins the file named `urn`, or none, returns whether it's changed.
+	pub fn set_pinned(&mut self, urn: Option<UrnBuf>) -> bool {
+		if self.pinned == urn {
+			return false;
+		}
+
+		let old = mem::replace(&mut self.pinned, urn);
+		if let Some(filter) = self.filter.take() {
+			self.set_filter(Some(filter));
+			return true;
+		} else if self.show_hidden {
+			return true;
+		}
+
+		// Without a filter, only the old and the new pinned files can move, if
+		// they're hidden
+		let unpinned = old.and_then(|u| self.items.iter().position(|f| &u == f.urn() && f.is_hidden()));
+		if let Some(i) = unpinned {
+			if i < self.sorted {
+				self.sorted -= 1;
+			}
+			self.hidden.push(self.items.remove(i));
+			self.revision += 1;
+		}
+		if let Some(i) = self.hidden.iter().position(|f| self.is_pinned(f)) {
+			self.items.push(self.hidden.swap_remove(i));
+			self.revision += 1;
+		}
 		true
 	}
 
@@ -414,3 +678,281 @@ impl Files {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::time::{Duration, SystemTime};
+
+	use yazi_shared::{LcgRng, fs::ChaKind};
+
+	use super::*;
+	use crate::FilterCase;
+
+	// A synthetic directory with 100k entries, some of them directories or hidden
+	fn synthetic() -> Vec<File> {
+		let mut rng = LcgRng::default();
+		(0..100_000)
+			.map(|i| {
+				let n = rng.next().unwrap();
+				let (dir, hidden) = (n % 10 == 0, n % 7 == 0);
+				let name =
+					format!("{}file{}{}", if hidden { "." } else { "" }, i, [".txt", ".rs", ""][i % 3]);
+
+				let mut kind = if dir { ChaKind::DIR } else { ChaKind::empty() };
+				if hidden {
+					kind |= ChaKind::HIDDEN;
+				}
+				File {
+					url: Url::from(format!("/synthetic/{name}")),
+					cha: Cha {
+						kind,
+						len: n % 4096,
+						mtime: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(n % 1_000_000)),
+						..Default::default()
+					},
+					..Default::default()
+				}
+			})
+			.collect()
+	}
+
+	fn files(by: SortBy) -> Files {
+		Files {
+			hidden:   vec![],
+			items:    vec![],
+			sorted:   0,
+			ticket:   0,
+			version:  0,
+			revision: 0,
+
+			sizes: Default::default(),
+
+			sorter:      FilesSorter { by, dir_first: true, ..Default::default() },
+			keys:        Default::default(),
+			filter:      None,
+			pinned:      None,
+			show_hidden: false,
+		}
+	}
+
+	fn urls(files: &Files) -> Vec<&Url> { files.iter().map(|f| &f.url).collect() }
+
+	#[tokio::test]
+	async fn test_chunked_loading() {
+		let entries: Vec<_> = synthetic().into_iter().take(3_000).collect();
+
+		// A slow reader, which stalls every now and then
+		let (tx, rx) = mpsc::unbounded_channel();
+		let sent = entries.clone();
+		tokio::spawn(async move {
+			for (i, f) in sent.into_iter().enumerate() {
+				if i % 500 == 0 {
+					tokio::time::sleep(Duration::from_millis(30)).await;
+				}
+				tx.send(Ok(f)).ok();
+			}
+		});
+
+		let mut chunks = vec![];
+		let err = Files::chunks(rx, Duration::from_millis(10), |c| chunks.push(c)).await;
+		assert_eq!(err, None);
+		assert!(chunks.len() > 2);
+		assert!(chunks[0].len() <= 100);
+
+		let mut full = files(SortBy::Natural);
+		full.set_show_hidden(true);
+		full.update_full(entries);
+		full.catchup_revision();
+
+		let mut part = files(SortBy::Natural);
+		part.set_show_hidden(true);
+		let ticket = FILES_TICKET.fetch_add(1, Ordering::Relaxed);
+		part.update_part(vec![], ticket);
+		for chunk in chunks {
+			part.update_part(chunk, ticket);
+			part.catchup_revision();
+		}
+		assert_eq!(urls(&full), urls(&part));
+	}
+
+	#[tokio::test]
+	async fn test_slow_getattr() {
+		let dir = std::env::temp_dir();
+		let (ft, meta) = (std::fs::metadata(&dir).unwrap().file_type(), std::fs::metadata(&dir));
+		let slow = async {
+			tokio::time::sleep(Duration::from_secs(5)).await;
+			std::fs::metadata(&dir)
+		};
+
+		// The one hanging is marked unavailable, but still known as a directory
+		let dur = Duration::from_millis(50);
+		let file = Files::from_meta_within(Url::from(&dir), Some(ft), slow, dur).await;
+		assert!(file.is_unavailable());
+		assert!(file.is_dir());
+
+		// And the ones after it are read as usual
+		let file = Files::from_meta_within(Url::from(&dir), Some(ft), async { meta }, dur).await;
+		assert!(!file.is_unavailable());
+		assert!(file.is_dir());
+	}
+
+	#[tokio::test]
+	async fn test_truncated_loading() {
+		let (tx, rx) = mpsc::unbounded_channel();
+		synthetic().into_iter().take(150).for_each(|f| _ = tx.send(Ok(f)));
+		tx.send(Err(std::io::ErrorKind::PermissionDenied.into())).ok();
+		tx.send(Ok(synthetic().pop().unwrap())).ok();
+
+		// Those read before the error are kept, and nothing after it
+		let mut read = 0;
+		let err = Files::chunks(rx, Duration::from_millis(10), |c| read += c.len()).await;
+		assert_eq!(err, Some(std::io::ErrorKind::PermissionDenied));
+		assert_eq!(read, 150);
+	}
+
+	#[test]
+	fn test_incremental_sort() {
+		let entries = synthetic();
+		for by in [SortBy::Natural, SortBy::Mtime, SortBy::Extension] {
+			let mut full = files(by);
+			full.update_full(entries.clone());
+			full.catchup_revision();
+
+			// Loaded in chunks, like `Folder::update()` with `FilesOp::Part`
+			let mut part = files(by);
+			let ticket = FILES_TICKET.fetch_add(1, Ordering::Relaxed);
+			part.update_part(vec![], ticket);
+			for chunk in entries.chunks(5_000) {
+				part.update_part(chunk.to_vec(), ticket);
+				part.catchup_revision();
+			}
+			assert_eq!(urls(&full), urls(&part), "{by:?}");
+
+			// Narrowed and then widened while typing
+			let filter = |s| Some(Filter::new(s, FilterCase::Sensitive).unwrap());
+			full.set_filter(filter("1"));
+			part.set_filter(filter("file1"));
+			part.set_filter(filter("1"));
+			assert_eq!(urls(&full), urls(&part), "{by:?}");
+
+			part.set_show_hidden(true);
+			part.set_filter(None);
+			part.catchup_revision();
+			let mut fresh = files(by);
+			fresh.set_show_hidden(true);
+			fresh.update_full(entries.clone());
+			fresh.catchup_revision();
+			assert_eq!(urls(&fresh), urls(&part), "{by:?}");
+
+			// A few of them are modified, and a lot more
+			for n in [3, 3_000] {
+				let changed: HashMap<_, _> = part
+					.iter()
+					.step_by(part.len() / n)
+					.map(|f| {
+						let mut f = f.clone();
+						f.cha.mtime = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(f.cha.len * 7));
+						(f.urn_owned(), f)
+					})
+					.collect();
+				part.update_upserting(changed.clone());
+				part.catchup_revision();
+				fresh.update_full(part.iter().cloned().collect());
+				fresh.catchup_revision();
+				assert_eq!(urls(&fresh), urls(&part), "{by:?}");
+			}
+		}
+	}
+
+	#[test]
+	fn test_renamed_keys() {
+		let mut files = files(SortBy::Natural);
+		files.update_full(synthetic());
+		files.catchup_revision();
+
+		// Every 100th file is renamed, e.g. `file42.rs` to `0-file42.rs`
+		let renamed: HashMap<_, _> = files
+			.iter()
+			.step_by(100)
+			.map(|f| {
+				let name = format!("0-{}", f.name().to_string_lossy());
+				(f.urn_owned(), File { url: f.url.parent_url().unwrap().join(name), ..f.clone() })
+			})
+			.collect();
+		files.update_upserting(renamed.clone());
+		files.catchup_revision();
+		assert_eq!(files.keys.len(), files.len());
+		assert!(
+			renamed.iter().all(|(u, f)| !files.keys.contains_key(u) && files.keys.contains_key(f.urn()))
+		);
+
+		// Reversed with the keys kept
+		let mut fresh = self::files(SortBy::Natural);
+		fresh.update_full(files.iter().cloned().collect());
+		for f in [&mut files, &mut fresh] {
+			f.set_sorter(FilesSorter { reverse: true, ..*f.sorter() });
+			f.catchup_revision();
+		}
+		assert_eq!(urls(&fresh), urls(&files));
+	}
+
+	#[test]
+	fn test_pinned() {
+		let mut files = files(SortBy::Natural);
+		files.update_full(synthetic());
+		files.catchup_revision();
+
+		let pinned = files.iter().find(|f| !f.name().to_string_lossy().contains('7')).unwrap().clone();
+		files.set_filter(Some(Filter::new("7", FilterCase::Sensitive).unwrap()));
+		assert!(files.position(pinned.urn()).is_none());
+
+		files.set_pinned(Some(pinned.urn_owned()));
+		assert!(files.position(pinned.urn()).is_some());
+		assert!(files.is_pinned_unmatched(&pinned));
+
+		files.set_pinned(None);
+		assert!(files.position(pinned.urn()).is_none());
+	}
+
+	#[test]
+	fn test_pinned_hidden() {
+		let mut files = files(SortBy::Natural);
+		let entries = synthetic();
+		let pinned = entries.iter().find(|f| f.is_hidden()).unwrap().clone();
+
+		// Pinned before the files arrive, like a file revealed on startup
+		files.set_pinned(Some(pinned.urn_owned()));
+		files.update_full(entries);
+		files.catchup_revision();
+		assert!(files.position(pinned.urn()).is_some());
+		assert!(files.is_pinned_unmatched(&pinned));
+		assert_eq!(files.iter().filter(|f| f.is_hidden()).count(), 1);
+
+		files.update_deleting(HashSet::from_iter([pinned.urn_owned()]));
+		assert!(files.position(pinned.urn()).is_none());
+		files.update_creating(vec![pinned.clone()]);
+		files.catchup_revision();
+		assert!(files.position(pinned.urn()).is_some());
+
+		files.set_pinned(None);
+		files.catchup_revision();
+		assert!(files.position(pinned.urn()).is_none());
+		files.set_pinned(Some(pinned.urn_owned()));
+		files.catchup_revision();
+		assert!(files.position(pinned.urn()).is_some());
+	}
+
+	#[test]
+	fn test_position_near() {
+		let mut files = files(SortBy::Natural);
+		files.set_show_hidden(true);
+		files.update_full(synthetic().into_iter().take(1_000).collect());
+		files.catchup_revision();
+
+		for (i, hint) in [(0, 0), (0, 999), (500, 0), (500, 501), (999, 10), (999, 5_000)] {
+			let urn = files[i].urn_owned();
+			assert_eq!(files.position_near(urn.as_urn(), hint), Some(i));
+		}
+		assert_eq!(files.position_near(Url::from("/synthetic/gone").urn(), 500), None);
+	}
+}
@@ -2,11 +2,16 @@ use std::{ffi::OsStr, fmt::Display, ops::Range};
 
 use anyhow::Result;
 use regex::bytes::{Regex, RegexBuilder};
-use yazi_shared::event::Cmd;
+use yazi_config::manager::FindCase;
+use yazi_shared::{event::Cmd, fs::File};
 
+use crate::TAGS;
+
+#[derive(Clone)]
 pub struct Filter {
 	raw:   String,
-	regex: Regex,
+	case:  FilterCase,
+	regex: Option<Regex>,
 }
 
 impl Filter {
@@ -19,27 +24,63 @@ impl Filter {
 			FilterCase::Sensitive => Regex::new(s)?,
 			FilterCase::Insensitive => RegexBuilder::new(s).case_insensitive(true).build()?,
 		};
-		Ok(Self { raw: s.to_owned(), regex })
+		Ok(Self { raw: s.to_owned(), case, regex: Some(regex) })
+	}
+
+	/// A filter that keeps the files tagged with `tag`, rather than matching
+	/// their names.
+	#[inline]
+	pub fn tag(tag: &str) -> Self {
+		Self { raw: tag.to_owned(), case: Default::default(), regex: None }
+	}
+
+	/// The tag filtered by, if it's created with [`Self::tag`].
+	#[inline]
+	pub fn tagged(&self) -> Option<&str> { self.regex.is_none().then_some(&self.raw) }
+
+	#[inline]
+	pub fn case(&self) -> FilterCase { self.case }
+
+	/// The pattern it's created with, or the tag for a tag filter.
+	#[inline]
+	pub fn pattern(&self) -> &str { &self.raw }
+
+	pub fn matches(&self, file: &File) -> bool {
+		match &self.regex {
+			Some(r) => r.is_match(file.name().as_encoded_bytes()),
+			None => TAGS.read().contains(&file.url, &self.raw),
+		}
 	}
 
+	/// Whether the name matches, or [`None`] if it can't be told from the name
+	/// alone, which is the case for tag filters.
 	#[inline]
-	pub fn matches(&self, name: &OsStr) -> bool { self.regex.is_match(name.as_encoded_bytes()) }
+	pub fn matches_name(&self, name: &OsStr) -> Option<bool> {
+		Some(self.regex.as_ref()?.is_match(name.as_encoded_bytes()))
+	}
 
 	#[inline]
 	pub fn highlighted(&self, name: &OsStr) -> Option<Vec<Range<usize>>> {
-		self.regex.find(name.as_encoded_bytes()).map(|m| vec![m.range()])
+		self.regex.as_ref()?.find(name.as_encoded_bytes()).map(|m| vec![m.range()])
 	}
 }
 
 impl PartialEq for Filter {
-	fn eq(&self, other: &Self) -> bool { self.raw == other.raw }
+	fn eq(&self, other: &Self) -> bool {
+		self.raw == other.raw && self.regex.is_some() == other.regex.is_some()
+	}
 }
 
 impl Display for Filter {
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str(&self.raw) }
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.regex.is_none() {
+			f.write_str("#")?;
+		}
+		f.write_str(&self.raw)
+	}
 }
 
-#[derive(Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
 pub enum FilterCase {
 	Smart,
 	#[default]
@@ -47,6 +88,26 @@ pub enum FilterCase {
 	Insensitive,
 }
 
+impl FilterCase {
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Smart => "smart",
+			Self::Sensitive => "sensitive",
+			Self::Insensitive => "insensitive",
+		}
+	}
+}
+
+impl From<FindCase> for FilterCase {
+	fn from(value: FindCase) -> Self {
+		match value {
+			FindCase::Smart => Self::Smart,
+			FindCase::Sensitive => Self::Sensitive,
+			FindCase::Insensitive => Self::Insensitive,
+		}
+	}
+}
+
 impl From<&Cmd> for FilterCase {
 	fn from(c: &Cmd) -> Self {
 		match (c.bool("smart"), c.bool("insensitive")) {
@@ -56,3 +117,22 @@ impl From<&Cmd> for FilterCase {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	#[allow(clippy::single_range_in_vec_init)]
+	fn test_highlighted() {
+		let h = |s, case, name: &str| Filter::new(s, case).unwrap().highlighted(OsStr::new(name));
+
+		assert_eq!(h("readme", FilterCase::Smart, "README.md"), Some(vec![0..6]));
+		assert_eq!(h("Readme", FilterCase::Smart, "README.md"), None);
+		assert_eq!(h("readme", FilterCase::Sensitive, "README.md"), None);
+
+		// The spans are of the original name, even if case folding changes the lengths
+		assert_eq!(h("äß", FilterCase::Insensitive, "xÄẞy"), Some(vec![1..6]));
+		assert_eq!(h("straße", FilterCase::Insensitive, "Die STRASSE, die STRAẞE"), Some(vec![17..25]));
+	}
+}
@@ -1,48 +1,74 @@
-use std::mem;
+use std::{cmp::Ordering, mem, ops::Range, time::Instant};
 
-use yazi_config::{LAYOUT, MANAGER};
+use yazi_config::{LAYOUT, MANAGER, manager::SortOverride};
 use yazi_proxy::ManagerProxy;
-use yazi_shared::fs::{Cha, File, FilesOp, Url, Urn};
+use yazi_shared::fs::{Cha, File, FilesOp, Url, Urn, UrnBuf};
 
 use super::FolderStage;
 use crate::{Files, Step};
 
 #[derive(Default)]
 pub struct Folder {
-	pub url:   Url,
-	pub cha:   Cha,
-	pub files: Files,
-	pub stage: FolderStage,
+	pub url:       Url,
+	pub cha:       Cha,
+	pub files:     Files,
+	pub stage:     FolderStage,
+	/// When the folder entered its current `stage`, e.g. started loading.
+	pub staged_at: Option<Instant>,
 
 	pub offset: usize,
 	pub cursor: usize,
 
-	pub page:    usize,
-	pub tracing: bool,
+	pub page:     usize,
+	pub tracing:  bool,
+	/// Whether the cursor was moved up most recently, see [`Self::readahead`].
+	pub backward: bool,
+	// The file traced before the folder was reloaded in chunks, to be hovered
+	// again once it's read, unless the cursor is moved meanwhile
+	restore:      Option<UrnBuf>,
+
+	/// The sorting configured for this directory, looked up once it's created.
+	pub sort_override: Option<&'static SortOverride>,
 }
 
 impl From<&Url> for Folder {
-	fn from(url: &Url) -> Self { Self { url: url.clone(), ..Default::default() } }
+	fn from(url: &Url) -> Self {
+		Self {
+			url: url.clone(),
+			staged_at: Some(Instant::now()),
+			sort_override: MANAGER.sort_overrides.of(url),
+			..Default::default()
+		}
+	}
 }
 
 impl Folder {
+	pub const BLOCK: usize = 10;
+
 	pub fn update(&mut self, op: FilesOp) -> bool {
 		let (stage, revision) = (self.stage, self.files.revision);
 		match op {
 			FilesOp::Full(_, _, cha) => {
 				(self.cha, self.stage) = (cha, FolderStage::Loaded);
 			}
+			FilesOp::Part(_, ref files, ticket) if files.is_empty() && ticket != self.files.ticket() => {
+				self.stage = FolderStage::Loading;
+				self.restore = self.hovered().filter(|_| self.tracing).map(|h| h.urn_owned());
+			}
 			FilesOp::Part(_, _, ticket) if ticket == self.files.ticket() => {
 				self.stage = FolderStage::Loading;
 			}
-			FilesOp::Done(_, cha, ticket) if ticket == self.files.ticket() => {
-				(self.cha, self.stage) = (cha, FolderStage::Loaded);
+			FilesOp::Done(_, cha, ticket, err) if ticket == self.files.ticket() => {
+				(self.cha, self.stage) = (cha, err.map_or(FolderStage::Loaded, FolderStage::Truncated));
 			}
 			FilesOp::IOErr(_, kind) => {
 				(self.cha, self.stage) = (Cha::dummy(), FolderStage::Failed(kind));
 			}
 			_ => {}
 		}
+		if self.stage != stage {
+			self.staged_at = Some(Instant::now());
+		}
 
 		match op {
 			FilesOp::Full(_, files, _) => self.files.update_full(files),
@@ -57,12 +83,23 @@ impl Folder {
 			FilesOp::Upserting(_, files) => self.files.update_upserting(files),
 		}
 
-		self.arrow(0);
+		let restore = self.restore.take();
+		match restore.as_ref().map(|u| (u, self.files.position_near(u.as_urn(), self.cursor))) {
+			Some((_, Some(i))) => {
+				self.arrow(i as isize - self.cursor as isize);
+				self.tracing = true;
+			}
+			Some(_) if self.stage == FolderStage::Loading => {
+				self.restore = restore;
+				self.arrow(0);
+			}
+			_ => _ = self.arrow(0),
+		}
 		(stage, revision) != (self.stage, self.files.revision)
 	}
 
 	pub fn arrow(&mut self, step: impl Into<Step>) -> bool {
-		let step = step.into() as Step;
+		let (step, old) = (step.into() as Step, self.cursor);
 		let mut b = if self.files.is_empty() {
 			(self.cursor, self.offset, self.tracing) = (0, 0, false);
 			false
@@ -72,10 +109,19 @@ impl Folder {
 			self.prev(step)
 		};
 
+		if b {
+			self.restore = None;
+		}
 		self.tracing |= b;
 		b |= self.squeeze_offset();
 
-		self.sync_page(false);
+		// Readahead follows the direction, so it has to be redone when it's reversed
+		let reversed = match self.cursor.cmp(&old) {
+			Ordering::Greater => mem::replace(&mut self.backward, false),
+			Ordering::Less => !mem::replace(&mut self.backward, true),
+			Ordering::Equal => false,
+		};
+		self.sync_page(reversed);
 		b
 	}
 
@@ -84,7 +130,7 @@ impl Folder {
 			return false;
 		}
 
-		let new = self.files.position(urn).unwrap_or(self.cursor) as isize;
+		let new = self.files.position_near(urn, self.cursor).unwrap_or(self.cursor) as isize;
 		self.arrow(new - self.cursor as isize)
 	}
 
@@ -168,4 +214,52 @@ impl Folder {
 		let end = ((page + 2) * limit).min(len);
 		&self.files[start..end]
 	}
+
+	/// The files to preload around the cursor, nearest first in the direction
+	/// it was moved recently: two pages ahead of it, and half a page behind.
+	pub fn readahead(&self) -> Vec<&File> {
+		let limit = LAYOUT.load().current.height as usize;
+		let range = self.readahead_range(limit * 2, limit / 2);
+
+		let cursor = self.cursor.clamp(range.start, range.end);
+		let (before, after) = self.files[range.clone()].split_at(cursor - range.start);
+		if self.backward {
+			before.iter().rev().chain(after).collect()
+		} else {
+			after.iter().chain(before.iter().rev()).collect()
+		}
+	}
+
+	/// The files whose queued preloads are still worth running, which is
+	/// [`Self::readahead`] with some slack, so that the ones left far behind can
+	/// be dropped instead of holding up the files ahead.
+	pub fn readahead_retained(&self) -> &[File] {
+		let limit = LAYOUT.load().current.height as usize;
+		&self.files[self.readahead_range(limit * 3, limit + limit / 2)]
+	}
+
+	/// The files of the aligned block of [`Self::BLOCK`] pages that `page` is
+	/// in, extended to cover [`Self::paginate`] of it, so that walking through
+	/// the pages produces the same few blocks instead of a window per page.
+	pub fn paginate_block(&self, page: usize) -> &[File] {
+		let len = self.files.len();
+		let limit = LAYOUT.load().current.height as usize;
+
+		let first = page.saturating_sub(1) / Self::BLOCK * Self::BLOCK;
+		let last = (page + 2).div_ceil(Self::BLOCK) * Self::BLOCK;
+
+		let start = (first * limit).min(len.saturating_sub(1));
+		let end = (last * limit).min(len);
+		&self.files[start..end]
+	}
+
+	fn readahead_range(&self, ahead: usize, behind: usize) -> Range<usize> {
+		let len = self.files.len();
+		let cursor = self.cursor.min(len);
+		if self.backward {
+			cursor.saturating_sub(ahead)..(cursor + behind + 1).min(len)
+		} else {
+			cursor.saturating_sub(behind)..(cursor + ahead + 1).min(len)
+		}
+	}
 }
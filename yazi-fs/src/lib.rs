@@ -1,3 +1,5 @@
 #![allow(clippy::if_same_then_else)]
 
-yazi_macro::mod_flat!(files filter folder sorter stage step);
+yazi_macro::mod_pub!(archive);
+
+yazi_macro::mod_flat!(dir_stats files filter folder mount remote sorter stage step tags unwritable);
@@ -0,0 +1,99 @@
+use std::path::{Path, PathBuf};
+
+/// The filesystem mounted at `point`, which a path lives on.
+#[derive(Clone, Debug)]
+pub struct Mount {
+	pub point:  PathBuf,
+	pub source: String,
+	pub kind:   String,
+}
+
+impl Mount {
+	/// The mount `path` lives on.
+	///
+	/// This is a blocking call, which may take a while on an unresponsive mount.
+	#[cfg(target_os = "linux")]
+	pub fn of(path: &Path) -> Option<Self> {
+		let path = std::fs::canonicalize(path).ok()?;
+		let mounts = std::fs::read_to_string("/proc/self/mounts").ok()?;
+
+		// The last one wins among those mounted at the same point, as it shadows the
+		// others
+		let mut found: Option<Self> = None;
+		for line in mounts.lines() {
+			let mut it = line.split(' ');
+			let (Some(source), Some(point), Some(kind)) = (it.next(), it.next(), it.next()) else {
+				continue;
+			};
+
+			let point = PathBuf::from(Self::unescape(point));
+			if path.starts_with(&point)
+				&& Some(point.as_os_str().len()) >= found.as_ref().map(|f| f.point.as_os_str().len())
+			{
+				found = Some(Self { point, source: Self::unescape(source), kind: kind.to_owned() });
+			}
+		}
+		found
+	}
+
+	#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+	pub fn of(path: &Path) -> Option<Self> {
+		use std::{ffi::{CStr, CString}, os::unix::ffi::OsStrExt};
+
+		let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+		let mut buf: libc::statfs = unsafe { std::mem::zeroed() };
+		if unsafe { libc::statfs(path.as_ptr(), &mut buf) } != 0 {
+			return None;
+		}
+
+		let s =
+			|b: &[libc::c_char]| unsafe { CStr::from_ptr(b.as_ptr()) }.to_string_lossy().into_owned();
+		Some(Self {
+			point:  PathBuf::from(s(&buf.f_mntonname)),
+			source: s(&buf.f_mntfromname),
+			kind:   s(&buf.f_fstypename),
+		})
+	}
+
+	#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")))]
+	pub fn of(_: &Path) -> Option<Self> { None }
+
+	// Spaces, tabs, newlines and backslashes are escaped as octal in
+	// `/proc/self/mounts`
+	#[cfg(target_os = "linux")]
+	fn unescape(s: &str) -> String {
+		if !s.contains('\\') {
+			return s.to_owned();
+		}
+
+		let (b, mut out) = (s.as_bytes(), Vec::with_capacity(s.len()));
+		let mut i = 0;
+		while i < b.len() {
+			let code =
+				b.get(i + 1..i + 4).and_then(|o| u8::from_str_radix(std::str::from_utf8(o).ok()?, 8).ok());
+			match (b[i], code) {
+				(b'\\', Some(c)) => {
+					out.push(c);
+					i += 4;
+				}
+				(c, _) => {
+					out.push(c);
+					i += 1;
+				}
+			}
+		}
+		String::from_utf8_lossy(&out).into_owned()
+	}
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_unescape() {
+		assert_eq!(Mount::unescape("/mnt/my\\040disk"), "/mnt/my disk");
+		assert_eq!(Mount::unescape("/a\\134b"), "/a\\b");
+		assert_eq!(Mount::unescape("/plain"), "/plain");
+	}
+}
@@ -0,0 +1,50 @@
+use std::path::Path;
+
+/// Whether `path` is on a network filesystem, such as NFS, SMB, SSHFS or 9p,
+/// where the native watcher doesn't receive the changes made by other hosts.
+///
+/// This is a blocking call, which may take a while on an unresponsive mount.
+#[cfg(target_os = "linux")]
+pub fn is_remote(path: &Path) -> bool {
+	use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+	// See statfs(2), the magic numbers are 32-bit, while `f_type` is signed and
+	// as wide as a `long`, so it's truncated rather than sign-extended on 32-bit
+	const REMOTE: [u32; 10] = [
+		0x6969,     // NFS
+		0x517b,     // SMB
+		0xfe534d42, // SMB2
+		0xff534d42, // CIFS
+		0x65735546, // FUSE, e.g. SSHFS
+		0x01021997, // 9p
+		0x5346414f, // AFS
+		0x73757245, // Coda
+		0x564c,     // NCP
+		0x47504653, // GPFS
+	];
+
+	let Ok(path) = CString::new(path.as_os_str().as_bytes()) else { return false };
+	let mut buf: libc::statfs = unsafe { std::mem::zeroed() };
+	if unsafe { libc::statfs(path.as_ptr(), &mut buf) } != 0 {
+		return false;
+	}
+	REMOTE.contains(&(buf.f_type as u32))
+}
+
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+pub fn is_remote(path: &Path) -> bool {
+	use std::{ffi::{CStr, CString}, os::unix::ffi::OsStrExt};
+
+	const REMOTE: [&[u8]; 6] = [b"nfs", b"smbfs", b"afpfs", b"webdav", b"macfuse", b"osxfuse"];
+
+	let Ok(path) = CString::new(path.as_os_str().as_bytes()) else { return false };
+	let mut buf: libc::statfs = unsafe { std::mem::zeroed() };
+	if unsafe { libc::statfs(path.as_ptr(), &mut buf) } != 0 {
+		return false;
+	}
+	let name = unsafe { CStr::from_ptr(buf.f_fstypename.as_ptr()) };
+	REMOTE.contains(&name.to_bytes())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")))]
+pub fn is_remote(_: &Path) -> bool { false }
@@ -1,7 +1,7 @@
-use std::{cmp::Ordering, collections::HashMap, mem};
+use std::{borrow::Cow, cmp::Ordering, collections::{HashMap, HashSet}, mem};
 
-use yazi_config::manager::SortBy;
-use yazi_shared::{LcgRng, fs::{File, UrnBuf}, natsort, translit::Transliterator};
+use yazi_config::manager::{SortBy, SortDigits};
+use yazi_shared::{LcgRng, collate_key, fs::{File, UrnBuf}, natsort_key, translit::Transliterator};
 
 #[derive(Clone, Copy, Default, PartialEq)]
 pub struct FilesSorter {
@@ -10,15 +10,144 @@ pub struct FilesSorter {
 	pub reverse:   bool,
 	pub dir_first: bool,
 	pub translit:  bool,
+	pub locale:    bool,
+	pub digits:    SortDigits,
 }
 
+/// The [`FilesSorter::key`]s of the files, kept between sorts so that they're
+/// made only once for each file, until it's renamed, removed, or the options
+/// they depend on change.
+pub type SortKeys = HashMap<UrnBuf, Box<[u8]>>;
+
 impl FilesSorter {
-	pub(super) fn sort(&self, items: &mut Vec<File>, sizes: &HashMap<UrnBuf, u64>) {
-		if items.is_empty() {
+	/// Sorts `items`, of which the first `sorted` ones are already in order, so
+	/// only the rest of them are sorted and then merged into those, instead of
+	/// sorting everything again.
+	///
+	/// The keys of the files are looked up in `keys`, and the missing ones are
+	/// made and added to it.
+	pub fn sort(
+		&self,
+		items: &mut Vec<File>,
+		sorted: usize,
+		sizes: &HashMap<UrnBuf, u64>,
+		keys: &mut SortKeys,
+	) {
+		if sorted >= items.len() || self.by == SortBy::None {
+			return;
+		}
+
+		if self.keyed() {
+			self.fill_keys(items, sorted, keys);
+		}
+		let key = |f: &File| -> &[u8] { keys.get(f.urn()).map_or(&[], |k| k) };
+
+		if sorted > 0 && items.len() - sorted <= 32 {
+			// A few new files, e.g. created or moved in, go to their position directly
+			for file in items.split_off(sorted) {
+				let i = items
+					.partition_point(|f| self.cmp_keyed((f, key(f)), (&file, key(&file)), sizes).is_le());
+				items.insert(i, file);
+			}
 			return;
 		}
 
-		let by_alphabetical = |a: &File, b: &File| {
+		let (head, tail) = items.split_at(sorted);
+
+		// Sorts the files together with their keys, but not the files themselves, to
+		// move less memory around
+		let mut keyed: Vec<_> = tail.iter().enumerate().map(|(i, f)| (f, key(f), i)).collect();
+		if self.by == SortBy::Random {
+			let mut rng = LcgRng::default();
+			keyed.sort_unstable_by(|a, b| self.cmp(rng.next(), rng.next(), self.promote(a.0, b.0)));
+		} else {
+			keyed.sort_unstable_by(|a, b| self.cmp_keyed((a.0, a.1), (b.0, b.1), sizes));
+		}
+
+		// Merges them into the sorted ones
+		let mut order = Vec::with_capacity(items.len());
+		let (mut i, mut j) = (0, 0);
+		while i < head.len() && j < keyed.len() {
+			let (f, k, t) = keyed[j];
+			if self.cmp_keyed((f, k), (&head[i], key(&head[i])), sizes).is_lt() {
+				order.push(sorted + t);
+				j += 1;
+			} else {
+				order.push(i);
+				i += 1;
+			}
+		}
+		order.extend(i..head.len());
+		order.extend(keyed[j..].iter().map(|&(_, _, t)| sorted + t));
+
+		*items = order.into_iter().map(|i| mem::take(&mut items[i])).collect();
+	}
+
+	fn fill_keys(&self, items: &[File], sorted: usize, keys: &mut SortKeys) {
+		for f in &items[sorted..] {
+			if !keys.contains_key(f.urn()) {
+				keys.insert(f.urn_owned(), self.key(f).into());
+			}
+		}
+
+		// Those of the files gone without being deleted, e.g. when the directory is
+		// read again, are dropped once they outnumber the files there are
+		if keys.len() > items.len() * 2 {
+			let urns: HashSet<_> = items.iter().map(|f| f.urn()).collect();
+			keys.retain(|u, _| urns.contains(u.as_urn()));
+		}
+	}
+
+	/// Whether the keys made with `other` are the same as with this, i.e. only
+	/// the options that don't change them differ.
+	pub(super) fn same_keys(&self, other: &Self) -> bool {
+		self.by == other.by
+			&& self.sensitive == other.sensitive
+			&& self.translit == other.translit
+			&& self.locale == other.locale
+			&& self.digits == other.digits
+	}
+
+	#[inline]
+	fn keyed(&self) -> bool { matches!(self.by, SortBy::Natural | SortBy::Alphabetical) }
+
+	/// The name of `file` prepared for comparison, transliterated, case folded
+	/// and made into a [`natsort_key`] or [`collate_key`] once per file rather
+	/// than for every comparison.
+	fn key<'a>(&self, file: &'a File) -> Cow<'a, [u8]> {
+		let name = file.name().as_encoded_bytes();
+		let digits_last = self.digits == SortDigits::Last;
+		match self.by {
+			SortBy::Natural | SortBy::Alphabetical if self.locale => {
+				Cow::Owned(collate_key(name, !self.sensitive, self.by == SortBy::Natural, digits_last))
+			}
+			SortBy::Natural if self.translit && !name.is_ascii() => {
+				Cow::Owned(natsort_key(name.transliterate().as_bytes(), !self.sensitive, digits_last))
+			}
+			SortBy::Natural => Cow::Owned(natsort_key(name, !self.sensitive, digits_last)),
+			SortBy::Alphabetical if self.sensitive || !name.iter().any(|b| b.is_ascii_lowercase()) => {
+				Cow::Borrowed(name)
+			}
+			SortBy::Alphabetical => Cow::Owned(name.to_ascii_uppercase()),
+			_ => Cow::Borrowed(&[]),
+		}
+	}
+
+	fn cmp_keyed(
+		&self,
+		(a, ka): (&File, &[u8]),
+		(b, kb): (&File, &[u8]),
+		sizes: &HashMap<UrnBuf, u64>,
+	) -> Ordering {
+		match self.by {
+			SortBy::Natural | SortBy::Alphabetical => self.cmp(ka, kb, self.promote(a, b)),
+			SortBy::Random => self.promote(a, b),
+			_ => self.compare(a, b, sizes),
+		}
+	}
+
+	fn compare(&self, a: &File, b: &File, sizes: &HashMap<UrnBuf, u64>) -> Ordering {
+		let by_alphabetical = || {
 			if self.sensitive {
 				self.cmp(a.name(), b.name(), self.promote(a, b))
 			} else {
@@ -26,18 +155,12 @@ impl FilesSorter {
 			}
 		};
 
-		match self.by {
-			SortBy::None => {}
-			SortBy::Mtime => items.sort_unstable_by(|a, b| {
-				let ord = self.cmp(a.mtime, b.mtime, self.promote(a, b));
-				if ord == Ordering::Equal { by_alphabetical(a, b) } else { ord }
-			}),
-			SortBy::Btime => items.sort_unstable_by(|a, b| {
-				let ord = self.cmp(a.btime, b.btime, self.promote(a, b));
-				if ord == Ordering::Equal { by_alphabetical(a, b) } else { ord }
-			}),
-			SortBy::Extension => items.sort_unstable_by(|a, b| {
-				let ord = if self.sensitive {
+		let ord = match self.by {
+			SortBy::None | SortBy::Random | SortBy::Alphabetical | SortBy::Natural => Ordering::Equal,
+			SortBy::Mtime => self.cmp(a.mtime, b.mtime, self.promote(a, b)),
+			SortBy::Btime => self.cmp(a.btime, b.btime, self.promote(a, b)),
+			SortBy::Extension => {
+				if self.sensitive {
 					self.cmp(a.url.extension(), b.url.extension(), self.promote(a, b))
 				} else {
 					self.cmp(
@@ -45,48 +168,15 @@ impl FilesSorter {
 						b.url.extension().map(|s| s.to_ascii_lowercase()),
 						self.promote(a, b),
 					)
-				};
-				if ord == Ordering::Equal { by_alphabetical(a, b) } else { ord }
-			}),
-			SortBy::Alphabetical => items.sort_unstable_by(by_alphabetical),
-			SortBy::Natural => self.sort_naturally(items),
-			SortBy::Size => items.sort_unstable_by(|a, b| {
+				}
+			}
+			SortBy::Size => {
 				let aa = if a.is_dir() { sizes.get(a.urn()).copied() } else { None };
 				let bb = if b.is_dir() { sizes.get(b.urn()).copied() } else { None };
-				let ord = self.cmp(aa.unwrap_or(a.len), bb.unwrap_or(b.len), self.promote(a, b));
-				if ord == Ordering::Equal { by_alphabetical(a, b) } else { ord }
-			}),
-			SortBy::Random => {
-				let mut rng = LcgRng::default();
-				items.sort_unstable_by(|a, b| self.cmp(rng.next(), rng.next(), self.promote(a, b)))
-			}
-		}
-	}
-
-	fn sort_naturally(&self, items: &mut Vec<File>) {
-		let mut indices: Vec<usize> = (0..items.len()).collect();
-		indices.sort_unstable_by(|&a, &b| {
-			let (a, b) = (&items[a], &items[b]);
-
-			let promote = self.promote(a, b);
-			if promote != Ordering::Equal {
-				return promote;
+				self.cmp(aa.unwrap_or(a.len), bb.unwrap_or(b.len), self.promote(a, b))
 			}
-
-			let ordering = if self.translit {
-				natsort(
-					a.name().as_encoded_bytes().transliterate().as_bytes(),
-					b.name().as_encoded_bytes().transliterate().as_bytes(),
-					!self.sensitive,
-				)
-			} else {
-				natsort(a.name().as_encoded_bytes(), b.name().as_encoded_bytes(), !self.sensitive)
-			};
-
-			if self.reverse { ordering.reverse() } else { ordering }
-		});
-
-		*items = indices.into_iter().map(|i| mem::take(&mut items[i])).collect();
+		};
+		if ord == Ordering::Equal { by_alphabetical() } else { ord }
 	}
 
 	#[inline(always)]
@@ -104,3 +194,56 @@ impl FilesSorter {
 		if self.dir_first { b.is_dir().cmp(&a.is_dir()) } else { Ordering::Equal }
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use yazi_shared::{fs::Url, natsort};
+
+	use super::*;
+
+	// Compares the names for every pair, as the sorter did before the keys
+	fn pairwise(sorter: &FilesSorter, items: &mut [File]) {
+		items.sort_unstable_by(|a, b| {
+			let ord = if sorter.translit {
+				natsort(
+					a.name().as_encoded_bytes().transliterate().as_bytes(),
+					b.name().as_encoded_bytes().transliterate().as_bytes(),
+					!sorter.sensitive,
+				)
+			} else {
+				natsort(a.name().as_encoded_bytes(), b.name().as_encoded_bytes(), !sorter.sensitive)
+			};
+			if sorter.reverse { ord.reverse() } else { ord }
+		});
+	}
+
+	#[test]
+	fn test_sort_keys() {
+		// `img0001.png` to `img2000.png`, shuffled, with some accented and uppercase
+		// ones
+		let mut rng = LcgRng::default();
+		let mut files: Vec<_> = (1..=2_000)
+			.map(|i| {
+				let name = match i % 5 {
+					0 => format!("IMG{i:04}.png"),
+					1 => format!("ïmg{i:04}.png"),
+					_ => format!("img{i:04}.png"),
+				};
+				(rng.next(), File { url: Url::from(format!("/generated/{name}")), ..Default::default() })
+			})
+			.collect();
+		files.sort_unstable_by_key(|(n, _)| *n);
+		let files: Vec<_> = files.into_iter().map(|(_, f)| f).collect();
+
+		for (sensitive, translit) in [(true, false), (false, false), (false, true)] {
+			let sorter = FilesSorter { by: SortBy::Natural, sensitive, translit, ..Default::default() };
+
+			let (mut before, mut after) = (files.clone(), files.clone());
+			pairwise(&sorter, &mut before);
+			sorter.sort(&mut after, 0, &Default::default(), &mut Default::default());
+
+			let names = |v: &[File]| v.iter().map(|f| f.name().to_owned()).collect::<Vec<_>>();
+			assert_eq!(names(&before), names(&after));
+		}
+	}
+}
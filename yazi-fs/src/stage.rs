@@ -4,4 +4,6 @@ pub enum FolderStage {
 	Loading,
 	Loaded,
 	Failed(std::io::ErrorKind),
+	/// Loaded, but reading the directory stopped partway through with the error.
+	Truncated(std::io::ErrorKind),
 }
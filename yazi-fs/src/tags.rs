@@ -0,0 +1,252 @@
+use std::{collections::{BTreeSet, HashMap}, fs, mem, path::{Path, PathBuf}};
+
+use anyhow::Result;
+use parking_lot::{Mutex, RwLock};
+use tokio::task::spawn_blocking;
+use yazi_boot::BOOT;
+use yazi_macro::emit;
+use yazi_shared::{RoCell, fs::{Url, UrlScheme, long}};
+
+pub static TAGS: RoCell<RwLock<Tags>> = RoCell::new();
+
+static SAVING: Mutex<()> = Mutex::new(());
+
+/// The tags of files, which are saved to `<state_dir>/tags.json` whenever they
+/// change.
+///
+/// They're keyed by the path of the file with its parent directory resolved,
+/// so tagging a symlink itself doesn't tag its target. The directories are
+/// resolved with [`Self::resolve`] before tagging, and when the tags are
+/// loaded, so neither looking them up nor tagging touches the disk, and a file
+/// reached through a symlink to its directory only has them once that symlink
+/// has been resolved, i.e. something in the directory was tagged through it.
+#[derive(Default)]
+pub struct Tags {
+	entries: HashMap<PathBuf, BTreeSet<String>>,
+	// The resolved paths of the directories files have been tagged in
+	dirs:    HashMap<PathBuf, PathBuf>,
+}
+
+impl Tags {
+	/// Loads the saved tags in the background, and re-renders once done.
+	pub async fn load() {
+		let Ok(Ok(saved)) = spawn_blocking(|| -> Result<HashMap<PathBuf, BTreeSet<String>>> {
+			let saved: HashMap<PathBuf, BTreeSet<String>> =
+				serde_json::from_slice(&fs::read(Self::path())?)?;
+			Ok(saved.into_iter().map(|(path, tags)| (Self::canonicalize(&path), tags)).collect())
+		})
+		.await
+		else {
+			return;
+		};
+
+		let mut me = TAGS.write();
+		for (path, tags) in saved {
+			me.entries.entry(path).or_insert(tags);
+		}
+		emit!(Render);
+	}
+
+	/// Resolves the parent directories of `urls` that haven't been, which is
+	/// done before tagging them, so the lock isn't held while touching the disk.
+	pub async fn resolve<'a>(urls: impl IntoIterator<Item = &'a Url>) {
+		let parents: BTreeSet<_> = {
+			let me = TAGS.read();
+			urls
+				.into_iter()
+				.filter(|&u| Self::taggable(u))
+				.filter_map(|u| u.parent())
+				.filter(|&p| !me.dirs.contains_key(p))
+				.map(ToOwned::to_owned)
+				.collect()
+		};
+
+		let mut resolved = Vec::with_capacity(parents.len());
+		for parent in parents {
+			if let Ok(dir) = long::canonicalize(&parent).await {
+				resolved.push((parent, dir));
+			}
+		}
+		TAGS.write().dirs.extend(resolved);
+	}
+
+	/// The tags of the file at `url`, in alphabetical order.
+	pub fn get(&self, url: &Url) -> Option<&BTreeSet<String>> {
+		if self.entries.is_empty() || !Self::taggable(url) {
+			return None;
+		}
+		if let Some(tags) = self.entries.get(url.as_path()) {
+			return Some(tags);
+		}
+
+		let dir = self.dirs.get(url.parent()?)?;
+		self.entries.get(&dir.join(url.file_name()?))
+	}
+
+	#[inline]
+	pub fn contains(&self, url: &Url, tag: &str) -> bool {
+		self.get(url).is_some_and(|t| t.contains(tag))
+	}
+
+	/// Adds or removes `tag` of the file at `url`, returns whether it's changed.
+	pub fn set(&mut self, url: &Url, tag: &str, state: bool) -> bool {
+		let changed = self.put(url, tag, state);
+		if changed {
+			Self::save();
+		}
+		changed
+	}
+
+	/// Removes `tag` from the files at `urls` if all of them have it, otherwise
+	/// adds it to all of them, returns whether any of them is changed.
+	pub fn toggle(&mut self, urls: &[Url], tag: &str) -> bool {
+		let state = !urls.iter().all(|u| self.contains(u, tag));
+
+		let mut changed = false;
+		for url in urls {
+			changed |= self.put(url, tag, state);
+		}
+		if changed {
+			Self::save();
+		}
+		changed
+	}
+
+	/// Moves the tags of `from` to `to` after it's been renamed, along with the
+	/// tags of everything under it if it's a directory.
+	pub fn rename(&mut self, from: &Url, to: &Url) {
+		if self.remap(from, to) {
+			Self::save();
+		}
+	}
+
+	fn remap(&mut self, from: &Url, to: &Url) -> bool {
+		let (Some(from_key), Some(to_key)) = (self.key(from), self.key(to)) else { return false };
+
+		// The directories seen under `from` are now under `to`, while those resolved
+		// into it from elsewhere, through a symlink, no longer are
+		self.dirs = mem::take(&mut self.dirs)
+			.into_iter()
+			.filter_map(|(seen, dir)| {
+				let dir = match dir.strip_prefix(&from_key) {
+					Ok(rest) => Ok(to_key.join(rest)),
+					Err(_) => Err(dir),
+				};
+				match (seen.strip_prefix(from.as_path()), dir) {
+					(Ok(rest), Ok(dir) | Err(dir)) => Some((to.as_path().join(rest), dir)),
+					(Err(_), Ok(_)) => None,
+					(Err(_), Err(dir)) => Some((seen, dir)),
+				}
+			})
+			.collect();
+
+		let moved: Vec<_> = self.entries.keys().filter(|p| p.starts_with(&from_key)).cloned().collect();
+		for old in &moved {
+			let tags = self.entries.remove(old).unwrap();
+			self.entries.insert(to_key.join(old.strip_prefix(&from_key).unwrap()), tags);
+		}
+		!moved.is_empty()
+	}
+
+	fn put(&mut self, url: &Url, tag: &str, state: bool) -> bool {
+		let Some(key) = self.key(url) else { return false };
+		if state {
+			return self.entries.entry(key).or_default().insert(tag.to_owned());
+		}
+
+		let Some(tags) = self.entries.get_mut(&key) else { return false };
+		let removed = tags.remove(tag);
+		if tags.is_empty() {
+			self.entries.remove(&key);
+		}
+		removed
+	}
+
+	// The key of `url` to tag it with, with its parent directory resolved if it
+	// has been, or as it is if that fails.
+	fn key(&self, url: &Url) -> Option<PathBuf> {
+		if !Self::taggable(url) {
+			return None;
+		}
+
+		let (parent, name) = (url.parent()?, url.file_name()?);
+		Some(self.dirs.get(parent).map_or_else(|| url.to_path_buf(), |dir| dir.join(name)))
+	}
+
+	#[inline]
+	fn taggable(url: &Url) -> bool {
+		matches!(url.scheme(), UrlScheme::Regular | UrlScheme::SearchItem)
+	}
+
+	fn canonicalize(path: &Path) -> PathBuf {
+		match (path.parent(), path.file_name()) {
+			(Some(parent), Some(name)) => {
+				fs::canonicalize(parent).map_or_else(|_| path.to_owned(), |dir| dir.join(name))
+			}
+			_ => path.to_owned(),
+		}
+	}
+
+	// Takes the snapshot while holding `SAVING`, so that the last one written is
+	// always the latest.
+	fn save() {
+		spawn_blocking(|| -> Result<()> {
+			let _guard = SAVING.lock();
+			let json = serde_json::to_vec(&TAGS.read().entries)?;
+
+			let path = Self::path();
+			fs::create_dir_all(path.parent().unwrap())?;
+
+			let tmp = path.with_extension("json.tmp");
+			fs::write(&tmp, json)?;
+			Ok(fs::rename(tmp, path)?)
+		});
+	}
+
+	#[inline]
+	fn path() -> PathBuf { BOOT.state_dir.join("tags.json") }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_rename() {
+		let url = |s: &str| Url::from(Path::new(s));
+		let mut tags = Tags::default();
+		tags.dirs.extend([
+			(PathBuf::from("/r"), PathBuf::from("/r")),
+			(PathBuf::from("/r/a/sub"), PathBuf::from("/r/a/sub")),
+			(PathBuf::from("/r/link"), PathBuf::from("/r/a")),
+		]);
+
+		assert!(tags.put(&url("/r/link/f"), "keep", true));
+		assert!(tags.put(&url("/r/a/sub/g"), "keep", true));
+		assert!(tags.contains(&url("/r/a/f"), "keep"));
+		assert!(tags.contains(&url("/r/link/f"), "keep"));
+
+		assert!(tags.remap(&url("/r/a"), &url("/r/b")));
+		assert!(tags.contains(&url("/r/b/f"), "keep"));
+		assert!(tags.contains(&url("/r/b/sub/g"), "keep"));
+		assert!(!tags.contains(&url("/r/a/f"), "keep"));
+
+		// The symlink no longer points to the renamed directory
+		assert!(!tags.contains(&url("/r/link/f"), "keep"));
+		assert_eq!(tags.dirs.get(Path::new("/r/b/sub")), Some(&PathBuf::from("/r/b/sub")));
+		assert!(!tags.dirs.contains_key(Path::new("/r/a/sub")));
+		assert!(!tags.remap(&url("/r/a"), &url("/r/c")));
+	}
+
+	#[test]
+	fn test_unresolved() {
+		let url = |s: &str| Url::from(Path::new(s));
+		let mut tags = Tags::default();
+
+		assert!(tags.put(&url("/r/f"), "keep", true));
+		assert!(!tags.put(&url("/r/f"), "keep", true));
+		assert!(tags.contains(&url("/r/f"), "keep"));
+		assert!(tags.put(&url("/r/f"), "keep", false));
+		assert!(tags.entries.is_empty());
+	}
+}
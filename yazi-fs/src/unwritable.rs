@@ -0,0 +1,72 @@
+use std::{fmt::Display, io, path::{Path, PathBuf}};
+
+use tokio::task::spawn_blocking;
+
+use crate::Mount;
+
+/// Why nothing can be written into a directory, which holds for anything else
+/// going there as well, so a batch of operations can be aborted as a whole
+/// rather than failing one by one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Unwritable {
+	/// The mount point of the read-only filesystem.
+	ReadOnly(PathBuf),
+	/// The directory that can't be written into.
+	Denied(PathBuf),
+}
+
+impl Unwritable {
+	/// Tells from the error `e` of writing into the directory `dir`, or [`None`]
+	/// if it's about that file alone.
+	///
+	/// The mount of `dir` is looked up, and its permissions are checked, off the
+	/// async runtime.
+	pub async fn of(e: &io::Error, dir: &Path) -> Option<Self> {
+		let dir = dir.to_owned();
+		if Self::read_only(e) {
+			return spawn_blocking(move || {
+				Self::ReadOnly(Mount::of(&dir).map_or_else(|| dir.clone(), |m| m.point))
+			})
+			.await
+			.ok();
+		}
+
+		// The file itself may be the one denied, so make sure it's the directory
+		#[cfg(unix)]
+		if e.kind() == io::ErrorKind::PermissionDenied {
+			use std::{ffi::CString, os::unix::ffi::OsStrExt};
+			let c = CString::new(dir.as_os_str().as_bytes()).ok()?;
+			let denied = spawn_blocking(move || unsafe { libc::access(c.as_ptr(), libc::W_OK) } != 0);
+			if denied.await.ok()? {
+				return Some(Self::Denied(dir));
+			}
+		}
+		None
+	}
+
+	/// Whether writing into `dir` runs into it as well.
+	#[inline]
+	pub fn covers(&self, dir: &Path) -> bool {
+		match self {
+			Self::ReadOnly(p) => dir.starts_with(p),
+			Self::Denied(p) => dir == p,
+		}
+	}
+
+	// EROFS on Unix, and ERROR_WRITE_PROTECT on Windows
+	fn read_only(e: &io::Error) -> bool {
+		#[cfg(unix)]
+		return e.raw_os_error() == Some(libc::EROFS);
+		#[cfg(windows)]
+		return e.raw_os_error() == Some(19);
+	}
+}
+
+impl Display for Unwritable {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::ReadOnly(p) => write!(f, "`{}` is mounted read-only", p.display()),
+			Self::Denied(p) => write!(f, "No permission to write into `{}`", p.display()),
+		}
+	}
+}
@@ -12,8 +12,12 @@ end
 
 function Current:empty()
 	local text
+	local err = self._folder.stage.error
 	if self._folder.files.filter then
 		text = ui.Text("No filter results")
+	elseif err then
+		local what = self._folder.cwd.is_search and "search" or "load the directory"
+		text = ui.Text(string.format("Failed to %s: %s", what, err)):style(THEME.manager.error)
 	else
 		text = ui.Text(self._folder.stage.is_loading and "Loading..." or "No items")
 	end
@@ -23,6 +27,15 @@ function Current:empty()
 	}
 end
 
+-- Shown after the last file, if the directory was only read partway through
+function Current:truncated()
+	local f = self._folder
+	if not f.stage.is_truncated or #f.window >= self._area.h or f.offset + #f.window < #f.files then
+		return
+	end
+	return ui.Line(string.format("Truncated: %s", f.stage.error)):style(THEME.manager.error)
+end
+
 function Current:render()
 	local files = self._folder.window
 	if #files == 0 then
@@ -34,6 +47,7 @@ function Current:render()
 		entities[#entities + 1] = Entity:new(f):render()
 		linemodes[#linemodes + 1] = Linemode:new(f):render()
 	end
+	entities[#entities + 1] = self:truncated()
 
 	return {
 		ui.List(entities):area(self._area),
@@ -43,7 +57,7 @@ end
 
 -- Mouse events
 function Current:click(event, up)
-	if up or event.is_middle then
+	if up then
 		return
 	end
 
@@ -54,9 +68,7 @@ function Current:click(event, up)
 	end
 
 	ya.manager_emit("arrow", { y + f.offset - f.hovered.idx })
-	if event.is_right then
-		ya.manager_emit("open", {})
-	end
+	return self._id
 end
 
 function Current:scroll(event, step) ya.manager_emit("arrow", { step }) end
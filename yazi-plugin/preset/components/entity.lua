@@ -3,6 +3,7 @@ Entity = {
 	_children = {
 		{ "space", id = 1, order = 1000 },
 		{ "icon", id = 2, order = 2000 },
+		{ "tags", id = 7, order = 2500 },
 		{ "prefix", id = 3, order = 3000 },
 		{ "highlights", id = 4, order = 4000 },
 		{ "found", id = 5, order = 5000 },
@@ -15,6 +16,14 @@ function Entity:new(file) return setmetatable({ _file = file }, { __index = self
 function Entity:space() return ui.Line(" ") end
 
 function Entity:icon()
+	local thumb, id = nil, nil
+	if MANAGER.thumbnails and self._file:in_current() then
+		thumb, id = self._file:thumb()
+	end
+	if thumb then
+		return ui.Line(thumb .. " "):fg(id)
+	end
+
 	local icon = self._file:icon()
 	if not icon then
 		return ui.Line("")
@@ -25,6 +34,17 @@ function Entity:icon()
 	end
 end
 
+function Entity:tags()
+	local spans = {}
+	for _, tag in ipairs(self._file:tags()) do
+		spans[#spans + 1] = ui.Span(THEME.tag.symbol):style(THEME.tag.styles[tag] or THEME.tag.default)
+	end
+	if #spans > 0 then
+		spans[#spans + 1] = ui.Span(" ")
+	end
+	return ui.Line(spans)
+end
+
 function Entity:prefix()
 	local prefix = self._file:prefix() or ""
 	return ui.Line(prefix ~= "" and prefix .. "/" or "")
@@ -86,6 +106,12 @@ end
 
 function Entity:style()
 	local s = self._file:style()
+	if self._file:is_unmatched() then
+		s = (s or ui.Style()):dim()
+	end
+	if self._file:is_dimmed() then
+		s = s and s:patch(THEME.manager.hidden) or THEME.manager.hidden
+	end
 	if not self._file:is_hovered() then
 		return s
 	elseif self._file:in_preview() then
@@ -0,0 +1,28 @@
+Footer = {
+	_id = "footer",
+}
+
+function Footer:new(area, tab)
+	return setmetatable({
+		_area = area,
+		_tab = tab,
+	}, { __index = self })
+end
+
+function Footer:render()
+	local line = self._tab.preview.footer
+	if not line then
+		return {}
+	end
+
+	return {
+		ui.Text(" " .. line):area(self._area):style(ui.Style():fg("darkgray")),
+	}
+end
+
+-- Mouse events
+function Footer:click(event, up) end
+
+function Footer:scroll(event, step) end
+
+function Footer:touch(event, step) end
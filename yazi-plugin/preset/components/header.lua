@@ -8,11 +8,14 @@ Header = {
 		{ "cwd", id = 1, order = 1000 },
 	},
 	_right = {
+		{ "loading", id = 3, order = 500 },
 		{ "count", id = 1, order = 1000 },
 		{ "tabs", id = 2, order = 2000 },
 	},
 }
 
+local SPINNER = { "⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏" }
+
 function Header:new(area, tab)
 	return setmetatable({
 		_area = area,
@@ -31,17 +34,39 @@ function Header:cwd()
 end
 
 function Header:flags()
-	local cwd = self._tab.current.cwd
-	local filter = self._tab.current.files.filter
-
-	local s = cwd.is_search and string.format(" (search: %s", cwd:frag()) or ""
-	if not filter then
-		return s == "" and s or s .. ")"
-	elseif s == "" then
-		return string.format(" (filter: %s)", tostring(filter))
-	else
-		return string.format("%s, filter: %s)", s, tostring(filter))
+	local folder = self._tab.current
+	local filter = folder.files.filter
+
+	local flags = {}
+	if folder.cwd.is_search then
+		flags[#flags + 1] = string.format("search: %s", folder.cwd:frag())
+	end
+	if filter then
+		flags[#flags + 1] = string.format("filter: %s, %d matched", tostring(filter), filter.matched)
 	end
+	if folder.sort_override then
+		flags[#flags + 1] = string.format("sort: %s", folder.sort_override)
+	end
+	return #flags == 0 and "" or string.format(" (%s)", table.concat(flags, ", "))
+end
+
+function Header:loading()
+	local folder = self._tab.current
+	local elapsed = folder.stage.elapsed
+
+	-- Only shown once it takes a while, to avoid flickering for fast directories
+	if not folder.stage.is_loading or not elapsed or elapsed < 0.2 then
+		return ui.Line {}
+	end
+
+	local s = string.format("%s %.1fs", SPINNER[math.floor(elapsed * 10) % #SPINNER + 1], elapsed)
+	if folder.cwd.is_search then
+		s = string.format("%s, %d found", s, #folder.files)
+	end
+	return ui.Line {
+		ui.Span(s):style(THEME.manager.cwd),
+		ui.Span(" "),
+	}
 end
 
 function Header:count()
@@ -48,12 +48,17 @@ function Linemode:mtime()
 	if time == 0 then
 		return ui.Line("")
 	elseif os.date("%Y", time) == os.date("%Y") then
-		return ui.Line(os.date("%m/%d %H:%M", time))
+		return ui.Line(os.date(MANAGER.mtime_format, time))
 	else
-		return ui.Line(os.date("%m/%d  %Y", time))
+		return ui.Line(os.date(MANAGER.mtime_format_past, time))
 	end
 end
 
+function Linemode:mtime_relative()
+	local time = self._file.cha.mtime
+	return ui.Line(time and ya.readable_age(time) or "")
+end
+
 function Linemode:permissions() return ui.Line(self._file.cha:perm() or "") end
 
 function Linemode:owner()
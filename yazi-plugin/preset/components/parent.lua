@@ -38,6 +38,7 @@ function Parent:click(event, up)
 	else
 		ya.manager_emit("leave", {})
 	end
+	return self._id
 end
 
 function Parent:scroll(event, step) end
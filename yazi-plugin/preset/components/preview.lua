@@ -25,6 +25,7 @@ function Preview:click(event, up)
 	else
 		ya.manager_emit("enter", {})
 	end
+	return self._id
 end
 
 function Preview:scroll(event, step) ya.manager_emit("seek", { step }) end
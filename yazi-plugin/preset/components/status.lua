@@ -6,8 +6,10 @@ Status = {
 	_inc = 1000,
 	_left = {
 		{ "mode", id = 1, order = 1000 },
+		{ "recording", id = 7, order = 1500 },
 		{ "size", id = 2, order = 2000 },
 		{ "name", id = 3, order = 3000 },
+		{ "find", id = 8, order = 4000 },
 	},
 	_right = {
 		{ "permissions", id = 4, order = 1000 },
@@ -44,6 +46,18 @@ function Status:mode()
 	}
 end
 
+function Status:recording()
+	if not cx.recording then
+		return ui.Line {}
+	end
+
+	local style = self:style()
+	return ui.Line {
+		ui.Span(" @" .. cx.recording .. " "):fg(style.bg):bg(THEME.status.separator_style.bg):bold(),
+		ui.Span(THEME.status.separator_close):fg(THEME.status.separator_style.fg),
+	}
+end
+
 function Status:size()
 	local h = self._tab.current.hovered
 	if not h then
@@ -51,8 +65,9 @@ function Status:size()
 	end
 
 	local style = self:style()
+	local size = h.cha.is_unavailable and "unavailable" or ya.readable_size(h:size() or h.cha.len)
 	return ui.Line {
-		ui.Span(" " .. ya.readable_size(h:size() or h.cha.len) .. " "):fg(style.bg):bg(THEME.status.separator_style.bg),
+		ui.Span(" " .. size .. " "):fg(style.bg):bg(THEME.status.separator_style.bg),
 		ui.Span(THEME.status.separator_close):fg(THEME.status.separator_style.fg),
 	}
 end
@@ -66,6 +81,15 @@ function Status:name()
 	return ui.Line(" " .. h.name)
 end
 
+function Status:find()
+	local finder = self._tab.finder
+	if not finder or not finder.global then
+		return ui.Line {}
+	end
+
+	return ui.Line(string.format("  [found in %s]", finder.pane)):style(THEME.manager.find_position)
+end
+
 function Status:permissions()
 	local h = self._tab.current.hovered
 	if not h then
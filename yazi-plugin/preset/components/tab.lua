@@ -10,23 +10,36 @@ function Tab:new(area, tab)
 end
 
 function Tab:layout()
+	local ratio = MANAGER.ratio
+	local narrow = PREVIEW.peek_footer and self._area.w < PREVIEW.peek_footer_under
 	self._chunks = ui.Layout()
 		:direction(ui.Layout.HORIZONTAL)
 		:constraints({
-			ui.Constraint.Ratio(MANAGER.ratio.parent, MANAGER.ratio.all),
-			ui.Constraint.Ratio(MANAGER.ratio.current, MANAGER.ratio.all),
-			ui.Constraint.Ratio(MANAGER.ratio.preview, MANAGER.ratio.all),
+			ui.Constraint.Ratio(ratio.parent, narrow and ratio.parent + ratio.current or ratio.all),
+			ui.Constraint.Ratio(ratio.current, narrow and ratio.parent + ratio.current or ratio.all),
+			ui.Constraint.Ratio(narrow and 0 or ratio.preview, ratio.all),
 		})
 		:split(self._area)
+
+	-- The footer takes the last line of the current column
+	if PREVIEW.peek_footer then
+		self._rows = ui.Layout()
+			:direction(ui.Layout.VERTICAL)
+			:constraints({ ui.Constraint.Fill(1), ui.Constraint.Length(1) })
+			:split(self._chunks[2])
+	end
 end
 
 function Tab:build()
 	self._children = {
 		Parent:new(self._chunks[1]:padding(ui.Padding.x(1)), self._tab),
-		Current:new(self._chunks[2], self._tab),
+		Current:new(self._rows and self._rows[1] or self._chunks[2], self._tab),
 		Preview:new(self._chunks[3]:padding(ui.Padding.x(1)), self._tab),
 		Rail:new(self._chunks, self._tab),
 	}
+	if self._rows then
+		self._children[#self._children + 1] = Footer:new(self._rows[2], self._tab)
+	end
 end
 
 function Tab:render()
@@ -1,7 +1,14 @@
 local M = {}
 
+-- Remote navigation (`dds-cd`, `dds-reveal` and `dds-open`) is handled
+-- natively, see `yazi_dds::Remote`.
 function M:setup()
-	ps.sub_remote("dds-cd", function(url) ya.manager_emit("cd", { url }) end)
+	ps.sub_remote("dds-select", function(urls)
+		ya.manager_emit("escape", { select = true })
+		for _, url in ipairs(type(urls) == "table" and urls or { urls }) do
+			ya.manager_emit("toggle", { "on", url = url })
+		end
+	end)
 end
 
 return M
@@ -16,13 +16,58 @@ end
 
 function M:seek() end
 
-function M:spot(skip)
-	local rect = ui.Rect { x = 10, y = 10, w = 20, h = 20 }
+function M:spot()
+	local cmd = os.getenv("YAZI_FILE_ONE") or "file"
+	local output = Command(cmd):args({ "-bL", "--", tostring(self.file.url) }):stdout(Command.PIPED):output()
+
+	local cha = self.file.cha
+	local lines = {
+		"Mimetype:    " .. self._mime,
+		"Description: " .. (output and output.stdout:gsub("%s+$", "") or "-"),
+		"Size:        " .. ya.readable_size(cha.len),
+		"Modified:    " .. (cha.mtime and os.date("%Y-%m-%d %H:%M:%S", math.floor(cha.mtime)) or "-"),
+		"Permissions: " .. (cha:perm() or "-"),
+	}
+
+	local keys = {}
+	local attrs = fs.xattrs(self.file.url) or {}
+	if #attrs > 0 then
+		lines[#lines + 1] = ""
+		lines[#lines + 1] = "Extended attributes:"
+	end
+	for _, attr in ipairs(attrs) do
+		lines[#lines + 1] = M.xattr_line(attr)
+		if attr.name == "com.apple.quarantine" then
+			keys[#keys + 1] = { on = "u", desc = "Remove the quarantine attribute" }
+		end
+	end
 
 	ya.spot_widgets(self, {
-		ui.Clear(rect),
-		ui.Table(rect),
-	})
+		ui.Text(table.concat(lines, "\n", math.min(self.skip + 1, #lines))):area(self.area):wrap(ui.Text.WRAP),
+	}, keys)
+end
+
+function M:spot_key(key)
+	if key == "u" then
+		ya.manager_emit("unquarantine", {})
+	end
+end
+
+function M.xattr_line(attr)
+	if attr.tags then
+		local tags = {}
+		for _, t in ipairs(attr.tags) do
+			tags[#tags + 1] = t.color and string.format("%s (%s)", t.name, t.color) or t.name
+		end
+		return "  Tags: " .. (#tags > 0 and table.concat(tags, ", ") or "-")
+	end
+
+	-- Binary values such as plists aren't worth showing as they are
+	local value = attr.value:gsub("\0$", "")
+	if not utf8.len(value) or value:find("[\0-\8\14-\31\127]") then
+		value = string.format("<%d bytes>", #attr.value)
+	end
+	return string.format("  %s: %s", attr.name, ya.truncate(value, { max = 60 }))
 end
 
 return M
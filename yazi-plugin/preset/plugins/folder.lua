@@ -6,21 +6,29 @@ function M:peek()
 		return
 	end
 
-	local bound = math.max(0, #folder.files - self.area.h)
+	local bound = M.bound(folder, self.area)
 	if self.skip > bound then
 		return ya.manager_emit("peek", { bound, only_if = self.file.url, upper_bound = true })
 	end
 
+	local err = folder.stage.error
 	if #folder.files == 0 then
-		return ya.preview_widgets(self, {
-			ui.Text(folder.stage.is_loading and "Loading..." or "No items"):area(self.area):align(ui.Text.CENTER),
-		})
+		local text
+		if err then
+			text = ui.Text(string.format("Failed to load the directory: %s", err)):style(THEME.manager.error)
+		else
+			text = ui.Text(folder.stage.is_loading and "Loading..." or "No items")
+		end
+		return ya.preview_widgets(self, { text:area(self.area):align(ui.Text.CENTER) })
 	end
 
 	local items = {}
 	for _, f in ipairs(folder.window) do
 		items[#items + 1] = Entity:new(f):render()
 	end
+	if folder.stage.is_truncated and #items < self.area.h and folder.offset + #items >= #folder.files then
+		items[#items + 1] = ui.Line(string.format("Truncated: %s", err)):style(THEME.manager.error)
+	end
 
 	ya.preview_widgets(self, {
 		ui.List(items):area(self.area),
@@ -32,7 +40,7 @@ function M:seek(units)
 	local folder = cx.active.preview.folder
 	if folder and folder.cwd == self.file.url then
 		local step = math.floor(units * self.area.h / 10)
-		local bound = math.max(0, #folder.files - self.area.h)
+		local bound = M.bound(folder, self.area)
 		ya.manager_emit("peek", {
 			ya.clamp(0, cx.active.preview.skip + step, bound),
 			only_if = self.file.url,
@@ -40,4 +48,62 @@ function M:seek(units)
 	end
 end
 
+-- One more row for the truncated indicator, if any, so it can be scrolled to
+function M.bound(folder, area)
+	local rows = #folder.files + (folder.stage.is_truncated and 1 or 0)
+	return math.max(0, rows - area.h)
+end
+
+local SPINNER = { "⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏" }
+
+function M:spot()
+	local rx, frame = fs.dir_stats(self.file.url), 0
+	repeat
+		local stats = rx:recv()
+		frame = frame % #SPINNER + 1
+		self:spot_render(stats, SPINNER[frame])
+	until stats.done and stats.mount ~= nil
+end
+
+function M:spot_render(stats, spinner)
+	local pending = stats.done and "" or " " .. spinner
+	local cha = self.file.cha
+
+	local mount = spinner
+	if stats.mount then
+		mount = string.format("%s on %s (%s)", stats.mount.kind, stats.mount.point, stats.mount.source)
+	elseif stats.mount == false then
+		mount = "-"
+	end
+
+	local newest = stats.done and "-" or spinner
+	if stats.newest then
+		local name = stats.newest.url:strip_prefix(self.file.url) or stats.newest.url
+		newest = string.format("%s (%s)%s", name, os.date("%Y-%m-%d %H:%M:%S", math.floor(stats.newest.mtime)), pending)
+	end
+
+	local lines = {
+		"Total size:  " .. ya.readable_size(stats.len) .. pending,
+		"Files:       " .. stats.files .. pending,
+		"Directories: " .. stats.dirs .. pending,
+		"Newest file: " .. newest,
+		"Modified:    " .. (cha.mtime and os.date("%Y-%m-%d %H:%M:%S", math.floor(cha.mtime)) or "-"),
+		"Permissions: " .. (cha:perm() or "-"),
+		"Filesystem:  " .. mount,
+	}
+
+	if #stats.largest > 0 then
+		lines[#lines + 1] = ""
+		lines[#lines + 1] = "Largest files:" .. pending
+	end
+	for _, f in ipairs(stats.largest) do
+		local name = f.url:strip_prefix(self.file.url) or f.url
+		lines[#lines + 1] = string.format("  %8s  %s", ya.readable_size(f.len), name)
+	end
+
+	ya.spot_widgets(self, {
+		ui.Text(table.concat(lines, "\n", math.min(self.skip + 1, #lines))):area(self.area):wrap(ui.Text.WRAP),
+	})
+end
+
 return M
@@ -1,28 +1,71 @@
-local state = ya.sync(function() return cx.active.current.cwd end)
+local state = ya.sync(function(st)
+	return {
+		cwd = tostring(cx.active.current.cwd),
+		fd = st.fd,
+	}
+end)
+
+local set_state = ya.sync(function(st, fd) st.fd = fd end)
 
 local function fail(s, ...) ya.notify { title = "Fzf", content = string.format(s, ...), timeout = 5, level = "error" } end
 
-local function entry()
-	local _permit = ya.hide()
-	local cwd = tostring(state())
+-- Lists both the files and directories with `fd` if it's available, unless there's a `FZF_DEFAULT_COMMAND` already
+local function source(st)
+	if os.getenv("FZF_DEFAULT_COMMAND") then
+		return nil
+	end
+
+	if st.fd == nil then
+		local status = Command("fd"):arg("--version"):stdout(Command.NULL):stderr(Command.NULL):status()
+		st.fd = status ~= nil and status.success
+		set_state(st.fd)
+	end
+	return st.fd and "fd --hidden --follow --exclude .git --type f --type d --type l" or nil
+end
+
+local function entry(_, args)
+	local open = false
+	for _, arg in ipairs(args or {}) do
+		open = open or arg == "--open"
+	end
+
+	local st = state()
+	local src = source(st)
 
-	local child, err =
-		Command("fzf"):cwd(cwd):stdin(Command.INHERIT):stdout(Command.PIPED):stderr(Command.INHERIT):spawn()
+	local permit = ya.hide()
+	local cmd = Command("fzf"):cwd(st.cwd):stdin(Command.INHERIT):stdout(Command.PIPED):stderr(Command.INHERIT)
+	if src then
+		cmd = cmd:env("FZF_DEFAULT_COMMAND", src)
+	end
 
+	local child, err = cmd:spawn()
 	if not child then
+		permit:drop()
 		return fail("Spawn `fzf` failed with error code %s. Do you have it installed?", err)
 	end
 
+	-- Restore the TUI before anything else, no matter how `fzf` exits
 	local output, err = child:wait_with_output()
+	permit:drop()
+
+	-- 1 for no match, and 130 for cancelled with `Esc` or `Ctrl-C`
 	if not output then
 		return fail("Cannot read `fzf` output, error code %s", err)
-	elseif not output.status.success and output.status.code ~= 130 then
+	elseif not output.status.success and output.status.code ~= 1 and output.status.code ~= 130 then
 		return fail("`fzf` exited with error code %s", output.status.code)
 	end
 
-	local target = output.stdout:gsub("\n$", "")
-	if target ~= "" then
-		ya.manager_emit(target:find("[/\\]$") and "cd" or "reveal", { target })
+	local target = output.stdout:gsub("[\r\n]+$", "")
+	if target == "" then
+		return
+	end
+
+	local url = Url(st.cwd):join(target)
+	local cha = fs.cha(url, true)
+	if cha and cha.is_dir then
+		ya.manager_emit("cd", { tostring(url) })
+	else
+		ya.manager_emit("reveal", { tostring(url), open = open })
 	end
 end
 
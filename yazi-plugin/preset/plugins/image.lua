@@ -22,4 +22,31 @@ function M:preload()
 	return ya.image_precache(self.file.url, cache) and 1 or 2
 end
 
+function M:spot()
+	local info, cha = ya.image_info(self.file.url), self.file.cha
+	local lines = {
+		"Mimetype:    " .. self._mime,
+		"Dimensions:  " .. (info and string.format("%dx%d", info.w, info.h) or "-"),
+		"Size:        " .. ya.readable_size(cha.len),
+		"Modified:    " .. (cha.mtime and os.date("%Y-%m-%d %H:%M:%S", math.floor(cha.mtime)) or "-"),
+	}
+
+	-- The image follows the lines, and scrolls along with them
+	local skip = math.min(self.skip, #lines)
+	local top = #lines - skip + 1
+	ya.spot_widgets(self, {
+		ui.Text(table.concat(lines, "\n", skip + 1)):area(self.area),
+	})
+
+	local area = ui.Rect {
+		x = self.area.x,
+		y = self.area.y + top,
+		w = self.area.w,
+		h = math.max(0, self.area.h - top),
+	}
+	if area.h > 0 then
+		ya.image_show(self.file.url, area)
+	end
+end
+
 return M
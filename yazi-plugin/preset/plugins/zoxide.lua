@@ -10,7 +10,7 @@ local set_state = ya.sync(function(st, empty) st.empty = empty end)
 local function fail(s, ...) ya.notify { title = "Zoxide", content = s:format(...), timeout = 5, level = "error" } end
 
 local function head(cwd)
-	local child = Command("zoxide"):args({ "query", "-l" }):stdout(Command.PIPED):spawn()
+	local child = Command("zoxide"):cwd(cwd):args({ "query", "-l" }):stdout(Command.PIPED):spawn()
 	if not child then
 		return 0
 	end
@@ -57,8 +57,10 @@ local function entry()
 		return fail("No directory history in the database, check out the `zoxide` docs to set it up.")
 	end
 
-	local _permit = ya.hide()
+	-- Query from the current directory, which `--exclude` keeps out of the candidates
+	local permit = ya.hide()
 	local child, err = Command("zoxide")
+		:cwd(st.cwd)
 		:args({ "query", "-i", "--exclude" })
 		:arg(st.cwd)
 		:stdin(Command.INHERIT)
@@ -67,17 +69,21 @@ local function entry()
 		:spawn()
 
 	if not child then
+		permit:drop()
 		return fail("Spawn `zoxide` failed with error code %s. Do you have it installed?", err)
 	end
 
 	local output, err = child:wait_with_output()
+	permit:drop()
+
+	-- 1 for no match, and 130 for cancelled with `Esc` or `Ctrl-C`
 	if not output then
 		return fail("Cannot read `zoxide` output, error code %s", err)
-	elseif not output.status.success and output.status.code ~= 130 then
+	elseif not output.status.success and output.status.code ~= 1 and output.status.code ~= 130 then
 		return fail("`zoxide` exited with error code %s", output.status.code)
 	end
 
-	local target = output.stdout:gsub("\n$", "")
+	local target = output.stdout:gsub("[\r\n]+$", "")
 	if target ~= "" then
 		ya.manager_emit("cd", { target })
 	end
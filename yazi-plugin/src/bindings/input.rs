@@ -1,20 +1,36 @@
 use std::pin::Pin;
 
-use mlua::{UserData, prelude::LuaUserDataMethods};
+use mlua::{Function, Lua, RegistryKey, UserData, Value, prelude::LuaUserDataMethods};
 use tokio::pin;
 use tokio_stream::StreamExt;
+use yazi_proxy::InputProxy;
 use yazi_shared::errors::InputError;
 
 pub struct InputRx<T: StreamExt<Item = Result<String, InputError>>> {
-	inner: T,
+	inner:     T,
+	validator: Option<RegistryKey>,
 }
 
 impl<T: StreamExt<Item = Result<String, InputError>>> InputRx<T> {
-	pub fn new(inner: T) -> Self { Self { inner } }
+	pub fn new(inner: T) -> Self { Self { inner, validator: None } }
 
-	pub async fn consume(inner: T) -> (Option<String>, u8) {
+	#[inline]
+	pub fn with_validator(mut self, validator: Option<RegistryKey>) -> Self {
+		self.validator = validator;
+		self
+	}
+
+	pub async fn consume(self, lua: &Lua) -> mlua::Result<(Option<String>, u8)> {
+		let Self { inner, validator } = self;
 		pin!(inner);
-		inner.next().await.map(Self::parse).unwrap_or((None, 0))
+
+		while let Some(res) = inner.next().await {
+			match res {
+				Err(InputError::Typed(s)) => Self::validate(lua, validator.as_ref(), &s).await?,
+				res => return Ok(Self::parse(res)),
+			}
+		}
+		Ok((None, 0))
 	}
 
 	fn parse(res: Result<String, InputError>) -> (Option<String>, u8) {
@@ -25,13 +41,32 @@ impl<T: StreamExt<Item = Result<String, InputError>>> InputRx<T> {
 			_ => (None, 0),
 		}
 	}
+
+	/// Runs the validator against the typed value, and shows the error message it
+	/// returns, if any, inline in the input. Submitting is refused while an error
+	/// is being shown.
+	async fn validate(lua: &Lua, validator: Option<&RegistryKey>, value: &str) -> mlua::Result<()> {
+		let Some(key) = validator else { return Ok(()) };
+
+		let error = match lua.registry_value::<Function>(key)?.call_async::<_, Value>(value).await {
+			Ok(Value::String(s)) => Some(s.to_string_lossy().into_owned()),
+			Ok(_) => None,
+			Err(e) => Some(e.to_string()),
+		};
+
+		InputProxy::error(value, error.as_deref());
+		Ok(())
+	}
 }
 
 impl<T: StreamExt<Item = Result<String, InputError>> + 'static> UserData for InputRx<T> {
 	fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
-		methods.add_async_method_mut("recv", |_, me, ()| async move {
-			let mut inner = unsafe { Pin::new_unchecked(&mut me.inner) };
-			Ok(inner.next().await.map(Self::parse).unwrap_or((None, 0)))
+		methods.add_async_method_mut("recv", |lua, me, ()| async move {
+			let res = unsafe { Pin::new_unchecked(&mut me.inner) }.next().await;
+			if let Some(Err(InputError::Typed(s))) = &res {
+				Self::validate(lua, me.validator.as_ref(), s).await?;
+			}
+			Ok(res.map(Self::parse).unwrap_or((None, 0)))
 		});
 	}
 }
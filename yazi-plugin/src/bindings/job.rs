@@ -0,0 +1,37 @@
+use mlua::{ExternalError, UserData, UserDataFields, UserDataMethods};
+use yazi_proxy::options::PluginJob;
+
+pub struct Job {
+	inner: Option<PluginJob>,
+}
+
+impl Job {
+	#[inline]
+	pub fn new(inner: PluginJob) -> Self { Self { inner: Some(inner) } }
+
+	fn inner(&self) -> mlua::Result<&PluginJob> {
+		self.inner.as_ref().ok_or_else(|| "job has already finished".into_lua_err())
+	}
+}
+
+impl UserData for Job {
+	fn add_fields<'lua, F: UserDataFields<'lua, Self>>(fields: &mut F) {
+		fields.add_field_method_get("id", |_, me| Ok(me.inner()?.id));
+		fields.add_field_method_get("canceled", |_, me| {
+			Ok(match &me.inner {
+				Some(j) => j.canceled(),
+				None => true,
+			})
+		});
+	}
+
+	fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+		methods
+			.add_method("progress", |_, me, n: Option<u64>| Ok(me.inner()?.progress(n.unwrap_or(1))));
+		methods.add_method("log", |_, me, line: String| Ok(me.inner()?.log(line)));
+		methods.add_method_mut("finish", |_, me, error: Option<String>| {
+			me.inner.take().ok_or_else(|| "job has already finished".into_lua_err())?.finish(error);
+			Ok(())
+		});
+	}
+}
@@ -1,3 +1,3 @@
 #![allow(clippy::module_inception)]
 
-yazi_macro::mod_flat!(bindings icon input mouse permit position range window);
+yazi_macro::mod_flat!(bindings icon input job mouse permit position range window);
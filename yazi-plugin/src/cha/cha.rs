@@ -24,6 +24,7 @@ Please use the new `btime`, `mtime`, `atime`, `len`, and `perm` instead, in your
 			content: s.replace("{id}", &id),
 			level:   yazi_proxy::options::NotifyLevel::Warn,
 			timeout: Duration::from_secs(20),
+			actions: vec![],
 		});
 	}
 }
@@ -36,6 +37,7 @@ impl Cha {
 			reg.add_field_method_get("is_link", |_, me| Ok(me.is_link()));
 			reg.add_field_method_get("is_orphan", |_, me| Ok(me.is_orphan()));
 			reg.add_field_method_get("is_dummy", |_, me| Ok(me.is_dummy()));
+			reg.add_field_method_get("is_unavailable", |_, me| Ok(me.is_unavailable()));
 			reg.add_field_method_get("is_block", |_, me| Ok(me.is_block()));
 			reg.add_field_method_get("is_char", |_, me| Ok(me.is_char()));
 			reg.add_field_method_get("is_fifo", |_, me| Ok(me.is_fifo()));
@@ -1,7 +1,9 @@
-use std::ffi::OsString;
+use std::ffi::{OsStr, OsString};
 
 use parking_lot::Mutex;
-use yazi_shared::RoCell;
+use yazi_config::{MANAGER, manager::ClipboardBackend};
+use yazi_proxy::AppProxy;
+use yazi_shared::{RoCell, in_ssh_connection};
 
 pub static CLIPBOARD: RoCell<Clipboard> = RoCell::new();
 
@@ -16,9 +18,8 @@ impl Clipboard {
 		use std::os::unix::prelude::OsStringExt;
 
 		use tokio::process::Command;
-		use yazi_shared::in_ssh_connection;
 
-		if in_ssh_connection() {
+		if MANAGER.clipboard == ClipboardBackend::Osc52 || in_ssh_connection() {
 			return self.content.lock().clone();
 		}
 
@@ -44,6 +45,10 @@ impl Clipboard {
 	pub async fn get(&self) -> OsString {
 		use clipboard_win::{formats, get_clipboard};
 
+		if MANAGER.clipboard == ClipboardBackend::Osc52 {
+			return self.content.lock().clone();
+		}
+
 		let result = tokio::task::spawn_blocking(|| get_clipboard::<String, _>(formats::Unicode));
 		if let Ok(Ok(s)) = result.await {
 			return s.into();
@@ -52,16 +57,28 @@ impl Clipboard {
 		self.content.lock().clone()
 	}
 
+	pub async fn set(&self, s: impl AsRef<OsStr>) {
+		let s = s.as_ref();
+		s.clone_into(&mut self.content.lock());
+
+		match MANAGER.clipboard {
+			ClipboardBackend::Auto if in_ssh_connection() => Self::set_osc52(s),
+			ClipboardBackend::Auto => {
+				if !Self::set_native(s).await {
+					Self::set_osc52(s);
+				}
+			}
+			ClipboardBackend::Native => _ = Self::set_native(s).await,
+			ClipboardBackend::Osc52 => Self::set_osc52(s),
+		}
+	}
+
 	#[cfg(unix)]
-	pub async fn set(&self, s: impl AsRef<std::ffi::OsStr>) {
-		use std::{io::{BufWriter, stderr}, process::Stdio};
+	async fn set_native(s: &OsStr) -> bool {
+		use std::process::Stdio;
 
-		use crossterm::execute;
 		use tokio::{io::AsyncWriteExt, process::Command};
 
-		s.as_ref().clone_into(&mut self.content.lock());
-		execute!(BufWriter::new(stderr()), osc52::SetClipboard::new(s.as_ref())).ok();
-
 		let all = [
 			("pbcopy", &[][..]),
 			("wl-copy", &[]),
@@ -81,50 +98,133 @@ impl Clipboard {
 			let Ok(mut child) = cmd else { continue };
 
 			let mut stdin = child.stdin.take().unwrap();
-			if stdin.write_all(s.as_ref().as_encoded_bytes()).await.is_err() {
+			if stdin.write_all(s.as_encoded_bytes()).await.is_err() {
 				continue;
 			}
 			drop(stdin);
 
 			if child.wait().await.map(|s| s.success()).unwrap_or_default() {
-				break;
+				return true;
 			}
 		}
+		false
 	}
 
 	#[cfg(windows)]
-	pub async fn set(&self, s: impl AsRef<std::ffi::OsStr>) {
+	async fn set_native(s: &OsStr) -> bool {
 		use clipboard_win::{formats, set_clipboard};
 
-		let s = s.as_ref().to_owned();
-		*self.content.lock() = s.clone();
+		let s = s.to_owned();
+		let result =
+			tokio::task::spawn_blocking(move || set_clipboard(formats::Unicode, s.to_string_lossy()));
+		matches!(result.await, Ok(Ok(())))
+	}
 
-		tokio::task::spawn_blocking(move || set_clipboard(formats::Unicode, s.to_string_lossy()))
-			.await
-			.ok();
+	fn set_osc52(s: &OsStr) {
+		use std::io::{BufWriter, Write, stderr};
+
+		let mut w = BufWriter::new(stderr().lock());
+		let result = osc52::write(&mut w, s.as_encoded_bytes(), *yazi_adapter::TMUX);
+		match result.and_then(|n| w.flush().map(|_| n)) {
+			Ok(n) if n > osc52::LIMIT => AppProxy::notify_warn(
+				"Clipboard",
+				format!("{n} bytes exceeds what most terminals accept over OSC 52, it may be dropped"),
+			),
+			Ok(_) => {}
+			Err(e) => AppProxy::notify_warn("Clipboard", format!("Failed to write OSC 52: {e}")),
+		}
 	}
 }
 
-#[cfg(unix)]
 mod osc52 {
-	use std::ffi::OsStr;
+	use std::io::{self, Write};
 
 	use base64::{Engine, engine::general_purpose};
 
-	#[derive(Debug)]
-	pub struct SetClipboard {
-		content: String,
-	}
+	/// The payload size most terminals accept in a single OSC 52 sequence.
+	pub(super) const LIMIT: usize = 100_000;
+
+	// Tmux drops passthrough sequences longer than its input buffer, so larger
+	// payloads are split into several, which the outer terminal sees as one.
+	const TMUX_CHUNK: usize = 4096;
+
+	/// Writes `content` as an OSC 52 sequence setting the clipboard, wrapped in
+	/// tmux passthrough if `tmux`, and returns the length of the encoded payload.
+	pub(super) fn write(w: &mut impl Write, content: &[u8], tmux: bool) -> io::Result<usize> {
+		let payload = general_purpose::STANDARD.encode(content);
+		let seq = format!("\x1b]52;c;{payload}\x1b\\");
+		if !tmux {
+			w.write_all(seq.as_bytes())?;
+			return Ok(payload.len());
+		}
 
-	impl SetClipboard {
-		pub fn new(content: &OsStr) -> Self {
-			Self { content: general_purpose::STANDARD.encode(content.as_encoded_bytes()) }
+		for chunk in seq.as_bytes().chunks(TMUX_CHUNK) {
+			w.write_all(b"\x1bPtmux;")?;
+			for &b in chunk {
+				w.write_all(if b == 0x1b { b"\x1b\x1b" } else { std::slice::from_ref(&b) })?;
+			}
+			w.write_all(b"\x1b\\")?;
 		}
+		Ok(payload.len())
 	}
 
-	impl crossterm::Command for SetClipboard {
-		fn write_ansi(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
-			write!(f, "\x1b]52;c;{}\x1b\\", self.content)
+	#[cfg(test)]
+	mod tests {
+		use base64::{Engine, engine::general_purpose};
+
+		use super::*;
+
+		fn unwrap_tmux(mut s: &[u8]) -> (Vec<u8>, usize) {
+			let (mut inner, mut n) = (vec![], 0);
+			while !s.is_empty() {
+				s = s.strip_prefix(b"\x1bPtmux;").expect("missing passthrough start");
+				let mut i = 0;
+				loop {
+					match (s[i], s[i + 1]) {
+						(0x1b, 0x1b) => inner.push(0x1b),
+						(0x1b, b'\\') => break,
+						(0x1b, _) => panic!("unescaped ESC in passthrough"),
+						(b, _) => {
+							inner.push(b);
+							i += 1;
+							continue;
+						}
+					}
+					i += 2;
+				}
+				assert!(i <= TMUX_CHUNK * 2);
+				(s, n) = (&s[i + 2..], n + 1);
+			}
+			(inner, n)
+		}
+
+		fn decode(seq: &[u8]) -> Vec<u8> {
+			let b64 = seq.strip_prefix(b"\x1b]52;c;").unwrap().strip_suffix(b"\x1b\\").unwrap();
+			general_purpose::STANDARD.decode(b64).unwrap()
+		}
+
+		#[test]
+		fn test_osc52_plain() {
+			let mut w = vec![];
+			let n = write(&mut w, b"hello", false).unwrap();
+			assert_eq!(w, b"\x1b]52;c;aGVsbG8=\x1b\\");
+			assert_eq!(n, 8);
+			assert_eq!(decode(&w), b"hello");
+		}
+
+		#[test]
+		fn test_osc52_tmux() {
+			let content: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+
+			let mut w = vec![];
+			write(&mut w, &content, true).unwrap();
+			let (inner, chunks) = unwrap_tmux(&w);
+			assert!(chunks > 1);
+			assert_eq!(decode(&inner), content);
+
+			let mut w = vec![];
+			write(&mut w, b"hi", true).unwrap();
+			assert_eq!(w, b"\x1bPtmux;\x1b\x1b]52;c;aGk=\x1b\x1b\\\x1b\\");
 		}
 	}
 }
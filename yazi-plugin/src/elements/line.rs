@@ -3,6 +3,7 @@ use std::mem;
 use ansi_to_tui::IntoText;
 use mlua::{AnyUserData, ExternalError, ExternalResult, FromLua, IntoLua, Lua, Table, UserData, UserDataMethods, Value};
 use unicode_width::UnicodeWidthChar;
+use yazi_shared::escape_bytes;
 
 use super::Span;
 
@@ -52,7 +53,7 @@ impl TryFrom<Value<'_>> for Line {
 	fn try_from(value: Value) -> Result<Self, Self::Error> {
 		Ok(Self(match value {
 			Value::Table(tb) => return Self::try_from(tb),
-			Value::String(s) => s.to_string_lossy().into_owned().into(),
+			Value::String(s) => escape_bytes(s.as_bytes()).into_owned().into(),
 			Value::UserData(ud) => {
 				if let Ok(span) = ud.take::<Span>() {
 					span.0.into()
@@ -75,7 +76,7 @@ impl TryFrom<Table<'_>> for Line {
 		let mut spans = Vec::with_capacity(tb.raw_len());
 		for v in tb.sequence_values() {
 			match v? {
-				Value::String(s) => spans.push(s.to_string_lossy().into_owned().into()),
+				Value::String(s) => spans.push(escape_bytes(s.as_bytes()).into_owned().into()),
 				Value::UserData(ud) => {
 					if let Ok(span) = ud.take::<Span>() {
 						spans.push(span.0);
@@ -22,6 +22,7 @@ Please use the new `ui.Text` instead, in your {id}. See https://github.com/sxyaz
 			content: s.replace("{id}", &id),
 			level:   yazi_proxy::options::NotifyLevel::Warn,
 			timeout: Duration::from_secs(20),
+			actions: vec![],
 		});
 	}
 }
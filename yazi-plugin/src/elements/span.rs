@@ -1,5 +1,6 @@
 use mlua::{ExternalError, FromLua, Lua, Table, UserData, UserDataMethods, Value};
 use unicode_width::UnicodeWidthChar;
+use yazi_shared::escape_bytes;
 
 const EXPECTED: &str = "expected a string or ui.Span";
 
@@ -17,7 +18,7 @@ impl TryFrom<Value<'_>> for Span {
 
 	fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
 		Ok(Self(match value {
-			Value::String(s) => s.to_string_lossy().into_owned().into(),
+			Value::String(s) => escape_bytes(s.as_bytes()).into_owned().into(),
 			Value::UserData(ud) => {
 				if let Ok(span) = ud.take::<Span>() {
 					span.0
@@ -1,6 +1,7 @@
 use ansi_to_tui::IntoText;
 use mlua::{AnyUserData, ExternalError, ExternalResult, FromLua, IntoLua, Lua, Table, UserData, Value};
 use ratatui::widgets::Widget;
+use yazi_shared::escape_bytes;
 
 use super::{Line, Rect, Renderable, Span};
 
@@ -58,7 +59,7 @@ impl TryFrom<Value<'_>> for Text {
 	fn try_from(value: Value) -> mlua::Result<Self> {
 		let inner = match value {
 			Value::Table(tb) => return Self::try_from(tb),
-			Value::String(s) => s.to_string_lossy().into_owned().into(),
+			Value::String(s) => escape_bytes(s.as_bytes()).into_owned().into(),
 			Value::UserData(ud) => {
 				if let Ok(line) = ud.take::<Line>() {
 					line.0.into()
@@ -81,7 +82,7 @@ impl TryFrom<Table<'_>> for Text {
 		let mut lines = Vec::with_capacity(tb.raw_len());
 		for v in tb.sequence_values() {
 			match v? {
-				Value::String(s) => lines.push(s.to_string_lossy().into_owned().into()),
+				Value::String(s) => lines.push(escape_bytes(s.as_bytes()).into_owned().into()),
 				Value::UserData(ud) => {
 					if let Ok(span) = ud.take::<Span>() {
 						lines.push(span.0.into());
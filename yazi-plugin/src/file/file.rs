@@ -31,18 +31,7 @@ impl File {
 		});
 
 		reg.add_method("icon", |lua, me, ()| {
-			use yazi_shared::theme::IconCache;
-
-			let me = me.as_ref();
-			match me.icon.get() {
-				IconCache::Missing => {
-					let matched = THEME.icons.matches(me);
-					me.icon.set(matched.map_or(IconCache::Undefined, IconCache::Icon));
-					matched.map(|i| Icon::cast(lua, i)).transpose()
-				}
-				IconCache::Undefined => Ok(None),
-				IconCache::Icon(cached) => Some(Icon::cast(lua, cached)).transpose(),
-			}
+			THEME.icons.cached(me.as_ref()).map(|i| Icon::cast(lua, i)).transpose()
 		});
 	}
 
@@ -0,0 +1,145 @@
+use std::{collections::HashMap, sync::{Arc, atomic::AtomicBool}, time::{Duration, UNIX_EPOCH}};
+
+use mlua::{IntoLua, Lua, Table, UserData, Value};
+use tokio::{fs, sync::watch};
+use yazi_fs::{DirStats, Mount, SIZE_LOADING};
+use yazi_shared::fs::FilesOp;
+
+use crate::{bindings::Cast, url::Url};
+
+#[derive(Clone, Default)]
+struct Snapshot {
+	stats: DirStats,
+	mount: Option<Option<Mount>>,
+	done:  bool,
+}
+
+/// The stats of a directory being calculated in the background, which stops
+/// as soon as it's dropped, e.g. when the spot panel showing it closes.
+pub struct DirStatsRx {
+	rx: watch::Receiver<Snapshot>,
+}
+
+impl DirStatsRx {
+	pub fn new(url: yazi_shared::fs::Url) -> Self {
+		let (tx, rx) = watch::channel(Snapshot::default());
+		let tx = Arc::new(tx);
+
+		let (tx_, path) = (tx.clone(), url.to_path_buf());
+		tokio::task::spawn_blocking(move || {
+			let mount = Mount::of(&path);
+			tx_.send_modify(|s| s.mount = Some(mount));
+		});
+
+		tokio::spawn(async move {
+			let mtime = fs::metadata(&url).await.ok().and_then(|m| m.modified().ok());
+			let stats = loop {
+				if let Some(stats) = DirStats::cached(&url, mtime) {
+					break stats;
+				}
+
+				// Its size is being calculated, whose stats are cached once done
+				let Some(walking) = Self::claim(&url) else {
+					tokio::time::sleep(Duration::from_millis(100)).await;
+					if tx.is_closed() {
+						return;
+					}
+					continue;
+				};
+
+				let report = |stats: &DirStats| tx.send_modify(|s| s.stats = stats.clone());
+				let every = Duration::from_millis(100);
+				let stats = DirStats::walk(&url, mtime, every, || tx.is_closed(), report).await;
+				Self::release(&url, &walking);
+
+				let Some(stats) = stats else { return };
+
+				// Shown in the file list as well, like `calc_size` does
+				if let Some(parent) = url.parent_url() {
+					FilesOp::Size(parent, HashMap::from_iter([(url.urn_owned(), stats.len)])).emit();
+				}
+				break stats;
+			};
+			tx.send_modify(|s| (s.stats, s.done) = (stats, true));
+		});
+
+		Self { rx }
+	}
+
+	// Takes `url` over for walking, unless it's being walked already. The flag is
+	// only there for the size calculation to tell it's taken, as the walk stops
+	// once nothing receives the stats instead.
+	fn claim(url: &yazi_shared::fs::Url) -> Option<Arc<AtomicBool>> {
+		let mut loading = SIZE_LOADING.write();
+		if loading.contains_key(url) {
+			return None;
+		}
+
+		let walking = Arc::new(AtomicBool::new(false));
+		loading.insert(url.clone(), walking.clone());
+		Some(walking)
+	}
+
+	fn release(url: &yazi_shared::fs::Url, walking: &Arc<AtomicBool>) {
+		let mut loading = SIZE_LOADING.write();
+		if loading.get(url).is_some_and(|w| Arc::ptr_eq(w, walking)) {
+			loading.remove(url);
+		}
+	}
+
+	fn snapshot<'a>(&mut self, lua: &'a Lua) -> mlua::Result<Table<'a>> {
+		let snap = self.rx.borrow_and_update().clone();
+		let DirStats { len, files, dirs, largest, newest } = snap.stats;
+
+		let largest = largest.into_iter().map(|(p, len)| {
+			lua.create_table_from([
+				("url", Url::cast(lua, p)?.into_lua(lua)?),
+				("len", len.into_lua(lua)?),
+			])
+		});
+		let newest = newest.map(|(p, t)| {
+			let mtime = t.duration_since(UNIX_EPOCH).map(|d| d.as_secs_f64()).ok();
+			lua.create_table_from([
+				("url", Url::cast(lua, p)?.into_lua(lua)?),
+				("mtime", mtime.into_lua(lua)?),
+			])
+		});
+		let mount = match snap.mount {
+			None => Value::Nil,
+			Some(None) => Value::Boolean(false),
+			Some(Some(m)) => Value::Table(lua.create_table_from([
+				("point", Url::cast(lua, m.point)?.into_lua(lua)?),
+				("source", m.source.into_lua(lua)?),
+				("kind", m.kind.into_lua(lua)?),
+			])?),
+		};
+
+		lua.create_table_from([
+			("len", len.into_lua(lua)?),
+			("files", files.into_lua(lua)?),
+			("dirs", dirs.into_lua(lua)?),
+			(
+				"largest",
+				lua.create_sequence_from(largest.collect::<mlua::Result<Vec<_>>>()?)?.into_lua(lua)?,
+			),
+			("newest", newest.transpose()?.into_lua(lua)?),
+			("mount", mount),
+			("done", snap.done.into_lua(lua)?),
+		])
+	}
+}
+
+impl UserData for DirStatsRx {
+	fn add_methods<'lua, M: mlua::UserDataMethods<'lua, Self>>(methods: &mut M) {
+		// Waits for the stats to change for at most `timeout` seconds, so that the
+		// caller gets a chance to animate the pending ones.
+		methods.add_async_method_mut("recv", |lua, me, timeout: Option<f64>| async move {
+			// Waits without a limit if it's too large, e.g. `math.huge`
+			match Duration::try_from_secs_f64(timeout.unwrap_or(0.1).max(0.0)) {
+				Ok(timeout) => _ = tokio::time::timeout(timeout, me.rx.changed()).await,
+				Err(_) => _ = me.rx.changed().await,
+			}
+			me.snapshot(lua)
+		});
+	}
+}
@@ -1,8 +1,9 @@
 use globset::GlobBuilder;
-use mlua::{ExternalError, ExternalResult, IntoLuaMulti, Lua, Table, Value};
+use mlua::{ExternalError, ExternalResult, IntoLua, IntoLuaMulti, Lua, Table, Value};
 use tokio::fs;
-use yazi_shared::fs::remove_dir_clean;
+use yazi_shared::fs::{XATTR_TAGS, finder_tags, remove_dir_clean};
 
+use super::DirStatsRx;
 use crate::{bindings::Cast, cha::Cha, file::File, url::{Url, UrlRef}};
 
 pub fn install(lua: &Lua) -> mlua::Result<()> {
@@ -108,6 +109,39 @@ pub fn install(lua: &Lua) -> mlua::Result<()> {
 					(tbl, Value::Nil).into_lua_multi(lua)
 				})?,
 			),
+			(
+				"xattrs",
+				lua.create_async_function(|lua, url: UrlRef| async move {
+					let attrs = match yazi_shared::fs::xattrs(&*url).await {
+						Ok(a) => a,
+						Err(e) => return (Value::Nil, e.raw_os_error()).into_lua_multi(lua),
+					};
+
+					let tbl = lua.create_table_with_capacity(attrs.len(), 0)?;
+					for (name, value) in attrs {
+						let attr = lua.create_table_from([
+							("name", lua.create_string(name.as_encoded_bytes())?),
+							("value", lua.create_string(&value)?),
+						])?;
+						if name == XATTR_TAGS {
+							let tags = finder_tags(&value).into_iter().map(|(name, color)| {
+								lua.create_table_from([
+									("name", name.into_lua(lua)?),
+									("color", color.into_lua(lua)?),
+								])
+							});
+							attr.raw_set(
+								"tags",
+								lua.create_sequence_from(tags.collect::<mlua::Result<Vec<_>>>()?)?,
+							)?;
+						}
+						tbl.raw_push(attr)?;
+					}
+
+					(tbl, Value::Nil).into_lua_multi(lua)
+				})?,
+			),
+			("dir_stats", lua.create_function(|_, url: UrlRef| Ok(DirStatsRx::new(url.clone())))?),
 			(
 				"unique_name",
 				lua.create_async_function(|lua, url: UrlRef| async move {
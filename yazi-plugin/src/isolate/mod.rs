@@ -1,3 +1,3 @@
 #![allow(clippy::module_inception)]
 
-yazi_macro::mod_flat!(entry fetch isolate peek preload seek);
+yazi_macro::mod_flat!(entry fetch isolate peek peeker preload seek spot);
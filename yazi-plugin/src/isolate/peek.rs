@@ -1,14 +1,14 @@
 use std::borrow::Cow;
 
-use mlua::{ExternalError, ExternalResult, HookTriggers, Table, TableExt};
-use tokio::{runtime::Handle, select};
+use mlua::{ExternalError, ExternalResult, Function, HookTriggers, Table, TableExt};
+use tokio::{runtime::Handle, select, sync::mpsc};
 use tokio_util::sync::CancellationToken;
 use tracing::error;
 use yazi_config::LAYOUT;
 use yazi_macro::emit;
 use yazi_shared::{Layer, event::Cmd};
 
-use super::slim_lua;
+use super::{PeekerEvent, slim_lua};
 use crate::{LUA, Opt, OptCallback, bindings::{Cast, Window}, elements::Rect, file::File, loader::LOADER};
 
 pub fn peek(
@@ -18,7 +18,18 @@ pub fn peek(
 	skip: usize,
 ) -> CancellationToken {
 	let ct = CancellationToken::new();
+	spawn(cmd, file, mime, skip, ct.clone(), None);
+	ct
+}
 
+pub(super) fn spawn(
+	cmd: &Cmd,
+	file: yazi_shared::fs::File,
+	mime: Cow<'static, str>,
+	skip: usize,
+	ct: CancellationToken,
+	mut events: Option<mpsc::UnboundedReceiver<PeekerEvent>>,
+) {
 	let name = cmd.name.to_owned();
 	let (ct1, ct2) = (ct.clone(), ct.clone());
 	tokio::task::spawn_blocking(move || {
@@ -44,7 +55,26 @@ pub fn peek(
 			plugin.raw_set("area", Rect::from(LAYOUT.load().preview))?;
 			plugin.raw_set("window", Window::default())?;
 
-			if ct2.is_cancelled() { Ok(()) } else { plugin.call_async_method("peek", ()).await }
+			if ct2.is_cancelled() {
+				return Ok(());
+			}
+			plugin.call_async_method::<_, ()>("peek", ()).await?;
+
+			let Some(events) = &mut events else { return Ok(()) };
+			while let Some(event) = events.recv().await {
+				match event {
+					PeekerEvent::Seek(units) => plugin.call_async_method::<_, ()>("seek", units).await?,
+					PeekerEvent::Resize(area) => {
+						plugin.raw_set("area", area)?;
+						if plugin.get::<_, Option<Function>>("resize")?.is_some() {
+							plugin.call_async_method::<_, ()>("resize", area).await?
+						} else {
+							plugin.call_async_method::<_, ()>("peek", ()).await?
+						}
+					}
+				}
+			}
+			Ok(())
 		};
 
 		let result = Handle::current().block_on(async {
@@ -60,8 +90,6 @@ pub fn peek(
 			}
 		}
 	});
-
-	ct
 }
 
 pub fn peek_sync(cmd: &Cmd, file: yazi_shared::fs::File, mime: Cow<'static, str>, skip: usize) {
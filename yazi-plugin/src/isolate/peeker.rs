@@ -0,0 +1,56 @@
+use std::borrow::Cow;
+
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use yazi_config::LAYOUT;
+use yazi_shared::{event::Cmd, fs::{Cha, Url}};
+
+use crate::elements::Rect;
+
+pub(super) enum PeekerEvent {
+	Seek(i16),
+	Resize(Rect),
+}
+
+/// A stateful previewer instance, which is created for the hovered file,
+/// and then receives `seek` and `resize` calls incrementally in the same
+/// Lua state until it's dropped.
+pub struct Peeker {
+	url:  Url,
+	cha:  Cha,
+	mime: String,
+
+	tx: mpsc::UnboundedSender<PeekerEvent>,
+	ct: CancellationToken,
+}
+
+impl Peeker {
+	pub fn new(cmd: &Cmd, file: yazi_shared::fs::File, mime: Cow<'static, str>, skip: usize) -> Self {
+		let (tx, rx) = mpsc::unbounded_channel();
+		let ct = CancellationToken::new();
+
+		let me = Self { url: file.url_owned(), cha: file.cha, mime: mime.to_string(), tx, ct };
+		super::spawn(cmd, file, mime, skip, me.ct.clone(), Some(rx));
+		me
+	}
+
+	#[inline]
+	pub fn seek(&self, units: i16) { self.tx.send(PeekerEvent::Seek(units)).ok(); }
+
+	#[inline]
+	pub fn resize(&self) {
+		self.tx.send(PeekerEvent::Resize(Rect::from(LAYOUT.load().preview))).ok();
+	}
+
+	#[inline]
+	pub fn same_url(&self, url: &Url) -> bool { self.url == *url }
+
+	#[inline]
+	pub fn same_file(&self, file: &yazi_shared::fs::File, mime: &str) -> bool {
+		self.same_url(&file.url) && file.cha.hits(self.cha) && mime == self.mime
+	}
+}
+
+impl Drop for Peeker {
+	fn drop(&mut self) { self.ct.cancel(); }
+}
@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+
+use mlua::{ExternalError, ExternalResult, HookTriggers, Table, TableExt};
+use tokio::{runtime::Handle, select};
+use tokio_util::sync::CancellationToken;
+use tracing::error;
+use yazi_adapter::ADAPTOR;
+use yazi_proxy::ManagerProxy;
+
+use super::slim_lua;
+use crate::{bindings::Cast, elements::Rect, file::File, loader::LOADER};
+
+/// Runs the `spot` method of the spotter, or `spot_key` with the pressed key
+/// if `key` is given, so that the plugin can update its panel in place.
+pub fn spot(
+	name: &str,
+	file: yazi_shared::fs::File,
+	mime: Cow<'static, str>,
+	skip: usize,
+	area: ratatui::layout::Rect,
+	key: Option<String>,
+) -> CancellationToken {
+	let ct = CancellationToken::new();
+
+	let name = name.to_owned();
+	let (ct1, ct2) = (ct.clone(), ct.clone());
+	tokio::task::spawn_blocking(move || {
+		let future = async {
+			LOADER.ensure(&name).await.into_lua_err()?;
+
+			let lua = slim_lua(&name)?;
+			// Whether the spotter has shown an image in the overlay, see `ya.image_show`
+			lua.set_named_registry_value("spot_image", false)?;
+			lua.set_hook(
+				HookTriggers::new().on_calls().on_returns().every_nth_instruction(2000),
+				move |_, _| {
+					if ct1.is_cancelled() { Err("Spot task cancelled".into_lua_err()) } else { Ok(()) }
+				},
+			);
+
+			let plugin: Table = if let Some(b) = LOADER.read().get(&name) {
+				lua.load(b.as_ref()).set_name(name).call(())?
+			} else {
+				return Err("unloaded plugin".into_lua_err());
+			};
+			plugin.raw_set("file", File::cast(&lua, file)?)?;
+			plugin.raw_set("_mime", mime)?;
+			plugin.raw_set("skip", skip)?;
+			plugin.raw_set("area", Rect::from(area))?;
+
+			if ct2.is_cancelled() {
+				return Ok(());
+			} else if let Some(key) = key {
+				plugin.call_async_method::<_, ()>("spot_key", key).await?;
+			} else {
+				plugin.call_async_method::<_, ()>("spot", ()).await?;
+			}
+
+			// Erase the image left by the previous run if it's not shown anymore
+			if !ct2.is_cancelled()
+				&& !lua.named_registry_value::<bool>("spot_image")?
+				&& ADAPTOR.spotted().is_some()
+			{
+				ADAPTOR.spot_hide().ok();
+				ManagerProxy::peek(true);
+			}
+			Ok(())
+		};
+
+		let result = Handle::current().block_on(async {
+			select! {
+				_ = ct2.cancelled() => Ok(()),
+				r = future => r,
+			}
+		});
+
+		if let Err(e) = result {
+			if !e.to_string().contains("Spot task cancelled") {
+				error!("{e:?}");
+			}
+		}
+	});
+
+	ct
+}
@@ -6,10 +6,14 @@ yazi_macro::mod_pub!(
 	bindings, cha, elements, external, file, fs, isolate, loader, process, pubsub, url, utils
 );
 
-yazi_macro::mod_flat!(cast clipboard config lua opt runtime);
+yazi_macro::mod_flat!(cast clipboard config lua opt runtime store);
 
 pub fn init() -> anyhow::Result<()> {
 	CLIPBOARD.with(<_>::default);
+	STORE.with(<_>::default);
+	crate::utils::PROVIDERS.with(<_>::default);
+	crate::utils::ENTRIES.with(<_>::default);
+	Store::start();
 
 	crate::loader::init();
 	crate::init_lua()?;
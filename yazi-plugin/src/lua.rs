@@ -35,6 +35,7 @@ fn stage_1(lua: &'static Lua) -> Result<()> {
 	// Components
 	lua.load(preset!("components/current")).set_name("current.lua").exec()?;
 	lua.load(preset!("components/entity")).set_name("entity.lua").exec()?;
+	lua.load(preset!("components/footer")).set_name("footer.lua").exec()?;
 	lua.load(preset!("components/header")).set_name("header.lua").exec()?;
 	lua.load(preset!("components/linemode")).set_name("linemode.lua").exec()?;
 
@@ -53,6 +53,20 @@ impl Pubsub {
 			})?,
 		)?;
 
+		ps.raw_set(
+			"sub_pre",
+			lua.create_function(|lua, (kind, f): (mlua::String, Function)| {
+				let rt = lua.named_registry_value::<RtRef>("rt")?;
+				let Some(cur) = rt.current() else {
+					return Err("`sub_pre()` must be called in a sync plugin").into_lua_err();
+				};
+				if !yazi_dds::Pubsub::sub_pre(cur, kind.to_str()?, f) {
+					return Err("`sub_pre()` called twice").into_lua_err();
+				}
+				Ok(())
+			})?,
+		)?;
+
 		ps.raw_set(
 			"unsub",
 			lua.create_function(|_, kind: mlua::String| {
@@ -75,6 +89,17 @@ impl Pubsub {
 			})?,
 		)?;
 
+		ps.raw_set(
+			"unsub_pre",
+			lua.create_function(|_, kind: mlua::String| {
+				if let Some(cur) = lua.named_registry_value::<RtRef>("rt")?.current() {
+					Ok(yazi_dds::Pubsub::unsub_pre(cur, kind.to_str()?))
+				} else {
+					Err("`unsub_pre()` must be called in a sync plugin").into_lua_err()
+				}
+			})?,
+		)?;
+
 		lua.globals().raw_set("ps", ps)
 	}
 }
@@ -0,0 +1,196 @@
+use std::{collections::{HashMap, HashSet}, path::PathBuf, time::Duration};
+
+use anyhow::{Result, bail};
+use parking_lot::{Mutex, RwLock};
+use tokio::{fs, sync::Notify};
+use yazi_boot::BOOT;
+use yazi_shared::{RoCell, event::Data};
+
+pub static STORE: RoCell<Store> = RoCell::new();
+
+/// Key-value storage for plugins, namespaced by plugin name.
+///
+/// Values are kept as JSON; the persistent ones are written to the state
+/// directory in batches, at most once a second, and on exit.
+#[derive(Default)]
+pub struct Store {
+	persistent: RwLock<HashMap<String, Bucket>>,
+	session:    RwLock<HashMap<String, Bucket>>,
+
+	dirty:  Mutex<HashSet<String>>,
+	notify: Notify,
+}
+
+#[derive(Default)]
+struct Bucket {
+	values: HashMap<String, String>,
+	size:   usize,
+}
+
+impl Store {
+	/// The maximum size of the keys and serialized values of each plugin, for
+	/// the persistent and session storage respectively.
+	pub const QUOTA: usize = 1024 * 1024;
+
+	pub(super) fn start() {
+		tokio::spawn(async {
+			loop {
+				STORE.notify.notified().await;
+				tokio::time::sleep(Duration::from_secs(1)).await;
+				STORE.drain().await.ok();
+			}
+		});
+	}
+
+	pub async fn get(&self, plugin: &str, key: &str, persist: bool) -> Option<Data> {
+		if persist {
+			self.load(plugin).await;
+		}
+		self.get_loaded(plugin, key, persist)
+	}
+
+	pub async fn set(&self, plugin: &str, key: &str, value: Data, persist: bool) -> Result<()> {
+		if persist {
+			self.load(plugin).await;
+		}
+		self.set_loaded(plugin, key, value, persist)
+	}
+
+	pub async fn remove(&self, plugin: &str, key: &str, persist: bool) -> bool {
+		if persist {
+			self.load(plugin).await;
+		}
+		self.remove_loaded(plugin, key, persist)
+	}
+
+	/// Like [`Self::get`], for the sync context, where the persistent values of
+	/// the plugin are read blocking if they haven't been.
+	pub fn get_sync(&self, plugin: &str, key: &str, persist: bool) -> Option<Data> {
+		if persist {
+			self.load_sync(plugin);
+		}
+		self.get_loaded(plugin, key, persist)
+	}
+
+	/// Like [`Self::set`], for the sync context.
+	pub fn set_sync(&self, plugin: &str, key: &str, value: Data, persist: bool) -> Result<()> {
+		if persist {
+			self.load_sync(plugin);
+		}
+		self.set_loaded(plugin, key, value, persist)
+	}
+
+	/// Like [`Self::remove`], for the sync context.
+	pub fn remove_sync(&self, plugin: &str, key: &str, persist: bool) -> bool {
+		if persist {
+			self.load_sync(plugin);
+		}
+		self.remove_loaded(plugin, key, persist)
+	}
+
+	fn get_loaded(&self, plugin: &str, key: &str, persist: bool) -> Option<Data> {
+		let buckets = if persist { self.persistent.read() } else { self.session.read() };
+		serde_json::from_str(buckets.get(plugin)?.values.get(key)?).ok()
+	}
+
+	fn set_loaded(&self, plugin: &str, key: &str, value: Data, persist: bool) -> Result<()> {
+		if matches!(value, Data::Nil) {
+			self.remove_loaded(plugin, key, persist);
+			return Ok(());
+		}
+
+		let json = serde_json::to_string(&value)?;
+		let mut buckets = if persist { self.persistent.write() } else { self.session.write() };
+		let bucket = buckets.entry(plugin.to_owned()).or_default();
+
+		let old = bucket.values.get(key).map_or(0, |v| key.len() + v.len());
+		let size = bucket.size - old + key.len() + json.len();
+		if size > Self::QUOTA {
+			bail!("Storage quota of {} bytes exceeded", Self::QUOTA);
+		}
+
+		bucket.size = size;
+		bucket.values.insert(key.to_owned(), json);
+		drop(buckets);
+
+		if persist {
+			self.touch(plugin);
+		}
+		Ok(())
+	}
+
+	fn remove_loaded(&self, plugin: &str, key: &str, persist: bool) -> bool {
+		let mut buckets = if persist { self.persistent.write() } else { self.session.write() };
+		let Some(bucket) = buckets.get_mut(plugin) else { return false };
+		let Some(old) = bucket.values.remove(key) else { return false };
+
+		bucket.size -= key.len() + old.len();
+		drop(buckets);
+
+		if persist {
+			self.touch(plugin);
+		}
+		true
+	}
+
+	pub async fn drain(&self) -> Result<()> {
+		let dirty: Vec<_> = self.dirty.lock().drain().collect();
+		if dirty.is_empty() {
+			return Ok(());
+		}
+
+		// Those failed to be written stay dirty, to be retried on the next drain
+		let mut result = Ok(());
+		for plugin in dirty {
+			if let Err(e) = self.write(&plugin).await {
+				self.dirty.lock().insert(plugin);
+				result = Err(e);
+			}
+		}
+		result
+	}
+
+	async fn write(&self, plugin: &str) -> Result<()> {
+		let Some(json) = self.persistent.read().get(plugin).map(|b| serde_json::to_vec(&b.values))
+		else {
+			return Ok(());
+		};
+
+		fs::create_dir_all(Self::dir()).await?;
+		let (path, tmp) = (Self::path(plugin), Self::dir().join(format!(".{plugin}.json.tmp")));
+		fs::write(&tmp, json?).await?;
+		Ok(fs::rename(tmp, path).await?)
+	}
+
+	async fn load(&self, plugin: &str) {
+		if !self.persistent.read().contains_key(plugin) {
+			self.insert_read(plugin, fs::read(Self::path(plugin)).await.ok());
+		}
+	}
+
+	fn load_sync(&self, plugin: &str) {
+		if !self.persistent.read().contains_key(plugin) {
+			self.insert_read(plugin, std::fs::read(Self::path(plugin)).ok());
+		}
+	}
+
+	fn insert_read(&self, plugin: &str, bytes: Option<Vec<u8>>) {
+		let values: HashMap<String, String> =
+			bytes.and_then(|b| serde_json::from_slice(&b).ok()).unwrap_or_default();
+
+		let size = values.iter().map(|(k, v)| k.len() + v.len()).sum();
+		self.persistent.write().entry(plugin.to_owned()).or_insert(Bucket { values, size });
+	}
+
+	#[inline]
+	fn touch(&self, plugin: &str) {
+		self.dirty.lock().insert(plugin.to_owned());
+		self.notify.notify_one();
+	}
+
+	#[inline]
+	fn dir() -> PathBuf { BOOT.state_dir.join("store") }
+
+	#[inline]
+	fn path(plugin: &str) -> PathBuf { Self::dir().join(format!("{plugin}.json")) }
+}
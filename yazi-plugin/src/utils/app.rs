@@ -1,4 +1,7 @@
+use std::sync::atomic::Ordering;
+
 use mlua::{AnyUserData, ExternalError, Lua, Table};
+use yazi_boot::READ_ONLY;
 use yazi_proxy::{AppProxy, HIDER};
 
 use super::Utils;
@@ -22,6 +25,8 @@ impl Utils {
 			})?,
 		)?;
 
+		ya.raw_set("read_only", lua.create_function(|_, ()| Ok(READ_ONLY.load(Ordering::Relaxed)))?)?;
+
 		Ok(())
 	}
 }
@@ -1,6 +1,9 @@
+use std::path::PathBuf;
+
 use md5::{Digest, Md5};
 use mlua::{Lua, Table};
 use yazi_config::PREVIEW;
+use yazi_shared::fs::File;
 
 use super::Utils;
 use crate::{bindings::Cast, file::FileRef, url::Url};
@@ -11,20 +14,22 @@ impl Utils {
 			"file_cache",
 			lua.create_function(|lua, t: Table| {
 				let file: FileRef = t.raw_get("file")?;
-				if file.url.parent() == Some(&PREVIEW.cache_dir) {
-					return Ok(None);
-				}
-
-				let hex = {
-					let mut digest = Md5::new_with_prefix(file.url.as_os_str().as_encoded_bytes());
-					digest.update(format!("//{:?}//{}", file.cha.mtime, t.raw_get("skip").unwrap_or(0)));
-					format!("{:x}", digest.finalize())
-				};
-
-				Some(Url::cast(lua, PREVIEW.cache_dir.join(hex))).transpose()
+				file_cache(&file, t.raw_get("skip").unwrap_or(0)).map(|p| Url::cast(lua, p)).transpose()
 			})?,
 		)?;
 
 		Ok(())
 	}
 }
+
+/// Where the previewers cache `file` scrolled to `skip`, or [`None`] if it's a
+/// cache itself.
+pub fn file_cache(file: &File, skip: usize) -> Option<PathBuf> {
+	if file.url.parent() == Some(PREVIEW.work_dir()) {
+		return None;
+	}
+
+	let mut digest = Md5::new_with_prefix(file.url.as_os_str().as_encoded_bytes());
+	digest.update(format!("//{:?}//{skip}", file.cha.mtime));
+	Some(PREVIEW.work_dir().join(format!("{:x}", digest.finalize())))
+}
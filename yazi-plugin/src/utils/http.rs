@@ -0,0 +1,115 @@
+use std::{sync::OnceLock, time::Duration};
+
+use anyhow::{Result, bail};
+use mlua::{IntoLua, IntoLuaMulti, Lua, Table, Value};
+use reqwest::{Client, header::{HeaderMap, HeaderName, HeaderValue}, redirect};
+use yazi_config::PLUGIN;
+
+use super::Utils;
+
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+struct Response {
+	status:  u16,
+	headers: Vec<(String, String)>,
+	body:    Vec<u8>,
+}
+
+impl Utils {
+	pub(super) fn http(lua: &Lua, ya: &Table) -> mlua::Result<()> {
+		let http = lua.create_table()?;
+
+		http.raw_set(
+			"get",
+			lua.create_async_function(|lua, (url, opts): (String, Option<Table>)| async move {
+				let resp = match Self::http_get(&url, opts).await {
+					Ok(resp) => resp,
+					Err(e) => return (Value::Nil, e.to_string()).into_lua_multi(lua),
+				};
+
+				let headers = lua.create_table_with_capacity(0, resp.headers.len())?;
+				for (k, v) in resp.headers {
+					headers.raw_set(k, v)?;
+				}
+
+				let t = lua.create_table_from([
+					("status", resp.status.into_lua(lua)?),
+					("headers", Value::Table(headers)),
+					("body", Value::String(lua.create_string(resp.body)?)),
+				])?;
+				(t, Value::Nil).into_lua_multi(lua)
+			})?,
+		)?;
+
+		ya.raw_set("http", http)
+	}
+
+	async fn http_get(url: &str, opts: Option<Table<'_>>) -> Result<Response> {
+		let url = reqwest::Url::parse(url)?;
+		Self::http_check(&url)?;
+
+		let mut headers = HeaderMap::new();
+		let (mut timeout, mut max_size) = (30.0, 10 << 20);
+		if let Some(opts) = opts {
+			if let Ok(t) = opts.raw_get::<_, Table>("headers") {
+				for pair in t.pairs::<String, String>() {
+					let (k, v) = pair?;
+					headers.insert(HeaderName::try_from(k)?, HeaderValue::try_from(v)?);
+				}
+			}
+			timeout = opts.raw_get::<_, Option<f64>>("timeout")?.unwrap_or(timeout);
+			max_size = opts.raw_get::<_, Option<usize>>("max_size")?.unwrap_or(max_size);
+		}
+		let Some(timeout) = Duration::try_from_secs_f64(timeout).ok().filter(|d| !d.is_zero()) else {
+			bail!("timeout must be a positive number of seconds");
+		};
+
+		let client = CLIENT.get_or_init(|| {
+			Client::builder()
+				.user_agent(concat!("Yazi/", env!("CARGO_PKG_VERSION")))
+				.redirect(redirect::Policy::custom(|attempt| {
+					if attempt.previous().len() >= 10 {
+						attempt.error("too many redirects")
+					} else if let Err(e) = Self::http_check(attempt.url()) {
+						attempt.error(e.to_string())
+					} else {
+						attempt.follow()
+					}
+				}))
+				.build()
+				.unwrap()
+		});
+
+		let mut resp = client.get(url).headers(headers).timeout(timeout).send().await?;
+
+		let mut body = Vec::new();
+		while let Some(chunk) = resp.chunk().await? {
+			if body.len() + chunk.len() > max_size {
+				bail!("Response body exceeds the limit of {max_size} bytes");
+			}
+			body.extend_from_slice(&chunk);
+		}
+
+		Ok(Response {
+			status: resp.status().as_u16(),
+			headers: resp
+				.headers()
+				.iter()
+				.filter_map(|(k, v)| Some((k.as_str().to_owned(), v.to_str().ok()?.to_owned())))
+				.collect(),
+			body,
+		})
+	}
+
+	// Checked for the URL asked for, as well as every redirect on the way, so
+	// that `plugin.http_domains` can't be bypassed with one
+	fn http_check(url: &reqwest::Url) -> Result<()> {
+		if !matches!(url.scheme(), "http" | "https") {
+			bail!("Unsupported scheme: {}", url.scheme());
+		}
+		if !url.host_str().is_some_and(|h| PLUGIN.http_allowed(h)) {
+			bail!("Host not allowed, see `plugin.http_domains` in yazi.toml: {url}");
+		}
+		Ok(())
+	}
+}
@@ -1,5 +1,7 @@
 use mlua::{IntoLua, Lua, Table, Value};
 use yazi_adapter::{ADAPTOR, Image};
+use yazi_config::PREVIEW;
+use yazi_proxy::AppProxy;
 
 use super::Utils;
 use crate::{elements::Rect, url::UrlRef};
@@ -9,7 +11,14 @@ impl Utils {
 		ya.raw_set(
 			"image_show",
 			lua.create_async_function(|lua, (url, rect): (UrlRef, Rect)| async move {
-				if let Ok(area) = ADAPTOR.image_show(&url, *rect).await {
+				// Spotters show the image in the spot overlay instead of the preview pane
+				let result = if lua.named_registry_value::<Option<bool>>("spot_image")?.is_some() {
+					lua.set_named_registry_value("spot_image", true)?;
+					ADAPTOR.spot_show(&url, *rect).await
+				} else {
+					ADAPTOR.image_show(&url, *rect).await
+				};
+				if let Ok(area) = result {
 					Rect::from(area).into_lua(lua)
 				} else {
 					Value::Nil.into_lua(lua)
@@ -17,10 +26,28 @@ impl Utils {
 			})?,
 		)?;
 
+		ya.raw_set(
+			"image_info",
+			lua.create_async_function(|lua, url: UrlRef| async move {
+				match Image::size(&url).await {
+					Ok((w, h)) => lua.create_table_from([("w", w), ("h", h)])?.into_lua(lua),
+					Err(_) => Value::Nil.into_lua(lua),
+				}
+			})?,
+		)?;
+
 		ya.raw_set(
 			"image_precache",
 			lua.create_async_function(|_, (src, dist): (UrlRef, UrlRef)| async move {
-				Ok(Image::precache(&src, dist.to_path_buf()).await.is_ok())
+				if Image::precache(&src, dist.to_path_buf()).await.is_ok() {
+					return Ok(true);
+				}
+
+				// The cache directory may have filled up, or become read-only since startup
+				if let Some(msg) = PREVIEW.check_dir() {
+					AppProxy::notify_warn("Preview", msg);
+				}
+				Ok(false)
 			})?,
 		)?;
 
@@ -1,15 +1,15 @@
-use std::{str::FromStr, time::Duration};
+use std::{ptr, str::FromStr, time::Duration};
 
-use mlua::{ExternalError, ExternalResult, IntoLuaMulti, Lua, Table, Value};
+use mlua::{ExternalError, ExternalResult, Function, IntoLuaMulti, Lua, Table, Value};
 use tokio::sync::mpsc;
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::{StreamExt, wrappers::UnboundedReceiverStream};
 use yazi_config::{keymap::{Chord, Key}, popup::InputCfg};
 use yazi_macro::emit;
-use yazi_proxy::{AppProxy, InputProxy};
-use yazi_shared::{Debounce, Layer, event::Cmd};
+use yazi_proxy::{AppProxy, CompletionProxy, InputProxy, options::{NotifyAction, NotifyOpt}};
+use yazi_shared::{Debounce, Layer, errors::InputError, event::Cmd};
 
 use super::Utils;
-use crate::bindings::{InputRx, Position};
+use crate::{LUA, OptCallback, bindings::{InputRx, Position}, loader::LOADER, runtime::RtRef};
 
 impl Utils {
 	fn parse_keys(value: Value) -> mlua::Result<Vec<Key>> {
@@ -28,6 +28,48 @@ impl Utils {
 		})
 	}
 
+	/// Parses an action, whose `run` is either a command string executed on the
+	/// manager layer, or a function called in the context of the owning plugin.
+	fn notify_action(lua: &Lua, t: Table) -> mlua::Result<NotifyAction> {
+		let label: String = t.raw_get("label")?;
+		let f = match t.raw_get::<_, Value>("run")? {
+			Value::String(s) => {
+				let cmd = Cmd::from_str(s.to_str()?).into_lua_err()?;
+				return Ok(NotifyAction::cmds(label, vec![cmd], Layer::Manager));
+			}
+			Value::Function(f) => f,
+			_ => return Err("`run` must be a string or function".into_lua_err()),
+		};
+
+		if !ptr::eq(lua, &*LUA) {
+			return Err("function actions are only supported in the sync context".into_lua_err());
+		}
+		let Some(id) = lua.named_registry_value::<RtRef>("rt")?.current().map(ToOwned::to_owned) else {
+			return Err("function actions must be attached in a plugin".into_lua_err());
+		};
+
+		// The keys of those dropped along with their notifications are only marked,
+		// so clear them out of the registry before adding new ones
+		lua.expire_registry_values();
+
+		// Held to tell if the plugin has been reloaded once the action is run, as
+		// the function then belongs to a stale instance of it
+		let (key, owner) =
+			(lua.create_registry_value(f)?, lua.create_registry_value(LOADER.load(lua, &id)?)?);
+		Ok(NotifyAction::new(label, move || {
+			let cb: OptCallback = Box::new(move |lua, plugin| {
+				if lua.registry_value::<Table>(&owner)? != plugin {
+					return Ok(());
+				}
+				lua.registry_value::<Function>(&key)?.call(())
+			});
+			emit!(Call(
+				Cmd::args("plugin", &[id]).with_bool("sync", true).with_any("callback", cb),
+				Layer::App
+			));
+		}))
+	}
+
 	pub(super) fn layer(lua: &Lua, ya: &Table) -> mlua::Result<()> {
 		ya.raw_set(
 			"which",
@@ -61,28 +103,45 @@ impl Utils {
 			"input",
 			lua.create_async_function(|lua, t: Table| async move {
 				let realtime = t.raw_get("realtime").unwrap_or_default();
+				let validator = t.raw_get::<_, Option<Function>>("validate")?;
+				let cands = t.raw_get::<_, Option<Vec<String>>>("cands")?.unwrap_or_default();
+
 				let rx = UnboundedReceiverStream::new(InputProxy::show(InputCfg {
-					title: t.raw_get("title")?,
-					value: t.raw_get("value").unwrap_or_default(),
-					cursor: None, // TODO
-					position: Position::try_from(t.raw_get::<_, Table>("position")?)?.into(),
-					realtime,
-					completion: false,
-					highlight: false,
-				}));
+					title:      t.raw_get("title")?,
+					value:      t.raw_get("value").unwrap_or_default(),
+					cursor:     None, // TODO
+					position:   Position::try_from(t.raw_get::<_, Table>("position")?)?.into(),
+					realtime:   realtime || validator.is_some(),
+					completion: !cands.is_empty(),
+					eager:      false,
+					highlight:  false,
+					obscure:    t.raw_get("obscure").unwrap_or_default(),
+					selected:   t.raw_get("selected").unwrap_or_default(),
+					validate:   validator.is_some(),
+				}))
+				.filter(move |res| {
+					let Err(InputError::Completed(before, ticket)) = res else { return true };
+					CompletionProxy::show(cands.clone(), before, *ticket);
+					false
+				});
 
+				let validator = validator.map(|f| lua.create_registry_value(f)).transpose()?;
 				if !realtime {
-					return InputRx::consume(rx).await.into_lua_multi(lua);
+					return InputRx::new(rx)
+						.with_validator(validator)
+						.consume(lua)
+						.await?
+						.into_lua_multi(lua);
 				}
 
 				let debounce = t.raw_get::<_, f64>("debounce").unwrap_or_default();
 				if debounce < 0.0 {
 					Err("negative debounce duration".into_lua_err())
 				} else if debounce == 0.0 {
-					(InputRx::new(rx), Value::Nil).into_lua_multi(lua)
+					(InputRx::new(rx).with_validator(validator), Value::Nil).into_lua_multi(lua)
 				} else {
-					(InputRx::new(Debounce::new(rx, Duration::from_secs_f64(debounce))), Value::Nil)
-						.into_lua_multi(lua)
+					let rx = Debounce::new(rx, Duration::from_secs_f64(debounce));
+					(InputRx::new(rx).with_validator(validator), Value::Nil).into_lua_multi(lua)
 				}
 			})?,
 		)?;
@@ -102,8 +161,19 @@ impl Utils {
 
 		ya.raw_set(
 			"notify",
-			lua.create_function(|_, t: Table| {
-				AppProxy::notify(t.try_into()?);
+			lua.create_function(|lua, t: Table| {
+				let actions = t.raw_get::<_, Option<Vec<Table>>>("actions")?.unwrap_or_default();
+				if actions.len() > NotifyAction::LIMIT {
+					return Err(
+						format!("at most {} actions are allowed", NotifyAction::LIMIT).into_lua_err(),
+					);
+				}
+
+				let mut opt = NotifyOpt::try_from(t)?;
+				opt.actions =
+					actions.into_iter().map(|a| Self::notify_action(lua, a)).collect::<mlua::Result<_>>()?;
+
+				AppProxy::notify(opt);
 				Ok(())
 			})?,
 		)?;
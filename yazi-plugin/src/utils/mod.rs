@@ -1,5 +1,5 @@
 #![allow(clippy::module_inception)]
 
 yazi_macro::mod_flat!(
-	app cache call image layer log preview sync target text time user utils
+	app cache call http image layer log opener preview spot store sync tag target task text time user utils
 );
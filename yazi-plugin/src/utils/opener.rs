@@ -0,0 +1,130 @@
+use mlua::{ExternalError, ExternalResult, Function, Lua, Table};
+use parking_lot::RwLock;
+use yazi_config::Pattern;
+use yazi_proxy::AppProxy;
+use yazi_shared::{MIME_DIR, RoCell, fs::Url};
+
+use super::Utils;
+use crate::{LUA, bindings::Cast, runtime::RtRef, url::Url as LuaUrl};
+
+pub(crate) static PROVIDERS: RoCell<RwLock<Vec<Provider>>> = RoCell::new();
+
+// The `run` functions of the entries last provided, which `PluginOpener::slot`
+// points into, as Lua functions can't leave the main thread
+pub(crate) static ENTRIES: RoCell<RwLock<Vec<Function<'static>>>> = RoCell::new();
+
+// Registered by `ya.opener()`, which is only called for the files that match
+// `mime` or `name`, or all of them if neither is given
+pub(crate) struct Provider {
+	mime:    Option<Pattern>,
+	name:    Option<Pattern>,
+	provide: Function<'static>,
+}
+
+/// An entry added by a plugin to the list of `open --interactive`, which
+/// applies to the targets at `indices`.
+#[derive(Clone, Debug)]
+pub struct PluginOpener {
+	pub desc:    String,
+	pub indices: Vec<usize>,
+	slot:        usize,
+}
+
+impl Utils {
+	pub(super) fn opener(lua: &'static Lua, ya: &Table) -> mlua::Result<()> {
+		ya.raw_set(
+			"opener",
+			lua.create_function(|lua, t: Table| {
+				let rt = lua.named_registry_value::<RtRef>("rt")?;
+				let mut providers = match PROVIDERS.try_write() {
+					Some(p) if rt.current().is_none() => p,
+					_ => return Err("`ya.opener()` must be called in init.lua").into_lua_err(),
+				};
+
+				let pattern = |s: Option<mlua::String>| -> mlua::Result<_> {
+					s.map(|s| Pattern::try_from(s.to_str()?).into_lua_err()).transpose()
+				};
+
+				providers.push(Provider {
+					mime:    pattern(t.raw_get("mime")?)?,
+					name:    pattern(t.raw_get("name")?)?,
+					provide: t.raw_get("provide")?,
+				});
+				Ok(())
+			})?,
+		)?;
+
+		Ok(())
+	}
+}
+
+impl PluginOpener {
+	/// The entries the plugins provide for `targets`, each being a URL and its
+	/// mimetype, or nothing from those failing, which is notified.
+	///
+	/// This must be called on the main thread.
+	pub fn provide(targets: &[(Url, String)]) -> Vec<Self> {
+		let providers = PROVIDERS.read();
+		let mut openers = vec![];
+		ENTRIES.write().clear();
+		for p in providers.iter() {
+			let indices: Vec<_> = (0..targets.len()).filter(|&i| p.matches(&targets[i])).collect();
+			if indices.is_empty() {
+				continue;
+			}
+
+			match p.call(targets, &indices) {
+				Ok(o) => openers.extend(o),
+				Err(e) => AppProxy::notify_error("Open", format!("Failed to get plugin openers: {e}")),
+			}
+		}
+		openers
+	}
+
+	/// Runs it with the `targets` it applies to.
+	///
+	/// This must be called on the main thread.
+	pub fn run(&self, targets: Vec<Url>) -> mlua::Result<()> {
+		let Some(f) = ENTRIES.read().get(self.slot).cloned() else {
+			return Err("the opener is no longer available".into_lua_err());
+		};
+		f.call(files(targets)?)
+	}
+}
+
+impl Provider {
+	fn matches(&self, (url, mime): &(Url, String)) -> bool {
+		match (&self.mime, &self.name) {
+			(None, None) => true,
+			(m, n) => {
+				m.as_ref().is_some_and(|p| p.match_mime(mime))
+					|| n.as_ref().is_some_and(|p| p.match_path(url, mime == MIME_DIR))
+			}
+		}
+	}
+
+	fn call(&self, targets: &[(Url, String)], indices: &[usize]) -> mlua::Result<Vec<PluginOpener>> {
+		let files = files(indices.iter().map(|&i| targets[i].0.clone()))?;
+		let entries: Table = self.provide.call(files)?;
+
+		entries
+			.sequence_values::<Table>()
+			.map(|t| {
+				let t = t?;
+				let mut entries = ENTRIES.write();
+				entries.push(t.raw_get("run")?);
+				Ok(PluginOpener {
+					desc:    t.raw_get("desc")?,
+					indices: indices.to_vec(),
+					slot:    entries.len() - 1,
+				})
+			})
+			.collect()
+	}
+}
+
+fn files(urls: impl IntoIterator<Item = Url>) -> mlua::Result<Table<'static>> {
+	let urls: Vec<_> =
+		urls.into_iter().map(|u| LuaUrl::cast(&LUA, u)).collect::<mlua::Result<_>>()?;
+	LUA.create_sequence_from(urls)
+}
@@ -0,0 +1,78 @@
+use std::str::FromStr;
+
+use mlua::{AnyUserData, ExternalResult, Lua, Table};
+use yazi_config::keymap::Key;
+use yazi_macro::emit;
+use yazi_shared::{Layer, event::Cmd};
+
+use super::Utils;
+use crate::{cast_to_renderable, elements::Renderable, file::FileRef};
+
+pub struct SpotLock {
+	pub url:  yazi_shared::fs::Url,
+	pub cha:  yazi_shared::fs::Cha,
+	pub mime: String,
+
+	pub skip: usize,
+	pub data: Vec<Box<dyn Renderable + Send>>,
+	pub keys: Vec<SpotKey>,
+}
+
+/// A key claimed by the spotter while its panel is shown, which is delivered
+/// to the `spot_key` method of the plugin instead of the `[spot]` keymap.
+pub struct SpotKey {
+	pub on:   Key,
+	pub desc: String,
+}
+
+impl<'a> TryFrom<Table<'a>> for SpotLock {
+	type Error = mlua::Error;
+
+	fn try_from(t: Table) -> Result<Self, Self::Error> {
+		let file: FileRef = t.raw_get("file")?;
+		Ok(Self {
+			url:  file.url_owned(),
+			cha:  file.cha,
+			mime: t.raw_get("_mime")?,
+
+			skip: t.raw_get("skip")?,
+			data: Default::default(),
+			keys: Default::default(),
+		})
+	}
+}
+
+impl<'a> TryFrom<Table<'a>> for SpotKey {
+	type Error = mlua::Error;
+
+	fn try_from(t: Table) -> Result<Self, Self::Error> {
+		Ok(Self {
+			on:   Key::from_str(t.raw_get::<_, mlua::String>("on")?.to_str()?).into_lua_err()?,
+			desc: t.raw_get("desc").unwrap_or_default(),
+		})
+	}
+}
+
+impl Utils {
+	pub(super) fn spot(lua: &Lua, ya: &Table) -> mlua::Result<()> {
+		ya.raw_set(
+			"spot_widgets",
+			lua.create_async_function(
+				|_, (t, widgets, keys): (Table, Vec<AnyUserData>, Option<Vec<Table>>)| async move {
+					let mut lock = SpotLock::try_from(t)?;
+					lock.data = widgets.into_iter().filter_map(|ud| cast_to_renderable(&ud)).collect();
+					lock.keys = keys
+						.unwrap_or_default()
+						.into_iter()
+						.map(SpotKey::try_from)
+						.collect::<mlua::Result<_>>()?;
+
+					emit!(Call(Cmd::new("update_spotted").with_any("lock", lock), Layer::Manager));
+					Ok(())
+				},
+			)?,
+		)?;
+
+		Ok(())
+	}
+}
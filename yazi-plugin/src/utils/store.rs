@@ -0,0 +1,84 @@
+use mlua::{ExternalError, IntoLuaMulti, Lua, Table, Value};
+use yazi_dds::Sendable;
+
+use super::Utils;
+use crate::{runtime::RtRef, store::STORE};
+
+impl Utils {
+	pub(super) fn store(lua: &Lua, ya: &Table) -> mlua::Result<()> {
+		let store = Self::store_table(lua, true)?;
+		store.raw_set("session", Self::store_table(lua, false)?)?;
+
+		ya.raw_set("store", store)
+	}
+
+	pub(super) fn store_isolate(lua: &Lua, ya: &Table) -> mlua::Result<()> {
+		let store = Self::store_table_isolate(lua, true)?;
+		store.raw_set("session", Self::store_table_isolate(lua, false)?)?;
+
+		ya.raw_set("store", store)
+	}
+
+	fn store_table(lua: &Lua, persist: bool) -> mlua::Result<Table<'_>> {
+		lua.create_table_from([
+			(
+				"get",
+				lua.create_function(move |lua, key: mlua::String| {
+					let data = STORE.get_sync(&current(lua)?, key.to_str()?, persist);
+					data.map(|d| Sendable::data_to_value(lua, d)).unwrap_or(Ok(Value::Nil))
+				})?,
+			),
+			(
+				"set",
+				lua.create_function(move |lua, (key, value): (mlua::String, Value)| {
+					let data = Sendable::value_to_data(value)?;
+					match STORE.set_sync(&current(lua)?, key.to_str()?, data, persist) {
+						Ok(()) => (true, Value::Nil).into_lua_multi(lua),
+						Err(e) => (false, e.to_string()).into_lua_multi(lua),
+					}
+				})?,
+			),
+			(
+				"remove",
+				lua.create_function(move |lua, key: mlua::String| {
+					Ok(STORE.remove_sync(&current(lua)?, key.to_str()?, persist))
+				})?,
+			),
+		])
+	}
+
+	fn store_table_isolate(lua: &Lua, persist: bool) -> mlua::Result<Table<'_>> {
+		lua.create_table_from([
+			(
+				"get",
+				lua.create_async_function(move |lua, key: mlua::String| async move {
+					let data = STORE.get(&current(lua)?, key.to_str()?, persist).await;
+					data.map(|d| Sendable::data_to_value(lua, d)).unwrap_or(Ok(Value::Nil))
+				})?,
+			),
+			(
+				"set",
+				lua.create_async_function(move |lua, (key, value): (mlua::String, Value)| async move {
+					let data = Sendable::value_to_data(value)?;
+					match STORE.set(&current(lua)?, key.to_str()?, data, persist).await {
+						Ok(()) => (true, Value::Nil).into_lua_multi(lua),
+						Err(e) => (false, e.to_string()).into_lua_multi(lua),
+					}
+				})?,
+			),
+			(
+				"remove",
+				lua.create_async_function(move |lua, key: mlua::String| async move {
+					Ok(STORE.remove(&current(lua)?, key.to_str()?, persist).await)
+				})?,
+			),
+		])
+	}
+}
+
+fn current(lua: &Lua) -> mlua::Result<String> {
+	match lua.named_registry_value::<RtRef>("rt")?.current() {
+		Some(cur) => Ok(cur.to_owned()),
+		None => Err("`ya.store` must be called in a plugin".into_lua_err()),
+	}
+}
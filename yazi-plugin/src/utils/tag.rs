@@ -0,0 +1,64 @@
+use mlua::{Lua, Table};
+use yazi_fs::{TAGS, Tags};
+use yazi_proxy::ManagerProxy;
+use yazi_shared::fs::Url;
+
+use super::Utils;
+use crate::url::UrlRef;
+
+impl Utils {
+	pub(super) fn tag(lua: &Lua, ya: &Table) -> mlua::Result<()> {
+		Self::tags(lua, ya)?;
+
+		// The directory is resolved in the background, so the tag is set after the
+		// call returns, in the sync context
+		ya.raw_set(
+			"tag",
+			lua.create_function(|_, (url, name, state): (UrlRef, mlua::String, Option<bool>)| {
+				let (url, name) = (url.clone(), name.to_str()?.to_owned());
+				let state = state.unwrap_or_else(|| !TAGS.read().contains(&url, &name));
+
+				tokio::spawn(async move { Self::tag_do(&url, &name, state).await });
+				Ok(state)
+			})?,
+		)?;
+
+		Ok(())
+	}
+
+	pub(super) fn tag_isolate(lua: &Lua, ya: &Table) -> mlua::Result<()> {
+		Self::tags(lua, ya)?;
+
+		ya.raw_set(
+			"tag",
+			lua.create_async_function(
+				|_, (url, name, state): (UrlRef, mlua::String, Option<bool>)| async move {
+					let name = name.to_str()?;
+					Tags::resolve([&*url]).await;
+
+					let state = state.unwrap_or_else(|| !TAGS.read().contains(&url, name));
+					Self::tag_do(&url, name, state).await;
+					Ok(state)
+				},
+			)?,
+		)?;
+
+		Ok(())
+	}
+
+	fn tags(lua: &Lua, ya: &Table) -> mlua::Result<()> {
+		ya.raw_set(
+			"tags",
+			lua.create_function(|_, url: UrlRef| {
+				Ok(TAGS.read().get(&url).map(|t| t.iter().cloned().collect::<Vec<_>>()).unwrap_or_default())
+			})?,
+		)
+	}
+
+	async fn tag_do(url: &Url, name: &str, state: bool) {
+		Tags::resolve([url]).await;
+		if TAGS.write().set(url, name, state) {
+			ManagerProxy::update_tagged();
+		}
+	}
+}
@@ -0,0 +1,24 @@
+use mlua::{ExternalError, Lua, Table};
+use yazi_proxy::TasksProxy;
+
+use super::Utils;
+use crate::bindings::Job;
+
+impl Utils {
+	pub(super) fn task(lua: &Lua, ya: &Table) -> mlua::Result<()> {
+		ya.raw_set(
+			"task",
+			lua.create_async_function(|_, t: Table| async move {
+				let title: String = t.raw_get("title")?;
+				let total: Option<u64> = t.raw_get("total")?;
+
+				match TasksProxy::plugin_job(title, total.unwrap_or(0)).await {
+					Some(job) => Ok(Job::new(job)),
+					None => Err("failed to create the task".into_lua_err()),
+				}
+			})?,
+		)?;
+
+		Ok(())
+	}
+}
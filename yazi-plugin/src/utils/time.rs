@@ -1,6 +1,7 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use mlua::{ExternalError, Lua, Table};
+use yazi_shared::{clock, readable_age};
 
 use super::Utils;
 
@@ -13,6 +14,13 @@ impl Utils {
 			})?,
 		)?;
 
+		ya.raw_set(
+			"readable_age",
+			lua.create_function(|_, time: f64| {
+				Ok(readable_age(clock().saturating_sub(time.max(0.0) as u64)))
+			})?,
+		)?;
+
 		ya.raw_set(
 			"sleep",
 			lua.create_async_function(|_, secs: f64| async move {
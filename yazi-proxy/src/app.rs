@@ -33,6 +33,7 @@ impl AppProxy {
 			content: content.to_string(),
 			level:   NotifyLevel::Warn,
 			timeout: Duration::from_secs(5),
+			actions: vec![],
 		});
 	}
 
@@ -43,6 +44,7 @@ impl AppProxy {
 			content: content.to_string(),
 			level:   NotifyLevel::Error,
 			timeout: Duration::from_secs(10),
+			actions: vec![],
 		});
 	}
 }
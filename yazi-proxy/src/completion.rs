@@ -9,6 +9,14 @@ impl CompletionProxy {
 		emit!(Call(Cmd::new("close"), Layer::Completion));
 	}
 
+	#[inline]
+	pub fn show(cands: Vec<String>, word: &str, ticket: usize) {
+		emit!(Call(
+			Cmd::args("trigger", &[word]).with_any("cands", cands).with("ticket", ticket),
+			Layer::Completion
+		));
+	}
+
 	#[inline]
 	pub fn trigger(word: &str, ticket: usize) {
 		emit!(Call(Cmd::args("trigger", &[word]).with("ticket", ticket), Layer::Completion));
@@ -13,6 +13,14 @@ impl InputProxy {
 		rx
 	}
 
+	#[inline]
+	pub fn error(value: &str, message: Option<&str>) {
+		emit!(Call(
+			Cmd::args("error", &[message.unwrap_or_default()]).with("value", value),
+			Layer::Input
+		));
+	}
+
 	#[inline]
 	pub fn complete(word: &str, ticket: usize) {
 		emit!(Call(Cmd::args("complete", &[word]).with("ticket", ticket), Layer::Input));
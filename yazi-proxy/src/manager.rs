@@ -1,3 +1,5 @@
+use std::{sync::atomic::{AtomicU64, Ordering}, time::Duration};
+
 use yazi_macro::emit;
 use yazi_shared::{Layer, event::Cmd, fs::Url};
 
@@ -19,11 +21,39 @@ impl ManagerProxy {
 		));
 	}
 
+	/// Spots the hovered file again if the spot is shown, e.g. after changing it.
+	#[inline]
+	pub fn respot() {
+		emit!(Call(Cmd::new("spot").with_bool("refresh", true), Layer::Manager));
+	}
+
 	#[inline]
 	pub fn refresh() {
 		emit!(Call(Cmd::new("refresh"), Layer::Manager));
 	}
 
+	/// Reads the CWD, parent and hovered directory again, after something that
+	/// is likely to have changed them, e.g. a blocking process. It's done once
+	/// those coming in quick succession are all over.
+	pub fn reload() {
+		static SEQ: AtomicU64 = AtomicU64::new(0);
+		let seq = SEQ.fetch_add(1, Ordering::Relaxed) + 1;
+
+		tokio::spawn(async move {
+			tokio::time::sleep(Duration::from_millis(200)).await;
+			if SEQ.load(Ordering::Relaxed) == seq {
+				emit!(Call(Cmd::new("refresh").with_bool("force", true), Layer::Manager));
+			}
+		});
+	}
+
+	/// Opens the hovered file, once the events emitted before, e.g. a `hover`,
+	/// have been handled.
+	#[inline]
+	pub fn open_hovered() {
+		emit!(Call(Cmd::new("open").with_bool("hovered", true), Layer::Manager));
+	}
+
 	#[inline]
 	pub fn open_do(opt: OpenDoOpt) {
 		emit!(Call(Cmd::new("open_do").with_any("option", opt), Layer::Manager));
@@ -37,11 +67,26 @@ impl ManagerProxy {
 		));
 	}
 
+	#[inline]
+	pub fn compress_do(targets: Vec<Url>, to: Url) {
+		emit!(Call(Cmd::args("compress_do", &[to]).with_any("targets", targets), Layer::Manager));
+	}
+
+	#[inline]
+	pub fn duplicate_do(pairs: Vec<(Url, Url)>) {
+		emit!(Call(Cmd::new("duplicate_do").with_any("pairs", pairs), Layer::Manager));
+	}
+
 	#[inline]
 	pub fn update_tasks(url: &Url) {
 		emit!(Call(Cmd::new("update_tasks").with_any("urls", vec![url.clone()]), Layer::Manager));
 	}
 
+	#[inline]
+	pub fn update_tagged() {
+		emit!(Call(Cmd::new("update_tagged"), Layer::Manager));
+	}
+
 	#[inline]
 	pub fn update_paged() {
 		emit!(Call(Cmd::new("update_paged"), Layer::Manager));
@@ -1 +1 @@
-yazi_macro::mod_flat!(notify open process search);
+yazi_macro::mod_flat!(notify open plugin process search);
@@ -3,13 +3,39 @@ use std::{str::FromStr, time::Duration};
 use anyhow::bail;
 use mlua::{ExternalError, ExternalResult};
 use yazi_config::THEME;
-use yazi_shared::{event::Cmd, theme::Style};
+use yazi_macro::emit;
+use yazi_shared::{Layer, event::Cmd, theme::Style};
 
 pub struct NotifyOpt {
 	pub title:   String,
 	pub content: String,
 	pub level:   NotifyLevel,
 	pub timeout: Duration,
+	pub actions: Vec<NotifyAction>,
+}
+
+/// A labeled callback attached to a notification, which can be invoked with
+/// the `notify_action` command while the notification is visible.
+///
+/// Notifications with actions don't time out, they stay until dismissed.
+pub struct NotifyAction {
+	pub label: String,
+	pub run:   Box<dyn FnOnce() + Send>,
+}
+
+impl NotifyAction {
+	/// The maximum number of actions a notification can carry.
+	pub const LIMIT: usize = 3;
+
+	#[inline]
+	pub fn new(label: impl Into<String>, run: impl FnOnce() + Send + 'static) -> Self {
+		Self { label: label.into(), run: Box::new(run) }
+	}
+
+	#[inline]
+	pub fn cmds(label: impl Into<String>, cmds: Vec<Cmd>, layer: Layer) -> Self {
+		Self::new(label, move || emit!(Seq(cmds.into(), layer)))
+	}
 }
 
 impl TryFrom<Cmd> for NotifyOpt {
@@ -38,6 +64,7 @@ impl<'a> TryFrom<mlua::Table<'a>> for NotifyOpt {
 			content: t.raw_get("content")?,
 			level,
 			timeout: Duration::from_secs_f64(timeout),
+			actions: vec![],
 		})
 	}
 }
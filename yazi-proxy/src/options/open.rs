@@ -1,14 +1,21 @@
 use std::borrow::Cow;
 
+use tokio::sync::oneshot;
 use yazi_config::open::Opener;
 use yazi_shared::{event::Cmd, fs::Url};
 
+use super::ProcessOrigin;
+
 // --- Open
 #[derive(Default)]
 pub struct OpenDoOpt {
 	pub hovered:     Url,
 	pub targets:     Vec<(Url, String)>,
 	pub interactive: bool,
+	/// Whether several openers can be picked to run one after another, when
+	/// `interactive`.
+	pub multi:       bool,
+	pub origin:      ProcessOrigin,
 }
 
 impl From<Cmd> for OpenDoOpt {
@@ -19,6 +26,9 @@ impl From<Cmd> for OpenDoOpt {
 pub struct OpenWithOpt {
 	pub targets: Vec<Url>,
 	pub opener:  Cow<'static, Opener>,
+	pub origin:  ProcessOrigin,
+	/// Told once all the tasks the `targets` are opened in have finished.
+	pub done:    Option<oneshot::Sender<()>>,
 }
 
 impl TryFrom<Cmd> for OpenWithOpt {
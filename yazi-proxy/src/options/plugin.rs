@@ -0,0 +1,48 @@
+use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+
+use tokio::sync::{mpsc, oneshot};
+use yazi_shared::event::Cmd;
+
+// --- Job
+pub struct PluginJobOpt {
+	pub title: String,
+	pub total: u64,
+	pub done:  oneshot::Sender<PluginJob>,
+}
+
+impl TryFrom<Cmd> for PluginJobOpt {
+	type Error = ();
+
+	fn try_from(mut c: Cmd) -> Result<Self, Self::Error> { c.take_any("option").ok_or(()) }
+}
+
+/// The plugin side of a task created by a plugin, which is considered failed
+/// if dropped before [`PluginJob::finish`] is called.
+pub struct PluginJob {
+	pub id:       usize,
+	pub tx:       mpsc::UnboundedSender<PluginJobProg>,
+	pub canceled: Arc<AtomicBool>,
+}
+
+#[derive(Debug)]
+pub enum PluginJobProg {
+	Adv(u64),
+	Log(String),
+	Succ,
+	Fail(String),
+}
+
+impl PluginJob {
+	#[inline]
+	pub fn progress(&self, n: u64) { self.tx.send(PluginJobProg::Adv(n)).ok(); }
+
+	#[inline]
+	pub fn log(&self, line: String) { self.tx.send(PluginJobProg::Log(line)).ok(); }
+
+	#[inline]
+	pub fn canceled(&self) -> bool { self.canceled.load(Ordering::Relaxed) }
+
+	pub fn finish(self, error: Option<String>) {
+		self.tx.send(error.map_or(PluginJobProg::Succ, PluginJobProg::Fail)).ok();
+	}
+}
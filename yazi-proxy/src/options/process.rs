@@ -1,11 +1,22 @@
-use std::{borrow::Cow, ffi::OsString};
+use std::{borrow::Cow, ffi::OsString, path::PathBuf};
 
 use tokio::sync::oneshot;
 use yazi_config::open::Opener;
 use yazi_shared::event::Cmd;
 
+// --- Origin
+/// Where a process is queued from, captured at the time so that switching tabs
+/// before it starts doesn't change the CWD it runs in.
+#[derive(Clone, Debug, Default)]
+pub struct ProcessOrigin {
+	pub cwd: PathBuf,
+	/// The tab it's queued from, if any.
+	pub tab: Option<usize>,
+}
+
 // --- Exec
 pub struct ProcessExecOpt {
+	pub cwd:    PathBuf,
 	pub args:   Vec<OsString>,
 	pub opener: Cow<'static, Opener>,
 	pub done:   oneshot::Sender<()>,
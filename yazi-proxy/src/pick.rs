@@ -7,7 +7,17 @@ pub struct PickProxy;
 
 impl PickProxy {
 	#[inline]
-	pub async fn show(cfg: PickCfg) -> anyhow::Result<usize> {
+	pub async fn show(cfg: PickCfg) -> anyhow::Result<usize> { Ok(Self::pick(cfg).await?[0]) }
+
+	/// Lets several items be toggled, and returns their indices in ascending
+	/// order, or only the one under the cursor if none of them is, unless some
+	/// were toggled at first with `selected`.
+	#[inline]
+	pub async fn show_multi(cfg: PickCfg) -> anyhow::Result<Vec<usize>> {
+		Self::pick(PickCfg { multi: true, ..cfg }).await
+	}
+
+	async fn pick(cfg: PickCfg) -> anyhow::Result<Vec<usize>> {
 		let (tx, rx) = oneshot::channel();
 		emit!(Call(Cmd::new("show").with_any("tx", tx).with_any("cfg", cfg), Layer::Pick));
 		rx.await?
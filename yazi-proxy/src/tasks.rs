@@ -1,30 +1,62 @@
-use std::{borrow::Cow, ffi::OsString};
+use std::{borrow::Cow, ffi::OsString, path::PathBuf};
 
 use tokio::sync::oneshot;
 use yazi_config::open::Opener;
 use yazi_macro::emit;
 use yazi_shared::{Layer, event::Cmd, fs::Url};
 
-use crate::options::{OpenWithOpt, ProcessExecOpt};
+use crate::options::{OpenWithOpt, PluginJob, PluginJobOpt, ProcessExecOpt, ProcessOrigin};
 
 pub struct TasksProxy;
 
 impl TasksProxy {
 	#[inline]
-	pub fn open_with(targets: Vec<Url>, opener: Cow<'static, Opener>) {
+	pub fn open_with(targets: Vec<Url>, opener: Cow<'static, Opener>, origin: ProcessOrigin) {
 		emit!(Call(
-			Cmd::new("open_with").with_any("option", OpenWithOpt { targets, opener }),
+			Cmd::new("open_with").with_any("option", OpenWithOpt { targets, opener, origin, done: None }),
 			Layer::Tasks
 		));
 	}
 
+	/// Opens the `targets` with the `opener`, and waits for all of the tasks
+	/// they're opened in to finish.
 	#[inline]
-	pub async fn process_exec(args: Vec<OsString>, opener: Cow<'static, Opener>) {
+	pub async fn open_with_wait(
+		targets: Vec<Url>,
+		opener: Cow<'static, Opener>,
+		origin: ProcessOrigin,
+	) {
 		let (tx, rx) = oneshot::channel();
 		emit!(Call(
-			Cmd::new("process_exec").with_any("option", ProcessExecOpt { args, opener, done: tx }),
+			Cmd::new("open_with").with_any("option", OpenWithOpt {
+				targets,
+				opener,
+				origin,
+				done: Some(tx)
+			}),
 			Layer::Tasks
 		));
 		rx.await.ok();
 	}
+
+	/// Runs the `opener` with `args` in `cwd`, and waits for it to finish.
+	#[inline]
+	pub async fn process_exec(cwd: PathBuf, args: Vec<OsString>, opener: Cow<'static, Opener>) {
+		let (tx, rx) = oneshot::channel();
+		emit!(Call(
+			Cmd::new("process_exec").with_any("option", ProcessExecOpt { cwd, args, opener, done: tx }),
+			Layer::Tasks
+		));
+		rx.await.ok();
+	}
+
+	#[inline]
+	pub async fn plugin_job(title: String, total: u64) -> Option<PluginJob> {
+		let (tx, rx) = oneshot::channel();
+		emit!(Call(
+			Cmd::new("plugin_job").with_any("option", PluginJobOpt { title, total, done: tx }),
+			Layer::Tasks
+		));
+		rx.await.ok()
+	}
 }
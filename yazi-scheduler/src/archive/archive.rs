@@ -0,0 +1,591 @@
+use std::{collections::HashSet, fs::{self, File}, io::{self, BufReader, BufWriter, Read, Write}, path::{Path, PathBuf}, process::{Output, Stdio}, sync::atomic::Ordering, time::Duration};
+
+use anyhow::{Result, anyhow, bail};
+use tokio::{process::Command, select, sync::mpsc, task::spawn_blocking, time::sleep};
+use yazi_config::popup::{ConfirmCfg, InputCfg};
+use yazi_fs::archive::{ArchiveCompress, ArchiveKind, Vfs, safe_link, through_link};
+use yazi_proxy::{ConfirmProxy, InputProxy};
+use yazi_shared::fs::max_common_root;
+use zip::{ZipArchive, ZipWriter, result::ZipError, write::SimpleFileOptions};
+
+use super::{ArchiveOp, ArchiveOpCompress, ArchiveOpCopy, ArchiveOpExtract, ArchiveOpUnpack, Entry};
+use crate::{LOW, TaskOp, TaskProg};
+
+pub struct Archive {
+	macro_: async_priority_channel::Sender<TaskOp, u8>,
+	prog:   mpsc::UnboundedSender<TaskProg>,
+}
+
+// A file to be added to an archive
+#[derive(Debug)]
+struct Input {
+	path: PathBuf,
+	name: PathBuf,
+	size: u64,
+	dir:  bool,
+}
+
+// The external tools to try for formats that aren't supported natively,
+// in order of preference.
+const SEVEN_ZIP: [&str; 3] = ["7zz", "7z", "7za"];
+const UNRAR: &str = "unrar";
+
+impl Archive {
+	pub fn new(
+		macro_: async_priority_channel::Sender<TaskOp, u8>,
+		prog: mpsc::UnboundedSender<TaskProg>,
+	) -> Self {
+		Self { macro_, prog }
+	}
+
+	pub async fn work(&self, op: ArchiveOp) -> Result<()> {
+		match op {
+			ArchiveOp::Unpack(task) => match task.tool {
+				Some(tool) => self.unpack_external(&task, tool).await,
+				None => {
+					let prog = self.prog.clone();
+					spawn_blocking(move || Self::unpack_native(&prog, &task)).await?
+				}
+			},
+			ArchiveOp::Compress(task) => self.pack(task).await,
+		}
+	}
+
+	/// Lists the entries of the archive, and asks about the conflicts and the
+	/// password, if any, before queuing the unpacking.
+	pub async fn extract(&self, task: ArchiveOpExtract) -> Result<()> {
+		let id = task.id;
+		let (entries, tool) = match task.kind {
+			ArchiveKind::Zip => (Self::list_zip(&task.from).await?, None),
+			ArchiveKind::Tar(c) => (Self::list_tar(&task.from, c).await?, None),
+			ArchiveKind::SevenZip | ArchiveKind::Rar => {
+				let (entries, tool) = Self::list_external(&task.from, task.kind).await?;
+				(entries, Some(tool))
+			}
+		};
+
+		let mut conflicts = vec![];
+		for e in entries.iter().filter(|e| !e.dir) {
+			let dest = task.to.join(&e.path);
+			if fs::symlink_metadata(&dest).is_ok_and(|m| !m.is_dir()) {
+				conflicts.push(e.path.to_string_lossy().into_owned());
+			}
+		}
+
+		let overwrite =
+			conflicts.is_empty() || ConfirmProxy::show(ConfirmCfg::extract(conflicts)).await;
+		let password = match entries.iter().find(|e| e.encrypted) {
+			Some(e) => Some(self.password(&task, e.index).await?),
+			None => None,
+		};
+
+		if task.canceled.load(Ordering::Relaxed) {
+			return Ok(());
+		}
+		for e in &entries {
+			self.prog.send(TaskProg::New(id, e.size))?;
+		}
+
+		fs::create_dir_all(&task.to)?;
+		let ArchiveOpExtract { from, to, kind, canceled, .. } = task;
+		let op = ArchiveOpUnpack { id, from, to, kind, canceled, entries, tool, password, overwrite };
+		self.queue(ArchiveOp::Unpack(op), LOW).await?;
+
+		Ok(self.prog.send(TaskProg::Succ(id))?)
+	}
+
+	async fn list_zip(from: &Path) -> Result<Vec<Entry>> {
+		let from = from.to_owned();
+		spawn_blocking(move || {
+			let mut zip = ZipArchive::new(BufReader::new(File::open(from)?))?;
+			let mut entries = Vec::with_capacity(zip.len());
+			for index in 0..zip.len() {
+				let file = zip.by_index_raw(index)?;
+				let Some(path) = file.enclosed_name() else {
+					bail!("Unsafe path in archive: {}", file.name());
+				};
+				entries.push(Entry {
+					index,
+					path,
+					size: file.size(),
+					dir: file.is_dir(),
+					encrypted: file.encrypted(),
+				});
+			}
+			Ok(entries)
+		})
+		.await?
+	}
+
+	async fn list_tar(from: &Path, compress: ArchiveCompress) -> Result<Vec<Entry>> {
+		let from = from.to_owned();
+		spawn_blocking(move || {
+			let mut tar = tar::Archive::new(compress.decoder(&from)?);
+			let mut entries = vec![];
+			for (index, entry) in tar.entries()?.enumerate() {
+				let entry = entry?;
+				entries.push(Entry {
+					index,
+					path: entry.path()?.into_owned(),
+					size: entry.header().size()?,
+					dir: entry.header().entry_type().is_dir(),
+					encrypted: false,
+				});
+			}
+			Ok(entries)
+		})
+		.await?
+	}
+
+	async fn list_external(from: &Path, kind: ArchiveKind) -> Result<(Vec<Entry>, &'static str)> {
+		let tools = match kind {
+			ArchiveKind::Rar => [&[UNRAR][..], &SEVEN_ZIP].concat(),
+			_ => SEVEN_ZIP.to_vec(),
+		};
+
+		for tool in tools {
+			let mut cmd = Command::new(tool);
+			if tool == UNRAR {
+				cmd.args(["lb", "-p-"]);
+			} else {
+				cmd.args(["l", "-ba", "-slt", "-p"]);
+			}
+
+			let output = match cmd.arg(from).stdin(Stdio::null()).output().await {
+				Ok(output) => output,
+				Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+				Err(e) => Err(e)?,
+			};
+			if !output.status.success() {
+				bail!("`{tool}` failed to list the archive:\n{}", String::from_utf8_lossy(&output.stderr));
+			}
+
+			let stdout = String::from_utf8_lossy(&output.stdout);
+			let entries =
+				if tool == UNRAR { Self::parse_unrar(&stdout) } else { Self::parse_seven_zip(&stdout) };
+			return Ok((entries, tool));
+		}
+
+		bail!("No tool found to extract the archive, please install one of: {}", match kind {
+			ArchiveKind::Rar => format!("{UNRAR}, {}", SEVEN_ZIP.join(", ")),
+			_ => SEVEN_ZIP.join(", "),
+		})
+	}
+
+	fn parse_seven_zip(s: &str) -> Vec<Entry> {
+		let mut entries: Vec<Entry> = vec![];
+		for line in s.lines() {
+			let Some((key, value)) = line.split_once(" = ") else { continue };
+			if key == "Path" {
+				entries.push(Entry {
+					index:     entries.len(),
+					path:      value.into(),
+					size:      0,
+					dir:       false,
+					encrypted: false,
+				});
+			}
+
+			let Some(e) = entries.last_mut() else { continue };
+			match key {
+				"Size" => e.size = value.parse().unwrap_or(0),
+				"Folder" => e.dir |= value == "+",
+				"Attributes" => e.dir |= value.starts_with('D'),
+				_ => {}
+			}
+		}
+		entries
+	}
+
+	fn parse_unrar(s: &str) -> Vec<Entry> {
+		s.lines()
+			.filter(|l| !l.is_empty())
+			.enumerate()
+			.map(|(index, l)| Entry { index, path: l.into(), size: 0, dir: false, encrypted: false })
+			.collect()
+	}
+
+	async fn password(&self, task: &ArchiveOpExtract, index: usize) -> Result<Vec<u8>> {
+		let name = task.from.name().to_string_lossy().into_owned();
+		loop {
+			let mut rx = InputProxy::show(InputCfg::password(&name));
+			let Some(Ok(password)) = rx.recv().await else {
+				bail!("A password is required to extract the archive");
+			};
+
+			let from = task.from.to_path_buf();
+			let password = password.into_bytes();
+			let result = spawn_blocking(move || {
+				let mut zip = ZipArchive::new(BufReader::new(File::open(from)?))?;
+				let valid = match zip.by_index_decrypt(index, &password) {
+					Ok(_) => true,
+					Err(ZipError::InvalidPassword) => false,
+					Err(e) => Err(e)?,
+				};
+				Ok::<_, anyhow::Error>(valid.then_some(password))
+			})
+			.await??;
+
+			if let Some(password) = result {
+				return Ok(password);
+			}
+			self.prog.send(TaskProg::Log(task.id, "Incorrect password, please try again".to_owned()))?;
+		}
+	}
+
+	fn unpack_native(prog: &mpsc::UnboundedSender<TaskProg>, task: &ArchiveOpUnpack) -> Result<()> {
+		let (entries, password, overwrite) = (&task.entries, &task.password, task.overwrite);
+		match task.kind {
+			ArchiveKind::Zip => {
+				let mut zip = ZipArchive::new(BufReader::new(File::open(&task.from)?))?;
+				for e in entries {
+					if task.canceled.load(Ordering::Relaxed) {
+						break;
+					}
+					let result =
+						Self::unpack_zip_entry(&mut zip, &task.to, e, password.as_deref(), overwrite);
+					Self::report(prog, task.id, e, result)?;
+				}
+			}
+			ArchiveKind::Tar(c) => {
+				let mut tar = tar::Archive::new(c.decoder(&task.from)?);
+				tar.set_preserve_mtime(true);
+				for (entry, e) in tar.entries()?.zip(entries) {
+					if task.canceled.load(Ordering::Relaxed) {
+						break;
+					}
+					let result = entry.map_err(Into::into).and_then(|mut entry| {
+						if !overwrite && !e.dir && fs::symlink_metadata(task.to.join(&e.path)).is_ok() {
+							return Ok(());
+						}
+						entry.unpack_in(&task.to)?;
+						Ok(())
+					});
+					Self::report(prog, task.id, e, result)?;
+				}
+			}
+			_ => unreachable!(),
+		}
+		Ok(())
+	}
+
+	fn unpack_zip_entry(
+		zip: &mut ZipArchive<BufReader<File>>,
+		to: &Path,
+		e: &Entry,
+		password: Option<&[u8]>,
+		overwrite: bool,
+	) -> Result<()> {
+		if through_link(to, &e.path) {
+			bail!("Refusing to extract through a symlink: {}", e.path.display());
+		}
+
+		let dest = to.join(&e.path);
+		if e.dir {
+			return Ok(fs::create_dir_all(dest)?);
+		}
+
+		match fs::symlink_metadata(&dest) {
+			Ok(_) if !overwrite => return Ok(()),
+			Ok(m) if !m.is_dir() => fs::remove_file(&dest)?,
+			_ => {}
+		}
+		if let Some(parent) = dest.parent() {
+			fs::create_dir_all(parent)?;
+		}
+
+		let mut file = match password {
+			Some(p) if e.encrypted => zip.by_index_decrypt(e.index, p)?,
+			_ => zip.by_index(e.index)?,
+		};
+
+		#[cfg(unix)]
+		if file.is_symlink() {
+			let mut target = String::new();
+			file.read_to_string(&mut target)?;
+			if !safe_link(Path::new(&target)) {
+				bail!("Unsafe symlink target in archive: {target}");
+			}
+			return Ok(std::os::unix::fs::symlink(target, dest)?);
+		}
+
+		io::copy(&mut file, &mut File::create(&dest)?)?;
+
+		#[cfg(unix)]
+		if let Some(mode) = file.unix_mode() {
+			use std::os::unix::fs::PermissionsExt;
+			fs::set_permissions(&dest, fs::Permissions::from_mode(mode & 0o777))?;
+		}
+		Ok(())
+	}
+
+	async fn unpack_external(&self, task: &ArchiveOpUnpack, tool: &str) -> Result<()> {
+		let (entries, overwrite) = (&task.entries, task.overwrite);
+		let mut cmd = Command::new(tool);
+		if tool == UNRAR {
+			cmd.args(["x", "-y", "-p-", if overwrite { "-o+" } else { "-o-" }]);
+			cmd.arg(&task.from).arg(task.to.join(""));
+		} else {
+			cmd.args(["x", "-y", "-p", if overwrite { "-aoa" } else { "-aos" }]);
+			cmd.arg(format!("-o{}", task.to.display())).arg(&task.from);
+		}
+
+		let output = cmd.stdin(Stdio::null()).kill_on_drop(true).output();
+		tokio::pin!(output);
+
+		let output: Output = loop {
+			select! {
+				output = &mut output => break output?,
+				_ = sleep(Duration::from_millis(100)) => {
+					if task.canceled.load(Ordering::Relaxed) {
+						return Ok(());
+					}
+				}
+			}
+		};
+
+		if !output.status.success() {
+			bail!("`{tool}` failed to extract the archive:\n{}", String::from_utf8_lossy(&output.stderr));
+		}
+
+		let size = entries.iter().map(|e| e.size).sum();
+		Ok(self.prog.send(TaskProg::Adv(task.id, entries.len() as u32, size))?)
+	}
+
+	/// Queues the compression, which is done as a whole on the macro queue.
+	#[inline]
+	pub async fn compress(&self, task: ArchiveOpCompress) -> Result<()> {
+		self.queue(ArchiveOp::Compress(task), LOW).await
+	}
+
+	async fn pack(&self, task: ArchiveOpCompress) -> Result<()> {
+		let id = task.id;
+		let tmp = task.to.with_file_name(format!(".{}.part", task.to.name().to_string_lossy()));
+
+		let (inputs, skipped) = {
+			let (targets, follow) = (task.targets.clone(), task.kind == ArchiveKind::Zip);
+			let skip = [task.to.to_path_buf(), tmp.clone()];
+			spawn_blocking(move || Self::walk(&targets, follow, &skip)).await??
+		};
+		for s in skipped {
+			self.prog.send(TaskProg::Log(id, s))?;
+		}
+		for i in &inputs {
+			self.prog.send(TaskProg::New(id, i.size))?;
+		}
+
+		let prog = self.prog.clone();
+		let result = spawn_blocking({
+			let tmp = tmp.clone();
+			move || {
+				let file = BufWriter::new(File::create(&tmp)?);
+				match task.kind {
+					ArchiveKind::Zip => Self::pack_zip(&prog, &task, &inputs, file)?.flush()?,
+					ArchiveKind::Tar(c) => Self::pack_tar(&prog, &task, &inputs, file, c)?,
+					_ => bail!("Creating this kind of archive is not supported"),
+				}
+				if task.canceled.load(Ordering::Relaxed) {
+					return Ok(false);
+				}
+				fs::rename(&tmp, &task.to)?;
+				Ok(true)
+			}
+		})
+		.await?;
+
+		match result {
+			Ok(true) => Ok(self.prog.send(TaskProg::Succ(id))?),
+			Ok(false) => Ok(fs::remove_file(&tmp).unwrap_or(())),
+			Err(e) => {
+				fs::remove_file(&tmp).ok();
+				Err(e)
+			}
+		}
+	}
+
+	/// Copies an entry out of an archive, by extracting it to `to`.
+	pub async fn copy(&self, task: ArchiveOpCopy) -> Result<()> {
+		self.prog.send(TaskProg::New(task.id, 0))?;
+		Vfs::extract(&task.from, &task.to).await?;
+
+		self.prog.send(TaskProg::Adv(task.id, 1, 0))?;
+		Ok(self.prog.send(TaskProg::Succ(task.id))?)
+	}
+
+	/// Walks the `targets` for the files to add, where those under them that
+	/// can't be read are skipped, with the reasons returned, rather than failing.
+	fn walk(
+		targets: &[yazi_shared::fs::Url],
+		follow: bool,
+		skip: &[PathBuf],
+	) -> Result<(Vec<Input>, Vec<String>)> {
+		let root = max_common_root(targets);
+		let (mut inputs, mut skipped) = (vec![], vec![]);
+		let mut visited = HashSet::new();
+
+		let mut stack: Vec<_> = targets.iter().rev().map(|u| (u.to_path_buf(), true)).collect();
+		while let Some((path, top)) = stack.pop() {
+			if skip.contains(&path) {
+				continue;
+			}
+
+			let (meta, children) = match Self::walk_one(&path, follow, &mut visited) {
+				Ok(Some(v)) => v,
+				Ok(None) => continue,
+				Err(e) if top => Err(e)?,
+				Err(e) => {
+					skipped.push(format!("Skipped {}: {e}", path.display()));
+					continue;
+				}
+			};
+
+			stack.extend(children.into_iter().map(|p| (p, false)));
+			inputs.push(Input {
+				name: path.strip_prefix(&root).unwrap_or(&path).to_owned(),
+				path,
+				size: if meta.is_file() { meta.len() } else { 0 },
+				dir: meta.is_dir(),
+			});
+		}
+		Ok((inputs, skipped))
+	}
+
+	// The metadata of `path`, and its children in reverse order if it's a
+	// directory, or `None` if it's been walked already.
+	fn walk_one(
+		path: &Path,
+		follow: bool,
+		visited: &mut HashSet<PathBuf>,
+	) -> io::Result<Option<(fs::Metadata, Vec<PathBuf>)>> {
+		let meta = if follow { fs::metadata(path) } else { fs::symlink_metadata(path) }?;
+		if !meta.is_dir() {
+			return Ok(Some((meta, vec![])));
+		}
+
+		// Following symlinks may lead back to a directory that's already walked
+		if follow && !visited.insert(fs::canonicalize(path)?) {
+			return Ok(None);
+		}
+
+		let mut children: Vec<_> =
+			fs::read_dir(path)?.map(|e| e.map(|e| e.path())).collect::<io::Result<_>>()?;
+		children.sort_unstable_by(|a, b| b.cmp(a));
+		Ok(Some((meta, children)))
+	}
+
+	fn pack_zip<W: Write + io::Seek>(
+		prog: &mpsc::UnboundedSender<TaskProg>,
+		task: &ArchiveOpCompress,
+		inputs: &[Input],
+		w: W,
+	) -> Result<W> {
+		let mut zip = ZipWriter::new(w);
+		for i in inputs {
+			if task.canceled.load(Ordering::Relaxed) {
+				break;
+			}
+
+			let name =
+				i.name.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+			let mut options = SimpleFileOptions::default().large_file(i.size >= u32::MAX as u64);
+			#[cfg(unix)]
+			{
+				use std::os::unix::fs::PermissionsExt;
+				options = options.unix_permissions(fs::metadata(&i.path)?.permissions().mode() & 0o7777);
+			}
+
+			let result = if i.dir {
+				zip.add_directory(name, options).map_err(Into::into)
+			} else {
+				zip.start_file(name, options).map_err(Into::into).and_then(|_| {
+					io::copy(&mut File::open(&i.path)?, &mut zip)?;
+					Ok(())
+				})
+			};
+			Self::report_input(prog, task.id, i, result)?;
+		}
+		Ok(zip.finish()?)
+	}
+
+	fn pack_tar<W: Write>(
+		prog: &mpsc::UnboundedSender<TaskProg>,
+		task: &ArchiveOpCompress,
+		inputs: &[Input],
+		w: W,
+		compress: ArchiveCompress,
+	) -> Result<()> {
+		fn build<W: Write>(
+			prog: &mpsc::UnboundedSender<TaskProg>,
+			task: &ArchiveOpCompress,
+			inputs: &[Input],
+			w: W,
+		) -> Result<W> {
+			let mut tar = tar::Builder::new(w);
+			tar.follow_symlinks(false);
+			for i in inputs {
+				if task.canceled.load(Ordering::Relaxed) {
+					break;
+				}
+				let result = tar.append_path_with_name(&i.path, &i.name).map_err(Into::into);
+				Archive::report_input(prog, task.id, i, result)?;
+			}
+			Ok(tar.into_inner()?)
+		}
+
+		match compress {
+			ArchiveCompress::None => build(prog, task, inputs, w)?.flush()?,
+			ArchiveCompress::Gz => {
+				let w = flate2::write::GzEncoder::new(w, flate2::Compression::default());
+				build(prog, task, inputs, w)?.finish()?.flush()?
+			}
+			ArchiveCompress::Bz2 => {
+				let w = bzip2::write::BzEncoder::new(w, bzip2::Compression::default());
+				build(prog, task, inputs, w)?.finish()?.flush()?
+			}
+			ArchiveCompress::Xz => {
+				let w = xz2::write::XzEncoder::new(w, 6);
+				build(prog, task, inputs, w)?.finish()?.flush()?
+			}
+			ArchiveCompress::Zst => {
+				let w = zstd::Encoder::new(w, 0)?;
+				build(prog, task, inputs, w)?.finish()?.flush()?
+			}
+		}
+		Ok(())
+	}
+
+	fn report_input(
+		prog: &mpsc::UnboundedSender<TaskProg>,
+		id: usize,
+		i: &Input,
+		result: Result<()>,
+	) -> Result<()> {
+		match result {
+			Ok(()) => prog.send(TaskProg::Adv(id, 1, i.size))?,
+			Err(err) => {
+				prog.send(TaskProg::Fail(id, format!("Failed to add {}: {err}", i.path.display())))?
+			}
+		}
+		Ok(())
+	}
+
+	fn report(
+		prog: &mpsc::UnboundedSender<TaskProg>,
+		id: usize,
+		e: &Entry,
+		result: Result<()>,
+	) -> Result<()> {
+		match result {
+			Ok(()) => prog.send(TaskProg::Adv(id, 1, e.size))?,
+			Err(err) => {
+				prog.send(TaskProg::Fail(id, format!("Failed to extract {}: {err}", e.path.display())))?
+			}
+		}
+		Ok(())
+	}
+
+	#[inline]
+	async fn queue(&self, op: impl Into<TaskOp>, priority: u8) -> Result<()> {
+		self.macro_.send(op.into(), priority).await.map_err(|_| anyhow!("Failed to send task"))
+	}
+}
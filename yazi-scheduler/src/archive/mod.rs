@@ -0,0 +1,3 @@
+#![allow(clippy::module_inception)]
+
+yazi_macro::mod_flat!(archive op);
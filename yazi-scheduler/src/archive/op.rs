@@ -0,0 +1,69 @@
+use std::{path::PathBuf, sync::{Arc, atomic::AtomicBool}};
+
+use yazi_fs::archive::ArchiveKind;
+use yazi_shared::fs::Url;
+
+#[derive(Debug)]
+pub enum ArchiveOp {
+	Unpack(ArchiveOpUnpack),
+	Compress(ArchiveOpCompress),
+}
+
+impl ArchiveOp {
+	pub fn id(&self) -> usize {
+		match self {
+			Self::Unpack(op) => op.id,
+			Self::Compress(op) => op.id,
+		}
+	}
+}
+
+#[derive(Debug)]
+pub struct ArchiveOpExtract {
+	pub id:       usize,
+	pub from:     Url,
+	pub to:       Url,
+	pub kind:     ArchiveKind,
+	pub canceled: Arc<AtomicBool>,
+}
+
+// --- Unpack
+/// An extraction whose entries are listed, and whose prompts are answered, so
+/// that only the unpacking is left to the macro queue.
+#[derive(Debug)]
+pub struct ArchiveOpUnpack {
+	pub(super) id:        usize,
+	pub(super) from:      Url,
+	pub(super) to:        Url,
+	pub(super) kind:      ArchiveKind,
+	pub(super) canceled:  Arc<AtomicBool>,
+	pub(super) entries:   Vec<Entry>,
+	pub(super) tool:      Option<&'static str>,
+	pub(super) password:  Option<Vec<u8>>,
+	pub(super) overwrite: bool,
+}
+
+#[derive(Debug)]
+pub(super) struct Entry {
+	pub(super) index:     usize,
+	pub(super) path:      PathBuf,
+	pub(super) size:      u64,
+	pub(super) dir:       bool,
+	pub(super) encrypted: bool,
+}
+
+#[derive(Debug)]
+pub struct ArchiveOpCompress {
+	pub id:       usize,
+	pub targets:  Vec<Url>,
+	pub to:       Url,
+	pub kind:     ArchiveKind,
+	pub canceled: Arc<AtomicBool>,
+}
+
+#[derive(Debug)]
+pub struct ArchiveOpCopy {
+	pub id:   usize,
+	pub from: Url,
+	pub to:   Url,
+}
@@ -0,0 +1,89 @@
+use std::{fmt::{self, Display}, str::FromStr};
+
+use anyhow::bail;
+use md5::{Digest, Md5};
+use sha2::Sha256;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChecksumAlgo {
+	#[default]
+	Sha256,
+	Md5,
+	Blake3,
+}
+
+impl ChecksumAlgo {
+	/// The suffixes of the sidecar files, such as `foo.iso.sha256sum`.
+	pub(super) fn suffixes(self) -> &'static [&'static str] {
+		match self {
+			Self::Sha256 => &["sha256sum", "sha256"],
+			Self::Md5 => &["md5sum", "md5"],
+			Self::Blake3 => &["b3sum", "b3", "blake3"],
+		}
+	}
+
+	/// The name of the file listing the checksums of a whole directory.
+	pub(super) fn sums(self) -> &'static str {
+		match self {
+			Self::Sha256 => "SHA256SUMS",
+			Self::Md5 => "MD5SUMS",
+			Self::Blake3 => "B3SUMS",
+		}
+	}
+
+	pub(super) fn hasher(self) -> Hasher {
+		match self {
+			Self::Sha256 => Hasher::Sha256(Sha256::new()),
+			Self::Md5 => Hasher::Md5(Md5::new()),
+			Self::Blake3 => Hasher::Blake3(Box::default()),
+		}
+	}
+}
+
+impl FromStr for ChecksumAlgo {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"sha256" => Self::Sha256,
+			"md5" => Self::Md5,
+			"blake3" => Self::Blake3,
+			_ => bail!("Unknown checksum algorithm: `{s}`, expected `sha256`, `md5` or `blake3`"),
+		})
+	}
+}
+
+impl Display for ChecksumAlgo {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::Sha256 => "sha256",
+			Self::Md5 => "md5",
+			Self::Blake3 => "blake3",
+		})
+	}
+}
+
+pub(super) enum Hasher {
+	Sha256(Sha256),
+	Md5(Md5),
+	Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+	pub(super) fn update(&mut self, buf: &[u8]) {
+		match self {
+			Self::Sha256(h) => h.update(buf),
+			Self::Md5(h) => h.update(buf),
+			Self::Blake3(h) => _ = h.update(buf),
+		}
+	}
+
+	pub(super) fn finalize(self) -> String {
+		let bytes = match self {
+			Self::Sha256(h) => h.finalize().to_vec(),
+			Self::Md5(h) => h.finalize().to_vec(),
+			Self::Blake3(h) => return h.finalize().to_hex().to_string(),
+		};
+		bytes.iter().map(|b| format!("{b:02x}")).collect()
+	}
+}
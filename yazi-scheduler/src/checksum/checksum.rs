@@ -0,0 +1,203 @@
+use std::{fs::File, io::{self, Read}, path::{Path, PathBuf}, sync::atomic::Ordering};
+
+use anyhow::Result;
+use ratatui::{style::{Style, Stylize}, text::Line};
+use tokio::{fs, sync::mpsc, task::spawn_blocking};
+use yazi_config::popup::{ConfirmCfg, InputCfg};
+use yazi_plugin::CLIPBOARD;
+use yazi_proxy::{ConfirmProxy, InputProxy};
+
+use super::{ChecksumAlgo, ChecksumOp};
+use crate::TaskProg;
+
+pub struct Checksum {
+	prog: mpsc::UnboundedSender<TaskProg>,
+}
+
+// How many bytes are hashed between two progress updates
+const CHUNK: usize = 1 << 20;
+
+enum Outcome {
+	Pass,
+	Fail(String),
+	Missing,
+	Error(io::Error),
+}
+
+impl Checksum {
+	pub fn new(prog: mpsc::UnboundedSender<TaskProg>) -> Self { Self { prog } }
+
+	pub async fn work(&self, task: ChecksumOp) -> Result<()> {
+		let id = task.id;
+
+		let mut files = Vec::with_capacity(task.targets.len());
+		for u in &task.targets {
+			match fs::metadata(u).await {
+				Ok(m) if m.is_dir() => {}
+				Ok(m) => files.push((u.to_path_buf(), m.len())),
+				Err(e) => self.prog.send(TaskProg::Log(id, format!("Skipped {u}: {e}")))?,
+			}
+		}
+
+		let mut expected = Vec::with_capacity(files.len());
+		if task.verify {
+			for (path, _) in &files {
+				expected.push(Self::expected(path, task.algo).await);
+			}
+			if let [(path, _)] = &files[..] {
+				if expected[0].is_none() {
+					expected[0] = Self::prompt(path).await;
+				}
+			}
+		}
+
+		for &(_, size) in &files {
+			self.prog.send(TaskProg::New(id, size))?;
+		}
+
+		let mut digests = Vec::with_capacity(files.len());
+		for (path, _) in &files {
+			let result = spawn_blocking({
+				let (id, prog, path, algo, canceled) =
+					(id, self.prog.clone(), path.clone(), task.algo, task.canceled.clone());
+				move || {
+					let mut file = File::open(path)?;
+					let (mut hasher, mut buf) = (algo.hasher(), vec![0; CHUNK]);
+					loop {
+						if canceled.load(Ordering::Relaxed) {
+							return Ok(None);
+						}
+						let n = file.read(&mut buf)?;
+						if n == 0 {
+							break;
+						}
+						hasher.update(&buf[..n]);
+						prog.send(TaskProg::Adv(id, 0, n as u64)).ok();
+					}
+					Ok::<_, io::Error>(Some(hasher.finalize()))
+				}
+			})
+			.await?;
+
+			match result {
+				Ok(Some(digest)) => digests.push(Ok(digest)),
+				Ok(None) => return Ok(()),
+				Err(e) => digests.push(Err(e)),
+			}
+			self.prog.send(TaskProg::Adv(id, 1, 0))?;
+		}
+
+		if task.verify {
+			Self::report(&files, expected, digests);
+		} else {
+			self.show(&files, digests).await;
+		}
+		Ok(self.prog.send(TaskProg::Succ(id))?)
+	}
+
+	async fn show(&self, files: &[(PathBuf, u64)], digests: Vec<io::Result<String>>) {
+		let (mut text, mut lines) = (String::new(), Vec::with_capacity(files.len()));
+		for ((path, _), digest) in files.iter().zip(digests) {
+			let name = Self::name(path);
+			match digest {
+				Ok(d) => {
+					text.push_str(&format!("{d}  {name}\n"));
+					lines.push(Line::raw(format!("{d}  {name}")));
+				}
+				Err(e) => lines.push(Line::styled(format!("{name}: {e}"), Style::new().red())),
+			}
+		}
+
+		CLIPBOARD.set(text).await;
+		ConfirmProxy::show_rx(ConfirmCfg::checksum(lines));
+	}
+
+	fn report(
+		files: &[(PathBuf, u64)],
+		expected: Vec<Option<String>>,
+		digests: Vec<io::Result<String>>,
+	) {
+		let outcomes: Vec<_> = expected
+			.into_iter()
+			.zip(digests)
+			.map(|(expected, digest)| match (expected, digest) {
+				(_, Err(e)) => Outcome::Error(e),
+				(None, _) => Outcome::Missing,
+				(Some(e), Ok(d)) if e == d => Outcome::Pass,
+				(Some(e), Ok(_)) => Outcome::Fail(e),
+			})
+			.collect();
+
+		let count = |f: fn(&Outcome) -> bool| outcomes.iter().filter(|o| f(o)).count();
+		let summary = format!(
+			"{} passed, {} failed, {} without a checksum",
+			count(|o| matches!(o, Outcome::Pass)),
+			count(|o| matches!(o, Outcome::Fail(_) | Outcome::Error(_))),
+			count(|o| matches!(o, Outcome::Missing)),
+		);
+
+		let lines = files
+			.iter()
+			.zip(outcomes)
+			.map(|((path, _), outcome)| {
+				let name = Self::name(path);
+				match outcome {
+					Outcome::Pass => Line::raw(format!("PASS     {name}")),
+					Outcome::Fail(e) => {
+						Line::styled(format!("FAIL     {name} (expected {e})"), Style::new().red())
+					}
+					Outcome::Missing => Line::styled(format!("MISSING  {name}"), Style::new().yellow()),
+					Outcome::Error(e) => Line::styled(format!("ERROR    {name}: {e}"), Style::new().red()),
+				}
+			})
+			.collect();
+
+		ConfirmProxy::show_rx(ConfirmCfg::verify(summary, lines));
+	}
+
+	/// Looks up the expected checksum of `path` from an adjacent sidecar file
+	/// like `foo.iso.sha256sum`, or from a `SHA256SUMS` in the same directory.
+	async fn expected(path: &Path, algo: ChecksumAlgo) -> Option<String> {
+		let name = Self::name(path);
+		let mut candidates: Vec<_> =
+			algo.suffixes().iter().map(|s| path.with_file_name(format!("{name}.{s}"))).collect();
+		candidates.push(path.with_file_name(algo.sums()));
+
+		for candidate in candidates {
+			let Ok(content) = fs::read_to_string(&candidate).await else { continue };
+			if let Some(digest) = Self::parse(&content, &name) {
+				return Some(digest);
+			}
+		}
+		None
+	}
+
+	/// Finds the digest of `name` in the `sha256sum`-style `content`, where each
+	/// line is the digest followed by the filename, which is prefixed with a `*`
+	/// in binary mode. A sidecar file with just the digest is accepted as well.
+	fn parse(content: &str, name: &str) -> Option<String> {
+		let mut only = None;
+		for line in content.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+			let (digest, file) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+			let file = file.trim_start().trim_start_matches('*');
+			if file.is_empty() {
+				only = Some(digest);
+			} else if Path::new(file).file_name().is_some_and(|f| f == name) {
+				return Some(digest.to_ascii_lowercase());
+			}
+		}
+		only.map(str::to_ascii_lowercase)
+	}
+
+	async fn prompt(path: &Path) -> Option<String> {
+		let mut rx = InputProxy::show(InputCfg::checksum(&Self::name(path)));
+		let value = rx.recv().await?.ok()?;
+		let digest = value.split_whitespace().next()?;
+		Some(digest.to_ascii_lowercase())
+	}
+
+	#[inline]
+	fn name(path: &Path) -> String {
+		path.file_name().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+	}
+}
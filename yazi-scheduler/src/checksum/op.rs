@@ -0,0 +1,14 @@
+use std::sync::{Arc, atomic::AtomicBool};
+
+use yazi_shared::fs::Url;
+
+use super::ChecksumAlgo;
+
+#[derive(Debug)]
+pub struct ChecksumOp {
+	pub id:       usize,
+	pub targets:  Vec<Url>,
+	pub algo:     ChecksumAlgo,
+	pub verify:   bool,
+	pub canceled: Arc<AtomicBool>,
+}
@@ -0,0 +1,52 @@
+use std::process::Stdio;
+
+use tokio::process::Command;
+
+/// Sends a desktop notification in the background, which silently does nothing
+/// if there's no notification daemon or tool to show it.
+pub(super) fn notify_desktop(title: String, body: String) {
+	tokio::spawn(async move {
+		let mut cmd = command(&title, &body);
+		cmd.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).kill_on_drop(true);
+		cmd.status().await.ok();
+	});
+}
+
+#[cfg(target_os = "macos")]
+fn command(title: &str, body: &str) -> Command {
+	let mut cmd = Command::new("osascript");
+	cmd
+		.args(["-e", "on run argv"])
+		.args(["-e", "display notification (item 2 of argv) with title (item 1 of argv)"])
+		.args(["-e", "end run", title, body]);
+	cmd
+}
+
+// Toasts are only shown for an AppUserModelID registered with the Start menu,
+// which Yazi has none of, so they're sent as from the PowerShell it runs
+#[cfg(windows)]
+fn command(title: &str, body: &str) -> Command {
+	const SCRIPT: &str = r#"
+		$m = [Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime]
+		$t = $m::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02)
+		$x = $t.GetElementsByTagName('text')
+		$x.Item(0).AppendChild($t.CreateTextNode($env:YAZI_NOTIFY_TITLE)) > $null
+		$x.Item(1).AppendChild($t.CreateTextNode($env:YAZI_NOTIFY_BODY)) > $null
+		$a = '{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\WindowsPowerShell\v1.0\powershell.exe'
+		$m::CreateToastNotifier($a).Show([Windows.UI.Notifications.ToastNotification]::new($t))
+	"#;
+
+	let mut cmd = Command::new("powershell");
+	cmd
+		.args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+		.env("YAZI_NOTIFY_TITLE", title)
+		.env("YAZI_NOTIFY_BODY", body);
+	cmd
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn command(title: &str, body: &str) -> Command {
+	let mut cmd = Command::new("notify-send");
+	cmd.args(["--app-name=Yazi", title, body]);
+	cmd
+}
@@ -1,12 +1,14 @@
-use std::{borrow::Cow, collections::VecDeque, path::{Path, PathBuf}};
+use std::{borrow::Cow, collections::{HashMap, HashSet, VecDeque}, path::{Path, PathBuf}, sync::atomic::Ordering, time::Duration};
 
-use anyhow::{Result, anyhow};
-use tokio::{fs::{self, DirEntry}, io::{self, ErrorKind::{AlreadyExists, NotFound}}, sync::mpsc};
+use anyhow::{Result, anyhow, bail};
+use tokio::{fs::{self, DirEntry}, io::{self, ErrorKind::{AlreadyExists, NotFound}}, select, sync::mpsc};
 use tracing::warn;
-use yazi_config::TASKS;
-use yazi_shared::fs::{Cha, Url, calculate_size, copy_with_progress, maybe_exists, ok_or_not_found, path_relative_to};
+use yazi_config::{MANAGER, TASKS};
+use yazi_fs::Unwritable;
+use yazi_proxy::AppProxy;
+use yazi_shared::fs::{Cha, FilesOp, Url, copy_with_progress, long, maybe_exists, ok_or_not_found, path_relative_to};
 
-use super::{FileOp, FileOpDelete, FileOpHardlink, FileOpLink, FileOpPaste, FileOpTrash};
+use super::{FileBatch, FileOp, FileOpDelete, FileOpHardlink, FileOpLink, FileOpPaste, FileOpTrash};
 use crate::{LOW, NORMAL, TaskOp, TaskProg};
 
 pub struct File {
@@ -25,14 +27,14 @@ impl File {
 	pub async fn work(&self, op: FileOp) -> Result<()> {
 		match op {
 			FileOp::Paste(mut task) => {
-				ok_or_not_found(fs::remove_file(&task.to).await)?;
-				let mut it = copy_with_progress(&task.from, &task.to, task.cha.unwrap());
+				ok_or_not_found(long::remove_file(&task.to).await)?;
+				let mut it = copy_with_progress(&task.from, &task.to, task.cha.unwrap(), task.preserve);
 
 				while let Some(res) = it.recv().await {
 					match res {
 						Ok(0) => {
 							if task.cut {
-								fs::remove_file(&task.from).await.ok();
+								long::remove_file(&task.from).await.ok();
 							}
 							break;
 						}
@@ -52,7 +54,12 @@ impl File {
 							self.queue(FileOp::Paste(task), LOW).await?;
 							return Ok(());
 						}
-						Err(e) => Err(e)?,
+						Err(e) => {
+							if self.abort_unwritable(task.id, &task.batch, &e, &task.to).await? {
+								return Ok(());
+							}
+							Err(e)?
+						}
 					}
 				}
 				self.prog.send(TaskProg::Adv(task.id, 1, 0))?;
@@ -61,7 +68,7 @@ impl File {
 				let cha = task.cha.unwrap();
 
 				let src = if task.resolve {
-					match fs::read_link(&task.from).await {
+					match long::read_link(&task.from).await {
 						Ok(p) => Cow::Owned(p),
 						Err(e) if e.kind() == NotFound => {
 							warn!("Link task partially done: {task:?}");
@@ -73,28 +80,41 @@ impl File {
 					Cow::Borrowed(task.from.as_path())
 				};
 
+				// Both ends are canonicalized, so that symlinked directories on the way
+				// don't throw off the relative path
 				let src = if task.relative {
-					path_relative_to(&src, &fs::canonicalize(task.to.parent().unwrap()).await?)
+					let src = match (src.parent(), src.file_name()) {
+						(Some(p), Some(n)) => fs::canonicalize(p).await?.join(n),
+						_ => src.into_owned(),
+					};
+					let root = fs::canonicalize(task.to.parent().unwrap()).await?;
+					Cow::Owned(path_relative_to(&src, &root).into_owned())
 				} else {
 					src
 				};
 
-				ok_or_not_found(fs::remove_file(&task.to).await)?;
+				ok_or_not_found(long::remove_file(&task.to).await)?;
 				#[cfg(unix)]
-				{
-					fs::symlink(src, &task.to).await?;
-				}
+				let result = long::symlink(src, &task.to).await;
 				#[cfg(windows)]
-				{
-					if cha.is_dir() {
-						fs::symlink_dir(src, &task.to).await?;
-					} else {
-						fs::symlink_file(src, &task.to).await?;
-					}
+				let result = if cha.is_dir() {
+					long::symlink_dir(src, &task.to).await
+				} else {
+					long::symlink_file(src, &task.to).await
+				};
+
+				// ERROR_PRIVILEGE_NOT_HELD
+				#[cfg(windows)]
+				if result.as_ref().is_err_and(|e| e.raw_os_error() == Some(1314)) {
+					bail!(
+						"Failed to link {:?}: creating symlinks requires Developer Mode to be enabled in the Windows settings, or running as administrator",
+						task.to
+					);
 				}
+				result?;
 
 				if task.delete {
-					fs::remove_file(&task.from).await.ok();
+					long::remove_file(&task.from).await.ok();
 				}
 				self.prog.send(TaskProg::Adv(task.id, 1, cha.len))?;
 			}
@@ -108,49 +128,79 @@ impl File {
 					Cow::Borrowed(task.from.as_path())
 				};
 
-				ok_or_not_found(fs::remove_file(&task.to).await)?;
-				match fs::hard_link(src, &task.to).await {
+				ok_or_not_found(long::remove_file(&task.to).await)?;
+				match long::hard_link(&src, &task.to).await {
 					Err(e) if e.kind() == NotFound => {
 						warn!("Hardlink task partially done: {task:?}");
 					}
+					Err(e) if Self::cross_device(&e) => bail!(
+						"Failed to hardlink {:?} to {:?}: they're on different filesystems, use `link` to create a symlink instead",
+						task.from,
+						task.to
+					),
 					v => v?,
 				}
 
 				self.prog.send(TaskProg::Adv(task.id, 1, cha.len))?;
 			}
 			FileOp::Delete(task) => {
-				if let Err(e) = fs::remove_file(&task.target).await {
+				if let Err(e) = long::remove_file(&task.target).await {
 					if e.kind() != NotFound && maybe_exists(&task.target).await {
 						self.fail(task.id, format!("Delete task failed: {:?}, {e}", task))?;
 						Err(e)?
 					}
 				}
-				self.prog.send(TaskProg::Adv(task.id, 1, task.length))?
+				self.prog.send(TaskProg::Adv(task.id, 1, task.length))?;
+				task.removed.done(task.target, Self::tell_removed);
 			}
-			FileOp::Trash(task) => {
-				tokio::task::spawn_blocking(move || {
+			FileOp::Trash(mut task) => {
+				let target = task.target.clone();
+				let mut trashing = tokio::task::spawn_blocking(move || {
 					#[cfg(target_os = "macos")]
 					{
 						use trash::{TrashContext, macos::{DeleteMethod, TrashContextExtMacos}};
 						let mut ctx = TrashContext::default();
 						ctx.set_delete_method(DeleteMethod::NsFileManager);
-						ctx.delete(&task.target)?;
+						ctx.delete(&target)?;
 					}
 					#[cfg(all(not(target_os = "macos"), not(target_os = "android")))]
 					{
-						trash::delete(&task.target)?;
+						trash::delete(&target)?;
 					}
 					Ok::<_, anyhow::Error>(())
-				})
-				.await??;
-				self.prog.send(TaskProg::Adv(task.id, 1, task.length))?;
+				});
+
+				// A directory is kept as a whole in the trash, so it can be restored as such,
+				// and its progress is told as its entries leave it, if the OS moves them one
+				// by one rather than at once
+				let mut interval = tokio::time::interval(Duration::from_millis(300));
+				loop {
+					select! {
+						r = &mut trashing => break r??,
+						_ = interval.tick() => self.trash_left(&mut task).await?,
+					}
+				}
+
+				self.prog.send(TaskProg::Adv(task.id, task.count, task.length))?;
+				for (path, ..) in task.entries {
+					task.removed.done(Url::from(path), Self::tell_removed);
+				}
+				task.removed.done(task.target, Self::tell_removed);
 			}
 		}
 		Ok(())
 	}
 
 	pub async fn paste(&self, mut task: FileOpPaste) -> Result<()> {
-		if task.cut && ok_or_not_found(fs::rename(&task.from, &task.to).await).is_ok() {
+		// Another task of the same batch has just run into it, so don't even try
+		if let Some(u) = Self::unwritable(&task.batch, &task.to) {
+			return self.abort(task.id, u);
+		}
+		// Those protected under it are left behind, so it's walked instead
+		if task.cut
+			&& !Self::contains_protected(&task.from).await
+			&& ok_or_not_found(long::rename(&task.from, &task.to).await).is_ok()
+		{
 			return self.succ(task.id);
 		}
 
@@ -188,26 +238,41 @@ impl File {
 		let skip = task.from.components().count();
 		let mut dirs = VecDeque::from([task.from.clone()]);
 
+		let (guard_from, guard_to) =
+			(task.cut && Self::unprotected(&task.from), Self::unprotected(root));
 		while let Some(src) = dirs.pop_front() {
 			let dest = root.join(src.components().skip(skip).collect::<PathBuf>());
-			continue_unless_ok!(match fs::create_dir(&dest).await {
-				Err(e) if e.kind() != AlreadyExists => Err(e),
+			continue_unless_ok!(match long::create_dir(&dest).await {
+				Err(e) if e.kind() != AlreadyExists => {
+					if self.abort_unwritable(task.id, &task.batch, &e, &dest).await? {
+						return Ok(());
+					}
+					Err(e)
+				}
 				_ => Ok(()),
 			});
 
-			let mut it = continue_unless_ok!(fs::read_dir(&src).await);
+			let mut it = continue_unless_ok!(long::read_dir(&src).await);
 			while let Ok(Some(entry)) = it.next_entry().await {
-				let from = Url::from(entry.path());
-				let cha = continue_unless_ok!(Self::cha_from(entry, &from, task.follow).await);
+				let from = Url::from(src.as_path().join(entry.file_name()));
+				if guard_from && !Self::unprotected(&from) {
+					self.skip_protected(task.id, &from)?;
+					continue;
+				}
 
+				let cha = continue_unless_ok!(Self::cha_from(entry, &from, task.follow).await);
 				if cha.is_dir() {
 					dirs.push_back(from);
 					continue;
 				}
 
 				let to = dest.join(from.file_name().unwrap());
-				self.prog.send(TaskProg::New(task.id, cha.len))?;
+				if guard_to && !Self::unprotected(&to) && maybe_exists(&to).await {
+					self.skip_protected(task.id, &to)?;
+					continue;
+				}
 
+				self.prog.send(TaskProg::New(task.id, cha.len))?;
 				if cha.is_orphan() || (cha.is_link() && !task.follow) {
 					self.queue(FileOp::Link(task.spawn(from, to, cha).into()), NORMAL).await?;
 				} else {
@@ -234,58 +299,24 @@ impl File {
 			task.cha = Some(Self::cha(&task.from, task.follow).await?);
 		}
 
+		// Directories can't be hardlinked, so fail before anything's created
 		let cha = task.cha.unwrap();
-		if !cha.is_dir() {
-			let id = task.id;
-			self.prog.send(TaskProg::New(id, cha.len))?;
-			self.queue(FileOp::Hardlink(task), NORMAL).await?;
-			return self.succ(id);
+		if cha.is_dir() {
+			self.prog.send(TaskProg::New(task.id, 0))?;
+			return self.fail(
+				task.id,
+				format!("Cannot hardlink the directory `{}`, only files can be", task.from.display()),
+			);
 		}
 
-		macro_rules! continue_unless_ok {
-			($result:expr) => {
-				match $result {
-					Ok(v) => v,
-					Err(e) => {
-						self.prog.send(TaskProg::New(task.id, 0))?;
-						self.fail(task.id, format!("An error occurred while hardlinking: {e}"))?;
-						continue;
-					}
-				}
-			};
-		}
-
-		let root = &task.to;
-		let skip = task.from.components().count();
-		let mut dirs = VecDeque::from([task.from.clone()]);
-
-		while let Some(src) = dirs.pop_front() {
-			let dest = root.join(src.components().skip(skip).collect::<PathBuf>());
-			continue_unless_ok!(match fs::create_dir(&dest).await {
-				Err(e) if e.kind() != AlreadyExists => Err(e),
-				_ => Ok(()),
-			});
-
-			let mut it = continue_unless_ok!(fs::read_dir(&src).await);
-			while let Ok(Some(entry)) = it.next_entry().await {
-				let from = Url::from(entry.path());
-				let cha = continue_unless_ok!(Self::cha_from(entry, &from, task.follow).await);
-
-				if cha.is_dir() {
-					dirs.push_back(from);
-					continue;
-				}
-
-				let to = dest.join(from.file_name().unwrap());
-				self.prog.send(TaskProg::New(task.id, cha.len))?;
-				self.queue(FileOp::Hardlink(task.spawn(from, to, cha)), NORMAL).await?;
-			}
-		}
-		self.succ(task.id)
+		let id = task.id;
+		self.prog.send(TaskProg::New(id, cha.len))?;
+		self.queue(FileOp::Hardlink(task), NORMAL).await?;
+		self.succ(id)
 	}
 
 	pub async fn delete(&self, mut task: FileOpDelete) -> Result<()> {
-		let meta = fs::symlink_metadata(&task.target).await?;
+		let meta = long::symlink_metadata(&task.target).await?;
 		if !meta.is_dir() {
 			let id = task.id;
 			task.length = meta.len();
@@ -294,19 +325,29 @@ impl File {
 			return self.succ(id);
 		}
 
-		let mut dirs = VecDeque::from([task.target]);
+		let guard = Self::unprotected(&task.target);
+		let mut dirs = VecDeque::from([task.target.clone()]);
 		while let Some(target) = dirs.pop_front() {
-			let Ok(mut it) = fs::read_dir(target).await else { continue };
+			let Ok(mut it) = long::read_dir(&target).await else { continue };
 
 			while let Ok(Some(entry)) = it.next_entry().await {
-				let Ok(meta) = entry.metadata().await else { continue };
+				if task.canceled.load(Ordering::Relaxed) {
+					return Ok(());
+				}
 
+				let url = Url::from(target.as_path().join(entry.file_name()));
+				if guard && !Self::unprotected(&url) {
+					self.skip_protected(task.id, &url)?;
+					continue;
+				}
+
+				let Ok(meta) = entry.metadata().await else { continue };
 				if meta.is_dir() {
-					dirs.push_front(Url::from(entry.path()));
+					dirs.push_front(url);
 					continue;
 				}
 
-				task.target = Url::from(entry.path());
+				task.target = url;
 				task.length = meta.len();
 				self.prog.send(TaskProg::New(task.id, meta.len()))?;
 				self.queue(FileOp::Delete(task.clone()), NORMAL).await?;
@@ -315,18 +356,85 @@ impl File {
 		self.succ(task.id)
 	}
 
+	// A directory goes to the trash as a whole, but its files are counted one by
+	// one first, so the total grows while walking a huge tree, and grouped by the
+	// entry of it they're under, to tell the progress by
 	pub async fn trash(&self, mut task: FileOpTrash) -> Result<()> {
 		let id = task.id;
-		task.length = calculate_size(&task.target).await;
+		let meta = long::symlink_metadata(&task.target).await?;
+		if !meta.is_dir() {
+			(task.length, task.count) = (meta.len(), 1);
+			self.prog.send(TaskProg::New(id, meta.len()))?;
+			self.queue(FileOp::Trash(task), LOW).await?;
+			return self.succ(id);
+		}
+
+		let mut dirs = VecDeque::from([(task.target.to_path_buf(), None)]);
+		while let Some((dir, top)) = dirs.pop_front() {
+			let Ok(mut it) = long::read_dir(&dir).await else { continue };
+
+			while let Ok(Some(entry)) = it.next_entry().await {
+				if task.canceled.load(Ordering::Relaxed) {
+					return Ok(());
+				}
+
+				let Ok(meta) = entry.metadata().await else { continue };
+				let top = top.unwrap_or_else(|| {
+					task.entries.push((entry.path(), 0, 0));
+					task.entries.len() - 1
+				});
+				if meta.is_dir() {
+					dirs.push_back((entry.path(), Some(top)));
+					continue;
+				}
+
+				task.entries[top].1 += 1;
+				task.entries[top].2 += meta.len();
+				task.length += meta.len();
+				task.count += 1;
+				self.prog.send(TaskProg::New(id, meta.len()))?;
+			}
+		}
+
+		// The directory itself, so that an empty one is waited for as well
+		task.count += 1;
+		self.prog.send(TaskProg::New(id, 0))?;
 
-		self.prog.send(TaskProg::New(id, task.length))?;
 		self.queue(FileOp::Trash(task), LOW).await?;
 		self.succ(id)
 	}
 
+	// Those entries of the directory being trashed that have left it are done
+	async fn trash_left(&self, task: &mut FileOpTrash) -> Result<()> {
+		let mut i = 0;
+		while i < task.entries.len() {
+			if maybe_exists(&task.entries[i].0).await {
+				i += 1;
+				continue;
+			}
+
+			let (path, count, length) = task.entries.swap_remove(i);
+			(task.count, task.length) = (task.count - count, task.length - length);
+			self.prog.send(TaskProg::Adv(task.id, count, length))?;
+			task.removed.done(Url::from(path), Self::tell_removed);
+		}
+		Ok(())
+	}
+
+	/// Tells the views about the files removed, grouped by their parents.
+	pub(crate) fn tell_removed(urls: Vec<Url>) {
+		let mut parents: HashMap<_, HashSet<_>> = HashMap::new();
+		for (parent, urn) in urls.iter().filter_map(|u| u.pair()) {
+			parents.entry(parent).or_default().insert(urn);
+		}
+		for (parent, urns) in parents {
+			FilesOp::Deleting(parent, urns).emit();
+		}
+	}
+
 	#[inline]
 	async fn cha(path: &Path, follow: bool) -> io::Result<Cha> {
-		let meta = fs::symlink_metadata(path).await?;
+		let meta = long::symlink_metadata(path).await?;
 		Ok(if follow { Cha::new(path, meta).await } else { Cha::new_nofollow(path, meta) })
 	}
 
@@ -338,6 +446,27 @@ impl File {
 			Cha::new_nofollow(path, entry.metadata().await?)
 		})
 	}
+
+	// The protected paths are confirmed before a task starts, along with anything
+	// under them, so only the ones found while walking an unprotected directory
+	// are skipped
+	#[inline]
+	fn unprotected(path: &Path) -> bool { MANAGER.protected.of(path).is_none() }
+
+	async fn contains_protected(dir: &Path) -> bool {
+		let dir = dir.to_owned();
+		tokio::task::spawn_blocking(move || !MANAGER.protected.under(&dir).is_empty())
+			.await
+			.unwrap_or(true)
+	}
+
+	// EXDEV on Unix, and ERROR_NOT_SAME_DEVICE on Windows
+	fn cross_device(e: &io::Error) -> bool {
+		#[cfg(unix)]
+		return e.raw_os_error() == Some(libc::EXDEV);
+		#[cfg(windows)]
+		return e.raw_os_error() == Some(17);
+	}
 }
 
 impl File {
@@ -349,6 +478,49 @@ impl File {
 		Ok(self.prog.send(TaskProg::Fail(id, reason))?)
 	}
 
+	fn skip_protected(&self, id: usize, url: &Url) -> Result<()> {
+		self.prog.send(TaskProg::New(id, 0))?;
+		self.fail(id, format!("Skipped the protected `{}`", url.display()))
+	}
+
+	// Aborts the task if `e` of writing `path` tells that nothing can be written
+	// next to it either, unless it's configured to keep trying
+	async fn abort_unwritable(
+		&self,
+		id: usize,
+		batch: &FileBatch,
+		e: &io::Error,
+		path: &Path,
+	) -> Result<bool> {
+		if !TASKS.abort_unwritable {
+			return Ok(false);
+		}
+
+		let dir = path.parent().unwrap_or(path);
+		let Some(u) = Unwritable::of(e, dir).await else { return Ok(false) };
+
+		if batch.add(u.clone()) {
+			AppProxy::notify_warn("Paste", format!("{u}, so the rest of the batch is skipped"));
+		}
+		self.abort(id, u)?;
+		Ok(true)
+	}
+
+	// The destination of a write of the same batch that's just failed for good,
+	// which `path` goes into
+	fn unwritable(batch: &FileBatch, path: &Path) -> Option<Unwritable> {
+		if !TASKS.abort_unwritable {
+			return None;
+		}
+		batch.covering(path.parent().unwrap_or(path))
+	}
+
+	#[inline]
+	fn abort(&self, id: usize, u: Unwritable) -> Result<()> {
+		self.prog.send(TaskProg::New(id, 0))?;
+		Ok(self.prog.send(TaskProg::Abort(id, format!("Aborted: {u}")))?)
+	}
+
 	#[inline]
 	fn log(&self, id: usize, line: String) -> Result<()> {
 		Ok(self.prog.send(TaskProg::Log(id, line))?)
@@ -359,3 +531,69 @@ impl File {
 		self.macro_.send(op.into(), priority).await.map_err(|_| anyhow!("Failed to send task"))
 	}
 }
+
+#[cfg(all(test, windows))]
+mod tests {
+	use std::sync::Once;
+
+	use yazi_shared::fs::{Preserve, long_path};
+
+	use super::*;
+
+	#[tokio::test]
+	async fn test_paste_long_path() {
+		static INIT: Once = Once::new();
+		INIT.call_once(|| {
+			yazi_shared::init();
+			yazi_config::init(&[], None).unwrap();
+		});
+
+		let root = std::env::temp_dir().join(format!("yazi-paste-long-{}", std::process::id()));
+		long::remove_dir_all(&root).await.ok();
+
+		// A tree nested beyond `MAX_PATH`, with a file at each level
+		let from = root.join("from");
+		let mut dir = from.clone();
+		for i in 0..20 {
+			dir.push(format!("{i:02}-{}", "x".repeat(12)));
+			long::create_dir_all(&dir).await.unwrap();
+			long::write(dir.join("file"), i.to_string()).await.unwrap();
+		}
+		assert!(dir.as_os_str().len() > 260);
+
+		let (macro_tx, macro_rx) = async_priority_channel::unbounded();
+		let (prog_tx, mut prog_rx) = mpsc::unbounded_channel();
+		let file = File::new(macro_tx, prog_tx);
+
+		let to = root.join("to");
+		file
+			.paste(FileOpPaste {
+				id:       0,
+				from:     Url::from(from),
+				to:       Url::from(to.clone()),
+				cha:      None,
+				cut:      false,
+				follow:   false,
+				retry:    0,
+				preserve: Preserve::Attrs,
+				batch:    Default::default(),
+			})
+			.await
+			.unwrap();
+		while let Ok((TaskOp::File(op), _)) = macro_rx.try_recv() {
+			file.work(*op).await.unwrap();
+		}
+
+		while let Ok(prog) = prog_rx.try_recv() {
+			assert!(!matches!(prog, TaskProg::Fail(..) | TaskProg::Abort(..)), "{prog:?}");
+		}
+		let mut dir = to;
+		for i in 0..20 {
+			dir.push(format!("{i:02}-{}", "x".repeat(12)));
+			let content = tokio::fs::read_to_string(long_path(&dir.join("file"))).await.unwrap();
+			assert_eq!(content, i.to_string());
+		}
+
+		long::remove_dir_all(&root).await.unwrap();
+	}
+}
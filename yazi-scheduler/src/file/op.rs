@@ -1,4 +1,8 @@
-use yazi_shared::fs::{Cha, Url};
+use std::{path::{Path, PathBuf}, sync::{Arc, atomic::AtomicBool}};
+
+use parking_lot::Mutex;
+use yazi_fs::Unwritable;
+use yazi_shared::{Throttle, fs::{Cha, Preserve, Url}};
 
 #[derive(Debug)]
 pub enum FileOp {
@@ -24,13 +28,15 @@ impl FileOp {
 // --- Paste
 #[derive(Clone, Debug)]
 pub struct FileOpPaste {
-	pub id:     usize,
-	pub from:   Url,
-	pub to:     Url,
-	pub cha:    Option<Cha>,
-	pub cut:    bool,
-	pub follow: bool,
-	pub retry:  u8,
+	pub id:       usize,
+	pub from:     Url,
+	pub to:       Url,
+	pub cha:      Option<Cha>,
+	pub cut:      bool,
+	pub follow:   bool,
+	pub retry:    u8,
+	pub preserve: Preserve,
+	pub batch:    FileBatch,
 }
 
 impl FileOpPaste {
@@ -43,10 +49,35 @@ impl FileOpPaste {
 			cut: self.cut,
 			follow: self.follow,
 			retry: self.retry,
+			preserve: self.preserve,
+			batch: self.batch.clone(),
 		}
 	}
 }
 
+/// The tasks queued together, e.g. by one paste, which share the destinations
+/// that writes have failed for good, so that the rest of them are aborted
+/// before trying, and it's notified only once.
+#[derive(Clone, Debug, Default)]
+pub struct FileBatch(Arc<Mutex<Vec<Unwritable>>>);
+
+impl FileBatch {
+	/// Records `u`, returns whether it's new to the batch.
+	pub(super) fn add(&self, u: Unwritable) -> bool {
+		let mut recorded = self.0.lock();
+		if recorded.contains(&u) {
+			return false;
+		}
+		recorded.push(u);
+		true
+	}
+
+	/// The recorded one that writing into `dir` runs into, if any.
+	pub(super) fn covering(&self, dir: &Path) -> Option<Unwritable> {
+		self.0.lock().iter().find(|u| u.covers(dir)).cloned()
+	}
+}
+
 // --- Link
 #[derive(Clone, Debug)]
 pub struct FileOpLink {
@@ -83,24 +114,26 @@ pub struct FileOpHardlink {
 	pub follow: bool,
 }
 
-impl FileOpHardlink {
-	pub(super) fn spawn(&self, from: Url, to: Url, cha: Cha) -> Self {
-		Self { id: self.id, from, to, cha: Some(cha), follow: self.follow }
-	}
-}
-
 // --- Delete
 #[derive(Clone, Debug)]
 pub struct FileOpDelete {
-	pub id:     usize,
-	pub target: Url,
-	pub length: u64,
+	pub id:       usize,
+	pub target:   Url,
+	pub length:   u64,
+	pub canceled: Arc<AtomicBool>,
+	pub removed:  Arc<Throttle<Url>>,
 }
 
 // --- Trash
 #[derive(Clone, Debug)]
 pub struct FileOpTrash {
-	pub id:     usize,
-	pub target: Url,
-	pub length: u64,
+	pub id:       usize,
+	pub target:   Url,
+	pub length:   u64,
+	pub count:    u32,
+	pub canceled: Arc<AtomicBool>,
+	pub removed:  Arc<Throttle<Url>>,
+	/// The entries of the directory, with the number and size of the files under
+	/// each of them, which are done once it's left.
+	pub entries:  Vec<(PathBuf, u32, u64)>,
 }
@@ -0,0 +1,129 @@
+use std::path::Path;
+
+/// Whether `path` can go to the trash, rather than having to be permanently
+/// deleted, as on a filesystem without one, like a network mount.
+///
+/// On freedesktop systems, that's when it's on the same device as the home
+/// trash, or where that would be created, or when the top directory of its
+/// mount has a usable `.Trash/$uid` or `.Trash-$uid` in it, or room for the
+/// latter.
+///
+/// This is a blocking call, as it looks up the directories above `path`.
+#[cfg(all(unix, not(target_os = "macos"), not(target_os = "android")))]
+pub fn trashable(path: &Path) -> bool {
+	let home = dirs::data_dir().unwrap_or_default().join("Trash");
+	trashable_with(path, &home, unsafe { libc::getuid() })
+}
+
+#[cfg(target_os = "android")]
+pub fn trashable(_: &Path) -> bool { false }
+
+#[cfg(any(target_os = "macos", windows))]
+pub fn trashable(_: &Path) -> bool { true }
+
+#[cfg(all(unix, not(target_os = "macos"), not(target_os = "android")))]
+fn trashable_with(path: &Path, home: &Path, uid: u32) -> bool {
+	use std::os::unix::fs::MetadataExt;
+
+	let dev = |p: &Path| std::fs::metadata(p).ok().map(|m| m.dev());
+	let Some(dir) = path.parent().and_then(|p| std::fs::canonicalize(p).ok()) else {
+		return false;
+	};
+	let Some(target) = dev(&dir) else {
+		return false;
+	};
+
+	// The nearest existing directory of the home trash, for it's created on demand
+	if home.ancestors().find_map(dev) == Some(target) {
+		return true;
+	}
+
+	let top = dir.ancestors().take_while(|&p| dev(p) == Some(target)).last().unwrap_or(&dir);
+	topdir_trashable(top, uid)
+}
+
+/// Whether the top directory `top` of a mount has a trash for `uid`, or can
+/// have one created, as the freedesktop spec describes.
+#[cfg(all(unix, not(target_os = "macos"), not(target_os = "android")))]
+fn topdir_trashable(top: &Path, uid: u32) -> bool {
+	use std::{ffi::CString, os::unix::{ffi::OsStrExt, fs::PermissionsExt}};
+
+	let writable = |p: &Path| {
+		CString::new(p.as_os_str().as_bytes())
+			.is_ok_and(|c| unsafe { libc::access(c.as_ptr(), libc::W_OK) } == 0)
+	};
+
+	// `.Trash` set up by the admin, which must be a sticky directory, not a link
+	let admin = top.join(".Trash");
+	if std::fs::symlink_metadata(&admin)
+		.is_ok_and(|m| m.is_dir() && m.permissions().mode() & 0o1000 != 0)
+	{
+		let own = admin.join(uid.to_string());
+		match std::fs::symlink_metadata(&own) {
+			Ok(m) if m.is_dir() && writable(&own) => return true,
+			Ok(_) => {}
+			Err(_) if writable(&admin) => return true,
+			Err(_) => {}
+		}
+	}
+
+	let own = top.join(format!(".Trash-{uid}"));
+	match std::fs::symlink_metadata(&own) {
+		Ok(m) => m.is_dir() && writable(&own),
+		Err(_) => writable(top),
+	}
+}
+
+#[cfg(all(test, unix, not(target_os = "macos"), not(target_os = "android")))]
+mod tests {
+	use std::{fs, os::unix::fs::PermissionsExt, path::PathBuf};
+
+	use super::*;
+
+	// A fresh directory standing in for the top directory of a mount
+	fn mount(name: &str) -> PathBuf {
+		let dir = std::env::temp_dir().join(format!("yazi-trash-{name}-{}", std::process::id()));
+		fs::remove_dir_all(&dir).ok();
+		fs::create_dir_all(&dir).unwrap();
+		dir
+	}
+
+	#[test]
+	fn test_topdir() {
+		let top = mount("topdir");
+		assert!(topdir_trashable(&top, 1000));
+
+		// Not a directory
+		fs::write(top.join(".Trash-1000"), "").unwrap();
+		assert!(!topdir_trashable(&top, 1000));
+
+		// An admin trash that isn't sticky is ignored
+		fs::create_dir(top.join(".Trash")).unwrap();
+		fs::create_dir(top.join(".Trash/1000")).unwrap();
+		assert!(!topdir_trashable(&top, 1000));
+
+		let mut perm = fs::metadata(top.join(".Trash")).unwrap().permissions();
+		perm.set_mode(0o1777);
+		fs::set_permissions(top.join(".Trash"), perm).unwrap();
+		assert!(topdir_trashable(&top, 1000));
+
+		// So is a symlink to one
+		fs::rename(top.join(".Trash"), top.join("real")).unwrap();
+		std::os::unix::fs::symlink(top.join("real"), top.join(".Trash")).unwrap();
+		assert!(!topdir_trashable(&top, 1000));
+
+		fs::remove_dir_all(&top).unwrap();
+	}
+
+	#[test]
+	fn test_home() {
+		let top = mount("home");
+		fs::write(top.join("file"), "").unwrap();
+
+		// The home trash doesn't exist yet, but would be on the same device
+		assert!(trashable_with(&top.join("file"), &top.join("data/Trash"), 1000));
+		assert!(!trashable_with(&top.join("missing/file"), &top.join("data/Trash"), 1000));
+
+		fs::remove_dir_all(&top).unwrap();
+	}
+}
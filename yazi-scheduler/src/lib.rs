@@ -1,8 +1,8 @@
 #![allow(clippy::option_map_unit_fn, clippy::unit_arg)]
 
-yazi_macro::mod_pub!(file plugin prework process);
+yazi_macro::mod_pub!(archive checksum file plugin prework process);
 
-yazi_macro::mod_flat!(ongoing op scheduler task);
+yazi_macro::mod_flat!(desktop ongoing op scheduler task);
 
 const LOW: u8 = yazi_config::Priority::Low as u8;
 const NORMAL: u8 = yazi_config::Priority::Normal as u8;
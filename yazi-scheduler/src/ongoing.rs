@@ -17,7 +17,10 @@ pub struct Ongoing {
 impl Ongoing {
 	pub fn add(&mut self, kind: TaskKind, name: String) -> usize {
 		self.incr += 1;
-		self.all.insert(self.incr, Task::new(self.incr, kind, name));
+		let mut task = Task::new(self.incr, kind, name);
+		task.publish("start");
+
+		self.all.insert(self.incr, task);
 		self.incr
 	}
 
@@ -73,7 +76,7 @@ impl Ongoing {
 				TaskStage::Hooked => {}
 			}
 
-			self.all.remove(&id);
+			self.all.remove(&id).map(|mut t| t.publish("finish"));
 		}
 		None
 	}
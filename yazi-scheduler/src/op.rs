@@ -1,7 +1,8 @@
-use crate::{file::FileOp, plugin::PluginOp, prework::PreworkOp};
+use crate::{archive::ArchiveOp, file::FileOp, plugin::PluginOp, prework::PreworkOp};
 
 #[derive(Debug)]
 pub enum TaskOp {
+	Archive(Box<ArchiveOp>),
 	File(Box<FileOp>),
 	Plugin(Box<PluginOp>),
 	Prework(Box<PreworkOp>),
@@ -10,6 +11,7 @@ pub enum TaskOp {
 impl TaskOp {
 	pub fn id(&self) -> usize {
 		match self {
+			TaskOp::Archive(op) => op.id(),
 			TaskOp::File(op) => op.id(),
 			TaskOp::Plugin(op) => op.id(),
 			TaskOp::Prework(op) => op.id(),
@@ -17,6 +19,10 @@ impl TaskOp {
 	}
 }
 
+impl From<ArchiveOp> for TaskOp {
+	fn from(op: ArchiveOp) -> Self { Self::Archive(Box::new(op)) }
+}
+
 impl From<FileOp> for TaskOp {
 	fn from(op: FileOp) -> Self { Self::File(Box::new(op)) }
 }
@@ -1,6 +1,7 @@
 use anyhow::{Result, anyhow};
 use tokio::sync::mpsc;
 use yazi_plugin::isolate;
+use yazi_proxy::options::PluginJobProg;
 
 use super::{PluginOp, PluginOpEntry};
 use crate::{HIGH, TaskOp, TaskProg};
@@ -39,6 +40,34 @@ impl Plugin {
 		self.succ(task.id)
 	}
 
+	pub async fn job(
+		&self,
+		id: usize,
+		total: u64,
+		mut rx: mpsc::UnboundedReceiver<PluginJobProg>,
+	) -> Result<()> {
+		self.prog.send(TaskProg::New(id, total))?;
+
+		while let Some(prog) = rx.recv().await {
+			match prog {
+				PluginJobProg::Adv(n) => self.prog.send(TaskProg::Adv(id, 0, n))?,
+				PluginJobProg::Log(line) => self.prog.send(TaskProg::Log(id, line))?,
+				PluginJobProg::Succ => {
+					self.prog.send(TaskProg::Adv(id, 1, 0))?;
+					return self.succ(id);
+				}
+				PluginJobProg::Fail(reason) => {
+					self.fail(id, reason)?;
+					return self.succ(id);
+				}
+			}
+		}
+
+		// The plugin has errored or exited without finishing the job
+		self.fail(id, "Plugin job was dropped before it finished".to_owned())?;
+		self.succ(id)
+	}
+
 	pub fn macro_(&self, task: PluginOpEntry) -> Result<()> {
 		let id = task.id;
 
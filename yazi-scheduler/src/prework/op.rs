@@ -1,5 +1,6 @@
-use std::sync::Arc;
+use std::{sync::{Arc, atomic::AtomicBool}, time::SystemTime};
 
+use tokio::sync::OwnedSemaphorePermit;
 use yazi_config::plugin::{FetcherProps, PreloaderProps};
 use yazi_shared::{Throttle, fs::Url};
 
@@ -20,11 +21,13 @@ impl PreworkOp {
 	}
 }
 
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct PreworkOpFetch {
 	pub id:      usize,
 	pub plugin:  FetcherProps,
 	pub targets: Vec<yazi_shared::fs::File>,
+	/// Held until the fetcher has run, to cap how many calls of it run at once.
+	pub permit:  OwnedSemaphorePermit,
 }
 
 #[derive(Clone, Debug)]
@@ -38,5 +41,7 @@ pub struct PreworkOpLoad {
 pub struct PreworkOpSize {
 	pub id:       usize,
 	pub target:   Url,
-	pub throttle: Arc<Throttle<(Url, u64)>>,
+	pub mtime:    Option<SystemTime>,
+	pub canceled: Arc<AtomicBool>,
+	pub throttle: Arc<Throttle<(Url, Option<u64>)>>,
 }
@@ -1,12 +1,13 @@
-use std::collections::{HashMap, HashSet};
+use std::{collections::{HashMap, HashSet}, path::PathBuf, sync::{Arc, atomic::{AtomicBool, AtomicUsize, Ordering}}, time::{Duration, SystemTime}};
 
 use anyhow::{Result, anyhow};
 use parking_lot::{Mutex, RwLock};
-use tokio::sync::mpsc;
+use tokio::sync::{Semaphore, mpsc};
 use tracing::error;
-use yazi_config::Priority;
+use yazi_config::{PLUGIN, Priority, TASKS};
+use yazi_fs::{DirStats, SIZE_LOADING};
 use yazi_plugin::isolate;
-use yazi_shared::fs::{FilesOp, Url, calculate_size};
+use yazi_shared::fs::{FilesOp, Url};
 
 use super::{PreworkOp, PreworkOpFetch, PreworkOpLoad, PreworkOpSize};
 use crate::{HIGH, NORMAL, TaskOp, TaskProg};
@@ -15,8 +16,19 @@ pub struct Prework {
 	macro_: async_priority_channel::Sender<TaskOp, u8>,
 	prog:   mpsc::UnboundedSender<TaskProg>,
 
-	pub loaded:       Mutex<HashMap<Url, u32>>,
-	pub size_loading: RwLock<HashSet<Url>>,
+	pub loaded:   Mutex<HashMap<Url, u32>>,
+	/// The files whose queued preloads are still wanted, or `None` for all.
+	pub retained: RwLock<Option<HashSet<Url>>>,
+	/// The paths of the folders whose files' queued fetches and preloads are
+	/// still wanted, or `None` for all.
+	pub folders:  RwLock<Option<HashSet<PathBuf>>>,
+
+	/// How many fetches and preloads have run, and been dropped before running.
+	pub completed: AtomicUsize,
+	pub canceled:  AtomicUsize,
+
+	fetching: Vec<Arc<Semaphore>>,
+	sizing:   Semaphore,
 }
 
 impl Prework {
@@ -24,12 +36,36 @@ impl Prework {
 		macro_: async_priority_channel::Sender<TaskOp, u8>,
 		prog: mpsc::UnboundedSender<TaskProg>,
 	) -> Self {
-		Self { macro_, prog, loaded: Default::default(), size_loading: Default::default() }
+		Self {
+			macro_,
+			prog,
+			loaded: Default::default(),
+			retained: Default::default(),
+			folders: Default::default(),
+			completed: Default::default(),
+			canceled: Default::default(),
+			fetching: PLUGIN
+				.fetchers
+				.iter()
+				.map(|f| {
+					Arc::new(Semaphore::new(f.max_concurrency.unwrap_or(Semaphore::MAX_PERMITS).max(1)))
+				})
+				.collect(),
+			sizing: Semaphore::new(TASKS.size_workers as usize),
+		}
 	}
 
 	pub async fn work(&self, op: PreworkOp) -> Result<()> {
 		match op {
-			PreworkOp::Fetch(task) => {
+			PreworkOp::Fetch(mut task) => {
+				// Those left behind while queued can be queued again once they're visible
+				let id = task.plugin.id;
+				task.targets.retain(|f| self.wanted(&f.url, id, false));
+				if task.targets.is_empty() {
+					self.canceled.fetch_add(1, Ordering::Relaxed);
+					return Ok(self.prog.send(TaskProg::Adv(task.id, 1, 0))?);
+				}
+
 				let urls: Vec<_> = task.targets.iter().map(|f| f.url_owned()).collect();
 				let result = isolate::fetch(&task.plugin.name, task.targets).await;
 				if let Err(e) = result {
@@ -58,10 +94,16 @@ impl Prework {
 						loaded.get_mut(&url).map(|x| *x &= !(1 << task.plugin.id));
 					}
 				}
+				self.completed.fetch_add(1, Ordering::Relaxed);
 				self.prog.send(TaskProg::Adv(task.id, 1, 0))?;
 			}
 			PreworkOp::Load(task) => {
 				let url = task.target.url_owned();
+				if !self.wanted(&url, task.plugin.id, true) {
+					self.canceled.fetch_add(1, Ordering::Relaxed);
+					return Ok(self.prog.send(TaskProg::Adv(task.id, 1, 0))?);
+				}
+
 				let result = isolate::preload(&task.plugin.name, task.target).await;
 				if let Err(e) = result {
 					self.fail(
@@ -79,24 +121,29 @@ impl Prework {
 					let mut loaded = self.loaded.lock();
 					loaded.get_mut(&url).map(|x| *x &= !(1 << task.plugin.id));
 				}
+				self.completed.fetch_add(1, Ordering::Relaxed);
 				self.prog.send(TaskProg::Adv(task.id, 1, 0))?;
 			}
 			PreworkOp::Size(task) => {
-				let length = calculate_size(&task.target).await;
+				let length = {
+					let _permit = self.sizing.acquire().await?;
+					Self::walk_size(&task.target, task.mtime, &task.canceled).await
+				};
+
 				task.throttle.done((task.target, length), |buf| {
 					{
-						let mut loading = self.size_loading.write();
+						let mut loading = SIZE_LOADING.write();
 						for (path, _) in &buf {
 							loading.remove(path);
 						}
 					}
 
 					let parent = buf[0].0.parent_url().unwrap();
-					FilesOp::Size(
-						parent,
-						HashMap::from_iter(buf.into_iter().map(|(u, s)| (u.urn_owned(), s))),
-					)
-					.emit();
+					let sizes: HashMap<_, _> =
+						buf.into_iter().filter_map(|(u, s)| Some((u.urn_owned(), s?))).collect();
+					if !sizes.is_empty() {
+						FilesOp::Size(parent, sizes).emit();
+					}
 				});
 				self.prog.send(TaskProg::Adv(task.id, 1, 0))?;
 			}
@@ -104,6 +151,11 @@ impl Prework {
 		Ok(())
 	}
 
+	/// The semaphore capping the calls of the fetcher, to be waited on before
+	/// they're queued, rather than in a worker.
+	#[inline]
+	pub fn fetching(&self, fetcher: u8) -> Arc<Semaphore> { self.fetching[fetcher as usize].clone() }
+
 	pub async fn fetch(&self, task: PreworkOpFetch) -> Result<()> {
 		let id = task.id;
 		self.prog.send(TaskProg::New(id, 0))?;
@@ -138,6 +190,45 @@ impl Prework {
 }
 
 impl Prework {
+	// Walks the directory `path` to sum up the size of its files, reporting the
+	// size so far every now and then, or returns `None` if it's canceled.
+	async fn walk_size(path: &Url, mtime: Option<SystemTime>, canceled: &AtomicBool) -> Option<u64> {
+		if let Some(stats) = DirStats::cached(path, mtime) {
+			return Some(stats.len);
+		}
+
+		let report = |stats: &DirStats| {
+			if let Some(parent) = path.parent_url() {
+				FilesOp::Size(parent, HashMap::from_iter([(path.urn_owned(), stats.len)])).emit();
+			}
+		};
+
+		let canceled = || canceled.load(Ordering::Relaxed);
+		DirStats::walk(path, mtime, Duration::from_millis(500), canceled, report).await.map(|s| s.len)
+	}
+
+	// Whether the queued fetch or preload `plugin` of `url` is still wanted, i.e.
+	// it's in one of `folders`, and in `retained` too for a preload. If not, the
+	// mark of it being loaded is cleared, so it can be queued again once it is.
+	//
+	// The folder is told by the base of the path rather than the parent URL,
+	// which is the search the items of a search result are listed in, however
+	// deep they're nested under it
+	fn wanted(&self, url: &Url, plugin: u8, preload: bool) -> bool {
+		let wanted = match &*self.folders.read() {
+			Some(f) => f.contains(url.base()),
+			None => true,
+		} && match &*self.retained.read() {
+			Some(r) if preload => r.contains(url),
+			_ => true,
+		};
+
+		if !wanted {
+			self.loaded.lock().get_mut(url).map(|x| *x &= !(1 << plugin));
+		}
+		wanted
+	}
+
 	#[inline]
 	fn succ(&self, id: usize) -> Result<()> { Ok(self.prog.send(TaskProg::Succ(id))?) }
 
@@ -1,3 +1,3 @@
 #![allow(clippy::module_inception)]
 
-yazi_macro::mod_flat!(op process shell);
+yazi_macro::mod_flat!(op process selection shell system);
@@ -1,4 +1,4 @@
-use std::ffi::OsString;
+use std::{collections::BTreeMap, ffi::OsString, path::PathBuf};
 
 use tokio::sync::mpsc;
 
@@ -9,11 +9,20 @@ pub struct ProcessOpBlock {
 	pub id:   usize,
 	pub cmd:  OsString,
 	pub args: Vec<OsString>,
+	pub cwd:  Option<PathBuf>,
+	pub env:  BTreeMap<String, String>,
 }
 
 impl From<ProcessOpBlock> for ShellOpt {
 	fn from(op: ProcessOpBlock) -> Self {
-		Self { cmd: op.cmd, args: op.args, piped: false, orphan: false }
+		Self {
+			cmd:    op.cmd,
+			args:   op.args,
+			cwd:    op.cwd,
+			env:    op.env,
+			piped:  false,
+			orphan: false,
+		}
 	}
 }
 
@@ -22,24 +31,49 @@ pub struct ProcessOpOrphan {
 	pub id:   usize,
 	pub cmd:  OsString,
 	pub args: Vec<OsString>,
+	pub cwd:  Option<PathBuf>,
+	pub env:  BTreeMap<String, String>,
 }
 
 impl From<ProcessOpOrphan> for ShellOpt {
 	fn from(op: ProcessOpOrphan) -> Self {
-		Self { cmd: op.cmd, args: op.args, piped: false, orphan: true }
+		Self {
+			cmd:    op.cmd,
+			args:   op.args,
+			cwd:    op.cwd,
+			env:    op.env,
+			piped:  false,
+			orphan: true,
+		}
 	}
 }
 
+#[derive(Debug)]
+pub struct ProcessOpSystem {
+	pub id:      usize,
+	pub targets: Vec<OsString>,
+	pub dialog:  bool,
+}
+
 #[derive(Debug)]
 pub struct ProcessOpBg {
 	pub id:     usize,
 	pub cmd:    OsString,
 	pub args:   Vec<OsString>,
+	pub cwd:    Option<PathBuf>,
+	pub env:    BTreeMap<String, String>,
 	pub cancel: mpsc::Receiver<()>,
 }
 
 impl From<ProcessOpBg> for ShellOpt {
 	fn from(op: ProcessOpBg) -> Self {
-		Self { cmd: op.cmd, args: op.args, piped: true, orphan: false }
+		Self {
+			cmd:    op.cmd,
+			args:   op.args,
+			cwd:    op.cwd,
+			env:    op.env,
+			piped:  true,
+			orphan: false,
+		}
 	}
 }
@@ -1,9 +1,12 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use scopeguard::defer;
 use tokio::{io::{AsyncBufReadExt, BufReader}, select, sync::mpsc};
-use yazi_proxy::{AppProxy, HIDER};
+use yazi_proxy::{AppProxy, HIDER, ManagerProxy, options::{NotifyAction, NotifyLevel, NotifyOpt}};
+use yazi_shared::{Layer, event::Cmd};
 
-use super::{ProcessOpBg, ProcessOpBlock, ProcessOpOrphan, ShellOpt};
+use super::{ProcessOpBg, ProcessOpBlock, ProcessOpOrphan, ProcessOpSystem, Selection, ShellOpt};
 use crate::TaskProg;
 
 pub struct Process {
@@ -19,13 +22,17 @@ impl Process {
 		AppProxy::stop().await;
 
 		let (id, cmd) = (task.id, task.cmd.clone());
-		let result = super::shell(task.into());
+		let mut opt = task.into();
+		let _selection = Selection::export(&mut opt);
+
+		let result = super::shell(opt);
 		if let Err(e) = result {
 			AppProxy::notify_warn(&cmd.to_string_lossy(), format!("Failed to spawn process: {e}"));
 			return self.succ(id);
 		}
 
 		let status = result.unwrap().wait().await?;
+		ManagerProxy::reload();
 		if !status.success() {
 			let content = match status.code() {
 				Some(130) => return self.succ(id), // Ctrl-C pressed by user
@@ -40,8 +47,18 @@ impl Process {
 
 	pub async fn orphan(&self, task: ProcessOpOrphan) -> Result<()> {
 		let id = task.id;
-		match super::shell(task.into()) {
-			Ok(_) => self.succ(id)?,
+		let mut opt = task.into();
+		let selection = Selection::export(&mut opt);
+
+		match super::shell(opt) {
+			Ok(mut child) => {
+				// Keep the selection until the orphan exits, without waiting for it
+				tokio::spawn(async move {
+					child.wait().await.ok();
+					drop(selection);
+				});
+				self.succ(id)?
+			}
 			Err(e) => {
 				self.prog.send(TaskProg::New(id, 0))?;
 				self.fail(id, format!("Failed to spawn process: {e}"))?;
@@ -51,10 +68,35 @@ impl Process {
 		Ok(())
 	}
 
+	// The "Open with" dialog is modal, and stays open for as long as the user
+	// likes, so the targets are opened aside rather than holding the worker
+	pub async fn system(&self, task: ProcessOpSystem) -> Result<()> {
+		let prog = self.prog.clone();
+		tokio::task::spawn_blocking(move || {
+			for target in task.targets {
+				if let Err(e) = super::system_open(&target, task.dialog) {
+					AppProxy::notify_warn("Open", format!("Failed to open {:?}: {e}", target));
+				}
+			}
+			prog.send(TaskProg::Succ(task.id)).ok();
+		});
+		Ok(())
+	}
+
 	pub async fn bg(&self, task: ProcessOpBg) -> Result<()> {
 		self.prog.send(TaskProg::New(task.id, 0))?;
-		let mut child =
-			super::shell(ShellOpt { cmd: task.cmd, args: task.args, piped: true, ..Default::default() })?;
+		let cmd = task.cmd.clone();
+		let mut opt = ShellOpt {
+			cmd: task.cmd,
+			args: task.args,
+			cwd: task.cwd,
+			env: task.env,
+			piped: true,
+			..Default::default()
+		};
+		let _selection = Selection::export(&mut opt);
+
+		let mut child = super::shell(opt)?;
 
 		let mut stdout = BufReader::new(child.stdout.take().unwrap()).lines();
 		let mut stderr = BufReader::new(child.stderr.take().unwrap()).lines();
@@ -64,6 +106,7 @@ impl Process {
 				_ = cancel.recv() => {
 					child.start_kill().ok();
 					cancel.close();
+					child.wait().await.ok();
 					break;
 				}
 				Ok(Some(line)) = stdout.next_line() => {
@@ -78,6 +121,13 @@ impl Process {
 						None => "Process terminated by signal".to_string(),
 					})?;
 					if !status.success() {
+						AppProxy::notify(NotifyOpt {
+							title:   "Background process failed".to_owned(),
+							content: format!("`{}` exited unsuccessfully", cmd.to_string_lossy()),
+							level:   NotifyLevel::Warn,
+							timeout: Duration::from_secs(5),
+							actions: vec![NotifyAction::cmds("Show tasks", vec![Cmd::new("tasks_show")], Layer::Manager)],
+						});
 						return self.fail(task.id, "Process failed".to_string());
 					}
 					break;
@@ -104,3 +154,31 @@ impl Process {
 		Ok(self.prog.send(TaskProg::Log(id, line))?)
 	}
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+	use std::env;
+
+	use super::*;
+
+	#[tokio::test]
+	async fn test_origin_cwd() {
+		// The CWD Yazi has since switched to, by refreshing for another tab
+		let dir = env::temp_dir().canonicalize().unwrap();
+		assert_ne!(env::current_dir().unwrap(), dir);
+
+		let task = ProcessOpBlock {
+			id:   0,
+			cmd:  r#"pwd -P; printf '%s' "$YAZI_CWD""#.into(),
+			args: vec![],
+			cwd:  Some(dir.clone()),
+			env:  Default::default(),
+		};
+		let mut opt = ShellOpt { piped: true, ..task.into() };
+		let _selection = Selection::export(&mut opt);
+
+		let output = super::super::shell(opt).unwrap().wait_with_output().await.unwrap();
+		let dir = dir.to_str().unwrap();
+		assert_eq!(String::from_utf8(output.stdout).unwrap(), format!("{dir}\n{dir}"));
+	}
+}
@@ -0,0 +1,53 @@
+use std::{fs, path::PathBuf, sync::atomic::{AtomicUsize, Ordering}};
+
+use yazi_config::PREVIEW;
+
+use super::ShellOpt;
+
+static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+/// The selection of a spawned process written to a temporary file, so that it
+/// isn't bound by the length limit of arguments, which is removed once dropped.
+pub(super) struct Selection(PathBuf);
+
+impl Selection {
+	/// Exports `YAZI_HOVERED`, `YAZI_CWD`, and the selection as
+	/// `YAZI_SELECTION_FILE`, separated by newlines, or NULs if
+	/// `YAZI_SELECTION_SEP` is "nul", to the process of `opt`.
+	///
+	/// `YAZI_CWD` is the `cwd` of `opt` the process runs in, if any. The file is
+	/// written for any process, as a script it runs may read it too, and the
+	/// returned `Self` is to be kept until the process exits.
+	pub(super) fn export(opt: &mut ShellOpt) -> Option<Self> {
+		if let Some(cwd) = opt.cwd.clone().or_else(|| std::env::current_dir().ok()) {
+			opt.env.insert("YAZI_CWD".to_owned(), cwd.to_string_lossy().into_owned());
+		}
+
+		let (hovered, selected) = opt.args.split_first()?;
+		opt.env.insert("YAZI_HOVERED".to_owned(), hovered.to_string_lossy().into_owned());
+
+		let sep = opt
+			.env
+			.get("YAZI_SELECTION_SEP")
+			.cloned()
+			.or_else(|| std::env::var("YAZI_SELECTION_SEP").ok());
+		let nul = sep.is_some_and(|s| s.eq_ignore_ascii_case("nul"));
+
+		let mut buf = Vec::with_capacity(selected.iter().map(|s| s.len() + 1).sum());
+		for s in selected {
+			buf.extend_from_slice(s.as_encoded_bytes());
+			buf.push(if nul { b'\0' } else { b'\n' });
+		}
+
+		let path = PREVIEW.tmpfile(&format!("selection-{}", NEXT.fetch_add(1, Ordering::Relaxed)));
+		fs::write(&path, buf).ok()?;
+
+		opt.env.insert("YAZI_SELECTION_SEP".to_owned(), if nul { "nul" } else { "newline" }.to_owned());
+		opt.env.insert("YAZI_SELECTION_FILE".to_owned(), path.to_string_lossy().into_owned());
+		Some(Self(path))
+	}
+}
+
+impl Drop for Selection {
+	fn drop(&mut self) { fs::remove_file(&self.0).ok(); }
+}
@@ -1,4 +1,4 @@
-use std::{ffi::OsString, process::Stdio};
+use std::{collections::BTreeMap, ffi::OsString, path::PathBuf, process::Stdio};
 
 use anyhow::Result;
 use tokio::process::{Child, Command};
@@ -7,11 +7,23 @@ use tokio::process::{Child, Command};
 pub struct ShellOpt {
 	pub cmd:    OsString,
 	pub args:   Vec<OsString>,
+	pub cwd:    Option<PathBuf>,
+	pub env:    BTreeMap<String, String>,
 	pub piped:  bool,
 	pub orphan: bool,
 }
 
 impl ShellOpt {
+	// Only the spawned command gets the `cwd` and `env`, Yazi itself keeps its own
+	fn command(&self, program: &str) -> Command {
+		let mut cmd = Command::new(program);
+		cmd.envs(&self.env);
+		if let Some(cwd) = &self.cwd {
+			cmd.current_dir(cwd);
+		}
+		cmd
+	}
+
 	#[inline]
 	fn stdio(&self) -> Stdio {
 		if self.orphan {
@@ -27,7 +39,8 @@ impl ShellOpt {
 pub fn shell(opt: ShellOpt) -> Result<Child> {
 	#[cfg(unix)]
 	return Ok(unsafe {
-		Command::new("sh")
+		opt
+			.command("sh")
 			.arg("-c")
 			.stdin(opt.stdio())
 			.stdout(opt.stdio())
@@ -47,7 +60,8 @@ pub fn shell(opt: ShellOpt) -> Result<Child> {
 	#[cfg(windows)]
 	{
 		Ok(
-			Command::new("cmd.exe")
+			opt
+				.command("cmd.exe")
 				.raw_arg("/C")
 				.raw_arg(parser::parse(&opt.cmd, &opt.args))
 				.stdin(opt.stdio())
@@ -231,3 +245,35 @@ mod parser {
 		}
 	}
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+	use super::*;
+
+	async fn run(opt: ShellOpt) -> String {
+		let child = shell(ShellOpt { piped: true, ..opt }).unwrap();
+		String::from_utf8(child.wait_with_output().await.unwrap().stdout).unwrap()
+	}
+
+	#[tokio::test]
+	async fn test_env_not_leaked() {
+		let cmd = OsString::from(r#"printf '%s' "$YAZI_TEST_ENV""#);
+		let env = BTreeMap::from([("YAZI_TEST_ENV".to_owned(), "foo".to_owned())]);
+
+		assert_eq!(run(ShellOpt { cmd: cmd.clone(), env, ..Default::default() }).await, "foo");
+		assert_eq!(run(ShellOpt { cmd, ..Default::default() }).await, "");
+		assert!(std::env::var_os("YAZI_TEST_ENV").is_none());
+	}
+
+	#[tokio::test]
+	async fn test_cwd() {
+		let dir = std::env::temp_dir().canonicalize().unwrap();
+		let opt = ShellOpt { cmd: "pwd -P".into(), cwd: Some(dir.clone()), ..Default::default() };
+
+		assert_eq!(run(opt).await.trim_end(), dir.to_str().unwrap());
+		assert_ne!(
+			run(ShellOpt { cmd: "pwd -P".into(), ..Default::default() }).await.trim_end(),
+			dir.to_str().unwrap()
+		);
+	}
+}
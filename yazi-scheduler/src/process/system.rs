@@ -0,0 +1,60 @@
+use std::{ffi::OsStr, io};
+
+/// Opens `path` with the default verb of the handler registered for it, or
+/// shows the "Open with" dialog for it if `dialog`.
+///
+/// It blocks until the handler is started, or the dialog is closed.
+#[cfg(windows)]
+pub fn system_open(path: &OsStr, dialog: bool) -> io::Result<()> {
+	use std::{mem, os::windows::ffi::OsStrExt, ptr};
+
+	use windows_sys::Win32::{System::Com::{COINIT_APARTMENTTHREADED, COINIT_DISABLE_OLE1DDE, CoInitializeEx, CoUninitialize}, UI::{Shell::{OAIF_ALLOW_REGISTRATION, OAIF_EXEC, OPENASINFO, SEE_MASK_INVOKEIDLIST, SEE_MASK_NOASYNC, SHELLEXECUTEINFOW, SHOpenWithDialog, ShellExecuteExW}, WindowsAndMessaging::SW_SHOWNORMAL}};
+
+	// Some shell extensions behind the verbs are COM objects
+	let initialized =
+		unsafe { CoInitializeEx(ptr::null(), COINIT_APARTMENTTHREADED | COINIT_DISABLE_OLE1DDE) } >= 0;
+	scopeguard::defer! {
+		if initialized {
+			unsafe { CoUninitialize() };
+		}
+	}
+
+	let file: Vec<u16> = path.encode_wide().chain([0]).collect();
+	if dialog {
+		let info = OPENASINFO {
+			pcszFile:    file.as_ptr(),
+			pcszClass:   ptr::null(),
+			oaifInFlags: OAIF_ALLOW_REGISTRATION | OAIF_EXEC,
+		};
+		let hr = unsafe { SHOpenWithDialog(ptr::null_mut(), &info) };
+		return if hr >= 0 { Ok(()) } else { Err(hresult_error(hr)) };
+	}
+
+	let mut info: SHELLEXECUTEINFOW = unsafe { mem::zeroed() };
+	info.cbSize = mem::size_of::<SHELLEXECUTEINFOW>() as u32;
+	info.fMask = SEE_MASK_NOASYNC | SEE_MASK_INVOKEIDLIST;
+	info.lpFile = file.as_ptr();
+	info.nShow = SW_SHOWNORMAL;
+
+	if unsafe { ShellExecuteExW(&mut info) } != 0 { Ok(()) } else { Err(io::Error::last_os_error()) }
+}
+
+// An HRESULT wrapping a Win32 error, i.e. of `FACILITY_WIN32`, is turned back
+// into that error as `HRESULT_CODE` does, so its message is looked up, while
+// any other one is kept as it is
+#[cfg(windows)]
+fn hresult_error(hr: i32) -> io::Error {
+	const FACILITY_WIN32: u32 = 7;
+
+	let hr = hr as u32;
+	if (hr >> 16) & 0x1fff == FACILITY_WIN32 {
+		io::Error::from_raw_os_error((hr & 0xffff) as i32)
+	} else {
+		io::Error::from_raw_os_error(hr as i32)
+	}
+}
+
+#[cfg(not(windows))]
+pub fn system_open(_: &OsStr, _: bool) -> io::Result<()> {
+	Err(io::Error::new(io::ErrorKind::Unsupported, "The system opener is only available on Windows"))
+}
@@ -1,22 +1,25 @@
-use std::{borrow::Cow, ffi::OsString, future::Future, sync::Arc, time::Duration};
+use std::{borrow::Cow, collections::HashMap, ffi::OsString, future::Future, mem, path::Path, sync::{Arc, atomic::{AtomicBool, Ordering}}, time::Duration};
 
 use anyhow::Result;
 use futures::{FutureExt, future::BoxFuture};
 use parking_lot::Mutex;
 use tokio::{fs, select, sync::{mpsc::{self, UnboundedReceiver}, oneshot}, task::JoinHandle};
-use yazi_config::{TASKS, open::Opener, plugin::{Fetcher, Preloader}};
+use yazi_config::{TASKS, open::Opener, plugin::{Fetcher, FetcherProps, Preloader}};
 use yazi_dds::Pump;
-use yazi_proxy::ManagerProxy;
-use yazi_shared::{Throttle, event::Data, fs::{Url, must_be_dir, remove_dir_clean, unique_name}};
+use yazi_fs::archive::{ArchiveKind, Vfs};
+use yazi_proxy::{ManagerProxy, TabProxy, options::{PluginJob, ProcessOrigin}};
+use yazi_shared::{Throttle, event::Data, fs::{FilesOp, Preserve, Url, maybe_exists, must_be_dir, remove_dir_clean, unique_name}};
 
 use super::{Ongoing, TaskProg, TaskStage};
-use crate::{HIGH, LOW, NORMAL, TaskKind, TaskOp, file::{File, FileOpDelete, FileOpHardlink, FileOpLink, FileOpPaste, FileOpTrash}, plugin::{Plugin, PluginOpEntry}, prework::{Prework, PreworkOpFetch, PreworkOpLoad, PreworkOpSize}, process::{Process, ProcessOpBg, ProcessOpBlock, ProcessOpOrphan}};
+use crate::{HIGH, LOW, NORMAL, TaskKind, TaskOp, archive::{Archive, ArchiveOpCompress, ArchiveOpCopy, ArchiveOpExtract}, checksum::{Checksum, ChecksumAlgo, ChecksumOp}, file::{File, FileBatch, FileOpDelete, FileOpHardlink, FileOpLink, FileOpPaste, FileOpTrash}, plugin::{Plugin, PluginOpEntry}, prework::{Prework, PreworkOpFetch, PreworkOpLoad, PreworkOpSize}, process::{Process, ProcessOpBg, ProcessOpBlock, ProcessOpOrphan, ProcessOpSystem}};
 
 pub struct Scheduler {
-	pub file:    Arc<File>,
-	pub plugin:  Arc<Plugin>,
-	pub prework: Arc<Prework>,
-	pub process: Arc<Process>,
+	pub archive:  Arc<Archive>,
+	pub checksum: Arc<Checksum>,
+	pub file:     Arc<File>,
+	pub plugin:   Arc<Plugin>,
+	pub prework:  Arc<Prework>,
+	pub process:  Arc<Process>,
 
 	micro:       async_priority_channel::Sender<BoxFuture<'static, ()>, u8>,
 	prog:        mpsc::UnboundedSender<TaskProg>,
@@ -31,10 +34,12 @@ impl Scheduler {
 		let (prog_tx, prog_rx) = mpsc::unbounded_channel();
 
 		let mut scheduler = Self {
-			file:    Arc::new(File::new(macro_tx.clone(), prog_tx.clone())),
-			plugin:  Arc::new(Plugin::new(macro_tx.clone(), prog_tx.clone())),
-			prework: Arc::new(Prework::new(macro_tx.clone(), prog_tx.clone())),
-			process: Arc::new(Process::new(prog_tx.clone())),
+			archive:  Arc::new(Archive::new(macro_tx.clone(), prog_tx.clone())),
+			checksum: Arc::new(Checksum::new(prog_tx.clone())),
+			file:     Arc::new(File::new(macro_tx.clone(), prog_tx.clone())),
+			plugin:   Arc::new(Plugin::new(macro_tx.clone(), prog_tx.clone())),
+			prework:  Arc::new(Prework::new(macro_tx.clone(), prog_tx.clone())),
+			process:  Arc::new(Process::new(prog_tx.clone())),
 
 			micro:   micro_tx,
 			prog:    prog_tx,
@@ -55,12 +60,15 @@ impl Scheduler {
 	pub fn cancel(&self, id: usize) -> bool {
 		let mut ongoing = self.ongoing.lock();
 
+		if let Some(task) = ongoing.get_mut(id) {
+			task.canceled = true;
+		}
 		if let Some(hook) = ongoing.hooks.remove(&id) {
 			self.micro.try_send(hook(true), HIGH).ok();
 			return false;
 		}
 
-		ongoing.all.remove(&id).is_some()
+		ongoing.all.remove(&id).map(|mut t| t.publish("finish")).is_some()
 	}
 
 	pub fn shutdown(&self) {
@@ -69,41 +77,79 @@ impl Scheduler {
 		}
 	}
 
-	pub fn file_cut(&self, from: Url, mut to: Url, force: bool) {
-		let mut ongoing = self.ongoing.lock();
-		let id = ongoing.add(TaskKind::User, format!("Cut {from} to {to}"));
+	pub fn file_cut(
+		&self,
+		from: Url,
+		mut to: Url,
+		force: bool,
+		preserve: Preserve,
+		batch: FileBatch,
+	) {
+		let id = self.ongoing.lock().add(TaskKind::User, format!("Cut {from} to {to}"));
 
 		if to.starts_with(&from) && to != from {
 			self.new_and_fail(id, "Cannot cut directory into itself").ok();
 			return;
 		}
 
-		ongoing.hooks.insert(id, {
-			let ongoing = self.ongoing.clone();
-			let (from, to) = (from.clone(), to.clone());
-
-			Box::new(move |canceled: bool| {
-				async move {
-					if !canceled {
-						remove_dir_clean(&from).await;
-						Pump::push_move(from, to);
-					}
-					ongoing.lock().try_remove(id, TaskStage::Hooked);
-				}
-				.boxed()
-			})
-		});
-
-		let file = self.file.clone();
+		let (file, ongoing) = (self.file.clone(), self.ongoing.clone());
 		self.send_micro(id, LOW, async move {
 			if !force {
 				to = unique_name(to, must_be_dir(&from)).await?;
 			}
-			file.paste(FileOpPaste { id, from, to, cha: None, cut: true, follow: false, retry: 0 }).await
+
+			// The directories merged into are left as they were
+			let created = !maybe_exists(&to).await;
+
+			// Hooked once the destination is resolved, to report the one written to
+			let hook = {
+				let (ongoing, from, to) = (ongoing.clone(), from.clone(), to.clone());
+				move |canceled: bool| {
+					async move {
+						if !canceled {
+							if created {
+								preserve.apply_tree(&from, &to).await;
+							}
+							remove_dir_clean(&from).await;
+							Pump::push_move(from, to);
+						}
+						ongoing.lock().try_remove(id, TaskStage::Hooked);
+					}
+					.boxed()
+				}
+			};
+			if !Self::hook_later(&ongoing, id, hook) {
+				return Ok(());
+			}
+
+			file
+				.paste(FileOpPaste {
+					id,
+					from,
+					to,
+					cha: None,
+					cut: true,
+					follow: false,
+					retry: 0,
+					preserve,
+					batch,
+				})
+				.await
 		});
 	}
 
-	pub fn file_copy(&self, from: Url, mut to: Url, force: bool, follow: bool) {
+	/// Copies `from` to `to`, which is hovered once done if `reveal` is set.
+	#[allow(clippy::too_many_arguments)]
+	pub fn file_copy(
+		&self,
+		from: Url,
+		mut to: Url,
+		force: bool,
+		follow: bool,
+		reveal: bool,
+		preserve: Preserve,
+		batch: FileBatch,
+	) {
 		let id = self.ongoing.lock().add(TaskKind::User, format!("Copy {from} to {to}"));
 
 		if to.starts_with(&from) && to != from {
@@ -111,12 +157,55 @@ impl Scheduler {
 			return;
 		}
 
-		let file = self.file.clone();
+		let (file, ongoing) = (self.file.clone(), self.ongoing.clone());
 		self.send_micro(id, LOW, async move {
 			if !force {
 				to = unique_name(to, must_be_dir(&from)).await?;
 			}
-			file.paste(FileOpPaste { id, from, to, cha: None, cut: false, follow, retry: 0 }).await
+
+			// The directories merged into are left as they were
+			let created = !maybe_exists(&to).await;
+
+			let hook = {
+				let (ongoing, from, to) = (ongoing.clone(), from.clone(), to.clone());
+				move |canceled: bool| {
+					async move {
+						if !canceled && created {
+							preserve.apply_tree(&from, &to).await;
+						}
+						if !canceled && reveal {
+							if let (Some((parent, urn)), Ok(file)) =
+								(to.pair(), yazi_shared::fs::File::from(to.clone()).await)
+							{
+								FilesOp::Upserting(parent, HashMap::from_iter([(urn, file)])).emit();
+								TabProxy::reveal(&to);
+							}
+						}
+						if !canceled {
+							Pump::push_copy(from, to);
+						}
+						ongoing.lock().try_remove(id, TaskStage::Hooked);
+					}
+					.boxed()
+				}
+			};
+			if !Self::hook_later(&ongoing, id, hook) {
+				return Ok(());
+			}
+
+			file
+				.paste(FileOpPaste {
+					id,
+					from,
+					to,
+					cha: None,
+					cut: false,
+					follow,
+					retry: 0,
+					preserve,
+					batch,
+				})
+				.await
 		});
 	}
 
@@ -152,16 +241,21 @@ impl Scheduler {
 	}
 
 	pub fn file_delete(&self, target: Url) {
+		let canceled = Arc::new(AtomicBool::new(false));
 		let mut ongoing = self.ongoing.lock();
 		let id = ongoing.add(TaskKind::User, format!("Delete {target}"));
 
+		// The files removed are told to the views in batches, rather than one by one
+		let removed = Arc::new(Throttle::new(usize::MAX, Duration::from_millis(300)));
 		ongoing.hooks.insert(id, {
-			let target = target.clone();
+			let (target, canceled, removed) = (target.clone(), canceled.clone(), removed.clone());
 			let ongoing = self.ongoing.clone();
 
-			Box::new(move |canceled: bool| {
+			Box::new(move |c: bool| {
 				async move {
-					if !canceled {
+					canceled.store(c, Ordering::Relaxed);
+					if !c {
+						File::tell_removed(removed.take());
 						fs::remove_dir_all(&target).await.ok();
 						ManagerProxy::update_tasks(&target);
 						Pump::push_delete(target);
@@ -173,24 +267,26 @@ impl Scheduler {
 		});
 
 		let file = self.file.clone();
-		self.send_micro(
-			id,
-			LOW,
-			async move { file.delete(FileOpDelete { id, target, length: 0 }).await },
-		);
+		self.send_micro(id, LOW, async move {
+			file.delete(FileOpDelete { id, target, length: 0, canceled, removed }).await
+		});
 	}
 
 	pub fn file_trash(&self, target: Url) {
+		let canceled = Arc::new(AtomicBool::new(false));
 		let mut ongoing = self.ongoing.lock();
 		let id = ongoing.add(TaskKind::User, format!("Trash {target}"));
 
+		let removed = Arc::new(Throttle::new(usize::MAX, Duration::from_millis(300)));
 		ongoing.hooks.insert(id, {
-			let target = target.clone();
+			let (target, canceled, removed) = (target.clone(), canceled.clone(), removed.clone());
 			let ongoing = self.ongoing.clone();
 
-			Box::new(move |canceled: bool| {
+			Box::new(move |c: bool| {
 				async move {
-					if !canceled {
+					canceled.store(c, Ordering::Relaxed);
+					if !c {
+						File::tell_removed(removed.take());
 						ManagerProxy::update_tasks(&target);
 						Pump::push_trash(target);
 					}
@@ -202,10 +298,129 @@ impl Scheduler {
 
 		let file = self.file.clone();
 		self.send_micro(id, LOW, async move {
-			file.trash(FileOpTrash { id, target: target.clone(), length: 0 }).await
+			file
+				.trash(FileOpTrash {
+					id,
+					target: target.clone(),
+					length: 0,
+					count: 0,
+					canceled,
+					removed,
+					entries: vec![],
+				})
+				.await
 		})
 	}
 
+	/// Extracts `from` into the directory `to`, which is created if missing, and
+	/// hovered once done if `reveal` is set.
+	pub fn archive_extract(&self, from: Url, to: Url, kind: ArchiveKind, reveal: bool) {
+		let canceled = Arc::new(AtomicBool::new(false));
+		let mut ongoing = self.ongoing.lock();
+
+		let id = ongoing.add(TaskKind::User, format!("Extract {from} to {to}"));
+		ongoing.hooks.insert(id, {
+			let ongoing = self.ongoing.clone();
+			let (to, canceled) = (to.clone(), canceled.clone());
+
+			Box::new(move |c: bool| {
+				async move {
+					canceled.store(c, Ordering::Relaxed);
+					if !c && reveal {
+						if let (Some((parent, urn)), Ok(file)) =
+							(to.pair(), yazi_shared::fs::File::from(to.clone()).await)
+						{
+							FilesOp::Upserting(parent, HashMap::from_iter([(urn, file)])).emit();
+							TabProxy::reveal(&to);
+						}
+					}
+					ongoing.lock().try_remove(id, TaskStage::Hooked);
+				}
+				.boxed()
+			})
+		});
+
+		// Not on a micro worker, as it may wait on the prompts for long
+		let archive = self.archive.clone();
+		tokio::spawn(Self::micro_future(self.prog.clone(), id, async move {
+			archive.extract(ArchiveOpExtract { id, from, to, kind, canceled }).await
+		}));
+	}
+
+	pub fn archive_compress(&self, targets: Vec<Url>, to: Url, kind: ArchiveKind) {
+		let canceled = Arc::new(AtomicBool::new(false));
+		let mut ongoing = self.ongoing.lock();
+
+		let id = ongoing.add(TaskKind::User, format!("Compress {} file(s) to {to}", targets.len()));
+		ongoing.hooks.insert(id, {
+			let ongoing = self.ongoing.clone();
+			let (to, canceled) = (to.clone(), canceled.clone());
+
+			Box::new(move |c: bool| {
+				async move {
+					canceled.store(c, Ordering::Relaxed);
+					if !c {
+						if let (Some((parent, urn)), Ok(file)) =
+							(to.pair(), yazi_shared::fs::File::from(to.clone()).await)
+						{
+							FilesOp::Upserting(parent, HashMap::from_iter([(urn, file)])).emit();
+							TabProxy::reveal(&to);
+						}
+					}
+					ongoing.lock().try_remove(id, TaskStage::Hooked);
+				}
+				.boxed()
+			})
+		});
+
+		let archive = self.archive.clone();
+		self.send_micro(id, LOW, async move {
+			archive.compress(ArchiveOpCompress { id, targets, to, kind, canceled }).await
+		});
+	}
+
+	pub fn archive_copy(&self, from: Url, mut to: Url, force: bool) {
+		let id = self.ongoing.lock().add(TaskKind::User, format!("Copy {from} to {to}"));
+
+		let archive = self.archive.clone();
+		self.send_micro(id, LOW, async move {
+			if !force {
+				let dir = Vfs::cha(&from).await.is_ok_and(|c| c.is_dir());
+				to = unique_name(to, async { dir }).await?;
+			}
+			archive.copy(ArchiveOpCopy { id, from, to }).await
+		});
+	}
+
+	/// Computes the checksums of `targets`, or verifies them against the expected
+	/// ones if `verify` is set, and shows the results once done.
+	pub fn checksum(&self, targets: Vec<Url>, algo: ChecksumAlgo, verify: bool) {
+		let canceled = Arc::new(AtomicBool::new(false));
+		let mut ongoing = self.ongoing.lock();
+
+		let verb = if verify { "Verify" } else { "Checksum" };
+		let id = ongoing.add(TaskKind::User, format!("{verb} {} file(s) with {algo}", targets.len()));
+		ongoing.hooks.insert(id, {
+			let (ongoing, canceled) = (self.ongoing.clone(), canceled.clone());
+			Box::new(move |c: bool| {
+				async move {
+					canceled.store(c, Ordering::Relaxed);
+					ongoing.lock().try_remove(id, TaskStage::Hooked);
+				}
+				.boxed()
+			})
+		});
+
+		// Verifying a single file may ask for the expected checksum
+		let (checksum, prog) = (self.checksum.clone(), self.prog.clone());
+		tokio::spawn(async move {
+			if let Err(e) = checksum.work(ChecksumOp { id, targets, algo, verify, canceled }).await {
+				prog.send(TaskProg::New(id, 0)).ok();
+				prog.send(TaskProg::Fail(id, format!("Checksum failed:\n{e}"))).ok();
+			}
+		});
+	}
+
 	pub fn plugin_micro(&self, name: String, args: Vec<Data>) {
 		let id = self.ongoing.lock().add(TaskKind::User, format!("Run micro plugin `{name}`"));
 
@@ -223,17 +438,63 @@ impl Scheduler {
 		self.plugin.macro_(PluginOpEntry { id, name, args }).ok();
 	}
 
-	pub fn fetch_paged(&self, fetcher: &Fetcher, targets: Vec<yazi_shared::fs::File>) {
-		let id = self.ongoing.lock().add(
-			TaskKind::Preload,
-			format!("Run fetcher `{}` with {} target(s)", fetcher.run.name, targets.len()),
-		);
+	pub fn plugin_job(&self, title: String, total: u64, done: oneshot::Sender<PluginJob>) {
+		let canceled = Arc::new(AtomicBool::new(false));
+		let mut ongoing = self.ongoing.lock();
 
-		let plugin = fetcher.into();
-		let prework = self.prework.clone();
-		self.send_micro(id, NORMAL, async move {
-			prework.fetch(PreworkOpFetch { id, plugin, targets }).await
+		let id = ongoing.add(TaskKind::User, title);
+		ongoing.hooks.insert(id, {
+			let ongoing = self.ongoing.clone();
+			let canceled = canceled.clone();
+			Box::new(move |c: bool| {
+				async move {
+					canceled.store(c, Ordering::Relaxed);
+					ongoing.lock().try_remove(id, TaskStage::Hooked);
+				}
+				.boxed()
+			})
 		});
+
+		let (tx, rx) = mpsc::unbounded_channel();
+		if done.send(PluginJob { id, tx, canceled }).is_err() {
+			ongoing.hooks.remove(&id);
+			ongoing.all.remove(&id);
+			return;
+		}
+
+		// Jobs can run for a long time, so they shouldn't occupy a micro worker
+		let plugin = self.plugin.clone();
+		tokio::spawn(async move { plugin.job(id, total, rx).await.ok() });
+	}
+
+	pub fn fetch_paged(&self, fetcher: &Fetcher, mut targets: Vec<yazi_shared::fs::File>) {
+		let batch = fetcher.max_batch.unwrap_or(usize::MAX).max(1);
+		let mut ongoing = self.ongoing.lock();
+
+		while !targets.is_empty() {
+			let rest = targets.split_off(batch.min(targets.len()));
+			let targets = mem::replace(&mut targets, rest);
+			let id = ongoing.add(
+				TaskKind::Preload,
+				format!("Run fetcher `{}` with {} target(s)", fetcher.run.name, targets.len()),
+			);
+
+			let plugin: FetcherProps = fetcher.into();
+			let (prework, semaphore) = (self.prework.clone(), self.prework.fetching(plugin.id));
+			let (micro, prog, prio) = (self.micro.clone(), self.prog.clone(), fetcher.prio as u8);
+
+			// Queued only once permitted, so a fetcher at its `max_concurrency` doesn't
+			// park the workers
+			tokio::spawn(async move {
+				let Ok(permit) = semaphore.acquire_owned().await else { return };
+				_ = micro.try_send(
+					Self::micro_future(prog, id, async move {
+						prework.fetch(PreworkOpFetch { id, plugin, targets, permit }).await
+					}),
+					prio,
+				);
+			});
+		}
 	}
 
 	pub fn preload_paged(&self, preloader: &Preloader, target: &yazi_shared::fs::File) {
@@ -243,25 +504,28 @@ impl Scheduler {
 		let plugin = preloader.into();
 		let target = target.clone();
 		let prework = self.prework.clone();
-		self.send_micro(
-			id,
-			NORMAL,
-			async move { prework.load(PreworkOpLoad { id, plugin, target }).await },
-		);
+		self.send_micro(id, preloader.prio as u8, async move {
+			prework.load(PreworkOpLoad { id, plugin, target }).await
+		});
 	}
 
-	pub fn prework_size(&self, targets: Vec<&Url>) {
+	pub fn prework_size(&self, targets: Vec<(&yazi_shared::fs::File, Arc<AtomicBool>)>) {
 		let throttle = Arc::new(Throttle::new(targets.len(), Duration::from_millis(300)));
 		let mut ongoing = self.ongoing.lock();
 
-		for target in targets {
-			let id = ongoing.add(TaskKind::Preload, format!("Calculate the size of {target}"));
-			let target = target.clone();
+		for (target, canceled) in targets {
+			let id = ongoing.add(TaskKind::Preload, format!("Calculate the size of {}", target.url));
+			let (target, mtime) = (target.url_owned(), target.cha.mtime);
 			let throttle = throttle.clone();
 
-			let prework = self.prework.clone();
-			self.send_micro(id, NORMAL, async move {
-				prework.size(PreworkOpSize { id, target, throttle }).await
+			// The walks are capped by `size_workers` instead of occupying micro workers
+			let (prework, prog) = (self.prework.clone(), self.prog.clone());
+			tokio::spawn(async move {
+				if let Err(e) = prework.size(PreworkOpSize { id, target, mtime, canceled, throttle }).await
+				{
+					prog.send(TaskProg::New(id, 0)).ok();
+					prog.send(TaskProg::Fail(id, format!("Failed to calculate the size:\n{e}"))).ok();
+				}
 			});
 		}
 	}
@@ -270,14 +534,18 @@ impl Scheduler {
 		&self,
 		opener: Cow<'static, Opener>,
 		args: Vec<OsString>,
+		origin: ProcessOrigin,
 		done: Option<oneshot::Sender<()>>,
 	) {
+		// The `cwd` of the opener takes precedence over the one it's queued from
+		let cwd = opener.cwd(args.first().map(Path::new)).unwrap_or(origin.cwd);
 		let name = {
 			let args = args.iter().map(|a| a.to_string_lossy()).collect::<Vec<_>>().join(" ");
+			let tab = origin.tab.map(|t| format!(" of tab {}", t + 1)).unwrap_or_default();
 			if args.is_empty() {
-				format!("Run {:?}", opener.run)
+				format!("Run {:?} in {}{tab}", opener.run, cwd.display())
 			} else {
-				format!("Run {:?} with `{args}`", opener.run)
+				format!("Run {:?} with `{args}` in {}{tab}", opener.run, cwd.display())
 			}
 		};
 
@@ -302,15 +570,22 @@ impl Scheduler {
 			})
 		});
 
-		let cmd = OsString::from(&opener.run);
+		let (cmd, cwd) = (OsString::from(&opener.run), Some(cwd));
 		let process = self.process.clone();
 		self.send_micro(id, NORMAL, async move {
-			if opener.block {
-				process.block(ProcessOpBlock { id, cmd, args }).await
-			} else if opener.orphan {
-				process.orphan(ProcessOpOrphan { id, cmd, args }).await
+			if opener.is_system() {
+				let (targets, dialog) =
+					(args.into_iter().skip(1).collect(), opener.run == Opener::SYSTEM_DIALOG);
+				return process.system(ProcessOpSystem { id, targets, dialog }).await;
+			}
+
+			let (block, orphan, env) = (opener.block, opener.orphan, opener.into_owned().env);
+			if block {
+				process.block(ProcessOpBlock { id, cmd, args, cwd, env }).await
+			} else if orphan {
+				process.orphan(ProcessOpOrphan { id, cmd, args, cwd, env }).await
 			} else {
-				process.bg(ProcessOpBg { id, cmd, args, cancel: cancel_rx }).await
+				process.bg(ProcessOpBg { id, cmd, args, cwd, env, cancel: cancel_rx }).await
 			}
 		});
 	}
@@ -333,6 +608,7 @@ impl Scheduler {
 		micro: async_priority_channel::Receiver<BoxFuture<'static, ()>, u8>,
 		macro_: async_priority_channel::Receiver<TaskOp, u8>,
 	) -> JoinHandle<()> {
+		let archive = self.archive.clone();
 		let file = self.file.clone();
 		let plugin = self.plugin.clone();
 		let prework = self.prework.clone();
@@ -351,8 +627,10 @@ impl Scheduler {
 						if !ongoing.lock().exists(id) {
 							continue;
 						}
+						prog.send(TaskProg::Run(id)).ok();
 
 						let result = match op {
+							TaskOp::Archive(op) => archive.work(*op).await,
 							TaskOp::File(op) => file.work(*op).await,
 							TaskOp::Plugin(op) => plugin.work(*op).await,
 							TaskOp::Prework(op) => prework.work(*op).await,
@@ -374,10 +652,16 @@ impl Scheduler {
 		tokio::spawn(async move {
 			while let Some(op) = rx.recv().await {
 				match op {
+					TaskProg::Run(id) => {
+						if let Some(task) = ongoing.lock().get_mut(id) {
+							task.running = true;
+						}
+					}
 					TaskProg::New(id, size) => {
 						if let Some(task) = ongoing.lock().get_mut(id) {
 							task.total += 1;
 							task.found += size;
+							task.publish_progress();
 						}
 					}
 					TaskProg::Adv(id, succ, processed) => {
@@ -385,6 +669,7 @@ impl Scheduler {
 						if let Some(task) = ongoing.get_mut(id) {
 							task.succ += succ;
 							task.processed += processed;
+							task.publish_progress();
 						}
 						if succ > 0 {
 							if let Some(fut) = ongoing.try_remove(id, TaskStage::Pending) {
@@ -402,11 +687,29 @@ impl Scheduler {
 							task.fail += 1;
 							task.logs.push_str(&reason);
 							task.logs.push('\n');
+							task.publish_progress();
+
+							if let Some(logger) = &task.logger {
+								logger.send(reason).ok();
+							}
+						}
+					}
+					TaskProg::Abort(id, reason) => {
+						let mut ongoing = ongoing.lock();
+						if let Some(task) = ongoing.get_mut(id) {
+							task.fail += 1;
+							task.logs.push_str(&reason);
+							task.logs.push('\n');
 
 							if let Some(logger) = &task.logger {
 								logger.send(reason).ok();
 							}
 						}
+						if let Some(hook) = ongoing.hooks.remove(&id) {
+							micro.try_send(hook(true), HIGH).ok();
+						} else if let Some(mut task) = ongoing.all.remove(&id) {
+							task.publish("finish");
+						}
 					}
 					TaskProg::Log(id, line) => {
 						if let Some(task) = ongoing.lock().get_mut(id) {
@@ -427,17 +730,41 @@ impl Scheduler {
 	where
 		F: Future<Output = Result<()>> + Send + 'static,
 	{
-		let prog = self.prog.clone();
-		_ = self.micro.try_send(
-			async move {
-				if let Err(e) = f.await {
-					prog.send(TaskProg::New(id, 0)).ok();
-					prog.send(TaskProg::Fail(id, format!("Task initialization failed:\n{e:?}"))).ok();
-				}
+		_ = self.micro.try_send(Self::micro_future(self.prog.clone(), id, f), priority);
+	}
+
+	fn micro_future<F>(
+		prog: mpsc::UnboundedSender<TaskProg>,
+		id: usize,
+		f: F,
+	) -> BoxFuture<'static, ()>
+	where
+		F: Future<Output = Result<()>> + Send + 'static,
+	{
+		async move {
+			prog.send(TaskProg::Run(id)).ok();
+			if let Err(e) = f.await {
+				prog.send(TaskProg::New(id, 0)).ok();
+				prog.send(TaskProg::Fail(id, format!("Task initialization failed:\n{e:?}"))).ok();
 			}
-			.boxed(),
-			priority,
-		);
+		}
+		.boxed()
+	}
+
+	/// Adds the hook of a task that's already running, e.g. once its destination
+	/// is resolved, unless it's been canceled in the meantime.
+	fn hook_later(
+		ongoing: &Mutex<Ongoing>,
+		id: usize,
+		hook: impl FnOnce(bool) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+	) -> bool {
+		let mut ongoing = ongoing.lock();
+		if !ongoing.exists(id) {
+			return false;
+		}
+
+		ongoing.hooks.insert(id, Box::new(hook));
+		true
 	}
 
 	fn new_and_fail(&self, id: usize, reason: &str) -> Result<()> {
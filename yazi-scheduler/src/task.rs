@@ -1,4 +1,11 @@
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
 use tokio::sync::mpsc;
+use yazi_config::TASKS;
+use yazi_dds::{Pubsub, body::BodyTask};
+
+use crate::notify_desktop;
 
 #[derive(Debug, Default)]
 pub struct Task {
@@ -13,27 +20,98 @@ pub struct Task {
 
 	pub found:     u64,
 	pub processed: u64,
+	/// Whether a worker has started on it, which it hasn't if it's still waiting
+	/// in the queues.
+	pub running:   bool,
 
 	pub logs:   String,
 	pub logger: Option<mpsc::UnboundedSender<String>>,
+
+	pub(super) canceled:  bool,
+	pub(super) finished:  bool,
+	pub(super) published: Option<Instant>,
+	pub(super) started:   Option<Instant>,
 }
 
 impl Task {
 	pub fn new(id: usize, kind: TaskKind, name: String) -> Self {
-		Self { id, kind, name, ..Default::default() }
+		Self { id, kind, name, started: Some(Instant::now()), ..Default::default() }
+	}
+
+	pub(super) fn publish(&mut self, event: &str) {
+		if self.kind != TaskKind::User || self.finished {
+			return;
+		}
+
+		let status = match event {
+			"finish" if self.canceled => Some("canceled"),
+			"finish" if self.fail > 0 => Some("failed"),
+			"finish" => Some("succeeded"),
+			_ => None,
+		};
+
+		self.finished = status.is_some();
+		self.published = Some(Instant::now());
+		if let Some(status @ ("succeeded" | "failed")) = status {
+			self.notify_desktop(status);
+		}
+
+		Pubsub::pub_from_task(BodyTask {
+			id:     self.id,
+			event:  event.to_owned(),
+			name:   self.name.clone(),
+			status: status.map(ToOwned::to_owned),
+
+			total: self.total,
+			succ:  self.succ,
+			fail:  self.fail,
+
+			found:     self.found,
+			processed: self.processed,
+		});
+	}
+
+	fn notify_desktop(&self, status: &str) {
+		let elapsed = self.started.map(|t| t.elapsed()).unwrap_or_default();
+		if !TASKS.notify_desktop || elapsed < Duration::from_secs(TASKS.notify_after) {
+			return;
+		}
+
+		let body = if self.fail > 0 {
+			format!("{}\n{} of {} failed", self.name, self.fail, self.total)
+		} else {
+			self.name.clone()
+		};
+		notify_desktop(format!("Task {status}"), body);
+	}
+
+	// Progress is reported at most 4 times per second, and once all the work
+	// has been done, the task is considered finished even if some of it failed.
+	pub(super) fn publish_progress(&mut self) {
+		if self.stage >= TaskStage::Dispatched && self.fail > 0 && self.succ + self.fail >= self.total {
+			return self.publish("finish");
+		}
+		match self.published {
+			Some(t) if t.elapsed() < Duration::from_millis(250) => {}
+			_ => self.publish("progress"),
+		}
 	}
 }
 
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum TaskKind {
 	#[default]
 	User,
 	Preload,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct TaskSummary {
-	pub name: String,
+	pub id:    usize,
+	pub kind:  TaskKind,
+	pub name:  String,
+	pub stage: TaskStage,
 
 	pub total: u32,
 	pub succ:  u32,
@@ -41,12 +119,31 @@ pub struct TaskSummary {
 
 	pub found:     u64,
 	pub processed: u64,
+	pub running:   bool,
+}
+
+impl TaskSummary {
+	/// Which group it's listed in, a task is running once a worker has started
+	/// on it, or any of its work has been done, and finished once all of it has,
+	/// but some failed, as it stays until canceled then.
+	pub fn state(&self) -> TaskState {
+		if self.stage >= TaskStage::Dispatched && self.fail > 0 && self.succ + self.fail >= self.total {
+			TaskState::Finished
+		} else if self.running || self.succ + self.fail > 0 || self.processed > 0 {
+			TaskState::Running
+		} else {
+			TaskState::Pending
+		}
+	}
 }
 
 impl From<&Task> for TaskSummary {
 	fn from(task: &Task) -> Self {
 		TaskSummary {
-			name: task.name.clone(),
+			id:    task.id,
+			kind:  task.kind,
+			name:  task.name.clone(),
+			stage: task.stage.clone(),
 
 			total: task.total,
 			succ:  task.succ,
@@ -54,12 +151,15 @@ impl From<&Task> for TaskSummary {
 
 			found:     task.found,
 			processed: task.processed,
+			running:   task.running,
 		}
 	}
 }
 
 #[derive(Debug)]
 pub enum TaskProg {
+	// id, once a worker starts on it
+	Run(usize),
 	// id, size
 	New(usize, u64),
 	// id, processed, size
@@ -68,11 +168,35 @@ pub enum TaskProg {
 	Succ(usize),
 	// id
 	Fail(usize, String),
+	// id, the rest of it is canceled after failing
+	Abort(usize, String),
 	// id, line
 	Log(usize, String),
 }
 
-#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
+/// The groups of the task list, in the order they're listed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum TaskState {
+	Running,
+	Pending,
+	Finished,
+}
+
+impl TaskState {
+	/// The header of the group.
+	pub fn title(self) -> &'static str {
+		match self {
+			Self::Running => "Running",
+			Self::Pending => "Pending",
+			Self::Finished => "Failed",
+		}
+	}
+}
+
+/// `pending` while the work is still being found, `dispatched` once all of it
+/// has been, and `hooked` while the finishing touches are being made.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum TaskStage {
 	#[default]
 	Pending,
@@ -1,4 +1,5 @@
 use core::str;
+use std::{borrow::Cow, fmt::Write};
 
 pub const MIME_DIR: &str = "inode/directory";
 
@@ -28,6 +29,31 @@ pub fn strip_trailing_newline(mut s: String) -> String {
 	s
 }
 
+/// `b` as a string, with each byte that isn't valid UTF-8 escaped as `\xNN`
+/// instead of being replaced with `U+FFFD`, so that such names stay distinct.
+pub fn escape_bytes(b: &[u8]) -> Cow<'_, str> {
+	if let Ok(s) = str::from_utf8(b) {
+		return Cow::Borrowed(s);
+	}
+
+	let (mut s, mut b) = (String::with_capacity(b.len() + 8), b);
+	while let Err(e) = str::from_utf8(b) {
+		let (valid, rest) = b.split_at(e.valid_up_to());
+		// SAFETY: the bytes up to `valid_up_to()` are valid UTF-8
+		s.push_str(unsafe { str::from_utf8_unchecked(valid) });
+
+		let (invalid, rest) = rest.split_at(e.error_len().unwrap_or(rest.len()));
+		for byte in invalid {
+			_ = write!(s, "\\x{byte:02X}");
+		}
+		b = rest;
+	}
+
+	// SAFETY: `from_utf8()` above succeeded on the rest
+	s.push_str(unsafe { str::from_utf8_unchecked(b) });
+	Cow::Owned(s)
+}
+
 pub fn replace_to_printable(s: &[String], tab_size: u8) -> String {
 	let mut buf = Vec::new();
 	buf.try_reserve_exact(s.iter().map(|s| s.len()).sum::<usize>() | 15).unwrap_or_else(|_| panic!());
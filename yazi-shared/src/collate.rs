@@ -0,0 +1,136 @@
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+use crate::{push_number, translit::Transliterator};
+
+/// A key of `s` for sorting it the way people read it, rather than by code
+/// points, which puts `Äpfel` after `Zucker`, and all katakana after hiragana.
+///
+/// It has two levels split by a `\0`, and the second one only breaks the ties
+/// of the first. The first level is the base letters of `s`: its compatibility
+/// decomposition without the accents, transliterated, lowercased, and with the
+/// katakana folded into hiragana, so `Äpfel` goes with `apfel`, `ガ` with `か`,
+/// and `Ｆｉｌｅ` with `file`. If `natural`, the whitespace is dropped and the
+/// numbers are tagged as [`natsort_key`](crate::natsort_key) does. The second
+/// level is `s` itself, lowercased if `insensitive`.
+pub fn collate_key(s: &[u8], insensitive: bool, natural: bool, digits_last: bool) -> Vec<u8> {
+	let lossy = String::from_utf8_lossy(s);
+	let stripped: String = lossy.nfkd().filter(|&c| !is_combining_mark(c)).collect();
+	let stripped = stripped.as_bytes();
+	let base = stripped.transliterate();
+
+	let mut key = Vec::with_capacity(s.len() * 2 + 8);
+	let mut buf = [0; 4];
+	let mut chars = base.char_indices().peekable();
+	while let Some((i, c)) = chars.next() {
+		if natural && c.is_whitespace() {
+			continue;
+		} else if natural && c.is_ascii_digit() {
+			let mut j = i + 1;
+			while chars.next_if(|(_, c)| c.is_ascii_digit()).is_some() {
+				j += 1;
+			}
+			push_number(&mut key, &base.as_bytes()[i..j], digits_last);
+			continue;
+		}
+
+		for c in c.to_lowercase() {
+			let c = match c {
+				'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+				_ => c,
+			};
+			key.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+		}
+	}
+
+	key.push(0);
+	if insensitive {
+		key.extend_from_slice(lossy.to_lowercase().as_bytes());
+	} else {
+		key.extend_from_slice(s);
+	}
+	key
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn sorted<'a>(names: &[&'a str], natural: bool, digits_last: bool) -> Vec<&'a str> {
+		let mut names = names.to_vec();
+		names.sort_by_cached_key(|s| collate_key(s.as_bytes(), true, natural, digits_last));
+		names
+	}
+
+	#[test]
+	fn test_german() {
+		let names = ["Zucker", "Übel", "Öl", "Bären", "Uhr", "Äpfel", "Baum", "Ofen", "apfel", "Bär"];
+		assert_eq!(sorted(&names, false, false), [
+			"apfel", "Äpfel", "Bär", "Bären", "Baum", "Ofen", "Öl", "Übel", "Uhr", "Zucker"
+		]);
+	}
+
+	#[test]
+	fn test_japanese() {
+		let names = [
+			"漢字",
+			"パン",
+			"ばら",
+			"はな",
+			"ひかり",
+			"ドア",
+			"さくら",
+			"きもの",
+			"がっこう",
+			"カメラ",
+			"いちご",
+			"アイス",
+			"あさひ",
+		];
+		assert_eq!(sorted(&names, false, false), [
+			"アイス",
+			"あさひ",
+			"いちご",
+			"がっこう",
+			"カメラ",
+			"きもの",
+			"さくら",
+			"ドア",
+			"はな",
+			"ばら",
+			"パン",
+			"ひかり",
+			"漢字"
+		]);
+	}
+
+	#[test]
+	fn test_numbers() {
+		let names = ["file10.txt", "Datei 2.txt", "file2.txt", "1.txt", "Übel 10", "übel 9"];
+		assert_eq!(sorted(&names, true, false), [
+			"1.txt",
+			"Datei 2.txt",
+			"file2.txt",
+			"file10.txt",
+			"übel 9",
+			"Übel 10"
+		]);
+		assert_eq!(sorted(&names, true, true), [
+			"Datei 2.txt",
+			"file2.txt",
+			"file10.txt",
+			"übel 9",
+			"Übel 10",
+			"1.txt"
+		]);
+		assert_eq!(sorted(&["a10", "a9"], false, false), ["a10", "a9"]);
+	}
+
+	#[test]
+	fn test_ties() {
+		let key = |s: &str, insensitive| collate_key(s.as_bytes(), insensitive, true, false);
+		assert!(key("apfel", false) < key("Äpfel", false));
+		assert!(key("Apfel", false) < key("apfel", false));
+		assert_eq!(key("Apfel", true), key("apfel", true));
+		assert!(key("Ｆｉｌｅ", true) < key("filez", true));
+	}
+}
@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, ffi::OsString};
+use std::{collections::VecDeque, path::PathBuf};
 
 use crossterm::event::{KeyEvent, MouseEvent};
 use tokio::sync::mpsc;
@@ -24,7 +24,7 @@ pub enum Event {
 #[derive(Debug, Default)]
 pub struct EventQuit {
 	pub no_cwd_file: bool,
-	pub selected:    Option<OsString>,
+	pub selected:    Option<Vec<PathBuf>>,
 }
 
 impl Event {
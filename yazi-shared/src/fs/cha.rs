@@ -3,7 +3,7 @@ use std::{fs::{FileType, Metadata}, path::Path, time::SystemTime};
 use bitflags::bitflags;
 use yazi_macro::unix_either;
 
-use super::Urn;
+use super::{Urn, long};
 
 bitflags! {
 	#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -15,6 +15,8 @@ bitflags! {
 		const ORPHAN = 0b00001000;
 
 		const DUMMY  = 0b00010000;
+		// A dummy whose metadata couldn't be read in time, e.g. on a stalled mount
+		const UNAVAILABLE = 0b00100000;
 	}
 }
 
@@ -133,7 +135,7 @@ impl Cha {
 
 		if meta.is_symlink() {
 			attached |= ChaKind::LINK;
-			meta = tokio::fs::metadata(path).await.unwrap_or(meta);
+			meta = long::metadata(path).await.unwrap_or(meta);
 		}
 		if meta.is_symlink() {
 			attached |= ChaKind::ORPHAN;
@@ -195,6 +197,9 @@ impl Cha {
 	#[inline]
 	pub const fn is_dummy(&self) -> bool { self.kind.contains(ChaKind::DUMMY) }
 
+	#[inline]
+	pub const fn is_unavailable(&self) -> bool { self.kind.contains(ChaKind::UNAVAILABLE) }
+
 	#[inline]
 	pub const fn is_block(&self) -> bool {
 		unix_either!(self.perm & libc::S_IFMT == libc::S_IFBLK, false)
@@ -1,9 +1,8 @@
 use std::{cell::Cell, ffi::OsStr, fs::{FileType, Metadata}, ops::Deref};
 
 use anyhow::Result;
-use tokio::fs;
 
-use super::{Urn, UrnBuf};
+use super::{ChaKind, Urn, UrnBuf, long};
 use crate::{fs::{Cha, Url}, theme::IconCache};
 
 #[derive(Clone, Debug, Default)]
@@ -29,14 +28,14 @@ impl AsRef<File> for File {
 impl File {
 	#[inline]
 	pub async fn from(url: Url) -> Result<Self> {
-		let meta = fs::symlink_metadata(&url).await?;
+		let meta = long::symlink_metadata(&url).await?;
 		Ok(Self::from_meta(url, meta).await)
 	}
 
 	#[inline]
 	pub async fn from_meta(url: Url, meta: Metadata) -> Self {
 		let link_to =
-			if meta.is_symlink() { fs::read_link(&url).await.map(Url::from).ok() } else { None };
+			if meta.is_symlink() { long::read_link(&url).await.map(Url::from).ok() } else { None };
 
 		let cha = Cha::new(&url, meta).await;
 
@@ -53,6 +52,15 @@ impl File {
 		}
 	}
 
+	/// A file with only its name, and its kind if `ft` is known, as its metadata
+	/// couldn't be read in time.
+	#[inline]
+	pub fn from_unavailable(url: Url, ft: Option<FileType>) -> Self {
+		let mut file = Self::from_dummy(url, ft);
+		file.cha.kind |= ChaKind::UNAVAILABLE;
+		file
+	}
+
 	#[inline]
 	pub fn rebase(&self, parent: &Url) -> Self {
 		Self {
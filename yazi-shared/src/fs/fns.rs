@@ -3,14 +3,14 @@ use std::{borrow::Cow, collections::{HashMap, HashSet, VecDeque}, ffi::{OsStr, O
 use anyhow::{Result, bail};
 use tokio::{fs, io, select, sync::{mpsc, oneshot}, time};
 
-use super::Cha;
+use super::{Cha, Preserve, long, long_path};
 
 #[inline]
-pub async fn must_exists(p: impl AsRef<Path>) -> bool { fs::symlink_metadata(p).await.is_ok() }
+pub async fn must_exists(p: impl AsRef<Path>) -> bool { long::symlink_metadata(&p).await.is_ok() }
 
 #[inline]
 pub async fn maybe_exists(p: impl AsRef<Path>) -> bool {
-	match fs::symlink_metadata(p).await {
+	match long::symlink_metadata(&p).await {
 		Ok(_) => true,
 		Err(e) => e.kind() != io::ErrorKind::NotFound,
 	}
@@ -18,7 +18,7 @@ pub async fn maybe_exists(p: impl AsRef<Path>) -> bool {
 
 #[inline]
 pub async fn must_be_dir(p: impl AsRef<Path>) -> bool {
-	fs::metadata(p).await.map_or(false, |m| m.is_dir())
+	long::metadata(&p).await.is_ok_and(|m| m.is_dir())
 }
 
 #[inline]
@@ -125,6 +125,42 @@ async fn test_realname_unchecked() {
 	check("/tmp/issue-1173/D", "D", c).await;
 }
 
+#[cfg(windows)]
+#[tokio::test]
+async fn test_long_path_ops() {
+	let root = std::env::temp_dir().join("yazi-long-path");
+	long::remove_dir_all(&root).await.ok();
+
+	// Nested beyond `MAX_PATH`, where all of these failed before
+	let deep = (0..20).fold(root.clone(), |p, i| p.join(format!("{i:02}-{}", "x".repeat(12))));
+	assert!(deep.as_os_str().len() > 260);
+	long::create_dir_all(&deep).await.unwrap();
+
+	// Renames one by one, like `bulk_rename`
+	for i in 0..3 {
+		long::write(&deep.join(format!("{i}.txt")), "yazi").await.unwrap();
+	}
+	for i in 0..3 {
+		let (old, new) = (deep.join(format!("{i}.txt")), deep.join(format!("renamed-{i}.txt")));
+		long::rename(&old, &new).await.unwrap();
+		assert!(!maybe_exists(&old).await && must_exists(&new).await);
+	}
+
+	// Copies with the same function as the paste task
+	let (from, to) = (deep.join("renamed-0.txt"), deep.join("pasted.txt"));
+	let cha = Cha::from(long::symlink_metadata(&from).await.unwrap());
+	let mut it = copy_with_progress(&from, &to, cha, Preserve::Attrs);
+	while let Some(n) = it.recv().await {
+		if n.unwrap() == 0 {
+			break;
+		}
+	}
+	assert!(must_exists(&to).await);
+	assert_eq!(calculate_size(&deep).await, 16);
+
+	long::remove_dir_all(&root).await.unwrap();
+}
+
 // realpath(3) without resolving symlinks. This is useful for case-insensitive
 // filesystems.
 //
@@ -159,13 +195,13 @@ pub async fn calculate_size(path: &Path) -> u64 {
 	let mut total = 0;
 	let mut stack = VecDeque::from([path.to_path_buf()]);
 	while let Some(path) = stack.pop_front() {
-		let Ok(meta) = fs::symlink_metadata(&path).await else { continue };
+		let Ok(meta) = long::symlink_metadata(&path).await else { continue };
 		if !meta.is_dir() {
 			total += meta.len();
 			continue;
 		}
 
-		let Ok(mut it) = fs::read_dir(path).await else { continue };
+		let Ok(mut it) = long::read_dir(&path).await else { continue };
 		while let Ok(Some(entry)) = it.next_entry().await {
 			let Ok(meta) = entry.metadata().await else { continue };
 
@@ -183,34 +219,18 @@ pub fn copy_with_progress(
 	from: &Path,
 	to: &Path,
 	cha: Cha,
+	preserve: Preserve,
 ) -> mpsc::Receiver<Result<u64, io::Error>> {
 	let (tx, rx) = mpsc::channel(1);
 	let (tick_tx, mut tick_rx) = oneshot::channel();
 
 	tokio::spawn({
-		let (from, to) = (from.to_owned(), to.to_owned());
-
-		let mut ft = std::fs::FileTimes::new();
-		cha.atime.map(|t| ft = ft.set_accessed(t));
-		cha.mtime.map(|t| ft = ft.set_modified(t));
-		#[cfg(target_os = "macos")]
-		{
-			use std::os::macos::fs::FileTimesExt;
-			cha.btime.map(|t| ft = ft.set_created(t));
-		}
-		#[cfg(windows)]
-		{
-			use std::os::windows::fs::FileTimesExt;
-			cha.btime.map(|t| ft = ft.set_created(t));
-		}
+		let (from, to) = (long_path(from).into_owned(), long_path(to).into_owned());
 
 		async move {
 			_ = match fs::copy(&from, &to).await {
 				Ok(len) => {
-					_ = tokio::task::spawn_blocking(move || {
-						std::fs::File::options().write(true).open(to).and_then(|f| f.set_times(ft)).ok();
-					})
-					.await;
+					_ = tokio::task::spawn_blocking(move || preserve.apply(&to, cha).ok()).await;
 					tick_tx.send(Ok(len))
 				}
 				Err(e) => tick_tx.send(Err(e)),
@@ -220,7 +240,7 @@ pub fn copy_with_progress(
 
 	tokio::spawn({
 		let tx = tx.clone();
-		let to = to.to_path_buf();
+		let to = long_path(to).into_owned();
 
 		async move {
 			let mut last = 0;
@@ -260,7 +280,8 @@ pub fn copy_with_progress(
 }
 
 pub async fn remove_dir_clean(dir: &Path) {
-	let Ok(mut it) = fs::read_dir(dir).await else { return };
+	let dir = long_path(dir);
+	let Ok(mut it) = fs::read_dir(&dir).await else { return };
 
 	while let Ok(Some(entry)) = it.next_entry().await {
 		if entry.file_type().await.is_ok_and(|t| t.is_dir()) {
@@ -349,6 +370,40 @@ pub fn max_common_root(files: &[impl AsRef<Path>]) -> PathBuf {
 	root
 }
 
+// Whether renaming `old` to `new` changes its extension, which giving one to a
+// file without any, or changing only its case, doesn't count as
+pub fn ext_changed(old: &Path, new: &Path) -> bool {
+	old.extension().is_some_and(|a| !new.extension().is_some_and(|b| a.eq_ignore_ascii_case(b)))
+}
+
+/// Writes `contents` to `path` through a temporary file next to it, so that
+/// it's never left truncated if the process gets killed halfway, nor seen
+/// half-written by anyone reading it meanwhile.
+///
+/// The temporary file is unique to each call, as the autosave may write the
+/// same `path` from another thread.
+///
+/// Anything other than a regular file, such as `/dev/stdout`, a FIFO, or a
+/// symlink, is written in place instead, as replacing it would break it.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+	static SEQ: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+	match std::fs::symlink_metadata(path) {
+		Ok(m) if !m.is_file() => return std::fs::write(path, contents),
+		_ => {}
+	}
+
+	let mut tmp = path.as_os_str().to_owned();
+	let seq = SEQ.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+	tmp.push(format!(".{}-{seq}.tmp", std::process::id()));
+
+	let result = std::fs::write(&tmp, contents).and_then(|_| std::fs::rename(&tmp, path));
+	if result.is_err() {
+		std::fs::remove_file(&tmp).ok();
+	}
+	result
+}
+
 #[cfg(unix)]
 #[test]
 fn test_max_common_root() {
@@ -373,3 +428,43 @@ fn test_max_common_root() {
 		"/aa/bb"
 	);
 }
+
+#[test]
+fn test_ext_changed() {
+	let changed = |a: &str, b: &str| ext_changed(Path::new(a), Path::new(b));
+	assert!(changed("photo.jpg", "photo.jg"));
+	assert!(changed("photo.jpg", "photo"));
+	assert!(changed("a/photo.tar.gz", "a/photo.tar.xz"));
+	assert!(!changed("photo.jpg", "picture.jpg"));
+	assert!(!changed("photo.JPG", "photo.jpg"));
+	assert!(!changed("photo", "photo.jpg"));
+	assert!(!changed(".bashrc", ".zshrc"));
+}
+
+#[test]
+fn test_write_atomic() {
+	let dir = std::env::temp_dir().join(format!("yazi-write-atomic-{}", std::process::id()));
+	std::fs::remove_dir_all(&dir).ok();
+	std::fs::create_dir_all(&dir).unwrap();
+
+	let p = dir.join("cwd");
+	write_atomic(&p, "/a/b").unwrap();
+	write_atomic(&p, "/c").unwrap();
+	assert_eq!(std::fs::read_to_string(&p).unwrap(), "/c");
+
+	// No temporary file is left behind, even when it fails
+	assert!(write_atomic(&dir.join("missing/cwd"), "/d").is_err());
+	assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+	// A symlink is written through, rather than replaced
+	#[cfg(unix)]
+	{
+		let link = dir.join("link");
+		std::os::unix::fs::symlink(&p, &link).unwrap();
+		write_atomic(&link, "/e").unwrap();
+		assert!(link.symlink_metadata().unwrap().is_symlink());
+		assert_eq!(std::fs::read_to_string(&p).unwrap(), "/e");
+	}
+
+	std::fs::remove_dir_all(&dir).unwrap();
+}
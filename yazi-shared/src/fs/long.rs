@@ -0,0 +1,99 @@
+//! The functions of [`tokio::fs`] that file operations go through, which pass
+//! the paths to the OS in their [`long_path`] form, so that those nested beyond
+//! `MAX_PATH` on Windows can be operated on like any others.
+
+use std::{fs::Metadata, io, path::{Path, PathBuf}};
+
+use tokio::fs;
+
+use super::{long_path, short_path};
+
+/// Canonicalizes `path`, with the result back in the normal form.
+#[inline]
+pub async fn canonicalize(path: impl AsRef<Path>) -> io::Result<PathBuf> {
+	fs::canonicalize(long_path(&path)).await.map(short_path)
+}
+
+#[inline]
+pub async fn create_dir(path: impl AsRef<Path>) -> io::Result<()> {
+	fs::create_dir(long_path(&path)).await
+}
+
+#[inline]
+pub async fn create_dir_all(path: impl AsRef<Path>) -> io::Result<()> {
+	fs::create_dir_all(long_path(&path)).await
+}
+
+#[inline]
+pub async fn create_file(path: impl AsRef<Path>) -> io::Result<fs::File> {
+	fs::File::create(long_path(&path)).await
+}
+
+#[inline]
+pub async fn hard_link(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> io::Result<()> {
+	fs::hard_link(long_path(&src), long_path(&dst)).await
+}
+
+#[inline]
+pub async fn metadata(path: impl AsRef<Path>) -> io::Result<Metadata> {
+	fs::metadata(long_path(&path)).await
+}
+
+#[inline]
+pub async fn read_dir(path: impl AsRef<Path>) -> io::Result<fs::ReadDir> {
+	fs::read_dir(long_path(&path)).await
+}
+
+#[inline]
+pub async fn read_link(path: impl AsRef<Path>) -> io::Result<PathBuf> {
+	fs::read_link(long_path(&path)).await
+}
+
+#[inline]
+pub async fn remove_dir_all(path: impl AsRef<Path>) -> io::Result<()> {
+	fs::remove_dir_all(long_path(&path)).await
+}
+
+#[inline]
+pub async fn remove_file(path: impl AsRef<Path>) -> io::Result<()> {
+	fs::remove_file(long_path(&path)).await
+}
+
+#[inline]
+pub async fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> {
+	fs::rename(long_path(&from), long_path(&to)).await
+}
+
+/// Creates a symlink at `dst` pointing to `src`, which is kept as-is since
+/// it's stored in the link, rather than operated on.
+#[cfg(unix)]
+#[inline]
+pub async fn symlink(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> io::Result<()> {
+	fs::symlink(src, long_path(&dst)).await
+}
+
+/// Creates a directory symlink at `dst` pointing to `src`, which is kept as-is
+/// since it's stored in the link, rather than operated on.
+#[cfg(windows)]
+#[inline]
+pub async fn symlink_dir(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> io::Result<()> {
+	fs::symlink_dir(src, long_path(&dst)).await
+}
+
+/// Creates a file symlink at `dst` pointing to `src`, which is kept as-is
+/// since it's stored in the link, rather than operated on.
+#[cfg(windows)]
+#[inline]
+pub async fn symlink_file(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> io::Result<()> {
+	fs::symlink_file(src, long_path(&dst)).await
+}
+
+#[inline]
+pub async fn symlink_metadata(path: impl AsRef<Path>) -> io::Result<Metadata> {
+	fs::symlink_metadata(long_path(&path)).await
+}
+
+#[inline]
+pub async fn write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+	fs::write(long_path(&path), contents).await
+}
@@ -1 +1,3 @@
-yazi_macro::mod_flat!(cha file fns loc op path url urn);
+yazi_macro::mod_pub!(long);
+
+yazi_macro::mod_flat!(cha file fns loc op path preserve url urn xattr);
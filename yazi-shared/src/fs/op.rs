@@ -9,7 +9,7 @@ pub static FILES_TICKET: AtomicU64 = AtomicU64::new(0);
 pub enum FilesOp {
 	Full(Url, Vec<File>, Cha),
 	Part(Url, Vec<File>, u64),
-	Done(Url, Cha, u64),
+	Done(Url, Cha, u64, Option<std::io::ErrorKind>),
 	Size(Url, HashMap<UrnBuf, u64>),
 	IOErr(Url, std::io::ErrorKind),
 
@@ -104,7 +104,7 @@ impl FilesOp {
 		match self {
 			Self::Full(_, files, cha) => Self::Full(n, files!(files), *cha),
 			Self::Part(_, files, ticket) => Self::Part(n, files!(files), *ticket),
-			Self::Done(_, cha, ticket) => Self::Done(n, *cha, *ticket),
+			Self::Done(_, cha, ticket, err) => Self::Done(n, *cha, *ticket, *err),
 			Self::Size(_, map) => Self::Size(n, map.iter().map(|(u, &s)| (u.clone(), s)).collect()),
 			Self::IOErr(_, err) => Self::IOErr(n, *err),
 
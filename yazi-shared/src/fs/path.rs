@@ -1,4 +1,4 @@
-use std::{borrow::Cow, env, ffi::OsString, future::Future, io, path::{Component, Path, PathBuf}};
+use std::{borrow::Cow, env, ffi::{OsStr, OsString}, future::Future, io, path::{Component, Path, PathBuf}};
 
 use tokio::fs;
 
@@ -35,6 +35,88 @@ fn _clean_path(path: &Path) -> PathBuf {
 	if out.is_empty() { PathBuf::from(".") } else { out.iter().collect() }
 }
 
+/// The extended-length form of `path` on Windows, i.e. prefixed with `\\?\`,
+/// for passing it to the OS when it's too long for the `MAX_PATH` limit.
+///
+/// Only absolute paths are converted, as the extended-length form isn't
+/// normalized by the OS. `path` is returned as-is on other platforms.
+#[inline]
+pub fn long_path<P: AsRef<Path> + ?Sized>(path: &P) -> Cow<'_, Path> { _long_path(path.as_ref()) }
+
+#[cfg(unix)]
+#[inline]
+fn _long_path(path: &Path) -> Cow<'_, Path> { Cow::Borrowed(path) }
+
+#[cfg(windows)]
+fn _long_path(path: &Path) -> Cow<'_, Path> {
+	use std::path::Prefix;
+
+	// `CreateDirectoryW` is limited to `MAX_PATH` minus the 8.3 file name
+	if path.as_os_str().len() < 248 {
+		return Cow::Borrowed(path);
+	}
+
+	let mut it = path.components();
+	let mut buf = match (it.next(), it.next()) {
+		(Some(Component::Prefix(p)), Some(Component::RootDir)) => match p.kind() {
+			Prefix::Disk(d) => PathBuf::from(format!(r"\\?\{}:\", d as char)),
+			Prefix::UNC(server, share) => {
+				let mut s = OsString::from(r"\\?\UNC\");
+				s.push(server);
+				s.push(r"\");
+				s.push(share);
+				s.push(r"\");
+				PathBuf::from(s)
+			}
+			_ => return Cow::Borrowed(path),
+		},
+		_ => return Cow::Borrowed(path),
+	};
+
+	for c in it {
+		match c {
+			Component::Normal(s) => buf.push(s),
+			Component::ParentDir => _ = buf.pop(),
+			_ => {}
+		}
+	}
+	Cow::Owned(buf)
+}
+
+/// The reverse of [`long_path`], for turning the paths reported by the OS
+/// back into their normal form for display.
+#[inline]
+pub fn short_path(path: PathBuf) -> PathBuf { _short_path(path) }
+
+#[cfg(unix)]
+#[inline]
+fn _short_path(path: PathBuf) -> PathBuf { path }
+
+#[cfg(windows)]
+fn _short_path(path: PathBuf) -> PathBuf {
+	use std::path::Prefix;
+
+	let mut it = path.components();
+	let mut buf = match it.next() {
+		Some(Component::Prefix(p)) => match p.kind() {
+			Prefix::VerbatimDisk(d) => PathBuf::from(format!(r"{}:\", d as char)),
+			Prefix::VerbatimUNC(server, share) => {
+				let mut s = OsString::from(r"\\");
+				s.push(server);
+				s.push(r"\");
+				s.push(share);
+				s.push(r"\");
+				PathBuf::from(s)
+			}
+			_ => return path,
+		},
+		_ => return path,
+	};
+
+	buf.extend(it.filter(|c| matches!(c, Component::Normal(_))));
+	buf
+}
+
 #[inline]
 pub fn expand_path(p: impl AsRef<Path>) -> PathBuf { _expand_path(p.as_ref()) }
 
@@ -125,6 +207,27 @@ async fn _unique_name(mut u: Url, append: bool) -> io::Result<Url> {
 	Ok(u)
 }
 
+/// The `s` to show, with the bytes that aren't valid UTF-8 escaped, see
+/// [`escape_bytes`](crate::escape_bytes).
+#[inline]
+pub fn escape_non_utf8(s: &OsStr) -> Cow<'_, str> { crate::escape_bytes(s.as_encoded_bytes()) }
+
+/// The paths in `b`, one per line like [`str::lines`], taken as raw bytes on
+/// Unix, so that names that aren't valid UTF-8 survive a text file.
+pub fn paths_from_lines(b: &[u8]) -> Vec<PathBuf> {
+	if b.is_empty() {
+		return vec![];
+	}
+
+	#[cfg(unix)]
+	let path = |l: &[u8]| PathBuf::from(<OsStr as std::os::unix::ffi::OsStrExt>::from_bytes(l));
+	#[cfg(windows)]
+	let path = |l: &[u8]| PathBuf::from(String::from_utf8_lossy(l).into_owned());
+
+	let b = b.strip_suffix(b"\n").unwrap_or(b);
+	b.split(|&c| c == b'\n').map(|l| path(l.strip_suffix(b"\r").unwrap_or(l))).collect()
+}
+
 // Parameters
 // * `path`: The absolute path(contains no `/./`) to get relative path.
 // * `root`: The absolute path(contains no `/./`) to be compared.
@@ -190,6 +293,27 @@ mod tests {
 		assert("/a/a/b", "/a/b/b", "../../a/b");
 	}
 
+	#[cfg(unix)]
+	#[test]
+	fn test_non_utf8() {
+		use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::PathBuf};
+
+		use super::{escape_non_utf8, paths_from_lines};
+
+		let name = OsStr::from_bytes(b"foo\xE9bar");
+		assert_eq!(escape_non_utf8(name), "foo\\xE9bar");
+		assert_eq!(escape_non_utf8(OsStr::from_bytes(b"\xFF\xFEcaf\xC3\xA9")), "\\xFF\\xFEcafé");
+		assert_eq!(escape_non_utf8(OsStr::new("café")), Cow::Borrowed("café"));
+
+		assert_eq!(paths_from_lines(b"a/foo\xE9bar\r\nb\n\nc\n"), [
+			PathBuf::from(OsStr::from_bytes(b"a/foo\xE9bar")),
+			PathBuf::from("b"),
+			PathBuf::new(),
+			PathBuf::from("c"),
+		]);
+		assert!(paths_from_lines(b"").is_empty());
+	}
+
 	#[cfg(windows)]
 	#[test]
 	fn test_path_relative_to() {
@@ -203,4 +327,24 @@ mod tests {
 		assert("C:\\a", "C:\\a\\b\\c", "..\\..\\");
 		assert("C:\\a\\a\\b", "C:\\a\\b\\b", "..\\..\\a\\b");
 	}
+
+	#[cfg(windows)]
+	#[test]
+	fn test_long_path() {
+		use super::{long_path, short_path};
+
+		let short = Path::new("C:\\a\\b");
+		assert_eq!(long_path(short), Cow::Borrowed(short));
+
+		let name = "x".repeat(200);
+		let p = format!("C:\\a\\{name}\\.\\{name}\\..\\b");
+		assert_eq!(long_path(&p), Cow::<Path>::Owned(format!("\\\\?\\C:\\a\\{name}\\b").into()));
+
+		let p = format!("\\\\server\\share\\{name}\\{name}");
+		assert_eq!(
+			long_path(&p),
+			Cow::<Path>::Owned(format!("\\\\?\\UNC\\server\\share\\{name}\\{name}").into())
+		);
+		assert_eq!(short_path(long_path(&p).into_owned()), Path::new(&p));
+	}
 }
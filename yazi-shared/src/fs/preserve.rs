@@ -0,0 +1,221 @@
+use std::{collections::VecDeque, io, path::Path};
+
+use super::{Cha, long, long_path};
+
+/// What a copy keeps of the source besides its content.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Preserve {
+	/// Only the permissions, masked by the umask as a newly created file would
+	/// be, with fresh timestamps.
+	None,
+	/// The permissions, and the access and modification times.
+	#[default]
+	Attrs,
+	/// The ownership as well, which only works as root.
+	Owner,
+}
+
+impl Preserve {
+	/// Applies the attributes of `cha`, the source, to the copy at `path`.
+	///
+	/// The ownership goes first, as changing it clears the setuid bits, and the
+	/// times before the permissions, which might forbid writing them.
+	///
+	/// This is a blocking call.
+	pub fn apply(self, path: &Path, cha: Cha) -> io::Result<()> {
+		#[cfg(unix)]
+		if self == Self::Owner {
+			std::os::unix::fs::chown(path, Some(cha.uid as _), Some(cha.gid as _))?;
+		}
+		if self != Self::None {
+			Self::set_times(path, cha)?;
+		} else if cfg!(windows) {
+			// Where copying keeps the modification time already
+			let now = Some(std::time::SystemTime::now());
+			Self::set_times(path, Cha { atime: now, btime: None, mtime: now, ..cha })?;
+		}
+
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::PermissionsExt;
+			let mode = match self {
+				Self::None => cha.perm & 0o777 & !umask(),
+				Self::Attrs | Self::Owner => cha.perm & 0o7777,
+			};
+			std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode as _))?;
+		}
+		Ok(())
+	}
+
+	/// Applies the attributes of the directories under `from` to those copied
+	/// to `to`, once everything in them has been, so that their modification
+	/// times aren't bumped afterwards. Symlinks are skipped.
+	pub async fn apply_tree(self, from: &Path, to: &Path) {
+		if self == Self::None {
+			return;
+		}
+
+		let mut dirs = VecDeque::from([to.to_path_buf()]);
+		while let Some(dir) = dirs.pop_front() {
+			let Ok(rel) = dir.strip_prefix(to) else { continue };
+			let Ok(meta) = long::metadata(&from.join(rel)).await else { continue };
+			if !meta.is_dir() {
+				continue;
+			}
+
+			let Ok(mut it) = long::read_dir(&dir).await else { continue };
+			while let Ok(Some(entry)) = it.next_entry().await {
+				if entry.file_type().await.is_ok_and(|t| t.is_dir()) {
+					dirs.push_back(entry.path());
+				}
+			}
+
+			let (dir, cha) = (long_path(&dir).into_owned(), Cha::from(meta));
+			tokio::task::spawn_blocking(move || self.apply(&dir, cha)).await.ok();
+		}
+	}
+
+	fn set_times(path: &Path, cha: Cha) -> io::Result<()> {
+		let mut ft = std::fs::FileTimes::new();
+		cha.atime.map(|t| ft = ft.set_accessed(t));
+		cha.mtime.map(|t| ft = ft.set_modified(t));
+		#[cfg(target_os = "macos")]
+		{
+			use std::os::macos::fs::FileTimesExt;
+			cha.btime.map(|t| ft = ft.set_created(t));
+		}
+		#[cfg(windows)]
+		{
+			use std::os::windows::fs::FileTimesExt;
+			cha.btime.map(|t| ft = ft.set_created(t));
+		}
+
+		// Only the owner is needed to set the times explicitly, not the write mode
+		#[cfg(unix)]
+		return std::fs::File::open(path)?.set_times(ft);
+
+		// A read-only file can't be opened for writing, nor a directory without
+		// `FILE_FLAG_BACKUP_SEMANTICS`, so the attribute is lifted for a moment
+		#[cfg(windows)]
+		{
+			use std::os::windows::fs::OpenOptionsExt;
+
+			let mut perm = std::fs::metadata(path)?.permissions();
+			let readonly = perm.readonly();
+			if readonly {
+				perm.set_readonly(false);
+				std::fs::set_permissions(path, perm.clone())?;
+			}
+
+			let result = std::fs::File::options()
+				.write(true)
+				.custom_flags(0x02000000)
+				.open(path)
+				.and_then(|f| f.set_times(ft));
+
+			if readonly {
+				perm.set_readonly(true);
+				std::fs::set_permissions(path, perm)?;
+			}
+			result
+		}
+	}
+}
+
+/// The umask of the process, which is read once, as there's no way to do so
+/// without setting it.
+#[cfg(unix)]
+pub fn umask() -> libc::mode_t {
+	static UMASK: std::sync::OnceLock<libc::mode_t> = std::sync::OnceLock::new();
+	*UMASK.get_or_init(|| unsafe {
+		let mask = libc::umask(0o022);
+		libc::umask(mask);
+		mask
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{path::PathBuf, time::{Duration, SystemTime}};
+
+	use tokio::fs;
+
+	use super::*;
+	use crate::fs::copy_with_progress;
+
+	async fn copy(from: &Path, to: &Path, preserve: Preserve) -> Cha {
+		let cha = Cha::from(fs::symlink_metadata(from).await.unwrap());
+		let mut it = copy_with_progress(from, to, cha, preserve);
+		while let Some(n) = it.recv().await {
+			if n.unwrap() == 0 {
+				break;
+			}
+		}
+		Cha::from(fs::symlink_metadata(to).await.unwrap())
+	}
+
+	async fn setup(name: &str) -> (PathBuf, SystemTime) {
+		let root = std::env::temp_dir().join(format!("yazi-preserve-{name}"));
+		fs::remove_dir_all(&root).await.ok();
+		fs::create_dir_all(&root).await.unwrap();
+		fs::write(root.join("src"), "yazi").await.unwrap();
+
+		let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+		std::fs::File::options()
+			.write(true)
+			.open(root.join("src"))
+			.unwrap()
+			.set_times(std::fs::FileTimes::new().set_modified(mtime))
+			.unwrap();
+		(root, mtime)
+	}
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_mode_and_mtime() {
+		use std::os::unix::fs::PermissionsExt;
+
+		let (root, mtime) = setup("unix").await;
+		fs::set_permissions(root.join("src"), std::fs::Permissions::from_mode(0o755)).await.unwrap();
+
+		let cha = copy(&root.join("src"), &root.join("kept"), Preserve::Attrs).await;
+		assert_eq!(cha.perm & 0o7777, 0o755);
+		assert_eq!(cha.mtime, Some(mtime));
+
+		let cha = copy(&root.join("src"), &root.join("fresh"), Preserve::None).await;
+		assert_eq!(cha.perm & 0o7777, 0o755 & !umask());
+		assert_ne!(cha.mtime, Some(mtime));
+
+		// Read-only ones get their times too
+		fs::set_permissions(root.join("src"), std::fs::Permissions::from_mode(0o444)).await.unwrap();
+		let cha = copy(&root.join("src"), &root.join("readonly"), Preserve::Attrs).await;
+		assert_eq!(cha.perm & 0o7777, 0o444);
+		assert_eq!(cha.mtime, Some(mtime));
+
+		fs::remove_dir_all(&root).await.unwrap();
+	}
+
+	#[cfg(windows)]
+	#[tokio::test]
+	async fn test_readonly() {
+		let (root, mtime) = setup("windows").await;
+		let mut perm = fs::metadata(root.join("src")).await.unwrap().permissions();
+		perm.set_readonly(true);
+		fs::set_permissions(root.join("src"), perm).await.unwrap();
+
+		let cha = copy(&root.join("src"), &root.join("kept"), Preserve::Attrs).await;
+		assert!(fs::metadata(root.join("kept")).await.unwrap().permissions().readonly());
+		assert_eq!(cha.mtime, Some(mtime));
+
+		let cha = copy(&root.join("src"), &root.join("fresh"), Preserve::None).await;
+		assert!(fs::metadata(root.join("fresh")).await.unwrap().permissions().readonly());
+		assert_ne!(cha.mtime, Some(mtime));
+
+		for name in ["src", "kept", "fresh"] {
+			let mut perm = fs::metadata(root.join(name)).await.unwrap().permissions();
+			perm.set_readonly(false);
+			fs::set_permissions(root.join(name), perm).await.unwrap();
+		}
+		fs::remove_dir_all(&root).await.unwrap();
+	}
+}
@@ -3,7 +3,7 @@ use std::{ffi::OsStr, fmt::{Debug, Display, Formatter}, ops::Deref, path::{Path,
 use percent_encoding::{AsciiSet, CONTROLS, percent_decode_str, percent_encode};
 use serde::{Deserialize, Serialize};
 
-use super::{Loc, UrnBuf};
+use super::{Loc, UrnBuf, escape_non_utf8};
 
 const ENCODE_SET: &AsciiSet = &CONTROLS.add(b'#');
 
@@ -111,7 +111,7 @@ impl AsRef<OsStr> for Url {
 impl Display for Url {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		if matches!(self.scheme, UrlScheme::Regular | UrlScheme::SearchItem) {
-			return f.write_str(&self.loc.to_string_lossy());
+			return f.write_str(&escape_non_utf8(self.loc.as_os_str()));
 		}
 
 		let scheme = match self.scheme {
@@ -147,7 +147,10 @@ impl Url {
 				let loc = Loc::from(self.loc.base(), self.loc.join(path));
 				Self::from(loc).into_search_item()
 			}
-			UrlScheme::Archive => Self::from(self.loc.join(path)).into_archive(),
+			UrlScheme::Archive => {
+				let depth = self.archive_depth() + path.as_ref().components().count();
+				Self::from(self.loc.join(path)).into_archive_at(depth)
+			}
 		}
 	}
 
@@ -163,7 +166,10 @@ impl Url {
 					Self::from(p).into_search_item()
 				}
 			}
-			UrlScheme::Archive => Self::from(p),
+			UrlScheme::Archive => match self.archive_depth() {
+				0 => Self::from(p),
+				n => Self::from(p).into_archive_at(n - 1),
+			},
 		})
 	}
 
@@ -220,21 +226,51 @@ impl Url {
 		self
 	}
 
+	// --- Archive
+	//
+	// The path of an archive URL is the archive file followed by the path of the
+	// entry inside it, e.g. `archive:///a.zip/b/c#2`, where the fragment is the
+	// number of components after the archive file, and is omitted for the root.
 	#[inline]
 	pub fn is_archive(&self) -> bool { self.scheme == UrlScheme::Archive }
 
+	/// The root directory of the archive file at this URL.
 	#[inline]
 	pub fn to_archive(&self) -> Self {
 		Self { loc: self.loc.clone(), scheme: UrlScheme::Archive, frag: String::new() }
 	}
 
 	#[inline]
-	pub fn into_archive(mut self) -> Self {
+	pub fn into_archive(self) -> Self { self.into_archive_at(0) }
+
+	#[inline]
+	fn into_archive_at(mut self, depth: usize) -> Self {
 		self.scheme = UrlScheme::Archive;
-		self.frag = String::new();
+		self.frag = if depth == 0 { String::new() } else { depth.to_string() };
 		self
 	}
 
+	#[inline]
+	pub fn archive_depth(&self) -> usize {
+		if self.is_archive() { self.frag.parse().unwrap_or(0) } else { 0 }
+	}
+
+	/// Splits an archive URL into the path of the archive file, and the path of
+	/// the entry inside it.
+	pub fn archive_split(&self) -> Option<(&Path, &Path)> {
+		if !self.is_archive() {
+			return None;
+		}
+
+		let depth = self.archive_depth();
+		let archive = self.loc.ancestors().nth(depth)?;
+		let mut it = self.loc.components();
+		for _ in 0..archive.components().count() {
+			it.next();
+		}
+		Some((archive, it.as_path()))
+	}
+
 	// --- Loc
 	#[inline]
 	pub fn set_loc(&mut self, loc: Loc) { self.loc = loc; }
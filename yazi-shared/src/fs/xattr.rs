@@ -0,0 +1,99 @@
+use std::{ffi::OsString, io, path::Path};
+
+/// The extended attribute macOS sets on downloaded files, which makes
+/// Gatekeeper check them before they can be run.
+pub const XATTR_QUARANTINE: &str = "com.apple.quarantine";
+
+/// The extended attribute Finder stores the tags of a file in.
+pub const XATTR_TAGS: &str = "com.apple.metadata:_kMDItemUserTags";
+
+/// The extended attributes of `path` with their values, without following
+/// symlinks, or none if the platform or the filesystem doesn't support them.
+pub async fn xattrs(path: impl AsRef<Path>) -> io::Result<Vec<(OsString, Vec<u8>)>> {
+	#[cfg(unix)]
+	{
+		let path = path.as_ref().to_owned();
+		tokio::task::spawn_blocking(move || {
+			let names = match xattr::list(&path) {
+				Ok(it) => it,
+				Err(e) if unsupported(&e) => return Ok(vec![]),
+				Err(e) => return Err(e),
+			};
+
+			// The ones removed in the meantime, or not readable, are skipped
+			Ok(
+				names
+					.filter_map(|n| Some((xattr::get(&path, &n).ok()??, n)))
+					.map(|(v, n)| (n, v))
+					.collect(),
+			)
+		})
+		.await?
+	}
+
+	#[cfg(windows)]
+	{
+		_ = path;
+		Ok(vec![])
+	}
+}
+
+pub async fn remove_xattr(path: impl AsRef<Path>, name: &str) -> io::Result<()> {
+	#[cfg(unix)]
+	{
+		let (path, name) = (path.as_ref().to_owned(), name.to_owned());
+		tokio::task::spawn_blocking(move || xattr::remove(path, name)).await?
+	}
+
+	#[cfg(windows)]
+	{
+		_ = (path, name);
+		Err(io::Error::new(
+			io::ErrorKind::Unsupported,
+			"Extended attributes aren't supported on Windows",
+		))
+	}
+}
+
+/// Decodes the value of [`XATTR_TAGS`], a binary property list of the tag
+/// names, each followed by the index of its color if it has one.
+pub fn finder_tags(value: &[u8]) -> Vec<(String, Option<&'static str>)> {
+	const COLORS: [&str; 8] = ["none", "gray", "green", "purple", "blue", "yellow", "red", "orange"];
+
+	let Ok(tags) = plist::from_bytes::<Vec<String>>(value) else { return vec![] };
+	tags
+		.into_iter()
+		.map(|t| match t.rsplit_once('\n') {
+			Some((name, color)) => {
+				let color = color.parse::<usize>().ok().and_then(|i| COLORS.get(i).copied());
+				(name.to_owned(), color.filter(|&c| c != "none"))
+			}
+			None => (t, None),
+		})
+		.collect()
+}
+
+#[cfg(unix)]
+fn unsupported(e: &io::Error) -> bool {
+	e.kind() == io::ErrorKind::Unsupported || e.raw_os_error() == Some(libc::ENOTSUP)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::finder_tags;
+
+	#[test]
+	fn test_finder_tags() {
+		let mut buf = vec![];
+		plist::to_writer_binary(&mut buf, &["Important\n6", "Work\n4", "Untagged\n0", "Plain"])
+			.unwrap();
+
+		assert_eq!(finder_tags(&buf), [
+			("Important".to_owned(), Some("red")),
+			("Work".to_owned(), Some("blue")),
+			("Untagged".to_owned(), None),
+			("Plain".to_owned(), None),
+		]);
+		assert!(finder_tags(b"not a plist").is_empty());
+	}
+}
@@ -8,6 +8,7 @@ pub enum Layer {
 	App,
 	Manager,
 	Tasks,
+	Spot,
 	Pick,
 	Input,
 	Confirm,
@@ -22,6 +23,7 @@ impl Display for Layer {
 			Self::App => "app",
 			Self::Manager => "manager",
 			Self::Tasks => "tasks",
+			Self::Spot => "spot",
 			Self::Pick => "pick",
 			Self::Input => "input",
 			Self::Confirm => "confirm",
@@ -40,6 +42,7 @@ impl FromStr for Layer {
 			"app" => Self::App,
 			"manager" => Self::Manager,
 			"tasks" => Self::Tasks,
+			"spot" => Self::Spot,
 			"pick" => Self::Pick,
 			"input" => Self::Input,
 			"confirm" => Self::Confirm,
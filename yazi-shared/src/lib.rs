@@ -3,8 +3,8 @@
 yazi_macro::mod_pub!(errors event fs shell theme translit);
 
 yazi_macro::mod_flat!(
-	chars condition debounce env layer natsort number os rand ro_cell terminal throttle
-	time xdg
+	chars collate condition debounce env layer natsort number os rand ro_cell terminal
+	throttle time xdg
 );
 
 pub fn init() {
@@ -41,6 +41,11 @@ impl<T> Throttle<T> {
 		self.buf.lock().push(data);
 	}
 
+	/// Takes the data that hasn't been flushed yet, e.g. once it's done, when
+	/// the total isn't known beforehand.
+	#[inline]
+	pub fn take(&self) -> Vec<T> { mem::take(&mut *self.buf.lock()) }
+
 	#[inline]
 	fn flush<F>(&self, data: T, f: F)
 	where
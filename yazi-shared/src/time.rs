@@ -1,6 +1,83 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::{sync::atomic::{AtomicU64, Ordering}, time::{SystemTime, UNIX_EPOCH}};
+
+static CLOCK: AtomicU64 = AtomicU64::new(0);
 
 #[inline]
 pub fn timestamp_us() -> u64 {
 	SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards").as_micros() as _
 }
+
+/// The seconds since the Unix epoch as of the last [`tick_clock`], which the
+/// relative times are measured from, so they only change once it ticks rather
+/// than on every render.
+pub fn clock() -> u64 {
+	match CLOCK.load(Ordering::Relaxed) {
+		0 => tick_clock(),
+		n => n,
+	}
+}
+
+pub fn tick_clock() -> u64 {
+	let now = timestamp_us() / 1_000_000;
+	CLOCK.store(now, Ordering::Relaxed);
+	now
+}
+
+/// How long ago something was, given the seconds `elapsed` since, e.g. `3m ago`
+/// or `yesterday`, the same as `ya.readable_age` in Lua.
+pub fn readable_age(elapsed: u64) -> String {
+	const MINUTE: u64 = 60;
+	const HOUR: u64 = 60 * MINUTE;
+	const DAY: u64 = 24 * HOUR;
+	const WEEK: u64 = 7 * DAY;
+	const MONTH: u64 = 30 * DAY;
+	const YEAR: u64 = 365 * DAY;
+
+	if elapsed < MINUTE {
+		"just now".to_owned()
+	} else if elapsed < HOUR {
+		format!("{}m ago", elapsed / MINUTE)
+	} else if elapsed < DAY {
+		format!("{}h ago", elapsed / HOUR)
+	} else if elapsed < 2 * DAY {
+		"yesterday".to_owned()
+	} else if elapsed < 2 * WEEK {
+		format!("{}d ago", elapsed / DAY)
+	} else if elapsed < 2 * MONTH {
+		format!("{}w ago", elapsed / WEEK)
+	} else if elapsed < YEAR {
+		format!("{}mo ago", elapsed / MONTH)
+	} else {
+		format!("{}y ago", elapsed / YEAR)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_readable_age() {
+		let cases = [
+			(0, "just now"),
+			(59, "just now"),
+			(60, "1m ago"),
+			(3599, "59m ago"),
+			(3600, "1h ago"),
+			(86399, "23h ago"),
+			(86400, "yesterday"),
+			(172799, "yesterday"),
+			(172800, "2d ago"),
+			(13 * 86400, "13d ago"),
+			(14 * 86400, "2w ago"),
+			(59 * 86400, "8w ago"),
+			(60 * 86400, "2mo ago"),
+			(364 * 86400, "12mo ago"),
+			(365 * 86400, "1y ago"),
+			(800 * 86400, "2y ago"),
+		];
+		for (elapsed, expected) in cases {
+			assert_eq!(readable_age(elapsed), expected, "{elapsed}");
+		}
+	}
+}